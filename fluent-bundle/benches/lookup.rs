@@ -0,0 +1,70 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use unic_langid::langid;
+
+/// The number of messages of the catalog.
+const MESSAGES: usize = 5000;
+
+/// Returns a catalog of `MESSAGES` messages referencing terms, other
+/// messages and variables, and the identifiers of the messages.
+fn get_catalog() -> (String, Vec<String>) {
+    let mut source = String::new();
+    for i in 0..MESSAGES / 50 {
+        source.push_str(&format!("-brand-{i} = Brand {i}\n"));
+    }
+    let mut ids = vec![];
+    for i in 0..MESSAGES {
+        let id = format!("settings-panel-message-{i}");
+        source.push_str(&format!(
+            "{id} = {{ -brand-{} }} has {{ $count }} items for {{ $user }}\n    .title = {{ {} }}\n",
+            i % (MESSAGES / 50),
+            ids.get(i / 2).unwrap_or(&id),
+        ));
+        ids.push(id);
+    }
+    (source, ids)
+}
+
+fn lookup_bench(c: &mut Criterion) {
+    let (source, ids) = get_catalog();
+    let res = FluentResource::try_new(source).expect("Couldn't parse an FTL source");
+    let mut bundle = FluentBundle::new(vec![langid!("en")]);
+    bundle
+        .add_resource(res)
+        .expect("Couldn't add FluentResource to the FluentBundle");
+    let mut args = FluentArgs::new();
+    args.set("count", 5);
+    args.set("user", "John");
+
+    let mut group = c.benchmark_group("lookup");
+    group.bench_function("get_message", |b| {
+        b.iter(|| {
+            for id in &ids {
+                assert!(bundle.get_message(id).is_some());
+            }
+        })
+    });
+    group.bench_function("resolve", |b| {
+        b.iter(|| {
+            let mut s = String::new();
+            for id in &ids {
+                let msg = bundle.get_message(id).expect("Message found");
+                let mut errors = vec![];
+                let _ =
+                    bundle.write_pattern(&mut s, msg.value().unwrap(), Some(&args), &mut errors);
+                for attr in msg.attributes() {
+                    let _ = bundle.write_pattern(&mut s, attr.value(), Some(&args), &mut errors);
+                }
+                assert!(errors.is_empty(), "Resolver errors: {:#?}", errors);
+                s.clear();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookup_bench);
+criterion_main!(benches);
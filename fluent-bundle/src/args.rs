@@ -1,7 +1,10 @@
 use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
 use std::iter::FromIterator;
+use std::str::FromStr;
 
-use crate::types::FluentValue;
+use crate::types::{FluentDateTime, FluentNumber, FluentType, FluentValue};
 
 /// Fluent messages can use arguments in order to programmatically add values to a
 /// translated string. For instance, in a localized application you may wish to display
@@ -52,7 +55,7 @@ use crate::types::FluentValue;
 ///     "Hello, John. You have 5 messages."
 /// );
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct FluentArgs<'args>(Vec<(Cow<'args, str>, FluentValue<'args>)>);
 
 impl<'args> FluentArgs<'args> {
@@ -79,6 +82,211 @@ impl<'args> FluentArgs<'args> {
         }
     }
 
+    /// Gets the value at `key` like [`FluentArgs::get`], without converting
+    /// the key.
+    pub(crate) fn get_by_str(&self, key: &str) -> Option<&FluentValue<'args>> {
+        let idx = self.0.binary_search_by(|(k, _)| (**k).cmp(key)).ok()?;
+        Some(&self.0[idx].1)
+    }
+
+    /// Gets the string at the `key`, if it exists and is a string.
+    ///
+    /// This and the other typed getters help functions read their named
+    /// options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentArgs;
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("ellipsis", "...");
+    /// args.set("max", 10);
+    ///
+    /// assert_eq!(args.get_str("ellipsis"), Some("..."));
+    /// assert_eq!(args.get_str("max"), None);
+    /// assert_eq!(args.get_str("missing").unwrap_or("…"), "…");
+    /// ```
+    pub fn get_str<K>(&self, key: K) -> Option<&str>
+    where
+        K: Into<Cow<'args, str>>,
+    {
+        match self.get(key)? {
+            FluentValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Gets the number at the `key`, if it exists and is a number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentArgs;
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("width", 10);
+    /// args.set("align", "right");
+    ///
+    /// assert_eq!(args.get_number("width"), Some(10.0));
+    /// assert_eq!(args.get_number("align"), None);
+    /// ```
+    pub fn get_number<K>(&self, key: K) -> Option<f64>
+    where
+        K: Into<Cow<'args, str>>,
+    {
+        match self.get(key)? {
+            FluentValue::Number(n) => Some(n.value),
+            _ => None,
+        }
+    }
+
+    /// Parses the string at the `key` with [`FromStr`], such as an option
+    /// whose values are the variants of an enum.
+    ///
+    /// Returns `None` if the `key` does not exist or is not a string, and the
+    /// parsing error if the string is not a valid value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentArgs;
+    /// use std::str::FromStr;
+    ///
+    /// #[derive(Debug, Default, PartialEq)]
+    /// enum Style {
+    ///     #[default]
+    ///     Long,
+    ///     Short,
+    /// }
+    ///
+    /// impl FromStr for Style {
+    ///     type Err = String;
+    ///
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         match s {
+    ///             "long" => Ok(Self::Long),
+    ///             "short" => Ok(Self::Short),
+    ///             _ => Err(format!("Unknown style: {}", s)),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("style", "short");
+    /// args.set("timeStyle", "tiny");
+    ///
+    /// assert_eq!(args.get_enum::<Style, _>("style"), Some(Ok(Style::Short)));
+    /// assert!(matches!(args.get_enum::<Style, _>("timeStyle"), Some(Err(_))));
+    /// let style: Style = args.get_enum("dateStyle").and_then(Result::ok).unwrap_or_default();
+    /// assert_eq!(style, Style::Long);
+    /// ```
+    pub fn get_enum<T, K>(&self, key: K) -> Option<Result<T, T::Err>>
+    where
+        T: FromStr,
+        K: Into<Cow<'args, str>>,
+    {
+        self.get_str(key).map(str::parse)
+    }
+
+    /// Gets the value at the `key` converted to `T`, such as a [`String`], an
+    /// `f64` or an `i64`, see [`FromFluentValue`].
+    ///
+    /// Unlike the other typed getters, it returns an [`ArgumentError`] which
+    /// describes whether the argument is missing or has another type, such as
+    /// to report it from a custom function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{ArgumentError, FluentArgs};
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("name", "John");
+    /// args.set("emailCount", 5);
+    ///
+    /// assert_eq!(args.get_as::<String, _>("name"), Ok("John".to_string()));
+    /// assert_eq!(args.get_as::<i64, _>("emailCount"), Ok(5));
+    ///
+    /// let err = args.get_as::<f64, _>("name").unwrap_err();
+    /// assert_eq!(err.to_string(), "Argument `name` is a string, expected f64");
+    /// assert_eq!(
+    ///     args.get_as::<String, _>("title"),
+    ///     Err(ArgumentError::Missing("title".to_string()))
+    /// );
+    /// ```
+    pub fn get_as<T, K>(&self, key: K) -> Result<T, ArgumentError>
+    where
+        T: FromFluentValue,
+        K: Into<Cow<'args, str>>,
+    {
+        let key = key.into();
+        let value = self
+            .get(key.clone())
+            .ok_or_else(|| ArgumentError::Missing(key.to_string()))?;
+        T::from_fluent_value(value).ok_or_else(|| ArgumentError::Mismatch {
+            key: key.to_string(),
+            expected: std::any::type_name::<T>(),
+            found: value_kind(value),
+        })
+    }
+
+    /// Gets the value of the custom type `T` at the `key`, such as a value
+    /// returned by a custom function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// use fluent_bundle::types::FluentType;
+    /// use fluent_bundle::{FluentArgs, FluentValue};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Temperature(f64);
+    ///
+    /// impl FluentType for Temperature {
+    ///     fn duplicate(&self) -> Box<dyn FluentType + Send> {
+    ///         Box::new(self.clone())
+    ///     }
+    ///     fn as_string(&self, _: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+    ///         format!("{}°", self.0).into()
+    ///     }
+    ///     fn as_string_threadsafe(
+    ///         &self,
+    ///         _: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ///     ) -> Cow<'static, str> {
+    ///         format!("{}°", self.0).into()
+    ///     }
+    /// }
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("outside", FluentValue::Custom(Box::new(Temperature(21.5))));
+    /// args.set("inside", 20);
+    ///
+    /// assert_eq!(args.get_custom::<Temperature, _>("outside"), Ok(&Temperature(21.5)));
+    /// assert!(args.get_custom::<Temperature, _>("inside").is_err());
+    /// ```
+    pub fn get_custom<T, K>(&self, key: K) -> Result<&T, ArgumentError>
+    where
+        T: FluentType,
+        K: Into<Cow<'args, str>>,
+    {
+        let key = key.into();
+        let value = self
+            .get(key.clone())
+            .ok_or_else(|| ArgumentError::Missing(key.to_string()))?;
+        let custom = match value {
+            FluentValue::Custom(custom) => custom.as_any().downcast_ref::<T>(),
+            _ => None,
+        };
+        custom.ok_or_else(|| ArgumentError::Mismatch {
+            key: key.to_string(),
+            expected: std::any::type_name::<T>(),
+            found: value_kind(value),
+        })
+    }
+
     /// Sets the key value pair.
     pub fn set<K, V>(&mut self, key: K, value: V)
     where
@@ -113,12 +321,43 @@ where
         } else {
             FluentArgs::new()
         };
+        args.extend(iter);
+        args
+    }
+}
 
+/// Sets each key value pair, replacing the values of existing keys, such as
+/// to add query parameters or the entries of a configuration map to a set of
+/// default arguments.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fluent_bundle::FluentArgs;
+///
+/// let mut args = FluentArgs::new();
+/// args.set("theme", "light");
+///
+/// let query = HashMap::from([("theme", "dark"), ("user", "John")]);
+/// args.extend(query);
+///
+/// assert_eq!(args.get_str("theme"), Some("dark"));
+/// assert_eq!(args.get_str("user"), Some("John"));
+/// ```
+impl<'args, K, V> Extend<(K, V)> for FluentArgs<'args>
+where
+    K: Into<Cow<'args, str>>,
+    V: Into<FluentValue<'args>>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
         for (k, v) in iter {
-            args.set(k, v);
+            self.set(k, v);
         }
-
-        args
     }
 }
 
@@ -131,6 +370,191 @@ impl<'args> IntoIterator for FluentArgs<'args> {
     }
 }
 
+/// An error returned by [`FluentArgs::get_as`] and [`FluentArgs::get_custom`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgumentError {
+    /// There is no argument with this name.
+    Missing(String),
+    /// The argument has a value which does not convert to the expected type.
+    Mismatch {
+        key: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(key) => write!(f, "Argument `{}` is missing", key),
+            Self::Mismatch {
+                key,
+                expected,
+                found,
+            } => write!(f, "Argument `{}` is {}, expected {}", key, found, expected),
+        }
+    }
+}
+
+impl Error for ArgumentError {}
+
+/// Describes the kind of a value in an [`ArgumentError`].
+fn value_kind(value: &FluentValue) -> &'static str {
+    match value {
+        FluentValue::String(_) => "a string",
+        FluentValue::Number(_) => "a number",
+        FluentValue::DateTime(_) => "a date and time",
+        FluentValue::List(_) => "a list",
+        FluentValue::Custom(_) => "a custom value",
+        FluentValue::None => "missing a value",
+        FluentValue::Error => "an error",
+    }
+}
+
+/// A set of options read from the named arguments of a function, such as the
+/// `style` and `currency` options of `NUMBER()`.
+///
+/// With the `macros` feature, `#[derive(FromFluentArgs)]` implements this
+/// trait and `From<&FluentArgs>` for a struct with named fields. Each field
+/// is read from the option named after it in camelCase, such as `dateStyle`
+/// for a `date_style` field, and converted with [`FromFluentValue`]. Options
+/// which are missing or have a value of the wrong type leave the field
+/// unchanged. The `#[fluent(rename = "...")]` attribute sets the name of the
+/// option of a field, and `#[fluent(skip)]` excludes a field.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "macros")]
+/// # {
+/// use fluent_bundle::{FluentArgs, FromFluentArgs, FromFluentValue};
+///
+/// #[derive(Debug, Default, PartialEq, FromFluentValue)]
+/// enum Style {
+///     #[default]
+///     Long,
+///     Short,
+///     #[fluent(rename = "2-digit")]
+///     TwoDigit,
+/// }
+///
+/// #[derive(Debug, Default, PartialEq, FromFluentArgs)]
+/// struct DateTimeOptions {
+///     date_style: Option<Style>,
+///     time_style: Option<Style>,
+///     #[fluent(rename = "hour12")]
+///     twelve_hours: bool,
+/// }
+///
+/// let mut args = FluentArgs::new();
+/// args.set("dateStyle", "short");
+/// args.set("hour12", "true");
+///
+/// assert_eq!(
+///     DateTimeOptions::from(&args),
+///     DateTimeOptions {
+///         date_style: Some(Style::Short),
+///         time_style: None,
+///         twelve_hours: true,
+///     }
+/// );
+/// # }
+/// ```
+pub trait FromFluentArgs: Default {
+    /// Sets the options given in `args`, keeping the others.
+    fn merge(&mut self, args: &FluentArgs);
+
+    /// Returns the default options, with the ones given in `args` set.
+    fn from_args(args: &FluentArgs) -> Self {
+        let mut options = Self::default();
+        options.merge(args);
+        options
+    }
+}
+
+/// A type which can be read from the value of a named argument, see
+/// [`FromFluentArgs`].
+///
+/// Strings and numbers are read from string and number values, and `bool`
+/// from the strings `"true"` and `"false"`, as the `useGrouping` option of
+/// `NUMBER()`. `Vec<T>` is read from list values whose items are all valid.
+/// With the `macros` feature, `#[derive(FromFluentValue)]`
+/// implements this trait for an enum of unit variants, read from strings
+/// which are the names of the variants in camelCase, or the names set by
+/// `#[fluent(rename = "...")]`.
+pub trait FromFluentValue: Sized {
+    /// Converts `value`, or returns `None` if it is not a valid value.
+    fn from_fluent_value(value: &FluentValue) -> Option<Self>;
+}
+
+impl<T: FromFluentValue> FromFluentValue for Option<T> {
+    fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+        T::from_fluent_value(value).map(Some)
+    }
+}
+
+impl FromFluentValue for String {
+    fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+        match value {
+            FluentValue::String(s) => Some(s.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl FromFluentValue for FluentNumber {
+    fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+        match value {
+            FluentValue::Number(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromFluentValue for FluentDateTime {
+    fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+        match value {
+            FluentValue::DateTime(d) => Some(d.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromFluentValue> FromFluentValue for Vec<T> {
+    fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+        match value {
+            FluentValue::List(items) => items.iter().map(T::from_fluent_value).collect(),
+            _ => None,
+        }
+    }
+}
+
+impl FromFluentValue for bool {
+    fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+        match value {
+            FluentValue::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! from_fluent_value_num {
+    ($($num:ty)+) => {
+        $(
+            impl FromFluentValue for $num {
+                fn from_fluent_value(value: &FluentValue) -> Option<Self> {
+                    match value {
+                        FluentValue::Number(n) => Some(n.into()),
+                        _ => None,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+from_fluent_value_num!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +581,50 @@ mod tests {
         );
         assert_eq!(args.get("emailCount"), Some(&FluentValue::try_number("7")));
     }
+
+    #[test]
+    fn extend_arguments() {
+        let mut args: FluentArgs = vec![("name", "John"), ("title", "Mr")]
+            .into_iter()
+            .collect();
+        args.extend([("name", Some("Jane")), ("folder", None)]);
+
+        assert_eq!(args.0.len(), 3);
+        assert_eq!(args.get_str("name"), Some("Jane"));
+        assert_eq!(args.get_str("title"), Some("Mr"));
+        assert!(matches!(args.get("folder"), Some(FluentValue::None)));
+    }
+    #[test]
+    fn typed_arguments() {
+        let mut args = FluentArgs::new();
+        args.set("names", vec!["Anna", "Bob"]);
+        args.set("count", 2.5);
+        args.set("date", FluentDateTime::from_epoch(0));
+
+        assert_eq!(
+            args.get_as::<Vec<String>, _>("names"),
+            Ok(vec!["Anna".to_string(), "Bob".to_string()])
+        );
+        assert_eq!(args.get_as::<f64, _>("count"), Ok(2.5));
+        assert_eq!(
+            args.get_as::<FluentNumber, _>("count").map(|n| n.value),
+            Ok(2.5)
+        );
+        assert_eq!(
+            args.get_as::<FluentDateTime, _>("date"),
+            Ok(FluentDateTime::from_epoch(0))
+        );
+        assert_eq!(
+            args.get_as::<Vec<f64>, _>("names"),
+            Err(ArgumentError::Mismatch {
+                key: "names".to_string(),
+                expected: std::any::type_name::<Vec<f64>>(),
+                found: "a list",
+            })
+        );
+        assert_eq!(
+            args.get_as::<bool, _>("missing").unwrap_err().to_string(),
+            "Argument `missing` is missing"
+        );
+    }
 }
@@ -0,0 +1,82 @@
+//! `bidi` detects the direction of text, which
+//! [`FluentBundle::set_detect_direction`](crate::bundle::FluentBundle::set_detect_direction)
+//! uses to choose the isolation marks around arguments.
+
+/// The direction of a text, as given by its strongly directional characters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+    /// The text has no strongly directional characters, such as a number.
+    Neutral,
+}
+
+impl Direction {
+    /// Returns the mark which opens an isolate of this direction: LRI, RLI, or
+    /// FSI for neutral text, which lets the renderer choose. An isolate is
+    /// closed by PDI, `'\u{2069}'`.
+    pub fn isolate_mark(self) -> char {
+        match self {
+            Self::LeftToRight => '\u{2066}',
+            Self::RightToLeft => '\u{2067}',
+            Self::Neutral => '\u{2068}',
+        }
+    }
+}
+
+/// Returns the dominant direction of `text`, which is the direction of most of
+/// its strongly directional characters, or of the first one on a tie.
+///
+/// Unlike the first strong character rule of FSI, a mostly Arabic text which
+/// starts with a Latin word is detected as right-to-left.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::bidi::{detect_direction, Direction};
+///
+/// assert_eq!(detect_direction("Hello"), Direction::LeftToRight);
+/// assert_eq!(detect_direction("abc مرحبا بالعالم"), Direction::RightToLeft);
+/// assert_eq!(detect_direction("42 / 7"), Direction::Neutral);
+/// ```
+pub fn detect_direction(text: &str) -> Direction {
+    let mut first = Direction::Neutral;
+    let mut balance = 0isize;
+    for c in text.chars() {
+        let direction = if is_strong_rtl(c) {
+            Direction::RightToLeft
+        } else if c.is_alphabetic() {
+            Direction::LeftToRight
+        } else {
+            continue;
+        };
+        if first == Direction::Neutral {
+            first = direction;
+        }
+        balance += if direction == Direction::LeftToRight {
+            1
+        } else {
+            -1
+        };
+    }
+    match balance {
+        0 => first,
+        balance if balance > 0 => Direction::LeftToRight,
+        _ => Direction::RightToLeft,
+    }
+}
+
+/// Returns `true` for the letters of the right-to-left scripts, whose bidi
+/// class is R or AL. Arabic digits, whose class is AN or EN, are excluded.
+fn is_strong_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{05BE}' | '\u{05C0}' | '\u{05C3}' | '\u{05C6}' | '\u{05D0}'..='\u{05F4}'
+        | '\u{0608}' | '\u{060B}' | '\u{060D}' | '\u{061B}'..='\u{064A}'
+        | '\u{066D}'..='\u{066F}' | '\u{0671}'..='\u{06D5}' | '\u{06E5}' | '\u{06E6}'
+        | '\u{06EE}' | '\u{06EF}' | '\u{06FA}'..='\u{0710}' | '\u{0712}'..='\u{072F}'
+        | '\u{074D}'..='\u{07A5}' | '\u{07B1}' | '\u{07C0}'..='\u{07EA}'
+        | '\u{0800}'..='\u{0815}' | '\u{0840}'..='\u{0858}' | '\u{0860}'..='\u{08C9}'
+        | '\u{FB1D}' | '\u{FB1F}'..='\u{FB28}' | '\u{FB2A}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFE}' | '\u{10800}'..='\u{10FFF}' | '\u{1E800}'..='\u{1EFFF}'
+    )
+}
@@ -1,4 +1,87 @@
-use crate::{FluentArgs, FluentValue};
+use std::borrow::Cow;
+use std::sync::{Arc, RwLock};
+
+use rustc_hash::FxHashMap;
+use unic_langid::LanguageIdentifier;
+
+use crate::validate::{FunctionSignature, OptionType};
+use crate::{FluentArgs, FluentError, FluentValue};
+
+/// The values set with [`FluentBundle::set_env`](crate::bundle::FluentBundle::set_env),
+/// shared with the `PLATFORM` and `ENV` functions of the bundle.
+pub(crate) type Env = Arc<RwLock<FxHashMap<String, String>>>;
+
+/// The functions of a [`FluentBundle`](crate::FluentBundle) or of a
+/// [`BundlePool`](crate::pool::BundlePool), to which the builtins are added.
+pub(crate) trait Functions {
+    /// The values read by `PLATFORM()` and `ENV()`.
+    fn env(&self) -> Env;
+
+    /// The locale whose rules `UPPER()`, `LOWER()`, `CAPITALIZE()` and
+    /// `ORDINAL()` follow.
+    fn first_locale(&self) -> Option<LanguageIdentifier>;
+
+    fn add_builtin<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static;
+
+    fn add_builtin_signature(&mut self, id: &str, signature: FunctionSignature);
+}
+
+/// Adds `NUMBER()` and its signature to `functions`, stopping at the first
+/// identifier in use.
+pub(crate) fn add_builtins(functions: &mut impl Functions) -> Result<(), FluentError> {
+    functions.add_builtin("NUMBER", NUMBER)?;
+    functions.add_builtin_signature("NUMBER", number_signature());
+    Ok(())
+}
+
+/// Adds the builtins which are not in the specification to `functions`, and
+/// returns the errors of those whose identifiers are in use, which are
+/// skipped.
+pub(crate) fn add_builtins_extended(functions: &mut impl Functions) -> Vec<FluentError> {
+    let env = functions.env();
+    let langid = functions.first_locale();
+    let langid = langid.as_ref();
+    let mut errors = vec![];
+
+    match functions.add_builtin("DATETIME", DATETIME) {
+        Ok(()) => functions.add_builtin_signature("DATETIME", datetime_signature()),
+        Err(err) => errors.push(err),
+    }
+    errors.extend(
+        functions
+            .add_builtin("PLATFORM", platform(env.clone()))
+            .err(),
+    );
+    errors.extend(functions.add_builtin("ENV", self::env(env)).err());
+    errors.extend(functions.add_builtin("DURATION", DURATION).err());
+    errors.extend(functions.add_builtin("PAD", PAD).err());
+    errors.extend(functions.add_builtin("TRUNCATE", TRUNCATE).err());
+    errors.extend(
+        functions
+            .add_builtin("UPPER", case(langid, Case::Upper))
+            .err(),
+    );
+    errors.extend(
+        functions
+            .add_builtin("LOWER", case(langid, Case::Lower))
+            .err(),
+    );
+    errors.extend(
+        functions
+            .add_builtin("CAPITALIZE", case(langid, Case::Capitalize))
+            .err(),
+    );
+    errors.extend(functions.add_builtin("ORDINAL", ordinal(langid)).err());
+    #[cfg(feature = "list-format")]
+    errors.extend(functions.add_builtin("LISTFORMAT", LISTFORMAT).err());
+    #[cfg(feature = "relative-time")]
+    errors.extend(functions.add_builtin("RELATIVETIME", RELATIVETIME).err());
+    #[cfg(feature = "units")]
+    errors.extend(functions.add_builtin("UNIT", UNIT).err());
+    errors
+}
 
 #[allow(non_snake_case)]
 pub fn NUMBER<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
@@ -11,3 +94,443 @@ pub fn NUMBER<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentV
 
     FluentValue::Number(n)
 }
+
+/// The signature of `NUMBER()`, with the options of
+/// [`FluentNumberOptions`](crate::types::FluentNumberOptions).
+pub(crate) fn number_signature() -> FunctionSignature {
+    let strings = [
+        "type",
+        "style",
+        "currency",
+        "currencyDisplay",
+        "notation",
+        "compactDisplay",
+        "useGrouping",
+        "signDisplay",
+        "roundingMode",
+    ];
+    let numbers = [
+        "minimumIntegerDigits",
+        "minimumFractionDigits",
+        "maximumFractionDigits",
+        "minimumSignificantDigits",
+        "maximumSignificantDigits",
+    ];
+    let signature = strings
+        .into_iter()
+        .fold(FunctionSignature::new().positional(1), |s, name| {
+            s.option(name, OptionType::String)
+        });
+    numbers
+        .into_iter()
+        .fold(signature, |s, name| s.option(name, OptionType::Number))
+}
+
+/// The signature of `DATETIME()`, with the options of
+/// [`FluentDateTimeOptions`](crate::types::FluentDateTimeOptions).
+pub(crate) fn datetime_signature() -> FunctionSignature {
+    FunctionSignature::new()
+        .positional(1)
+        .option("dateStyle", OptionType::String)
+        .option("timeStyle", OptionType::String)
+}
+
+/// Formats a date and time, from a number of milliseconds since the Unix epoch
+/// or a [`FluentValue::DateTime`], with the `dateStyle` and `timeStyle`
+/// options (`full`, `long`, `medium` or `short`), such as "March 5, 2024".
+///
+/// See [`FluentDateTime`](crate::types::FluentDateTime).
+#[allow(non_snake_case)]
+pub fn DATETIME<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let mut date = match positional.first() {
+        Some(FluentValue::DateTime(date)) => date.clone(),
+        Some(FluentValue::Number(n)) => crate::types::FluentDateTime::from_epoch(n.value as i64),
+        _ => return FluentValue::Error,
+    };
+    date.options.merge(named);
+
+    FluentValue::DateTime(date)
+}
+
+/// Formats a number of seconds as hours, minutes and seconds, such as
+/// "1:42:07" or "1h 42m 7s".
+///
+/// The `style` option is one of `long`, `short` (default), `narrow` or
+/// `digital`; see [`FluentDuration`](crate::types::FluentDuration).
+#[allow(non_snake_case)]
+pub fn DURATION<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let Some(FluentValue::Number(n)) = positional.first() else {
+        return FluentValue::Error;
+    };
+    let style = named.get_str("style").map(Into::into).unwrap_or_default();
+
+    FluentValue::Custom(Box::new(crate::types::FluentDuration::new(n.value, style)))
+}
+
+/// Joins a list of values, such as "Anna, Bob, and Carol", with the list
+/// patterns of the bundle locale.
+///
+/// The `type` option is one of `conjunction` (default), `disjunction` or
+/// `unit`, and the `style` option one of `long` (default), `short` or
+/// `narrow`; see [`FluentList`](crate::types::FluentList). A single value is
+/// formatted as a list of one item.
+#[cfg(feature = "list-format")]
+#[allow(non_snake_case)]
+pub fn LISTFORMAT<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let items = match positional.first() {
+        Some(FluentValue::List(items)) => items.iter().map(FluentValue::into_owned).collect(),
+        Some(FluentValue::Error) | None => return FluentValue::Error,
+        Some(value) => vec![value.into_owned()],
+    };
+    let r#type = named.get_str("type").map(Into::into).unwrap_or_default();
+    let style = named.get_str("style").map(Into::into).unwrap_or_default();
+
+    FluentValue::Custom(Box::new(crate::types::FluentList::new(
+        items, r#type, style,
+    )))
+}
+
+/// Formats a number with a measurement unit, such as "16 MB" or
+/// "3 kilometers", with the CLDR English name of the unit.
+///
+/// The `unit` option is a CLDR core unit identifier, such as `megabyte`,
+/// `kilometer` or `hour`, and the `display` option one of `long`, `short`
+/// (default) or `narrow`. Number options set by `NUMBER()` are kept.
+#[cfg(feature = "units")]
+#[allow(non_snake_case)]
+pub fn UNIT<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let Some(FluentValue::Number(n)) = positional.first() else {
+        return FluentValue::Error;
+    };
+    let Some(unit) = named.get_str("unit") else {
+        return FluentValue::Error;
+    };
+    let display = named.get_str("display").map(Into::into).unwrap_or_default();
+
+    FluentValue::Custom(Box::new(crate::types::FluentUnit::new(
+        n.clone(),
+        unit,
+        display,
+    )))
+}
+
+/// Formats a number of seconds relative to now, such as "3 minutes ago" or
+/// "in 2 days", using the largest unit which fits.
+///
+/// The `style` option is one of `long` (default), `short` or `narrow`.
+#[cfg(feature = "relative-time")]
+#[allow(non_snake_case)]
+pub fn RELATIVETIME<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let Some(FluentValue::Number(n)) = positional.first() else {
+        return FluentValue::Error;
+    };
+    let style = named.get_str("style").map(Into::into).unwrap_or_default();
+
+    FluentValue::Custom(Box::new(crate::types::FluentRelativeTime::new(
+        n.value, style,
+    )))
+}
+
+/// Returns a `PLATFORM()` function, which returns the `platform` value of `env`,
+/// or else the current operating system as one of `android`, `linux`, `macos`,
+/// `windows` or `other`.
+pub(crate) fn platform(
+    env: Env,
+) -> impl for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync {
+    move |_, _| {
+        if let Some(platform) = env.read().unwrap().get("platform") {
+            return FluentValue::String(platform.clone().into());
+        }
+        let platform = match std::env::consts::OS {
+            os @ ("android" | "linux" | "macos" | "windows") => os,
+            _ => "other",
+        };
+        FluentValue::String(platform.into())
+    }
+}
+
+/// Returns an `ENV("key")` function, which returns the value of `key` in `env`.
+pub(crate) fn env(
+    env: Env,
+) -> impl for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync {
+    move |positional, _| {
+        let Some(FluentValue::String(key)) = positional.first() else {
+            return FluentValue::Error;
+        };
+        match env.read().unwrap().get(key.as_ref()) {
+            Some(value) => FluentValue::String(value.clone().into()),
+            None => FluentValue::None,
+        }
+    }
+}
+
+/// Returns a function which formats its argument as an ordinal number in the
+/// language of `langid`, such as "21st" in English or "21." in German.
+///
+/// With the `spellout` feature, the `notation: "spellout"` option spells the
+/// number out, such as "twenty-first". Languages without ordinal rules get
+/// the number alone.
+pub(crate) fn ordinal(
+    langid: Option<&LanguageIdentifier>,
+) -> impl for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync {
+    let langid = langid.cloned();
+    move |positional, named| {
+        let Some(FluentValue::Number(n)) = positional.first() else {
+            return FluentValue::Error;
+        };
+        let mut n = n.clone();
+        n.options.merge(named);
+        n.options.r#type = crate::types::FluentNumberType::Ordinal;
+
+        #[cfg(feature = "spellout")]
+        if n.options.notation == crate::types::FluentNumberNotation::Spellout {
+            if let Some(words) = crate::types::spellout::spell_out(n.value, true, langid.as_ref()) {
+                return FluentValue::String(words.into());
+            }
+        }
+        let digits = n.as_string();
+        let suffix = match langid.as_ref() {
+            Some(langid) if n.value.fract() == 0.0 => ordinal_suffix(n.value, langid),
+            _ => "",
+        };
+        FluentValue::String(format!("{}{}", digits, suffix).into())
+    }
+}
+
+/// Returns the suffix of the integer `value` written as an ordinal number.
+fn ordinal_suffix(value: f64, langid: &LanguageIdentifier) -> &'static str {
+    let value = value.abs() as u64;
+    match langid.language.as_str() {
+        "en" => match (value % 10, value % 100) {
+            (1, 11) | (2, 12) | (3, 13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        },
+        "fr" if value == 1 => "er",
+        "fr" | "nl" => "e",
+        "sv" => match (value % 10, value % 100) {
+            (1 | 2, 11 | 12) => ":e",
+            (1 | 2, _) => ":a",
+            _ => ":e",
+        },
+        "es" => ".º",
+        "it" | "pt" => "º",
+        "cs" | "da" | "de" | "et" | "fi" | "hr" | "hu" | "nb" | "nn" | "no" | "pl" | "sk"
+        | "sl" | "tr" => ".",
+        _ => "",
+    }
+}
+
+/// The case mappings of the `UPPER`, `LOWER` and `CAPITALIZE` functions.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Case {
+    Upper,
+    Lower,
+    Capitalize,
+}
+
+/// Returns a function which maps the case of its argument with the rules of
+/// the language of `langid`, such as the dotted and dotless `i` of Turkish,
+/// or the accents dropped by Greek uppercase.
+pub(crate) fn case(
+    langid: Option<&LanguageIdentifier>,
+    case: Case,
+) -> impl for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync {
+    let language = langid.map_or("und", |langid| langid.language.as_str());
+    let turkic = matches!(language, "tr" | "az");
+    let greek = language == "el";
+    move |positional, _| {
+        let Some(value) = positional.first().and_then(string_arg) else {
+            return FluentValue::Error;
+        };
+        let value = match case {
+            Case::Upper => to_upper(&value, turkic, greek),
+            Case::Lower => to_lower(&value, turkic),
+            Case::Capitalize => {
+                let mut chars = value.chars();
+                match chars.next() {
+                    Some(first) => {
+                        to_upper(first.encode_utf8(&mut [0; 4]), turkic, greek) + chars.as_str()
+                    }
+                    None => String::new(),
+                }
+            }
+        };
+        FluentValue::String(value.into())
+    }
+}
+
+fn to_upper(s: &str, turkic: bool, greek: bool) -> String {
+    let mut upper = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            'i' if turkic => upper.push('İ'),
+            // Greek drops the tonos in uppercase.
+            '\u{301}' if greek => {}
+            _ => upper.extend(c.to_uppercase().map(|c| match c {
+                'Ά' if greek => 'Α',
+                'Έ' if greek => 'Ε',
+                'Ή' if greek => 'Η',
+                'Ί' if greek => 'Ι',
+                'Ό' if greek => 'Ο',
+                'Ύ' if greek => 'Υ',
+                'Ώ' if greek => 'Ω',
+                c => c,
+            })),
+        }
+    }
+    upper
+}
+
+fn to_lower(s: &str, turkic: bool) -> String {
+    if turkic {
+        // `str::to_lowercase` handles the final sigma, which depends on the
+        // following letters, so only the Turkic letters are mapped first.
+        s.replace('I', "ı").replace('İ', "i").to_lowercase()
+    } else {
+        s.to_lowercase()
+    }
+}
+
+/// Pads a value with spaces to the `width` columns it takes in a terminal.
+///
+/// The `align` option is one of `left` (default), `right` or `center`. Values
+/// which are already wider are left unchanged.
+///
+/// Widths count East Asian wide characters, such as CJK ideographs and most
+/// emoji, as two columns and combining marks as none. Bidi isolation marks are
+/// not counted either, though terminal output is usually formatted with
+/// [`FluentBundle::set_use_isolating(false)`](crate::bundle::FluentBundle::set_use_isolating).
+#[allow(non_snake_case)]
+pub fn PAD<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let (Some(value), Some(width)) = (
+        positional.first().and_then(string_arg),
+        named.get_number("width"),
+    ) else {
+        return FluentValue::Error;
+    };
+    let padding = (width as usize).saturating_sub(display_width(&value));
+    if padding == 0 {
+        return FluentValue::String(value);
+    }
+
+    let (before, after) = match named.get_str("align") {
+        Some("right") => (padding, 0),
+        Some("center") => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    FluentValue::String(format!("{:before$}{}{:after$}", "", value, "").into())
+}
+
+/// Truncates a value to at most `max` terminal columns, ending it with the
+/// `ellipsis` option, `…` by default, when it is cut.
+///
+/// Widths are counted as in [`PAD`]. Combining marks are kept with the
+/// character they follow.
+#[allow(non_snake_case)]
+pub fn TRUNCATE<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+    let (Some(value), Some(max)) = (
+        positional.first().and_then(string_arg),
+        named.get_number("max"),
+    ) else {
+        return FluentValue::Error;
+    };
+    let max = max as usize;
+    if display_width(&value) <= max {
+        return FluentValue::String(value);
+    }
+
+    let ellipsis = named.get_str("ellipsis").unwrap_or("…");
+    let mut width = display_width(ellipsis);
+    let mut end = 0;
+    for (idx, c) in value.char_indices() {
+        width += char_width(c);
+        if width > max {
+            break;
+        }
+        end = idx + c.len_utf8();
+    }
+    FluentValue::String(format!("{}{}", &value[..end], ellipsis).into())
+}
+
+/// Returns the text of a string or number argument.
+fn string_arg<'a>(value: &FluentValue<'a>) -> Option<Cow<'a, str>> {
+    match value {
+        FluentValue::String(s) => Some(s.clone()),
+        FluentValue::Number(n) => Some(n.as_string()),
+        _ => None,
+    }
+}
+
+fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Returns the number of terminal columns taken by `c`, following the East
+/// Asian width of the most common scripts and emoji.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // Control characters, combining marks, zero-width and bidi formatting
+        // characters.
+        0x00..=0x1F
+        | 0x7F..=0x9F
+        | 0x0300..=0x036F
+        | 0x0483..=0x0489
+        | 0x0591..=0x05BD
+        | 0x0610..=0x061A
+        | 0x064B..=0x065F
+        | 0x0670
+        | 0x0E31
+        | 0x0E34..=0x0E3A
+        | 0x0E47..=0x0E4E
+        | 0x1160..=0x11FF
+        | 0x200B..=0x200F
+        | 0x202A..=0x202E
+        | 0x2060..=0x2069
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F
+        | 0xFEFF => 0,
+        // Wide and fullwidth characters.
+        0x1100..=0x115F
+        | 0x231A..=0x231B
+        | 0x2329..=0x232A
+        | 0x23E9..=0x23EC
+        | 0x25FD..=0x25FE
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE
+        | 0x26C4..=0x26C5
+        | 0x2705
+        | 0x270A..=0x270B
+        | 0x2728
+        | 0x274C
+        | 0x2753..=0x2755
+        | 0x2757
+        | 0x2795..=0x2797
+        | 0x2B1B..=0x2B1C
+        | 0x2B50
+        | 0x2B55
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE10..=0xFE19
+        | 0xFE30..=0xFE6F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
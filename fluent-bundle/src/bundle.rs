@@ -4,26 +4,42 @@
 //! internationalization formatters, functions, scopeironmental variables and are expected to be used
 //! together.
 
-use rustc_hash::FxHashMap;
 use std::borrow::Borrow;
 use std::borrow::Cow;
 use std::collections::hash_map::Entry as HashEntry;
 use std::default::Default;
 use std::fmt;
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
-use fluent_syntax::ast;
+use fluent_syntax::ast::{self, visit, visit::Visit};
+use fluent_syntax::parser::{self, ParserError};
 use intl_memoizer::IntlLangMemoizer;
+use rustc_hash::{FxHashMap, FxHashSet};
+use self_cell::self_cell;
 use unic_langid::LanguageIdentifier;
 
 use crate::args::FluentArgs;
+use crate::builtins::Env;
 use crate::entry::Entry;
 use crate::entry::GetEntry;
-use crate::errors::{EntryKind, FluentError};
+use crate::errors::{EntryKind, ErrorSink, FluentError};
+use crate::function::{AsyncCalls, AsyncFunctionFuture, FluentFunctionError, FunctionContext};
+use crate::interner::{Interner, Key, Symbol};
 use crate::memoizer::MemoizerKind;
-use crate::message::FluentMessage;
-use crate::resolver::{ResolveValue, Scope, WriteValue};
-use crate::resource::FluentResource;
-use crate::types::FluentValue;
+use crate::message::{FluentMessage, VariantTag};
+use crate::resolver::errors::ReferenceKind;
+use crate::resolver::DEFAULT_MAX_DEPTH;
+use crate::resolver::{ResolveValue, ResolverError, Scope, WriteValue};
+use crate::resource::{CatalogStats, FluentResource};
+use crate::types::{FluentNumber, FluentValue, NumberSymbols, PluralCategory, PluralRulesBackend};
+use crate::validate::{check_term_signatures, FunctionSignature, TermSignature};
+
+/// The most passes of [`FluentBundle::format_pattern_async`], which bounds the
+/// chains of async calls taking the results of other async calls.
+const MAX_ASYNC_PASSES: usize = 8;
 
 /// A collection of localization messages for a single locale, which are meant
 /// to be used together in a single view, widget or any other UI abstraction.
@@ -136,19 +152,171 @@ use crate::types::FluentValue;
 pub struct FluentBundle<R, M> {
     pub locales: Vec<LanguageIdentifier>,
     pub(crate) resources: Vec<R>,
-    pub(crate) entries: FxHashMap<String, Entry>,
+    /// Whether each resource was added with
+    /// [`FluentBundle::add_resource_overriding`], for the entries to be
+    /// re-resolved when a resource is removed or replaced.
+    pub(crate) overriding: Vec<bool>,
+    /// The entries of the bundle, keyed by the symbols of their identifiers
+    /// in `interner`.
+    pub(crate) entries: FxHashMap<Symbol, Entry>,
+    pub(crate) interner: Interner,
     pub(crate) intls: M,
+    /// The memoizers of the locales after the first, for formatting in them
+    /// with [`FluentBundle::format_pattern_in_locale`].
+    pub(crate) locale_intls: Vec<M>,
     pub(crate) use_isolating: bool,
+    pub(crate) detect_direction: bool,
+    pub(crate) max_depth: usize,
     pub(crate) transform: Option<fn(&str) -> Cow<str>>,
     pub(crate) formatter: Option<fn(&FluentValue, &M, FormatterPass) -> Option<String>>,
+    /// The final strings of message patterns made only of text, see
+    /// [`TextPatterns`].
+    pub(crate) text_patterns: TextPatterns,
+    /// The variants selected by plural select expressions, see
+    /// [`VariantCache`].
+    pub(crate) variant_cache: VariantCache,
+    /// The patterns parsed by [`FluentBundle::format_raw_pattern`], keyed by
+    /// their source.
+    pub(crate) raw_patterns: RawPatterns,
+    pub(crate) deprecation_hook: Option<fn(&str, &str)>,
+    /// The chooser of message variants, see
+    /// [`FluentBundle::set_variant_selector`].
+    pub(crate) variant_selector: Option<fn(&str, &[VariantTag]) -> usize>,
+    /// The supplier of the values of unknown references, see
+    /// [`FluentBundle::set_unknown_reference_handler`].
+    pub(crate) unknown_reference_handler: Option<Arc<UnknownReferenceHandler>>,
+    /// The symbols used to format numbers, see
+    /// [`FluentBundle::set_number_symbols`].
+    pub(crate) number_symbols: Option<NumberSymbols>,
+    /// The resolver of the plural categories of numbers, see
+    /// [`FluentBundle::set_plural_rules`].
+    pub(crate) plural_rules: Option<Arc<dyn PluralRulesBackend>>,
+    /// The matcher of selectors against variant keys, see
+    /// [`FluentBundle::set_selector_matcher`].
+    pub(crate) selector_matcher: Option<Arc<SelectorMatcher>>,
+    /// The separator between a namespace and a function name, see
+    /// [`FluentBundle::add_function_ns`].
+    pub(crate) namespace_separator: &'static str,
+    /// The signatures of terms, see [`FluentBundle::add_term_signature`].
+    pub(crate) term_signatures: FxHashMap<String, TermSignature>,
+    /// The signatures of functions, see
+    /// [`FluentBundle::add_function_signature`].
+    pub(crate) function_signatures: FxHashMap<String, FunctionSignature>,
+    /// The flags required by the entries annotated with
+    /// `# fluent: requires-flag=flag`, see [`FluentBundle::set_enabled_flags`].
+    pub(crate) required_flags: FxHashMap<Symbol, Box<str>>,
+    pub(crate) enabled_flags: FxHashSet<String>,
+    /// The values returned by the `PLATFORM` and `ENV` functions.
+    pub(crate) env: Env,
+    /// The bundle this one is an overlay of, see [`FluentBundle::overlay`].
+    pub(crate) parent: Option<Arc<FluentBundle<R, M>>>,
+    /// The bundle whose entries are used for the ones missing from this
+    /// bundle and its parents, see [`FallbackBundle`].
+    pub(crate) fallback: Option<Arc<FluentBundle<R, M>>>,
 }
 
+/// A function which supplies the value of a reference to an unknown message,
+/// term or variable.
+pub(crate) type UnknownReferenceHandler =
+    dyn Fn(&ReferenceKind) -> Option<FluentValue<'static>> + Send + Sync;
+
+/// A function which decides whether a selector matches a variant key, or
+/// declines with `None`.
+pub(crate) type SelectorMatcher = dyn Fn(&FluentValue, &FluentValue) -> Option<bool> + Send + Sync;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FormatterPass {
     Final,
     Inner,
 }
 
+/// A pattern formatted with [`FluentBundle::format_pattern_in_locale`], along
+/// with the locale whose rules were used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedPattern<'bundle> {
+    value: Cow<'bundle, str>,
+    locale: Option<&'bundle LanguageIdentifier>,
+}
+
+impl<'bundle> FormattedPattern<'bundle> {
+    /// Returns the formatted pattern.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the formatted pattern, dropping the locale.
+    pub fn into_value(self) -> Cow<'bundle, str> {
+        self.value
+    }
+
+    /// Returns the locale of the bundle whose plural rules and number
+    /// formatting were used, or `None` if the bundle has no locales.
+    pub fn resolved_locale(&self) -> Option<&'bundle LanguageIdentifier> {
+        self.locale
+    }
+}
+
+impl fmt::Display for FormattedPattern<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+/// A part of a pattern formatted with [`FluentBundle::format_pattern_to_parts`].
+///
+/// Displaying the parts one after the other gives the same string as
+/// [`FluentBundle::format_pattern`], unless a
+/// [`formatter`](FluentBundle::set_formatter) changes the final string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormattedPart<'bundle> {
+    /// Text of the pattern, or of the selected variant of one of its select
+    /// expressions, after the [transform](FluentBundle::set_transform).
+    Text(Cow<'bundle, str>),
+    /// A formatted placeable, along with the expression of the pattern it
+    /// comes from. Referenced messages and terms are formatted as a whole.
+    Placeable {
+        expression: &'bundle ast::Expression<&'bundle str>,
+        value: String,
+    },
+    /// The mark which opens the isolate of a placeable, U+2068 FIRST STRONG
+    /// ISOLATE unless the [direction is detected](FluentBundle::set_detect_direction).
+    IsolateStart(char),
+    /// U+2069 POP DIRECTIONAL ISOLATE, which closes the isolate of a placeable.
+    IsolateEnd,
+}
+
+impl fmt::Display for FormattedPart<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Placeable { value, .. } => f.write_str(value),
+            Self::IsolateStart(mark) => write!(f, "{}", mark),
+            Self::IsolateEnd => f.write_str("\u{2069}"),
+        }
+    }
+}
+
+/// Entry counts and an estimate of the memory held by a [`FluentBundle`], see
+/// [`FluentBundle::memory_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BundleMemoryStats {
+    /// The number of resources added to the bundle.
+    pub resources: usize,
+    pub messages: usize,
+    pub terms: usize,
+    pub functions: usize,
+    pub aliases: usize,
+    /// The total length in bytes of the FTL sources of the resources.
+    pub resource_size: usize,
+    /// The number of patterns kept by [`FluentBundle::format_raw_pattern`].
+    pub raw_patterns: usize,
+    /// The memoized internationalization formatters of the bundle.
+    pub intls: intl_memoizer::MemoryStats,
+    /// An estimate of the heap memory held by the bundle, in bytes, including
+    /// `resource_size` and `intls.heap_size`.
+    pub heap_size: usize,
+}
+
 impl<R, M> FluentBundle<R, M> {
     /// Adds a resource to the bundle, returning an empty [`Result<T>`] on success.
     ///
@@ -161,6 +329,7 @@ impl<R, M> FluentBundle<R, M> {
     ///   - `&FluentResource`
     ///   - `Rc<FluentResource>`
     ///   - `Arc<FluentResource>`
+    ///   - [`ResourceHandle`](crate::ResourceHandle), to mix the above in one bundle
     ///
     /// This allows the user to introduce custom resource management and share
     /// resources between instances of `FluentBundle`.
@@ -203,36 +372,18 @@ impl<R, M> FluentBundle<R, M> {
 
         let res = r.borrow();
         let res_pos = self.resources.len();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("fluent_bundle::add_resource", res_pos).entered();
 
-        for (entry_pos, entry) in res.entries().enumerate() {
-            let (id, entry) = match entry {
-                ast::Entry::Message(ast::Message { ref id, .. }) => {
-                    (id.name, Entry::Message((res_pos, entry_pos)))
-                }
-                ast::Entry::Term(ast::Term { ref id, .. }) => {
-                    (id.name, Entry::Term((res_pos, entry_pos)))
-                }
-                _ => continue,
-            };
-
-            match self.entries.entry(id.to_string()) {
-                HashEntry::Vacant(empty) => {
-                    empty.insert(entry);
-                }
-                HashEntry::Occupied(_) => {
-                    let kind = match entry {
-                        Entry::Message(..) => EntryKind::Message,
-                        Entry::Term(..) => EntryKind::Term,
-                        _ => unreachable!(),
-                    };
-                    errors.push(FluentError::Overriding {
-                        kind,
-                        id: id.to_string(),
-                    });
-                }
-            }
-        }
+        self.insert_entries(resource_entries(res, res_pos), &mut errors);
+        self.insert_required_flags(res, res_pos);
+        check_term_signatures(res, &self.term_signatures, &mut errors);
+        self.text_patterns
+            .insert_resource(res, res_pos, self.transform);
+        self.variant_cache.insert_resource(res, res_pos);
+        self.interner.insert_references(res);
         self.resources.push(r);
+        self.overriding.push(false);
 
         if errors.is_empty() {
             Ok(())
@@ -251,6 +402,7 @@ impl<R, M> FluentBundle<R, M> {
     ///   - `&FluentResource`
     ///   - `Rc<FluentResource>`
     ///   - `Arc<FluentResource>`
+    ///   - [`ResourceHandle`](crate::ResourceHandle), to mix the above in one bundle
     ///
     /// This allows the user to introduce custom resource management and share
     /// resources between instances of `FluentBundle`.
@@ -307,115 +459,167 @@ impl<R, M> FluentBundle<R, M> {
         let res = r.borrow();
         let res_pos = self.resources.len();
 
-        for (entry_pos, entry) in res.entries().enumerate() {
-            let (id, entry) = match entry {
-                ast::Entry::Message(ast::Message { ref id, .. }) => {
-                    (id.name, Entry::Message((res_pos, entry_pos)))
-                }
-                ast::Entry::Term(ast::Term { ref id, .. }) => {
-                    (id.name, Entry::Term((res_pos, entry_pos)))
-                }
-                _ => continue,
-            };
-
-            self.entries.insert(id.to_string(), entry);
+        for (id, entry) in resource_entries(res, res_pos) {
+            let _ = self.insert_entry(id, entry, true);
         }
+        self.insert_required_flags(res, res_pos);
+        self.text_patterns
+            .insert_resource(res, res_pos, self.transform);
+        self.variant_cache.insert_resource(res, res_pos);
+        self.interner.insert_references(res);
         self.resources.push(r);
+        self.overriding.push(true);
     }
 
-    /// When formatting patterns, `FluentBundle` inserts
-    /// Unicode Directionality Isolation Marks to indicate
-    /// that the direction of a placeable may differ from
-    /// the surrounding message.
-    ///
-    /// This is important for cases such as when a
-    /// right-to-left user name is presented in the
-    /// left-to-right message.
-    ///
-    /// In some cases, such as testing, the user may want
-    /// to disable the isolating.
-    pub fn set_use_isolating(&mut self, value: bool) {
-        self.use_isolating = value;
-    }
-
-    /// This method allows to specify a function that will
-    /// be called on all textual fragments of the pattern
-    /// during formatting.
-    ///
-    /// This is currently primarily used for pseudolocalization,
-    /// and `fluent-pseudo` crate provides a function
-    /// that can be passed here.
-    pub fn set_transform(&mut self, func: Option<fn(&str) -> Cow<str>>) {
-        self.transform = func;
-    }
-
-    /// This method allows to specify a function that will
-    /// be called before any `FluentValue` is formatted
-    /// allowing overrides.
+    /// Adds many resources to the bundle at once, as if by calling
+    /// [`add_resource`](FluentBundle::add_resource) on each of them in order.
     ///
-    /// It's particularly useful for plugging in an external
-    /// formatter for `FluentValue::Number`.
-    pub fn set_formatter(
-        &mut self,
-        func: Option<fn(&FluentValue, &M, FormatterPass) -> Option<String>>,
-    ) {
-        self.formatter = func;
-    }
-
-    /// Returns true if this bundle contains a message with the given id.
+    /// The entries of the resources are collected on the rayon thread pool,
+    /// so that only the insertion into the bundle happens sequentially. Combined
+    /// with [`FluentResource::try_new_parallel`], this cuts the startup time of
+    /// applications which load many resources.
     ///
-    /// # Examples
+    /// # Example
     ///
     /// ```
     /// use fluent_bundle::{FluentBundle, FluentResource};
     /// use unic_langid::langid;
     ///
-    /// let ftl_string = String::from("hello = Hi!");
-    /// let resource = FluentResource::try_new(ftl_string)
-    ///     .expect("Failed to parse an FTL string.");
-    /// let langid_en = langid!("en-US");
-    /// let mut bundle = FluentBundle::new(vec![langid_en]);
-    /// bundle.add_resource(&resource)
+    /// let sources = vec![
+    ///     "hello = Hello!".to_string(),
+    ///     "goodbye = Goodbye!".to_string(),
+    /// ];
+    /// let resources = FluentResource::try_new_parallel(sources)
+    ///     .into_iter()
+    ///     .map(|res| res.expect("Failed to parse an FTL string."))
+    ///     .collect();
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resources_parallel(resources)
     ///     .expect("Failed to add FTL resources to the bundle.");
-    /// assert_eq!(true, bundle.has_message("hello"));
     ///
+    /// assert!(bundle.has_message("hello"));
+    /// assert!(bundle.has_message("goodbye"));
     /// ```
-    pub fn has_message(&self, id: &str) -> bool
+    #[cfg(feature = "rayon")]
+    pub fn add_resources_parallel(&mut self, resources: Vec<R>) -> Result<(), Vec<FluentError>>
     where
-        R: Borrow<FluentResource>,
+        R: Borrow<FluentResource> + Sync,
     {
-        self.get_entry_message(id).is_some()
+        use rayon::prelude::*;
+
+        let mut errors = vec![];
+        let first_pos = self.resources.len();
+        let transform = self.transform;
+
+        let indexed: Vec<_> = resources
+            .par_iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let res = r.borrow();
+                let entries: Vec<_> = resource_entries(res, first_pos + i).collect();
+                let mut text_patterns = TextPatterns::default();
+                text_patterns.insert_resource(res, first_pos + i, transform);
+                (entries, text_patterns)
+            })
+            .collect();
+
+        for (entries, text_patterns) in indexed {
+            self.insert_entries(entries.into_iter(), &mut errors);
+            self.text_patterns.extend(text_patterns);
+        }
+        for (i, r) in resources.iter().enumerate() {
+            self.insert_required_flags(r.borrow(), first_pos + i);
+            check_term_signatures(r.borrow(), &self.term_signatures, &mut errors);
+            self.variant_cache
+                .insert_resource(r.borrow(), first_pos + i);
+            self.interner.insert_references(r.borrow());
+        }
+        self.overriding.resize(first_pos + resources.len(), false);
+        self.resources.extend(resources);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
-    /// Retrieves a `FluentMessage` from a bundle.
+    /// Removes the resource added at `index`, counting the resources in the
+    /// order they were added, along with its entries, and returns it.
     ///
-    /// # Examples
+    /// The identifiers of the removed entries are resolved again as if the
+    /// remaining resources had been added in order: each is bound to the
+    /// entry of the first resource which defines it, or of the last one added
+    /// with [`add_resource_overriding`](FluentBundle::add_resource_overriding),
+    /// and is removed if no resource defines it. Functions and aliases added
+    /// to the bundle are kept. The resources after `index` are shifted down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Example
     ///
     /// ```
     /// use fluent_bundle::{FluentBundle, FluentResource};
     /// use unic_langid::langid;
     ///
-    /// let ftl_string = String::from("hello-world = Hello World!");
-    /// let resource = FluentResource::try_new(ftl_string)
+    /// let base = FluentResource::try_new("hello = Hello!\ngoodbye = Goodbye!".to_string())
+    ///     .expect("Failed to parse an FTL string.");
+    /// let update = FluentResource::try_new("hello = Hi!".to_string())
     ///     .expect("Failed to parse an FTL string.");
     ///
-    /// let langid_en = langid!("en-US");
-    /// let mut bundle = FluentBundle::new(vec![langid_en]);
-    ///
-    /// bundle.add_resource(&resource)
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(base)
     ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.add_resource_overriding(update);
     ///
-    /// let msg = bundle.get_message("hello-world");
-    /// assert_eq!(msg.is_some(), true);
+    /// bundle.remove_resource(1);
+    /// let msg = bundle.get_message("hello").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// assert_eq!(bundle.format_pattern(msg.value().unwrap(), None, &mut errors), "Hello!");
+    ///
+    /// bundle.remove_resource(0);
+    /// assert!(!bundle.has_message("hello"));
     /// ```
-    pub fn get_message<'l>(&'l self, id: &str) -> Option<FluentMessage<'l>>
+    pub fn remove_resource(&mut self, index: usize) -> R
     where
         R: Borrow<FluentResource>,
     {
-        self.get_entry_message(id).map(Into::into)
+        let old = self.resources.remove(index);
+        self.overriding.remove(index);
+        for entry in self.entries.values_mut() {
+            if let Entry::Message((pos, _)) | Entry::Term((pos, _)) = entry {
+                if *pos > index {
+                    *pos -= 1;
+                }
+            }
+        }
+
+        let ids = old
+            .borrow()
+            .entry_ids()
+            .map(|(_, id, _)| id.to_string())
+            .collect();
+        self.resolve_entries(ids, old.borrow());
+        old
     }
 
-    /// Writes a formatted pattern which comes from a `FluentMessage`.
+    /// Replaces the resource added at `index` with `r`, and returns the
+    /// replaced resource.
+    ///
+    /// The new resource takes the place of the replaced one in the order of
+    /// the resources, and the identifiers of the entries of both are resolved
+    /// again as in [`remove_resource`](FluentBundle::remove_resource). The
+    /// bundle is never left without the entries which are defined in both.
+    /// Entries of `r` which lose to the ones of other resources are ignored
+    /// and reported as overriding errors in `errors`, as by
+    /// [`add_resource`](FluentBundle::add_resource).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
     ///
     /// # Example
     ///
@@ -423,143 +627,2052 @@ impl<R, M> FluentBundle<R, M> {
     /// use fluent_bundle::{FluentBundle, FluentResource};
     /// use unic_langid::langid;
     ///
-    /// let ftl_string = String::from("hello-world = Hello World!");
-    /// let resource = FluentResource::try_new(ftl_string)
+    /// let res = FluentResource::try_new("hello = Hello!\ngoodbye = Goodbye!".to_string())
     ///     .expect("Failed to parse an FTL string.");
-    ///
-    /// let langid_en = langid!("en-US");
-    /// let mut bundle = FluentBundle::new(vec![langid_en]);
-    ///
-    /// bundle.add_resource(&resource)
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(res)
     ///     .expect("Failed to add FTL resources to the bundle.");
     ///
-    /// let msg = bundle.get_message("hello-world")
-    ///     .expect("Failed to retrieve a FluentMessage.");
-    ///
-    /// let pattern = msg.value()
-    ///     .expect("Missing Value.");
+    /// let reloaded = FluentResource::try_new("hello = Hi!".to_string())
+    ///     .expect("Failed to parse an FTL string.");
     /// let mut errors = vec![];
+    /// bundle.replace_resource(0, reloaded, &mut errors);
+    /// assert!(errors.is_empty());
     ///
-    /// let mut s = String::new();
-    /// bundle.write_pattern(&mut s, &pattern, None, &mut errors)
-    ///     .expect("Failed to write.");
-    ///
-    /// assert_eq!(s, "Hello World!");
+    /// let msg = bundle.get_message("hello").expect("Message doesn't exist.");
+    /// assert_eq!(bundle.format_pattern(msg.value().unwrap(), None, &mut errors), "Hi!");
+    /// assert!(!bundle.has_message("goodbye"));
     /// ```
-    pub fn write_pattern<'bundle, W>(
-        &'bundle self,
-        w: &mut W,
-        pattern: &'bundle ast::Pattern<&str>,
-        args: Option<&'bundle FluentArgs>,
-        errors: &mut Vec<FluentError>,
-    ) -> fmt::Result
+    pub fn replace_resource<E>(&mut self, index: usize, r: R, errors: &mut E) -> R
     where
         R: Borrow<FluentResource>,
-        W: fmt::Write,
-        M: MemoizerKind,
+        E: ErrorSink,
     {
-        let mut scope = Scope::new(self, args, Some(errors));
-        pattern.write(w, &mut scope)
+        let old = std::mem::replace(&mut self.resources[index], r);
+
+        let res = self.resources[index].borrow();
+        let ids = old
+            .borrow()
+            .entry_ids()
+            .chain(res.entry_ids())
+            .map(|(_, id, _)| id.to_string())
+            .collect();
+        self.resolve_entries(ids, old.borrow());
+
+        let mut rejected = vec![];
+        let res = self.resources[index].borrow();
+        for (id, entry) in resource_entries(res, index) {
+            let current = self.own_entry(id);
+            let kind = match (current, &entry) {
+                (Some(Entry::Message(a)), Entry::Message(b)) if a == b => continue,
+                (Some(Entry::Term(a)), Entry::Term(b)) if a == b => continue,
+                (Some(Entry::Alias(a)), Entry::Alias(b)) if a == b => continue,
+                (_, Entry::Term(..)) => EntryKind::Term,
+                _ => EntryKind::Message,
+            };
+            rejected.push(FluentError::Overriding {
+                kind,
+                id: id.to_string(),
+            });
+        }
+        check_term_signatures(res, &self.term_signatures, &mut rejected);
+        for error in rejected {
+            errors.push_error(error);
+        }
+        old
     }
 
-    /// Formats a pattern which comes from a `FluentMessage`.
+    /// Binds each of `ids` to the entry of the resource which wins it, as if
+    /// the resources had been added again in order, or removes it if no
+    /// resource defines it, along with the flag the entry requires.
+    ///
+    /// Entries which do not come from the resources, such as functions, are
+    /// kept. `old` is the resource which was removed or replaced, whose
+    /// aliases are told apart from the ones added to the bundle.
+    fn resolve_entries(&mut self, ids: FxHashSet<String>, old: &FluentResource)
+    where
+        R: Borrow<FluentResource>,
+    {
+        let mut aliases: FxHashSet<_> = old.deprecated_aliases().into_iter().collect();
+        let mut winners: FxHashMap<&str, (Entry, Option<&str>)> = FxHashMap::default();
+        for (pos, res) in self.resources.iter().enumerate() {
+            let res = res.borrow();
+            aliases.extend(res.deprecated_aliases());
+            let flags: FxHashMap<_, _> = res.required_flags().into_iter().collect();
+            for (id, entry) in resource_entries(res, pos) {
+                if !ids.contains(id) || (!self.overriding[pos] && winners.contains_key(id)) {
+                    continue;
+                }
+                winners.insert(id, (entry, flags.get(id).copied()));
+            }
+        }
+
+        let resolved: Vec<_> = ids
+            .into_iter()
+            .filter(|id| {
+                let current = self.own_entry(id);
+                match current {
+                    Some(Entry::Function(_) | Entry::AsyncFunction(_)) => false,
+                    Some(Entry::Alias(target)) => aliases.contains(&(id.as_str(), &**target)),
+                    _ => true,
+                }
+            })
+            .map(|id| {
+                let winner = winners
+                    .remove(id.as_str())
+                    .map(|(entry, flag)| (entry, flag.map(Box::<str>::from)));
+                (id, winner)
+            })
+            .collect();
+
+        for (id, winner) in resolved {
+            let symbol = self.interner.intern(&id);
+            self.required_flags.remove(&symbol);
+            match winner {
+                Some((entry, flag)) => {
+                    let _ = self.insert_entry(&id, entry, true);
+                    if let Some(flag) = flag {
+                        self.required_flags.insert(symbol, flag);
+                    }
+                }
+                None => {
+                    self.entries.remove(&symbol);
+                }
+            }
+        }
+
+        // The positions of the patterns following the removed or replaced
+        // resource have changed, and the addresses of the identifiers and
+        // variants of the removed one may be reused.
+        self.cache_text_patterns();
+        self.variant_cache = VariantCache::default();
+        for (res_pos, res) in self.resources.iter().enumerate() {
+            self.variant_cache.insert_resource(res.borrow(), res_pos);
+        }
+        self.interner.clear_references();
+        for res in &self.resources {
+            self.interner.insert_references(res.borrow());
+        }
+    }
+
+    /// Inserts `entries`, keeping the existing ones and reporting an
+    /// overriding error for each of the new ones which is rejected.
+    fn insert_entries<'r>(
+        &mut self,
+        entries: impl Iterator<Item = (&'r str, Entry)>,
+        errors: &mut Vec<FluentError>,
+    ) {
+        for (id, entry) in entries {
+            if let Err(entry) = self.insert_entry(id, entry, false) {
+                let kind = match entry {
+                    Entry::Message(..) | Entry::Alias(..) => EntryKind::Message,
+                    Entry::Term(..) => EntryKind::Term,
+                    _ => unreachable!(),
+                };
+                errors.push(FluentError::Overriding {
+                    kind,
+                    id: id.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Records the flags required by the entries of `res`, which is stored at
+    /// `res_pos`, replacing the flags of the entries they override. The
+    /// entries of `res` which were rejected are skipped.
+    fn insert_required_flags(&mut self, res: &FluentResource, res_pos: usize) {
+        let from_res = |bundle: &Self, id: &str| {
+            let symbol = bundle.interner.get(id)?;
+            let entry = bundle.entries.get(&symbol);
+            matches!(entry, Some(Entry::Message((pos, _)) | Entry::Term((pos, _))) if *pos == res_pos)
+                .then_some(symbol)
+        };
+        if !self.required_flags.is_empty() {
+            for (_, id, _) in res.entry_ids() {
+                if let Some(symbol) = from_res(self, id) {
+                    self.required_flags.remove(&symbol);
+                }
+            }
+        }
+        for (id, flag) in res.required_flags() {
+            if let Some(symbol) = from_res(self, id) {
+                self.required_flags.insert(symbol, flag.into());
+            }
+        }
+    }
+
+    /// Inserts `entry` under `id`, replacing an existing entry only if
+    /// `overriding` is set. The rejected entry is returned otherwise.
+    fn insert_entry(&mut self, id: &str, entry: Entry, overriding: bool) -> Result<(), Entry> {
+        match self.entries.entry(self.interner.intern(id)) {
+            HashEntry::Vacant(empty) => {
+                empty.insert(entry);
+            }
+            HashEntry::Occupied(mut existing) if overriding => {
+                existing.insert(entry);
+            }
+            HashEntry::Occupied(_) => return Err(entry),
+        }
+        Ok(())
+    }
+
+    /// Returns the entry `id` of this bundle only.
+    fn own_entry(&self, id: &str) -> Option<&Entry> {
+        self.entries.get(&self.interner.get(id)?)
+    }
+
+    /// Returns the key of the identifier `name`, to look it up in this bundle.
+    pub(crate) fn key<'a>(&self, name: &'a str) -> Key<'a> {
+        Key {
+            name,
+            symbol: self.interner.get(name),
+        }
+    }
+
+    /// Returns the key of the identifier `name` referenced in a pattern,
+    /// which is found by its address if the pattern belongs to a resource of
+    /// this bundle or of its parents.
+    pub(crate) fn reference_key<'a>(&self, name: &'a str) -> Key<'a> {
+        let mut bundle = self;
+        loop {
+            if let Some(symbol) = bundle.interner.reference(name) {
+                return Key {
+                    name,
+                    symbol: Some(symbol),
+                };
+            }
+            match bundle.parent.as_deref() {
+                Some(parent) => bundle = parent,
+                None => return self.key(name),
+            }
+        }
+    }
+
+    /// Looks up the entry `key` in this bundle, or else in the bundles it is
+    /// an overlay of, along with the bundle which holds it.
+    ///
+    /// Aliases are resolved to the entry of their target, and reported to the
+    /// deprecation hook.
+    pub(crate) fn get_entry(&self, key: Key) -> Option<(&Self, &Entry)> {
+        match self.get_own_entry(key)? {
+            (_, Entry::Alias(target)) => {
+                if let Some(hook) = self.deprecation_hook {
+                    hook(key.name, target);
+                }
+                self.get_own_entry(self.key(target))
+                    .filter(|(_, entry)| !matches!(entry, Entry::Alias(_)))
+            }
+            found => Some(found),
+        }
+    }
+
+    /// Returns the identifier of the message `key` is an alias of, if it is one.
+    pub(crate) fn alias_target(&self, key: Key) -> Option<&str> {
+        match self.get_own_entry(key)? {
+            (_, Entry::Alias(target)) => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Looks up the entry `key` like [`FluentBundle::get_entry`], without
+    /// resolving aliases.
+    ///
+    /// The symbols of the parents are the ones of this bundle, whose
+    /// interner is forked from theirs, while the fallbacks have their own.
+    fn get_own_entry(&self, key: Key) -> Option<(&Self, &Entry)> {
+        if let Some(symbol) = key.symbol {
+            let mut bundle = self;
+            loop {
+                if let Some(entry) = bundle.entries.get(&symbol) {
+                    if !self.is_enabled_in(bundle, symbol) {
+                        break;
+                    }
+                    return Some((bundle, entry));
+                }
+                match bundle.parent.as_deref() {
+                    Some(parent) => bundle = parent,
+                    None => break,
+                }
+            }
+        }
+
+        // The fallbacks are only used for the entries missing from the whole
+        // chain of parents.
+        let mut bundle = self;
+        loop {
+            if let Some(found) = bundle
+                .fallback
+                .as_deref()
+                .and_then(|fallback| fallback.get_own_entry(fallback.key(key.name)))
+            {
+                return Some(found);
+            }
+            bundle = bundle.parent.as_deref()?;
+        }
+    }
+
+    /// Returns `false` if the entry `symbol` of `bundle`, which is this bundle
+    /// or one of its parents, requires a flag which is not enabled in this
+    /// bundle.
+    fn is_enabled_in(&self, bundle: &Self, symbol: Symbol) -> bool {
+        bundle.required_flags.is_empty()
+            || bundle
+                .required_flags
+                .get(&symbol)
+                .map_or(true, |flag| self.enabled_flags.contains(&**flag))
+    }
+
+    /// Creates an overlay of this bundle, which shares its resources and
+    /// functions, and only stores the entries added to it.
+    ///
+    /// Entries of the overlay shadow the ones of the parent with the same
+    /// identifier, without an overriding error, which makes overlays suited
+    /// to per-tenant or per-experiment overrides of a base catalog. The parent
+    /// cannot be changed while it is shared. The locales and the formatting
+    /// options are copied from the parent.
     ///
     /// # Example
     ///
     /// ```
+    /// use std::sync::Arc;
+    ///
     /// use fluent_bundle::{FluentBundle, FluentResource};
     /// use unic_langid::langid;
     ///
-    /// let ftl_string = String::from("hello-world = Hello World!");
-    /// let resource = FluentResource::try_new(ftl_string)
+    /// let base = FluentResource::try_new("hello = Hello!\ngoodbye = Goodbye!".to_string())
+    ///     .expect("Failed to parse an FTL string.");
+    /// let tenant = FluentResource::try_new("hello = Howdy!".to_string())
     ///     .expect("Failed to parse an FTL string.");
     ///
-    /// let langid_en = langid!("en-US");
-    /// let mut bundle = FluentBundle::new(vec![langid_en]);
-    ///
-    /// bundle.add_resource(&resource)
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(base)
     ///     .expect("Failed to add FTL resources to the bundle.");
-    ///
-    /// let msg = bundle.get_message("hello-world")
-    ///     .expect("Failed to retrieve a FluentMessage.");
-    ///
-    /// let pattern = msg.value()
-    ///     .expect("Missing Value.");
-    /// let mut errors = vec![];
-    ///
-    /// let result = bundle.format_pattern(&pattern, None, &mut errors);
-    ///
-    /// assert_eq!(result, "Hello World!");
+    /// let bundle = Arc::new(bundle);
+    ///
+    /// let mut overlay = bundle.overlay();
+    /// overlay.add_resource(tenant)
+    ///     .expect("Failed to add FTL resources to the overlay.");
+    ///
+    /// let format = |bundle: &FluentBundle<_>, id| {
+    ///     let msg = bundle.get_message(id).expect("Message doesn't exist.");
+    ///     let mut errors = vec![];
+    ///     bundle.format_pattern(msg.value().unwrap(), None, &mut errors).to_string()
+    /// };
+    /// assert_eq!(format(&overlay, "hello"), "Howdy!");
+    /// assert_eq!(format(&overlay, "goodbye"), "Goodbye!");
+    /// assert_eq!(format(&bundle, "hello"), "Hello!");
     /// ```
-    pub fn format_pattern<'bundle>(
-        &'bundle self,
-        pattern: &'bundle ast::Pattern<&'bundle str>,
-        args: Option<&FluentArgs>,
-        errors: &mut Vec<FluentError>,
-    ) -> Cow<'bundle, str>
+    pub fn overlay(self: &Arc<Self>) -> Self
     where
-        R: Borrow<FluentResource>,
         M: MemoizerKind,
     {
-        let mut scope = Scope::new(self, args, Some(errors));
-        let value = pattern.resolve(&mut scope);
-        value.into_string(&scope, FormatterPass::Final)
+        let first_locale = self.locales.first().cloned().unwrap_or_default();
+        Self {
+            locales: self.locales.clone(),
+            resources: vec![],
+            overriding: vec![],
+            entries: FxHashMap::default(),
+            interner: self.interner.fork(),
+            intls: M::new(first_locale),
+            locale_intls: self.locales.iter().skip(1).cloned().map(M::new).collect(),
+            use_isolating: self.use_isolating,
+            detect_direction: self.detect_direction,
+            max_depth: self.max_depth,
+            transform: self.transform,
+            formatter: self.formatter,
+            text_patterns: TextPatterns::default(),
+            variant_cache: VariantCache::default(),
+            raw_patterns: RawPatterns::default(),
+            deprecation_hook: self.deprecation_hook,
+            variant_selector: self.variant_selector,
+            unknown_reference_handler: self.unknown_reference_handler.clone(),
+            number_symbols: self.number_symbols.clone(),
+            plural_rules: self.plural_rules.clone(),
+            selector_matcher: self.selector_matcher.clone(),
+            namespace_separator: self.namespace_separator,
+            term_signatures: self.term_signatures.clone(),
+            function_signatures: self.function_signatures.clone(),
+            required_flags: FxHashMap::default(),
+            enabled_flags: self.enabled_flags.clone(),
+            env: Arc::new(RwLock::new(self.env.read().unwrap().clone())),
+            parent: Some(Arc::clone(self)),
+            fallback: None,
+        }
     }
 
-    /// Makes the provided rust function available to messages with the name `id`. See
+    /// Creates a child of this bundle, which inherits its resources,
+    /// functions and configuration, and to which only additional resources
+    /// can be added.
+    ///
+    /// Unlike an [overlay](FluentBundle::overlay), a child cannot shadow the
+    /// entries of its parent, nor change its functions or formatting options,
+    /// which makes children suited to plugins extending the catalog of an
+    /// application. The environment of the builtins is shared with the parent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let app = FluentResource::try_new("hello = Hello!".to_string())
+    ///     .expect("Failed to parse an FTL string.");
+    /// let plugin = FluentResource::try_new("hello = Hijacked!\nplugin = Plugin".to_string())
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(app)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// let bundle = Arc::new(bundle);
+    ///
+    /// let mut child = bundle.child();
+    /// assert!(child.add_resource(plugin).is_err());
+    ///
+    /// let format = |bundle: &FluentBundle<_>, id| {
+    ///     let msg = bundle.get_message(id).expect("Message doesn't exist.");
+    ///     let mut errors = vec![];
+    ///     bundle.format_pattern(msg.value().unwrap(), None, &mut errors).to_string()
+    /// };
+    /// assert_eq!(format(&child, "hello"), "Hello!");
+    /// assert_eq!(format(&child, "plugin"), "Plugin");
+    /// ```
+    pub fn child(self: &Arc<Self>) -> ChildBundle<R, M>
+    where
+        M: MemoizerKind,
+    {
+        let mut bundle = self.overlay();
+        bundle.env = Arc::clone(&self.env);
+        ChildBundle(bundle)
+    }
+
+    /// When formatting patterns, `FluentBundle` inserts
+    /// Unicode Directionality Isolation Marks to indicate
+    /// that the direction of a placeable may differ from
+    /// the surrounding message.
+    ///
+    /// This is important for cases such as when a
+    /// right-to-left user name is presented in the
+    /// left-to-right message.
+    ///
+    /// In some cases, such as testing, the user may want
+    /// to disable the isolating.
+    ///
+    /// Without the `bidi-isolation` feature, the marks are never
+    /// inserted and this setting has no effect.
+    pub fn set_use_isolating(&mut self, value: bool) {
+        self.use_isolating = value;
+    }
+
+    /// When isolating placeables, wraps the string arguments in the
+    /// isolation mark of their dominant direction, LRI or RLI, rather than in
+    /// FSI, which only looks at their first strongly directional character.
+    ///
+    /// See [`detect_direction`](crate::bidi::detect_direction). Other
+    /// placeables are still wrapped in FSI. This is off by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("welcome = Welcome, { $name }!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_detect_direction(true);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("welcome").expect("Message doesn't exist.");
+    /// let mut args = FluentArgs::new();
+    /// args.set("name", "abc مرحبا");
+    /// let mut errors = vec![];
+    /// let value = bundle.format_pattern(msg.value().unwrap(), Some(&args), &mut errors);
+    /// assert_eq!(value, "Welcome, \u{2067}abc مرحبا\u{2069}!");
+    /// ```
+    pub fn set_detect_direction(&mut self, value: bool) {
+        self.detect_direction = value;
+    }
+
+    /// Sets the maximum depth of nested message and term references
+    /// followed while formatting a single pattern.
+    ///
+    /// References nested deeper are not resolved; they are replaced with their
+    /// identifier and a [`ResolverError::TooDeep`](crate::resolver::ResolverError::TooDeep)
+    /// error is reported instead. The default is 100.
+    pub fn set_max_depth(&mut self, depth: usize) {
+        self.max_depth = depth;
+    }
+
+    /// Sets the number of patterns parsed by
+    /// [`FluentBundle::format_raw_pattern`] which the bundle keeps.
+    ///
+    /// Once the bundle keeps that many patterns, each new one replaces the
+    /// least recently formatted one. With a capacity of 0, patterns are parsed
+    /// every time they are formatted. The default is 256.
+    pub fn set_raw_pattern_capacity(&mut self, capacity: usize) {
+        self.raw_patterns.set_capacity(capacity);
+    }
+
+    /// This method allows to specify a function that will
+    /// be called on all textual fragments of the pattern
+    /// during formatting.
+    ///
+    /// This is currently primarily used for pseudolocalization,
+    /// and `fluent-pseudo` crate provides a function
+    /// that can be passed here.
+    pub fn set_transform(&mut self, func: Option<fn(&str) -> Cow<str>>)
+    where
+        R: Borrow<FluentResource>,
+    {
+        self.transform = func;
+
+        // The cached text patterns were transformed with the previous function.
+        self.cache_text_patterns();
+        self.variant_cache.reset();
+    }
+
+    /// Caches the text patterns of all the resources again.
+    fn cache_text_patterns(&mut self)
+    where
+        R: Borrow<FluentResource>,
+    {
+        self.text_patterns = TextPatterns::default();
+        for (res_pos, res) in self.resources.iter().enumerate() {
+            self.text_patterns
+                .insert_resource(res.borrow(), res_pos, self.transform);
+        }
+    }
+
+    /// This method allows to specify a function that will
+    /// be called before any `FluentValue` is formatted
+    /// allowing overrides.
+    ///
+    /// It's particularly useful for plugging in an external
+    /// formatter for `FluentValue::Number`.
+    pub fn set_formatter(
+        &mut self,
+        func: Option<fn(&FluentValue, &M, FormatterPass) -> Option<String>>,
+    ) {
+        self.formatter = func;
+    }
+
+    /// Sets a function which is called with the old and new identifiers
+    /// whenever a message is looked up through a deprecated alias, for
+    /// instance to count the call sites which still need to be migrated.
+    ///
+    /// See [`FluentBundle::add_alias`].
+    pub fn set_deprecation_hook(&mut self, func: Option<fn(&str, &str)>) {
+        self.deprecation_hook = func;
+    }
+
+    /// Sets a function which chooses the value of the messages which have
+    /// candidate values, for instance to run copy experiments from the
+    /// catalog.
+    ///
+    /// The candidates of a message are authored as `.variant-<tag>`
+    /// attributes next to its value. The function is called with the
+    /// identifier of the message and the tags of its candidates, the first of
+    /// which is `default` for the value itself, and returns the index of the
+    /// chosen one. Out of range indices choose the value. Any weighting or
+    /// bucketing of users is left to the function.
+    ///
+    /// The chosen value is returned by [`FluentMessage::value`] and used for
+    /// references to the message, while the `.variant-*` attributes stay
+    /// available as regular attributes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource, VariantTag};
+    ///
+    /// let ftl_string = String::from("
+    /// sign-up = Sign up
+    ///     .variant-short = Join
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// fn choose(_id: &str, tags: &[VariantTag]) -> usize {
+    ///     tags.iter().position(|tag| tag.name == "short").unwrap_or(0)
+    /// }
+    /// bundle.set_variant_selector(Some(choose));
+    ///
+    /// let value = bundle.get_message("sign-up").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// assert_eq!(bundle.format_pattern(value, None, &mut errors), "Join");
+    /// ```
+    pub fn set_variant_selector(&mut self, func: Option<fn(&str, &[VariantTag]) -> usize>) {
+        self.variant_selector = func;
+        self.variant_cache.reset();
+    }
+
+    /// Sets a function which supplies the values of the references to unknown
+    /// messages, terms and variables, for instance by looking up strings in a
+    /// CMS or terms in a database.
+    ///
+    /// The function is called before the error fallback, such as `{ $name }`,
+    /// is written. When it returns a value, the value is used in place of the
+    /// reference and no error is reported. Variables of terms, which are only
+    /// given as arguments of the term reference, are not passed to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::resolver::errors::ReferenceKind;
+    /// use fluent_bundle::{FluentBundle, FluentResource, FluentValue};
+    ///
+    /// let ftl_string = String::from("welcome = Welcome to { -site }, { $user }!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.set_unknown_reference_handler(|reference| match reference {
+    ///     ReferenceKind::Term { id, attribute: None } if id == "site" => {
+    ///         Some(FluentValue::from("Example.com"))
+    ///     }
+    ///     _ => None,
+    /// });
+    ///
+    /// let value = bundle.get_message("welcome").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let result = bundle.format_pattern(value, None, &mut errors);
+    /// assert_eq!(result, "Welcome to Example.com, {$user}!");
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn set_unknown_reference_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&ReferenceKind) -> Option<FluentValue<'static>> + Send + Sync + 'static,
+    {
+        self.unknown_reference_handler = Some(Arc::new(handler));
+    }
+
+    /// Sets the symbols used to format numbers, such as the decimal and group
+    /// separators and the percent sign, which corrects the formatting of
+    /// numbers for the locale of the bundle in builds without the ICU number
+    /// formatters.
+    ///
+    /// With symbols, the integer digits of numbers are grouped and the
+    /// percent style multiplies numbers by 100. Without them, which is the
+    /// default, numbers are written like in Rust. A
+    /// [custom formatter](FluentBundle::set_formatter) still takes precedence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::types::NumberSymbols;
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    ///
+    /// let ftl_string = String::from("
+    /// size = { $size } MB
+    /// done = { NUMBER($ratio, style: \"percent\") }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.add_builtins()
+    ///     .expect("Failed to add builtin functions to the bundle.");
+    ///
+    /// bundle.set_number_symbols(Some(NumberSymbols {
+    ///     decimal: ",".to_string(),
+    ///     group: ".".to_string(),
+    ///     percent: "\u{a0}%".to_string(),
+    ///     ..Default::default()
+    /// }));
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("size", 1234.5);
+    /// args.set("ratio", 0.25);
+    /// let mut errors = vec![];
+    /// let size = bundle.get_message("size").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// assert_eq!(bundle.format_pattern(size, Some(&args), &mut errors), "1.234,5 MB");
+    /// let done = bundle.get_message("done").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// assert_eq!(bundle.format_pattern(done, Some(&args), &mut errors), "25\u{a0}%");
+    /// ```
+    pub fn set_number_symbols(&mut self, symbols: Option<NumberSymbols>) {
+        self.number_symbols = symbols;
+    }
+
+    /// Sets the backend which resolves the plural categories of numbers, such
+    /// as `one` or `few`, when they are matched against the keys of select
+    /// expressions.
+    ///
+    /// This replaces the data of `intl_pluralrules`, for instance with the
+    /// plural rules of `icu_plurals` in applications which already ship
+    /// ICU4X data, which can then be built without the `plural-rules`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::types::{FluentNumber, PluralCategory, PluralRulesBackend};
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::{langid, LanguageIdentifier};
+    ///
+    /// struct Dual;
+    ///
+    /// impl PluralRulesBackend for Dual {
+    ///     fn category(
+    ///         &self,
+    ///         _locale: &LanguageIdentifier,
+    ///         number: &FluentNumber,
+    ///     ) -> Option<PluralCategory> {
+    ///         Some(match number.value {
+    ///             n if n == 1.0 => PluralCategory::One,
+    ///             n if n == 2.0 => PluralCategory::Two,
+    ///             _ => PluralCategory::Other,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let ftl_string = String::from("
+    /// eyes = { $count ->
+    ///     [one] One eye
+    ///     [two] A pair of eyes
+    ///    *[other] { $count } eyes
+    /// }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.set_plural_rules(Dual);
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("count", 2);
+    /// let value = bundle.get_message("eyes").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// assert_eq!(bundle.format_pattern(value, Some(&args), &mut errors), "A pair of eyes");
+    /// ```
+    pub fn set_plural_rules<B>(&mut self, backend: B)
+    where
+        B: PluralRulesBackend + 'static,
+    {
+        self.plural_rules = Some(Arc::new(backend));
+        self.variant_cache.reset();
+    }
+
+    /// Sets a function which decides whether the selector of a select
+    /// expression matches the key of a variant, for instance to match
+    /// strings regardless of their case, or values of custom types against
+    /// keys.
+    ///
+    /// The function is called with the selector and the key of each variant
+    /// in turn, and the first variant for which it returns `Some(true)` is
+    /// chosen. When it returns `None`, the default matching applies: equal
+    /// strings and numbers, and plural categories. The default variant is
+    /// chosen when no variant matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+    ///
+    /// let ftl_string = String::from("
+    /// greeting = { $tone ->
+    ///     [formal] Good evening.
+    ///    *[casual] Hi!
+    /// }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.set_selector_matcher(|selector, key| match (selector, key) {
+    ///     (FluentValue::String(selector), FluentValue::String(key)) => {
+    ///         Some(selector.eq_ignore_ascii_case(key))
+    ///     }
+    ///     _ => None,
+    /// });
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("tone", "FORMAL");
+    /// let value = bundle.get_message("greeting").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// assert_eq!(bundle.format_pattern(value, Some(&args), &mut errors), "Good evening.");
+    /// ```
+    pub fn set_selector_matcher<F>(&mut self, matcher: F)
+    where
+        F: Fn(&FluentValue, &FluentValue) -> Option<bool> + Send + Sync + 'static,
+    {
+        self.selector_matcher = Some(Arc::new(matcher));
+        self.variant_cache.reset();
+    }
+
+    /// Sets the value of `key` returned by the builtin `ENV("key")` function.
+    ///
+    /// The `platform` key also overrides the value of the builtin `PLATFORM()`
+    /// function, which otherwise is the current operating system, as one of
+    /// `android`, `linux`, `macos`, `windows` or `other`. See
+    /// [`FluentBundle::add_builtins_extended`].
+    ///
+    /// An overlay starts with a copy of the values of its parent; the builtins
+    /// registered in the parent keep reading the values of the parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from(r#"
+    /// settings = { PLATFORM() ->
+    ///     [macos] Preferences…
+    ///    *[other] Options…
+    /// }
+    /// channel = { ENV("channel") }
+    /// "#);
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.add_builtins_extended().expect("Failed to add builtins to the bundle.");
+    /// bundle.set_use_isolating(false);
+    ///
+    /// bundle.set_env("platform", "macos");
+    /// bundle.set_env("channel", "nightly");
+    ///
+    /// let mut errors = vec![];
+    /// let msg = bundle.get_message("settings").expect("Message doesn't exist.");
+    /// let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    /// assert_eq!(value, "Preferences…");
+    ///
+    /// let msg = bundle.get_message("channel").expect("Message doesn't exist.");
+    /// let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    /// assert_eq!(value, "nightly");
+    /// ```
+    pub fn set_env(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.env.write().unwrap().insert(key.into(), value.into());
+    }
+
+    /// Returns true if this bundle contains a message with the given id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello = Hi!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// assert_eq!(true, bundle.has_message("hello"));
+    ///
+    /// ```
+    pub fn has_message(&self, id: &str) -> bool
+    where
+        R: Borrow<FluentResource>,
+    {
+        self.get_entry_message(id).is_some()
+    }
+
+    /// Retrieves a `FluentMessage` from a bundle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello-world = Hello World!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    ///
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("hello-world");
+    /// assert_eq!(msg.is_some(), true);
+    /// ```
+    pub fn get_message<'l>(&'l self, id: &str) -> Option<FluentMessage<'l>>
+    where
+        R: Borrow<FluentResource>,
+    {
+        self.get_entry_message(id)
+            .map(|msg| FluentMessage::with_variant(msg, self.variant_selector))
+    }
+
+    /// Formats the attribute `attribute` of the term `id`, with or without its
+    /// leading `-`, such as the grammatical gender of a product name.
+    ///
+    /// Term attributes cannot be referenced from messages, but they can be
+    /// selected on, which lets translations make adjectives and articles
+    /// agree with the term, while the application queries the same metadata
+    /// with this method. Resolution errors are ignored, since such attributes
+    /// are usually plain text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    ///
+    /// let ftl_string = String::from("
+    /// -app = Encyclopédie
+    ///     .gender = feminine
+    /// update = { -app.gender ->
+    ///     [feminine] La nouvelle { -app } est prête.
+    ///    *[other] Le nouveau { -app } est prêt.
+    /// }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// assert_eq!(bundle.get_term_attribute("-app", "gender").as_deref(), Some("feminine"));
+    /// assert_eq!(bundle.get_term_attribute("-app", "plural"), None);
+    /// ```
+    pub fn get_term_attribute(&self, id: &str, attribute: &str) -> Option<Cow<'_, str>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let id = id.strip_prefix('-').unwrap_or(id);
+        let attr = self
+            .get_entry_term(id)?
+            .attributes
+            .iter()
+            .find(|attr| attr.id.name == attribute)?;
+        let mut errors = vec![];
+        Some(self.format_pattern(&attr.value, None, &mut errors))
+    }
+
+    /// Writes a formatted pattern which comes from a `FluentMessage` into any
+    /// [`fmt::Write`] sink.
+    ///
+    /// The output is the same as the one of [`FluentBundle::format_pattern`],
+    /// but the pattern is written piece by piece instead of being collected
+    /// into a new `String`, so that a buffer can be reused across calls. When a
+    /// [`formatter`](FluentBundle::set_formatter) is set, the pattern is
+    /// formatted into a `String` first, since the formatter sees the final
+    /// value as a whole.
+    ///
+    /// Resolution errors are appended to `errors`, which is a `Vec` or any
+    /// other [`ErrorSink`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello-world = Hello World!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    ///
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("hello-world")
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    ///
+    /// let pattern = msg.value()
+    ///     .expect("Missing Value.");
+    /// let mut errors = vec![];
+    ///
+    /// let mut s = String::new();
+    /// bundle.write_pattern(&mut s, &pattern, None, &mut errors)
+    ///     .expect("Failed to write.");
+    ///
+    /// assert_eq!(s, "Hello World!");
+    ///
+    /// // The buffer keeps its allocation for the next pattern.
+    /// s.clear();
+    /// bundle.write_pattern(&mut s, &pattern, None, &mut errors)
+    ///     .expect("Failed to write.");
+    /// assert_eq!(s, "Hello World!");
+    /// ```
+    pub fn write_pattern<'bundle, W, E>(
+        &'bundle self,
+        w: &mut W,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> fmt::Result
+    where
+        R: Borrow<FluentResource>,
+        W: fmt::Write,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        if let Some(text) = self.get_text_pattern(pattern) {
+            return w.write_str(text);
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("fluent_bundle::format_pattern").entered();
+        let mut scope = Scope::with_error_sink(self, args, Some(errors));
+        if self.formatter.is_some() {
+            let value = pattern.resolve(&mut scope);
+            return w.write_str(&value.into_string(&scope, FormatterPass::Final));
+        }
+        pattern.write(w, &mut scope)
+    }
+
+    /// Formats a pattern which comes from a `FluentMessage`.
+    ///
+    /// Resolution errors are appended to `errors`, which is a `Vec` or any
+    /// other [`ErrorSink`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello-world = Hello World!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    ///
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("hello-world")
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    ///
+    /// let pattern = msg.value()
+    ///     .expect("Missing Value.");
+    /// let mut errors = vec![];
+    ///
+    /// let result = bundle.format_pattern(&pattern, None, &mut errors);
+    ///
+    /// assert_eq!(result, "Hello World!");
+    /// ```
+    pub fn format_pattern<'bundle, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> Cow<'bundle, str>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        if let Some(text) = self.get_text_pattern(pattern) {
+            return Cow::Borrowed(text);
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("fluent_bundle::format_pattern").entered();
+        let mut scope = Scope::with_error_sink(self, args, Some(errors));
+        let value = pattern.resolve(&mut scope);
+        value.into_string(&scope, FormatterPass::Final)
+    }
+
+    /// Formats a pattern like [`FluentBundle::format_pattern`], but fails
+    /// instead of falling back when a placeable can't be resolved, such as
+    /// when a variable, message or function is missing.
+    ///
+    /// No partial output is returned: if any error occurs, all the errors of
+    /// the pattern are returned instead of the formatted string. This suits
+    /// builds and tests which must only ship complete translations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello = Hello, { $user }!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let pattern = bundle.get_message("hello")
+    ///     .and_then(|msg| msg.value())
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("user", "Anna");
+    /// assert_eq!(
+    ///     bundle.format_pattern_strict(pattern, Some(&args)).as_deref(),
+    ///     Ok("Hello, Anna!")
+    /// );
+    ///
+    /// let errors = bundle.format_pattern_strict(pattern, None).unwrap_err();
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn format_pattern_strict<'bundle>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+    ) -> Result<Cow<'bundle, str>, Vec<FluentError>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let mut errors = Vec::new();
+        let value = self.format_pattern(pattern, args, &mut errors);
+        if errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Formats a pattern like [`FluentBundle::format_pattern`], awaiting the calls to the
+    /// functions added with [`FluentBundle::add_async_function`].
+    ///
+    /// The pattern is resolved in passes: each pass starts the calls whose results are
+    /// not known yet, which are then awaited one after the other, and the pattern is
+    /// resolved again with their results, until a pass makes no new call. Calls with the
+    /// same function and arguments are only awaited once. The arguments are copied, so
+    /// that the future is [`Send`] for bundles which are [`Sync`], such as
+    /// [concurrent bundles](FluentBundle::new_concurrent), and sink types which are
+    /// [`Send`].
+    ///
+    /// Only the errors of the last pass are reported. Async functions called with
+    /// [`FluentBundle::format_pattern`] fail with a
+    /// [`ResolverError::Function`](crate::resolver::ResolverError::Function).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("welcome = Welcome back, { USERNAME($id) }!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.add_async_function("USERNAME", |positional, _named, _ctx| async move {
+    ///     // Such as a lookup in an async cache.
+    ///     match positional.first() {
+    ///         Some(FluentValue::Number(n)) if n.value == 7.0 => Ok("Anna".into()),
+    ///         _ => Ok("stranger".into()),
+    ///     }
+    /// }).expect("Failed to add a function to the bundle.");
+    ///
+    /// let pattern = bundle.get_message("welcome").and_then(|msg| msg.value())
+    ///     .expect("Message doesn't exist.");
+    /// let mut args = FluentArgs::new();
+    /// args.set("id", 7);
+    /// let mut errors = vec![];
+    /// let future = bundle.format_pattern_async(pattern, Some(&args), &mut errors);
+    /// assert_eq!(futures::executor::block_on(future), "Welcome back, Anna!");
+    /// ```
+    pub fn format_pattern_async<'bundle, 'errors, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &'errors mut E,
+    ) -> impl Future<Output = Cow<'bundle, str>> + 'errors
+    where
+        'bundle: 'errors,
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        let args: Option<FluentArgs<'static>> = args.map(|args| {
+            args.iter()
+                .map(|(key, value)| (key.to_string(), value.into_owned()))
+                .collect()
+        });
+        async move {
+            let calls = AsyncCalls::default();
+            for _ in 0..MAX_ASYNC_PASSES {
+                let mut pass_errors = vec![];
+                let value =
+                    self.format_with_async_calls(pattern, args.as_ref(), &calls, &mut pass_errors);
+                if !calls.has_pending() {
+                    for error in pass_errors {
+                        errors.push_error(error);
+                    }
+                    return value;
+                }
+                calls.resolve().await;
+            }
+            let value = self.format_with_async_calls(pattern, args.as_ref(), &calls, errors);
+            for id in calls.unresolved() {
+                errors.push_error(
+                    ResolverError::Function {
+                        id,
+                        error: FluentFunctionError::new("The call was not awaited"),
+                    }
+                    .into(),
+                );
+            }
+            value
+        }
+    }
+
+    /// Formats a pattern with the results of the awaited async calls, and
+    /// starts the others.
+    fn format_with_async_calls<'bundle, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        calls: &AsyncCalls,
+        errors: &mut E,
+    ) -> Cow<'bundle, str>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        if let Some(text) = self.get_text_pattern(pattern) {
+            return Cow::Borrowed(text);
+        }
+        let mut scope = Scope::with_error_sink(self, args, Some(errors)).with_async_calls(calls);
+        let value = pattern.resolve(&mut scope);
+        value.into_string(&scope, FormatterPass::Final)
+    }
+
+    /// Formats a pattern which comes from a `FluentMessage` into a sequence of
+    /// parts, so that user interfaces can style the values of placeables
+    /// differently from the text around them.
+    ///
+    /// The text and the placeables of the selected variants of select
+    /// expressions are parts of their own, while referenced messages and terms
+    /// are formatted into a single [`FormattedPart::Placeable`]. The
+    /// [formatter](FluentBundle::set_formatter) only formats the values of the
+    /// placeables, since there is no final string.
+    ///
+    /// Resolution errors are appended to `errors`, which is a `Vec` or any
+    /// other [`ErrorSink`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::bundle::FormattedPart;
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello = Hello, { $user }!");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("hello")
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    /// let mut args = FluentArgs::new();
+    /// args.set("user", "Anna");
+    /// let mut errors = vec![];
+    ///
+    /// let parts = bundle.format_pattern_to_parts(msg.value().unwrap(), Some(&args), &mut errors);
+    /// let styled: String = parts
+    ///     .iter()
+    ///     .map(|part| match part {
+    ///         FormattedPart::Placeable { value, .. } => format!("<b>{}</b>", value),
+    ///         part => part.to_string(),
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(styled, "Hello, <b>Anna</b>!");
+    /// ```
+    pub fn format_pattern_to_parts<'bundle, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> Vec<FormattedPart<'bundle>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("fluent_bundle::format_pattern").entered();
+        let mut scope = Scope::with_error_sink(self, args, Some(errors));
+        let mut parts = Vec::new();
+        scope.write_parts(pattern, &mut parts);
+        parts
+    }
+
+    /// Formats a pattern like [`FluentBundle::format_pattern`], with the
+    /// plural rules and number formatting of one of the locales of the bundle
+    /// rather than of the first one.
+    ///
+    /// The locale used is the one of the bundle equal to `locale`, or else
+    /// the first one of the same language, or else the first locale of the
+    /// bundle, which is also used if `locale` is `None`. It is returned along
+    /// with the formatted pattern, see [`FormattedPattern::resolved_locale`].
+    /// The functions added by [`FluentBundle::add_builtins_extended`] keep
+    /// using the first locale.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("price = { $amount }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("pt-PT"), langid!("pt-BR")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("price")
+    ///     .expect("Failed to retrieve a FluentMessage.");
+    /// let mut args = FluentArgs::new();
+    /// args.set("amount", 5);
+    /// let mut errors = vec![];
+    ///
+    /// let brazil = langid!("pt-BR");
+    /// let result = bundle.format_pattern_in_locale(
+    ///     msg.value().unwrap(),
+    ///     Some(&args),
+    ///     Some(&brazil),
+    ///     &mut errors,
+    /// );
+    /// assert_eq!(result.resolved_locale(), Some(&brazil));
+    ///
+    /// let result = bundle.format_pattern_in_locale(
+    ///     msg.value().unwrap(),
+    ///     Some(&args),
+    ///     Some(&langid!("pt-AO")),
+    ///     &mut errors,
+    /// );
+    /// assert_eq!(result.resolved_locale(), Some(&langid!("pt-PT")));
+    /// ```
+    pub fn format_pattern_in_locale<'bundle, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        locale: Option<&LanguageIdentifier>,
+        errors: &mut E,
+    ) -> FormattedPattern<'bundle>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        let index = self.locale_index(locale);
+        let locale = self.locales.get(index);
+        if let Some(text) = self.get_text_pattern(pattern) {
+            return FormattedPattern {
+                value: Cow::Borrowed(text),
+                locale,
+            };
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("fluent_bundle::format_pattern").entered();
+        let mut scope = Scope::with_error_sink(self, args, Some(errors)).in_locale(index);
+        let value = pattern.resolve(&mut scope);
+        FormattedPattern {
+            value: value.into_string(&scope, FormatterPass::Final),
+            locale,
+        }
+    }
+
+    /// Returns the index of the locale to format in for `locale`, see
+    /// [`FluentBundle::format_pattern_in_locale`].
+    fn locale_index(&self, locale: Option<&LanguageIdentifier>) -> usize {
+        let Some(locale) = locale else {
+            return 0;
+        };
+        // The locales added to the bundle after it was created have no
+        // memoizer.
+        let count = self.locales.len().min(self.locale_intls.len() + 1);
+        let locales = &self.locales[..count];
+        locales
+            .iter()
+            .position(|candidate| candidate == locale)
+            .or_else(|| {
+                locales
+                    .iter()
+                    .position(|candidate| candidate.language == locale.language)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Formats a pattern given as a string rather than as a message of a
+    /// resource, such as a template configured by a user.
+    ///
+    /// The whole string is the pattern, as parsed by
+    /// [`parse_pattern`](fluent_syntax::parser::parse_pattern): its text is
+    /// kept as is, including blank and indented lines, and lines starting with
+    /// `.`, `[` or `*`. The pattern may reference the terms, messages and
+    /// functions of the bundle.
+    ///
+    /// The pattern is parsed the first time it is formatted, and kept by the
+    /// bundle for the next times, up to the capacity set with
+    /// [`FluentBundle::set_raw_pattern_capacity`].
+    ///
+    /// If the pattern could not be parsed, the syntax error is appended to
+    /// `errors`, with its position in `source`, and the pattern is returned as
+    /// is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("-brand = Fluent");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let mut args = FluentArgs::new();
+    /// args.set("name", "Anna");
+    /// let mut errors = vec![];
+    /// let value = bundle.format_raw_pattern("{ -brand } says hello, { $name }!", Some(&args), &mut errors);
+    /// assert_eq!(value, "Fluent says hello, Anna!");
+    /// ```
+    pub fn format_raw_pattern<E>(
+        &self,
+        source: &str,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> String
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+        E: ErrorSink,
+    {
+        let raw = self.raw_patterns.get_or_parse(source);
+        match raw.borrow_dependent() {
+            Ok(pattern) => self.format_pattern(pattern, args, errors).into_owned(),
+            Err(err) => {
+                errors.push_error(FluentError::ParserError(err.clone()));
+                source.to_string()
+            }
+        }
+    }
+
+    fn get_text_pattern(&self, pattern: &ast::Pattern<&str>) -> Option<&str>
+    where
+        R: Borrow<FluentResource>,
+    {
+        self.text_patterns.get(&self.resources, pattern)
+    }
+
+    /// Returns the number of entries in the bundle, and an estimate of the
+    /// memory it uses.
+    ///
+    /// The estimate covers the sources and top-level entries of the resources,
+    /// the identifiers and caches of the bundle, and the memoized formatters.
+    /// Resources shared with other bundles are counted in full by each of
+    /// them, and the entries of the parent of an [`overlay`](Self::overlay)
+    /// are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("hello = Hi!\n-brand = Firefox");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let stats = bundle.memory_stats();
+    /// assert_eq!(stats.messages, 1);
+    /// assert_eq!(stats.terms, 1);
+    /// assert_eq!(stats.resource_size, 28);
+    /// assert!(stats.heap_size > stats.resource_size);
+    /// ```
+    pub fn memory_stats(&self) -> BundleMemoryStats
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let mut stats = BundleMemoryStats {
+            resources: self.resources.len(),
+            intls: self.intls.memory_stats(),
+            ..Default::default()
+        };
+        for entry in self.entries.values() {
+            match entry {
+                Entry::Message(_) => stats.messages += 1,
+                Entry::Term(_) => stats.terms += 1,
+                Entry::Function(_) | Entry::AsyncFunction(_) => stats.functions += 1,
+                Entry::Alias(_) => stats.aliases += 1,
+            }
+        }
+        stats.resource_size = self
+            .resources
+            .iter()
+            .map(|res| res.borrow().source().len())
+            .sum();
+        let (raw_patterns, raw_patterns_size) = self.raw_patterns.stats();
+        stats.raw_patterns = raw_patterns;
+
+        stats.heap_size = self
+            .resources
+            .iter()
+            .map(|res| res.borrow().heap_size())
+            .sum::<usize>()
+            + self.resources.capacity() * std::mem::size_of::<R>()
+            + self.entries.capacity() * std::mem::size_of::<(Symbol, Entry)>()
+            + self.interner.heap_size()
+            + self.text_patterns.heap_size()
+            + self.variant_cache.heap_size()
+            + raw_patterns_size
+            + stats.intls.heap_size;
+        stats
+    }
+
+    /// Returns the counts of the messages, terms and translatable content of
+    /// the entries of this bundle, for instance for translation dashboards and
+    /// cost estimates.
+    ///
+    /// Entries rejected or overridden when adding resources are not counted,
+    /// nor are the entries of the parents of an overlay.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    ///
+    /// let ftl_string = String::from("
+    /// emails = { $count ->
+    ///     [one] One new email
+    ///    *[other] { $count } new emails
+    /// }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::default();
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let stats = bundle.stats();
+    /// assert_eq!(stats.messages, 1);
+    /// assert_eq!(stats.words, 5);
+    /// assert_eq!(stats.placeables, 2);
+    /// assert_eq!(stats.select_expressions, 1);
+    /// ```
+    pub fn stats(&self) -> CatalogStats
+    where
+        R: Borrow<FluentResource>,
+    {
+        let mut stats = CatalogStats::default();
+        for entry in self.entries.values() {
+            if let Entry::Message((res_pos, entry_pos)) | Entry::Term((res_pos, entry_pos)) = entry
+            {
+                if let Some(entry) = self.resources[*res_pos].borrow().get_entry(*entry_pos) {
+                    stats.add_entry(entry);
+                }
+            }
+        }
+        stats
+    }
+
+    /// Makes the message `new_id` available under its former identifier
+    /// `old_id`, so that the call sites and references which still use
+    /// `old_id` keep resolving after a rename.
+    ///
+    /// Every lookup through the alias calls the
+    /// [deprecation hook](FluentBundle::set_deprecation_hook), and references
+    /// to it from other messages also report a
+    /// [`ResolverError::Deprecated`](crate::resolver::ResolverError::Deprecated).
+    /// The target does not need to exist yet. Aliases of aliases are not
+    /// followed.
+    ///
+    /// A message can also be marked as an alias in FTL, with an annotation in
+    /// the comment right above it; its own value is then ignored:
+    ///
+    /// ```ftl
+    /// # fluent: deprecated-alias-of new-id
+    /// old-id = Old value
+    /// ```
+    ///
+    /// The annotation can also be written as `# fluent: deprecated-alias-of=new-id`,
+    /// or as the [annotation](fluent_syntax::ast::annotation)
+    /// `# .deprecated-alias-of: new-id` of `fluent-syntax`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FluentError::Overriding`] if an entry named `old_id`
+    /// already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("welcome = Welcome!\nintro = { greeting }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    /// bundle.add_alias("greeting", "welcome")
+    ///     .expect("Failed to add an alias.");
+    ///
+    /// let msg = bundle.get_message("intro").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    /// assert_eq!(value, "Welcome!");
+    /// assert_eq!(
+    ///     errors[0].to_string(),
+    ///     "Resolver error: Deprecated message: greeting, use welcome instead"
+    /// );
+    /// ```
+    pub fn add_alias(&mut self, old_id: &str, new_id: &str) -> Result<(), FluentError> {
+        self.insert_entry(old_id, Entry::Alias(new_id.into()), false)
+            .map_err(|_| FluentError::Overriding {
+                kind: EntryKind::Message,
+                id: old_id.to_owned(),
+            })
+    }
+
+    /// Makes the provided rust function available to messages with the name `id`. See
     /// the [FTL syntax guide] to learn how these are used in messages.
     ///
-    /// FTL functions accept both positional and named args. The rust function you
-    /// provide therefore has two parameters: a slice of values for the positional
-    /// args, and a `FluentArgs` for named args.
+    /// FTL functions accept both positional and named args. The rust function you
+    /// provide therefore has two parameters: a slice of values for the positional
+    /// args, and a `FluentArgs` for named args.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource, FluentValue};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("length = { STRLEN(\"12345\") }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Could not parse an FTL string.");
+    /// let langid_en = langid!("en-US");
+    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// // Register a fn that maps from string to string length
+    /// bundle.add_function("STRLEN", |positional, _named| match positional {
+    ///     [FluentValue::String(str)] => str.len().into(),
+    ///     _ => FluentValue::Error,
+    /// }).expect("Failed to add a function to the bundle.");
+    ///
+    /// let msg = bundle.get_message("length").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let value = bundle.format_pattern(&pattern, None, &mut errors);
+    /// assert_eq!(&value, "5");
+    /// ```
+    ///
+    /// [FTL syntax guide]: https://projectfluent.org/fluent/guide/functions.html
+    pub fn add_function<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
+    {
+        self.add_function_with_ctx(id, move |positional, named, _ctx| func(positional, named))
+    }
+
+    /// Makes the provided rust function available to messages with the name `id`, like
+    /// [`FluentBundle::add_function`], and passes it a [`FunctionContext`] with the
+    /// locales and the memoizer of the bundle.
+    ///
+    /// The context lets functions format in the locale of the bundle, or in the one
+    /// passed to [`FluentBundle::format_pattern_in_locale`], with formatters memoized
+    /// by the bundle, instead of capturing a locale when they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource, FluentValue};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("lang = { LANG() }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Could not parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("de-CH"), langid!("de")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.add_function_with_ctx("LANG", |_positional, _named, ctx| {
+    ///     match ctx.locale() {
+    ///         Some(locale) => locale.language.as_str().to_string().into(),
+    ///         None => FluentValue::Error,
+    ///     }
+    /// }).expect("Failed to add a function to the bundle.");
+    ///
+    /// let msg = bundle.get_message("lang").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let value = bundle.format_pattern(&pattern, None, &mut errors);
+    /// assert_eq!(&value, "de");
+    /// ```
+    pub fn add_function_with_ctx<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs, &FunctionContext) -> FluentValue<'a>
+            + Sync
+            + Send
+            + 'static,
+    {
+        self.add_fallible_function(id, move |positional, named, ctx| {
+            Ok(func(positional, named, ctx))
+        })
+    }
+
+    /// Makes the provided rust function available to messages with the name `id`, like
+    /// [`FluentBundle::add_function_with_ctx`], for functions which can fail.
+    ///
+    /// When the function returns an error, the call is formatted as `{ ID() }` and a
+    /// [`ResolverError::Function`](crate::resolver::ResolverError::Function) with the name
+    /// of the function and the [`FluentFunctionError`] is reported in the errors of the
+    /// formatting, where returning [`FluentValue::Error`] gives no detail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::resolver::ResolverError;
+    /// use fluent_bundle::{FluentBundle, FluentError, FluentFunctionError, FluentResource, FluentValue};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("share = { PERCENT(\"half\") }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Could not parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.add_fallible_function("PERCENT", |positional, _named, _ctx| match positional {
+    ///     [FluentValue::Number(n)] => Ok(format!("{}%", n.value * 100.0).into()),
+    ///     [_] => Err(FluentFunctionError::argument("0", "expected a number")),
+    ///     _ => Err(FluentFunctionError::new("expected one argument")),
+    /// }).expect("Failed to add a function to the bundle.");
+    ///
+    /// let msg = bundle.get_message("share").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let value = bundle.format_pattern(&pattern, None, &mut errors);
+    /// assert_eq!(&value, "{PERCENT()}");
+    /// assert_eq!(
+    ///     errors[0].to_string(),
+    ///     "Resolver error: Function PERCENT() failed: Invalid argument 0: expected a number"
+    /// );
+    /// ```
+    pub fn add_fallible_function<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(
+                &[FluentValue<'a>],
+                &FluentArgs,
+                &FunctionContext,
+            ) -> Result<FluentValue<'a>, FluentFunctionError>
+            + Sync
+            + Send
+            + 'static,
+    {
+        self.insert_entry(id, Entry::Function(Box::new(func)), false)
+            .map_err(|_| FluentError::Overriding {
+                kind: EntryKind::Function,
+                id: id.to_owned(),
+            })
+    }
+
+    /// Makes the provided async rust function available to messages with the name `id`,
+    /// for patterns formatted with [`FluentBundle::format_pattern_async`], such as
+    /// functions which look values up in async caches.
+    ///
+    /// The function is called with copies of the arguments and the
+    /// [`FunctionContext`] of the bundle, and returns a future which must not borrow
+    /// the context. See [`FluentBundle::format_pattern_async`] for an example.
+    pub fn add_async_function<F, Fut>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: Fn(Vec<FluentValue<'static>>, FluentArgs<'static>, &FunctionContext) -> Fut
+            + Sync
+            + Send
+            + 'static,
+        Fut: Future<Output = Result<FluentValue<'static>, FluentFunctionError>> + Send + 'static,
+    {
+        let func = move |positional, named, ctx: &FunctionContext| -> AsyncFunctionFuture {
+            Box::pin(func(positional, named, ctx))
+        };
+        self.insert_entry(id, Entry::AsyncFunction(Box::new(func)), false)
+            .map_err(|_| FluentError::Overriding {
+                kind: EntryKind::Function,
+                id: id.to_owned(),
+            })
+    }
+
+    /// Makes the provided rust function available to messages with the name `id` in the
+    /// namespace `ns`, as in `ICU.DATETIME()`, see [`FluentBundle::add_function`].
+    ///
+    /// Namespaces keep the functions of plugins from colliding with, or shadowing, the
+    /// builtins and the functions of the application. The function is registered as
+    /// `ns` and `id` joined by the [namespace separator](FluentBundle::set_namespace_separator),
+    /// a `.` by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource, FluentValue};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("length = { TEXT.LEN(\"12345\") }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Could not parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.add_function_ns("TEXT", "LEN", |positional, _named| match positional {
+    ///     [FluentValue::String(str)] => str.len().into(),
+    ///     _ => FluentValue::Error,
+    /// }).expect("Failed to add a function to the bundle.");
+    ///
+    /// let msg = bundle.get_message("length").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let pattern = msg.value().expect("Message has no value.");
+    /// let value = bundle.format_pattern(&pattern, None, &mut errors);
+    /// assert_eq!(&value, "5");
+    /// ```
+    pub fn add_function_ns<F>(&mut self, ns: &str, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
+    {
+        let id = format!("{}{}{}", ns, self.namespace_separator, id);
+        self.add_function(&id, func)
+    }
+
+    /// Sets the separator between the namespace and the name of the functions added with
+    /// [`FluentBundle::add_function_ns`] afterwards.
+    ///
+    /// FTL only allows `.` between a namespace and a name in function calls, besides the
+    /// `_` and `-` which are valid in a single function name, as in `ICU_DATETIME()`.
+    pub fn set_namespace_separator(&mut self, separator: &'static str) {
+        self.namespace_separator = separator;
+    }
+
+    /// Sets the flags which enable the entries annotated as requiring them, with
+    /// an annotation in the comment right above them:
+    ///
+    /// ```ftl
+    /// # fluent: requires-flag=beta-ui
+    /// sidebar-title = Sidebar (beta)
+    /// ```
+    ///
+    /// The annotation can also be written as `# fluent: requires-flag beta-ui`,
+    /// or as the [annotation](fluent_syntax::ast::annotation)
+    /// `# .requires-flag: beta-ui` of `fluent-syntax`. The annotations are read
+    /// once, when the resource is created.
+    ///
+    /// The entries whose flag is not enabled are hidden, as if they were
+    /// missing: [`get_message`](FluentBundle::get_message) and
+    /// [`has_message`](FluentBundle::has_message) do not find them, and
+    /// references to them are unknown. No flag is enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// ## fluent: requires-flag=beta-ui
+    /// sidebar-title = Sidebar
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// assert_eq!(bundle.required_flag("sidebar-title"), Some("beta-ui"));
+    /// assert!(!bundle.has_message("sidebar-title"));
     ///
-    /// # Examples
+    /// bundle.set_enabled_flags(["beta-ui"]);
+    /// assert!(bundle.has_message("sidebar-title"));
+    /// ```
+    pub fn set_enabled_flags<I, S>(&mut self, flags: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.enabled_flags = flags.into_iter().map(Into::into).collect();
+    }
+
+    /// Returns the flag required by the entry `id`, whether it is enabled or
+    /// not, see [`FluentBundle::set_enabled_flags`].
+    pub fn required_flag(&self, id: &str) -> Option<&str> {
+        let symbol = self.interner.get(id)?;
+        let mut bundle = self;
+        loop {
+            if bundle.entries.contains_key(&symbol) {
+                return bundle.required_flags.get(&symbol).map(AsRef::as_ref);
+            }
+            bundle = bundle.parent.as_deref()?;
+        }
+    }
+
+    /// Declares the parameters which the term `id` accepts, such as the
+    /// grammatical cases of `{ -brand(case: "genitive") }`.
+    ///
+    /// The resources added to the bundle are checked against the signature:
+    /// the term must have a variant for each value of the parameters it
+    /// selects on, and references to the term must only pass declared
+    /// parameters and values. Mismatches are reported as
+    /// [`FluentError::TermSignature`] errors by
+    /// [`add_resource`](FluentBundle::add_resource), and by this method for
+    /// the resources already added. Like overriding errors, they do not
+    /// prevent the entries from being added.
+    ///
+    /// The resources added with
+    /// [`add_resource_overriding`](FluentBundle::add_resource_overriding),
+    /// which reports no errors, are not checked. Checking a lazy resource
+    /// parses all of its entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::validate::{TermSignature, TermSignatureError};
+    /// use fluent_bundle::{FluentBundle, FluentError, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from(r#"
+    /// -brand = { $case ->
+    ///    *[nominative] Firefox
+    ///     [genitive] Firefoksa
+    /// }
+    /// about = O { -brand(case: "locative") }
+    /// "#);
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("pl")]);
+    /// bundle.add_term_signature(
+    ///     "brand",
+    ///     TermSignature::new().param("case", ["nominative", "genitive", "locative"]),
+    /// ).expect("No resources to check.");
+    ///
+    /// let errors = bundle.add_resource(&resource).unwrap_err();
+    /// assert_eq!(
+    ///     errors,
+    ///     [FluentError::TermSignature(TermSignatureError::MissingVariant {
+    ///         term: "brand".to_string(),
+    ///         param: "case".to_string(),
+    ///         value: "locative".to_string(),
+    ///     })]
+    /// );
+    /// ```
+    pub fn add_term_signature(
+        &mut self,
+        id: &str,
+        signature: TermSignature,
+    ) -> Result<(), Vec<FluentError>>
+    where
+        R: Borrow<FluentResource>,
+    {
+        let mut signatures = FxHashMap::default();
+        signatures.insert(id.to_string(), signature.clone());
+        let mut errors = vec![];
+        for res in &self.resources {
+            check_term_signatures(res.borrow(), &signatures, &mut errors);
+        }
+        self.term_signatures.insert(id.to_string(), signature);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Sets the signature of the function `id`, which declares the number of its
+    /// positional arguments and its options, such as `NUMBER($n, style: "percent")`.
+    ///
+    /// Calls which do not match the signature are reported as
+    /// [`ResolverError::FunctionSignature`](crate::resolver::ResolverError::FunctionSignature)
+    /// while formatting, with the closest declared option when an option is misspelled. The
+    /// function is not called, and the call is formatted as if it returned
+    /// [`FluentValue::Error`].
+    ///
+    /// # Example
     ///
     /// ```
+    /// use fluent_bundle::validate::{FunctionSignature, OptionType};
     /// use fluent_bundle::{FluentBundle, FluentResource, FluentValue};
     /// use unic_langid::langid;
     ///
-    /// let ftl_string = String::from("length = { STRLEN(\"12345\") }");
+    /// let ftl_string = String::from(r#"label = { PAD_LEFT("OK", witdh: 4) }"#);
     /// let resource = FluentResource::try_new(ftl_string)
     ///     .expect("Could not parse an FTL string.");
-    /// let langid_en = langid!("en-US");
-    /// let mut bundle = FluentBundle::new(vec![langid_en]);
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
     /// bundle.add_resource(&resource)
     ///     .expect("Failed to add FTL resources to the bundle.");
     ///
-    /// // Register a fn that maps from string to string length
-    /// bundle.add_function("STRLEN", |positional, _named| match positional {
-    ///     [FluentValue::String(str)] => str.len().into(),
+    /// bundle.add_function("PAD_LEFT", |positional, named| match (positional, named.get("width")) {
+    ///     ([FluentValue::String(s)], Some(FluentValue::Number(n))) => {
+    ///         format!("{:>1$}", s, n.value as usize).into()
+    ///     }
     ///     _ => FluentValue::Error,
     /// }).expect("Failed to add a function to the bundle.");
+    /// bundle.add_function_signature(
+    ///     "PAD_LEFT",
+    ///     FunctionSignature::new().positional(1).option("width", OptionType::Number),
+    /// );
     ///
-    /// let msg = bundle.get_message("length").expect("Message doesn't exist.");
+    /// let msg = bundle.get_message("label").expect("Message doesn't exist.");
     /// let mut errors = vec![];
     /// let pattern = msg.value().expect("Message has no value.");
-    /// let value = bundle.format_pattern(&pattern, None, &mut errors);
-    /// assert_eq!(&value, "5");
+    /// bundle.format_pattern(&pattern, None, &mut errors);
+    /// assert_eq!(
+    ///     errors[0].to_string(),
+    ///     "Resolver error: PAD_LEFT: unknown option 'witdh' (did you mean width?)"
+    /// );
     /// ```
-    ///
-    /// [FTL syntax guide]: https://projectfluent.org/fluent/guide/functions.html
-    pub fn add_function<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
-    where
-        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
-    {
-        match self.entries.entry(id.to_owned()) {
-            HashEntry::Vacant(entry) => {
-                entry.insert(Entry::Function(Box::new(func)));
-                Ok(())
-            }
-            HashEntry::Occupied(_) => Err(FluentError::Overriding {
-                kind: EntryKind::Function,
-                id: id.to_owned(),
-            }),
-        }
+    pub fn add_function_signature(&mut self, id: &str, signature: FunctionSignature) {
+        self.function_signatures.insert(id.to_string(), signature);
     }
 
     /// Adds the builtin functions described in the [FTL syntax guide] to the bundle, making them
     /// available in messages.
     ///
+    /// With the `builtins` feature, numbers are formatted with the ICU4X data of the locale,
+    /// such as "1,234.50" or "$5.00". Calls to `NUMBER()` are checked against its
+    /// [signature](FluentBundle::add_function_signature), which reports misspelled options.
+    /// The functions which are not part of the specification, such as `DATETIME()`, are
+    /// added by [`FluentBundle::add_builtins_extended`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -606,10 +2719,602 @@ impl<R, M> FluentBundle<R, M> {
     ///
     /// [FTL syntax guide]: https://projectfluent.org/fluent/guide/functions.html
     pub fn add_builtins(&mut self) -> Result<(), FluentError> {
-        self.add_function("NUMBER", crate::builtins::NUMBER)?;
-        // TODO: DATETIME()
+        crate::builtins::add_builtins(self)
+    }
 
-        Ok(())
+    /// Adds the builtin functions which are not part of the [FTL syntax guide] to the bundle,
+    /// besides those added by [`FluentBundle::add_builtins`].
+    ///
+    /// `DATETIME($date, dateStyle: "long")` formats dates, and is checked against its
+    /// [signature](FluentBundle::add_function_signature). The `PLATFORM()` and `ENV("key")`
+    /// functions return values of the environment of the bundle, see
+    /// [`FluentBundle::set_env`], and `DURATION($seconds)` formats durations such as
+    /// "1:42:07". `UPPER()`, `LOWER()` and `CAPITALIZE()` map the case of their argument with
+    /// the rules of the first locale of the bundle, such as the dotted and dotless `i` of
+    /// Turkish, and `ORDINAL($n)` formats numbers such as "21st". `PAD($value, width: 12)`
+    /// and `TRUNCATE($value, max: 20)` align text in terminal columns. With the `list-format`
+    /// feature, `LISTFORMAT($names, type: "disjunction")` joins lists such as "a, b, or c".
+    /// With the `relative-time` feature, `RELATIVETIME($seconds)` formats times such as
+    /// "3 minutes ago", and with the `units` feature, `UNIT($value, unit: "megabyte")` formats
+    /// measurements such as "16 MB".
+    ///
+    /// Functions whose identifiers are already in use, such as a `DATETIME()` of the
+    /// application, are kept and reported as errors, while the other builtins are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentError, FluentResource, FluentValue};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("shout = { UPPER(\"hello\") }");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Could not parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// bundle.add_function("PAD", |_, _| FluentValue::None)
+    ///     .expect("Failed to add a function to the bundle.");
+    /// let errors = bundle.add_builtins_extended().unwrap_err();
+    /// assert!(matches!(&errors[..], [FluentError::Overriding { id, .. }] if id == "PAD"));
+    ///
+    /// let msg = bundle.get_message("shout").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    /// assert_eq!(value, "HELLO");
+    /// ```
+    ///
+    /// [FTL syntax guide]: https://projectfluent.org/fluent/guide/functions.html
+    pub fn add_builtins_extended(&mut self) -> Result<(), Vec<FluentError>> {
+        let errors = crate::builtins::add_builtins_extended(self);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<R, M> crate::builtins::Functions for FluentBundle<R, M> {
+    fn env(&self) -> Env {
+        self.env.clone()
+    }
+
+    fn first_locale(&self) -> Option<LanguageIdentifier> {
+        self.locales.first().cloned()
+    }
+
+    fn add_builtin<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
+    {
+        self.add_function(id, func)
+    }
+
+    fn add_builtin_signature(&mut self, id: &str, signature: FunctionSignature) {
+        self.add_function_signature(id, signature);
+    }
+}
+
+/// Returns the messages and terms of `res`, which is stored at `res_pos` in
+/// the bundle resources.
+fn resource_entries(res: &FluentResource, res_pos: usize) -> impl Iterator<Item = (&str, Entry)> {
+    let aliases = res.deprecated_aliases();
+    res.entry_ids()
+        .map(move |(entry_pos, id, kind)| match kind {
+            EntryKind::Term => (id, Entry::Term((res_pos, entry_pos))),
+            _ => match aliases.iter().find(|(alias, _)| *alias == id) {
+                Some((_, target)) => (id, Entry::Alias((*target).into())),
+                None => (id, Entry::Message((res_pos, entry_pos))),
+            },
+        })
+}
+
+/// The position of a message pattern in the resources of a bundle: the
+/// resource, the entry, and the attribute unless it is the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PatternId {
+    res_pos: usize,
+    entry_pos: usize,
+    attribute: Option<usize>,
+}
+
+/// The final strings of the message values and attributes made only of text,
+/// so that formatting them is a lookup.
+///
+/// The strings are keyed by the entry of their pattern. The patterns passed
+/// to [`FluentBundle::format_pattern`] are looked up by address, and only
+/// used once they are found to be the pattern of that entry, so patterns the
+/// bundle does not own never match.
+#[derive(Debug, Default)]
+pub(crate) struct TextPatterns {
+    texts: FxHashMap<PatternId, Box<str>>,
+    ids: FxHashMap<usize, PatternId>,
+}
+
+impl TextPatterns {
+    /// Caches the text patterns of `res`, which is stored at `res_pos`.
+    ///
+    /// Single text elements without a transform are skipped, as the resolver
+    /// already borrows them directly.
+    fn insert_resource(
+        &mut self,
+        res: &FluentResource,
+        res_pos: usize,
+        transform: Option<fn(&str) -> Cow<str>>,
+    ) {
+        // Caching would parse every entry of a lazy resource, whose text
+        // patterns are formatted from their elements instead, see
+        // `FluentResource::new_lazy`.
+        if res.is_lazy() {
+            return;
+        }
+        for (entry_pos, entry) in res.entries().enumerate() {
+            let ast::Entry::Message(msg) = entry else {
+                continue;
+            };
+            let value = msg.value.iter().map(|value| (value, None));
+            let attributes =
+                (msg.attributes.iter().enumerate()).map(|(idx, attr)| (&attr.value, Some(idx)));
+            for (pattern, attribute) in value.chain(attributes) {
+                let is_text = pattern
+                    .elements
+                    .iter()
+                    .all(|elem| matches!(elem, ast::PatternElement::TextElement { .. }));
+                if !is_text || (pattern.elements.len() == 1 && transform.is_none()) {
+                    continue;
+                }
+
+                let mut text = String::new();
+                for elem in &pattern.elements {
+                    if let ast::PatternElement::TextElement { value } = elem {
+                        match transform {
+                            Some(transform) => text.push_str(&transform(value)),
+                            None => text.push_str(value),
+                        }
+                    }
+                }
+                let id = PatternId {
+                    res_pos,
+                    entry_pos,
+                    attribute,
+                };
+                self.ids
+                    .insert(pattern as *const ast::Pattern<&str> as usize, id);
+                self.texts.insert(id, text.into());
+            }
+        }
+    }
+
+    /// Returns the cached string of `pattern`, if it is a text pattern of an
+    /// entry of `resources`.
+    fn get<'b, R>(&'b self, resources: &'b [R], pattern: &ast::Pattern<&str>) -> Option<&'b str>
+    where
+        R: Borrow<FluentResource>,
+    {
+        if self.ids.is_empty() {
+            return None;
+        }
+        let id = self
+            .ids
+            .get(&(pattern as *const ast::Pattern<&str> as usize))?;
+        let res = resources.get(id.res_pos)?.borrow();
+        let ast::Entry::Message(msg) = res.get_entry(id.entry_pos)? else {
+            return None;
+        };
+        let owned = match id.attribute {
+            Some(idx) => &msg.attributes.get(idx)?.value,
+            None => msg.value.as_ref()?,
+        };
+        if !std::ptr::eq(owned, pattern) {
+            return None;
+        }
+        self.texts.get(id).map(AsRef::as_ref)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn extend(&mut self, other: Self) {
+        self.texts.extend(other.texts);
+        self.ids.extend(other.ids);
+    }
+
+    /// Returns an estimate of the heap memory used by the cache, in bytes.
+    fn heap_size(&self) -> usize {
+        self.texts.capacity() * std::mem::size_of::<(PatternId, Box<str>)>()
+            + self.texts.values().map(|text| text.len()).sum::<usize>()
+            + self.ids.capacity() * std::mem::size_of::<(usize, PatternId)>()
+    }
+}
+
+/// The variant which a plural select expression selects for each plural
+/// category, and the category of the last number it selected on.
+#[derive(Debug)]
+struct Selection {
+    /// The index of the variant of each category, or `usize::MAX` until the
+    /// category is first selected.
+    variants: [AtomicUsize; 6],
+    last: Mutex<Option<(FluentNumber, Option<PluralCategory>)>>,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            variants: std::array::from_fn(|_| AtomicUsize::new(usize::MAX)),
+            last: Mutex::new(None),
+        }
+    }
+}
+
+/// The variants selected by the plural select expressions of the messages,
+/// so that formatting a message over and over with the same number, such as
+/// a progress counter, neither runs the plural rules nor matches the variant
+/// keys again.
+///
+/// The selections of each message are keyed by its entry, and its select
+/// expressions whose keys are all identifiers by the address of their
+/// variants. Only the resources of the bundle are indexed, and the cache is
+/// built again whenever they change, so patterns the bundle does not own
+/// never match.
+#[derive(Debug, Default)]
+pub(crate) struct VariantCache {
+    messages: FxHashMap<(usize, usize), Box<[Selection]>>,
+    selects: FxHashMap<usize, ((usize, usize), usize)>,
+}
+
+impl VariantCache {
+    /// Indexes the plural select expressions of the messages of `res`, which
+    /// is stored at `res_pos`, and forgets the variants selected so far, as
+    /// for every change of the resources of the bundle.
+    fn insert_resource(&mut self, res: &FluentResource, res_pos: usize) {
+        self.reset();
+        // Lazy resources parse their entries on first access, and their
+        // select expressions are matched by the resolver instead.
+        if res.is_lazy() {
+            return;
+        }
+        for (entry_pos, entry) in res.entries().enumerate() {
+            let ast::Entry::Message(msg) = entry else {
+                continue;
+            };
+            let mut selects = PluralSelects(vec![]);
+            selects.visit_message(msg);
+            if selects.0.is_empty() {
+                continue;
+            }
+            for (idx, variants) in selects.0.iter().enumerate() {
+                self.selects
+                    .insert(variants.as_ptr() as usize, ((res_pos, entry_pos), idx));
+            }
+            let selections = selects.0.iter().map(|_| Selection::default()).collect();
+            self.messages.insert((res_pos, entry_pos), selections);
+        }
+    }
+
+    /// Returns the index of the variant of `variants` selected by `number`,
+    /// whose plural category is returned by `category`, or `None` if
+    /// `variants` are not those of a plural select expression of a message.
+    ///
+    /// `category` is only called when `number` differs from the last number
+    /// selected on by the expression.
+    pub(crate) fn select<F>(
+        &self,
+        variants: &[ast::Variant<&str>],
+        number: &FluentNumber,
+        category: F,
+    ) -> Option<usize>
+    where
+        F: FnOnce() -> Option<PluralCategory>,
+    {
+        if self.selects.is_empty() {
+            return None;
+        }
+        let (message, idx) = self.selects.get(&(variants.as_ptr() as usize))?;
+        let selection = self.messages.get(message)?.get(*idx)?;
+
+        // Another thread formatting the same expression computes the
+        // category itself rather than waiting for the lock.
+        let category = match selection.last.try_lock() {
+            Ok(mut last) => match &*last {
+                Some((last_number, category)) if last_number == number => *category,
+                _ => {
+                    let category = category();
+                    *last = Some((number.clone(), category));
+                    category
+                }
+            },
+            Err(_) => category(),
+        };
+
+        let default = || variants.iter().position(|variant| variant.default);
+        let Some(category) = category else {
+            return default();
+        };
+        let slot = &selection.variants[category as usize];
+        match slot.load(Ordering::Relaxed) {
+            usize::MAX => {
+                let idx = variants
+                    .iter()
+                    .position(|variant| {
+                        matches!(variant.key, ast::VariantKey::Identifier { name }
+                            if PluralCategory::from_key(name) == Some(category))
+                    })
+                    .or_else(default)?;
+                slot.store(idx, Ordering::Relaxed);
+                Some(idx)
+            }
+            idx => Some(idx),
+        }
+    }
+
+    /// Forgets the selected variants, for instance once the plural rules or
+    /// the matching of variant keys change.
+    pub(crate) fn reset(&mut self) {
+        for selections in self.messages.values_mut() {
+            for selection in selections.iter_mut() {
+                *selection = Selection::default();
+            }
+        }
+    }
+
+    /// Returns an estimate of the heap memory used by the cache, in bytes.
+    fn heap_size(&self) -> usize {
+        self.messages.capacity() * std::mem::size_of::<((usize, usize), Box<[Selection]>)>()
+            + self.selects.len() * std::mem::size_of::<Selection>()
+            + self.selects.capacity() * std::mem::size_of::<(usize, ((usize, usize), usize))>()
+    }
+}
+
+/// Collects the variants of the select expressions of a message whose keys
+/// are all identifiers, such as plural categories, in the order they are
+/// visited.
+struct PluralSelects<'ast, 's>(Vec<&'ast [ast::Variant<&'s str>]>);
+
+impl<'ast, 's> Visit<'ast, &'s str> for PluralSelects<'ast, 's> {
+    fn visit_expression(&mut self, node: &'ast ast::Expression<&'s str>) {
+        if let ast::Expression::Select { variants, .. } = node {
+            let is_plural = variants
+                .iter()
+                .all(|variant| matches!(variant.key, ast::VariantKey::Identifier { .. }));
+            if is_plural && !variants.is_empty() {
+                self.0.push(variants);
+            }
+        }
+        visit::walk_expression(self, node);
+    }
+}
+
+type RawPatternResult<'s> = Result<ast::Pattern<&'s str>, ParserError>;
+
+self_cell!(
+    /// A pattern parsed by [`FluentBundle::format_raw_pattern`], and its source.
+    pub(crate) struct RawPattern {
+        owner: Arc<str>,
+
+        #[covariant]
+        dependent: RawPatternResult,
+    }
+);
+
+/// The patterns parsed by [`FluentBundle::format_raw_pattern`], keyed by
+/// their source.
+///
+/// Each pattern is stamped with a tick of `clock` when it is used, and once
+/// `capacity` patterns are kept, the one with the oldest tick is dropped for
+/// each new one.
+pub(crate) struct RawPatterns {
+    patterns: RwLock<FxHashMap<Arc<str>, (Arc<RawPattern>, AtomicU64)>>,
+    clock: AtomicU64,
+    capacity: usize,
+}
+
+impl Default for RawPatterns {
+    fn default() -> Self {
+        Self {
+            patterns: RwLock::default(),
+            clock: AtomicU64::new(0),
+            capacity: 256,
+        }
+    }
+}
+
+impl RawPatterns {
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Returns the pattern parsed from `source`, parsing and keeping it if it
+    /// is not kept yet.
+    fn get_or_parse(&self, source: &str) -> Arc<RawPattern> {
+        if let Some((raw, last_used)) = self.patterns.read().unwrap().get(source) {
+            last_used.store(self.tick(), Ordering::Relaxed);
+            return raw.clone();
+        }
+
+        let raw = Arc::new(RawPattern::new(source.into(), |source| {
+            parser::parse_pattern(&**source)
+        }));
+        if self.capacity == 0 {
+            return raw;
+        }
+        let mut patterns = self.patterns.write().unwrap();
+        if !patterns.contains_key(source) {
+            Self::truncate(&mut patterns, self.capacity - 1);
+        }
+        let tick = self.tick();
+        let (raw, _) = patterns
+            .entry(raw.borrow_owner().clone())
+            .or_insert_with(|| (raw, AtomicU64::new(tick)));
+        raw.clone()
+    }
+
+    /// Drops the least recently used patterns until `len` are left.
+    fn truncate(patterns: &mut FxHashMap<Arc<str>, (Arc<RawPattern>, AtomicU64)>, len: usize) {
+        let Some(count) = patterns.len().checked_sub(len).filter(|&count| count > 0) else {
+            return;
+        };
+        let mut ticks: Vec<_> = patterns
+            .iter()
+            .map(|(source, (_, last_used))| (last_used.load(Ordering::Relaxed), source.clone()))
+            .collect();
+        ticks.sort_unstable_by_key(|(tick, _)| *tick);
+        for (_, source) in ticks.into_iter().take(count) {
+            patterns.remove(&source);
+        }
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        Self::truncate(self.patterns.get_mut().unwrap(), capacity);
+    }
+
+    /// Returns the number of kept patterns, and an estimate of the heap
+    /// memory they use, in bytes.
+    fn stats(&self) -> (usize, usize) {
+        let patterns = self.patterns.read().unwrap();
+        let heap_size = patterns.capacity()
+            * std::mem::size_of::<(Arc<str>, (Arc<RawPattern>, AtomicU64))>()
+            + patterns
+                .keys()
+                .map(|source| source.len() + std::mem::size_of::<RawPatternResult>())
+                .sum::<usize>();
+        (patterns.len(), heap_size)
+    }
+
+    /// Drops all the patterns, keeping the capacity.
+    pub(crate) fn clear(&mut self) {
+        self.patterns.get_mut().unwrap().clear();
+    }
+}
+
+/// A chain of bundles for a list of locales, in which each message, term or
+/// function missing from a bundle is taken from the next one.
+///
+/// Unlike the fallback of `fluent-fallback`, which formats a whole message
+/// from the first locale which has it, the references within a message fall
+/// back independently, so a translated message referencing an untranslated
+/// term still formats. The fallback entries are formatted with the locale
+/// of the first bundle. It dereferences to the first [`FluentBundle`].
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::bundle::FallbackBundle;
+/// use fluent_bundle::{FluentBundle, FluentResource};
+/// use unic_langid::langid;
+///
+/// let pl = FluentResource::try_new("about = O { -brand }".to_string())
+///     .expect("Failed to parse an FTL string.");
+/// let en = FluentResource::try_new("-brand = Firefox\nabout = About { -brand }\nhelp = Help".to_string())
+///     .expect("Failed to parse an FTL string.");
+///
+/// let mut bundle_pl = FluentBundle::new(vec![langid!("pl")]);
+/// bundle_pl.set_use_isolating(false);
+/// bundle_pl.add_resource(&pl).expect("Failed to add FTL resources to the bundle.");
+/// let mut bundle_en = FluentBundle::new(vec![langid!("en-US")]);
+/// bundle_en.add_resource(&en).expect("Failed to add FTL resources to the bundle.");
+///
+/// let bundle = FallbackBundle::new(bundle_pl, vec![bundle_en]);
+/// let format = |id| {
+///     let msg = bundle.get_message(id).expect("Message doesn't exist.");
+///     let mut errors = vec![];
+///     bundle.format_pattern(msg.value().unwrap(), None, &mut errors).to_string()
+/// };
+/// assert_eq!(format("about"), "O Firefox");
+/// assert_eq!(format("help"), "Help");
+/// ```
+pub struct FallbackBundle<R, M>(FluentBundle<R, M>);
+
+impl<R, M> FallbackBundle<R, M> {
+    /// Chains `bundle` with `fallbacks`, in order. The fallbacks these
+    /// bundles had are replaced.
+    pub fn new(mut bundle: FluentBundle<R, M>, fallbacks: Vec<FluentBundle<R, M>>) -> Self {
+        let mut next = None;
+        for mut fallback in fallbacks.into_iter().rev() {
+            fallback.fallback = next;
+            next = Some(Arc::new(fallback));
+        }
+        bundle.fallback = next;
+        Self(bundle)
+    }
+
+    /// Returns the bundles of the chain, in order.
+    pub fn bundles(&self) -> impl Iterator<Item = &FluentBundle<R, M>> {
+        std::iter::successors(Some(&self.0), |bundle| bundle.fallback.as_deref())
+    }
+}
+
+impl<R, M> Deref for FallbackBundle<R, M> {
+    type Target = FluentBundle<R, M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A bundle created with [`FluentBundle::child`], which only allows adding
+/// resources. It dereferences to a [`FluentBundle`] for formatting.
+pub struct ChildBundle<R, M>(FluentBundle<R, M>);
+
+impl<R, M> ChildBundle<R, M> {
+    /// Adds a resource to the bundle, see [`FluentBundle::add_resource`].
+    ///
+    /// Entries whose identifiers are used by the parent are ignored and
+    /// reported as overriding errors too.
+    pub fn add_resource(&mut self, r: R) -> Result<(), Vec<FluentError>>
+    where
+        R: Borrow<FluentResource>,
+    {
+        let mut errors = vec![];
+        let bundle = &mut self.0;
+
+        let res = r.borrow();
+        let res_pos = bundle.resources.len();
+        let parent = bundle.parent.clone();
+        let entries = resource_entries(res, res_pos).filter(|(id, entry)| {
+            let Some(parent) = parent.as_deref() else {
+                return true;
+            };
+            if parent.get_own_entry(parent.key(id)).is_none() {
+                return true;
+            }
+            let kind = match entry {
+                Entry::Term(..) => EntryKind::Term,
+                _ => EntryKind::Message,
+            };
+            errors.push(FluentError::Overriding {
+                kind,
+                id: id.to_string(),
+            });
+            false
+        });
+        let entries: Vec<_> = entries.collect();
+
+        bundle.insert_entries(entries.into_iter(), &mut errors);
+        bundle.insert_required_flags(res, res_pos);
+        check_term_signatures(res, &bundle.term_signatures, &mut errors);
+        bundle
+            .text_patterns
+            .insert_resource(res, res_pos, bundle.transform);
+        bundle.variant_cache.insert_resource(res, res_pos);
+        bundle.interner.insert_references(res);
+        bundle.resources.push(r);
+        bundle.overriding.push(false);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<R, M> Deref for ChildBundle<R, M> {
+    type Target = FluentBundle<R, M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
@@ -642,14 +3347,42 @@ impl<R> FluentBundle<R, IntlLangMemoizer> {
     /// This will panic if no formatters can be found for the locales.
     pub fn new(locales: Vec<LanguageIdentifier>) -> Self {
         let first_locale = locales.first().cloned().unwrap_or_default();
+        let locale_intls = locales
+            .iter()
+            .skip(1)
+            .cloned()
+            .map(IntlLangMemoizer::new)
+            .collect();
         Self {
             locales,
             resources: vec![],
+            overriding: vec![],
             entries: FxHashMap::default(),
+            interner: Interner::default(),
             intls: IntlLangMemoizer::new(first_locale),
-            use_isolating: true,
+            locale_intls,
+            use_isolating: cfg!(feature = "bidi-isolation"),
+            detect_direction: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             transform: None,
             formatter: None,
+            text_patterns: TextPatterns::default(),
+            variant_cache: VariantCache::default(),
+            raw_patterns: RawPatterns::default(),
+            deprecation_hook: None,
+            variant_selector: None,
+            unknown_reference_handler: None,
+            number_symbols: None,
+            plural_rules: None,
+            selector_matcher: None,
+            namespace_separator: ".",
+            term_signatures: FxHashMap::default(),
+            function_signatures: FxHashMap::default(),
+            required_flags: FxHashMap::default(),
+            enabled_flags: FxHashSet::default(),
+            env: Env::default(),
+            parent: None,
+            fallback: None,
         }
     }
 }
@@ -678,4 +3411,8 @@ impl crate::memoizer::MemoizerKind for IntlLangMemoizer {
     ) -> std::borrow::Cow<'static, str> {
         value.as_string(self)
     }
+
+    fn memory_stats(&self) -> intl_memoizer::MemoryStats {
+        self.memory_stats()
+    }
 }
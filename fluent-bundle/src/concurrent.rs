@@ -1,9 +1,21 @@
+use std::borrow::{Borrow, Cow};
+use std::fmt;
+
+use fluent_syntax::ast;
 use intl_memoizer::{concurrent::IntlLangMemoizer, Memoizable};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use unic_langid::LanguageIdentifier;
 
+use crate::builtins::Env;
+use crate::bundle::{BundleMemoryStats, FormattedPart, RawPatterns, TextPatterns, VariantCache};
+use crate::errors::{ErrorSink, FluentError};
+use crate::interner::Interner;
 use crate::memoizer::MemoizerKind;
+use crate::message::FluentMessage;
+use crate::resolver::DEFAULT_MAX_DEPTH;
+use crate::resource::{CatalogStats, FluentResource};
 use crate::types::FluentType;
+use crate::FluentArgs;
 
 /// Specialized [`FluentBundle`](crate::bundle::FluentBundle) over
 /// concurrent [`IntlLangMemoizer`].
@@ -31,16 +43,186 @@ impl<R> FluentBundle<R> {
     /// ```
     pub fn new_concurrent(locales: Vec<LanguageIdentifier>) -> Self {
         let first_locale = locales.first().cloned().unwrap_or_default();
+        let locale_intls = locales
+            .iter()
+            .skip(1)
+            .cloned()
+            .map(IntlLangMemoizer::new)
+            .collect();
         Self {
             locales,
             resources: vec![],
+            overriding: vec![],
             entries: FxHashMap::default(),
+            interner: Interner::default(),
             intls: IntlLangMemoizer::new(first_locale),
-            use_isolating: true,
+            locale_intls,
+            use_isolating: cfg!(feature = "bidi-isolation"),
+            detect_direction: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             transform: None,
             formatter: None,
+            text_patterns: TextPatterns::default(),
+            variant_cache: VariantCache::default(),
+            raw_patterns: RawPatterns::default(),
+            deprecation_hook: None,
+            variant_selector: None,
+            unknown_reference_handler: None,
+            number_symbols: None,
+            plural_rules: None,
+            selector_matcher: None,
+            namespace_separator: ".",
+            term_signatures: FxHashMap::default(),
+            function_signatures: FxHashMap::default(),
+            required_flags: FxHashMap::default(),
+            enabled_flags: FxHashSet::default(),
+            env: Env::default(),
+            parent: None,
+            fallback: None,
         }
     }
+
+    /// Turns the bundle into a [`FrozenBundle`], which can only be read.
+    ///
+    /// The patterns cached by [`FluentBundle::format_raw_pattern`] and the
+    /// variants cached for plural select expressions are dropped, so that
+    /// formatting with the frozen bundle only takes the locks of its
+    /// memoizer.
+    pub fn freeze(mut self) -> FrozenBundle<R> {
+        self.raw_patterns.clear();
+        self.variant_cache = VariantCache::default();
+        FrozenBundle(self)
+    }
+}
+
+/// An immutable concurrent bundle, created with [`FluentBundle::freeze`].
+///
+/// A frozen bundle is [`Send`] and [`Sync`], and unlike a [`FluentBundle`] it
+/// has no caches behind locks, so that it can be shared between threads, for
+/// instance with an [`Arc`](std::sync::Arc), and formatted from all of them
+/// without contention.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use fluent_bundle::concurrent::FluentBundle;
+/// use fluent_bundle::{FluentArgs, FluentResource};
+/// use unic_langid::langid;
+///
+/// let resource = FluentResource::try_new("items = { $count } items".to_string())
+///     .expect("Failed to parse an FTL string.");
+/// let mut bundle = FluentBundle::new_concurrent(vec![langid!("en-US")]);
+/// bundle.set_use_isolating(false);
+/// bundle.add_resource(resource)
+///     .expect("Failed to add FTL resources to the bundle.");
+/// let bundle = Arc::new(bundle.freeze());
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|count| {
+///         let bundle = Arc::clone(&bundle);
+///         thread::spawn(move || {
+///             let msg = bundle.get_message("items").expect("Message doesn't exist.");
+///             let mut args = FluentArgs::new();
+///             args.set("count", count);
+///             let mut errors = vec![];
+///             bundle
+///                 .format_pattern(msg.value().unwrap(), Some(&args), &mut errors)
+///                 .to_string()
+///         })
+///     })
+///     .collect();
+/// for (count, handle) in handles.into_iter().enumerate() {
+///     assert_eq!(handle.join().unwrap(), format!("{} items", count));
+/// }
+/// ```
+pub struct FrozenBundle<R>(FluentBundle<R>);
+
+impl<R> FrozenBundle<R>
+where
+    R: Borrow<FluentResource>,
+{
+    /// See [`FluentBundle::has_message`].
+    pub fn has_message(&self, id: &str) -> bool {
+        self.0.has_message(id)
+    }
+
+    /// See [`FluentBundle::get_message`].
+    pub fn get_message<'l>(&'l self, id: &str) -> Option<FluentMessage<'l>> {
+        self.0.get_message(id)
+    }
+
+    /// See [`FluentBundle::get_term_attribute`].
+    pub fn get_term_attribute(&self, id: &str, attribute: &str) -> Option<Cow<'_, str>> {
+        self.0.get_term_attribute(id, attribute)
+    }
+
+    /// See [`FluentBundle::write_pattern`].
+    pub fn write_pattern<'bundle, W, E>(
+        &'bundle self,
+        w: &mut W,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> fmt::Result
+    where
+        W: fmt::Write,
+        E: ErrorSink,
+    {
+        self.0.write_pattern(w, pattern, args, errors)
+    }
+
+    /// See [`FluentBundle::format_pattern`].
+    pub fn format_pattern<'bundle, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> Cow<'bundle, str>
+    where
+        E: ErrorSink,
+    {
+        self.0.format_pattern(pattern, args, errors)
+    }
+
+    /// See [`FluentBundle::format_pattern_strict`].
+    pub fn format_pattern_strict<'bundle>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+    ) -> Result<Cow<'bundle, str>, Vec<FluentError>> {
+        self.0.format_pattern_strict(pattern, args)
+    }
+
+    /// See [`FluentBundle::format_pattern_to_parts`].
+    pub fn format_pattern_to_parts<'bundle, E>(
+        &'bundle self,
+        pattern: &'bundle ast::Pattern<&'bundle str>,
+        args: Option<&FluentArgs>,
+        errors: &mut E,
+    ) -> Vec<FormattedPart<'bundle>>
+    where
+        E: ErrorSink,
+    {
+        self.0.format_pattern_to_parts(pattern, args, errors)
+    }
+
+    /// See [`FluentBundle::required_flag`].
+    pub fn required_flag(&self, id: &str) -> Option<&str> {
+        self.0.required_flag(id)
+    }
+
+    /// See [`FluentBundle::stats`].
+    pub fn stats(&self) -> CatalogStats {
+        self.0.stats()
+    }
+
+    /// See [`FluentBundle::memory_stats`].
+    pub fn memory_stats(&self) -> BundleMemoryStats {
+        self.0.memory_stats()
+    }
 }
 
 impl MemoizerKind for IntlLangMemoizer {
@@ -64,4 +246,8 @@ impl MemoizerKind for IntlLangMemoizer {
     fn stringify_value(&self, value: &dyn FluentType) -> std::borrow::Cow<'static, str> {
         value.as_string_threadsafe(self)
     }
+
+    fn memory_stats(&self) -> intl_memoizer::MemoryStats {
+        self.memory_stats()
+    }
 }
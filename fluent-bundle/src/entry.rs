@@ -7,11 +7,26 @@ use fluent_syntax::ast;
 
 use crate::args::FluentArgs;
 use crate::bundle::FluentBundle;
+use crate::function::{AsyncFunctionFuture, FluentFunctionError, FunctionContext};
+use crate::interner::Key;
 use crate::resource::FluentResource;
 use crate::types::FluentValue;
 
-pub type FluentFunction =
-    Box<dyn for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync>;
+pub type FluentFunction = Box<
+    dyn for<'a> Fn(
+            &[FluentValue<'a>],
+            &FluentArgs,
+            &FunctionContext,
+        ) -> Result<FluentValue<'a>, FluentFunctionError>
+        + Send
+        + Sync,
+>;
+
+pub type AsyncFluentFunction = Box<
+    dyn Fn(Vec<FluentValue<'static>>, FluentArgs<'static>, &FunctionContext) -> AsyncFunctionFuture
+        + Send
+        + Sync,
+>;
 
 type ResourceIdx = usize;
 type EntryIdx = usize;
@@ -22,6 +37,12 @@ pub enum Entry {
     Message((ResourceIdx, EntryIdx)),
     Term((ResourceIdx, EntryIdx)),
     Function(FluentFunction),
+    /// A function which is awaited by
+    /// [`FluentBundle::format_pattern_async`].
+    AsyncFunction(AsyncFluentFunction),
+    /// A deprecated identifier of the message with the given identifier, see
+    /// [`FluentBundle::add_alias`].
+    Alias(Box<str>),
 }
 
 pub trait GetEntry {
@@ -30,16 +51,24 @@ pub trait GetEntry {
 
     /// Looks up a term by its string ID, and returns it if it exists.
     fn get_entry_term(&self, id: &str) -> Option<&ast::Term<&str>>;
-
-    /// Looks up a function by its string ID, and returns it if it exists.
-    fn get_entry_function(&self, id: &str) -> Option<&FluentFunction>;
 }
 
 impl<R: Borrow<FluentResource>, M> GetEntry for FluentBundle<R, M> {
     fn get_entry_message(&self, id: &str) -> Option<&ast::Message<&str>> {
-        self.entries.get(id).and_then(|ref entry| match entry {
+        self.get_message_entry(self.key(id))
+    }
+
+    fn get_entry_term(&self, id: &str) -> Option<&ast::Term<&str>> {
+        self.get_term_entry(self.key(id))
+    }
+}
+
+impl<R: Borrow<FluentResource>, M> FluentBundle<R, M> {
+    /// Looks up a message by its key, and returns it if it exists.
+    pub(crate) fn get_message_entry(&self, key: Key) -> Option<&ast::Message<&str>> {
+        self.get_entry(key).and_then(|(bundle, entry)| match entry {
             Entry::Message((resource_idx, entry_idx)) => {
-                let res = self.resources.get(*resource_idx)?.borrow();
+                let res = bundle.resources.get(*resource_idx)?.borrow();
                 if let ast::Entry::Message(ref msg) = res.get_entry(*entry_idx)? {
                     Some(msg)
                 } else {
@@ -50,10 +79,11 @@ impl<R: Borrow<FluentResource>, M> GetEntry for FluentBundle<R, M> {
         })
     }
 
-    fn get_entry_term(&self, id: &str) -> Option<&ast::Term<&str>> {
-        self.entries.get(id).and_then(|ref entry| match entry {
+    /// Looks up a term by its key, and returns it if it exists.
+    pub(crate) fn get_term_entry(&self, key: Key) -> Option<&ast::Term<&str>> {
+        self.get_entry(key).and_then(|(bundle, entry)| match entry {
             Entry::Term((resource_idx, entry_idx)) => {
-                let res = self.resources.get(*resource_idx)?.borrow();
+                let res = bundle.resources.get(*resource_idx)?.borrow();
                 if let ast::Entry::Term(ref msg) = res.get_entry(*entry_idx)? {
                     Some(msg)
                 } else {
@@ -63,11 +93,4 @@ impl<R: Borrow<FluentResource>, M> GetEntry for FluentBundle<R, M> {
             _ => None,
         })
     }
-
-    fn get_entry_function(&self, id: &str) -> Option<&FluentFunction> {
-        self.entries.get(id).and_then(|ref entry| match entry {
-            Entry::Function(function) => Some(function),
-            _ => None,
-        })
-    }
 }
@@ -1,5 +1,7 @@
 use crate::resolver::ResolverError;
+use crate::validate::TermSignatureError;
 use fluent_syntax::parser::ParserError;
+use smallvec::SmallVec;
 use std::error::Error;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -57,6 +59,10 @@ pub enum FluentError {
     },
     ParserError(ParserError),
     ResolverError(ResolverError),
+    /// A term definition or reference which does not match the signature of
+    /// the term, see
+    /// [`FluentBundle::add_term_signature`](crate::bundle::FluentBundle::add_term_signature).
+    TermSignature(TermSignatureError),
 }
 
 impl std::fmt::Display for FluentError {
@@ -67,6 +73,7 @@ impl std::fmt::Display for FluentError {
             }
             Self::ParserError(err) => write!(f, "Parser error: {}", err),
             Self::ResolverError(err) => write!(f, "Resolver error: {}", err),
+            Self::TermSignature(err) => write!(f, "Term signature error: {}", err),
         }
     }
 }
@@ -79,8 +86,61 @@ impl From<ResolverError> for FluentError {
     }
 }
 
+impl From<TermSignatureError> for FluentError {
+    fn from(error: TermSignatureError) -> Self {
+        Self::TermSignature(error)
+    }
+}
+
 impl From<ParserError> for FluentError {
     fn from(error: ParserError) -> Self {
         Self::ParserError(error)
     }
 }
+
+/// A collection which accumulates the errors encountered while formatting, see
+/// [`FluentBundle::format_pattern`](crate::bundle::FluentBundle::format_pattern).
+///
+/// Besides `Vec`, it is implemented for `SmallVec`, which keeps the first few
+/// errors inline and avoids the heap in the common case.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::{FluentBundle, FluentError, FluentResource};
+/// use smallvec::SmallVec;
+/// use unic_langid::langid;
+///
+/// let res = FluentResource::try_new("hello = Hello, { $name }!".to_string())
+///     .expect("Failed to parse an FTL string.");
+/// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+/// bundle.add_resource(res)
+///     .expect("Failed to add FTL resources to the bundle.");
+///
+/// let msg = bundle.get_message("hello").expect("Message doesn't exist.");
+/// let pattern = msg.value().expect("Message has no value.");
+///
+/// let mut errors = SmallVec::<[FluentError; 4]>::new();
+/// bundle.format_pattern(pattern, None, &mut errors);
+/// assert_eq!(errors.len(), 1);
+/// assert!(!errors.spilled());
+/// ```
+pub trait ErrorSink {
+    /// Appends `error` to the collection.
+    fn push_error(&mut self, error: FluentError);
+}
+
+impl ErrorSink for Vec<FluentError> {
+    fn push_error(&mut self, error: FluentError) {
+        self.push(error);
+    }
+}
+
+impl<A> ErrorSink for SmallVec<A>
+where
+    A: smallvec::Array<Item = FluentError>,
+{
+    fn push_error(&mut self, error: FluentError) {
+        self.push(error);
+    }
+}
@@ -0,0 +1,216 @@
+//! `FunctionContext` gives the functions added with
+//! [`FluentBundle::add_function_with_ctx`](crate::bundle::FluentBundle::add_function_with_ctx)
+//! access to the locales and the memoizer of the bundle which calls them, and
+//! `FluentFunctionError` describes why a fallible function failed.
+//!
+//! `AsyncCalls` records the calls to the functions added with
+//! [`FluentBundle::add_async_function`](crate::bundle::FluentBundle::add_async_function)
+//! while a pattern is formatted with
+//! [`FluentBundle::format_pattern_async`](crate::bundle::FluentBundle::format_pattern_async).
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use intl_memoizer::Memoizable;
+use unic_langid::LanguageIdentifier;
+
+use crate::args::FluentArgs;
+use crate::memoizer::MemoizerKind;
+use crate::types::FluentValue;
+
+/// The bundle state available to a function while it is called.
+#[derive(Clone, Copy)]
+pub struct FunctionContext<'a> {
+    locales: &'a [LanguageIdentifier],
+    locale: usize,
+    intls: &'a dyn Any,
+}
+
+impl<'a> FunctionContext<'a> {
+    pub(crate) fn new<M: MemoizerKind>(
+        locales: &'a [LanguageIdentifier],
+        locale: usize,
+        intls: &'a M,
+    ) -> Self {
+        Self {
+            locales,
+            locale,
+            intls,
+        }
+    }
+
+    /// Returns the locale the pattern is formatted in, which is the first
+    /// locale of the bundle unless it is formatted with
+    /// [`FluentBundle::format_pattern_in_locale`](crate::bundle::FluentBundle::format_pattern_in_locale).
+    pub fn locale(&self) -> Option<&'a LanguageIdentifier> {
+        self.locales.get(self.locale)
+    }
+
+    /// Returns the locales of the bundle, in the order of its fallback chain.
+    pub fn locales(&self) -> &'a [LanguageIdentifier] {
+        self.locales
+    }
+
+    /// Returns the memoizer of the locale formatted in, if the bundle uses a
+    /// memoizer of type `M`, such as [`intl_memoizer::IntlLangMemoizer`] for
+    /// [`FluentBundle`](crate::FluentBundle) and
+    /// [`intl_memoizer::concurrent::IntlLangMemoizer`] for concurrent bundles.
+    pub fn intls<M: MemoizerKind>(&self) -> Option<&'a M> {
+        self.intls.downcast_ref()
+    }
+
+    /// Calls `callback` with the formatter `I` for the locale formatted in,
+    /// constructed on first use and memoized by the bundle, whichever its
+    /// memoizer is.
+    ///
+    /// `None` is returned for bundles with custom memoizers.
+    pub fn with_try_get<I, R, U>(&self, args: I::Args, callback: U) -> Option<Result<R, I::Error>>
+    where
+        I: Memoizable + Send + Sync + 'static,
+        I::Args: Send + Sync + 'static,
+        U: FnOnce(&I) -> R,
+    {
+        if let Some(intls) = self.intls::<intl_memoizer::IntlLangMemoizer>() {
+            Some(intls.with_try_get_threadsafe(args, callback))
+        } else {
+            self.intls::<intl_memoizer::concurrent::IntlLangMemoizer>()
+                .map(|intls| intls.with_try_get_threadsafe(args, callback))
+        }
+    }
+}
+
+impl fmt::Debug for FunctionContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FunctionContext")
+            .field("locales", &self.locales)
+            .field("locale", &self.locale())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The failure of a function added with
+/// [`FluentBundle::add_fallible_function`](crate::bundle::FluentBundle::add_fallible_function),
+/// which is reported as
+/// [`ResolverError::Function`](crate::resolver::ResolverError::Function).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FluentFunctionError {
+    /// The positional index or the name of the offending argument, if any.
+    pub argument: Option<String>,
+    pub reason: String,
+}
+
+impl FluentFunctionError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            argument: None,
+            reason: reason.into(),
+        }
+    }
+
+    /// Creates an error caused by the value of the argument `argument`, such
+    /// as `"0"` for the first positional argument or `"style"`.
+    pub fn argument(argument: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            argument: Some(argument.into()),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for FluentFunctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.argument {
+            Some(argument) => write!(f, "Invalid argument {}: {}", argument, self.reason),
+            None => f.write_str(&self.reason),
+        }
+    }
+}
+
+impl Error for FluentFunctionError {}
+
+/// The future returned by the functions added with
+/// [`FluentBundle::add_async_function`](crate::bundle::FluentBundle::add_async_function).
+pub type AsyncFunctionFuture =
+    Pin<Box<dyn Future<Output = Result<FluentValue<'static>, FluentFunctionError>> + Send>>;
+
+/// A call to an async function, and its result once it is awaited.
+struct AsyncCall {
+    id: String,
+    positional: Vec<FluentValue<'static>>,
+    named: FluentArgs<'static>,
+    result: Option<Result<FluentValue<'static>, FluentFunctionError>>,
+}
+
+/// The calls to async functions made while formatting a pattern, which are
+/// awaited between the passes of the resolver.
+#[derive(Default)]
+pub(crate) struct AsyncCalls {
+    calls: Mutex<Vec<AsyncCall>>,
+    /// The futures of the calls without a result, by index in `calls`.
+    pending: Mutex<Vec<(usize, AsyncFunctionFuture)>>,
+}
+
+impl AsyncCalls {
+    /// Returns the result of the call to `id` with these arguments, or starts
+    /// it with `start` and returns `None` if it has not been awaited yet.
+    pub(crate) fn call<F>(
+        &self,
+        id: &str,
+        positional: &[FluentValue],
+        named: &FluentArgs,
+        start: F,
+    ) -> Option<Result<FluentValue<'static>, FluentFunctionError>>
+    where
+        F: FnOnce(Vec<FluentValue<'static>>, FluentArgs<'static>) -> AsyncFunctionFuture,
+    {
+        let mut calls = self.calls.lock().unwrap();
+        if let Some(call) = calls
+            .iter()
+            .find(|call| call.id == id && call.positional == positional && &call.named == named)
+        {
+            return call.result.clone();
+        }
+        let positional: Vec<_> = positional.iter().map(FluentValue::into_owned).collect();
+        let named: FluentArgs<'static> = named
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.into_owned()))
+            .collect();
+        let future = start(positional.clone(), named.clone());
+        self.pending.lock().unwrap().push((calls.len(), future));
+        calls.push(AsyncCall {
+            id: id.to_string(),
+            positional,
+            named,
+            result: None,
+        });
+        None
+    }
+
+    /// Returns whether some calls have not been awaited yet.
+    pub(crate) fn has_pending(&self) -> bool {
+        !self.pending.lock().unwrap().is_empty()
+    }
+
+    /// Returns the identifiers of the functions whose calls have no result.
+    pub(crate) fn unresolved(&self) -> Vec<String> {
+        let calls = self.calls.lock().unwrap();
+        calls
+            .iter()
+            .filter(|call| call.result.is_none())
+            .map(|call| call.id.clone())
+            .collect()
+    }
+
+    /// Awaits the pending calls, one after the other.
+    pub(crate) async fn resolve(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        for (idx, future) in pending {
+            let result = future.await;
+            self.calls.lock().unwrap()[idx].result = Some(result);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! `Interner` maps the identifiers of the entries of a `FluentBundle`, and
+//! the identifiers referenced by their patterns, to integer `Symbol`s.
+//!
+//! Entries are keyed by symbol, so that the references resolved while
+//! formatting look up integers instead of hashing strings. The symbol of each
+//! message, term, function and variable reference of the resources is found
+//! when they are added, and is looked up by the address of its identifier.
+
+use fluent_syntax::ast::{self, visit::Visit};
+use rustc_hash::FxHashMap;
+
+use crate::resource::FluentResource;
+
+/// An interned identifier, valid for the [`Interner`] which produced it and
+/// for the interners [forked](Interner::fork) from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Symbol(u32);
+
+/// An identifier looked up in a bundle, along with its symbol, if it has
+/// been interned.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Key<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) symbol: Option<Symbol>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Interner {
+    symbols: FxHashMap<Box<str>, Symbol>,
+    /// The symbols of the identifiers referenced in the patterns of the
+    /// resources, keyed by the address and length of the identifier.
+    references: FxHashMap<(usize, usize), Symbol>,
+}
+
+impl Interner {
+    /// Returns the symbol of `name`, if it has been interned.
+    pub(crate) fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// Returns the symbol of `name`, interning it if needed.
+    ///
+    /// Symbols are allocated sequentially and never freed, so that the
+    /// symbols of a forked interner are the ones of the original, followed by
+    /// its own.
+    pub(crate) fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.get(name) {
+            return symbol;
+        }
+        let symbol = Symbol(
+            u32::try_from(self.symbols.len()).expect("Too many identifiers in a single bundle"),
+        );
+        self.symbols.insert(name.into(), symbol);
+        symbol
+    }
+
+    /// Returns the symbol of the identifier `name` referenced in a pattern of
+    /// the resources, if `name` is the slice of that resource.
+    pub(crate) fn reference(&self, name: &str) -> Option<Symbol> {
+        if self.references.is_empty() {
+            return None;
+        }
+        self.references
+            .get(&(name.as_ptr() as usize, name.len()))
+            .copied()
+    }
+
+    /// Interns the identifiers referenced in the patterns of `res`.
+    ///
+    /// The entries of lazy resources are parsed on demand, so their
+    /// references are looked up by name instead.
+    pub(crate) fn insert_references(&mut self, res: &FluentResource) {
+        if res.is_lazy() {
+            return;
+        }
+        let mut collector = References(vec![]);
+        for entry in res.entries() {
+            collector.visit_entry(entry);
+        }
+        for name in collector.0 {
+            let symbol = self.intern(name);
+            self.references
+                .insert((name.as_ptr() as usize, name.len()), symbol);
+        }
+    }
+
+    /// Forgets the references of the resources, which must be interned again
+    /// once resources are removed, as their addresses may be reused.
+    pub(crate) fn clear_references(&mut self) {
+        self.references.clear();
+    }
+
+    /// Returns an interner with the same symbols, and no references.
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            symbols: self.symbols.clone(),
+            references: FxHashMap::default(),
+        }
+    }
+
+    /// Returns an estimate of the heap memory used by the interner, in bytes.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.symbols.capacity() * std::mem::size_of::<(Box<str>, Symbol)>()
+            + self.symbols.keys().map(|name| name.len()).sum::<usize>()
+            + self.references.capacity() * std::mem::size_of::<((usize, usize), Symbol)>()
+    }
+}
+
+/// Collects the identifiers of the messages, terms, functions and variables
+/// referenced in a resource.
+struct References<'s>(Vec<&'s str>);
+
+impl<'ast, 's: 'ast> Visit<'ast, &'s str> for References<'s> {
+    fn visit_inline_expression(&mut self, node: &'ast ast::InlineExpression<&'s str>) {
+        match node {
+            ast::InlineExpression::MessageReference { id, .. }
+            | ast::InlineExpression::TermReference { id, .. }
+            | ast::InlineExpression::FunctionReference { id, .. }
+            | ast::InlineExpression::VariableReference { id } => self.0.push(id.name),
+            _ => {}
+        }
+        ast::visit::walk_inline_expression(self, node);
+    }
+}
@@ -98,21 +98,80 @@
 //! At the moment it is expected that users will use
 //! the `fluent-bundle` crate directly, while the ecosystem
 //! matures and higher level APIs are being developed.
+//!
+//! # Features
+//!
+//! * `plural-rules` (default) - Matches variant keys such as `one` or `few` against
+//!   numbers using the CLDR plural rules of the bundle locale. Without it, numbers only
+//!   match numeric variant keys, and `intl_pluralrules` and `fluent-langneg` are not built.
+//!   Other plural rules, such as the ones of `icu_plurals`, can be plugged in with
+//!   [`FluentBundle::set_plural_rules`](crate::bundle::FluentBundle::set_plural_rules),
+//!   with or without this feature.
+//! * `bidi-isolation` (default) - Wraps placeables in Unicode isolation marks, see
+//!   [`FluentBundle::set_use_isolating`](crate::bundle::FluentBundle::set_use_isolating).
+//! * `builtins` - Formats numbers as the `NUMBER()` function of the specification does,
+//!   with the digits, separators and signs of the bundle locale from ICU4X, and the `style`
+//!   (`decimal`, `percent` or `currency`), `currency`, `currencyDisplay`, `useGrouping`,
+//!   `signDisplay`, `roundingMode` and digit options. Symbols set with
+//!   [`FluentBundle::set_number_symbols`](crate::bundle::FluentBundle::set_number_symbols)
+//!   take precedence.
+//! * `list-format` - Adds the builtin `LISTFORMAT($list, type: "conjunction", style: "long")`
+//!   function, and joins [`FluentValue::List`] values with the list patterns of the bundle
+//!   locale from ICU4X, such as "a, b, and c" or "a, b, or c". Without it, lists are
+//!   joined in English.
+//! * `relative-time` - Adds the builtin `RELATIVETIME($seconds, style: "long")` function,
+//!   which formats times such as "3 minutes ago" or "in 2 days" with ICU4X.
+//! * `units` - Adds the builtin `UNIT($value, unit: "megabyte", display: "short")` function,
+//!   which formats measurements with the CLDR English unit names, such as "16 MB" or
+//!   "3 kilometers". The builtin `DURATION()` function also formats its fields with it.
+//! * `spellout` - Spells out numbers formatted with `NUMBER($n, notation: "spellout")` or
+//!   `ORDINAL($n, notation: "spellout")`, such as "twenty-one" or "twenty-first". Rules are
+//!   only provided for English; numbers in other languages are written with digits.
+//! * `tracing` - Emits [`tracing`](https://docs.rs/tracing) spans around parsing resources,
+//!   adding them to bundles, constructing memoized formatters and formatting patterns, and
+//!   events with the identifiers of the messages and terms referenced while formatting.
+//! * `serde` - Adds [`FluentArgs::from_serialize`], which creates arguments from the fields
+//!   of any struct implementing `serde::Serialize`.
+//! * `rayon` - Parses resources and builds bundles in parallel.
+//! * `chrono`, `time`, `jiff` - Convert the date and time types of these crates into
+//!   [`FluentDateTime`](crate::types::FluentDateTime) and [`FluentValue`].
+//! * `rust_decimal` - Converts `rust_decimal::Decimal` into
+//!   [`FluentNumber`](crate::types::FluentNumber) and [`FluentValue`] without losing digits.
+//! * `macros` - Re-exports `include_ftl!` and the `FromFluentArgs` and `FromFluentValue`
+//!   derives from `fluent-macros`.
+//!
+//! Disabling the default features gives the smallest build, such as for size-constrained
+//! WebAssembly targets which only need plain interpolation.
+//!
+//! # `no_std`
+//!
+//! Unlike `fluent-syntax`, this crate requires `std` with every set of features. Bundles are
+//! keyed by `unic-langid` language identifiers and memoize formatters with `intl-memoizer`,
+//! and both, like `intl_pluralrules`, are built on `std`. Firmware without `std` can parse
+//! and validate resources with `fluent-syntax` built with `default-features = false`.
 mod args;
+pub mod bidi;
 pub mod builtins;
 pub mod bundle;
 pub mod concurrent;
 mod entry;
 mod errors;
+mod function;
+mod interner;
 #[doc(hidden)]
 pub mod memoizer;
 mod message;
+pub mod overlay;
+pub mod pool;
 #[doc(hidden)]
 pub mod resolver;
 mod resource;
+#[cfg(feature = "serde")]
+mod serialize;
 pub mod types;
+pub mod validate;
 
-pub use args::FluentArgs;
+pub use args::{ArgumentError, FluentArgs, FromFluentArgs, FromFluentValue};
 /// Specialized [`FluentBundle`](crate::bundle::FluentBundle) over
 /// non-concurrent [`IntlLangMemoizer`](intl_memoizer::IntlLangMemoizer).
 ///
@@ -121,8 +180,19 @@ pub use args::FluentArgs;
 /// The concurrent specialization can be constructed with
 /// [`FluentBundle::new_concurrent`](crate::concurrent::FluentBundle::new_concurrent).
 pub type FluentBundle<R> = bundle::FluentBundle<R, intl_memoizer::IntlLangMemoizer>;
-pub use errors::FluentError;
-pub use message::{FluentAttribute, FluentMessage};
-pub use resource::FluentResource;
+pub use errors::{ErrorSink, FluentError};
+pub use function::{AsyncFunctionFuture, FluentFunctionError, FunctionContext};
+pub use message::{FluentAttribute, FluentMessage, VariantTag};
+pub use resource::{CatalogStats, FluentResource, ResourceHandle};
+#[cfg(feature = "serde")]
+pub use serialize::SerializeError;
 #[doc(inline)]
 pub use types::FluentValue;
+
+#[cfg(feature = "macros")]
+pub use fluent_macros::{include_ftl, FromFluentArgs, FromFluentValue};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use fluent_syntax::ast;
+}
@@ -1,5 +1,5 @@
 use crate::types::FluentType;
-use intl_memoizer::Memoizable;
+use intl_memoizer::{Memoizable, MemoryStats};
 use unic_langid::LanguageIdentifier;
 
 /// This trait contains thread-safe methods which extend [`intl_memoizer::IntlLangMemoizer`].
@@ -27,4 +27,10 @@ pub trait MemoizerKind: 'static {
 
     /// Wires up the `as_string` or `as_string_threadsafe` variants for [`FluentType`].
     fn stringify_value(&self, value: &dyn FluentType) -> std::borrow::Cow<'static, str>;
+
+    /// Returns the number of memoized formatters and an estimate of their
+    /// size, see [`intl_memoizer::IntlLangMemoizer::memory_stats`].
+    fn memory_stats(&self) -> MemoryStats {
+        MemoryStats::default()
+    }
 }
@@ -166,9 +166,21 @@ impl<'m> From<&'m ast::Attribute<&'m str>> for FluentAttribute<'m> {
 #[derive(Debug, PartialEq)]
 pub struct FluentMessage<'m> {
     node: &'m ast::Message<&'m str>,
+    value: Option<&'m ast::Pattern<&'m str>>,
 }
 
 impl<'m> FluentMessage<'m> {
+    /// Creates a message whose value is the variant chosen by `selector`.
+    pub(crate) fn with_variant(
+        msg: &'m ast::Message<&'m str>,
+        selector: Option<fn(&str, &[VariantTag]) -> usize>,
+    ) -> Self {
+        FluentMessage {
+            node: msg,
+            value: select_variant(msg, selector),
+        }
+    }
+
     /// Retrieves an option of a [`ast::Pattern`](fluent_syntax::ast::Pattern).
     ///
     /// # Example
@@ -196,7 +208,7 @@ impl<'m> FluentMessage<'m> {
     /// }
     /// ```
     pub fn value(&self) -> Option<&'m ast::Pattern<&'m str>> {
-        self.node.value.as_ref()
+        self.value
     }
 
     /// An iterator over [`FluentAttribute`] elements.
@@ -269,6 +281,55 @@ impl<'m> FluentMessage<'m> {
 
 impl<'m> From<&'m ast::Message<&'m str>> for FluentMessage<'m> {
     fn from(msg: &'m ast::Message<&'m str>) -> Self {
-        FluentMessage { node: msg }
+        FluentMessage {
+            node: msg,
+            value: msg.value.as_ref(),
+        }
+    }
+}
+
+/// The prefix of the attributes which are candidate values of a message, see
+/// [`FluentBundle::set_variant_selector`](crate::bundle::FluentBundle::set_variant_selector).
+const VARIANT_PREFIX: &str = "variant-";
+
+/// A candidate value of a message, passed to the
+/// [variant selector](crate::bundle::FluentBundle::set_variant_selector).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VariantTag<'m> {
+    /// The tag of the variant, which is `default` for the value of the
+    /// message, and `b` for its `.variant-b` attribute.
+    pub name: &'m str,
+}
+
+/// Returns the value of `msg` chosen by `selector` among the value and the
+/// `.variant-*` attributes of `msg`, or the value of `msg` if there is no
+/// selector, `msg` has no variants, or `selector` returns an index out of
+/// range.
+pub(crate) fn select_variant<'m>(
+    msg: &'m ast::Message<&'m str>,
+    selector: Option<fn(&str, &[VariantTag]) -> usize>,
+) -> Option<&'m ast::Pattern<&'m str>> {
+    let Some(selector) = selector else {
+        return msg.value.as_ref();
+    };
+    let variants: Vec<_> = msg
+        .attributes
+        .iter()
+        .filter_map(|attr| {
+            let name = attr.id.name.strip_prefix(VARIANT_PREFIX)?;
+            Some((VariantTag { name }, &attr.value))
+        })
+        .collect();
+    let value = msg.value.as_ref()?;
+    if variants.is_empty() {
+        return Some(value);
+    }
+
+    let tags: Vec<_> = std::iter::once(VariantTag { name: "default" })
+        .chain(variants.iter().map(|(tag, _)| *tag))
+        .collect();
+    match selector(msg.id.name, &tags) {
+        0 => Some(value),
+        idx => Some(variants.get(idx - 1).map_or(value, |(_, pattern)| pattern)),
     }
 }
@@ -0,0 +1,573 @@
+//! `overlay` combines the markup of translations with elements provided by the
+//! caller, like the DOM overlays of `fluent-dom`.
+//!
+//! A translation such as `Click <a data-l10n-name="link">here</a>` may only
+//! contain text-level elements, such as `<em>` and `<strong>`, and the
+//! localizable attributes of its elements, such as `title`. Other elements
+//! need a `data-l10n-name`, and are replaced by the element of the source with
+//! the same name, so that a translation can't change their `href`, their event
+//! handlers and so on. The translation only provides their text and their
+//! localizable attributes. Anything else is reduced to its text.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_bundle::overlay::{Element, Overlay};
+//!
+//! let overlay = Overlay::parse(
+//!     r#"<em onclick="steal()">Click</em> <a data-l10n-name="link" href="https://evil.example">here</a>!"#,
+//! );
+//! let link = Element::new("a")
+//!     .with_attribute("data-l10n-name", "link")
+//!     .with_attribute("href", "https://example.com");
+//!
+//! assert_eq!(
+//!     overlay.to_html(&[link]),
+//!     r#"<em>Click</em> <a data-l10n-name="link" href="https://example.com">here</a>!"#
+//! );
+//! ```
+//!
+//! The values of arguments are part of the formatted pattern, so markup in
+//! them is parsed like the markup of the translation. Escape arguments which
+//! come from users before formatting.
+use std::fmt::Write;
+
+/// The elements which translations may contain without a `data-l10n-name`.
+const TEXT_LEVEL_ELEMENTS: &[&str] = &[
+    "em", "strong", "small", "s", "cite", "q", "dfn", "abbr", "data", "time", "code", "var",
+    "samp", "kbd", "sub", "sup", "i", "b", "u", "mark", "bdi", "bdo", "span", "br", "wbr",
+];
+
+/// The elements which have no content and no closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// The attribute naming an element of the source.
+const L10N_NAME: &str = "data-l10n-name";
+
+/// Returns `true` if translations may set the attribute on the element.
+fn is_localizable_attribute(element: &str, attribute: &str) -> bool {
+    matches!(attribute, "title" | "aria-label" | "aria-valuetext")
+        || matches!(
+            (element, attribute),
+            ("a", "download")
+                | ("area", "download" | "alt")
+                | ("input", "alt" | "placeholder")
+                | (
+                    "menuitem" | "menu" | "optgroup" | "option" | "track",
+                    "label"
+                )
+                | ("img", "alt")
+                | ("textarea", "placeholder")
+                | ("th", "abbr")
+        )
+}
+
+/// A node of an [`Overlay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Text(String),
+    Element(Element),
+}
+
+impl Node {
+    fn write_text_content(&self, out: &mut String) {
+        match self {
+            Self::Text(text) => out.push_str(text),
+            Self::Element(element) => {
+                for child in &element.children {
+                    child.write_text_content(out);
+                }
+            }
+        }
+    }
+}
+
+/// An element, either parsed from a translation or provided by the caller as
+/// one of the sources of [`Overlay::apply`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Element {
+    /// The name of the element, in lowercase.
+    pub name: String,
+    /// The attributes of the element, in order, with their names in
+    /// lowercase.
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Creates an element without attributes and children.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into().to_ascii_lowercase(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets an attribute of the element, replacing its previous value.
+    pub fn with_attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_attribute(name.into().to_ascii_lowercase(), value.into());
+        self
+    }
+
+    /// Returns the value of an attribute of the element.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the `data-l10n-name` of the element.
+    pub fn l10n_name(&self) -> Option<&str> {
+        self.attribute(L10N_NAME)
+    }
+
+    /// Returns the text of the element and of its descendants.
+    pub fn text_content(&self) -> String {
+        let mut out = String::new();
+        for child in &self.children {
+            child.write_text_content(&mut out);
+        }
+        out
+    }
+
+    fn set_attribute(&mut self, name: String, value: String) {
+        match self.attributes.iter_mut().find(|(other, _)| *other == name) {
+            Some((_, old)) => *old = value,
+            None => self.attributes.push((name, value)),
+        }
+    }
+
+    fn is_void(&self) -> bool {
+        VOID_ELEMENTS.contains(&self.name.as_str())
+    }
+}
+
+/// The markup of a formatted translation, reduced to what translations are
+/// allowed to contain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overlay {
+    nodes: Vec<Node>,
+}
+
+impl Overlay {
+    /// Parses the markup of a formatted translation.
+    ///
+    /// Parsing never fails: a `<` which doesn't start a tag is text, closing
+    /// tags without an open element are ignored, and elements left open are
+    /// closed at the end. Character references such as `&amp;` are decoded.
+    ///
+    /// Text-level elements keep their localizable attributes and their
+    /// `data-l10n-name`, and other elements with a `data-l10n-name` are kept
+    /// for [`Overlay::apply`]. Other elements are replaced by their text,
+    /// and comments are removed.
+    pub fn parse(markup: &str) -> Self {
+        let mut nodes = Vec::new();
+        for node in Parser::new(markup).parse() {
+            sanitize(node, &mut nodes);
+        }
+        Self { nodes }
+    }
+
+    /// Returns the nodes of the translation.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns `true` if the translation contains elements.
+    pub fn has_markup(&self) -> bool {
+        self.nodes
+            .iter()
+            .any(|node| matches!(node, Node::Element(_)))
+    }
+
+    /// Returns the text of the translation, without its markup.
+    pub fn text_content(&self) -> String {
+        let mut out = String::new();
+        for node in &self.nodes {
+            node.write_text_content(&mut out);
+        }
+        out
+    }
+
+    /// Combines the translation with the elements of the source.
+    ///
+    /// Each element of the translation with a `data-l10n-name` is replaced by
+    /// the element of `sources` with the same `data-l10n-name` and the same
+    /// name, with the attributes of the source, the localizable attributes of
+    /// the translation and the text of the translation. Elements without such
+    /// a source, or whose source was already used, are replaced by their text.
+    /// Sources which the translation doesn't use are left out.
+    pub fn apply(&self, sources: &[Element]) -> Vec<Node> {
+        let mut used = vec![false; sources.len()];
+        apply_nodes(&self.nodes, sources, &mut used)
+    }
+
+    /// Combines the translation with the elements of the source like
+    /// [`Overlay::apply`], and writes the result as HTML, escaping text and
+    /// attribute values.
+    pub fn to_html(&self, sources: &[Element]) -> String {
+        let mut out = String::new();
+        for node in self.apply(sources) {
+            write_html(&node, &mut out);
+        }
+        out
+    }
+}
+
+fn apply_nodes(nodes: &[Node], sources: &[Element], used: &mut [bool]) -> Vec<Node> {
+    let mut result = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let element = match node {
+            Node::Element(element) => element,
+            Node::Text(_) => {
+                push_node(&mut result, node.clone());
+                continue;
+            }
+        };
+        let Some(l10n_name) = element.l10n_name() else {
+            push_node(
+                &mut result,
+                Node::Element(Element {
+                    children: apply_nodes(&element.children, sources, used),
+                    ..element.clone()
+                }),
+            );
+            continue;
+        };
+        let source = sources.iter().enumerate().find(|(index, source)| {
+            !used[*index] && source.name == element.name && source.l10n_name() == Some(l10n_name)
+        });
+        let node = match source {
+            Some((index, source)) => {
+                used[index] = true;
+                let mut combined = Element {
+                    name: source.name.clone(),
+                    attributes: source.attributes.clone(),
+                    children: vec![],
+                };
+                for (name, value) in &element.attributes {
+                    if is_localizable_attribute(&element.name, name) {
+                        combined.set_attribute(name.clone(), value.clone());
+                    }
+                }
+                let text = element.text_content();
+                if !text.is_empty() {
+                    combined.children.push(Node::Text(text));
+                }
+                Node::Element(combined)
+            }
+            None => Node::Text(element.text_content()),
+        };
+        push_node(&mut result, node);
+    }
+    result
+}
+
+/// Keeps what translations may contain of `node`, see [`Overlay::parse`].
+fn sanitize(node: Node, out: &mut Vec<Node>) {
+    let element = match node {
+        Node::Element(element) => element,
+        node => return push_node(out, node),
+    };
+    let is_text_level = TEXT_LEVEL_ELEMENTS.contains(&element.name.as_str());
+    if !is_text_level && element.l10n_name().is_none() {
+        return push_node(out, Node::Text(element.text_content()));
+    }
+    let name = element.name;
+    let attributes = element
+        .attributes
+        .into_iter()
+        .filter(|(attribute, _)| {
+            attribute == L10N_NAME || is_localizable_attribute(&name, attribute)
+        })
+        .collect();
+    let mut children = Vec::new();
+    for child in element.children {
+        sanitize(child, &mut children);
+    }
+    out.push(Node::Element(Element {
+        name,
+        attributes,
+        children,
+    }));
+}
+
+/// Appends `node`, merging adjacent text.
+fn push_node(nodes: &mut Vec<Node>, node: Node) {
+    if let Node::Text(text) = &node {
+        if text.is_empty() {
+            return;
+        }
+        if let Some(Node::Text(last)) = nodes.last_mut() {
+            last.push_str(text);
+            return;
+        }
+    }
+    nodes.push(node);
+}
+
+fn write_html(node: &Node, out: &mut String) {
+    match node {
+        Node::Text(text) => escape(text, false, out),
+        Node::Element(element) => {
+            out.push('<');
+            out.push_str(&element.name);
+            for (name, value) in &element.attributes {
+                let _ = write!(out, " {}=\"", name);
+                escape(value, true, out);
+                out.push('"');
+            }
+            out.push('>');
+            if element.is_void() {
+                return;
+            }
+            for child in &element.children {
+                write_html(child, out);
+            }
+            let _ = write!(out, "</{}>", element.name);
+        }
+    }
+}
+
+fn escape(text: &str, in_attribute: bool, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if in_attribute => out.push_str("&quot;"),
+            '\u{A0}' => out.push_str("&nbsp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// A lenient parser of the markup of translations.
+struct Parser<'s> {
+    source: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.source[self.pos..]
+    }
+
+    fn parse(mut self) -> Vec<Node> {
+        // The open elements, with the children of the root first.
+        let mut root = Vec::new();
+        let mut open: Vec<Element> = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = self.rest().chars().next() {
+            let tag = if c == '<' { self.tag() } else { None };
+            let Some(tag) = tag else {
+                if c == '&' {
+                    self.pos += decode_reference(self.rest(), &mut text);
+                } else {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                }
+                continue;
+            };
+
+            let children = open
+                .last_mut()
+                .map_or(&mut root, |parent| &mut parent.children);
+            push_node(children, Node::Text(std::mem::take(&mut text)));
+            match tag {
+                Tag::Comment => {}
+                Tag::Start {
+                    element,
+                    self_closing,
+                } => {
+                    if self_closing || element.is_void() {
+                        children.push(Node::Element(element));
+                    } else {
+                        open.push(element);
+                    }
+                }
+                Tag::End(name) => {
+                    if let Some(depth) = open.iter().rposition(|element| element.name == name) {
+                        while open.len() > depth {
+                            close(&mut open, &mut root);
+                        }
+                    }
+                }
+            }
+        }
+
+        let children = open
+            .last_mut()
+            .map_or(&mut root, |parent| &mut parent.children);
+        push_node(children, Node::Text(text));
+        while !open.is_empty() {
+            close(&mut open, &mut root);
+        }
+        root
+    }
+
+    /// Parses the tag at the current position, or returns `None` if the `<`
+    /// doesn't start one.
+    fn tag(&mut self) -> Option<Tag> {
+        let rest = self.rest();
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(comment.len(), |end| end + 3);
+            self.pos += 4 + end;
+            return Some(Tag::Comment);
+        }
+        if let Some(end_tag) = rest.strip_prefix("</") {
+            let name_len = name_length(end_tag);
+            let after = &end_tag[name_len..];
+            let close = after.find('>')?;
+            if name_len == 0 || !after[..close].trim().is_empty() {
+                return None;
+            }
+            self.pos += 2 + name_len + close + 1;
+            return Some(Tag::End(end_tag[..name_len].to_ascii_lowercase()));
+        }
+
+        let start = &rest[1..];
+        let name_len = name_length(start);
+        if name_len == 0 {
+            return None;
+        }
+        let mut element = Element::new(&start[..name_len]);
+        let mut cursor = 1 + name_len;
+        loop {
+            let attrs = &rest[cursor..];
+            let trimmed = attrs.trim_start();
+            cursor += attrs.len() - trimmed.len();
+            if trimmed.starts_with("/>") {
+                self.pos += cursor + 2;
+                return Some(Tag::Start {
+                    element,
+                    self_closing: true,
+                });
+            }
+            if trimmed.starts_with('>') {
+                self.pos += cursor + 1;
+                return Some(Tag::Start {
+                    element,
+                    self_closing: false,
+                });
+            }
+            let attr_len = trimmed
+                .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))
+                .unwrap_or(trimmed.len());
+            if attr_len == 0 {
+                if trimmed.starts_with('/') {
+                    cursor += 1;
+                    continue;
+                }
+                // The tag is never closed.
+                return None;
+            }
+            let name = trimmed[..attr_len].to_ascii_lowercase();
+            cursor += attr_len;
+
+            let after_name = &rest[cursor..];
+            let trimmed = after_name.trim_start();
+            let mut value = String::new();
+            if let Some(after_eq) = trimmed.strip_prefix('=') {
+                let value_start = after_eq.trim_start();
+                cursor += after_name.len() - value_start.len();
+                let raw = match value_start.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = value_start[1..].find(quote)?;
+                        cursor += end + 2;
+                        &value_start[1..=end]
+                    }
+                    _ => {
+                        let end = value_start
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(value_start.len());
+                        cursor += end;
+                        &value_start[..end]
+                    }
+                };
+                decode_references(raw, &mut value);
+            }
+            element.set_attribute(name, value);
+        }
+    }
+}
+
+enum Tag {
+    Start {
+        element: Element,
+        self_closing: bool,
+    },
+    End(String),
+    Comment,
+}
+
+/// Moves the innermost open element into its parent.
+fn close(open: &mut Vec<Element>, root: &mut Vec<Node>) {
+    if let Some(element) = open.pop() {
+        let parent = open.last_mut().map_or(root, |parent| &mut parent.children);
+        parent.push(Node::Element(element));
+    }
+}
+
+/// Returns the length of the element name at the start of `s`.
+fn name_length(s: &str) -> usize {
+    if !s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return 0;
+    }
+    s.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(s.len())
+}
+
+fn decode_references(mut s: &str, out: &mut String) {
+    while let Some(amp) = s.find('&') {
+        out.push_str(&s[..amp]);
+        s = &s[amp..];
+        let len = decode_reference(s, out);
+        s = &s[len..];
+    }
+    out.push_str(s);
+}
+
+/// Decodes the character reference at the start of `s`, which starts with
+/// `&`, and returns its length. An unknown reference is kept as text.
+fn decode_reference(s: &str, out: &mut String) -> usize {
+    let decoded = s[1..].find(';').and_then(|end| {
+        let name = &s[1..=end];
+        let c = match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{A0}',
+            _ => {
+                let code = name.strip_prefix('#')?;
+                let code = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(code)?
+            }
+        };
+        Some((c, end + 2))
+    });
+    match decoded {
+        Some((c, len)) => {
+            out.push(c);
+            len
+        }
+        None => {
+            out.push('&');
+            1
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! `BundlePool` gives every thread its own [`FluentBundle`](crate::FluentBundle)
+//! built from the same shared resources and functions.
+//!
+//! The non-concurrent [`IntlLangMemoizer`](intl_memoizer::IntlLangMemoizer) is
+//! faster than the concurrent one, since it does not lock, but it is not
+//! `Sync`, so a bundle using it cannot be shared between the threads of a
+//! server. A pool constructs one such bundle per thread, on first use, and
+//! keeps it for the following calls on that thread.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Weak};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use unic_langid::LanguageIdentifier;
+
+use crate::args::FluentArgs;
+use crate::builtins::Env;
+use crate::errors::{EntryKind, FluentError};
+use crate::function::{FluentFunctionError, FunctionContext};
+use crate::resource::FluentResource;
+use crate::types::FluentValue;
+use crate::validate::FunctionSignature;
+
+/// The bundle type of a [`BundlePool`].
+pub type PooledBundle = crate::FluentBundle<Arc<FluentResource>>;
+
+type SharedFunction = Arc<
+    dyn for<'a> Fn(
+            &[FluentValue<'a>],
+            &FluentArgs,
+            &FunctionContext,
+        ) -> Result<FluentValue<'a>, FluentFunctionError>
+        + Send
+        + Sync,
+>;
+
+/// Everything a thread needs to build its bundle.
+#[derive(Clone)]
+struct Shared {
+    locales: Vec<LanguageIdentifier>,
+    resources: Vec<Arc<FluentResource>>,
+    functions: Vec<(String, SharedFunction)>,
+    signatures: Vec<(String, FunctionSignature)>,
+    /// The identifiers of the messages, terms and functions, which share a
+    /// single namespace, as in a bundle.
+    ids: FxHashSet<Box<str>>,
+    use_isolating: bool,
+    detect_direction: bool,
+    transform: Option<fn(&str) -> Cow<str>>,
+    /// The environment of the builtins, shared by the bundles of all threads.
+    env: Env,
+}
+
+impl Shared {
+    fn build(&self) -> PooledBundle {
+        let mut bundle = PooledBundle::new(self.locales.clone());
+        bundle.set_use_isolating(self.use_isolating);
+        bundle.set_detect_direction(self.detect_direction);
+        bundle.set_transform(self.transform);
+        for (id, func) in &self.functions {
+            let func = Arc::clone(func);
+            // Duplicate identifiers were rejected by `BundlePool::add_function`.
+            let _ = bundle.add_fallible_function(id, move |positional, named, ctx| {
+                func(positional, named, ctx)
+            });
+        }
+        for (id, signature) in &self.signatures {
+            bundle.add_function_signature(id, signature.clone());
+        }
+        for res in &self.resources {
+            // Overriding errors are reported by `BundlePool::add_resource`.
+            let _ = bundle.add_resource(Arc::clone(res));
+        }
+        bundle
+    }
+}
+
+/// The bundles of a thread, keyed by the address of the `Shared` state of
+/// their pool. The weak reference keeps the address from being reused after
+/// the pool is dropped.
+type ThreadBundles = FxHashMap<usize, (Weak<Shared>, Rc<PooledBundle>)>;
+
+thread_local! {
+    static BUNDLES: RefCell<ThreadBundles> = RefCell::default();
+}
+
+/// A set of resources and functions from which every thread lazily builds its
+/// own non-concurrent bundle.
+///
+/// Resources are shared between the threads through [`Arc`]; only the
+/// bundle, with its entry index and memoized formatters, is duplicated.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use fluent_bundle::pool::BundlePool;
+/// use fluent_bundle::FluentResource;
+/// use unic_langid::langid;
+///
+/// let res = FluentResource::try_new("hello = Hello, { $name }!".to_string())
+///     .expect("Failed to parse an FTL string.");
+///
+/// let mut pool = BundlePool::new(vec![langid!("en-US")]);
+/// pool.set_use_isolating(false);
+/// pool.add_resource(Arc::new(res))
+///     .expect("Failed to add FTL resources to the pool.");
+/// let pool = Arc::new(pool);
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let pool = Arc::clone(&pool);
+///         thread::spawn(move || {
+///             pool.with_bundle(|bundle| {
+///                 let msg = bundle.get_message("hello").expect("Message doesn't exist.");
+///                 let mut args = fluent_bundle::FluentArgs::new();
+///                 args.set("name", "World");
+///                 let mut errors = vec![];
+///                 bundle
+///                     .format_pattern(msg.value().unwrap(), Some(&args), &mut errors)
+///                     .to_string()
+///             })
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), "Hello, World!");
+/// }
+/// ```
+pub struct BundlePool {
+    shared: Arc<Shared>,
+}
+
+impl BundlePool {
+    /// Creates an empty pool whose bundles use `locales`, see
+    /// [`FluentBundle::new`](crate::FluentBundle::new).
+    pub fn new(locales: Vec<LanguageIdentifier>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                locales,
+                resources: vec![],
+                functions: vec![],
+                signatures: vec![],
+                ids: FxHashSet::default(),
+                use_isolating: cfg!(feature = "bidi-isolation"),
+                detect_direction: false,
+                transform: None,
+                env: Env::default(),
+            }),
+        }
+    }
+
+    /// Adds a resource to the bundles of the pool.
+    ///
+    /// As in [`FluentBundle::add_resource`](crate::bundle::FluentBundle::add_resource),
+    /// entries whose identifiers are already in use are ignored and reported
+    /// as errors.
+    pub fn add_resource(&mut self, res: Arc<FluentResource>) -> Result<(), Vec<FluentError>> {
+        let shared = Arc::make_mut(&mut self.shared);
+        let errors: Vec<_> = res
+            .entry_ids()
+            .filter(|(_, id, _)| !shared.ids.insert((*id).into()))
+            .map(|(_, id, kind)| FluentError::Overriding {
+                kind,
+                id: id.to_owned(),
+            })
+            .collect();
+        shared.resources.push(res);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Makes `func` available to the messages of the bundles of the pool, see
+    /// [`FluentBundle::add_function`](crate::bundle::FluentBundle::add_function).
+    pub fn add_function<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
+    {
+        self.add_function_with_ctx(id, move |positional, named, _ctx| func(positional, named))
+    }
+
+    /// Makes `func` available to the messages of the bundles of the pool, and
+    /// passes it the context of the calling bundle, see
+    /// [`FluentBundle::add_function_with_ctx`](crate::bundle::FluentBundle::add_function_with_ctx).
+    pub fn add_function_with_ctx<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs, &FunctionContext) -> FluentValue<'a>
+            + Sync
+            + Send
+            + 'static,
+    {
+        self.add_fallible_function(id, move |positional, named, ctx| {
+            Ok(func(positional, named, ctx))
+        })
+    }
+
+    /// Makes the fallible `func` available to the messages of the bundles of
+    /// the pool, see
+    /// [`FluentBundle::add_fallible_function`](crate::bundle::FluentBundle::add_fallible_function).
+    pub fn add_fallible_function<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(
+                &[FluentValue<'a>],
+                &FluentArgs,
+                &FunctionContext,
+            ) -> Result<FluentValue<'a>, FluentFunctionError>
+            + Sync
+            + Send
+            + 'static,
+    {
+        let shared = Arc::make_mut(&mut self.shared);
+        if !shared.ids.insert(id.into()) {
+            return Err(FluentError::Overriding {
+                kind: EntryKind::Function,
+                id: id.to_owned(),
+            });
+        }
+        shared.functions.push((id.to_owned(), Arc::new(func)));
+        Ok(())
+    }
+
+    /// Checks the calls to the function `id` against `signature` in the
+    /// bundles of the pool, see
+    /// [`FluentBundle::add_function_signature`](crate::bundle::FluentBundle::add_function_signature).
+    pub fn add_function_signature(&mut self, id: &str, signature: FunctionSignature) {
+        let shared = Arc::make_mut(&mut self.shared);
+        shared.signatures.retain(|(existing, _)| existing != id);
+        shared.signatures.push((id.to_owned(), signature));
+    }
+
+    /// Adds the builtin functions described in the [FTL syntax guide] to the
+    /// bundles of the pool, see
+    /// [`FluentBundle::add_builtins`](crate::bundle::FluentBundle::add_builtins).
+    ///
+    /// [FTL syntax guide]: https://projectfluent.org/fluent/guide/functions.html
+    pub fn add_builtins(&mut self) -> Result<(), FluentError> {
+        crate::builtins::add_builtins(self)
+    }
+
+    /// Adds the builtin functions which are not part of the specification to
+    /// the bundles of the pool, see
+    /// [`FluentBundle::add_builtins_extended`](crate::bundle::FluentBundle::add_builtins_extended).
+    pub fn add_builtins_extended(&mut self) -> Result<(), Vec<FluentError>> {
+        let errors = crate::builtins::add_builtins_extended(self);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Sets the value of `key` returned by the builtin `ENV("key")` function, in
+    /// the bundles of all threads, see
+    /// [`FluentBundle::set_env`](crate::bundle::FluentBundle::set_env).
+    pub fn set_env(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.shared
+            .env
+            .write()
+            .unwrap()
+            .insert(key.into(), value.into());
+    }
+
+    /// See [`FluentBundle::set_use_isolating`](crate::bundle::FluentBundle::set_use_isolating).
+    pub fn set_use_isolating(&mut self, value: bool) {
+        Arc::make_mut(&mut self.shared).use_isolating = value;
+    }
+
+    /// See [`FluentBundle::set_detect_direction`](crate::bundle::FluentBundle::set_detect_direction).
+    pub fn set_detect_direction(&mut self, value: bool) {
+        Arc::make_mut(&mut self.shared).detect_direction = value;
+    }
+
+    /// See [`FluentBundle::set_transform`](crate::bundle::FluentBundle::set_transform).
+    pub fn set_transform(&mut self, func: Option<fn(&str) -> Cow<str>>) {
+        Arc::make_mut(&mut self.shared).transform = func;
+    }
+
+    /// Calls `f` with the bundle of the current thread, building it first if
+    /// this thread has not used the pool yet, or if the pool changed since.
+    pub fn with_bundle<T>(&self, f: impl FnOnce(&PooledBundle) -> T) -> T {
+        let key = Arc::as_ptr(&self.shared) as usize;
+        let bundle = BUNDLES.with(|bundles| {
+            let mut bundles = bundles.borrow_mut();
+            if let Some((_, bundle)) = bundles.get(&key) {
+                return Rc::clone(bundle);
+            }
+            // Drop the bundles of pools which no longer exist, before they
+            // accumulate in long-lived threads.
+            bundles.retain(|_, (shared, _)| shared.strong_count() > 0);
+            let bundle = Rc::new(self.shared.build());
+            bundles.insert(key, (Arc::downgrade(&self.shared), Rc::clone(&bundle)));
+            bundle
+        });
+        f(&bundle)
+    }
+}
+
+impl crate::builtins::Functions for BundlePool {
+    fn env(&self) -> Env {
+        self.shared.env.clone()
+    }
+
+    fn first_locale(&self) -> Option<LanguageIdentifier> {
+        self.shared.locales.first().cloned()
+    }
+
+    fn add_builtin<F>(&mut self, id: &str, func: F) -> Result<(), FluentError>
+    where
+        F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Sync + Send + 'static,
+    {
+        self.add_function(id, func)
+    }
+
+    fn add_builtin_signature(&mut self, id: &str, signature: FunctionSignature) {
+        self.add_function_signature(id, signature);
+    }
+}
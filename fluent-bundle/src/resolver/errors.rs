@@ -1,3 +1,5 @@
+use crate::function::FluentFunctionError;
+use crate::validate::FunctionSignatureError;
 use fluent_syntax::ast::InlineExpression;
 use std::error::Error;
 
@@ -56,6 +58,26 @@ pub enum ResolverError {
     MissingDefault,
     Cyclic,
     TooManyPlaceables,
+    TooDeep,
+    /// A reference to a message through a deprecated alias, see
+    /// [`FluentBundle::add_alias`](crate::bundle::FluentBundle::add_alias).
+    Deprecated {
+        id: String,
+        replacement: String,
+    },
+    /// A call to a function which failed, see
+    /// [`FluentBundle::add_fallible_function`](crate::bundle::FluentBundle::add_fallible_function).
+    Function {
+        id: String,
+        error: FluentFunctionError,
+    },
+    /// A call to a function which does not match its signature, see
+    /// [`FluentBundle::add_function_signature`](crate::bundle::FluentBundle::add_function_signature).
+    FunctionSignature(FunctionSignatureError),
+    /// A placeable which the parser could not parse, kept by the
+    /// `recover-placeables` or `unknown-expressions` features of
+    /// `fluent-syntax`. It is formatted as `{???}`.
+    InvalidPlaceable,
 }
 
 impl std::fmt::Display for ResolverError {
@@ -85,6 +107,13 @@ impl std::fmt::Display for ResolverError {
             Self::MissingDefault => f.write_str("No default"),
             Self::Cyclic => f.write_str("Cyclical dependency detected"),
             Self::TooManyPlaceables => f.write_str("Too many placeables"),
+            Self::TooDeep => f.write_str("Too many nested references"),
+            Self::Deprecated { id, replacement } => {
+                write!(f, "Deprecated message: {}, use {} instead", id, replacement)
+            }
+            Self::Function { id, error } => write!(f, "Function {}() failed: {}", id, error),
+            Self::FunctionSignature(error) => error.fmt(f),
+            Self::InvalidPlaceable => f.write_str("Invalid placeable"),
         }
     }
 }
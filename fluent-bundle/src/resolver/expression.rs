@@ -7,9 +7,7 @@ use std::fmt;
 use fluent_syntax::ast;
 
 use crate::memoizer::MemoizerKind;
-use crate::resolver::{ResolveValue, ResolverError};
 use crate::resource::FluentResource;
-use crate::types::FluentValue;
 
 impl<'bundle> WriteValue<'bundle> for ast::Expression<&'bundle str> {
     fn write<'ast, 'args, 'errors, W, R, M>(
@@ -22,36 +20,7 @@ impl<'bundle> WriteValue<'bundle> for ast::Expression<&'bundle str> {
         R: Borrow<FluentResource>,
         M: MemoizerKind,
     {
-        match self {
-            Self::Inline(exp) => exp.write(w, scope),
-            Self::Select { selector, variants } => {
-                let selector = selector.resolve(scope);
-                match selector {
-                    FluentValue::String(_) | FluentValue::Number(_) => {
-                        for variant in variants {
-                            let key = match variant.key {
-                                ast::VariantKey::Identifier { name } => name.into(),
-                                ast::VariantKey::NumberLiteral { value } => {
-                                    FluentValue::try_number(value)
-                                }
-                            };
-                            if key.matches(&selector, scope) {
-                                return variant.value.write(w, scope);
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-
-                for variant in variants {
-                    if variant.default {
-                        return variant.value.write(w, scope);
-                    }
-                }
-                scope.add_error(ResolverError::MissingDefault);
-                Ok(())
-            }
-        }
+        scope.run(w, |scope, w, tasks| scope.write_expression(w, self, tasks))
     }
 
     fn write_error<W>(&self, w: &mut W) -> fmt::Result
@@ -8,7 +8,6 @@ use fluent_syntax::ast;
 use fluent_syntax::unicode::{unescape_unicode, unescape_unicode_to_string};
 
 use crate::bundle::FormatterPass;
-use crate::entry::GetEntry;
 use crate::memoizer::MemoizerKind;
 use crate::resource::FluentResource;
 use crate::types::FluentValue;
@@ -26,89 +25,47 @@ impl<'bundle> WriteValue<'bundle> for ast::InlineExpression<&'bundle str> {
     {
         match self {
             Self::StringLiteral { value } => unescape_unicode(w, value),
-            Self::MessageReference { id, attribute } => {
-                if let Some(msg) = scope.bundle.get_entry_message(id.name) {
-                    if let Some(attr) = attribute {
-                        msg.attributes
-                            .iter()
-                            .find_map(|a| {
-                                if a.id.name == attr.name {
-                                    Some(scope.track(w, &a.value, self))
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap_or_else(|| scope.write_ref_error(w, self))
-                    } else {
-                        msg.value
-                            .as_ref()
-                            .map(|value| scope.track(w, value, self))
-                            .unwrap_or_else(|| {
-                                scope.add_error(ResolverError::NoValue(id.name.to_string()));
-                                w.write_char('{')?;
-                                self.write_error(w)?;
-                                w.write_char('}')
-                            })
-                    }
-                } else {
-                    scope.write_ref_error(w, self)
-                }
-            },
+            Self::MessageReference { .. } | Self::TermReference { .. } | Self::Placeable { .. } => {
+                scope.run(w, |scope, w, tasks| {
+                    scope.write_inline_expression(w, self, tasks)
+                })
+            }
             Self::NumberLiteral { value } => {
                 FluentValue::try_number(value).write(w, scope, FormatterPass::Inner)
-            },
-            Self::TermReference {
-                id,
-                attribute,
-                arguments,
-            } => {
-                let (_, resolved_named_args) = scope.get_arguments(arguments.as_ref());
-
-                scope.local_args = Some(resolved_named_args);
-                let result = scope
-                    .bundle
-                    .get_entry_term(id.name)
-                    .and_then(|term| {
-                        if let Some(attr) = attribute {
-                            term.attributes.iter().find_map(|a| {
-                                if a.id.name == attr.name {
-                                    Some(scope.track(w, &a.value, self))
-                                } else {
-                                    None
-                                }
-                            })
-                        } else {
-                            Some(scope.track(w, &term.value, self))
-                        }
-                    })
-                    .unwrap_or_else(|| scope.write_ref_error(w, self));
-                scope.local_args = None;
-                result
             }
             Self::FunctionReference { id, arguments } => {
-                let (resolved_positional_args, resolved_named_args) =
-                    scope.get_arguments(Some(arguments));
-
-                let func = scope.bundle.get_entry_function(id.name);
+                let result = scope.with_arena(|scope, arena| {
+                    let (resolved_positional_args, resolved_named_args) =
+                        scope.get_arguments_in(Some(arguments), arena);
+                    scope.call_function(id.name, &resolved_positional_args, &resolved_named_args)
+                });
 
-                if let Some(func) = func {
-                    let result = func(resolved_positional_args.as_slice(), &resolved_named_args);
-                    if let FluentValue::Error = result {
-                        self.write_error(w)
-                    } else {
-                        w.write_str(&result.into_string(scope, FormatterPass::Inner))
+                if let Some(result) = result {
+                    match result {
+                        Ok(FluentValue::Error) => self.write_error(w),
+                        Ok(result) => w.write_str(&result.into_string(scope, FormatterPass::Inner)),
+                        Err(error) => {
+                            scope.add_error(ResolverError::Function {
+                                id: id.name.to_string(),
+                                error,
+                            });
+                            w.write_char('{')?;
+                            self.write_error(w)?;
+                            w.write_char('}')
+                        }
                     }
                 } else {
                     scope.write_ref_error(w, self)
                 }
             }
             Self::VariableReference { id } => {
-                let args = scope.local_args.as_ref().or(scope.args);
-
-                if let Some(arg) = args.and_then(|args| args.get(id.name)) {
+                if let Some(arg) = scope.get_variable(id.name) {
                     arg.write(w, scope, FormatterPass::Inner)
                 } else {
                     if scope.local_args.is_none() {
+                        if let Some(value) = scope.resolve_unknown_reference(self) {
+                            return value.write(w, scope, FormatterPass::Inner);
+                        }
                         scope.add_error(self.into());
                     }
                     w.write_char('{')?;
@@ -116,7 +73,14 @@ impl<'bundle> WriteValue<'bundle> for ast::InlineExpression<&'bundle str> {
                     w.write_char('}')
                 }
             }
-            Self::Placeable { expression } => expression.write(w, scope),
+            // The unknown expressions kept by the `unknown-expressions`
+            // feature of `fluent-syntax`, whose syntax the parser reported.
+            _ => {
+                scope.add_error(ResolverError::InvalidPlaceable);
+                w.write_char('{')?;
+                self.write_error(w)?;
+                w.write_char('}')
+            }
         }
     }
 
@@ -145,7 +109,10 @@ impl<'bundle> WriteValue<'bundle> for ast::InlineExpression<&'bundle str> {
             } => write!(w, "-{}", id.name),
             Self::FunctionReference { id, .. } => write!(w, "{}()", id.name),
             Self::VariableReference { id } => write!(w, "${}", id.name),
-            _ => unreachable!(),
+            // Literals and placeables are never written as errors. Unknown
+            // expressions are written like the invalid placeables kept by
+            // the `recover-placeables` feature of `fluent-syntax`.
+            _ => w.write_str("???"),
         }
     }
 }
@@ -164,36 +131,49 @@ impl<'bundle> ResolveValue<'bundle> for ast::InlineExpression<&'bundle str> {
             Self::NumberLiteral { value } => FluentValue::try_number(value),
             Self::VariableReference { id } => {
                 if let Some(local_args) = &scope.local_args {
-                    if let Some(arg) = local_args.get(id.name) {
+                    if let Some(arg) = local_args.get_by_str(id.name) {
                         return arg.clone();
                     }
-                } else if let Some(arg) = scope.args.and_then(|args| args.get(id.name)) {
+                } else if let Some(arg) = scope.get_arg(id.name) {
                     return arg.into_owned();
                 }
 
                 if scope.local_args.is_none() {
+                    if let Some(value) = scope.resolve_unknown_reference(self) {
+                        return value;
+                    }
                     scope.add_error(self.into());
                 }
                 FluentValue::Error
             }
             Self::FunctionReference { id, arguments } => {
-                let (resolved_positional_args, resolved_named_args) =
-                    scope.get_arguments(Some(arguments));
-
-                let func = scope.bundle.get_entry_function(id.name);
+                let result = scope.with_arena(|scope, arena| {
+                    let (resolved_positional_args, resolved_named_args) =
+                        scope.get_arguments_in(Some(arguments), arena);
+                    scope.call_function(id.name, &resolved_positional_args, &resolved_named_args)
+                });
 
-                if let Some(func) = func {
-                    let result = func(resolved_positional_args.as_slice(), &resolved_named_args);
-                    result
+                if let Some(result) = result {
+                    result.unwrap_or_else(|error| {
+                        scope.add_error(ResolverError::Function {
+                            id: id.name.to_string(),
+                            error,
+                        });
+                        FluentValue::Error
+                    })
                 } else {
                     FluentValue::Error
                 }
             }
-            _ => {
+            Self::MessageReference { .. } | Self::TermReference { .. } | Self::Placeable { .. } => {
                 let mut result = String::new();
                 self.write(&mut result, scope).expect("Failed to write");
                 result.into()
             }
+            _ => {
+                scope.add_error(ResolverError::InvalidPlaceable);
+                FluentValue::Error
+            }
         }
     }
 }
@@ -11,6 +11,7 @@ mod scope;
 
 pub use errors::ResolverError;
 pub use scope::Scope;
+pub(crate) use scope::DEFAULT_MAX_DEPTH;
 
 use std::borrow::Borrow;
 use std::fmt;
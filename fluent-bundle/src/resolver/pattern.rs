@@ -1,5 +1,5 @@
-use super::scope::Scope;
-use super::{ResolverError, WriteValue};
+use super::scope::{Scope, Task};
+use super::WriteValue;
 
 use std::borrow::Borrow;
 use std::fmt;
@@ -11,8 +11,6 @@ use crate::resolver::ResolveValue;
 use crate::resource::FluentResource;
 use crate::types::FluentValue;
 
-const MAX_PLACEABLES: u8 = 100;
-
 impl<'bundle> WriteValue<'bundle> for ast::Pattern<&'bundle str> {
     fn write<'ast, 'args, 'errors, W, R, M>(
         &'ast self,
@@ -24,52 +22,13 @@ impl<'bundle> WriteValue<'bundle> for ast::Pattern<&'bundle str> {
         R: Borrow<FluentResource>,
         M: MemoizerKind,
     {
-        let len = self.elements.len();
-
-        for elem in &self.elements {
-            if scope.dirty {
-                return Ok(());
-            }
-
-            match elem {
-                ast::PatternElement::TextElement { value } => {
-                    if let Some(ref transform) = scope.bundle.transform {
-                        w.write_str(&transform(value))?;
-                    } else {
-                        w.write_str(value)?;
-                    }
-                }
-                ast::PatternElement::Placeable { ref expression } => {
-                    scope.placeables += 1;
-                    if scope.placeables > MAX_PLACEABLES {
-                        scope.dirty = true;
-                        scope.add_error(ResolverError::TooManyPlaceables);
-                        return Ok(());
-                    }
-
-                    let needs_isolation = scope.bundle.use_isolating
-                        && len > 1
-                        && !matches!(
-                            expression,
-                            ast::Expression::Inline(ast::InlineExpression::MessageReference { .. },)
-                                | ast::Expression::Inline(
-                                    ast::InlineExpression::TermReference { .. },
-                                )
-                                | ast::Expression::Inline(
-                                    ast::InlineExpression::StringLiteral { .. },
-                                )
-                        );
-                    if needs_isolation {
-                        w.write_char('\u{2068}')?;
-                    }
-                    scope.maybe_track(w, self, expression)?;
-                    if needs_isolation {
-                        w.write_char('\u{2069}')?;
-                    }
-                }
-            }
-        }
-        Ok(())
+        scope.run(w, |_, _, tasks| {
+            tasks.push(Task::Elements {
+                pattern: self,
+                index: 0,
+            });
+            Ok(())
+        })
     }
 
     fn write_error<W>(&self, _w: &mut W) -> fmt::Result
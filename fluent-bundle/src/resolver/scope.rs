@@ -1,11 +1,55 @@
-use crate::bundle::FluentBundle;
+use crate::bidi::detect_direction;
+use crate::bundle::{FluentBundle, FormattedPart, FormatterPass};
+use crate::entry::Entry;
+use crate::function::{AsyncCalls, FluentFunctionError, FunctionContext};
+use crate::interner::Symbol;
 use crate::memoizer::MemoizerKind;
+use crate::message::select_variant;
 use crate::resolver::{ResolveValue, ResolverError, WriteValue};
-use crate::types::FluentValue;
-use crate::{FluentArgs, FluentError, FluentResource};
+use crate::types::{FluentNumber, FluentValue, PluralCategory};
+#[cfg(feature = "plural-rules")]
+use crate::types::{FluentNumberType, PluralRules};
+use crate::{ErrorSink, FluentArgs, FluentError, FluentResource};
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
 use fluent_syntax::ast;
+#[cfg(feature = "plural-rules")]
+use intl_pluralrules::PluralRuleType;
+use once_cell::unsync::OnceCell;
 use std::borrow::Borrow;
 use std::fmt;
+use unic_langid::LanguageIdentifier;
+
+/// The maximum number of placeables resolved in a single call, which protects
+/// against the Billion Laughs and Quadratic Blowup attacks.
+const MAX_PLACEABLES: u8 = 100;
+
+/// The default maximum depth of nested message and term references, see
+/// [`FluentBundle::set_max_depth`].
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 100;
+
+/// The stack of tasks of [`Scope::run`], allocated in the scope arena.
+pub(super) type Tasks<'arena, 'ast, 'bundle> = BumpVec<'arena, Task<'ast, 'bundle>>;
+
+/// A unit of work of [`Scope::run`].
+pub(super) enum Task<'ast, 'bundle> {
+    /// Write the elements of `pattern`, starting at `index`.
+    Elements {
+        pattern: &'ast ast::Pattern<&'bundle str>,
+        index: usize,
+    },
+    /// Close a placeable, writing the fallback for `expression` if resolving
+    /// it failed.
+    EndPlaceable {
+        expression: &'ast ast::Expression<&'bundle str>,
+        isolated: bool,
+    },
+    /// Leave a referenced pattern, restoring the previous local arguments if
+    /// they were replaced.
+    EndReference {
+        local_args: Option<Option<FluentArgs<'bundle>>>,
+    },
+}
 
 /// State for a single `ResolveValue::to_value` call.
 pub struct Scope<'bundle, 'ast, 'args, 'errors, R, M> {
@@ -13,6 +57,9 @@ pub struct Scope<'bundle, 'ast, 'args, 'errors, R, M> {
     pub bundle: &'bundle FluentBundle<R, M>,
     /// The current arguments passed by the developer.
     pub(super) args: Option<&'args FluentArgs<'args>>,
+    /// The symbols of the names of `args` in the interner of the bundle, see
+    /// [`Scope::get_arg`].
+    arg_symbols: OnceCell<smallvec::SmallVec<[(Symbol, &'args FluentValue<'args>); 4]>>,
     /// Local args
     pub(super) local_args: Option<FluentArgs<'bundle>>,
     /// The running count of resolved placeables. Used to detect the Billion
@@ -21,9 +68,18 @@ pub struct Scope<'bundle, 'ast, 'args, 'errors, R, M> {
     /// Tracks hashes to prevent infinite recursion.
     traveled: smallvec::SmallVec<[&'ast ast::Pattern<&'bundle str>; 2]>,
     /// Track errors accumulated during resolving.
-    pub errors: Option<&'errors mut Vec<FluentError>>,
+    pub errors: Option<&'errors mut dyn ErrorSink>,
     /// Makes the resolver bail.
     pub dirty: bool,
+    /// Backs the temporaries of the resolution, such as task stacks and
+    /// positional arguments. It is dropped along with the scope, once every
+    /// `format_pattern` call.
+    arena: Bump,
+    /// The index in the locales of the bundle of the locale formatted in.
+    locale: usize,
+    /// The calls to async functions, when formatting with
+    /// [`FluentBundle::format_pattern_async`].
+    async_calls: Option<&'args AsyncCalls>,
 }
 
 impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R, M> {
@@ -31,21 +87,227 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         bundle: &'bundle FluentBundle<R, M>,
         args: Option<&'args FluentArgs>,
         errors: Option<&'errors mut Vec<FluentError>>,
+    ) -> Self {
+        Self::with_error_sink(
+            bundle,
+            args,
+            errors.map(|errors| errors as &mut dyn ErrorSink),
+        )
+    }
+
+    /// Creates a scope which reports its errors to `errors`, which may be any
+    /// [`ErrorSink`] rather than a `Vec`.
+    pub fn with_error_sink(
+        bundle: &'bundle FluentBundle<R, M>,
+        args: Option<&'args FluentArgs>,
+        errors: Option<&'errors mut dyn ErrorSink>,
     ) -> Self {
         Scope {
             bundle,
             args,
+            arg_symbols: OnceCell::new(),
             local_args: None,
             placeables: 0,
             traveled: Default::default(),
             errors,
             dirty: false,
+            arena: Bump::new(),
+            locale: 0,
+            async_calls: None,
+        }
+    }
+
+    /// Records the calls to async functions in `calls`, and uses their
+    /// results once they are awaited.
+    pub(crate) fn with_async_calls(mut self, calls: &'args AsyncCalls) -> Self {
+        self.async_calls = Some(calls);
+        self
+    }
+
+    /// Formats in the locale at `index` in the locales of the bundle, which
+    /// must have a memoizer.
+    pub(crate) fn in_locale(mut self, index: usize) -> Self {
+        self.locale = index;
+        self
+    }
+
+    /// Returns the locale formatted in.
+    pub(crate) fn locale(&self) -> Option<&'bundle LanguageIdentifier> {
+        self.bundle.locales.get(self.locale)
+    }
+
+    /// Returns the memoizer of the locale formatted in.
+    pub(crate) fn intls(&self) -> &'bundle M {
+        match self.locale.checked_sub(1) {
+            Some(index) => &self.bundle.locale_intls[index],
+            None => &self.bundle.intls,
         }
     }
 
+    /// Returns the context passed to the functions called while formatting.
+    pub(crate) fn function_context(&self) -> FunctionContext<'bundle>
+    where
+        M: MemoizerKind,
+    {
+        FunctionContext::new(&self.bundle.locales, self.locale, self.intls())
+    }
+
+    /// Calls the function `id` with the resolved arguments, or returns `None`
+    /// if there is no such function.
+    ///
+    /// Calls which do not match the signature of the function report their
+    /// errors and return [`FluentValue::Error`], and calls to async functions
+    /// which have not been awaited yet return [`FluentValue::None`].
+    pub(super) fn call_function<'a>(
+        &mut self,
+        id: &str,
+        positional: &[FluentValue<'a>],
+        named: &FluentArgs,
+    ) -> Option<Result<FluentValue<'a>, FluentFunctionError>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let (bundle, entry) = self.bundle.get_entry(self.bundle.reference_key(id))?;
+        if !matches!(entry, Entry::Function(_) | Entry::AsyncFunction(_)) {
+            return None;
+        }
+        if let Some(signature) = bundle.function_signatures.get(id) {
+            let errors = signature.check(id, positional, named);
+            if !errors.is_empty() {
+                for error in errors {
+                    self.add_error(ResolverError::FunctionSignature(error));
+                }
+                return Some(Ok(FluentValue::Error));
+            }
+        }
+        let ctx = self.function_context();
+        match entry {
+            Entry::Function(func) => Some(func(positional, named, &ctx)),
+            Entry::AsyncFunction(func) => {
+                let Some(calls) = self.async_calls else {
+                    return Some(Err(FluentFunctionError::new(
+                        "Async functions can only be called with format_pattern_async",
+                    )));
+                };
+                let result = calls.call(id, positional, named, |positional, named| {
+                    func(positional, named, &ctx)
+                });
+                Some(result.unwrap_or(Ok(FluentValue::None)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the argument `name` passed by the developer.
+    ///
+    /// The names of the arguments are interned the first time one is looked
+    /// up, so that the following lookups compare their symbols with the one
+    /// of the reference, found by [`FluentBundle::reference_key`].
+    pub(super) fn get_arg(&self, name: &str) -> Option<&'args FluentValue<'args>> {
+        let args = self.args?;
+        let Some(symbol) = self.bundle.reference_key(name).symbol else {
+            return args.get_by_str(name);
+        };
+        let symbols = self.arg_symbols.get_or_init(|| {
+            args.iter()
+                .filter_map(|(name, value)| Some((self.bundle.interner.get(name)?, value)))
+                .collect()
+        });
+        symbols
+            .iter()
+            .find(|(arg_symbol, _)| *arg_symbol == symbol)
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns the value of the variable `name`: the local argument of the
+    /// term being formatted, or else the argument passed by the developer.
+    pub(super) fn get_variable(&self, name: &str) -> Option<&FluentValue<'_>> {
+        match &self.local_args {
+            Some(local_args) => local_args.get_by_str(name),
+            None => self.get_arg(name),
+        }
+    }
+
+    /// Returns the plural category of `number` in the locale formatted in,
+    /// from the [plural rules backend](FluentBundle::set_plural_rules) of the
+    /// bundle, or else from the builtin plural rules.
+    pub(crate) fn plural_category(&self, number: &FluentNumber) -> Option<PluralCategory>
+    where
+        M: MemoizerKind,
+    {
+        if let Some(backend) = &self.bundle.plural_rules {
+            return backend.category(self.locale()?, number);
+        }
+        #[cfg(feature = "plural-rules")]
+        {
+            let r#type = match number.options.r#type {
+                FluentNumberType::Cardinal => PluralRuleType::CARDINAL,
+                FluentNumberType::Ordinal => PluralRuleType::ORDINAL,
+            };
+            self.intls()
+                .with_try_get_threadsafe::<PluralRules, _, _>((r#type,), |pr| pr.0.select(number))
+                .unwrap()
+                .ok()
+                .map(Into::into)
+        }
+        #[cfg(not(feature = "plural-rules"))]
+        None
+    }
+
+    /// Calls `f` with the arena of this scope, which is reset afterwards.
+    ///
+    /// The arena is taken out of the scope meanwhile, so nested calls use
+    /// another one, which is reused by the nested calls which follow.
+    pub(super) fn with_arena<T, F>(&mut self, f: F) -> T
+    where
+        F: FnOnce(&mut Self, &Bump) -> T,
+    {
+        let mut arena = std::mem::take(&mut self.arena);
+        let result = f(self, &arena);
+        arena.reset();
+        self.arena = arena;
+        result
+    }
+
+    /// Returns the mark opening the isolate of a placeable, see
+    /// [`FluentBundle::set_detect_direction`].
+    fn isolate_mark(&self, expression: &ast::Expression<&str>) -> char {
+        if self.bundle.detect_direction {
+            if let ast::Expression::Inline(ast::InlineExpression::VariableReference { id }) =
+                expression
+            {
+                if let Some(FluentValue::String(s)) = self.get_variable(id.name) {
+                    return detect_direction(s).isolate_mark();
+                }
+            }
+        }
+        '\u{2068}'
+    }
+
+    /// Returns `true` if the placeable of `expression` in `pattern` is wrapped
+    /// in isolation marks.
+    fn is_isolated(
+        &self,
+        pattern: &ast::Pattern<&str>,
+        expression: &ast::Expression<&str>,
+    ) -> bool {
+        cfg!(feature = "bidi-isolation")
+            && self.bundle.use_isolating
+            && pattern.elements.len() > 1
+            && !matches!(
+                expression,
+                ast::Expression::Inline(
+                    ast::InlineExpression::MessageReference { .. }
+                        | ast::InlineExpression::TermReference { .. }
+                        | ast::InlineExpression::StringLiteral { .. }
+                )
+            )
+    }
+
     pub fn add_error(&mut self, error: ResolverError) {
         if let Some(errors) = self.errors.as_mut() {
-            errors.push(error.into());
+            errors.push_error(error.into());
         }
     }
 
@@ -68,14 +330,13 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         if self.traveled.is_empty() {
             self.traveled.push(pattern);
         }
-        exp.write(w, self)?;
-        if self.dirty {
-            w.write_char('{')?;
-            exp.write_error(w)?;
-            w.write_char('}')
-        } else {
-            Ok(())
-        }
+        self.run(w, |scope, w, tasks| {
+            tasks.push(Task::EndPlaceable {
+                expression: exp,
+                isolated: false,
+            });
+            scope.write_expression(w, exp, tasks)
+        })
     }
 
     pub fn track<W>(
@@ -88,17 +349,415 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
         R: Borrow<FluentResource>,
         W: fmt::Write,
         M: MemoizerKind,
+    {
+        self.run(w, |scope, w, tasks| {
+            scope.enter(w, pattern, exp, None, tasks)
+        })
+    }
+
+    /// Schedules writing the referenced `pattern`, unless it is already being
+    /// written, which would be a cycle, or the reference is nested too deeply.
+    ///
+    /// `local_args` replace the current local arguments until the pattern is
+    /// written, which is how terms receive their parameters.
+    fn enter<W>(
+        &mut self,
+        w: &mut W,
+        pattern: &'ast ast::Pattern<&'bundle str>,
+        exp: &'ast ast::InlineExpression<&'bundle str>,
+        local_args: Option<FluentArgs<'bundle>>,
+        tasks: &mut Tasks<'_, 'ast, 'bundle>,
+    ) -> fmt::Result
+    where
+        W: fmt::Write,
     {
         if self.traveled.contains(&pattern) {
             self.add_error(ResolverError::Cyclic);
             w.write_char('{')?;
             exp.write_error(w)?;
-            w.write_char('}')
-        } else {
-            self.traveled.push(pattern);
-            let result = pattern.write(w, self);
-            self.traveled.pop();
-            result
+            return w.write_char('}');
+        }
+        if self.traveled.len() > self.bundle.max_depth {
+            self.add_error(ResolverError::TooDeep);
+            w.write_char('{')?;
+            exp.write_error(w)?;
+            return w.write_char('}');
+        }
+
+        #[cfg(feature = "tracing")]
+        match exp {
+            ast::InlineExpression::MessageReference { id, attribute } => tracing::trace!(
+                id = id.name,
+                attribute = attribute.as_ref().map(|attr| attr.name),
+                "resolving message reference"
+            ),
+            ast::InlineExpression::TermReference { id, attribute, .. } => tracing::trace!(
+                id = id.name,
+                attribute = attribute.as_ref().map(|attr| attr.name),
+                "resolving term reference"
+            ),
+            _ => {}
+        }
+        self.traveled.push(pattern);
+        let local_args = local_args.map(|args| self.local_args.replace(args));
+        tasks.push(Task::EndReference { local_args });
+        tasks.push(Task::Elements { pattern, index: 0 });
+        Ok(())
+    }
+
+    /// Writes patterns using an explicit stack of tasks rather than recursion,
+    /// so that the depth of nested references is bounded by
+    /// [`FluentBundle::set_max_depth`] rather than by the size of the call stack.
+    ///
+    /// `schedule` pushes the initial tasks, or writes directly to `w`.
+    pub(super) fn run<W, S>(&mut self, w: &mut W, schedule: S) -> fmt::Result
+    where
+        R: Borrow<FluentResource>,
+        W: fmt::Write,
+        M: MemoizerKind,
+        S: FnOnce(&mut Self, &mut W, &mut Tasks<'_, 'ast, 'bundle>) -> fmt::Result,
+    {
+        self.with_arena(|scope, arena| scope.run_in(w, schedule, arena))
+    }
+
+    /// Runs the tasks scheduled by `schedule`, see [`Scope::run`], with the
+    /// stack of tasks allocated in `arena`.
+    fn run_in<W, S>(&mut self, w: &mut W, schedule: S, arena: &Bump) -> fmt::Result
+    where
+        R: Borrow<FluentResource>,
+        W: fmt::Write,
+        M: MemoizerKind,
+        S: FnOnce(&mut Self, &mut W, &mut Tasks<'_, 'ast, 'bundle>) -> fmt::Result,
+    {
+        let mut tasks = BumpVec::new_in(arena);
+        schedule(self, w, &mut tasks)?;
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Elements { pattern, index } => {
+                    if self.dirty {
+                        continue;
+                    }
+                    let Some(elem) = pattern.elements.get(index) else {
+                        continue;
+                    };
+                    tasks.push(Task::Elements {
+                        pattern,
+                        index: index + 1,
+                    });
+
+                    match elem {
+                        ast::PatternElement::TextElement { value } => {
+                            if let Some(ref transform) = self.bundle.transform {
+                                w.write_str(&transform(value))?;
+                            } else {
+                                w.write_str(value)?;
+                            }
+                        }
+                        ast::PatternElement::Placeable { ref expression } => {
+                            self.placeables += 1;
+                            if self.placeables > MAX_PLACEABLES {
+                                self.dirty = true;
+                                self.add_error(ResolverError::TooManyPlaceables);
+                                continue;
+                            }
+
+                            let isolated = self.is_isolated(pattern, expression);
+                            if isolated {
+                                w.write_char(self.isolate_mark(expression))?;
+                            }
+                            if self.traveled.is_empty() {
+                                self.traveled.push(pattern);
+                            }
+                            tasks.push(Task::EndPlaceable {
+                                expression,
+                                isolated,
+                            });
+                            self.write_expression(w, expression, &mut tasks)?;
+                        }
+                        // The invalid placeables kept by the
+                        // `recover-placeables` feature of `fluent-syntax`.
+                        _ => {
+                            self.add_error(ResolverError::InvalidPlaceable);
+                            w.write_str("{???}")?;
+                        }
+                    }
+                }
+                Task::EndPlaceable {
+                    expression,
+                    isolated,
+                } => {
+                    if self.dirty {
+                        w.write_char('{')?;
+                        expression.write_error(w)?;
+                        w.write_char('}')?;
+                    }
+                    if isolated {
+                        w.write_char('\u{2069}')?;
+                    }
+                }
+                Task::EndReference { local_args } => {
+                    self.traveled.pop();
+                    if let Some(local_args) = local_args {
+                        self.local_args = local_args;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats `pattern` into parts, see
+    /// [`FluentBundle::format_pattern_to_parts`].
+    ///
+    /// The placeables are written like [`Scope::run`] does, except for select
+    /// expressions, whose selected variants are split into parts as well.
+    pub(crate) fn write_parts(
+        &mut self,
+        pattern: &'ast ast::Pattern<&'bundle str>,
+        parts: &mut Vec<FormattedPart<'ast>>,
+    ) where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        enum Step<'ast, 'bundle> {
+            Elements {
+                pattern: &'ast ast::Pattern<&'bundle str>,
+                index: usize,
+            },
+            EndSelect {
+                expression: &'ast ast::Expression<&'bundle str>,
+                isolated: bool,
+            },
+        }
+
+        let mut steps = vec![Step::Elements { pattern, index: 0 }];
+        while let Some(step) = steps.pop() {
+            let (pattern, index) = match step {
+                Step::Elements { pattern, index } => (pattern, index),
+                Step::EndSelect {
+                    expression,
+                    isolated,
+                } => {
+                    if self.dirty {
+                        let mut fallback = String::from("{");
+                        let _ = expression.write_error(&mut fallback);
+                        fallback.push('}');
+                        parts.push(FormattedPart::Text(fallback.into()));
+                    }
+                    if isolated {
+                        parts.push(FormattedPart::IsolateEnd);
+                    }
+                    continue;
+                }
+            };
+            if self.dirty {
+                continue;
+            }
+            let Some(elem) = pattern.elements.get(index) else {
+                continue;
+            };
+            steps.push(Step::Elements {
+                pattern,
+                index: index + 1,
+            });
+
+            let expression = match elem {
+                ast::PatternElement::TextElement { value } => {
+                    let text = match self.bundle.transform {
+                        Some(transform) => transform(value),
+                        None => (*value).into(),
+                    };
+                    parts.push(FormattedPart::Text(text));
+                    continue;
+                }
+                ast::PatternElement::Placeable { expression } => expression,
+                _ => {
+                    self.add_error(ResolverError::InvalidPlaceable);
+                    parts.push(FormattedPart::Text("{???}".into()));
+                    continue;
+                }
+            };
+
+            self.placeables += 1;
+            if self.placeables > MAX_PLACEABLES {
+                self.dirty = true;
+                self.add_error(ResolverError::TooManyPlaceables);
+                continue;
+            }
+            let isolated = self.is_isolated(pattern, expression);
+            if isolated {
+                parts.push(FormattedPart::IsolateStart(self.isolate_mark(expression)));
+            }
+            if self.traveled.is_empty() {
+                self.traveled.push(pattern);
+            }
+
+            if let ast::Expression::Select { selector, variants } = expression {
+                steps.push(Step::EndSelect {
+                    expression,
+                    isolated,
+                });
+                if let Some(pattern) = self.select(selector, variants) {
+                    steps.push(Step::Elements { pattern, index: 0 });
+                }
+                continue;
+            }
+
+            let mut value = String::new();
+            self.run(&mut value, |scope, w, tasks| {
+                tasks.push(Task::EndPlaceable {
+                    expression,
+                    isolated: false,
+                });
+                scope.write_expression(w, expression, tasks)
+            })
+            .expect("Failed to write to a string.");
+            parts.push(FormattedPart::Placeable { expression, value });
+            if isolated {
+                parts.push(FormattedPart::IsolateEnd);
+            }
+        }
+    }
+
+    /// Writes `exp`, or schedules the pattern it resolves to on `tasks`.
+    pub(super) fn write_expression<W>(
+        &mut self,
+        w: &mut W,
+        exp: &'ast ast::Expression<&'bundle str>,
+        tasks: &mut Tasks<'_, 'ast, 'bundle>,
+    ) -> fmt::Result
+    where
+        R: Borrow<FluentResource>,
+        W: fmt::Write,
+        M: MemoizerKind,
+    {
+        match exp {
+            ast::Expression::Inline(exp) => self.write_inline_expression(w, exp, tasks),
+            ast::Expression::Select { selector, variants } => {
+                if let Some(pattern) = self.select(selector, variants) {
+                    tasks.push(Task::Elements { pattern, index: 0 });
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the pattern of the variant matching `selector`, or of the
+    /// default variant.
+    fn select(
+        &mut self,
+        selector: &'ast ast::InlineExpression<&'bundle str>,
+        variants: &'ast [ast::Variant<&'bundle str>],
+    ) -> Option<&'ast ast::Pattern<&'bundle str>>
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        let selector = selector.resolve(self);
+        let matcher = self.bundle.selector_matcher.as_deref();
+        if let FluentValue::Number(number) = &selector {
+            // The cached variants were selected with the plural rules of the
+            // first locale.
+            if matcher.is_none() && self.locale == 0 {
+                let bundle = self.bundle;
+                let selected = bundle
+                    .variant_cache
+                    .select(variants, number, || self.plural_category(number));
+                if let Some(idx) = selected {
+                    return Some(&variants[idx].value);
+                }
+            }
+        }
+        if matcher.is_some() || matches!(selector, FluentValue::String(_) | FluentValue::Number(_))
+        {
+            for variant in variants {
+                let key = match variant.key {
+                    ast::VariantKey::Identifier { name } => name.into(),
+                    ast::VariantKey::NumberLiteral { value } => FluentValue::try_number(value),
+                };
+                let matched = match matcher.and_then(|matcher| matcher(&selector, &key)) {
+                    Some(matched) => matched,
+                    None => key.matches(&selector, self),
+                };
+                if matched {
+                    return Some(&variant.value);
+                }
+            }
+        }
+
+        let default = variants.iter().find(|variant| variant.default);
+        if default.is_none() {
+            self.add_error(ResolverError::MissingDefault);
+        }
+        default.map(|variant| &variant.value)
+    }
+
+    /// Writes `exp`, or schedules the pattern it references on `tasks`.
+    pub(super) fn write_inline_expression<W>(
+        &mut self,
+        w: &mut W,
+        exp: &'ast ast::InlineExpression<&'bundle str>,
+        tasks: &mut Tasks<'_, 'ast, 'bundle>,
+    ) -> fmt::Result
+    where
+        R: Borrow<FluentResource>,
+        W: fmt::Write,
+        M: MemoizerKind,
+    {
+        match exp {
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                let key = self.bundle.reference_key(id.name);
+                if let Some(replacement) = self.bundle.alias_target(key) {
+                    self.add_error(ResolverError::Deprecated {
+                        id: id.name.to_string(),
+                        replacement: replacement.to_string(),
+                    });
+                }
+                let Some(msg) = self.bundle.get_message_entry(key) else {
+                    return self.write_ref_error(w, exp);
+                };
+                if let Some(attr) = attribute {
+                    match msg.attributes.iter().find(|a| a.id.name == attr.name) {
+                        Some(a) => self.enter(w, &a.value, exp, None, tasks),
+                        None => self.write_ref_error(w, exp),
+                    }
+                } else if let Some(value) = select_variant(msg, self.bundle.variant_selector) {
+                    self.enter(w, value, exp, None, tasks)
+                } else {
+                    self.add_error(ResolverError::NoValue(id.name.to_string()));
+                    w.write_char('{')?;
+                    exp.write_error(w)?;
+                    w.write_char('}')
+                }
+            }
+            ast::InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                let resolved_named_args = self
+                    .with_arena(|scope, arena| scope.get_arguments_in(arguments.as_ref(), arena).1);
+                let pattern = self
+                    .bundle
+                    .get_term_entry(self.bundle.reference_key(id.name))
+                    .and_then(|term| match attribute {
+                        Some(attr) => term
+                            .attributes
+                            .iter()
+                            .find(|a| a.id.name == attr.name)
+                            .map(|a| &a.value),
+                        None => Some(&term.value),
+                    });
+                match pattern {
+                    Some(pattern) => self.enter(w, pattern, exp, Some(resolved_named_args), tasks),
+                    None => self.write_ref_error(w, exp),
+                }
+            }
+            ast::InlineExpression::Placeable { expression } => {
+                self.write_expression(w, expression, tasks)
+            }
+            _ => exp.write(w, self),
         }
     }
 
@@ -109,32 +768,70 @@ impl<'bundle, 'ast, 'args, 'errors, R, M> Scope<'bundle, 'ast, 'args, 'errors, R
     ) -> fmt::Result
     where
         W: fmt::Write,
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
     {
+        if !matches!(exp, ast::InlineExpression::FunctionReference { .. }) {
+            if let Some(value) = self.resolve_unknown_reference(exp) {
+                return value.write(w, self, FormatterPass::Inner);
+            }
+        }
         self.add_error(exp.into());
         w.write_char('{')?;
         exp.write_error(w)?;
         w.write_char('}')
     }
 
+    /// Returns the value supplied by the
+    /// [unknown reference handler](FluentBundle::set_unknown_reference_handler)
+    /// for `exp`, if any.
+    pub(crate) fn resolve_unknown_reference(
+        &self,
+        exp: &ast::InlineExpression<&str>,
+    ) -> Option<FluentValue<'static>> {
+        let handler = self.bundle.unknown_reference_handler.as_deref()?;
+        handler(&exp.into())
+    }
+
     pub fn get_arguments(
         &mut self,
         arguments: Option<&'ast ast::CallArguments<&'bundle str>>,
     ) -> (Vec<FluentValue<'bundle>>, FluentArgs<'bundle>)
+    where
+        R: Borrow<FluentResource>,
+        M: MemoizerKind,
+    {
+        self.with_arena(|scope, arena| {
+            let (positional, named) = scope.get_arguments_in(arguments, arena);
+            (positional.into_iter().collect(), named)
+        })
+    }
+
+    /// Like [`Scope::get_arguments`], with the positional arguments allocated
+    /// in `arena`.
+    pub(super) fn get_arguments_in<'arena>(
+        &mut self,
+        arguments: Option<&'ast ast::CallArguments<&'bundle str>>,
+        arena: &'arena Bump,
+    ) -> (BumpVec<'arena, FluentValue<'bundle>>, FluentArgs<'bundle>)
     where
         R: Borrow<FluentResource>,
         M: MemoizerKind,
     {
         if let Some(ast::CallArguments { positional, named }) = arguments {
-            let positional = positional.iter().map(|expr| expr.resolve(self)).collect();
+            let mut resolved = BumpVec::with_capacity_in(positional.len(), arena);
+            for expr in positional {
+                resolved.push(expr.resolve(self));
+            }
 
             let named = named
                 .iter()
                 .map(|arg| (arg.name.name, arg.value.resolve(self)))
                 .collect();
 
-            (positional, named)
+            (resolved, named)
         } else {
-            (Vec::new(), FluentArgs::new())
+            (BumpVec::new_in(arena), FluentArgs::new())
         }
     }
 }
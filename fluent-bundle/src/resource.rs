@@ -1,7 +1,15 @@
 use fluent_syntax::ast;
-use fluent_syntax::parser::{parse_runtime, ParserError};
+use fluent_syntax::ast::annotation::Annotation;
+use fluent_syntax::parser::{parse_runtime_with_options, ParserError, ParserOptions};
 
+use once_cell::sync::OnceCell;
 use self_cell::self_cell;
+use std::borrow::Borrow;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::errors::EntryKind;
 
 type Resource<'s> = ast::Resource<&'s str>;
 
@@ -48,7 +56,220 @@ self_cell!(
 /// A resource owns the source string and the AST contains references
 /// to the slices of the source.
 #[derive(Debug)]
-pub struct FluentResource(InnerFluentResource);
+pub struct FluentResource {
+    body: Body,
+    /// The alias and flag annotations of the comments of its messages and
+    /// terms, read from the source when the resource is created.
+    annotations: Vec<EntryAnnotation>,
+}
+
+#[derive(Debug)]
+enum Body {
+    Parsed(InnerFluentResource),
+    Lazy(LazyResource),
+    Static(&'static str, Resource<'static>),
+}
+
+/// A resource split into messages and terms, whose ASTs are only built when
+/// they are first accessed, see [`FluentResource::new_lazy`].
+#[derive(Debug)]
+struct LazyResource {
+    source: String,
+    entries: Vec<LazyEntry>,
+}
+
+#[derive(Debug)]
+struct LazyEntry {
+    id: Range<usize>,
+    kind: EntryKind,
+    span: Range<usize>,
+    parsed: OnceCell<FluentResource>,
+}
+
+impl LazyResource {
+    fn new(source: String, annotations: &mut AnnotationScanner) -> Self {
+        let mut entries: Vec<LazyEntry> = vec![];
+        let mut current = None;
+        let mut start = 0;
+
+        for line in source.split_inclusive('\n') {
+            let end = start + line.len();
+            annotations.push_line(line, start);
+            // Like in the error recovery of the parser, entries start at the
+            // beginning of a line, and indented lines continue the previous one.
+            match line.as_bytes()[0] {
+                b'a'..=b'z' | b'A'..=b'Z' | b'-' | b'#' => {
+                    current = entry_id(line).map(|(id, kind)| {
+                        entries.push(LazyEntry {
+                            id: start + id.start..start + id.end,
+                            kind,
+                            span: start..end,
+                            parsed: OnceCell::new(),
+                        });
+                        entries.len() - 1
+                    });
+                }
+                _ => {
+                    if let Some(idx) = current {
+                        entries[idx].span.end = end;
+                    }
+                }
+            }
+            start = end;
+        }
+
+        Self { source, entries }
+    }
+
+    fn get_entry(&self, idx: usize) -> Option<&ast::Entry<&str>> {
+        let entry = self.entries.get(idx)?;
+        entry
+            .parsed
+            .get_or_init(|| {
+                // The span of an entry starts at its identifier, after the
+                // comment holding its annotations.
+                let source = self.source[entry.span.clone()].to_string();
+                let (res, _) = parse(source, ParserOptions::default());
+                FluentResource {
+                    body: Body::Parsed(res),
+                    annotations: vec![],
+                }
+            })
+            .get_entry(0)
+    }
+}
+
+/// The key of the annotation which turns the message into an alias of another
+/// message, see [`FluentBundle::add_alias`](crate::bundle::FluentBundle::add_alias).
+const ALIAS_ANNOTATION: &str = "deprecated-alias-of";
+
+/// The key of the annotation which hides the entry unless a flag is enabled, see
+/// [`FluentBundle::set_enabled_flags`](crate::bundle::FluentBundle::set_enabled_flags).
+const FLAG_ANNOTATION: &str = "requires-flag";
+
+/// An annotation of the comment of a message or term, with the ranges of the
+/// identifier of the entry and of the value in the source.
+#[derive(Debug)]
+struct EntryAnnotation {
+    id: Range<usize>,
+    kind: EntryKind,
+    key: &'static str,
+    value: Range<usize>,
+}
+
+/// Collects the [`EntryAnnotation`]s of a source one line at a time, so that
+/// [`LazyResource::new`] reads them while it locates the entries.
+#[derive(Debug, Default)]
+struct AnnotationScanner {
+    annotations: Vec<EntryAnnotation>,
+    /// The annotations of the comment above the current line.
+    pending: Vec<(&'static str, Range<usize>)>,
+}
+
+impl AnnotationScanner {
+    fn scan(source: &str) -> Vec<EntryAnnotation> {
+        let mut scanner = Self::default();
+        let mut start = 0;
+        for line in source.split_inclusive('\n') {
+            scanner.push_line(line, start);
+            start += line.len();
+        }
+        scanner.annotations
+    }
+
+    /// Reads `line`, which starts at `start` in the source.
+    ///
+    /// Like the comments kept by the parser, the annotations belong to the
+    /// entry on the line right after the comment, and the first annotation
+    /// with a key wins.
+    fn push_line(&mut self, line: &str, start: usize) {
+        if let Some(content) = line.strip_prefix('#').filter(|rest| !rest.starts_with('#')) {
+            if let Some((key, value)) = parse_annotation(content) {
+                if self.pending.iter().all(|(other, _)| *other != key) {
+                    let offset = start + (value.as_ptr() as usize - line.as_ptr() as usize);
+                    self.pending.push((key, offset..offset + value.len()));
+                }
+            }
+            return;
+        }
+        if self.pending.is_empty() {
+            return;
+        }
+        if let Some((id, kind)) = entry_id(line) {
+            for (key, value) in self.pending.drain(..) {
+                self.annotations.push(EntryAnnotation {
+                    id: start + id.start..start + id.end,
+                    kind: kind.clone(),
+                    key,
+                    value,
+                });
+            }
+        }
+        self.pending.clear();
+    }
+}
+
+/// Parses a line of a comment, without its `#`, as an alias or flag
+/// annotation, written either as `# fluent: key=value` or `# fluent: key value`,
+/// or as an [annotation](fluent_syntax::ast::annotation) `# .key: value`.
+fn parse_annotation(content: &str) -> Option<(&'static str, &str)> {
+    let content = content.trim();
+    let (key, value) = match content.strip_prefix("fluent:") {
+        Some(rest) => {
+            let (key, value) = rest
+                .trim_start()
+                .split_once(|c: char| c == '=' || c.is_whitespace())?;
+            let value = value.trim_start();
+            (key, value.strip_prefix('=').unwrap_or(value).trim_start())
+        }
+        None => {
+            let annotation = Annotation::parse(content)?;
+            (annotation.key, annotation.value)
+        }
+    };
+    let key = [ALIAS_ANNOTATION, FLAG_ANNOTATION]
+        .into_iter()
+        .find(|known| *known == key)?;
+    (!value.is_empty()).then_some((key, value))
+}
+
+/// Parses `source` with the runtime parser.
+fn parse(
+    source: String,
+    options: ParserOptions,
+) -> (InnerFluentResource, Option<Vec<ParserError>>) {
+    let mut errors = None;
+    let res = InnerFluentResource::new(source, |source| {
+        match parse_runtime_with_options(source.as_str(), options) {
+            Ok(ast) => ast,
+            Err((ast, err)) => {
+                errors = Some(err);
+                ast
+            }
+        }
+    });
+    (res, errors)
+}
+
+/// Returns the identifier of the message or term starting on `line`, if any.
+fn entry_id(line: &str) -> Option<(Range<usize>, EntryKind)> {
+    let bytes = line.as_bytes();
+    let (start, kind) = match bytes[0] {
+        b'-' => (1, EntryKind::Term),
+        _ => (0, EntryKind::Message),
+    };
+    if !bytes.get(start)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let end = bytes[start..]
+        .iter()
+        .position(|b| !(b.is_ascii_alphanumeric() || *b == b'-' || *b == b'_'))
+        .map_or(bytes.len(), |pos| start + pos);
+    line[end..]
+        .trim_start_matches(' ')
+        .starts_with('=')
+        .then_some((start..end, kind))
+}
 
 impl FluentResource {
     /// A fallible constructor of a new [`FluentResource`].
@@ -80,22 +301,163 @@ impl FluentResource {
     /// the `Err` variant will contain both the structure and a vector
     /// of errors.
     pub fn try_new(source: String) -> Result<Self, (Self, Vec<ParserError>)> {
-        let mut errors = None;
+        Self::try_new_with_options(source, ParserOptions::default())
+    }
 
-        let res = InnerFluentResource::new(source, |source| match parse_runtime(source.as_str()) {
-            Ok(ast) => ast,
-            Err((ast, err)) => {
-                errors = Some(err);
-                ast
-            }
-        });
+    /// A fallible constructor like [`FluentResource::try_new`], which parses
+    /// the source with `options`.
+    ///
+    /// With the `unknown-expressions` feature of `fluent-syntax` and
+    /// `ParserOptions::unknown_expressions`, placeables using syntax which
+    /// this version does not support are kept in their messages rather than
+    /// turning the messages into junk. They are formatted as `{???}`, with a
+    /// [`ResolverError::InvalidPlaceable`](crate::resolver::ResolverError::InvalidPlaceable).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use fluent_syntax::parser::ParserOptions;
+    /// use unic_langid::langid;
+    ///
+    /// let options = ParserOptions {
+    ///     unknown_expressions: true,
+    ///     ..ParserOptions::default()
+    /// };
+    /// let source = "hello = Hello{ @future-syntax }!".to_string();
+    /// let (resource, errors) = FluentResource::try_new_with_options(source, options)
+    ///     .expect_err("The resource contains an unknown expression.");
+    /// assert_eq!(errors.len(), 1);
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("hello").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    /// assert_eq!(value, "Hello{???}!");
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn try_new_with_options(
+        source: String,
+        options: ParserOptions,
+    ) -> Result<Self, (Self, Vec<ParserError>)> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("fluent_bundle::parse", len = source.len()).entered();
+        let annotations = AnnotationScanner::scan(&source);
+        let (res, errors) = parse(source, options);
 
+        let res = Self {
+            body: Body::Parsed(res),
+            annotations,
+        };
         match errors {
-            None => Ok(Self(res)),
-            Some(err) => Err((Self(res), err)),
+            None => Ok(res),
+            Some(err) => Err((res, err)),
         }
     }
 
+    /// Creates a resource which only locates its messages and terms up
+    /// front, and parses each of them when it is first accessed.
+    ///
+    /// This amortizes the parsing cost for applications which only ever format
+    /// a small fraction of a large resource. Syntax errors are not reported;
+    /// entries which fail to parse behave as if they were missing.
+    ///
+    /// Adding the resource to a bundle does not parse its entries: the aliases
+    /// and flags annotated in its comments are read from the source, and its
+    /// text patterns are formatted from their elements rather than cached.
+    /// Only checking the [term signatures](crate::bundle::FluentBundle::add_term_signature)
+    /// of the bundle parses all of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let source = r#"
+    ///
+    /// hello-world = Hello, { $user }!
+    /// -brand = Firefox
+    /// about = About { -brand }
+    ///
+    /// "#;
+    ///
+    /// let resource = FluentResource::new_lazy(source.to_string());
+    ///
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.set_use_isolating(false);
+    /// bundle.add_resource(resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let msg = bundle.get_message("about").expect("Message doesn't exist.");
+    /// let mut errors = vec![];
+    /// let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    /// assert_eq!(value, "About Firefox");
+    /// ```
+    pub fn new_lazy(source: String) -> Self {
+        let mut annotations = AnnotationScanner::default();
+        let res = LazyResource::new(source, &mut annotations);
+        Self {
+            body: Body::Lazy(res),
+            annotations: annotations.annotations,
+        }
+    }
+
+    /// Wraps the AST of a `'static` source, such as the one built by
+    /// `include_ftl!` at compile time, without parsing the source.
+    ///
+    /// The slices of `ast` should borrow from `source`, as those of the AST
+    /// returned by the parser do. [`FluentResource::entry_source`] returns
+    /// `None` for the entries which don't.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    /// use fluent_syntax::parser;
+    ///
+    /// let source = "hello = Hello!";
+    /// let ast = parser::parse(source).expect("Failed to parse FTL.");
+    /// let resource = FluentResource::from_static(source, ast);
+    ///
+    /// assert_eq!(resource.entries().count(), 1);
+    /// assert_eq!(resource.source(), "hello = Hello!");
+    /// ```
+    pub fn from_static(source: &'static str, ast: ast::Resource<&'static str>) -> Self {
+        Self {
+            body: Body::Static(source, ast),
+            annotations: AnnotationScanner::scan(source),
+        }
+    }
+
+    /// Parses many sources at once on the rayon thread pool, returning the
+    /// result of [`FluentResource::try_new`] for each source, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let sources = vec![
+    ///     "hello = Hello!".to_string(),
+    ///     "goodbye = { $".to_string(),
+    /// ];
+    /// let resources = FluentResource::try_new_parallel(sources);
+    ///
+    /// assert!(resources[0].is_ok());
+    /// assert!(resources[1].is_err());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn try_new_parallel(sources: Vec<String>) -> Vec<Result<Self, (Self, Vec<ParserError>)>> {
+        use rayon::prelude::*;
+
+        sources.into_par_iter().map(Self::try_new).collect()
+    }
+
     /// Returns a reference to the source string that was used
     /// to construct the [`FluentResource`].
     ///
@@ -115,7 +477,11 @@ impl FluentResource {
     /// );
     /// ```
     pub fn source(&self) -> &str {
-        self.0.borrow_owner()
+        match &self.body {
+            Body::Parsed(res) => res.borrow_owner(),
+            Body::Lazy(res) => &res.source,
+            Body::Static(source, _) => source,
+        }
     }
 
     /// Returns an iterator over [`entries`](fluent_syntax::ast::Entry) of the [`FluentResource`].
@@ -142,7 +508,12 @@ impl FluentResource {
     /// assert!(matches!(resource.entries().next(), Some(ast::Entry::Message(_))));
     /// ```
     pub fn entries(&self) -> impl Iterator<Item = &ast::Entry<&str>> {
-        self.0.borrow_dependent().body.iter()
+        let (parsed, lazy) = match &self.body {
+            Body::Parsed(res) => (res.borrow_dependent().body.iter(), 0..0),
+            Body::Lazy(res) => ([].iter(), 0..res.entries.len()),
+            Body::Static(_, res) => (res.body.iter(), 0..0),
+        };
+        parsed.chain(lazy.filter_map(move |idx| self.get_entry(idx)))
     }
 
     /// Returns an [`Entry`](fluent_syntax::ast::Entry) at the
@@ -166,6 +537,387 @@ impl FluentResource {
     /// assert!(matches!(resource.get_entry(0), Some(ast::Entry::Message(_))));
     /// ```
     pub fn get_entry(&self, idx: usize) -> Option<&ast::Entry<&str>> {
-        self.0.borrow_dependent().body.get(idx)
+        match &self.body {
+            Body::Parsed(res) => res.borrow_dependent().body.get(idx),
+            Body::Lazy(res) => res.get_entry(idx),
+            Body::Static(_, res) => res.body.get(idx),
+        }
+    }
+
+    /// Returns the source of `entry`, which is one of the entries of the
+    /// resource, as written in the FTL, without trailing blank lines.
+    ///
+    /// The comments attached to messages and terms are not included, since
+    /// the runtime parser does not keep them. `None` is returned if `entry`
+    /// does not belong to the resource.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let source = r#"
+    /// hello = Hello!
+    ///
+    /// ## A comment about emails.
+    /// emails = { $count ->
+    ///     [one] One email
+    ///    *[other] { $count } emails
+    /// }
+    ///
+    /// "#;
+    ///
+    /// let resource = FluentResource::try_new(source.to_string())
+    ///     .expect("Failed to parse FTL.");
+    /// let entry = resource.get_entry(1).expect("Entry doesn't exist.");
+    ///
+    /// assert_eq!(
+    ///     resource.entry_source(entry),
+    ///     Some("emails = { $count ->\n    [one] One email\n   *[other] { $count } emails\n}")
+    /// );
+    /// ```
+    pub fn entry_source(&self, entry: &ast::Entry<&str>) -> Option<&str> {
+        let source = self.source();
+        if let Body::Lazy(res) = &self.body {
+            // The entries of a lazy resource are parsed from a copy of their
+            // span, so they are found by address instead.
+            let lazy = res.entries.iter().find(|lazy| {
+                lazy.parsed
+                    .get()
+                    .and_then(|parsed| parsed.get_entry(0))
+                    .map_or(false, |parsed| std::ptr::eq(parsed, entry))
+            })?;
+            return Some(source[lazy.span.clone()].trim_end());
+        }
+
+        let first = match entry {
+            ast::Entry::Message(msg) => msg.id.name,
+            ast::Entry::Term(term) => term.id.name,
+            ast::Entry::Junk { content } => content,
+            _ => return None,
+        };
+        let start = (first.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|start| *start < source.len())?;
+        let start = source[..start].rfind('\n').map_or(0, |pos| pos + 1);
+
+        // Like in `LazyResource::new`, the entry ends before the next line
+        // which may start an entry.
+        let mut lines = source[start..].split_inclusive('\n');
+        let mut end = start + lines.next().map_or(0, str::len);
+        for line in lines {
+            if matches!(line.as_bytes()[0], b'a'..=b'z' | b'A'..=b'Z' | b'-' | b'#') {
+                break;
+            }
+            end += line.len();
+        }
+        Some(source[start..end].trim_end())
+    }
+
+    /// Returns the identifiers of the messages of the resource along with the
+    /// hashes of their content, see [`fluent_syntax::hash_entry`].
+    ///
+    /// Sync tools can compare the hashes of the source locale across versions
+    /// to find the messages whose translations are outdated. The entries of a
+    /// lazy resource are parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let old = FluentResource::try_new("hello = Hello!\nbye = Bye!".to_string())
+    ///     .expect("Failed to parse FTL.");
+    /// let new = FluentResource::try_new("hello = Hello!\nbye = Goodbye!".to_string())
+    ///     .expect("Failed to parse FTL.");
+    ///
+    /// let changed: Vec<_> = new
+    ///     .message_hashes()
+    ///     .filter(|entry| !old.message_hashes().any(|old_entry| old_entry == *entry))
+    ///     .map(|(id, _)| id)
+    ///     .collect();
+    /// assert_eq!(changed, ["bye"]);
+    /// ```
+    pub fn message_hashes(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.entries().filter_map(|entry| match entry {
+            ast::Entry::Message(msg) => Some((msg.id.name, fluent_syntax::hash_entry(entry))),
+            _ => None,
+        })
+    }
+
+    /// Returns the counts of the messages, terms and translatable content of
+    /// the resource. The entries of a lazy resource are parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let source = r#"
+    /// hello = Hello, { $user }!
+    ///     .title = Greeting
+    /// -brand = Firefox
+    /// "#;
+    ///
+    /// let resource = FluentResource::try_new(source.to_string())
+    ///     .expect("Failed to parse FTL.");
+    /// let stats = resource.stats();
+    ///
+    /// assert_eq!((stats.messages, stats.terms, stats.attributes), (1, 1, 1));
+    /// assert_eq!((stats.words, stats.placeables), (3, 1));
+    /// ```
+    pub fn stats(&self) -> CatalogStats {
+        let mut stats = CatalogStats::default();
+        for entry in self.entries() {
+            stats.add_entry(entry);
+        }
+        stats
+    }
+
+    /// Returns an estimate of the heap memory held by the resource, counting
+    /// the source and the top-level entries, in bytes.
+    pub(crate) fn heap_size(&self) -> usize {
+        let entries =
+            |res: &Resource| res.body.capacity() * std::mem::size_of::<ast::Entry<&str>>();
+        let body = match &self.body {
+            Body::Parsed(res) => res.borrow_owner().capacity() + entries(res.borrow_dependent()),
+            Body::Lazy(res) => {
+                res.source.capacity()
+                    + res.entries.capacity() * std::mem::size_of::<LazyEntry>()
+                    + res
+                        .entries
+                        .iter()
+                        .filter_map(|entry| entry.parsed.get())
+                        .map(FluentResource::heap_size)
+                        .sum::<usize>()
+            }
+            Body::Static(_, res) => entries(res),
+        };
+        body + self.annotations.capacity() * std::mem::size_of::<EntryAnnotation>()
+    }
+
+    /// Returns `true` if the resource was created with
+    /// [`FluentResource::new_lazy`].
+    pub(crate) fn is_lazy(&self) -> bool {
+        matches!(self.body, Body::Lazy(_))
+    }
+
+    /// Returns the identifiers of the messages annotated as deprecated aliases,
+    /// along with the identifiers of the messages they resolve to.
+    pub(crate) fn deprecated_aliases(&self) -> Vec<(&str, &str)> {
+        self.annotated_entries(ALIAS_ANNOTATION)
+            .filter(|(_, kind, _)| *kind == EntryKind::Message)
+            .map(|(id, _, target)| (id, target))
+            .collect()
+    }
+
+    /// Returns the identifiers of the messages and terms annotated as requiring
+    /// a flag, along with the flag.
+    pub(crate) fn required_flags(&self) -> Vec<(&str, &str)> {
+        self.annotated_entries(FLAG_ANNOTATION)
+            .map(|(id, _, flag)| (id, flag))
+            .collect()
+    }
+
+    /// Returns the identifier and kind of the messages and terms annotated
+    /// with `key`, along with the value of the annotation.
+    fn annotated_entries(
+        &self,
+        key: &'static str,
+    ) -> impl Iterator<Item = (&str, EntryKind, &str)> + '_ {
+        let source = self.source();
+        self.annotations
+            .iter()
+            .filter(move |annotation| annotation.key == key)
+            .map(move |annotation| {
+                (
+                    &source[annotation.id.clone()],
+                    annotation.kind.clone(),
+                    &source[annotation.value.clone()],
+                )
+            })
+    }
+
+    /// Returns the position, identifier and kind of the messages and terms,
+    /// without parsing lazy entries.
+    pub(crate) fn entry_ids(&self) -> Box<dyn Iterator<Item = (usize, &str, EntryKind)> + '_> {
+        match &self.body {
+            Body::Parsed(res) => Box::new(parsed_entry_ids(res.borrow_dependent())),
+            Body::Static(_, res) => Box::new(parsed_entry_ids(res)),
+            Body::Lazy(res) => Box::new(
+                res.entries
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, entry)| (idx, &res.source[entry.id.clone()], entry.kind.clone())),
+            ),
+        }
+    }
+}
+
+fn parsed_entry_ids<'r>(
+    res: &'r Resource<'_>,
+) -> impl Iterator<Item = (usize, &'r str, EntryKind)> {
+    res.body
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, entry)| match entry {
+            ast::Entry::Message(ast::Message { id, .. }) => {
+                Some((idx, id.name, EntryKind::Message))
+            }
+            ast::Entry::Term(ast::Term { id, .. }) => Some((idx, id.name, EntryKind::Term)),
+            _ => None,
+        })
+}
+
+/// A handle to a [`FluentResource`], which may own, borrow or share it.
+///
+/// A [`FluentBundle`](crate::bundle::FluentBundle) holds resources of a single
+/// type `R`. Using `ResourceHandle` as `R` lets one bundle mix resources owned by
+/// the bundle with ones shared through [`Rc`] or [`Arc`] caches, each added with
+/// `.into()`.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use std::sync::Arc;
+///
+/// use fluent_bundle::{FluentBundle, FluentResource, ResourceHandle};
+/// use unic_langid::langid;
+///
+/// let owned = FluentResource::try_new("hello = Hello!".to_string())
+///     .expect("Failed to parse an FTL string.");
+/// let rc = Rc::new(
+///     FluentResource::try_new("goodbye = Goodbye!".to_string())
+///         .expect("Failed to parse an FTL string."),
+/// );
+/// let arc = Arc::new(
+///     FluentResource::try_new("welcome = Welcome!".to_string())
+///         .expect("Failed to parse an FTL string."),
+/// );
+///
+/// let mut bundle: FluentBundle<ResourceHandle> = FluentBundle::new(vec![langid!("en-US")]);
+/// bundle.add_resource(owned.into()).expect("Failed to add a resource.");
+/// bundle.add_resource(rc.into()).expect("Failed to add a resource.");
+/// bundle.add_resource(arc.into()).expect("Failed to add a resource.");
+///
+/// assert!(bundle.has_message("hello"));
+/// assert!(bundle.has_message("goodbye"));
+/// assert!(bundle.has_message("welcome"));
+/// ```
+#[derive(Debug)]
+pub enum ResourceHandle<'r> {
+    Owned(FluentResource),
+    Borrowed(&'r FluentResource),
+    Rc(Rc<FluentResource>),
+    Arc(Arc<FluentResource>),
+}
+
+impl Borrow<FluentResource> for ResourceHandle<'_> {
+    fn borrow(&self) -> &FluentResource {
+        match self {
+            Self::Owned(res) => res,
+            Self::Borrowed(res) => res,
+            Self::Rc(res) => res,
+            Self::Arc(res) => res,
+        }
+    }
+}
+
+impl From<FluentResource> for ResourceHandle<'_> {
+    fn from(res: FluentResource) -> Self {
+        Self::Owned(res)
+    }
+}
+
+impl<'r> From<&'r FluentResource> for ResourceHandle<'r> {
+    fn from(res: &'r FluentResource) -> Self {
+        Self::Borrowed(res)
+    }
+}
+
+impl From<Rc<FluentResource>> for ResourceHandle<'_> {
+    fn from(res: Rc<FluentResource>) -> Self {
+        Self::Rc(res)
+    }
+}
+
+impl From<Arc<FluentResource>> for ResourceHandle<'_> {
+    fn from(res: Arc<FluentResource>) -> Self {
+        Self::Arc(res)
+    }
+}
+
+/// Counts of the translatable content of a catalog, see
+/// [`FluentResource::stats`] and
+/// [`FluentBundle::stats`](crate::bundle::FluentBundle::stats).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CatalogStats {
+    pub messages: usize,
+    pub terms: usize,
+    /// The number of attributes of messages and terms.
+    pub attributes: usize,
+    /// The number of words in the text of the values, attributes and variants,
+    /// as used for translation cost estimates. Words are separated by
+    /// whitespace, and punctuation alone is not a word.
+    pub words: usize,
+    /// The number of placeables, including the nested ones and the ones in
+    /// variants.
+    pub placeables: usize,
+    pub select_expressions: usize,
+}
+
+impl CatalogStats {
+    /// Adds the counts of `entry`, ignoring comments and junk.
+    pub(crate) fn add_entry(&mut self, entry: &ast::Entry<&str>) {
+        let (value, attributes) = match entry {
+            ast::Entry::Message(msg) => {
+                self.messages += 1;
+                (msg.value.as_ref(), &msg.attributes)
+            }
+            ast::Entry::Term(term) => {
+                self.terms += 1;
+                (Some(&term.value), &term.attributes)
+            }
+            _ => return,
+        };
+        self.attributes += attributes.len();
+        for pattern in value
+            .into_iter()
+            .chain(attributes.iter().map(|attr| &attr.value))
+        {
+            self.add_pattern(pattern);
+        }
+    }
+
+    fn add_pattern(&mut self, pattern: &ast::Pattern<&str>) {
+        for element in &pattern.elements {
+            match element {
+                ast::PatternElement::TextElement { value } => {
+                    self.words += value
+                        .split_whitespace()
+                        .filter(|word| word.chars().any(char::is_alphanumeric))
+                        .count();
+                }
+                ast::PatternElement::Placeable { expression } => self.add_expression(expression),
+                _ => {}
+            }
+        }
+    }
+
+    fn add_expression(&mut self, expression: &ast::Expression<&str>) {
+        self.placeables += 1;
+        match expression {
+            ast::Expression::Inline(ast::InlineExpression::Placeable { expression }) => {
+                self.add_expression(expression);
+            }
+            ast::Expression::Inline(_) => {}
+            ast::Expression::Select { variants, .. } => {
+                self.select_expressions += 1;
+                for variant in variants {
+                    self.add_pattern(&variant.value);
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,449 @@
+//! Building [`FluentArgs`] from the fields of a [`Serialize`] value, see
+//! [`FluentArgs::from_serialize`].
+use std::error::Error;
+use std::fmt;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::types::FluentValue;
+use crate::FluentArgs;
+
+/// An error returned by [`FluentArgs::from_serialize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerializeError {
+    /// The value is not a struct or a map, such as a number or a sequence.
+    NotAStruct,
+    /// A key of a map is not a string.
+    KeyNotString,
+    /// A field has a value which has no [`FluentValue`] equivalent, such as
+    /// bytes, a nested struct or map, or an enum variant with data.
+    Unsupported { key: String, kind: &'static str },
+    /// An error raised by the [`Serialize`] implementation of the value.
+    Custom(String),
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAStruct => write!(f, "Only structs and maps can be arguments"),
+            Self::KeyNotString => write!(f, "Argument names must be strings"),
+            Self::Unsupported { key, kind } => {
+                write!(f, "Argument `{}` is an unsupported {}", key, kind)
+            }
+            Self::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl FluentArgs<'_> {
+    /// Creates arguments from the fields of a struct, or the entries of a map
+    /// with string keys, which implements [`Serialize`].
+    ///
+    /// Fields keep their names, which can be changed with the `rename` and
+    /// `rename_all` attributes of `serde`. Numbers become number values,
+    /// strings, characters, booleans and unit enum variants become string
+    /// values, `None` and units become missing values, and sequences and
+    /// tuples become [`FluentValue::List`] values. Other shapes, such as
+    /// nested structs, return [`SerializeError::Unsupported`].
+    ///
+    /// This needs the `serde` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentValue};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct Inbox {
+    ///     user: String,
+    ///     email_count: u32,
+    ///     folder: Option<String>,
+    /// }
+    ///
+    /// let inbox = Inbox {
+    ///     user: "John".to_string(),
+    ///     email_count: 5,
+    ///     folder: None,
+    /// };
+    /// let args = FluentArgs::from_serialize(&inbox).expect("Failed to serialize.");
+    ///
+    /// assert_eq!(args.get_str("user"), Some("John"));
+    /// assert_eq!(args.get_number("emailCount"), Some(5.0));
+    /// assert!(matches!(args.get("folder"), Some(FluentValue::None)));
+    /// ```
+    pub fn from_serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self, SerializeError> {
+        value.serialize(ArgsSerializer)
+    }
+}
+
+/// Serializes a struct or a map into [`FluentArgs`].
+struct ArgsSerializer;
+
+/// The arguments collected by [`ArgsSerializer`], and the key of the map
+/// entry being serialized.
+struct ArgsBuilder {
+    args: FluentArgs<'static>,
+    key: Option<String>,
+}
+
+impl ArgsBuilder {
+    fn set<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), SerializeError> {
+        match value.serialize(ValueSerializer) {
+            Ok(value) => {
+                self.args.set(key, value);
+                Ok(())
+            }
+            Err(SerializeError::Unsupported { kind, .. }) => {
+                Err(SerializeError::Unsupported { key, kind })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Rejects the values of [`ArgsSerializer`] which are not structs or maps.
+macro_rules! not_a_struct {
+    ($($method:ident($($arg:ty),*) -> $ret:ty;)+) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ret, SerializeError> {
+                Err(SerializeError::NotAStruct)
+            }
+        )+
+    };
+}
+
+impl ser::Serializer for ArgsSerializer {
+    type Ok = FluentArgs<'static>;
+    type Error = SerializeError;
+    type SerializeSeq = Impossible<Self::Ok, SerializeError>;
+    type SerializeTuple = Impossible<Self::Ok, SerializeError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, SerializeError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, SerializeError>;
+    type SerializeMap = ArgsBuilder;
+    type SerializeStruct = ArgsBuilder;
+    type SerializeStructVariant = Impossible<Self::Ok, SerializeError>;
+
+    not_a_struct! {
+        serialize_bool(bool) -> Self::Ok;
+        serialize_i8(i8) -> Self::Ok;
+        serialize_i16(i16) -> Self::Ok;
+        serialize_i32(i32) -> Self::Ok;
+        serialize_i64(i64) -> Self::Ok;
+        serialize_u8(u8) -> Self::Ok;
+        serialize_u16(u16) -> Self::Ok;
+        serialize_u32(u32) -> Self::Ok;
+        serialize_u64(u64) -> Self::Ok;
+        serialize_f32(f32) -> Self::Ok;
+        serialize_f64(f64) -> Self::Ok;
+        serialize_char(char) -> Self::Ok;
+        serialize_str(&str) -> Self::Ok;
+        serialize_bytes(&[u8]) -> Self::Ok;
+        serialize_none() -> Self::Ok;
+        serialize_unit() -> Self::Ok;
+        serialize_unit_struct(&'static str) -> Self::Ok;
+        serialize_unit_variant(&'static str, u32, &'static str) -> Self::Ok;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, SerializeError> {
+        Err(SerializeError::NotAStruct)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, SerializeError> {
+        Ok(ArgsBuilder {
+            args: FluentArgs::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+}
+
+impl ser::SerializeMap for ArgsBuilder {
+    type Ok = FluentArgs<'static>;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        match key.serialize(ValueSerializer) {
+            Ok(FluentValue::String(key)) => {
+                self.key = Some(key.into_owned());
+                Ok(())
+            }
+            _ => Err(SerializeError::KeyNotString),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self.key.take().ok_or(SerializeError::KeyNotString)?;
+        self.set(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, SerializeError> {
+        Ok(self.args)
+    }
+}
+
+impl ser::SerializeStruct for ArgsBuilder {
+    type Ok = FluentArgs<'static>;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.set(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, SerializeError> {
+        Ok(self.args)
+    }
+}
+
+/// Serializes the value of an argument into a [`FluentValue`].
+struct ValueSerializer;
+
+/// The items of a sequence or a tuple serialized by [`ValueSerializer`].
+struct ListBuilder(Vec<FluentValue<'static>>);
+
+fn unsupported<T>(kind: &'static str) -> Result<T, SerializeError> {
+    Err(SerializeError::Unsupported {
+        key: String::new(),
+        kind,
+    })
+}
+
+/// Serializes numbers of [`ValueSerializer`] as number values.
+macro_rules! number {
+    ($($method:ident($num:ty);)+) => {
+        $(
+            fn $method(self, v: $num) -> Result<Self::Ok, SerializeError> {
+                Ok(v.into())
+            }
+        )+
+    };
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = FluentValue<'static>;
+    type Error = SerializeError;
+    type SerializeSeq = ListBuilder;
+    type SerializeTuple = ListBuilder;
+    type SerializeTupleStruct = ListBuilder;
+    type SerializeTupleVariant = Impossible<Self::Ok, SerializeError>;
+    type SerializeMap = Impossible<Self::Ok, SerializeError>;
+    type SerializeStruct = Impossible<Self::Ok, SerializeError>;
+    type SerializeStructVariant = Impossible<Self::Ok, SerializeError>;
+
+    number! {
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, SerializeError> {
+        Ok(v.to_string().into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, SerializeError> {
+        Ok(v.to_string().into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, SerializeError> {
+        Ok(v.to_string().into())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, SerializeError> {
+        unsupported("byte array")
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, SerializeError> {
+        Ok(FluentValue::None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, SerializeError> {
+        Ok(FluentValue::None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, SerializeError> {
+        Ok(FluentValue::None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, SerializeError> {
+        Ok(variant.into())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, SerializeError> {
+        unsupported("enum variant with data")
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, SerializeError> {
+        Ok(ListBuilder(Vec::with_capacity(len.unwrap_or_default())))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerializeError> {
+        unsupported("enum variant with data")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerializeError> {
+        unsupported("map")
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerializeError> {
+        unsupported("struct")
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerializeError> {
+        unsupported("enum variant with data")
+    }
+}
+
+impl ListBuilder {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.0.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for ListBuilder {
+    type Ok = FluentValue<'static>;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, SerializeError> {
+        Ok(FluentValue::List(self.0))
+    }
+}
+
+impl ser::SerializeTuple for ListBuilder {
+    type Ok = FluentValue<'static>;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, SerializeError> {
+        Ok(FluentValue::List(self.0))
+    }
+}
+
+impl ser::SerializeTupleStruct for ListBuilder {
+    type Ok = FluentValue<'static>;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, SerializeError> {
+        Ok(FluentValue::List(self.0))
+    }
+}
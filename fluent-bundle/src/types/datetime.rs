@@ -0,0 +1,309 @@
+use std::borrow::Cow;
+
+use crate::args::FluentArgs;
+use crate::types::FluentValue;
+
+/// The length of the date or the time of a [`FluentDateTime`], as in the
+/// `dateStyle` and `timeStyle` options of `Intl.DateTimeFormat`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FluentDateTimeStyle {
+    /// "Tuesday, March 5, 2024" and "14:07:09 UTC"
+    Full,
+    /// "March 5, 2024" and "14:07:09 UTC"
+    Long,
+    /// "Mar 5, 2024" and "14:07:09"
+    #[default]
+    Medium,
+    /// "2024-03-05" and "14:07"
+    Short,
+}
+
+impl From<&str> for FluentDateTimeStyle {
+    fn from(input: &str) -> Self {
+        match input {
+            "full" => Self::Full,
+            "long" => Self::Long,
+            "short" => Self::Short,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FluentDateTimeOptions {
+    pub date_style: Option<FluentDateTimeStyle>,
+    pub time_style: Option<FluentDateTimeStyle>,
+    /// The offset of the time zone the date and time are written in, in
+    /// seconds east of UTC.
+    pub time_zone_offset: i32,
+}
+
+impl FluentDateTimeOptions {
+    pub fn merge(&mut self, opts: &FluentArgs) {
+        for (key, value) in opts.iter() {
+            match (key, value) {
+                ("dateStyle", FluentValue::String(n)) => {
+                    self.date_style = Some(n.as_ref().into());
+                }
+                ("timeStyle", FluentValue::String(n)) => {
+                    self.time_style = Some(n.as_ref().into());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// An instant, which is formatted as a date, a time or both.
+///
+/// Without date and time styles, it is formatted in the ISO 8601 format, such
+/// as `2024-03-05T14:07:09Z`. The names of months and days of the `medium`,
+/// `long` and `full` styles are English.
+///
+/// With the `chrono`, `time` and `jiff` features, it converts from their date
+/// and time types, keeping their offset from UTC.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::types::{FluentDateTime, FluentDateTimeStyle};
+///
+/// let mut date = FluentDateTime::from_epoch(1_709_647_629_000);
+/// assert_eq!(date.as_string(), "2024-03-05T14:07:09Z");
+///
+/// date.options.date_style = Some(FluentDateTimeStyle::Long);
+/// date.options.time_style = Some(FluentDateTimeStyle::Short);
+/// assert_eq!(date.as_string(), "March 5, 2024, 14:07");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FluentDateTime {
+    /// The number of milliseconds since 1970-01-01T00:00:00Z, as in a
+    /// JavaScript `Date`.
+    pub epoch: i64,
+    pub options: FluentDateTimeOptions,
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+impl FluentDateTime {
+    pub const fn new(epoch: i64, options: FluentDateTimeOptions) -> Self {
+        Self { epoch, options }
+    }
+
+    /// Creates a date and time in UTC from a number of milliseconds since the
+    /// Unix epoch.
+    pub fn from_epoch(epoch: i64) -> Self {
+        Self::new(epoch, FluentDateTimeOptions::default())
+    }
+
+    pub fn as_string(&self) -> Cow<'static, str> {
+        let fields = Fields::new(self.epoch, self.options.time_zone_offset);
+        let date = self.options.date_style.map(|style| fields.date(style));
+        let time = self.options.time_style.map(|style| fields.time(style));
+        match (date, time) {
+            (Some(date), Some(time)) => format!("{}, {}", date, time),
+            (Some(date), None) => date,
+            (None, Some(time)) => time,
+            (None, None) => fields.iso(),
+        }
+        .into()
+    }
+}
+
+/// The fields of a date and time in a time zone.
+struct Fields {
+    year: i64,
+    month: usize,
+    day: u32,
+    weekday: usize,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset: i32,
+}
+
+impl Fields {
+    fn new(epoch: i64, offset: i32) -> Self {
+        let local = epoch.saturating_add(i64::from(offset) * 1000);
+        let days = local.div_euclid(86_400_000);
+        let millis = local.rem_euclid(86_400_000) as u32;
+
+        // The civil date of a number of days since 1970-01-01, by Howard
+        // Hinnant's algorithm, with years starting in March.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as usize;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month,
+            day,
+            // 1970-01-01 was a Thursday.
+            weekday: (days + 3).rem_euclid(7) as usize,
+            hour: millis / 3_600_000,
+            minute: millis / 60_000 % 60,
+            second: millis / 1000 % 60,
+            millisecond: millis % 1000,
+            offset,
+        }
+    }
+
+    fn date(&self, style: FluentDateTimeStyle) -> String {
+        let month = MONTHS[self.month - 1];
+        match style {
+            FluentDateTimeStyle::Full => format!(
+                "{}, {} {}, {}",
+                WEEKDAYS[self.weekday], month, self.day, self.year
+            ),
+            FluentDateTimeStyle::Long => format!("{} {}, {}", month, self.day, self.year),
+            FluentDateTimeStyle::Medium => format!("{} {}, {}", &month[..3], self.day, self.year),
+            FluentDateTimeStyle::Short => {
+                format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+            }
+        }
+    }
+
+    fn time(&self, style: FluentDateTimeStyle) -> String {
+        match style {
+            FluentDateTimeStyle::Full | FluentDateTimeStyle::Long => format!(
+                "{:02}:{:02}:{:02} {}",
+                self.hour,
+                self.minute,
+                self.second,
+                self.zone("UTC")
+            ),
+            FluentDateTimeStyle::Medium => {
+                format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+            }
+            FluentDateTimeStyle::Short => format!("{:02}:{:02}", self.hour, self.minute),
+        }
+    }
+
+    fn iso(&self) -> String {
+        let mut iso = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        );
+        if self.millisecond != 0 {
+            iso.push_str(&format!(".{:03}", self.millisecond));
+        }
+        iso.push_str(&self.zone("Z"));
+        iso
+    }
+
+    /// Returns the offset of the time zone as `+01:00`, or `utc` for UTC.
+    fn zone(&self, utc: &str) -> String {
+        if self.offset == 0 {
+            return utc.to_string();
+        }
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let minutes = self.offset.unsigned_abs() / 60;
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+impl From<FluentDateTime> for FluentValue<'_> {
+    fn from(input: FluentDateTime) -> Self {
+        FluentValue::DateTime(input)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for FluentDateTime {
+    fn from(input: chrono::DateTime<Tz>) -> Self {
+        use chrono::Offset;
+
+        let mut date = Self::from_epoch(input.timestamp_millis());
+        date.options.time_zone_offset = input.offset().fix().local_minus_utc();
+        date
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for FluentValue<'_> {
+    fn from(input: chrono::DateTime<Tz>) -> Self {
+        FluentValue::DateTime(input.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for FluentDateTime {
+    fn from(input: time::OffsetDateTime) -> Self {
+        let epoch = input.unix_timestamp_nanos().div_euclid(1_000_000) as i64;
+        let mut date = Self::from_epoch(epoch);
+        date.options.time_zone_offset = input.offset().whole_seconds();
+        date
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for FluentValue<'_> {
+    fn from(input: time::OffsetDateTime) -> Self {
+        FluentValue::DateTime(input.into())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<jiff::Timestamp> for FluentDateTime {
+    fn from(input: jiff::Timestamp) -> Self {
+        Self::from_epoch(input.as_millisecond())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<jiff::Timestamp> for FluentValue<'_> {
+    fn from(input: jiff::Timestamp) -> Self {
+        FluentValue::DateTime(input.into())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<&jiff::Zoned> for FluentDateTime {
+    fn from(input: &jiff::Zoned) -> Self {
+        let mut date = Self::from(input.timestamp());
+        date.options.time_zone_offset = input.offset().seconds();
+        date
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<&jiff::Zoned> for FluentValue<'_> {
+    fn from(input: &jiff::Zoned) -> Self {
+        FluentValue::DateTime(input.into())
+    }
+}
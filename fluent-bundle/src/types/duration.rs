@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+use super::FluentType;
+
+/// The style of a [`FluentDuration`], as in the `style` option of
+/// `Intl.DurationFormat`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum DurationStyle {
+    /// "1 hour 42 minutes 7 seconds"
+    Long,
+    /// "1 hr 42 min 7 sec"
+    #[default]
+    Short,
+    /// "1h 42m 7s"
+    Narrow,
+    /// "1:42:07"
+    Digital,
+}
+
+impl From<&str> for DurationStyle {
+    fn from(input: &str) -> Self {
+        match input {
+            "long" => Self::Long,
+            "narrow" => Self::Narrow,
+            "digital" => Self::Digital,
+            _ => Self::Short,
+        }
+    }
+}
+
+/// A duration in seconds, which is formatted as hours, minutes and seconds,
+/// such as "1:42:07" or "1h 42m 7s".
+///
+/// This is the value returned by the builtin `DURATION()` function, see
+/// [`builtins::DURATION`](crate::builtins::DURATION). The duration is rounded
+/// to whole seconds.
+///
+/// With the `units` feature, the fields of the `long`, `short` and `narrow`
+/// styles are formatted as `FluentUnit` values, whose
+/// numbers have the digits of the bundle locale with the `builtins` feature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluentDuration {
+    pub seconds: f64,
+    pub style: DurationStyle,
+}
+
+impl FluentDuration {
+    pub fn new(seconds: f64, style: DurationStyle) -> Self {
+        Self { seconds, style }
+    }
+
+    /// Returns the sign, hours, minutes and seconds of the duration.
+    fn fields(&self) -> (&'static str, u64, u64, u64) {
+        let sign = if self.seconds < 0.0 { "-" } else { "" };
+        let total = self.seconds.abs().round() as u64;
+        (sign, total / 3600, total / 60 % 60, total % 60)
+    }
+
+    /// Formats the non-zero fields of the duration with `unit`, which is given
+    /// a value and the name of its unit.
+    fn format_fields(&self, unit: impl Fn(u64, &str) -> String) -> String {
+        let (sign, hours, minutes, seconds) = self.fields();
+        if self.style == DurationStyle::Digital {
+            return format!("{}{}:{:02}:{:02}", sign, hours, minutes, seconds);
+        }
+
+        let mut fields: Vec<_> = [(hours, "hour"), (minutes, "minute"), (seconds, "second")]
+            .into_iter()
+            .filter(|(value, _)| *value != 0)
+            .map(|(value, name)| unit(value, name))
+            .collect();
+        if fields.is_empty() {
+            fields.push(unit(0, "second"));
+        }
+        format!("{}{}", sign, fields.join(" "))
+    }
+
+    /// Formats a field in English, for when locale data is not available.
+    #[cfg(not(feature = "units"))]
+    fn english_field(&self, value: u64, unit: &str) -> String {
+        match self.style {
+            DurationStyle::Long if value == 1 => format!("{} {}", value, unit),
+            DurationStyle::Long => format!("{} {}s", value, unit),
+            DurationStyle::Narrow => format!("{}{}", value, &unit[..1]),
+            _ => {
+                let unit = match unit {
+                    "hour" => "hr",
+                    "minute" => "min",
+                    _ => "sec",
+                };
+                format!("{} {}", value, unit)
+            }
+        }
+    }
+
+    #[cfg(feature = "units")]
+    fn unit(&self, value: u64, unit: &str) -> super::FluentUnit {
+        let display = match self.style {
+            DurationStyle::Long => super::UnitDisplay::Long,
+            DurationStyle::Narrow => super::UnitDisplay::Narrow,
+            _ => super::UnitDisplay::Short,
+        };
+        super::FluentUnit::new((value as f64).into(), unit, display)
+    }
+}
+
+impl FluentType for FluentDuration {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, _intls: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+        #[cfg(feature = "units")]
+        let value =
+            self.format_fields(|value, unit| self.unit(value, unit).as_string(_intls).into_owned());
+        #[cfg(not(feature = "units"))]
+        let value = self.format_fields(|value, unit| self.english_field(value, unit));
+        value.into()
+    }
+
+    fn as_string_threadsafe(
+        &self,
+        _intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ) -> Cow<'static, str> {
+        #[cfg(feature = "units")]
+        let value = self.format_fields(|value, unit| {
+            self.unit(value, unit)
+                .as_string_threadsafe(_intls)
+                .into_owned()
+        });
+        #[cfg(not(feature = "units"))]
+        let value = self.format_fields(|value, unit| self.english_field(value, unit));
+        value.into()
+    }
+}
@@ -0,0 +1,212 @@
+use std::borrow::Cow;
+
+#[cfg(feature = "list-format")]
+use icu_list::{ListError, ListFormatter, ListLength};
+#[cfg(feature = "list-format")]
+use intl_memoizer::Memoizable;
+#[cfg(feature = "list-format")]
+use unic_langid::LanguageIdentifier;
+
+use super::{FluentType, FluentValue};
+use crate::memoizer::MemoizerKind;
+
+/// How the items of a list are related, as in the `type` option of
+/// `Intl.ListFormat`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ListType {
+    /// "a, b, and c"
+    #[default]
+    Conjunction,
+    /// "a, b, or c"
+    Disjunction,
+    /// "a, b, c", as in lists of measurements.
+    Unit,
+}
+
+impl From<&str> for ListType {
+    fn from(input: &str) -> Self {
+        match input {
+            "disjunction" => Self::Disjunction,
+            "unit" => Self::Unit,
+            _ => Self::Conjunction,
+        }
+    }
+}
+
+/// The width of a list, as in the `style` option of `Intl.ListFormat`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ListStyle {
+    /// "a, b, and c"
+    #[default]
+    Long,
+    /// "a, b, & c"
+    Short,
+    /// "a, b, c"
+    Narrow,
+}
+
+impl From<&str> for ListStyle {
+    fn from(input: &str) -> Self {
+        match input {
+            "short" => Self::Short,
+            "narrow" => Self::Narrow,
+            _ => Self::Long,
+        }
+    }
+}
+
+/// A list of values, which is formatted as, for instance, "Anna, Bob, and
+/// Carol" in the bundle locale.
+///
+/// This is the value returned by the builtin `LISTFORMAT()` function, see
+/// [`builtins::LISTFORMAT`](crate::builtins::LISTFORMAT). Without the
+/// `list-format` feature, or without data for the locale, lists are joined
+/// in English.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluentList {
+    pub items: Vec<FluentValue<'static>>,
+    pub r#type: ListType,
+    pub style: ListStyle,
+}
+
+impl FluentList {
+    pub fn new(items: Vec<FluentValue<'static>>, r#type: ListType, style: ListStyle) -> Self {
+        Self {
+            items,
+            r#type,
+            style,
+        }
+    }
+
+    fn format<M: MemoizerKind>(&self, intls: &M) -> Cow<'static, str> {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .filter_map(|item| stringify(item, intls))
+            .collect();
+        join(&items, self.r#type, self.style, intls).into()
+    }
+}
+
+/// Formats an item of a [`FluentList`] without a scope, or returns `None` for
+/// missing values and errors, which are left out of the list.
+fn stringify<M: MemoizerKind>(value: &FluentValue, intls: &M) -> Option<Cow<'static, str>> {
+    match value {
+        FluentValue::String(s) => Some(s.to_string().into()),
+        FluentValue::Number(n) => Some(n.format(intls, None, None)),
+        FluentValue::DateTime(d) => Some(d.as_string()),
+        FluentValue::List(items) => {
+            let items: Vec<_> = items
+                .iter()
+                .filter_map(|item| stringify(item, intls))
+                .collect();
+            Some(join(&items, ListType::default(), ListStyle::default(), intls).into())
+        }
+        FluentValue::Custom(s) => Some(intls.stringify_value(&**s)),
+        FluentValue::None | FluentValue::Error => None,
+    }
+}
+
+/// Joins formatted items with the list patterns of the locale of `intls`,
+/// falling back to English when there is no data for it.
+pub(crate) fn join<M: MemoizerKind, S: AsRef<str>>(
+    items: &[S],
+    r#type: ListType,
+    style: ListStyle,
+    #[cfg_attr(not(feature = "list-format"), allow(unused_variables))] intls: &M,
+) -> String {
+    #[cfg(feature = "list-format")]
+    if let Ok(list) = intls.with_try_get_threadsafe::<ListFormat, _, _>((r#type, style), |lf| {
+        lf.0.format_to_string(items.iter().map(AsRef::as_ref))
+    }) {
+        return list;
+    }
+    fallback(items, r#type, style)
+}
+
+/// Joins items in English, as "a, b, and c".
+fn fallback<S: AsRef<str>>(items: &[S], r#type: ListType, style: ListStyle) -> String {
+    let word = match (r#type, style) {
+        (ListType::Conjunction, ListStyle::Long) => Some("and"),
+        (ListType::Conjunction, ListStyle::Short) => Some("&"),
+        (ListType::Disjunction, _) => Some("or"),
+        (ListType::Conjunction, ListStyle::Narrow) | (ListType::Unit, _) => None,
+    };
+    let mut result = String::new();
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            match word {
+                Some(word) if idx == items.len() - 1 && items.len() == 2 => {
+                    result.push(' ');
+                    result.push_str(word);
+                    result.push(' ');
+                }
+                Some(word) if idx == items.len() - 1 => {
+                    result.push_str(", ");
+                    result.push_str(word);
+                    result.push(' ');
+                }
+                _ if r#type == ListType::Unit && style == ListStyle::Narrow => result.push(' '),
+                _ => result.push_str(", "),
+            }
+        }
+        result.push_str(item.as_ref());
+    }
+    result
+}
+
+impl FluentType for FluentList {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, intls: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+        self.format(intls)
+    }
+
+    fn as_string_threadsafe(
+        &self,
+        intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ) -> Cow<'static, str> {
+        self.format(intls)
+    }
+}
+
+impl<'source, T> From<Vec<T>> for FluentValue<'source>
+where
+    T: Into<FluentValue<'source>>,
+{
+    fn from(input: Vec<T>) -> Self {
+        FluentValue::List(input.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<FluentList> for FluentValue<'_> {
+    fn from(input: FluentList) -> Self {
+        FluentValue::Custom(Box::new(input))
+    }
+}
+
+/// The ICU4X formatter of a list type and style, memoized per locale.
+#[cfg(feature = "list-format")]
+struct ListFormat(ListFormatter);
+
+#[cfg(feature = "list-format")]
+impl Memoizable for ListFormat {
+    type Args = (ListType, ListStyle);
+    type Error = ListError;
+    fn construct(lang: LanguageIdentifier, args: Self::Args) -> Result<Self, Self::Error> {
+        let locale = super::icu_locale(&lang);
+        let length = match args.1 {
+            ListStyle::Long => ListLength::Wide,
+            ListStyle::Short => ListLength::Short,
+            ListStyle::Narrow => ListLength::Narrow,
+        };
+        match args.0 {
+            ListType::Conjunction => ListFormatter::try_new_and_with_length(&locale, length),
+            ListType::Disjunction => ListFormatter::try_new_or_with_length(&locale, length),
+            ListType::Unit => ListFormatter::try_new_unit_with_length(&locale, length),
+        }
+        .map(Self)
+    }
+}
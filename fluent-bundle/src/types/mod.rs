@@ -8,27 +8,58 @@
 //! for the internationalization APIs to mature, at which point all number
 //! formatting operations will be moved out of Fluent.
 //!
-//! For now, [`FluentValue`] can be a string, a number, or a custom [`FluentType`]
-//! which allows users of the library to implement their own types of values,
-//! such as dates, or more complex structures needed for their bindings.
+//! For now, [`FluentValue`] can be a string, a number, a date and time, a list, or a custom
+//! [`FluentType`] which allows users of the library to implement their own types of
+//! values, or more complex structures needed for their bindings.
+mod datetime;
+mod duration;
+mod list;
 mod number;
+#[cfg(feature = "builtins")]
+mod number_format;
 mod plural;
+#[cfg(feature = "relative-time")]
+mod relative_time;
+#[cfg(feature = "spellout")]
+pub(crate) mod spellout;
+#[cfg(feature = "units")]
+mod unit;
 
+pub use datetime::{FluentDateTime, FluentDateTimeOptions, FluentDateTimeStyle};
+pub use duration::{DurationStyle, FluentDuration};
+pub use list::{FluentList, ListStyle, ListType};
 pub use number::*;
-use plural::PluralRules;
+#[cfg(feature = "plural-rules")]
+pub(crate) use plural::PluralRules;
+pub use plural::{PluralCategory, PluralRulesBackend};
+#[cfg(feature = "relative-time")]
+pub use relative_time::{FluentRelativeTime, RelativeTimeStyle};
+#[cfg(feature = "units")]
+pub use unit::{FluentUnit, UnitDisplay};
 
 use std::any::Any;
 use std::borrow::{Borrow, Cow};
 use std::fmt;
 use std::str::FromStr;
 
-use intl_pluralrules::{PluralCategory, PluralRuleType};
-
 use crate::bundle::FormatterPass;
 use crate::memoizer::MemoizerKind;
 use crate::resolver::Scope;
 use crate::resource::FluentResource;
 
+/// Converts the locale of a memoizer to the locale of the ICU4X formatters.
+#[cfg(any(
+    feature = "builtins",
+    feature = "list-format",
+    feature = "relative-time",
+    feature = "units"
+))]
+fn icu_locale(lang: &unic_langid::LanguageIdentifier) -> icu_provider::DataLocale {
+    // Both crates implement BCP 47, so a valid identifier always converts.
+    let locale: icu_locid::Locale = lang.to_string().parse().unwrap_or_default();
+    (&locale).into()
+}
+
 /// Custom types can implement the [`FluentType`] trait in order to generate a string
 /// value for use in the message generation process.
 pub trait FluentType: fmt::Debug + AnyEq + 'static {
@@ -78,6 +109,10 @@ impl<T: Any + PartialEq> AnyEq for T {
 pub enum FluentValue<'source> {
     String(Cow<'source, str>),
     Number(FluentNumber),
+    DateTime(FluentDateTime),
+    /// A list of values, formatted as a conjunction such as "a, b, and c"
+    /// in the bundle locale, see [`FluentList`] for other kinds of lists.
+    List(Vec<FluentValue<'source>>),
     Custom(Box<dyn FluentType + Send>),
     None,
     Error,
@@ -88,6 +123,8 @@ impl PartialEq for FluentValue<'_> {
         match (self, other) {
             (FluentValue::String(s), FluentValue::String(s2)) => s == s2,
             (FluentValue::Number(s), FluentValue::Number(s2)) => s == s2,
+            (FluentValue::DateTime(s), FluentValue::DateTime(s2)) => s == s2,
+            (FluentValue::List(s), FluentValue::List(s2)) => s == s2,
             (FluentValue::Custom(s), FluentValue::Custom(s2)) => s == s2,
             _ => false,
         }
@@ -99,6 +136,8 @@ impl Clone for FluentValue<'_> {
         match self {
             FluentValue::String(s) => FluentValue::String(s.clone()),
             FluentValue::Number(s) => FluentValue::Number(s.clone()),
+            FluentValue::DateTime(s) => FluentValue::DateTime(s.clone()),
+            FluentValue::List(s) => FluentValue::List(s.clone()),
             FluentValue::Custom(s) => {
                 let new_value: Box<dyn FluentType + Send> = s.duplicate();
                 FluentValue::Custom(new_value)
@@ -152,6 +191,7 @@ impl<'source> FluentValue<'source> {
     /// Checks to see if two [`FluentValues`](FluentValue) match each other by having the
     /// same type and contents. The special exception is in the case of a string being
     /// compared to a number. Here attempt to check that the plural rule category matches.
+    /// Without the `plural-rules` feature, strings never match numbers.
     ///
     /// ```
     /// use fluent_bundle::resolver::Scope;
@@ -186,29 +226,14 @@ impl<'source> FluentValue<'source> {
         match (self, other) {
             (FluentValue::String(a), FluentValue::String(b)) => a == b,
             (FluentValue::Number(a), FluentValue::Number(b)) => a == b,
+            (FluentValue::DateTime(a), FluentValue::DateTime(b)) => a == b,
             (FluentValue::String(a), FluentValue::Number(b)) => {
-                let cat = match a.as_ref() {
-                    "zero" => PluralCategory::ZERO,
-                    "one" => PluralCategory::ONE,
-                    "two" => PluralCategory::TWO,
-                    "few" => PluralCategory::FEW,
-                    "many" => PluralCategory::MANY,
-                    "other" => PluralCategory::OTHER,
-                    _ => return false,
-                };
                 // This string matches a plural rule keyword. Check if the number
                 // matches the plural rule category.
-                let r#type = match b.options.r#type {
-                    FluentNumberType::Cardinal => PluralRuleType::CARDINAL,
-                    FluentNumberType::Ordinal => PluralRuleType::ORDINAL,
-                };
-                scope
-                    .bundle
-                    .intls
-                    .with_try_get_threadsafe::<PluralRules, _, _>((r#type,), |pr| {
-                        pr.0.select(b) == Ok(cat)
-                    })
-                    .unwrap()
+                match PluralCategory::from_key(a) {
+                    Some(cat) => scope.plural_category(b) == Some(cat),
+                    None => false,
+                }
             }
             _ => false,
         }
@@ -222,14 +247,20 @@ impl<'source> FluentValue<'source> {
         M: MemoizerKind,
     {
         if let Some(formatter) = &scope.bundle.formatter {
-            if let Some(val) = formatter(self, &scope.bundle.intls, pass) {
+            if let Some(val) = formatter(self, scope.intls(), pass) {
                 return w.write_str(&val);
             }
         }
         match self {
             FluentValue::String(s) => w.write_str(s),
-            FluentValue::Number(n) => w.write_str(&n.as_string()),
-            FluentValue::Custom(s) => w.write_str(&scope.bundle.intls.stringify_value(&**s)),
+            FluentValue::Number(n) => w.write_str(&n.format(
+                scope.intls(),
+                scope.locale(),
+                scope.bundle.number_symbols.as_ref(),
+            )),
+            FluentValue::DateTime(d) => w.write_str(&d.as_string()),
+            FluentValue::List(items) => w.write_str(&Self::join(items, scope)),
+            FluentValue::Custom(s) => w.write_str(&scope.intls().stringify_value(&**s)),
             FluentValue::Error => Ok(()),
             FluentValue::None => Ok(()),
         }
@@ -248,14 +279,20 @@ impl<'source> FluentValue<'source> {
         M: MemoizerKind,
     {
         if let Some(formatter) = &scope.bundle.formatter {
-            if let Some(val) = formatter(self, &scope.bundle.intls, pass) {
+            if let Some(val) = formatter(self, scope.intls(), pass) {
                 return val.into();
             }
         }
         match self {
             FluentValue::String(s) => s.clone(),
-            FluentValue::Number(n) => n.as_string(),
-            FluentValue::Custom(s) => scope.bundle.intls.stringify_value(&**s),
+            FluentValue::Number(n) => n.format(
+                scope.intls(),
+                scope.locale(),
+                scope.bundle.number_symbols.as_ref(),
+            ),
+            FluentValue::DateTime(d) => d.as_string(),
+            FluentValue::List(items) => Self::join(items, scope).into(),
+            FluentValue::Custom(s) => scope.intls().stringify_value(&**s),
             FluentValue::Error => "".into(),
             FluentValue::None => "".into(),
         }
@@ -274,14 +311,20 @@ impl<'source> FluentValue<'source> {
         M: MemoizerKind,
     {
         if let Some(formatter) = &scope.bundle.formatter {
-            if let Some(val) = formatter(&self, &scope.bundle.intls, pass) {
+            if let Some(val) = formatter(&self, scope.intls(), pass) {
                 return val.into();
             }
         }
         match self {
             FluentValue::String(s) => s,
-            FluentValue::Number(n) => n.as_string(),
-            FluentValue::Custom(s) => scope.bundle.intls.stringify_value(s.as_ref()),
+            FluentValue::Number(n) => n.format(
+                scope.intls(),
+                scope.locale(),
+                scope.bundle.number_symbols.as_ref(),
+            ),
+            FluentValue::DateTime(d) => d.as_string(),
+            FluentValue::List(items) => Self::join(&items, scope).into(),
+            FluentValue::Custom(s) => scope.intls().stringify_value(s.as_ref()),
             FluentValue::Error => "".into(),
             FluentValue::None => "".into(),
         }
@@ -291,11 +334,32 @@ impl<'source> FluentValue<'source> {
         match self {
             FluentValue::String(str) => FluentValue::String(Cow::from(str.to_string())),
             FluentValue::Number(s) => FluentValue::Number(s.clone()),
+            FluentValue::DateTime(s) => FluentValue::DateTime(s.clone()),
+            FluentValue::List(s) => FluentValue::List(s.iter().map(Self::into_owned).collect()),
             FluentValue::Custom(s) => FluentValue::Custom(s.duplicate()),
             FluentValue::Error => FluentValue::Error,
             FluentValue::None => FluentValue::None,
         }
     }
+
+    /// Formats the items of a list and joins them as a conjunction, leaving
+    /// out missing values and errors.
+    fn join<R: Borrow<FluentResource>, M: MemoizerKind>(
+        items: &[FluentValue],
+        scope: &Scope<R, M>,
+    ) -> String {
+        let items: Vec<_> = items
+            .iter()
+            .filter(|item| !matches!(item, FluentValue::None | FluentValue::Error))
+            .map(|item| item.as_string(scope, FormatterPass::Inner))
+            .collect();
+        list::join(
+            &items,
+            ListType::default(),
+            ListStyle::default(),
+            scope.intls(),
+        )
+    }
 }
 
 impl From<String> for FluentValue<'_> {
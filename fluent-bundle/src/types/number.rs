@@ -1,17 +1,31 @@
 use std::borrow::Cow;
+#[cfg(feature = "plural-rules")]
 use std::convert::TryInto;
 use std::default::Default;
+use std::fmt;
 use std::str::FromStr;
 
+#[cfg(feature = "plural-rules")]
 use intl_pluralrules::operands::PluralOperands;
 
+use unic_langid::LanguageIdentifier;
+
 use crate::args::FluentArgs;
+use crate::memoizer::MemoizerKind;
 use crate::types::FluentValue;
 
+/// The plural rules a number is selected with, as in the `type` option of
+/// `NUMBER()`.
+///
+/// With `{ NUMBER($pos, type: "ordinal") -> ... }`, the variants of a select
+/// expression are chosen by the ordinal category of the number, such as `one`
+/// for "1st" and `two` for "2nd" in English, instead of its cardinal one.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub enum FluentNumberType {
+    /// Quantities, as in "1 file" and "2 files".
     #[default]
     Cardinal,
+    /// Positions, as in "1st", "2nd" and "3rd".
     Ordinal,
 }
 
@@ -63,13 +77,199 @@ impl From<&str> for FluentNumberCurrencyDisplayStyle {
     }
 }
 
+/// How the digits of a number are written, as in the `notation` option of
+/// `Intl.NumberFormat`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FluentNumberNotation {
+    #[default]
+    Standard,
+    /// Spelled out in words, such as "twenty-one", or "twenty-first" for
+    /// ordinal numbers. This needs the `spellout` feature and rules for the
+    /// bundle locale; otherwise the number is written with digits.
+    Spellout,
+    /// Scaled down to thousands, millions, billions or trillions, such as
+    /// "1.2K" or "1.2 thousand". The names of the units are English.
+    Compact,
+}
+
+impl From<&str> for FluentNumberNotation {
+    fn from(input: &str) -> Self {
+        match input {
+            "standard" => Self::Standard,
+            "spellout" => Self::Spellout,
+            "compact" => Self::Compact,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The names of the units of the compact notation, as in the `compactDisplay`
+/// option of `Intl.NumberFormat`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FluentNumberCompactDisplay {
+    /// "1.2K"
+    #[default]
+    Short,
+    /// "1.2 thousand"
+    Long,
+}
+
+impl From<&str> for FluentNumberCompactDisplay {
+    fn from(input: &str) -> Self {
+        match input {
+            "long" => Self::Long,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// When digits are grouped, as in the `useGrouping` option of
+/// `Intl.NumberFormat`, unless it is `"false"`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FluentNumberGrouping {
+    /// With the minimum grouping digits of the locale, such as "1,234" in
+    /// English and "1234" in Spanish.
+    #[default]
+    Auto,
+    /// Whenever a number has more than three integer digits. The ICU4X
+    /// formatters of the `builtins` feature group them as `Auto`.
+    Always,
+    /// When there are at least two digits in front of the first separator,
+    /// such as "1234" and "12,345".
+    Min2,
+}
+
+impl From<&str> for FluentNumberGrouping {
+    fn from(input: &str) -> Self {
+        match input {
+            "always" | "true" => Self::Always,
+            "min2" => Self::Min2,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// When the sign of a number is written, as in the `signDisplay` option of
+/// `Intl.NumberFormat`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FluentNumberSignDisplay {
+    /// "-1", "0" and "1"
+    #[default]
+    Auto,
+    /// "-1", "+0" and "+1"
+    Always,
+    /// "-1", "0" and "+1"
+    ExceptZero,
+    /// "-1", "0" and "1", without the sign of negative zero.
+    Negative,
+    /// "1", "0" and "1"
+    Never,
+}
+
+impl From<&str> for FluentNumberSignDisplay {
+    fn from(input: &str) -> Self {
+        match input {
+            "always" => Self::Always,
+            "exceptZero" => Self::ExceptZero,
+            "negative" => Self::Negative,
+            "never" => Self::Never,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl FluentNumberSignDisplay {
+    /// Returns the sign written in front of a number, if any.
+    pub(crate) fn sign(self, value: f64) -> Option<char> {
+        let negative = value.is_sign_negative();
+        let zero = value == 0.0;
+        match self {
+            Self::Auto if negative => Some('-'),
+            Self::Always => Some(if negative { '-' } else { '+' }),
+            Self::ExceptZero | Self::Negative if negative && !zero => Some('-'),
+            Self::ExceptZero if !zero => Some('+'),
+            _ => None,
+        }
+    }
+}
+
+/// How numbers are rounded to their maximum number of digits, as in the
+/// `roundingMode` option of `Intl.NumberFormat`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FluentNumberRoundingMode {
+    /// Towards positive infinity.
+    Ceil,
+    /// Towards negative infinity.
+    Floor,
+    /// Away from zero.
+    Expand,
+    /// Towards zero.
+    Trunc,
+    /// To the nearest digit, and ties towards positive infinity.
+    HalfCeil,
+    /// To the nearest digit, and ties towards negative infinity.
+    HalfFloor,
+    /// To the nearest digit, and ties away from zero.
+    #[default]
+    HalfExpand,
+    /// To the nearest digit, and ties towards zero.
+    HalfTrunc,
+    /// To the nearest digit, and ties to an even digit.
+    HalfEven,
+}
+
+impl From<&str> for FluentNumberRoundingMode {
+    fn from(input: &str) -> Self {
+        match input {
+            "ceil" => Self::Ceil,
+            "floor" => Self::Floor,
+            "expand" => Self::Expand,
+            "trunc" => Self::Trunc,
+            "halfCeil" => Self::HalfCeil,
+            "halfFloor" => Self::HalfFloor,
+            "halfTrunc" => Self::HalfTrunc,
+            "halfEven" => Self::HalfEven,
+            _ => Self::default(),
+        }
+    }
+}
+
+impl FluentNumberRoundingMode {
+    /// Rounds `value` to a number of fraction digits.
+    pub(crate) fn round(self, value: f64, fraction_digits: i32) -> f64 {
+        let scale = 10_f64.powi(fraction_digits);
+        let scaled = value * scale;
+        let floor = scaled.floor();
+        let rounded = match self {
+            Self::Ceil => scaled.ceil(),
+            Self::Floor => floor,
+            Self::Expand if scaled < 0.0 => floor,
+            Self::Expand => scaled.ceil(),
+            Self::Trunc => scaled.trunc(),
+            _ if scaled - floor != 0.5 => scaled.round(),
+            Self::HalfCeil => floor + 1.0,
+            Self::HalfFloor => floor,
+            Self::HalfExpand => scaled.round(),
+            Self::HalfTrunc => scaled.trunc(),
+            Self::HalfEven if floor % 2.0 == 0.0 => floor,
+            Self::HalfEven => floor + 1.0,
+        };
+        rounded / scale
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct FluentNumberOptions {
     pub r#type: FluentNumberType,
     pub style: FluentNumberStyle,
     pub currency: Option<String>,
     pub currency_display: FluentNumberCurrencyDisplayStyle,
+    pub notation: FluentNumberNotation,
+    pub compact_display: FluentNumberCompactDisplay,
     pub use_grouping: bool,
+    pub grouping: FluentNumberGrouping,
+    pub sign_display: FluentNumberSignDisplay,
+    pub rounding_mode: FluentNumberRoundingMode,
     pub minimum_integer_digits: Option<usize>,
     pub minimum_fraction_digits: Option<usize>,
     pub maximum_fraction_digits: Option<usize>,
@@ -84,7 +284,12 @@ impl Default for FluentNumberOptions {
             style: Default::default(),
             currency: None,
             currency_display: Default::default(),
+            notation: Default::default(),
+            compact_display: Default::default(),
             use_grouping: true,
+            grouping: Default::default(),
+            sign_display: Default::default(),
+            rounding_mode: Default::default(),
             minimum_integer_digits: None,
             minimum_fraction_digits: None,
             maximum_fraction_digits: None,
@@ -110,8 +315,21 @@ impl FluentNumberOptions {
                 ("currencyDisplay", FluentValue::String(n)) => {
                     self.currency_display = n.as_ref().into();
                 }
+                ("notation", FluentValue::String(n)) => {
+                    self.notation = n.as_ref().into();
+                }
+                ("compactDisplay", FluentValue::String(n)) => {
+                    self.compact_display = n.as_ref().into();
+                }
                 ("useGrouping", FluentValue::String(n)) => {
                     self.use_grouping = n != "false";
+                    self.grouping = n.as_ref().into();
+                }
+                ("signDisplay", FluentValue::String(n)) => {
+                    self.sign_display = n.as_ref().into();
+                }
+                ("roundingMode", FluentValue::String(n)) => {
+                    self.rounding_mode = n.as_ref().into();
                 }
                 ("minimumIntegerDigits", FluentValue::Number(n)) => {
                     self.minimum_integer_digits = Some(n.into());
@@ -134,19 +352,102 @@ impl FluentNumberOptions {
     }
 }
 
+/// A number, with the options it is formatted with.
+///
+/// Numbers which an `f64` cannot represent exactly, such as large integer
+/// identifiers or amounts with more than 15 significant digits, also keep
+/// their exact decimal digits, which are used to format them and select their
+/// plural category. They are kept when numbers are parsed with [`FromStr`],
+/// converted from integers, or from `rust_decimal::Decimal` with the
+/// `rust_decimal` feature.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::types::FluentNumber;
+///
+/// let id: FluentNumber = "12345678901234567891".parse().unwrap();
+/// assert_eq!(id.as_string(), "12345678901234567891");
+/// assert_eq!(id.exact(), Some("12345678901234567891"));
+/// assert_eq!(u64::from(id), 12345678901234567891);
+///
+/// let price: FluentNumber = "19.90".parse().unwrap();
+/// assert_eq!(price.as_string(), "19.90");
+/// assert_eq!(price.exact(), None);
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct FluentNumber {
     pub value: f64,
     pub options: FluentNumberOptions,
+    /// The canonical decimal digits of the number, if `value` is only an
+    /// approximation of it.
+    exact: Option<Box<str>>,
 }
 
+/// The first integer from which not every integer is exactly an `f64`.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
 impl FluentNumber {
     pub const fn new(value: f64, options: FluentNumberOptions) -> Self {
-        Self { value, options }
+        Self {
+            value,
+            options,
+            exact: None,
+        }
+    }
+
+    /// Returns the exact decimal digits of the number, such as
+    /// `"12345678901234567891"`, if `value` is only an approximation of it.
+    pub fn exact(&self) -> Option<&str> {
+        self.exact.as_deref()
+    }
+
+    /// Returns the decimal digits of the number, exact when they are known.
+    pub(crate) fn digits(&self) -> Cow<'_, str> {
+        match &self.exact {
+            Some(exact) => Cow::Borrowed(exact),
+            None => Cow::Owned(self.value.to_string()),
+        }
+    }
+
+    /// Returns the number multiplied by 100, such as for percentages.
+    fn percent(&self) -> Self {
+        let mut number = Self::new(self.value * 100.0, self.options.clone());
+        if let Some(exact) = &self.exact {
+            let (negative, digits) = match exact.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, &**exact),
+            };
+            let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+            let fraction = format!("{:0<2}", fraction);
+            let scaled = format!(
+                "{}{}{}.{}",
+                if negative { "-" } else { "" },
+                integer,
+                &fraction[..2],
+                &fraction[2..]
+            );
+            number.exact = exact_digits(&scaled, number.value);
+        }
+        number
+    }
+
+    /// Creates a number from an integer, keeping its digits if it is too large
+    /// to be exactly an `f64`.
+    fn from_integer(value: f64, n: impl fmt::Display) -> Self {
+        let mut number = Self::new(value, FluentNumberOptions::default());
+        if value.abs() >= MAX_SAFE_INTEGER {
+            number.exact = exact_digits(&n.to_string(), value);
+        }
+        number
+    }
+
+    fn from_float(value: f64, _n: impl fmt::Display) -> Self {
+        Self::new(value, FluentNumberOptions::default())
     }
 
     pub fn as_string(&self) -> Cow<'static, str> {
-        let mut val = self.value.to_string();
+        let mut val = self.digits().into_owned();
         if let Some(minfd) = self.options.minimum_fraction_digits {
             if let Some(pos) = val.find('.') {
                 let frac_num = val.len() - pos - 1;
@@ -158,6 +459,226 @@ impl FluentNumber {
         }
         val.into()
     }
+
+    /// Formats the number in the language of `langid`, with `symbols` if
+    /// given. Otherwise, it only differs from [`FluentNumber::as_string`] for
+    /// the spellout and compact notations and the sign display, or with the
+    /// `builtins` feature, which formats it with the ICU4X data of the locale
+    /// of `_intls`.
+    pub(crate) fn format<M: MemoizerKind>(
+        &self,
+        _intls: &M,
+        _langid: Option<&LanguageIdentifier>,
+        symbols: Option<&NumberSymbols>,
+    ) -> Cow<'static, str> {
+        #[cfg(feature = "spellout")]
+        if self.options.notation == FluentNumberNotation::Spellout {
+            let ordinal = self.options.r#type == FluentNumberType::Ordinal;
+            if let Some(words) = super::spellout::spell_out(self.value, ordinal, _langid) {
+                return words.into();
+            }
+        }
+        if self.options.notation == FluentNumberNotation::Compact {
+            let (number, unit) = self.compact();
+            return format!("{}{}", number.format(_intls, _langid, symbols), unit).into();
+        }
+        if let Some(symbols) = symbols {
+            return symbols.format(self).into();
+        }
+        #[cfg(feature = "builtins")]
+        if let Some(formatted) = super::number_format::format(self, _intls, _langid) {
+            return formatted.into();
+        }
+        let digits = self.as_string();
+        if self.options.sign_display == FluentNumberSignDisplay::Auto {
+            return digits;
+        }
+        let digits = digits.trim_start_matches('-');
+        match self.options.sign_display.sign(self.value) {
+            Some(sign) => format!("{}{}", sign, digits).into(),
+            None => digits.to_string().into(),
+        }
+    }
+
+    /// Returns the number scaled down for the compact notation, in the
+    /// standard notation, and the name of its unit.
+    ///
+    /// Without digit options, it is rounded to integers, or two significant
+    /// digits for numbers below 10, as "1.2K" and "12K".
+    fn compact(&self) -> (Self, &'static str) {
+        const UNITS: [(f64, &str, &str); 4] = [
+            (1e12, "T", " trillion"),
+            (1e9, "B", " billion"),
+            (1e6, "M", " million"),
+            (1e3, "K", " thousand"),
+        ];
+        let options = &self.options;
+        let rounded = options.minimum_fraction_digits.is_none()
+            && options.maximum_fraction_digits.is_none()
+            && options.minimum_significant_digits.is_none()
+            && options.maximum_significant_digits.is_none();
+
+        let mut unit = UNITS
+            .iter()
+            .position(|(size, ..)| self.value.abs() >= *size);
+        let mut value = self.value / unit.map_or(1.0, |idx| UNITS[idx].0);
+        let mut fraction_digits = None;
+        if rounded {
+            let digits = match value.abs() {
+                abs if abs == 0.0 || abs >= 10.0 => 0,
+                abs if abs >= 1.0 => 1,
+                abs => 1 - abs.log10().floor() as i32,
+            };
+            value = options.rounding_mode.round(value, digits);
+            // Rounding up to 1000 moves to the next unit, as in "1M".
+            let next = match unit {
+                Some(0) => None,
+                Some(idx) => Some(idx - 1),
+                None => Some(UNITS.len() - 1),
+            };
+            if let Some(next) = next.filter(|_| value.abs() >= 1000.0) {
+                value = options.rounding_mode.round(self.value / UNITS[next].0, 1);
+                unit = Some(next);
+            }
+            fraction_digits = Some(digits.max(0) as usize);
+        }
+
+        let mut number = Self::new(value, options.clone());
+        number.options.notation = FluentNumberNotation::Standard;
+        if rounded {
+            number.options.maximum_fraction_digits = fraction_digits;
+        }
+        let name = match (unit, options.compact_display) {
+            (Some(idx), FluentNumberCompactDisplay::Short) => UNITS[idx].1,
+            (Some(idx), FluentNumberCompactDisplay::Long) => UNITS[idx].2,
+            (None, _) => "",
+        };
+        (number, name)
+    }
+}
+
+/// The symbols used to format numbers in a locale, for builds without the
+/// ICU number formatters, see
+/// [`FluentBundle::set_number_symbols`](crate::bundle::FluentBundle::set_number_symbols).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NumberSymbols {
+    /// The separator of the fraction digits, such as `.` or `,`.
+    pub decimal: String,
+    /// The separator of the groups of three integer digits, such as `,`,
+    /// `.`, or a narrow no-break space. Numbers are grouped unless
+    /// `useGrouping` is `"false"`.
+    pub group: String,
+    /// The least number of digits in front of the first separator, which
+    /// is 2 when 4-digit numbers are not grouped, as in Spanish or Polish.
+    pub minimum_grouping_digits: usize,
+    /// The sign written after numbers with the percent style, along with the
+    /// space in front of it, if any.
+    pub percent: String,
+    /// The sign of negative numbers, such as `-` or `\u{2212}`.
+    pub minus: String,
+}
+
+impl Default for NumberSymbols {
+    /// Returns the symbols of English.
+    fn default() -> Self {
+        Self {
+            decimal: ".".to_string(),
+            group: ",".to_string(),
+            minimum_grouping_digits: 1,
+            percent: "%".to_string(),
+            minus: "-".to_string(),
+        }
+    }
+}
+
+impl NumberSymbols {
+    fn format(&self, number: &FluentNumber) -> String {
+        let percent = number.options.style == FluentNumberStyle::Percent;
+        let digits = if percent {
+            number.percent().as_string()
+        } else {
+            number.as_string()
+        };
+        let (negative, digits) = match digits.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, &*digits),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let mut result = String::new();
+        let sign = match number.options.sign_display {
+            FluentNumberSignDisplay::Auto if negative => Some('-'),
+            display => display.sign(number.value),
+        };
+        match sign {
+            Some('-') => result.push_str(&self.minus),
+            Some(sign) => result.push(sign),
+            None => {}
+        }
+        let minimum_grouping_digits = match number.options.grouping {
+            FluentNumberGrouping::Auto => self.minimum_grouping_digits,
+            FluentNumberGrouping::Always => 1,
+            FluentNumberGrouping::Min2 => 2,
+        };
+        let grouped = number.options.use_grouping
+            && integer.len() >= 4 + minimum_grouping_digits.saturating_sub(1);
+        for (idx, digit) in integer.char_indices() {
+            if grouped && idx > 0 && (integer.len() - idx) % 3 == 0 {
+                result.push_str(&self.group);
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push_str(&self.decimal);
+            result.push_str(fraction);
+        }
+        if percent {
+            result.push_str(&self.percent);
+        }
+        result
+    }
+}
+
+/// Returns the canonical form of the decimal number `input`, without leading
+/// and trailing zeros, if it differs from the shortest digits of `value`.
+fn exact_digits(input: &str, value: f64) -> Option<Box<str>> {
+    let (negative, digits) = match input.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let integer = match integer.trim_start_matches('0') {
+        "" => "0",
+        integer => integer,
+    };
+    let fraction = fraction.trim_end_matches('0');
+
+    let mut canonical = String::new();
+    if negative && (integer != "0" || !fraction.is_empty()) {
+        canonical.push('-');
+    }
+    canonical.push_str(integer);
+    if !fraction.is_empty() {
+        canonical.push('.');
+        canonical.push_str(fraction);
+    }
+    let shortest = if value == 0.0 {
+        "0".to_string()
+    } else {
+        value.to_string()
+    };
+    (canonical != shortest).then(|| canonical.into())
 }
 
 impl FromStr for FluentNumber {
@@ -170,7 +691,12 @@ impl FromStr for FluentNumber {
                 minimum_fraction_digits: mfd,
                 ..Default::default()
             };
-            Self::new(n, opts)
+            let mut number = Self::new(n, opts);
+            // Up to 15 significant digits always survive the `f64`.
+            if input.bytes().filter(u8::is_ascii_digit).count() > 15 {
+                number.exact = exact_digits(input, n);
+            }
+            number
         })
     }
 }
@@ -182,31 +708,28 @@ impl From<FluentNumber> for FluentValue<'_> {
 }
 
 macro_rules! from_num {
-    ($num:ty) => {
+    ($new:path, $num:ty) => {
         impl From<$num> for FluentNumber {
             fn from(n: $num) -> Self {
-                Self {
-                    value: n as f64,
-                    options: FluentNumberOptions::default(),
-                }
+                $new(n as f64, n)
             }
         }
         impl From<&$num> for FluentNumber {
             fn from(n: &$num) -> Self {
-                Self {
-                    value: *n as f64,
-                    options: FluentNumberOptions::default(),
-                }
+                $new(*n as f64, n)
             }
         }
         impl From<FluentNumber> for $num {
             fn from(input: FluentNumber) -> Self {
-                input.value as $num
+                (&input).into()
             }
         }
         impl From<&FluentNumber> for $num {
             fn from(input: &FluentNumber) -> Self {
-                input.value as $num
+                input
+                    .exact()
+                    .and_then(|exact| exact.parse().ok())
+                    .unwrap_or(input.value as $num)
             }
         }
         impl From<$num> for FluentValue<'_> {
@@ -220,17 +743,30 @@ macro_rules! from_num {
             }
         }
     };
-    ($($num:ty)+) => {
-        $(from_num!($num);)+
+    ($new:path: $($num:ty)+) => {
+        $(from_num!($new, $num);)+
     };
 }
 
+#[cfg(feature = "plural-rules")]
 impl From<&FluentNumber> for PluralOperands {
     fn from(input: &FluentNumber) -> Self {
-        let mut operands: Self = input
-            .value
-            .try_into()
-            .expect("Failed to generate operands out of FluentNumber");
+        let operands = match input.exact() {
+            Some(exact) => exact.try_into().or_else(|_| input.value.try_into()),
+            None => input.value.try_into(),
+        };
+        let mut operands: Self = operands.expect("Failed to generate operands out of FluentNumber");
+        if let Some(exact) = input.exact() {
+            // The integer digits of integers are read from their `f64`, and
+            // the last ones are enough for the rules of larger integers.
+            let digits = exact.trim_start_matches('-');
+            let integer = digits.split('.').next().unwrap_or(digits);
+            let last = &integer[integer.len().saturating_sub(18)..];
+            operands.i = integer
+                .parse()
+                .or_else(|_| last.parse())
+                .unwrap_or(operands.i);
+        }
         if let Some(mfd) = input.options.minimum_fraction_digits {
             if mfd > operands.v {
                 operands.f *= 10_u64.pow(mfd as u32 - operands.v as u32);
@@ -242,9 +778,39 @@ impl From<&FluentNumber> for PluralOperands {
     }
 }
 
-from_num!(i8 i16 i32 i64 i128 isize);
-from_num!(u8 u16 u32 u64 u128 usize);
-from_num!(f32 f64);
+from_num!(FluentNumber::from_integer: i8 i16 i32 i64 i128 isize);
+from_num!(FluentNumber::from_integer: u8 u16 u32 u64 u128 usize);
+from_num!(FluentNumber::from_float: f32 f64);
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for FluentNumber {
+    /// Converts a decimal, keeping its digits and its trailing zeros, as in
+    /// "19.90".
+    fn from(input: rust_decimal::Decimal) -> Self {
+        input
+            .to_string()
+            .parse()
+            .expect("Decimals are formatted as valid numbers")
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for FluentValue<'_> {
+    fn from(input: rust_decimal::Decimal) -> Self {
+        FluentValue::Number(input.into())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<&FluentNumber> for rust_decimal::Decimal {
+    type Error = rust_decimal::Error;
+
+    /// Converts a number to a decimal, from its exact digits if they are
+    /// known.
+    fn try_from(input: &FluentNumber) -> Result<Self, Self::Error> {
+        input.digits().parse()
+    }
+}
 
 #[cfg(test)]
 mod tests {
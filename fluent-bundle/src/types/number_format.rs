@@ -0,0 +1,218 @@
+use fixed_decimal::{FixedDecimal, Sign, SignDisplay};
+use icu_decimal::options::GroupingStrategy;
+use icu_decimal::{DecimalError, FixedDecimalFormatter};
+use intl_memoizer::Memoizable;
+use unic_langid::LanguageIdentifier;
+
+use super::{
+    FluentNumber, FluentNumberCurrencyDisplayStyle, FluentNumberGrouping, FluentNumberRoundingMode,
+    FluentNumberSignDisplay, FluentNumberStyle,
+};
+use crate::memoizer::MemoizerKind;
+
+/// Formats a number as `Intl.NumberFormat` does, with the digits, separators
+/// and signs of the locale of `intls`, or returns `None` if there is no data
+/// for it.
+pub(crate) fn format<M: MemoizerKind>(
+    number: &FluentNumber,
+    intls: &M,
+    langid: Option<&LanguageIdentifier>,
+) -> Option<String> {
+    let options = &number.options;
+    let currency = match (options.style, &options.currency) {
+        (FluentNumberStyle::Currency, Some(currency)) => Some(currency.to_ascii_uppercase()),
+        _ => None,
+    };
+    // `f64` is displayed with the shortest digits which parse back to it.
+    let mut value: FixedDecimal = number.digits().parse().ok()?;
+    if options.style == FluentNumberStyle::Percent {
+        value.multiply_pow10(2);
+        value.trim_start();
+    }
+    round(&mut value, number, currency.as_deref());
+    value.apply_sign_display(match options.sign_display {
+        FluentNumberSignDisplay::Auto => SignDisplay::Auto,
+        FluentNumberSignDisplay::Always => SignDisplay::Always,
+        FluentNumberSignDisplay::ExceptZero => SignDisplay::ExceptZero,
+        FluentNumberSignDisplay::Negative => SignDisplay::Negative,
+        FluentNumberSignDisplay::Never => SignDisplay::Never,
+    });
+
+    let (prefix, suffix) = match (options.style, &currency, langid) {
+        (FluentNumberStyle::Percent, _, Some(langid)) => percent_affixes(langid),
+        (FluentNumberStyle::Percent, _, None) => (String::new(), "%".to_string()),
+        (_, Some(currency), _) => currency_affixes(currency, options.currency_display, langid),
+        _ => (String::new(), String::new()),
+    };
+    let grouping = match options.grouping {
+        _ if !options.use_grouping => GroupingStrategy::Never,
+        FluentNumberGrouping::Auto => GroupingStrategy::Auto,
+        FluentNumberGrouping::Always => GroupingStrategy::Always,
+        FluentNumberGrouping::Min2 => GroupingStrategy::Min2,
+    };
+    intls
+        .with_try_get_threadsafe::<DecimalFormat, _, _>((grouping,), |format| {
+            let signed = format.0.format_to_string(&value);
+            if prefix.is_empty() && suffix.is_empty() {
+                return signed;
+            }
+            // The affixes go between the sign and the digits, as in "-$5.00".
+            let mut digits = value.clone();
+            digits.set_sign(Sign::None);
+            let digits = format.0.format_to_string(&digits);
+            signed.replacen(&digits, &format!("{}{}{}", prefix, digits, suffix), 1)
+        })
+        .ok()
+}
+
+/// Rounds `value` to the significant or fraction digits of the options of
+/// `number`, and pads it to their minimum numbers of digits.
+fn round(value: &mut FixedDecimal, number: &FluentNumber, currency: Option<&str>) {
+    let options = &number.options;
+    if options.minimum_significant_digits.is_some() || options.maximum_significant_digits.is_some()
+    {
+        let max = options
+            .maximum_significant_digits
+            .unwrap_or(21)
+            .clamp(1, 21) as i16;
+        let min = options.minimum_significant_digits.unwrap_or(1).clamp(1, 21) as i16;
+        round_at(
+            value,
+            options.rounding_mode,
+            value.nonzero_magnitude_start() - max + 1,
+        );
+        value.trim_end();
+        value.pad_end(value.nonzero_magnitude_start() - min.min(max) + 1);
+    } else {
+        let default = match currency {
+            Some(currency) => currency_digits(currency),
+            None => 0,
+        };
+        let min = options.minimum_fraction_digits.unwrap_or(default).min(20);
+        let max = match options.maximum_fraction_digits {
+            Some(max) => max.clamp(min, 20),
+            None if options.style == FluentNumberStyle::Percent => min,
+            None => min.max(default).max(3),
+        };
+        round_at(value, options.rounding_mode, -(max as i16));
+        value.trim_end();
+        value.pad_end(-(min as i16));
+    }
+    if let Some(min) = options.minimum_integer_digits {
+        value.pad_start(min.clamp(1, 21) as i16);
+    }
+}
+
+/// Rounds `value` at the digit of magnitude `position` with a rounding mode.
+fn round_at(value: &mut FixedDecimal, mode: FluentNumberRoundingMode, position: i16) {
+    match mode {
+        FluentNumberRoundingMode::Ceil => value.ceil(position),
+        FluentNumberRoundingMode::Floor => value.floor(position),
+        FluentNumberRoundingMode::Expand => value.expand(position),
+        FluentNumberRoundingMode::Trunc => value.trunc(position),
+        FluentNumberRoundingMode::HalfCeil => value.half_ceil(position),
+        FluentNumberRoundingMode::HalfFloor => value.half_floor(position),
+        FluentNumberRoundingMode::HalfExpand => value.half_expand(position),
+        FluentNumberRoundingMode::HalfTrunc => value.half_trunc(position),
+        FluentNumberRoundingMode::HalfEven => value.half_even(position),
+    }
+}
+
+/// Returns the number of fraction digits of an ISO 4217 currency.
+fn currency_digits(currency: &str) -> usize {
+    match currency {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+/// Returns the text around the digits of a percentage in a language.
+fn percent_affixes(langid: &LanguageIdentifier) -> (String, String) {
+    let (prefix, suffix) = match langid.language.as_str() {
+        "eu" | "tr" => ("%", ""),
+        "fr" => ("", "\u{202F}%"),
+        "bg" | "cs" | "da" | "de" | "es" | "et" | "fi" | "hr" | "lt" | "lv" | "nb" | "nn"
+        | "no" | "ru" | "sk" | "sl" | "sv" | "uk" => ("", "\u{A0}%"),
+        _ => ("", "%"),
+    };
+    (prefix.to_string(), suffix.to_string())
+}
+
+/// Returns the text around the digits of an amount of a currency in a
+/// language.
+fn currency_affixes(
+    currency: &str,
+    display: FluentNumberCurrencyDisplayStyle,
+    langid: Option<&LanguageIdentifier>,
+) -> (String, String) {
+    let symbol = match display {
+        FluentNumberCurrencyDisplayStyle::Symbol => currency_symbol(currency, langid),
+        // Without the plural names of currencies, names are written as codes.
+        FluentNumberCurrencyDisplayStyle::Code | FluentNumberCurrencyDisplayStyle::Name => currency,
+    };
+    let language = langid.map_or("en", |langid| langid.language.as_str());
+    let region = langid.and_then(|langid| langid.region);
+    let symbol_first = match language {
+        "en" | "he" | "hi" | "ja" | "ko" | "nl" | "th" | "zh" => true,
+        "pt" => region.map_or(true, |region| region.as_str() == "BR"),
+        _ => false,
+    };
+    // Codes are separated from the digits, like symbols written after them.
+    let space = if !symbol_first || language == "nl" || symbol.chars().any(char::is_alphabetic) {
+        "\u{A0}"
+    } else {
+        ""
+    };
+    if symbol_first {
+        (format!("{}{}", symbol, space), String::new())
+    } else {
+        (String::new(), format!("{}{}", space, symbol))
+    }
+}
+
+/// Returns the symbol of a currency in a language, or its code if it has no
+/// common symbol.
+fn currency_symbol<'a>(currency: &'a str, langid: Option<&LanguageIdentifier>) -> &'a str {
+    let region = langid.and_then(|langid| langid.region);
+    let region = region.as_ref().map(|region| region.as_str());
+    let language = langid.map_or("en", |langid| langid.language.as_str());
+    match (currency, region, language) {
+        ("USD", Some("US") | None, "en") => "$",
+        ("USD", ..) => "US$",
+        ("EUR", ..) => "€",
+        ("GBP", ..) => "£",
+        ("JPY", _, "ja") => "￥",
+        ("JPY", ..) => "¥",
+        ("CNY", _, "zh") => "¥",
+        ("CNY", ..) => "CN¥",
+        ("INR", ..) => "₹",
+        ("KRW", ..) => "₩",
+        ("ILS", ..) => "₪",
+        ("VND", ..) => "₫",
+        ("BRL", ..) => "R$",
+        ("CAD", Some("CA"), _) => "$",
+        ("CAD", ..) => "CA$",
+        ("AUD", Some("AU"), _) => "$",
+        ("AUD", ..) => "A$",
+        ("PLN", _, "pl") => "zł",
+        ("RUB", _, "ru") => "₽",
+        ("UAH", _, "uk") => "₴",
+        ("TRY", _, "tr") => "₺",
+        _ => currency,
+    }
+}
+
+/// The ICU4X formatter of decimal numbers, memoized per locale.
+struct DecimalFormat(FixedDecimalFormatter);
+
+impl Memoizable for DecimalFormat {
+    /// When the digits are grouped.
+    type Args = (GroupingStrategy,);
+    type Error = DecimalError;
+    fn construct(lang: LanguageIdentifier, args: Self::Args) -> Result<Self, Self::Error> {
+        let locale = super::icu_locale(&lang);
+        FixedDecimalFormatter::try_new(&locale, args.0.into()).map(Self)
+    }
+}
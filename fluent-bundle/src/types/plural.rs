@@ -1,10 +1,91 @@
+#[cfg(feature = "plural-rules")]
 use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+#[cfg(feature = "plural-rules")]
 use intl_memoizer::Memoizable;
+#[cfg(feature = "plural-rules")]
 use intl_pluralrules::{PluralRuleType, PluralRules as IntlPluralRules};
 use unic_langid::LanguageIdentifier;
 
+use super::FluentNumber;
+
+/// The CLDR plural category of a number, which selects the variant with the
+/// same key, such as `[one]` or `[few]`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Returns the category named by a variant key, or `None` if the key is
+    /// not a plural category.
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "zero" => Some(Self::Zero),
+            "one" => Some(Self::One),
+            "two" => Some(Self::Two),
+            "few" => Some(Self::Few),
+            "many" => Some(Self::Many),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    /// Returns the variant key of the category, such as `"few"`.
+    pub const fn as_key(self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+#[cfg(feature = "plural-rules")]
+impl From<intl_pluralrules::PluralCategory> for PluralCategory {
+    fn from(input: intl_pluralrules::PluralCategory) -> Self {
+        match input {
+            intl_pluralrules::PluralCategory::ZERO => Self::Zero,
+            intl_pluralrules::PluralCategory::ONE => Self::One,
+            intl_pluralrules::PluralCategory::TWO => Self::Two,
+            intl_pluralrules::PluralCategory::FEW => Self::Few,
+            intl_pluralrules::PluralCategory::MANY => Self::Many,
+            intl_pluralrules::PluralCategory::OTHER => Self::Other,
+        }
+    }
+}
+
+/// Resolves the plural categories of numbers in place of the builtin
+/// `intl_pluralrules` data, see
+/// [`FluentBundle::set_plural_rules`](crate::bundle::FluentBundle::set_plural_rules).
+///
+/// Implementations can wrap `icu_plurals`, building its operands from
+/// [`FluentNumber::as_string`], or provide their own rules.
+pub trait PluralRulesBackend: Send + Sync {
+    /// Returns the category of `number` in `locale`, or `None` if it has
+    /// none, in which case only numeric keys and the default variant match.
+    ///
+    /// Ordinal numbers, such as the ones returned by the builtin `ORDINAL()`
+    /// function, have the [`Ordinal`](super::FluentNumberType::Ordinal) type
+    /// in their options.
+    fn category(
+        &self,
+        locale: &LanguageIdentifier,
+        number: &FluentNumber,
+    ) -> Option<PluralCategory>;
+}
+
+#[cfg(feature = "plural-rules")]
 pub struct PluralRules(pub IntlPluralRules);
 
+#[cfg(feature = "plural-rules")]
 impl Memoizable for PluralRules {
     type Args = (PluralRuleType,);
     type Error = &'static str;
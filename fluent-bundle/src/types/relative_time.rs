@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+
+use fixed_decimal::FixedDecimal;
+use icu_experimental::relativetime::{
+    RelativeTimeError, RelativeTimeFormatter, RelativeTimeFormatterOptions,
+};
+use intl_memoizer::Memoizable;
+use unic_langid::LanguageIdentifier;
+
+use super::FluentType;
+
+/// The width of a [`FluentRelativeTime`], as in the `style` option of
+/// `Intl.RelativeTimeFormat`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum RelativeTimeStyle {
+    /// "in 3 minutes"
+    #[default]
+    Long,
+    /// "in 3 min."
+    Short,
+    /// "in 3m"
+    Narrow,
+}
+
+impl From<&str> for RelativeTimeStyle {
+    fn from(input: &str) -> Self {
+        match input {
+            "short" => Self::Short,
+            "narrow" => Self::Narrow,
+            _ => Self::Long,
+        }
+    }
+}
+
+/// The unit a [`FluentRelativeTime`] is formatted in.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+enum RelativeTimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RelativeTimeUnit {
+    /// Returns the largest unit which fits at least once in `seconds`, and its
+    /// length in seconds. Months and years use their average Gregorian length.
+    fn select(seconds: f64) -> (Self, f64) {
+        const UNITS: [(RelativeTimeUnit, f64); 6] = [
+            (RelativeTimeUnit::Year, 31_556_952.0),
+            (RelativeTimeUnit::Month, 2_629_746.0),
+            (RelativeTimeUnit::Week, 604_800.0),
+            (RelativeTimeUnit::Day, 86_400.0),
+            (RelativeTimeUnit::Hour, 3_600.0),
+            (RelativeTimeUnit::Minute, 60.0),
+        ];
+        UNITS
+            .into_iter()
+            .find(|(_, length)| seconds.abs() >= *length)
+            .unwrap_or((RelativeTimeUnit::Second, 1.0))
+    }
+}
+
+/// A time relative to now, in seconds, which is formatted as, for instance,
+/// "3 minutes ago" or "in 2 days" in the bundle locale.
+///
+/// This is the value returned by the builtin `RELATIVETIME()` function, see
+/// [`builtins::RELATIVETIME`](crate::builtins::RELATIVETIME). Negative values
+/// are in the past.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluentRelativeTime {
+    pub seconds: f64,
+    pub style: RelativeTimeStyle,
+}
+
+impl FluentRelativeTime {
+    pub fn new(seconds: f64, style: RelativeTimeStyle) -> Self {
+        Self { seconds, style }
+    }
+
+    /// Returns the unit and the rounded number of units to format.
+    fn units(&self) -> (RelativeTimeUnit, i64) {
+        let (unit, length) = RelativeTimeUnit::select(self.seconds);
+        (unit, (self.seconds / length).round() as i64)
+    }
+
+    /// Formats the value without locale data, in English, when no formatter
+    /// is available for the locale.
+    fn fallback(&self) -> String {
+        let (unit, value) = self.units();
+        let unit = format!("{:?}", unit).to_lowercase();
+        let plural = if value.abs() == 1 { "" } else { "s" };
+        if value < 0 {
+            format!("{} {}{} ago", -value, unit, plural)
+        } else {
+            format!("in {} {}{}", value, unit, plural)
+        }
+    }
+}
+
+impl FluentType for FluentRelativeTime {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, intls: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+        let (unit, value) = self.units();
+        intls
+            .with_try_get::<RelativeTimeFormat, _, _>((self.style, unit), |rtf| {
+                rtf.0.format(FixedDecimal::from(value)).to_string()
+            })
+            .unwrap_or_else(|_| self.fallback())
+            .into()
+    }
+
+    fn as_string_threadsafe(
+        &self,
+        intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ) -> Cow<'static, str> {
+        let (unit, value) = self.units();
+        intls
+            .with_try_get::<RelativeTimeFormat, _, _>((self.style, unit), |rtf| {
+                rtf.0.format(FixedDecimal::from(value)).to_string()
+            })
+            .unwrap_or_else(|_| self.fallback())
+            .into()
+    }
+}
+
+/// The ICU4X formatter of a style and unit, memoized per locale.
+struct RelativeTimeFormat(RelativeTimeFormatter);
+
+impl Memoizable for RelativeTimeFormat {
+    type Args = (RelativeTimeStyle, RelativeTimeUnit);
+    type Error = RelativeTimeError;
+    fn construct(lang: LanguageIdentifier, args: Self::Args) -> Result<Self, Self::Error> {
+        use RelativeTimeStyle::*;
+        use RelativeTimeUnit::*;
+
+        let locale = super::icu_locale(&lang);
+        let options = RelativeTimeFormatterOptions::default();
+        let formatter = match args {
+            (Long, Second) => RelativeTimeFormatter::try_new_long_second(&locale, options),
+            (Long, Minute) => RelativeTimeFormatter::try_new_long_minute(&locale, options),
+            (Long, Hour) => RelativeTimeFormatter::try_new_long_hour(&locale, options),
+            (Long, Day) => RelativeTimeFormatter::try_new_long_day(&locale, options),
+            (Long, Week) => RelativeTimeFormatter::try_new_long_week(&locale, options),
+            (Long, Month) => RelativeTimeFormatter::try_new_long_month(&locale, options),
+            (Long, Year) => RelativeTimeFormatter::try_new_long_year(&locale, options),
+            (Short, Second) => RelativeTimeFormatter::try_new_short_second(&locale, options),
+            (Short, Minute) => RelativeTimeFormatter::try_new_short_minute(&locale, options),
+            (Short, Hour) => RelativeTimeFormatter::try_new_short_hour(&locale, options),
+            (Short, Day) => RelativeTimeFormatter::try_new_short_day(&locale, options),
+            (Short, Week) => RelativeTimeFormatter::try_new_short_week(&locale, options),
+            (Short, Month) => RelativeTimeFormatter::try_new_short_month(&locale, options),
+            (Short, Year) => RelativeTimeFormatter::try_new_short_year(&locale, options),
+            (Narrow, Second) => RelativeTimeFormatter::try_new_narrow_second(&locale, options),
+            (Narrow, Minute) => RelativeTimeFormatter::try_new_narrow_minute(&locale, options),
+            (Narrow, Hour) => RelativeTimeFormatter::try_new_narrow_hour(&locale, options),
+            (Narrow, Day) => RelativeTimeFormatter::try_new_narrow_day(&locale, options),
+            (Narrow, Week) => RelativeTimeFormatter::try_new_narrow_week(&locale, options),
+            (Narrow, Month) => RelativeTimeFormatter::try_new_narrow_month(&locale, options),
+            (Narrow, Year) => RelativeTimeFormatter::try_new_narrow_year(&locale, options),
+        }?;
+        Ok(Self(formatter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_largest_unit() {
+        let units = |seconds| FluentRelativeTime::new(seconds, RelativeTimeStyle::Long).units();
+        assert_eq!(units(-30.0), (RelativeTimeUnit::Second, -30));
+        assert_eq!(units(-180.0), (RelativeTimeUnit::Minute, -3));
+        assert_eq!(units(172_800.0), (RelativeTimeUnit::Day, 2));
+        assert_eq!(units(-1_209_600.0), (RelativeTimeUnit::Week, -2));
+        assert_eq!(units(63_113_904.0), (RelativeTimeUnit::Year, 2));
+    }
+}
@@ -0,0 +1,119 @@
+use unic_langid::LanguageIdentifier;
+
+/// Spells out `value` in the language of `langid`, as a cardinal number such
+/// as "twenty-one", or as an ordinal number such as "twenty-first".
+///
+/// Returns `None` for the languages without spellout rules, and for the
+/// numbers which are not integers or are too large to spell out, which are
+/// then formatted with digits.
+pub(crate) fn spell_out(
+    value: f64,
+    ordinal: bool,
+    langid: Option<&LanguageIdentifier>,
+) -> Option<String> {
+    if value.fract() != 0.0 || value.abs() >= 1e15 || (ordinal && value < 0.0) {
+        return None;
+    }
+    let value = value as i64;
+    match langid.map(|langid| langid.language.as_str()) {
+        Some("en") => Some(english(value, ordinal)),
+        _ => None,
+    }
+}
+
+const ENGLISH_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ENGLISH_SCALES: [(i64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Spells out `value` with the `spellout-numbering` and `spellout-ordinal`
+/// rules of CLDR for English, as in "one hundred twenty-one".
+fn english(value: i64, ordinal: bool) -> String {
+    if value < 0 {
+        return format!("minus {}", english(-value, ordinal));
+    }
+
+    let mut words = vec![];
+    let mut rest = value;
+    for (scale, name) in ENGLISH_SCALES {
+        if rest >= scale {
+            words.push(english_below_thousand(rest / scale));
+            words.push(name.to_string());
+            rest %= scale;
+        }
+    }
+    if rest > 0 || words.is_empty() {
+        words.push(english_below_thousand(rest));
+    }
+    let cardinal = words.join(" ");
+    if !ordinal {
+        return cardinal;
+    }
+
+    // Only the last word, after any hyphen, takes the ordinal form.
+    let split = cardinal.rfind(['-', ' ']).map_or(0, |pos| pos + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ => match last.strip_suffix('y') {
+            Some(stem) => format!("{}ieth", stem),
+            None => format!("{}th", last),
+        },
+    };
+    format!("{}{}", head, last)
+}
+
+fn english_below_thousand(value: i64) -> String {
+    let (hundreds, rest) = (value / 100, (value % 100) as usize);
+    let rest = match rest {
+        0 => None,
+        1..=19 => Some(ENGLISH_ONES[rest].to_string()),
+        _ if rest % 10 == 0 => Some(ENGLISH_TENS[rest / 10].to_string()),
+        _ => Some(format!(
+            "{}-{}",
+            ENGLISH_TENS[rest / 10],
+            ENGLISH_ONES[rest % 10]
+        )),
+    };
+    match (hundreds, rest) {
+        (0, Some(rest)) => rest,
+        (0, None) => ENGLISH_ONES[0].to_string(),
+        (hundreds, None) => format!("{} hundred", ENGLISH_ONES[hundreds as usize]),
+        (hundreds, Some(rest)) => format!("{} hundred {}", ENGLISH_ONES[hundreds as usize], rest),
+    }
+}
@@ -0,0 +1,299 @@
+use std::borrow::Cow;
+
+use super::{FluentNumber, FluentType};
+use crate::memoizer::MemoizerKind;
+
+/// The width of the unit name of a [`FluentUnit`], as in the `unitDisplay`
+/// option of `Intl.NumberFormat`.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum UnitDisplay {
+    /// "16 megabytes"
+    Long,
+    /// "16 MB"
+    #[default]
+    Short,
+    /// "16MB"
+    Narrow,
+}
+
+impl From<&str> for UnitDisplay {
+    fn from(input: &str) -> Self {
+        match input {
+            "long" => Self::Long,
+            "narrow" => Self::Narrow,
+            _ => Self::Short,
+        }
+    }
+}
+
+/// A number of a measurement unit, such as `megabyte` or `kilometer`, which
+/// is formatted with the CLDR English name of the unit, such as "16 MB" or
+/// "3 kilometers".
+///
+/// This is the value returned by the builtin `UNIT()` function, see
+/// [`builtins::UNIT`](crate::builtins::UNIT). The unit is a CLDR core unit
+/// identifier. The number is formatted as by `NUMBER()`, with the digits of
+/// the bundle locale with the `builtins` feature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluentUnit {
+    pub value: FluentNumber,
+    pub unit: String,
+    pub display: UnitDisplay,
+}
+
+impl FluentUnit {
+    pub fn new(value: FluentNumber, unit: impl Into<String>, display: UnitDisplay) -> Self {
+        Self {
+            value,
+            unit: unit.into(),
+            display,
+        }
+    }
+
+    fn format<M: MemoizerKind>(&self, intls: &M) -> String {
+        let Some((_, long, short, narrow)) = UNITS.iter().find(|(id, ..)| *id == self.unit) else {
+            return self.fallback();
+        };
+        // English uses the singular for exactly "1".
+        let plural = usize::from(self.value.as_string() != "1");
+        let pattern = match self.display {
+            UnitDisplay::Long => long[plural],
+            UnitDisplay::Short => short[plural],
+            UnitDisplay::Narrow => narrow,
+        };
+        pattern.replace("{0}", &self.value.format(intls, None, None))
+    }
+
+    /// Formats the value of a unit which is not in [`UNITS`].
+    fn fallback(&self) -> String {
+        format!("{} {}", self.value.as_string(), self.unit)
+    }
+}
+
+impl FluentType for FluentUnit {
+    fn duplicate(&self) -> Box<dyn FluentType + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_string(&self, intls: &intl_memoizer::IntlLangMemoizer) -> Cow<'static, str> {
+        self.format(intls).into()
+    }
+
+    fn as_string_threadsafe(
+        &self,
+        intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+    ) -> Cow<'static, str> {
+        self.format(intls).into()
+    }
+}
+
+/// The CLDR core unit identifiers of the units of [`FluentUnit`], with their
+/// English patterns: in the singular and the plural for the long and short
+/// displays, and the narrow one.
+#[allow(clippy::type_complexity)]
+const UNITS: &[(&str, [&str; 2], [&str; 2], &str)] = &[
+    (
+        "bit",
+        ["{0} bit", "{0} bits"],
+        ["{0} bit", "{0} bit"],
+        "{0}bit",
+    ),
+    (
+        "byte",
+        ["{0} byte", "{0} bytes"],
+        ["{0} byte", "{0} byte"],
+        "{0}B",
+    ),
+    (
+        "kilobit",
+        ["{0} kilobit", "{0} kilobits"],
+        ["{0} kb", "{0} kb"],
+        "{0}kb",
+    ),
+    (
+        "kilobyte",
+        ["{0} kilobyte", "{0} kilobytes"],
+        ["{0} kB", "{0} kB"],
+        "{0}kB",
+    ),
+    (
+        "megabit",
+        ["{0} megabit", "{0} megabits"],
+        ["{0} Mb", "{0} Mb"],
+        "{0}Mb",
+    ),
+    (
+        "megabyte",
+        ["{0} megabyte", "{0} megabytes"],
+        ["{0} MB", "{0} MB"],
+        "{0}MB",
+    ),
+    (
+        "gigabit",
+        ["{0} gigabit", "{0} gigabits"],
+        ["{0} Gb", "{0} Gb"],
+        "{0}Gb",
+    ),
+    (
+        "gigabyte",
+        ["{0} gigabyte", "{0} gigabytes"],
+        ["{0} GB", "{0} GB"],
+        "{0}GB",
+    ),
+    (
+        "terabyte",
+        ["{0} terabyte", "{0} terabytes"],
+        ["{0} TB", "{0} TB"],
+        "{0}TB",
+    ),
+    (
+        "petabyte",
+        ["{0} petabyte", "{0} petabytes"],
+        ["{0} PB", "{0} PB"],
+        "{0}PB",
+    ),
+    (
+        "millimeter",
+        ["{0} millimeter", "{0} millimeters"],
+        ["{0} mm", "{0} mm"],
+        "{0}mm",
+    ),
+    (
+        "centimeter",
+        ["{0} centimeter", "{0} centimeters"],
+        ["{0} cm", "{0} cm"],
+        "{0}cm",
+    ),
+    (
+        "meter",
+        ["{0} meter", "{0} meters"],
+        ["{0} m", "{0} m"],
+        "{0}m",
+    ),
+    (
+        "kilometer",
+        ["{0} kilometer", "{0} kilometers"],
+        ["{0} km", "{0} km"],
+        "{0}km",
+    ),
+    (
+        "inch",
+        ["{0} inch", "{0} inches"],
+        ["{0} in", "{0} in"],
+        "{0}″",
+    ),
+    (
+        "foot",
+        ["{0} foot", "{0} feet"],
+        ["{0} ft", "{0} ft"],
+        "{0}′",
+    ),
+    (
+        "mile",
+        ["{0} mile", "{0} miles"],
+        ["{0} mi", "{0} mi"],
+        "{0}mi",
+    ),
+    (
+        "gram",
+        ["{0} gram", "{0} grams"],
+        ["{0} g", "{0} g"],
+        "{0}g",
+    ),
+    (
+        "kilogram",
+        ["{0} kilogram", "{0} kilograms"],
+        ["{0} kg", "{0} kg"],
+        "{0}kg",
+    ),
+    (
+        "ounce",
+        ["{0} ounce", "{0} ounces"],
+        ["{0} oz", "{0} oz"],
+        "{0}oz",
+    ),
+    (
+        "pound",
+        ["{0} pound", "{0} pounds"],
+        ["{0} lb", "{0} lb"],
+        "{0}#",
+    ),
+    (
+        "milliliter",
+        ["{0} milliliter", "{0} milliliters"],
+        ["{0} mL", "{0} mL"],
+        "{0}mL",
+    ),
+    (
+        "liter",
+        ["{0} liter", "{0} liters"],
+        ["{0} L", "{0} L"],
+        "{0}L",
+    ),
+    (
+        "millisecond",
+        ["{0} millisecond", "{0} milliseconds"],
+        ["{0} ms", "{0} ms"],
+        "{0}ms",
+    ),
+    (
+        "second",
+        ["{0} second", "{0} seconds"],
+        ["{0} sec", "{0} sec"],
+        "{0}s",
+    ),
+    (
+        "minute",
+        ["{0} minute", "{0} minutes"],
+        ["{0} min", "{0} min"],
+        "{0}m",
+    ),
+    (
+        "hour",
+        ["{0} hour", "{0} hours"],
+        ["{0} hr", "{0} hr"],
+        "{0}h",
+    ),
+    (
+        "day",
+        ["{0} day", "{0} days"],
+        ["{0} day", "{0} days"],
+        "{0}d",
+    ),
+    (
+        "week",
+        ["{0} week", "{0} weeks"],
+        ["{0} wk", "{0} wks"],
+        "{0}w",
+    ),
+    (
+        "month",
+        ["{0} month", "{0} months"],
+        ["{0} mth", "{0} mths"],
+        "{0}m",
+    ),
+    (
+        "year",
+        ["{0} year", "{0} years"],
+        ["{0} yr", "{0} yrs"],
+        "{0}y",
+    ),
+    (
+        "percent",
+        ["{0} percent", "{0} percent"],
+        ["{0}%", "{0}%"],
+        "{0}%",
+    ),
+    (
+        "celsius",
+        ["{0} degree Celsius", "{0} degrees Celsius"],
+        ["{0}°C", "{0}°C"],
+        "{0}°C",
+    ),
+    (
+        "fahrenheit",
+        ["{0} degree Fahrenheit", "{0} degrees Fahrenheit"],
+        ["{0}°F", "{0}°F"],
+        "{0}°",
+    ),
+];
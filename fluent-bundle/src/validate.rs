@@ -0,0 +1,817 @@
+//! `validate` checks the messages of a [`FluentBundle`] for mistakes which
+//! formatting does not report, see [`FluentBundle::validate_references`] and
+//! [`TermSignature`], and formats all of them at once, see
+//! [`FluentBundle::check_all`]. Calls to functions are checked against their
+//! [`FunctionSignature`] while formatting.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Range;
+
+use fluent_syntax::ast;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::args::FluentArgs;
+use crate::bundle::FluentBundle;
+use crate::entry::GetEntry;
+use crate::memoizer::MemoizerKind;
+use crate::resource::FluentResource;
+use crate::types::FluentValue;
+use crate::FluentError;
+
+/// A message reference whose referenced message uses a variable which the
+/// referencing pattern does not, see [`FluentBundle::validate_references`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceDiagnostic {
+    /// The referencing message, with the attribute if the reference is in one,
+    /// as in `message.attribute`.
+    pub message: String,
+    /// The referenced message, with the attribute if one is referenced.
+    pub reference: String,
+    /// The name of the variable, without the `$`.
+    pub variable: String,
+    /// The index of the resource of the referencing message, in the order
+    /// the resources were added to the bundle.
+    pub resource: usize,
+    /// The byte range of the reference in the source of the resource, if the
+    /// resource has a source.
+    pub span: Option<Range<usize>>,
+}
+
+impl fmt::Display for ReferenceDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} references {}, which uses ${} without {} using it",
+            self.message, self.reference, self.variable, self.message
+        )
+    }
+}
+
+/// An error reported while formatting a message, see
+/// [`FluentBundle::check_all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatDiagnostic {
+    /// The message, with the attribute if the error is in one, as in
+    /// `message.attribute`.
+    pub message: String,
+    /// The index of the resource of the message, in the order the resources
+    /// were added to the bundle.
+    pub resource: usize,
+    pub error: FluentError,
+}
+
+impl fmt::Display for FormatDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.message, self.error)
+    }
+}
+
+/// A writer which discards what is written.
+struct Discard;
+
+impl fmt::Write for Discard {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// The variables and message references of a pattern.
+#[derive(Default)]
+struct PatternRefs<'s> {
+    variables: FxHashSet<&'s str>,
+    messages: Vec<(&'s str, Option<&'s str>)>,
+}
+
+impl<'s> PatternRefs<'s> {
+    fn new(pattern: &ast::Pattern<&'s str>) -> Self {
+        let mut refs = Self::default();
+        refs.add_pattern(pattern);
+        refs
+    }
+
+    fn add_pattern(&mut self, pattern: &ast::Pattern<&'s str>) {
+        for element in &pattern.elements {
+            if let ast::PatternElement::Placeable { expression } = element {
+                self.add_expression(expression);
+            }
+        }
+    }
+
+    fn add_expression(&mut self, expression: &ast::Expression<&'s str>) {
+        match expression {
+            ast::Expression::Inline(expression) => self.add_inline_expression(expression),
+            ast::Expression::Select { selector, variants } => {
+                self.add_inline_expression(selector);
+                for variant in variants {
+                    self.add_pattern(&variant.value);
+                }
+            }
+        }
+    }
+
+    fn add_inline_expression(&mut self, expression: &ast::InlineExpression<&'s str>) {
+        match expression {
+            ast::InlineExpression::VariableReference { id } => {
+                self.variables.insert(id.name);
+            }
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                self.messages
+                    .push((id.name, attribute.as_ref().map(|attr| attr.name)));
+            }
+            // The pattern of a term only sees the arguments of its reference,
+            // which are added here.
+            ast::InlineExpression::TermReference {
+                arguments: Some(arguments),
+                ..
+            } => self.add_arguments(arguments),
+            ast::InlineExpression::FunctionReference { arguments, .. } => {
+                self.add_arguments(arguments);
+            }
+            ast::InlineExpression::Placeable { expression } => self.add_expression(expression),
+            _ => {}
+        }
+    }
+
+    fn add_arguments(&mut self, arguments: &ast::CallArguments<&'s str>) {
+        for expression in &arguments.positional {
+            self.add_inline_expression(expression);
+        }
+        for argument in &arguments.named {
+            self.add_inline_expression(&argument.value);
+        }
+    }
+}
+
+type PatternKey<'s> = (&'s str, Option<&'s str>);
+
+/// The parameters of a term and the values each of them accepts, such as the
+/// grammatical cases of a brand name, see [`FluentBundle::add_term_signature`].
+///
+/// A term which selects on one of the parameters must have a variant for
+/// each of its values. A term which does not select on it, in a language
+/// where the brand name does not change, is valid too.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::validate::TermSignature;
+///
+/// let signature = TermSignature::new()
+///     .param("case", ["nominative", "genitive"]);
+/// assert!(signature.accepts("case", "genitive"));
+/// assert!(!signature.accepts("case", "dative"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TermSignature {
+    params: Vec<(String, Vec<String>)>,
+}
+
+impl TermSignature {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares the parameter `name`, which accepts `values`.
+    pub fn param<I, S>(mut self, name: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.params
+            .push((name.into(), values.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Returns the values accepted by the parameter `name`, if it is declared.
+    pub fn values(&self, name: &str) -> Option<&[String]> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, values)| values.as_slice())
+    }
+
+    /// Returns `true` if the parameter `name` is declared and accepts `value`.
+    pub fn accepts(&self, name: &str, value: &str) -> bool {
+        self.values(name)
+            .map_or(false, |values| values.iter().any(|v| v == value))
+    }
+}
+
+/// A term definition or reference which does not match the
+/// [`TermSignature`] of the term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TermSignatureError {
+    /// The term selects on `param`, but has no variant for `value`.
+    MissingVariant {
+        term: String,
+        param: String,
+        value: String,
+    },
+    /// The entry `entry` references the term with a parameter which the
+    /// signature does not declare.
+    UnknownParam {
+        entry: String,
+        term: String,
+        param: String,
+    },
+    /// The entry `entry` references the term with a value which the
+    /// parameter does not accept.
+    UnknownValue {
+        entry: String,
+        term: String,
+        param: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for TermSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingVariant { term, param, value } => {
+                write!(f, "-{} has no variant for {}: \"{}\"", term, param, value)
+            }
+            Self::UnknownParam { entry, term, param } => write!(
+                f,
+                "{} references -{} with an unknown parameter {}",
+                entry, term, param
+            ),
+            Self::UnknownValue {
+                entry,
+                term,
+                param,
+                value,
+            } => write!(
+                f,
+                "{} references -{} with an unknown value {}: \"{}\"",
+                entry, term, param, value
+            ),
+        }
+    }
+}
+
+/// The type of the value of a named argument of a function, see
+/// [`FunctionSignature::option`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    Any,
+    String,
+    Number,
+}
+
+impl OptionType {
+    fn accepts(self, value: &FluentValue) -> bool {
+        match self {
+            Self::Any => true,
+            Self::String => matches!(value, FluentValue::String(_)),
+            Self::Number => matches!(value, FluentValue::Number(_)),
+        }
+    }
+}
+
+impl fmt::Display for OptionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Any => "any value",
+            Self::String => "a string",
+            Self::Number => "a number",
+        })
+    }
+}
+
+/// The arguments accepted by a function, such as the single positional
+/// argument and the options of `NUMBER()`, see
+/// [`FluentBundle::add_function_signature`].
+///
+/// Calls which do not match the signature report a
+/// [`FunctionSignatureError`], and the function is not called.
+///
+/// # Example
+///
+/// ```
+/// use fluent_bundle::validate::{FunctionSignature, OptionType};
+///
+/// let signature = FunctionSignature::new()
+///     .positional(1)
+///     .option("width", OptionType::Number);
+/// assert_eq!(signature.option_type("width"), Some(OptionType::Number));
+/// assert_eq!(signature.option_type("height"), None);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FunctionSignature {
+    positional: Option<usize>,
+    options: Vec<(String, OptionType)>,
+}
+
+impl FunctionSignature {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires exactly `count` positional arguments. Without it, any number
+    /// of positional arguments is accepted.
+    pub fn positional(mut self, count: usize) -> Self {
+        self.positional = Some(count);
+        self
+    }
+
+    /// Declares the named argument `name`, whose value has the type `r#type`.
+    pub fn option(mut self, name: impl Into<String>, r#type: OptionType) -> Self {
+        self.options.push((name.into(), r#type));
+        self
+    }
+
+    /// Returns the type of the named argument `name`, if it is declared.
+    pub fn option_type(&self, name: &str) -> Option<OptionType> {
+        self.options
+            .iter()
+            .find(|(option, _)| option == name)
+            .map(|(_, r#type)| *r#type)
+    }
+
+    /// Checks a call to the function `function` against the signature.
+    pub fn check(
+        &self,
+        function: &str,
+        positional: &[FluentValue],
+        named: &FluentArgs,
+    ) -> Vec<FunctionSignatureError> {
+        let mut errors = vec![];
+        if let Some(expected) = self.positional.filter(|count| *count != positional.len()) {
+            errors.push(FunctionSignatureError::ArgumentCount {
+                function: function.to_string(),
+                expected,
+                found: positional.len(),
+            });
+        }
+        for (name, value) in named.iter() {
+            match self.option_type(name) {
+                Some(r#type) if !r#type.accepts(value) => {
+                    errors.push(FunctionSignatureError::InvalidOption {
+                        function: function.to_string(),
+                        option: name.to_string(),
+                        expected: r#type,
+                    });
+                }
+                Some(_) => {}
+                None => errors.push(FunctionSignatureError::UnknownOption {
+                    function: function.to_string(),
+                    option: name.to_string(),
+                    suggestion: self.suggest(name),
+                }),
+            }
+        }
+        errors
+    }
+
+    /// Returns the declared option closest to the unknown option `name`, if
+    /// one is close enough to be a typo of it.
+    fn suggest(&self, name: &str) -> Option<String> {
+        self.options
+            .iter()
+            .map(|(option, _)| (edit_distance(name, option), option))
+            .filter(|(distance, option)| *distance <= (option.len() / 3).max(2))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, option)| option.clone())
+    }
+}
+
+/// Returns the number of characters to insert, delete or substitute to turn
+/// `a` into `b`, ignoring the case.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().flat_map(char::to_lowercase).enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// A function call which does not match the [`FunctionSignature`] of the
+/// function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FunctionSignatureError {
+    /// The function is called with `found` positional arguments instead of
+    /// `expected`.
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    /// The function is called with an option which the signature does not
+    /// declare, and `suggestion` is the declared option with the closest name.
+    UnknownOption {
+        function: String,
+        option: String,
+        suggestion: Option<String>,
+    },
+    /// The function is called with a value of the wrong type for `option`.
+    InvalidOption {
+        function: String,
+        option: String,
+        expected: OptionType,
+    },
+}
+
+impl fmt::Display for FunctionSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ArgumentCount {
+                function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected {} positional argument{}, found {}",
+                function,
+                expected,
+                if *expected == 1 { "" } else { "s" },
+                found
+            ),
+            Self::UnknownOption {
+                function,
+                option,
+                suggestion: Some(suggestion),
+            } => write!(
+                f,
+                "{}: unknown option '{}' (did you mean {}?)",
+                function, option, suggestion
+            ),
+            Self::UnknownOption {
+                function,
+                option,
+                suggestion: None,
+            } => write!(f, "{}: unknown option '{}'", function, option),
+            Self::InvalidOption {
+                function,
+                option,
+                expected,
+            } => write!(f, "{}: option '{}' must be {}", function, option, expected),
+        }
+    }
+}
+
+/// Calls `f` with every expression of `pattern`, including the nested ones.
+fn for_each_expression<'s>(
+    pattern: &ast::Pattern<&'s str>,
+    f: &mut impl FnMut(&ast::Expression<&'s str>),
+) {
+    for element in &pattern.elements {
+        if let ast::PatternElement::Placeable { expression } = element {
+            for_each_in_expression(expression, f);
+        }
+    }
+}
+
+fn for_each_in_expression<'s>(
+    expression: &ast::Expression<&'s str>,
+    f: &mut impl FnMut(&ast::Expression<&'s str>),
+) {
+    f(expression);
+    match expression {
+        ast::Expression::Inline(ast::InlineExpression::Placeable { expression }) => {
+            for_each_in_expression(expression, f);
+        }
+        ast::Expression::Inline(_) => {}
+        ast::Expression::Select { variants, .. } => {
+            for variant in variants {
+                for_each_expression(&variant.value, f);
+            }
+        }
+    }
+}
+
+/// Checks the terms defined and referenced by `res` against `signatures`.
+pub(crate) fn check_term_signatures(
+    res: &FluentResource,
+    signatures: &FxHashMap<String, TermSignature>,
+    errors: &mut Vec<FluentError>,
+) {
+    // The entries of a lazy resource are parsed to be checked.
+    if signatures.is_empty() {
+        return;
+    }
+
+    for entry in res.entries() {
+        let (name, value, attributes) = match entry {
+            ast::Entry::Message(msg) => {
+                (msg.id.name.to_string(), msg.value.as_ref(), &msg.attributes)
+            }
+            ast::Entry::Term(term) => (
+                format!("-{}", term.id.name),
+                Some(&term.value),
+                &term.attributes,
+            ),
+            _ => continue,
+        };
+        let patterns = value
+            .into_iter()
+            .chain(attributes.iter().map(|attr| &attr.value));
+
+        for pattern in patterns {
+            for_each_expression(pattern, &mut |expression| match expression {
+                ast::Expression::Select { selector, variants } => {
+                    let (ast::Entry::Term(term), ast::InlineExpression::VariableReference { id }) =
+                        (entry, selector)
+                    else {
+                        return;
+                    };
+                    let Some(values) = signatures
+                        .get(term.id.name)
+                        .and_then(|signature| signature.values(id.name))
+                    else {
+                        return;
+                    };
+                    for value in values {
+                        let found = variants.iter().any(|variant| match variant.key {
+                            ast::VariantKey::Identifier { name } => name == value,
+                            ast::VariantKey::NumberLiteral { .. } => false,
+                        });
+                        if !found {
+                            errors.push(
+                                TermSignatureError::MissingVariant {
+                                    term: term.id.name.to_string(),
+                                    param: id.name.to_string(),
+                                    value: value.clone(),
+                                }
+                                .into(),
+                            );
+                        }
+                    }
+                }
+                ast::Expression::Inline(ast::InlineExpression::TermReference {
+                    id,
+                    arguments: Some(arguments),
+                    ..
+                }) => {
+                    let Some(signature) = signatures.get(id.name) else {
+                        return;
+                    };
+                    for argument in &arguments.named {
+                        let param = argument.name.name;
+                        let error = match &argument.value {
+                            _ if signature.values(param).is_none() => {
+                                TermSignatureError::UnknownParam {
+                                    entry: name.clone(),
+                                    term: id.name.to_string(),
+                                    param: param.to_string(),
+                                }
+                            }
+                            ast::InlineExpression::StringLiteral { value }
+                                if !signature.accepts(param, value) =>
+                            {
+                                TermSignatureError::UnknownValue {
+                                    entry: name.clone(),
+                                    term: id.name.to_string(),
+                                    param: param.to_string(),
+                                    value: value.to_string(),
+                                }
+                            }
+                            _ => continue,
+                        };
+                        errors.push(error.into());
+                    }
+                }
+                _ => {}
+            });
+        }
+    }
+}
+
+impl<R, M> FluentBundle<R, M>
+where
+    R: Borrow<FluentResource>,
+{
+    /// Checks that the messages referenced by the messages of the bundle do not
+    /// use variables which the referencing pattern does not use itself.
+    ///
+    /// Message references cannot take arguments; the referenced message is
+    /// formatted with the arguments of the referencing one. A translator
+    /// writing `{ other-message }` may expect `$count` to flow through, which
+    /// only works if the application passes `$count` when formatting the
+    /// referencing message, which it has no reason to do unless the message
+    /// uses it. Variables used by messages referenced indirectly are checked
+    /// too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// emails = You have { $count } new emails.
+    /// welcome = Welcome back! { emails }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let diagnostics = bundle.validate_references();
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(
+    ///     diagnostics[0].to_string(),
+    ///     "welcome references emails, which uses $count without welcome using it"
+    /// );
+    /// assert_eq!(diagnostics[0].span, Some(68..74));
+    /// ```
+    pub fn validate_references(&self) -> Vec<ReferenceDiagnostic> {
+        let mut diagnostics = vec![];
+        let mut required = FxHashMap::default();
+
+        for (res_pos, res) in self.resources.iter().enumerate() {
+            let res: &FluentResource = res.borrow();
+            let source = res.source();
+            for entry in res.entries() {
+                let ast::Entry::Message(msg) = entry else {
+                    continue;
+                };
+                // Skip the messages overridden by a later resource.
+                if !self
+                    .get_entry_message(msg.id.name)
+                    .map_or(false, |found| std::ptr::eq(found, msg))
+                {
+                    continue;
+                }
+
+                let patterns = msg.value.iter().map(|value| (None, value)).chain(
+                    msg.attributes
+                        .iter()
+                        .map(|attr| (Some(attr.id.name), &attr.value)),
+                );
+                for (attribute, pattern) in patterns {
+                    let refs = PatternRefs::new(pattern);
+                    for &(id, ref_attribute) in &refs.messages {
+                        let mut variables: Vec<_> = self
+                            .required_variables((id, ref_attribute), &mut required, &mut vec![])
+                            .iter()
+                            .filter(|variable| !refs.variables.contains(*variable))
+                            .copied()
+                            .collect();
+                        variables.sort_unstable();
+                        for variable in variables {
+                            diagnostics.push(ReferenceDiagnostic {
+                                message: pattern_name(msg.id.name, attribute),
+                                reference: pattern_name(id, ref_attribute),
+                                variable: variable.to_string(),
+                                resource: res_pos,
+                                span: span(source, id, ref_attribute),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+
+    /// Formats the value and attributes of every message of the bundle, and
+    /// returns the errors reported, without keeping the formatted strings.
+    ///
+    /// `args` is called with the identifier of each message and returns the
+    /// arguments to format it with, such as representative values of the
+    /// variables an application passes. This gives a single call which checks
+    /// that a whole locale formats cleanly, for instance in CI.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+    /// use unic_langid::langid;
+    ///
+    /// let ftl_string = String::from("
+    /// hello = Hello, { $name }!
+    /// emails = You have { $count } new emails.
+    ///     .title = { missing }
+    /// ");
+    /// let resource = FluentResource::try_new(ftl_string)
+    ///     .expect("Failed to parse an FTL string.");
+    /// let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    /// bundle.add_resource(&resource)
+    ///     .expect("Failed to add FTL resources to the bundle.");
+    ///
+    /// let diagnostics = bundle.check_all(|id| {
+    ///     let mut args = FluentArgs::new();
+    ///     match id {
+    ///         "hello" => args.set("name", "Anna"),
+    ///         "emails" => args.set("count", 3),
+    ///         _ => return None,
+    ///     }
+    ///     Some(args)
+    /// });
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(
+    ///     diagnostics[0].to_string(),
+    ///     "emails.title: Resolver error: Unknown message: missing"
+    /// );
+    /// ```
+    pub fn check_all<'args, F>(&self, mut args: F) -> Vec<FormatDiagnostic>
+    where
+        F: FnMut(&str) -> Option<FluentArgs<'args>>,
+        M: MemoizerKind,
+    {
+        let mut diagnostics = vec![];
+        for (res_pos, res) in self.resources.iter().enumerate() {
+            let res: &FluentResource = res.borrow();
+            for entry in res.entries() {
+                let ast::Entry::Message(msg) = entry else {
+                    continue;
+                };
+                // Skip the messages overridden by a later resource.
+                if !self
+                    .get_entry_message(msg.id.name)
+                    .map_or(false, |found| std::ptr::eq(found, msg))
+                {
+                    continue;
+                }
+
+                let args = args(msg.id.name);
+                let patterns = msg.value.iter().map(|value| (None, value)).chain(
+                    msg.attributes
+                        .iter()
+                        .map(|attr| (Some(attr.id.name), &attr.value)),
+                );
+                for (attribute, pattern) in patterns {
+                    let mut errors = vec![];
+                    let _ = self.write_pattern(&mut Discard, pattern, args.as_ref(), &mut errors);
+                    diagnostics.extend(errors.into_iter().map(|error| FormatDiagnostic {
+                        message: pattern_name(msg.id.name, attribute),
+                        resource: res_pos,
+                        error,
+                    }));
+                }
+            }
+        }
+        diagnostics
+    }
+
+    /// Returns the variables used by the pattern `key`, and by the messages it
+    /// references. `visiting` holds the patterns being visited, which breaks
+    /// reference cycles.
+    fn required_variables<'s>(
+        &'s self,
+        key: PatternKey<'s>,
+        required: &mut FxHashMap<PatternKey<'s>, FxHashSet<&'s str>>,
+        visiting: &mut Vec<PatternKey<'s>>,
+    ) -> FxHashSet<&'s str> {
+        if let Some(variables) = required.get(&key) {
+            return variables.clone();
+        }
+        if visiting.contains(&key) {
+            return FxHashSet::default();
+        }
+
+        let pattern = self.get_entry_message(key.0).and_then(|msg| match key.1 {
+            Some(attribute) => msg
+                .attributes
+                .iter()
+                .find(|attr| attr.id.name == attribute)
+                .map(|attr| &attr.value),
+            None => msg.value.as_ref(),
+        });
+        let Some(pattern) = pattern else {
+            return FxHashSet::default();
+        };
+
+        visiting.push(key);
+        let refs = PatternRefs::new(pattern);
+        let mut variables = refs.variables;
+        for &reference in &refs.messages {
+            variables.extend(self.required_variables(reference, required, visiting));
+        }
+        visiting.pop();
+
+        required.insert(key, variables.clone());
+        variables
+    }
+}
+
+fn pattern_name(id: &str, attribute: Option<&str>) -> String {
+    match attribute {
+        Some(attribute) => format!("{}.{}", id, attribute),
+        None => id.to_string(),
+    }
+}
+
+/// Returns the byte range of a reference from the slices of its identifiers,
+/// which point into the source of their resource.
+fn span(source: &str, id: &str, attribute: Option<&str>) -> Option<Range<usize>> {
+    let offset = |s: &str| {
+        let start = (s.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+        (start + s.len() <= source.len()).then_some(start..start + s.len())
+    };
+    let id = offset(id)?;
+    let end = match attribute {
+        Some(attribute) => offset(attribute)?.end,
+        None => id.end,
+    };
+    Some(id.start..end)
+}
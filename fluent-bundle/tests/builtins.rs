@@ -1,5 +1,7 @@
+use fluent_bundle::types::{FluentDateTime, NumberSymbols};
 use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
 use fluent_syntax::ast::Pattern;
+use unic_langid::langid;
 
 #[test]
 fn test_builtin_number() {
@@ -67,3 +69,828 @@ order = { NUMBER($num, type: "ordinal") ->
     assert_eq!(get_val(order, 15).unwrap(), "\u{2068}15\u{2069}th");
     assert_eq!(get_val(order, 123).unwrap(), "\u{2068}123\u{2069}rd");
 }
+
+#[test]
+fn test_builtin_datetime() {
+    let ftl_string = String::from(
+        r#"
+updated = Updated { $date }
+updated-long = Updated { DATETIME($date, dateStyle: "long", timeStyle: "short") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, date: FluentValue| {
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("date", date);
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    let date = || FluentValue::from(FluentDateTime::from_epoch(1_709_647_629_000));
+    assert_eq!(format("updated", date()), "Updated 2024-03-05T14:07:09Z");
+    assert_eq!(
+        format("updated-long", date()),
+        "Updated March 5, 2024, 14:07"
+    );
+    assert_eq!(
+        format("updated-long", FluentValue::from(1_709_647_629_000_i64)),
+        "Updated March 5, 2024, 14:07"
+    );
+}
+
+#[test]
+fn test_list_values() {
+    let ftl_string = String::from(
+        r#"
+invited = You invited { $names }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+
+    let format = |names: FluentValue| {
+        let pattern = bundle
+            .get_message("invited")
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("names", names);
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format(vec!["Anna"].into()), "You invited Anna");
+    assert_eq!(
+        format(vec!["Anna", "Bob"].into()),
+        "You invited Anna and Bob"
+    );
+    assert_eq!(
+        format(vec!["Anna", "Bob", "Carol"].into()),
+        "You invited Anna, Bob, and Carol"
+    );
+    assert_eq!(
+        format(FluentValue::List(vec![
+            "Anna".into(),
+            FluentValue::None,
+            5.into()
+        ])),
+        "You invited Anna and 5"
+    );
+}
+
+#[cfg(feature = "list-format")]
+#[test]
+fn test_builtin_listformat() {
+    let ftl_string = String::from(
+        r#"
+invited = You invited { LISTFORMAT($names) }
+pick = Pick { LISTFORMAT($names, type: "disjunction") }
+sizes = { LISTFORMAT($names, type: "unit", style: "narrow") }
+        "#,
+    );
+
+    let format = |langid, id: &str, names: Vec<&str>| {
+        let mut bundle = FluentBundle::new(vec![langid]);
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(
+                FluentResource::try_new(ftl_string.clone())
+                    .expect("Could not parse an FTL string."),
+            )
+            .expect("Failed to add FTL resources to the bundle.");
+        bundle
+            .add_builtins()
+            .expect("Failed to add builtin functions to the bundle.");
+        bundle
+            .add_builtins_extended()
+            .expect("Failed to add builtin functions to the bundle.");
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("names", names);
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    let names = || vec!["Anna", "Bob", "Carol"];
+    assert_eq!(
+        format(langid!("en-US"), "invited", names()),
+        "You invited Anna, Bob, and Carol"
+    );
+    assert_eq!(
+        format(langid!("en-US"), "pick", names()),
+        "Pick Anna, Bob, or Carol"
+    );
+    assert_eq!(
+        format(langid!("en-US"), "sizes", vec!["5 ft", "7 in"]),
+        "5 ft 7 in"
+    );
+    assert_eq!(
+        format(langid!("es"), "invited", names()),
+        "You invited Anna, Bob y Carol"
+    );
+    assert_eq!(
+        format(langid!("de"), "pick", names()),
+        "Pick Anna, Bob oder Carol"
+    );
+}
+
+#[test]
+fn test_builtin_platform_and_env() {
+    let ftl_string = String::from(
+        r#"
+platform = { PLATFORM() }
+channel = { ENV("channel") ->
+    [beta] Beta
+   *[other] Stable
+}
+        "#,
+    );
+
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |bundle: &FluentBundle<FluentResource>, id: &str| {
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, None, &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    let platform = format(&bundle, "platform");
+    assert!(["android", "linux", "macos", "windows", "other"].contains(&platform.as_str()));
+    assert_eq!(format(&bundle, "channel"), "Stable");
+
+    bundle.set_env("platform", "ios");
+    bundle.set_env("channel", "beta");
+    assert_eq!(format(&bundle, "platform"), "ios");
+    assert_eq!(format(&bundle, "channel"), "Beta");
+}
+
+#[cfg(feature = "relative-time")]
+#[test]
+fn test_builtin_relativetime() {
+    let ftl_string = String::from(
+        r#"
+updated = Updated { RELATIVETIME($seconds) }
+updated-short = Updated { RELATIVETIME($seconds, style: "short") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, seconds: isize| {
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("seconds", FluentValue::from(seconds));
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format("updated", -180), "Updated 3 minutes ago");
+    assert_eq!(format("updated", 172_800), "Updated in 2 days");
+    assert_eq!(format("updated", -30), "Updated 30 seconds ago");
+    assert_eq!(format("updated-short", -180), "Updated 3 min. ago");
+}
+
+#[cfg(feature = "units")]
+#[test]
+fn test_builtin_unit() {
+    let ftl_string = String::from(
+        r#"
+size = { UNIT($size, unit: "megabyte") }
+distance = { UNIT($distance, unit: "kilometer", display: "long") }
+size-narrow = { UNIT($size, unit: "megabyte", display: "narrow") }
+unknown = { UNIT($size, unit: "furlong") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, key: &'static str, value: isize| {
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set(key, FluentValue::from(value));
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format("size", "size", 16), "16 MB");
+    assert_eq!(format("distance", "distance", 1), "1 kilometer");
+    assert_eq!(format("distance", "distance", 3), "3 kilometers");
+    assert_eq!(format("size-narrow", "size", 16), "16MB");
+    assert_eq!(format("unknown", "size", 2), "2 furlong");
+}
+
+#[test]
+fn test_builtin_duration() {
+    let ftl_string = String::from(
+        r#"
+timer = { DURATION($seconds, style: "digital") }
+elapsed = { DURATION($seconds, style: "narrow") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, seconds: isize| {
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("seconds", FluentValue::from(seconds));
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format("timer", 6127), "1:42:07");
+    assert_eq!(format("timer", 59), "0:00:59");
+    assert_eq!(format("elapsed", 6127), "1h 42m 7s");
+    assert_eq!(format("elapsed", 3600), "1h");
+    assert_eq!(format("elapsed", 0), "0s");
+}
+
+#[test]
+fn test_builtin_case() {
+    let ftl_string = String::from(
+        r#"
+upper = { UPPER($name) }
+lower = { LOWER($name) }
+capitalize = { CAPITALIZE($name) }
+        "#,
+    );
+    let res = FluentResource::try_new(ftl_string).expect("Could not parse an FTL string.");
+
+    let format = |langid, id: &str, name: &str| {
+        let mut bundle: FluentBundle<&FluentResource> = FluentBundle::new(vec![langid]);
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(&res)
+            .expect("Failed to add FTL resources to the bundle.");
+        bundle
+            .add_builtins()
+            .expect("Failed to add builtin functions to the bundle.");
+        bundle
+            .add_builtins_extended()
+            .expect("Failed to add builtin functions to the bundle.");
+
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("name", name.to_string());
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format(langid!("en-US"), "upper", "istanbul"), "ISTANBUL");
+    assert_eq!(format(langid!("tr"), "upper", "istanbul"), "İSTANBUL");
+    assert_eq!(format(langid!("tr"), "lower", "DIŞ"), "dış");
+    assert_eq!(format(langid!("tr"), "capitalize", "izmir"), "İzmir");
+    assert_eq!(format(langid!("el"), "upper", "καλημέρα"), "ΚΑΛΗΜΕΡΑ");
+    assert_eq!(format(langid!("el"), "lower", "ΟΔΟΣ"), "οδο\u{3c2}");
+}
+
+#[test]
+fn test_builtin_pad_and_truncate() {
+    let ftl_string = String::from(
+        r#"
+left = [{ PAD($value, width: 6) }]
+right = [{ PAD($value, width: 6, align: "right") }]
+center = [{ PAD($value, width: 6, align: "center") }]
+truncate = { TRUNCATE($value, max: 5) }
+truncate-dots = { TRUNCATE($value, max: 5, ellipsis: "...") }
+        "#,
+    );
+
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str, value: &str| {
+        let pattern = bundle
+            .get_message(id)
+            .expect("Message doesn't exist")
+            .value()
+            .expect("Message has no value");
+        let mut args = FluentArgs::new();
+        args.set("value", value.to_string());
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        val.into_owned()
+    };
+
+    assert_eq!(format("left", "abc"), "[abc   ]");
+    assert_eq!(format("right", "abc"), "[   abc]");
+    assert_eq!(format("center", "abc"), "[ abc  ]");
+    assert_eq!(format("left", "日本"), "[日本  ]");
+    assert_eq!(
+        format("left", "e\u{301}te\u{301}"),
+        "[e\u{301}te\u{301}   ]"
+    );
+    assert_eq!(format("left", "too long"), "[too long]");
+
+    assert_eq!(format("truncate", "short"), "short");
+    assert_eq!(format("truncate", "truncated"), "trun…");
+    assert_eq!(format("truncate", "日本語の文"), "日本…");
+    assert_eq!(format("truncate-dots", "truncated"), "tr...");
+}
+
+fn format_number(langid: unic_langid::LanguageIdentifier, id: &str, num: f64) -> String {
+    let ftl_string = String::from(
+        r#"
+ordinal = { ORDINAL($num) }
+ordinal-words = { ORDINAL($num, notation: "spellout") }
+cardinal-words = { NUMBER($num, notation: "spellout") }
+        "#,
+    );
+    let res = FluentResource::try_new(ftl_string).expect("Could not parse an FTL string.");
+    let mut bundle: FluentBundle<&FluentResource> = FluentBundle::new(vec![langid]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(&res)
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    bundle
+        .add_builtins_extended()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let pattern = bundle
+        .get_message(id)
+        .expect("Message doesn't exist")
+        .value()
+        .expect("Message has no value");
+    let mut args = FluentArgs::new();
+    args.set("num", num);
+    let mut errors = vec![];
+    let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+    assert!(errors.is_empty());
+    val.into_owned()
+}
+
+#[test]
+fn test_builtin_ordinal() {
+    assert_eq!(format_number(langid!("en-US"), "ordinal", 1.0), "1st");
+    assert_eq!(format_number(langid!("en-US"), "ordinal", 12.0), "12th");
+    assert_eq!(format_number(langid!("en-US"), "ordinal", 23.0), "23rd");
+    assert_eq!(format_number(langid!("en-US"), "ordinal", 111.0), "111th");
+    assert_eq!(format_number(langid!("de"), "ordinal", 21.0), "21.");
+    assert_eq!(format_number(langid!("fr"), "ordinal", 1.0), "1er");
+    assert_eq!(format_number(langid!("fr"), "ordinal", 2.0), "2e");
+    assert_eq!(format_number(langid!("ja"), "ordinal", 2.0), "2");
+}
+
+#[test]
+#[cfg(feature = "spellout")]
+fn test_builtin_spellout() {
+    let en = || langid!("en-US");
+    assert_eq!(format_number(en(), "cardinal-words", 0.0), "zero");
+    assert_eq!(format_number(en(), "cardinal-words", 21.0), "twenty-one");
+    assert_eq!(
+        format_number(en(), "cardinal-words", 1_200_340.0),
+        "one million two hundred thousand three hundred forty"
+    );
+    assert_eq!(format_number(en(), "cardinal-words", -7.0), "minus seven");
+    assert_eq!(format_number(en(), "cardinal-words", 2.5), "2.5");
+    assert_eq!(format_number(en(), "ordinal-words", 21.0), "twenty-first");
+    assert_eq!(format_number(en(), "ordinal-words", 40.0), "fortieth");
+    assert_eq!(
+        format_number(en(), "ordinal-words", 112.0),
+        "one hundred twelfth"
+    );
+    assert_eq!(format_number(langid!("de"), "cardinal-words", 21.0), "21");
+    assert_eq!(format_number(langid!("de"), "ordinal-words", 21.0), "21.");
+}
+
+#[cfg(feature = "builtins")]
+#[test]
+fn test_builtin_number_icu() {
+    let ftl_string = String::from(
+        r#"
+plain = { $num }
+ungrouped = { NUMBER($num, useGrouping: "false") }
+fraction = { NUMBER($num, minimumFractionDigits: 2, maximumFractionDigits: 2) }
+integer = { NUMBER($num, minimumIntegerDigits: 3) }
+significant = { NUMBER($num, maximumSignificantDigits: 3) }
+significant-min = { NUMBER($num, minimumSignificantDigits: 4) }
+percent = { NUMBER($num, style: "percent") }
+usd = { NUMBER($num, style: "currency", currency: "USD") }
+eur = { NUMBER($num, style: "currency", currency: "EUR") }
+eur-code = { NUMBER($num, style: "currency", currency: "EUR", currencyDisplay: "code") }
+jpy = { NUMBER($num, style: "currency", currency: "JPY") }
+        "#,
+    );
+    let res = FluentResource::try_new(ftl_string).expect("Could not parse an FTL string.");
+    let format = |langid: unic_langid::LanguageIdentifier, id: &str, num: f64| {
+        let mut bundle: FluentBundle<&FluentResource> = FluentBundle::new(vec![langid]);
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(&res)
+            .expect("Failed to add FTL resources to the bundle.");
+        bundle
+            .add_builtins()
+            .expect("Failed to add builtin functions to the bundle.");
+        let mut args = FluentArgs::new();
+        args.set("num", num);
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        val.into_owned()
+    };
+    let en = || langid!("en-US");
+    let de = || langid!("de-DE");
+
+    assert_eq!(format(en(), "plain", 1234567.891), "1,234,567.891");
+    assert_eq!(format(en(), "plain", 0.12345), "0.123");
+    assert_eq!(format(en(), "plain", -1234.5), "-1,234.5");
+    assert_eq!(format(de(), "plain", 1234567.5), "1.234.567,5");
+    assert_eq!(format(en(), "ungrouped", 1234567.0), "1234567");
+    assert_eq!(format(en(), "fraction", 1234.5), "1,234.50");
+    assert_eq!(format(en(), "fraction", 1.005), "1.01");
+    assert_eq!(format(en(), "integer", 7.0), "007");
+    assert_eq!(format(en(), "significant", 123456.0), "123,000");
+    assert_eq!(format(en(), "significant", 0.012345), "0.0123");
+    assert_eq!(format(en(), "significant-min", 1.5), "1.500");
+    assert_eq!(format(en(), "percent", 0.256), "26%");
+    assert_eq!(format(de(), "percent", 0.25), "25\u{a0}%");
+    assert_eq!(format(en(), "usd", 1234.5), "$1,234.50");
+    assert_eq!(format(en(), "usd", -5.0), "-$5.00");
+    assert_eq!(format(de(), "usd", 1234.5), "1.234,50\u{a0}US$");
+    assert_eq!(format(de(), "eur", 3.0), "3,00\u{a0}€");
+    assert_eq!(format(en(), "eur-code", 3.0), "EUR\u{a0}3.00");
+    assert_eq!(format(en(), "jpy", 1234.0), "¥1,234");
+}
+
+#[test]
+fn test_builtin_number_notation_and_sign() {
+    let ftl_string = String::from(
+        r#"
+compact = { NUMBER($num, notation: "compact") }
+compact-long = { NUMBER($num, notation: "compact", compactDisplay: "long") }
+signed = { NUMBER($num, signDisplay: "always") }
+except-zero = { NUMBER($num, signDisplay: "exceptZero") }
+unsigned = { NUMBER($num, signDisplay: "never") }
+        "#,
+    );
+    let res = FluentResource::try_new(ftl_string).expect("Could not parse an FTL string.");
+    let mut bundle: FluentBundle<&FluentResource> = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(&res)
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |bundle: &FluentBundle<&FluentResource>, id: &str, num: f64| {
+        let mut args = FluentArgs::new();
+        args.set("num", num);
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        val.into_owned()
+    };
+
+    let check = |bundle: &FluentBundle<&FluentResource>| {
+        assert_eq!(format(bundle, "compact", 1234.0), "1.2K");
+        assert_eq!(format(bundle, "compact", 12345.0), "12K");
+        assert_eq!(format(bundle, "compact", 999_999.0), "1M");
+        assert_eq!(format(bundle, "compact", -2_500_000.0), "-2.5M");
+        assert_eq!(format(bundle, "compact", 999.0), "999");
+        assert_eq!(format(bundle, "compact", 0.123), "0.12");
+        assert_eq!(format(bundle, "compact-long", 4.2e9), "4.2 billion");
+        assert_eq!(format(bundle, "signed", 5.0), "+5");
+        assert_eq!(format(bundle, "signed", 0.0), "+0");
+        assert_eq!(format(bundle, "except-zero", 0.0), "0");
+        assert_eq!(format(bundle, "except-zero", 5.0), "+5");
+        assert_eq!(format(bundle, "unsigned", -5.0), "5");
+    };
+    check(&bundle);
+    bundle.set_number_symbols(Some(NumberSymbols::default()));
+    check(&bundle);
+}
+
+#[cfg(feature = "builtins")]
+#[test]
+fn test_builtin_number_rounding_and_grouping() {
+    let ftl_string = String::from(
+        r#"
+ceil = { NUMBER($num, maximumFractionDigits: 0, roundingMode: "ceil") }
+trunc = { NUMBER($num, maximumFractionDigits: 1, roundingMode: "trunc") }
+half-even = { NUMBER($num, maximumFractionDigits: 0, roundingMode: "halfEven") }
+min2 = { NUMBER($num, useGrouping: "min2") }
+always = { NUMBER($num, useGrouping: "always") }
+        "#,
+    );
+    let res = FluentResource::try_new(ftl_string).expect("Could not parse an FTL string.");
+    let format = |langid: unic_langid::LanguageIdentifier, id: &str, num: f64| {
+        let mut bundle: FluentBundle<&FluentResource> = FluentBundle::new(vec![langid]);
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(&res)
+            .expect("Failed to add FTL resources to the bundle.");
+        bundle
+            .add_builtins()
+            .expect("Failed to add builtin functions to the bundle.");
+        let mut args = FluentArgs::new();
+        args.set("num", num);
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        val.into_owned()
+    };
+    let en = || langid!("en-US");
+
+    assert_eq!(format(en(), "ceil", 1.2), "2");
+    assert_eq!(format(en(), "ceil", -1.8), "-1");
+    assert_eq!(format(en(), "trunc", 1.99), "1.9");
+    assert_eq!(format(en(), "half-even", 2.5), "2");
+    assert_eq!(format(en(), "half-even", 3.5), "4");
+    assert_eq!(format(en(), "min2", 1234.0), "1234");
+    assert_eq!(format(en(), "min2", 12345.0), "12,345");
+    assert_eq!(format(en(), "always", 1234.0), "1,234");
+    assert_eq!(format(langid!("es"), "min2", 12345.0), "12.345");
+}
+
+#[test]
+fn test_number_symbols() {
+    let ftl_string = String::from(
+        r#"
+plain = { $num }
+ungrouped = { NUMBER($num, useGrouping: "false") }
+fraction = { NUMBER($num, minimumFractionDigits: 2) }
+percent = { NUMBER($num, style: "percent") }
+        "#,
+    );
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |bundle: &FluentBundle<FluentResource>, id: &str, num: f64| {
+        let mut args = FluentArgs::new();
+        args.set("num", num);
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let val = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        val.into_owned()
+    };
+
+    // Without symbols, numbers are only localized with the `builtins` feature.
+    #[cfg(not(feature = "builtins"))]
+    {
+        assert_eq!(format(&bundle, "plain", 1234567.5), "1234567.5");
+        assert_eq!(format(&bundle, "percent", 0.5), "0.5");
+    }
+    #[cfg(feature = "builtins")]
+    {
+        assert_eq!(format(&bundle, "plain", 1234567.5), "1,234,567.5");
+        assert_eq!(format(&bundle, "percent", 0.5), "50%");
+    }
+
+    bundle.set_number_symbols(Some(NumberSymbols::default()));
+    assert_eq!(format(&bundle, "plain", 1234567.5), "1,234,567.5");
+    assert_eq!(format(&bundle, "plain", -1234.0), "-1,234");
+    assert_eq!(format(&bundle, "plain", 123.0), "123");
+    assert_eq!(format(&bundle, "ungrouped", 1234567.0), "1234567");
+    assert_eq!(format(&bundle, "fraction", 1234.0), "1,234.00");
+    assert_eq!(format(&bundle, "percent", 0.5), "50%");
+
+    bundle.set_number_symbols(Some(NumberSymbols {
+        decimal: ",".to_string(),
+        group: "\u{a0}".to_string(),
+        minimum_grouping_digits: 2,
+        percent: "\u{a0}%".to_string(),
+        minus: "\u{2212}".to_string(),
+    }));
+    assert_eq!(format(&bundle, "plain", 1234.5), "1234,5");
+    assert_eq!(format(&bundle, "plain", 12345.5), "12\u{a0}345,5");
+    assert_eq!(
+        format(&bundle, "plain", -1234567.0),
+        "\u{2212}1\u{a0}234\u{a0}567"
+    );
+    assert_eq!(format(&bundle, "percent", 0.125), "12,5\u{a0}%");
+}
+
+#[test]
+fn test_builtin_number_signature() {
+    let ftl_string = String::from(
+        r#"
+typo = { NUMBER($num, minimumFractionDigit: 2) }
+wrong-type = { NUMBER($num, style: 2) }
+count = { NUMBER($num, 2) }
+valid = { NUMBER($num, minimumFractionDigits: 2) }
+"#,
+    );
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+
+    let format = |id: &str| {
+        let mut args = FluentArgs::new();
+        args.set("num", 3);
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        (value.to_string(), errors)
+    };
+
+    assert_eq!(
+        format("typo"),
+        (
+            "NUMBER()".to_string(),
+            vec![
+                "Resolver error: NUMBER: unknown option 'minimumFractionDigit' \
+                 (did you mean minimumFractionDigits?)"
+                    .to_string()
+            ]
+        )
+    );
+    assert_eq!(
+        format("wrong-type").1,
+        ["Resolver error: NUMBER: option 'style' must be a string"]
+    );
+    assert_eq!(
+        format("count").1,
+        ["Resolver error: NUMBER: expected 1 positional argument, found 2"]
+    );
+    assert_eq!(format("valid"), ("3.00".to_string(), vec![]));
+}
+
+#[test]
+fn test_builtins_extended() {
+    use fluent_bundle::pool::{BundlePool, PooledBundle};
+    use fluent_bundle::FluentError;
+    use std::sync::Arc;
+
+    let ftl_string = String::from(
+        r#"
+typo = { NUMBER($num, minimumFractionDigit: 2) }
+date = { DATETIME($num) }
+shout = { UPPER("hello") }
+"#,
+    );
+    let res =
+        Arc::new(FluentResource::try_new(ftl_string).expect("Could not parse an FTL string."));
+    let mut args = FluentArgs::new();
+    args.set("num", 0);
+    let format = |bundle: &PooledBundle, id: &str| {
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        (value.to_string(), errors.len())
+    };
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(Arc::clone(&res))
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtin functions to the bundle.");
+    assert_eq!(format(&bundle, "typo").1, 1);
+    assert_eq!(format(&bundle, "date").1, 1);
+
+    bundle
+        .add_function("UPPER", |_, _| "custom".into())
+        .expect("Failed to add a function to the bundle.");
+    let errors = bundle.add_builtins_extended().unwrap_err();
+    assert!(matches!(&errors[..], [FluentError::Overriding { id, .. }] if id == "UPPER"));
+    assert_eq!(format(&bundle, "date").1, 0);
+    assert_eq!(format(&bundle, "shout"), ("custom".to_string(), 0));
+
+    let mut pool = BundlePool::new(vec![langid!("en-US")]);
+    pool.set_use_isolating(false);
+    pool.add_resource(res)
+        .expect("Failed to add FTL resources to the pool.");
+    pool.add_builtins()
+        .expect("Failed to add builtin functions to the pool.");
+    pool.add_builtins_extended()
+        .expect("Failed to add builtin functions to the pool.");
+    pool.with_bundle(|bundle| {
+        assert_eq!(format(bundle, "typo").1, 1);
+        assert_eq!(format(bundle, "date").1, 0);
+        assert_eq!(format(bundle, "shout"), ("HELLO".to_string(), 0));
+    });
+}
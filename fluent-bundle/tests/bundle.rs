@@ -1,6 +1,16 @@
-use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use fluent_bundle::bundle::{FormattedPart, FormatterPass};
+use fluent_bundle::concurrent::{self, FrozenBundle};
+use fluent_bundle::resolver::ResolverError;
+use fluent_bundle::types::{FluentNumber, FluentNumberType, PluralCategory, PluralRulesBackend};
+use fluent_bundle::{
+    FluentArgs, FluentBundle, FluentError, FluentResource, FluentValue, VariantTag,
+};
+use fluent_syntax::ast;
+use fluent_syntax::parser::ErrorKind;
 use std::borrow::Cow;
-use unic_langid::langid;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use unic_langid::{langid, LanguageIdentifier};
 
 #[test]
 fn add_resource_override() {
@@ -59,6 +69,209 @@ fn borrowed_plain_message() {
     assert!(matches!(formatted_pattern, Cow::Borrowed(_)));
 }
 
+#[test]
+fn borrowed_transformed_text_message() {
+    fn transform(s: &str) -> Cow<'_, str> {
+        s.to_uppercase().into()
+    }
+
+    let res = FluentResource::try_new(
+        "key =\n    First line\n    second line\n    .title = Title\n".to_string(),
+    )
+    .unwrap();
+    let en_us = langid!("en-US");
+
+    let mut bundle = FluentBundle::new(vec![en_us]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.set_transform(Some(transform));
+
+    let mut errors = vec![];
+    let msg = bundle
+        .get_message("key")
+        .expect("Failed to retrieve a message");
+
+    let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    assert_eq!(value, "FIRST LINE\nSECOND LINE");
+    assert!(matches!(value, Cow::Borrowed(_)));
+
+    let title = msg.get_attribute("title").unwrap().value();
+    let title = bundle.format_pattern(title, None, &mut errors);
+    assert_eq!(title, "TITLE");
+    assert!(matches!(title, Cow::Borrowed(_)));
+
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn repeated_plural_selection() {
+    let res = FluentResource::try_new(
+        r#"
+progress = { $count ->
+    [one] { $count } file
+   *[other] { $count } files
+}
+decimal = { NUMBER($count, minimumFractionDigits: 1) ->
+    [one] one file
+   *[other] other files
+}
+place = { NUMBER($count, type: "ordinal") ->
+    [one] { $count }st
+    [two] { $count }nd
+    [few] { $count }rd
+   *[other] { $count }th
+}
+exact = { $count ->
+    [1] exactly one
+    [one] one
+   *[other] other
+}
+"#
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_builtins().unwrap();
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    let format = |id, count: f64| {
+        let mut args = FluentArgs::new();
+        args.set("count", count);
+        let msg = bundle.get_message(id).unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(msg.value().unwrap(), Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        value.to_string()
+    };
+
+    for _ in 0..2 {
+        assert_eq!(format("progress", 1.0), "1 file");
+        assert_eq!(format("progress", 1.0), "1 file");
+        assert_eq!(format("progress", 2.0), "2 files");
+        assert_eq!(format("decimal", 1.0), "other files");
+        assert_eq!(format("place", 1.0), "1st");
+        assert_eq!(format("place", 22.0), "22nd");
+        assert_eq!(format("place", 13.0), "13th");
+        assert_eq!(format("exact", 1.0), "exactly one");
+    }
+}
+
+#[test]
+fn bundle_pool_rebuilds_after_changes() {
+    use fluent_bundle::pool::BundlePool;
+    use std::sync::Arc;
+
+    let hello = Arc::new(FluentResource::try_new("hello = Hello!".to_string()).unwrap());
+    let goodbye =
+        Arc::new(FluentResource::try_new("hello = Hi!\ngoodbye = Goodbye!".to_string()).unwrap());
+
+    let mut pool = BundlePool::new(vec![langid!("en-US")]);
+    pool.add_resource(hello).expect("Failed to add a resource");
+    assert!(pool.with_bundle(|bundle| !bundle.has_message("goodbye")));
+
+    let errors = pool.add_resource(goodbye).unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    let mut errors = vec![];
+    let value = pool.with_bundle(|bundle| {
+        let value = bundle.get_message("hello").unwrap().value().unwrap();
+        let hello = bundle.format_pattern(value, None, &mut errors).to_string();
+        let value = bundle.get_message("goodbye").unwrap().value().unwrap();
+        let goodbye = bundle.format_pattern(value, None, &mut errors).to_string();
+        (hello, goodbye)
+    });
+    assert_eq!(value, ("Hello!".to_string(), "Goodbye!".to_string()));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn bundle_pool_function_signatures() {
+    use fluent_bundle::pool::BundlePool;
+    use fluent_bundle::validate::FunctionSignature;
+    use std::sync::Arc;
+
+    let res =
+        FluentResource::try_new(r#"greeting = { GREET("Anna", "Bob") }"#.to_string()).unwrap();
+
+    let mut pool = BundlePool::new(vec![langid!("en-US")]);
+    pool.add_resource(Arc::new(res))
+        .expect("Failed to add a resource");
+    pool.add_function("GREET", |_, _| "Hello!".into())
+        .expect("Failed to add a function");
+    pool.add_function_signature("GREET", FunctionSignature::new().positional(1));
+
+    let mut errors = vec![];
+    pool.with_bundle(|bundle| {
+        let value = bundle.get_message("greeting").unwrap().value().unwrap();
+        bundle.format_pattern(value, None, &mut errors);
+    });
+    assert_eq!(
+        errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        ["Resolver error: GREET: expected 1 positional argument, found 2"]
+    );
+}
+
+#[test]
+fn deprecated_alias_annotation() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+    fn hook(old_id: &str, new_id: &str) {
+        assert!(["greeting", "salutation"].contains(&old_id));
+        assert_eq!(new_id, "welcome");
+        LOOKUPS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let res = FluentResource::try_new(
+        r#"
+welcome = Welcome!
+
+# fluent: deprecated-alias-of welcome
+greeting = Hello!
+
+# The annotation syntax of fluent-syntax is read as well.
+# .deprecated-alias-of: welcome
+salutation = Greetings!
+
+# fluent: deprecated-alias-of welcome
+
+detached = Detached
+"#
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.set_deprecation_hook(Some(hook));
+
+    let mut errors = vec![];
+    let msg = bundle
+        .get_message("greeting")
+        .expect("Failed to retrieve a message");
+    let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    assert_eq!(value, "Welcome!");
+    assert_eq!(LOOKUPS.load(Ordering::SeqCst), 1);
+
+    let msg = bundle
+        .get_message("salutation")
+        .expect("Failed to retrieve a message");
+    let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    assert_eq!(value, "Welcome!");
+    assert_eq!(LOOKUPS.load(Ordering::SeqCst), 2);
+
+    let msg = bundle
+        .get_message("detached")
+        .expect("Failed to retrieve a message");
+    let value = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
+    assert_eq!(value, "Detached");
+    assert_eq!(LOOKUPS.load(Ordering::SeqCst), 2);
+    assert!(errors.is_empty());
+
+    assert!(bundle.add_alias("welcome", "detached").is_err());
+}
+
 #[test]
 fn arguments_outlive_formatted_pattern() {
     let res = FluentResource::try_new("key = { $variable }".to_string()).unwrap();
@@ -84,3 +297,1417 @@ fn arguments_outlive_formatted_pattern() {
 
     assert_eq!(formatted_pattern, "Variable");
 }
+
+#[test]
+fn nested_references_depth() {
+    let source = (0..80)
+        .map(|i| format!("msg{} = {{ msg{} }}\n", i, i + 1))
+        .chain(std::iter::once("msg80 = Value\n".to_string()))
+        .collect::<String>();
+    let res = FluentResource::try_new(source).unwrap();
+    let en_us = langid!("en-US");
+
+    let mut bundle = FluentBundle::new(vec![en_us]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    let format = |bundle: &FluentBundle<&FluentResource>, errors: &mut Vec<FluentError>| {
+        let value = bundle
+            .get_message("msg0")
+            .expect("Failed to retrieve a message")
+            .value()
+            .expect("Failed to retrieve a value of a message");
+        bundle.format_pattern(value, None, errors).to_string()
+    };
+
+    let mut errors = vec![];
+    assert_eq!(format(&bundle, &mut errors), "Value");
+    assert!(errors.is_empty());
+
+    bundle.set_max_depth(10);
+    let mut errors = vec![];
+    assert_eq!(format(&bundle, &mut errors), "{msg11}");
+    assert_eq!(
+        errors,
+        vec![FluentError::ResolverError(ResolverError::TooDeep)]
+    );
+}
+
+#[test]
+fn lazy_resource_matches_parsed() {
+    let source = r#"
+# A comment
+hello = Hello, { $name }!
+    .title = Greeting
+
+-brand = Firefox
+    .gender = feminine
+
+emails =
+    { $count ->
+[one] One email
+       *[other] { $count } emails
+    }
+
+junk = { $
+broken
+about = About { -brand } and { -brand.gender ->
+    [feminine] her
+   *[other] its
+}
+"#;
+    let parsed = FluentResource::try_new(source.to_string()).unwrap_err().0;
+    let lazy = FluentResource::new_lazy(source.to_string());
+    let entries = |res: &FluentResource| {
+        res.entries()
+            .filter(|entry| !matches!(entry, ast::Entry::Junk { .. }))
+            .count()
+    };
+    assert_eq!(entries(&lazy), entries(&parsed));
+
+    let mut args = FluentArgs::new();
+    args.set("name", "World");
+    args.set("count", 3);
+
+    let mut bundles = vec![];
+    for res in [&parsed, &lazy] {
+        let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+        bundle.set_use_isolating(false);
+        bundle.add_resource(res).expect("Failed to add a resource");
+        bundles.push(bundle);
+    }
+
+    for id in ["hello", "emails", "junk", "about", "-brand", "missing"] {
+        let format = |bundle: &FluentBundle<&FluentResource>| {
+            let msg = bundle.get_message(id)?;
+            let mut errors = vec![];
+            let value = bundle.format_pattern(msg.value()?, Some(&args), &mut errors);
+            let attrs = msg
+                .attributes()
+                .map(|attr| bundle.format_pattern(attr.value(), Some(&args), &mut errors))
+                .collect::<Vec<_>>();
+            Some((value.to_string(), attrs.join(","), errors))
+        };
+        assert_eq!(format(&bundles[0]), format(&bundles[1]), "{id}");
+    }
+    assert_eq!(
+        format_value(&bundles[1], "about"),
+        Some("About Firefox and her".to_string())
+    );
+}
+
+fn format_value(bundle: &FluentBundle<&FluentResource>, id: &str) -> Option<String> {
+    let msg = bundle.get_message(id)?;
+    let mut errors = vec![];
+    Some(
+        bundle
+            .format_pattern(msg.value()?, None, &mut errors)
+            .to_string(),
+    )
+}
+
+#[test]
+fn child_bundle_only_adds_entries() {
+    use std::sync::Arc;
+
+    let app = FluentResource::try_new("hello = Hello!\n-brand = App".to_string()).unwrap();
+    let plugin = FluentResource::try_new(
+        "hello = Hijacked!\n-brand = Plugin\nplugin = { -brand } plugin".to_string(),
+    )
+    .unwrap();
+
+    let mut bundle =
+        fluent_bundle::concurrent::FluentBundle::new_concurrent(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&app).unwrap();
+    let bundle = Arc::new(bundle);
+
+    let mut child = bundle.child();
+    let errors = child.add_resource(&plugin).unwrap_err();
+    let ids: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            FluentError::Overriding { id, .. } => id.as_str(),
+            err => panic!("Unexpected error: {}", err),
+        })
+        .collect();
+    assert_eq!(ids, ["hello", "brand"]);
+
+    let format = |id| {
+        let msg = child.get_message(id).expect("Message doesn't exist.");
+        let mut errors = vec![];
+        child
+            .format_pattern(msg.value().unwrap(), None, &mut errors)
+            .to_string()
+    };
+    assert_eq!(format("hello"), "Hello!");
+    assert_eq!(format("plugin"), "App plugin");
+    assert!(!bundle.has_message("plugin"));
+}
+
+#[test]
+fn validate_message_reference_variables() {
+    let res = FluentResource::try_new(
+        r#"
+emails = { $count } new emails for { $user }
+summary = { $user }: { emails }
+nested = { summary }
+attrs = { $count }
+    .title = { emails }
+    .label = { other.attr }
+other = Other
+    .attr = { $name }
+cycle-a = { $a } { cycle-b }
+cycle-b = { cycle-a }
+"#
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).unwrap();
+
+    let diagnostics: Vec<_> = bundle
+        .validate_references()
+        .into_iter()
+        .map(|diagnostic| {
+            let span = diagnostic.span.unwrap();
+            (diagnostic.message, diagnostic.variable, &res.source()[span])
+        })
+        .collect();
+    assert_eq!(
+        diagnostics,
+        [
+            ("summary".to_string(), "count".to_string(), "emails"),
+            ("nested".to_string(), "count".to_string(), "summary"),
+            ("nested".to_string(), "user".to_string(), "summary"),
+            ("attrs.title".to_string(), "count".to_string(), "emails"),
+            ("attrs.title".to_string(), "user".to_string(), "emails"),
+            ("attrs.label".to_string(), "name".to_string(), "other.attr"),
+            ("cycle-b".to_string(), "a".to_string(), "cycle-a"),
+        ]
+    );
+}
+
+#[test]
+fn format_raw_pattern() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = Fluent
+hello = Hello
+"#
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).unwrap();
+
+    let mut args = FluentArgs::new();
+    args.set("count", 2);
+    let template = "{ hello } from { -brand }\n{ $count ->\n    [one] one report\n   *[other] { $count } reports\n}";
+    for _ in 0..2 {
+        let mut errors = vec![];
+        assert_eq!(
+            bundle.format_raw_pattern(template, Some(&args), &mut errors),
+            "Hello from Fluent\n2 reports"
+        );
+        assert!(errors.is_empty());
+    }
+
+    // The text is kept as is, even where it would end a message pattern.
+    let template = "\n  { -brand }:\n.title = x\n[one] y\n*[other] z\n  ";
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_raw_pattern(template, None, &mut errors),
+        "\n  Fluent:\n.title = x\n[one] y\n*[other] z\n  "
+    );
+    assert!(errors.is_empty());
+
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_raw_pattern("Hello { $name", None, &mut errors),
+        "Hello { $name"
+    );
+    assert!(matches!(errors[..], [FluentError::ParserError(_)]));
+
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_raw_pattern("a\nb } c", None, &mut errors),
+        "a\nb } c"
+    );
+    match &errors[..] {
+        [FluentError::ParserError(err)] => {
+            assert_eq!(err.kind, ErrorKind::UnbalancedClosingBrace);
+            assert_eq!(err.pos, 4..5);
+        }
+        errors => panic!("Unexpected errors: {:?}", errors),
+    }
+}
+
+#[test]
+fn raw_pattern_capacity() {
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_raw_pattern_capacity(2);
+
+    let format = |bundle: &FluentBundle<FluentResource>, source: &str| {
+        let mut errors = vec![];
+        let value = bundle.format_raw_pattern(source, None, &mut errors);
+        assert!(errors.is_empty());
+        value
+    };
+    for i in 0..10 {
+        assert_eq!(
+            format(&bundle, &format!("Pattern {i}")),
+            format!("Pattern {i}")
+        );
+        assert!(bundle.memory_stats().raw_patterns <= 2);
+    }
+    assert_eq!(bundle.memory_stats().raw_patterns, 2);
+
+    let stats = bundle.memory_stats();
+
+    bundle.set_raw_pattern_capacity(1);
+    assert_eq!(bundle.memory_stats().raw_patterns, 1);
+    assert!(bundle.memory_stats().heap_size < stats.heap_size);
+
+    bundle.set_raw_pattern_capacity(0);
+    assert_eq!(format(&bundle, "d"), "d");
+    assert_eq!(bundle.memory_stats().raw_patterns, 0);
+}
+
+#[test]
+fn entry_source() {
+    let source = r#"
+# A comment
+hello = Hello, { $name }!
+    .title = Greeting
+
+-brand = Firefox
+
+## Group comment
+
+emails =
+    { $count ->
+[one] One email
+       *[other] { $count } emails
+    }
+"#;
+    let expected = [
+        "hello = Hello, { $name }!\n    .title = Greeting",
+        "-brand = Firefox",
+        "emails =\n    { $count ->\n[one] One email\n       *[other] { $count } emails\n    }",
+    ];
+
+    let parsed = FluentResource::try_new(source.to_string()).unwrap();
+    let lazy = FluentResource::new_lazy(source.to_string());
+    for res in [&parsed, &lazy] {
+        let sources: Vec<_> = res
+            .entries()
+            .map(|entry| res.entry_source(entry).unwrap())
+            .collect();
+        assert_eq!(sources, expected);
+    }
+
+    let entry = lazy.get_entry(0).unwrap();
+    assert_eq!(parsed.entry_source(entry), None);
+}
+
+#[test]
+fn term_signature() {
+    use fluent_bundle::validate::{TermSignature, TermSignatureError};
+
+    let terms = FluentResource::try_new(
+        r#"
+-brand = { $case ->
+   *[nominative] Firefox
+    [genitive] Firefoksa
+    [instrumental] Firefoksem
+}
+-product = Nightly
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let messages = FluentResource::try_new(
+        r#"
+about = O { -brand(case: "genitive") } i { -product }
+with = Z { -brand(case: "instrumental", gender: "masculine") }
+of = { -brand(case: "dative") }
+"#
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("pl")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&terms).unwrap();
+    let cases = ["nominative", "genitive", "instrumental", "locative"];
+    let errors = bundle
+        .add_term_signature("brand", TermSignature::new().param("case", cases))
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [FluentError::TermSignature(
+            TermSignatureError::MissingVariant {
+                term: "brand".to_string(),
+                param: "case".to_string(),
+                value: "locative".to_string(),
+            }
+        )]
+    );
+
+    let errors = bundle.add_resource(&messages).unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            FluentError::TermSignature(TermSignatureError::UnknownParam {
+                entry: "with".to_string(),
+                term: "brand".to_string(),
+                param: "gender".to_string(),
+            }),
+            FluentError::TermSignature(TermSignatureError::UnknownValue {
+                entry: "of".to_string(),
+                term: "brand".to_string(),
+                param: "case".to_string(),
+                value: "dative".to_string(),
+            }),
+        ]
+    );
+    assert_eq!(
+        format_value(&bundle, "about"),
+        Some("O Firefoksa i Nightly".to_string())
+    );
+}
+
+#[test]
+fn term_signature_of_lazy_and_static_resources() {
+    use fluent_bundle::validate::{TermSignature, TermSignatureError};
+    use fluent_syntax::parser::parse_runtime;
+
+    let source = "-brand = { $case ->\n   *[nominative] Firefox\n    [genitive] Firefoksa\n}\n";
+    let lazy = FluentResource::new_lazy(source.to_string());
+    let ast = parse_runtime(source).expect("Failed to parse FTL.");
+    let resources = [lazy, FluentResource::from_static(source, ast)];
+
+    for res in &resources {
+        let mut bundle = FluentBundle::new(vec![langid!("pl")]);
+        bundle
+            .add_term_signature(
+                "brand",
+                TermSignature::new().param("case", ["nominative", "locative"]),
+            )
+            .unwrap();
+        let errors = bundle.add_resource(res).unwrap_err();
+        assert_eq!(
+            errors,
+            [FluentError::TermSignature(
+                TermSignatureError::MissingVariant {
+                    term: "brand".to_string(),
+                    param: "case".to_string(),
+                    value: "locative".to_string(),
+                }
+            )]
+        );
+    }
+}
+
+#[test]
+fn lazy_resource_in_bundle() {
+    fn upper(s: &str) -> Cow<'_, str> {
+        s.to_uppercase().into()
+    }
+
+    let res = FluentResource::new_lazy(
+        r#"
+welcome = Welcome!
+# fluent: deprecated-alias-of welcome
+greeting = Hello!
+# fluent: requires-flag=beta-ui
+sidebar = Sidebar
+lines =
+    First line
+    second line
+"#
+        .to_string(),
+    );
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).unwrap();
+    assert_eq!(
+        format_value(&bundle, "greeting"),
+        Some("Welcome!".to_string())
+    );
+    assert_eq!(bundle.required_flag("sidebar"), Some("beta-ui"));
+    assert!(!bundle.has_message("sidebar"));
+
+    // The text patterns of lazy entries are not cached, but format the same.
+    bundle.set_transform(Some(upper));
+    assert_eq!(
+        format_value(&bundle, "lines"),
+        Some("FIRST LINE\nSECOND LINE".to_string())
+    );
+}
+
+#[test]
+fn required_flags() {
+    let res = FluentResource::try_new(
+        r#"
+# fluent: requires-flag=beta-ui
+sidebar = Sidebar
+    .title = New sidebar
+# A comment about the menu.
+# .requires-flag: beta-ui
+-menu = Menu
+menu-label = { -menu }
+stable = Stable and { sidebar }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let overrides =
+        FluentResource::try_new("stable = Stable\nsidebar = Old sidebar".to_string()).unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).unwrap();
+
+    assert_eq!(bundle.required_flag("sidebar"), Some("beta-ui"));
+    assert_eq!(bundle.required_flag("menu"), Some("beta-ui"));
+    assert_eq!(bundle.required_flag("stable"), None);
+    assert!(!bundle.has_message("sidebar"));
+    assert_eq!(
+        format_value(&bundle, "menu-label"),
+        Some("{-menu}".to_string())
+    );
+    assert_eq!(
+        format_value(&bundle, "stable"),
+        Some("Stable and {sidebar}".to_string())
+    );
+
+    bundle.set_enabled_flags(["beta-ui"]);
+    assert!(bundle.has_message("sidebar"));
+    assert_eq!(
+        format_value(&bundle, "menu-label"),
+        Some("Menu".to_string())
+    );
+
+    // Entries which are not added keep the flags of the existing ones.
+    bundle.set_enabled_flags(Vec::<String>::new());
+    assert!(bundle.add_resource(&overrides).is_err());
+    assert_eq!(bundle.required_flag("sidebar"), Some("beta-ui"));
+    bundle.add_resource_overriding(&overrides);
+    assert_eq!(bundle.required_flag("sidebar"), None);
+    assert_eq!(
+        format_value(&bundle, "sidebar"),
+        Some("Old sidebar".to_string())
+    );
+}
+
+#[test]
+fn check_all_reports_errors() {
+    let res = FluentResource::try_new(
+        r#"
+hello = Hello, { $name }!
+broken = { missing }
+cyclic = { cyclic }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let fixes = FluentResource::try_new("broken = Fixed".to_string()).unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).unwrap();
+    bundle.add_resource_overriding(&fixes);
+
+    let diagnostics: Vec<_> = bundle
+        .check_all(|_| None)
+        .into_iter()
+        .map(|diagnostic| (diagnostic.message, diagnostic.resource, diagnostic.error))
+        .collect();
+    assert_eq!(
+        diagnostics,
+        [
+            (
+                "hello".to_string(),
+                0,
+                FluentError::ResolverError(ResolverError::Reference(
+                    fluent_bundle::resolver::errors::ReferenceKind::Variable {
+                        id: "name".to_string()
+                    }
+                ))
+            ),
+            (
+                "cyclic".to_string(),
+                0,
+                FluentError::ResolverError(ResolverError::Cyclic)
+            ),
+        ]
+    );
+
+    let mut args = FluentArgs::new();
+    args.set("name", "Anna");
+    assert_eq!(
+        bundle
+            .check_all(|id| (id == "hello").then(|| args.clone()))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn fallback_bundle_resolves_references_through_the_chain() {
+    use fluent_bundle::bundle::FallbackBundle;
+
+    let resources = [
+        "about = O { -brand } { -version }\nbeta = Beta",
+        "-brand = Firefox\n-version = 1\nhelp = Help",
+        "-brand = Mozilla Firefox\n-version = 2\nabout = About\nlast = Last\nbeta = Old beta",
+    ]
+    .map(|source| FluentResource::try_new(source.to_string()).unwrap());
+
+    let mut bundles: Vec<_> = [langid!("pl"), langid!("de"), langid!("en-US")]
+        .into_iter()
+        .zip(&resources)
+        .map(|(langid, res)| {
+            let mut bundle = FluentBundle::new(vec![langid]);
+            bundle.set_use_isolating(false);
+            bundle.add_resource(res).unwrap();
+            bundle
+        })
+        .collect();
+    let first = bundles.remove(0);
+    let bundle = FallbackBundle::new(first, bundles);
+
+    assert_eq!(
+        bundle
+            .bundles()
+            .map(|bundle| bundle.locales[0].to_string())
+            .collect::<Vec<_>>(),
+        ["pl", "de", "en-US"]
+    );
+    assert_eq!(
+        format_value(&bundle, "about"),
+        Some("O Firefox 1".to_string())
+    );
+    assert_eq!(format_value(&bundle, "beta"), Some("Beta".to_string()));
+    assert_eq!(format_value(&bundle, "help"), Some("Help".to_string()));
+    assert_eq!(format_value(&bundle, "last"), Some("Last".to_string()));
+    assert_eq!(format_value(&bundle, "missing"), None);
+}
+
+#[test]
+fn variant_selector_chooses_message_values() {
+    let res = FluentResource::try_new(
+        "
+cta = Sign up
+    .variant-b = Join today
+    .variant-c = Start free
+plain = Plain
+banner = { cta }!
+"
+        .to_string(),
+    )
+    .expect("Failed to parse FTL.");
+    let mut bundle = FluentBundle::default();
+    bundle.add_resource(&res).expect("Failed to add FTL.");
+    assert_eq!(format_value(&bundle, "cta").as_deref(), Some("Sign up"));
+
+    fn choose(id: &str, tags: &[VariantTag]) -> usize {
+        assert_eq!(id, "cta");
+        let names: Vec<_> = tags.iter().map(|tag| tag.name).collect();
+        assert_eq!(names, ["default", "b", "c"]);
+        2
+    }
+    bundle.set_variant_selector(Some(choose));
+    assert_eq!(format_value(&bundle, "cta").as_deref(), Some("Start free"));
+    assert_eq!(
+        format_value(&bundle, "banner").as_deref(),
+        Some("Start free!")
+    );
+    assert_eq!(format_value(&bundle, "plain").as_deref(), Some("Plain"));
+
+    fn out_of_range(_: &str, _: &[VariantTag]) -> usize {
+        7
+    }
+    bundle.set_variant_selector(Some(out_of_range));
+    assert_eq!(format_value(&bundle, "cta").as_deref(), Some("Sign up"));
+}
+
+#[test]
+fn term_attributes_drive_agreement() {
+    let res = FluentResource::try_new(
+        "
+-app = Aplikacja
+    .gender = feminine
+-tool = Edytor
+    .gender = masculine
+app-ready = { -app.gender ->
+    [feminine] Gotowa
+   *[masculine] Gotowy
+}
+tool-ready = { -tool.gender ->
+    [feminine] Gotowa
+   *[masculine] Gotowy
+}
+"
+        .to_string(),
+    )
+    .expect("Failed to parse FTL.");
+    let mut bundle = FluentBundle::default();
+    bundle.add_resource(&res).expect("Failed to add FTL.");
+
+    assert_eq!(
+        format_value(&bundle, "app-ready").as_deref(),
+        Some("Gotowa")
+    );
+    assert_eq!(
+        format_value(&bundle, "tool-ready").as_deref(),
+        Some("Gotowy")
+    );
+    assert_eq!(
+        bundle.get_term_attribute("-app", "gender").as_deref(),
+        Some("feminine")
+    );
+    assert_eq!(
+        bundle.get_term_attribute("tool", "gender").as_deref(),
+        Some("masculine")
+    );
+    assert_eq!(bundle.get_term_attribute("-app", "case"), None);
+    assert_eq!(bundle.get_term_attribute("app-ready", "gender"), None);
+}
+
+#[test]
+fn catalog_stats() {
+    let res = FluentResource::try_new(
+        "
+# A comment is not counted.
+-brand = Nightly
+    .gender = feminine
+welcome = Welcome to { -brand }, { $user }!
+    .title = { { -brand } }
+tabs = { $count ->
+    [one] One tab is open.
+   *[other] { $count } tabs are open.
+}
+"
+        .to_string(),
+    )
+    .expect("Failed to parse FTL.");
+    let stats = res.stats();
+    assert_eq!(stats.messages, 2);
+    assert_eq!(stats.terms, 1);
+    assert_eq!(stats.attributes, 2);
+    assert_eq!(stats.words, 11);
+    assert_eq!(stats.placeables, 6);
+    assert_eq!(stats.select_expressions, 1);
+
+    let mut bundle = FluentBundle::default();
+    bundle.add_resource(&res).expect("Failed to add FTL.");
+    let other = FluentResource::try_new("welcome = Hi\nextra = Two words".to_string())
+        .expect("Failed to parse FTL.");
+    assert!(bundle.add_resource(&other).is_err());
+    let stats = bundle.stats();
+    assert_eq!(stats.messages, 3);
+    assert_eq!(stats.words, 13);
+}
+
+#[test]
+fn message_hashes_track_changes() {
+    let old = FluentResource::try_new(
+        "# Old comment\nhello = Hello!\nbye = Bye!\n-brand = Brand\n".to_string(),
+    )
+    .expect("Failed to parse FTL.");
+    let new = FluentResource::new_lazy(
+        "# New comment\nhello =\n    Hello!\nbye = Goodbye!\n-brand = Other\n".to_string(),
+    );
+
+    let old_hashes: Vec<_> = old.message_hashes().collect();
+    let new_hashes: Vec<_> = new.message_hashes().collect();
+    assert_eq!(old_hashes.len(), 2);
+    assert_eq!(old_hashes[0], new_hashes[0]);
+    assert_eq!(old_hashes[1].0, new_hashes[1].0);
+    assert_ne!(old_hashes[1].1, new_hashes[1].1);
+}
+
+#[test]
+fn unknown_reference_handler_supplies_values() {
+    use fluent_bundle::resolver::errors::ReferenceKind;
+
+    let res = FluentResource::try_new(
+        "
+known = Known
+greeting = { cms-hello }, { $user }! { -brand } { known }
+missing = { nothing-here } { -other }
+counted = { $count ->
+    [one] One item
+   *[other] Many items
+}
+-local = { $inner }
+term-args = { -local() }
+"
+        .to_string(),
+    )
+    .expect("Failed to parse FTL.");
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add FTL.");
+    bundle.set_unknown_reference_handler(|reference| match reference {
+        ReferenceKind::Message {
+            id,
+            attribute: None,
+        } if id == "cms-hello" => Some("Hello".into()),
+        ReferenceKind::Term {
+            id,
+            attribute: None,
+        } if id == "brand" => Some("Nightly".into()),
+        ReferenceKind::Variable { id } if id == "user" => Some("Anna".into()),
+        ReferenceKind::Variable { id } if id == "count" => Some(1.into()),
+        ReferenceKind::Variable { id } if id == "inner" => Some("Leaked".into()),
+        _ => None,
+    });
+
+    let format = |id: &str| {
+        let value = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let result = bundle.format_pattern(value, None, &mut errors).to_string();
+        (result, errors.len())
+    };
+    assert_eq!(format("greeting"), ("Hello, Anna! Nightly Known".into(), 0));
+    assert_eq!(format("missing"), ("{nothing-here} {-other}".into(), 2));
+    assert_eq!(format("counted"), ("One item".into(), 0));
+    assert_eq!(format("term-args"), ("{$inner}".into(), 0));
+}
+
+#[test]
+fn plural_rules_backend_selects_categories() {
+    struct Rules;
+
+    impl PluralRulesBackend for Rules {
+        fn category(
+            &self,
+            locale: &LanguageIdentifier,
+            number: &FluentNumber,
+        ) -> Option<PluralCategory> {
+            assert_eq!(locale, &langid!("pl"));
+            if number.options.r#type == FluentNumberType::Ordinal {
+                return Some(PluralCategory::Other);
+            }
+            Some(match number.value as i64 {
+                1 => PluralCategory::One,
+                2..=4 => PluralCategory::Few,
+                _ => PluralCategory::Many,
+            })
+        }
+    }
+
+    let res = FluentResource::try_new(
+        r#"
+files = { $count ->
+    [one] One file
+    [few] A few files
+    [many] Many files
+   *[other] Other files
+}
+place = { NUMBER($count, type: "ordinal") ->
+    [one] One
+   *[other] Other
+}
+fixed = { $count ->
+    [3] Three
+   *[few] Few
+}
+"#
+        .to_string(),
+    )
+    .expect("Failed to parse FTL.");
+    let mut bundle = FluentBundle::new(vec![langid!("pl")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add FTL.");
+    bundle.add_builtins().expect("Failed to add builtins.");
+
+    let format = |bundle: &FluentBundle<&FluentResource>, id: &str, count: i32| {
+        let mut args = FluentArgs::new();
+        args.set("count", count);
+        let value = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let result = bundle.format_pattern(value, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        result.to_string()
+    };
+
+    // The builtin rules are replaced, and the variants already selected by
+    // them are forgotten.
+    assert_eq!(format(&bundle, "files", 22), "A few files");
+    bundle.set_plural_rules(Rules);
+    assert_eq!(format(&bundle, "files", 22), "Many files");
+    assert_eq!(format(&bundle, "files", 1), "One file");
+    assert_eq!(format(&bundle, "files", 3), "A few files");
+    assert_eq!(format(&bundle, "files", 3), "A few files");
+    assert_eq!(format(&bundle, "place", 1), "Other");
+    assert_eq!(format(&bundle, "fixed", 3), "Three");
+}
+
+#[test]
+fn plural_selections_are_cached() {
+    struct Rules(Arc<AtomicUsize>);
+
+    impl PluralRulesBackend for Rules {
+        fn category(
+            &self,
+            _locale: &LanguageIdentifier,
+            number: &FluentNumber,
+        ) -> Option<PluralCategory> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            if number.value == 1.0 {
+                Some(PluralCategory::One)
+            } else {
+                Some(PluralCategory::Other)
+            }
+        }
+    }
+
+    let source = r#"
+progress = { $count ->
+    [one] { $count } file
+   *[other] { $count } files
+}
+exact = { $count ->
+    [1] Exactly one
+   *[other] Other
+}
+"#;
+    let res = FluentResource::try_new(source.to_string()).expect("Failed to parse FTL.");
+    let other = FluentResource::try_new("other = Other".to_string()).expect("Failed to parse FTL.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add FTL.");
+    let calls = Arc::new(AtomicUsize::new(0));
+    bundle.set_plural_rules(Rules(Arc::clone(&calls)));
+
+    let format = |bundle: &FluentBundle<&FluentResource>, id: &str, count: i32| {
+        let mut args = FluentArgs::new();
+        args.set("count", count);
+        let value = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let result = bundle.format_pattern(value, Some(&args), &mut errors);
+        assert_eq!(errors, vec![]);
+        result.to_string()
+    };
+    let mut counted = 0;
+    let mut new_calls = || {
+        let current = calls.load(Ordering::Relaxed);
+        current - std::mem::replace(&mut counted, current)
+    };
+
+    // The plural rules only run again for another number.
+    for _ in 0..3 {
+        assert_eq!(format(&bundle, "progress", 5), "5 files");
+    }
+    assert_eq!(new_calls(), 1);
+    assert_eq!(format(&bundle, "progress", 1), "1 file");
+    assert_eq!(format(&bundle, "progress", 1), "1 file");
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(new_calls(), 2);
+
+    // Select expressions with numeric keys, and patterns the bundle does not
+    // own, are matched every time.
+    assert_eq!(format(&bundle, "exact", 2), "Other");
+    assert_eq!(format(&bundle, "exact", 2), "Other");
+    assert_eq!(format(&bundle, "exact", 1), "Exactly one");
+    assert_eq!(new_calls(), 2);
+    let mut args = FluentArgs::new();
+    args.set("count", 7);
+    let mut errors = vec![];
+    for _ in 0..2 {
+        let value = bundle.format_raw_pattern(
+            "{ $count ->\n    [one] file\n   *[other] files\n}",
+            Some(&args),
+            &mut errors,
+        );
+        assert_eq!(value, "files");
+    }
+    assert!(errors.is_empty());
+    // Once for each identifier key of each format.
+    assert_eq!(new_calls(), 4);
+
+    // Changing the resources or the hooks forgets the selections.
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(new_calls(), 0);
+    bundle.add_resource(&other).expect("Failed to add FTL.");
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    bundle.set_transform(None);
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    bundle.set_variant_selector(None);
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    bundle.replace_resource(1, &other, &mut errors);
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    bundle.remove_resource(1);
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(new_calls(), 5);
+    bundle.set_plural_rules(Rules(Arc::clone(&calls)));
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(new_calls(), 1);
+
+    // Selector matchers may match variants on more than the category.
+    bundle.set_selector_matcher(|_, _| None);
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(format(&bundle, "progress", 7), "7 files");
+    assert_eq!(new_calls(), 4);
+}
+
+#[test]
+fn frozen_bundle_formats_without_caches() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<FrozenBundle<FluentResource>>();
+
+    let res = FluentResource::try_new(
+        "emails = { $n ->\n    [one] One email\n   *[other] { $n } emails\n}".to_string(),
+    )
+    .unwrap();
+    let mut bundle = concurrent::FluentBundle::new_concurrent(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(res).expect("Failed to add a resource");
+    let bundle = bundle.freeze();
+
+    let value = bundle
+        .get_message("emails")
+        .expect("Failed to retrieve a message")
+        .value()
+        .expect("Failed to retrieve a value of a message");
+    let mut errors = vec![];
+    for (n, expected) in [(1, "One email"), (5, "5 emails"), (1, "One email")] {
+        let mut args = FluentArgs::new();
+        args.set("n", n);
+        assert_eq!(
+            bundle.format_pattern(value, Some(&args), &mut errors),
+            expected
+        );
+    }
+    assert!(errors.is_empty());
+    assert!(bundle.has_message("emails"));
+    assert_eq!(bundle.stats().messages, 1);
+}
+
+#[test]
+fn format_pattern_in_locale_uses_its_plural_rules() {
+    let res = FluentResource::try_new(
+        "files = { $n ->\n    [one] one\n    [few] few\n   *[other] other\n}".to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US"), langid!("pl")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    let value = bundle
+        .get_message("files")
+        .expect("Failed to retrieve a message")
+        .value()
+        .expect("Failed to retrieve a value of a message");
+    let mut args = FluentArgs::new();
+    args.set("n", 2);
+    let mut errors = vec![];
+
+    // Formatting in the first locale fills the select cache, which must not
+    // be used for the other locales.
+    let result = bundle.format_pattern_in_locale(value, Some(&args), None, &mut errors);
+    assert_eq!(result.value(), "other");
+    assert_eq!(result.resolved_locale(), Some(&langid!("en-US")));
+
+    let result =
+        bundle.format_pattern_in_locale(value, Some(&args), Some(&langid!("pl-PL")), &mut errors);
+    assert_eq!(result.value(), "few");
+    assert_eq!(result.resolved_locale(), Some(&langid!("pl")));
+
+    let result =
+        bundle.format_pattern_in_locale(value, Some(&args), Some(&langid!("de")), &mut errors);
+    assert_eq!(result.value(), "other");
+    assert_eq!(result.resolved_locale(), Some(&langid!("en-US")));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn write_pattern_matches_format_pattern() {
+    fn final_formatter<M>(value: &FluentValue, _intls: &M, pass: FormatterPass) -> Option<String> {
+        match (value, pass) {
+            (FluentValue::String(s), FormatterPass::Final) => Some(format!("[{}]", s)),
+            _ => None,
+        }
+    }
+
+    let res = FluentResource::try_new(
+        r#"
+-brand = Firefox
+plain = Hello!
+welcome = Welcome to { -brand }, { $user }!
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+
+    let mut buffer = String::new();
+    let mut errors = vec![];
+    for pass in 0..2 {
+        if pass == 1 {
+            bundle.set_formatter(Some(final_formatter));
+        }
+        for id in ["plain", "welcome", "emails"] {
+            let value = bundle
+                .get_message(id)
+                .expect("Failed to retrieve a message")
+                .value()
+                .expect("Failed to retrieve a value of a message");
+            let mut args = FluentArgs::new();
+            args.set("user", "Anna");
+            args.set("count", 5);
+
+            buffer.clear();
+            bundle
+                .write_pattern(&mut buffer, value, Some(&args), &mut errors)
+                .expect("Failed to write");
+            assert_eq!(
+                buffer,
+                bundle.format_pattern(value, Some(&args), &mut errors)
+            );
+        }
+    }
+    assert_eq!(buffer, "[5 emails]");
+    assert!(errors.is_empty());
+}
+
+/// Formats a message into parts, checking that they join into its formatted
+/// value.
+fn format_parts<'bundle>(
+    bundle: &'bundle FluentBundle<&FluentResource>,
+    id: &str,
+    args: &FluentArgs,
+    errors: &mut Vec<FluentError>,
+) -> Vec<FormattedPart<'bundle>> {
+    let value = bundle
+        .get_message(id)
+        .expect("Failed to retrieve a message")
+        .value()
+        .expect("Failed to retrieve a value of a message");
+    let parts = bundle.format_pattern_to_parts(value, Some(args), errors);
+    let joined: String = parts.iter().map(ToString::to_string).collect();
+    assert_eq!(joined, bundle.format_pattern(value, Some(args), errors));
+    parts
+}
+
+#[test]
+fn format_pattern_to_parts() {
+    let res = FluentResource::try_new(
+        r#"
+-brand = Firefox
+welcome = Welcome to { -brand }, { $user }!
+emails = You have { $count ->
+    [one] one email
+   *[other] { $count } emails
+} from { $sender }.
+missing = Hello { $missing }!
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    let mut args = FluentArgs::new();
+    args.set("user", "Anna");
+    args.set("count", 5);
+    args.set("sender", "Bob");
+
+    let mut errors = vec![];
+    let parts = format_parts(&bundle, "welcome", &args, &mut errors);
+    let texts: Vec<_> = parts
+        .iter()
+        .map(|part| match part {
+            FormattedPart::Text(text) => format!("text:{}", text),
+            FormattedPart::Placeable { value, .. } => format!("placeable:{}", value),
+            FormattedPart::IsolateStart(_) => "start".to_string(),
+            FormattedPart::IsolateEnd => "end".to_string(),
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            "text:Welcome to ",
+            "placeable:Firefox",
+            "text:, ",
+            "start",
+            "placeable:Anna",
+            "end",
+            "text:!",
+        ]
+    );
+    assert!(matches!(
+        &parts[4],
+        FormattedPart::Placeable {
+            expression: ast::Expression::Inline(ast::InlineExpression::VariableReference { id }),
+            ..
+        } if id.name == "user"
+    ));
+
+    // The selected variant is split into parts too.
+    bundle.set_use_isolating(false);
+    let parts = format_parts(&bundle, "emails", &args, &mut errors);
+    let values: Vec<_> = parts
+        .iter()
+        .filter_map(|part| match part {
+            FormattedPart::Placeable { value, .. } => Some(value.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(values, vec!["5", "Bob"]);
+    assert!(errors.is_empty());
+
+    let parts = format_parts(&bundle, "missing", &args, &mut errors);
+    assert_eq!(
+        parts[1].to_string(),
+        "{$missing}",
+        "Unresolved placeables keep their fallback"
+    );
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn format_pattern_strict() {
+    let res = FluentResource::try_new(
+        r#"
+plain = Hello!
+hello = Hello, { $user }!
+broken = { missing } and { $user } and { FOO() }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    let value = |id| {
+        bundle
+            .get_message(id)
+            .and_then(|message| message.value())
+            .expect("Failed to retrieve a value of a message")
+    };
+
+    let mut args = FluentArgs::new();
+    args.set("user", "Anna");
+    assert_eq!(
+        bundle
+            .format_pattern_strict(value("plain"), None)
+            .as_deref(),
+        Ok("Hello!")
+    );
+    assert_eq!(
+        bundle
+            .format_pattern_strict(value("hello"), Some(&args))
+            .as_deref(),
+        Ok("Hello, Anna!")
+    );
+    assert_eq!(
+        bundle.format_pattern_strict(value("hello"), None),
+        Err(vec![FluentError::ResolverError(ResolverError::Reference(
+            fluent_bundle::resolver::errors::ReferenceKind::Variable {
+                id: "user".to_string()
+            }
+        ))])
+    );
+    let errors = bundle
+        .format_pattern_strict(value("broken"), None)
+        .unwrap_err();
+    assert_eq!(errors.len(), 3);
+}
+
+#[test]
+fn invalid_placeables_are_reported() {
+    use fluent_syntax::parser::ParserOptions;
+
+    let options = ParserOptions {
+        recover_placeables: true,
+        ..ParserOptions::default()
+    };
+    let (res, _) = FluentResource::try_new_with_options("hello = Hello{ $ }!".to_string(), options)
+        .expect_err("The resource contains an invalid placeable.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    let value = bundle.get_message("hello").unwrap().value().unwrap();
+
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(value, None, &mut errors),
+        "Hello{???}!"
+    );
+    assert_eq!(
+        errors,
+        [FluentError::ResolverError(ResolverError::InvalidPlaceable)]
+    );
+    assert_eq!(
+        bundle.format_pattern_strict(value, None),
+        Err(vec![FluentError::ResolverError(
+            ResolverError::InvalidPlaceable
+        )])
+    );
+}
+
+#[test]
+fn unknown_expressions_are_reported() {
+    use fluent_syntax::parser::ParserOptions;
+
+    let options = ParserOptions {
+        unknown_expressions: true,
+        ..ParserOptions::default()
+    };
+    let (res, _) = FluentResource::try_new_with_options(
+        "hello = Hello{ @future-syntax }!".to_string(),
+        options,
+    )
+    .expect_err("The resource contains an unknown expression.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    let value = bundle.get_message("hello").unwrap().value().unwrap();
+
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(value, None, &mut errors),
+        "Hello{???}!"
+    );
+    assert_eq!(
+        errors,
+        [FluentError::ResolverError(ResolverError::InvalidPlaceable)]
+    );
+    assert_eq!(
+        bundle.format_pattern_strict(value, None),
+        Err(vec![FluentError::ResolverError(
+            ResolverError::InvalidPlaceable
+        )])
+    );
+
+    let diagnostics = bundle.check_all(|_| None);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "hello: Resolver error: Invalid placeable"
+    );
+}
+
+#[test]
+fn remove_resource() {
+    let res = FluentResource::try_new("key = Value\nother = Other".to_string()).unwrap();
+    let res2 = FluentResource::try_new("key = Value 2\nlast = Last".to_string()).unwrap();
+    let res3 = FluentResource::try_new("key = Value 3\n-term = Term\nuses = { -term }".to_string())
+        .unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    bundle.add_resource_overriding(&res2);
+    assert!(bundle.add_resource(&res3).is_err());
+    let format = |bundle: &FluentBundle<_>, id| {
+        let value = bundle.get_message(id).and_then(|msg| msg.value())?;
+        let mut errors = vec![];
+        Some(bundle.format_pattern(value, None, &mut errors).to_string())
+    };
+    assert_eq!(format(&bundle, "key").as_deref(), Some("Value 2"));
+
+    let removed = bundle.remove_resource(1);
+    assert!(std::ptr::eq(removed, &res2));
+    assert_eq!(format(&bundle, "key").as_deref(), Some("Value"));
+    assert_eq!(format(&bundle, "last"), None);
+    assert_eq!(format(&bundle, "uses").as_deref(), Some("Term"));
+
+    bundle.remove_resource(0);
+    assert_eq!(format(&bundle, "key").as_deref(), Some("Value 3"));
+    assert_eq!(format(&bundle, "other"), None);
+    assert_eq!(format(&bundle, "uses").as_deref(), Some("Term"));
+
+    bundle.remove_resource(0);
+    assert_eq!(format(&bundle, "key"), None);
+    assert_eq!(format(&bundle, "uses"), None);
+}
+
+#[test]
+fn replace_resource() {
+    let res = FluentResource::try_new("key = Value\nother = Other".to_string()).unwrap();
+    let res2 = FluentResource::try_new("key = Value 2\nlast = Last".to_string()).unwrap();
+    let reloaded = FluentResource::try_new("key = Reloaded\nlast = Reloaded".to_string()).unwrap();
+
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle
+        .add_function("LAST", |_, _| "Function".into())
+        .expect("Failed to add a function");
+    bundle.add_resource(&res).expect("Failed to add a resource");
+    assert!(bundle.add_resource(&res2).is_err());
+    let format = |bundle: &FluentBundle<_>, id| {
+        let value = bundle.get_message(id).and_then(|msg| msg.value())?;
+        let mut errors = vec![];
+        Some(bundle.format_pattern(value, None, &mut errors).to_string())
+    };
+
+    let mut errors = vec![];
+    let replaced = bundle.replace_resource(0, &reloaded, &mut errors);
+    assert!(std::ptr::eq(replaced, &res));
+    assert!(errors.is_empty());
+    assert_eq!(format(&bundle, "key").as_deref(), Some("Reloaded"));
+    assert_eq!(format(&bundle, "last").as_deref(), Some("Reloaded"));
+    assert_eq!(format(&bundle, "other"), None);
+
+    bundle.replace_resource(1, &res, &mut errors);
+    assert!(matches!(
+        &errors[..],
+        [FluentError::Overriding { id, .. }] if id == "key"
+    ));
+    assert_eq!(format(&bundle, "key").as_deref(), Some("Reloaded"));
+    assert_eq!(format(&bundle, "last").as_deref(), Some("Reloaded"));
+    assert_eq!(format(&bundle, "other").as_deref(), Some("Other"));
+}
+
+#[test]
+fn references_are_resolved_by_symbol() {
+    let res = FluentResource::try_new(
+        "-brand = Fluent\nhello = Hello from { -brand }, { $user }!\nbye = { hello } Bye!"
+            .to_string(),
+    )
+    .unwrap();
+    let mut bundle = concurrent::FluentBundle::new_concurrent(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(res).unwrap();
+
+    let mut args = FluentArgs::new();
+    args.set("user", "Anna");
+    args.set("other", "Other");
+    let format = |bundle: &concurrent::FluentBundle<FluentResource>, id| {
+        let msg = bundle.get_message(id).expect("Message doesn't exist.");
+        let mut errors = vec![];
+        let value = bundle.format_pattern(msg.value().unwrap(), Some(&args), &mut errors);
+        assert!(errors.is_empty(), "Resolver errors: {:?}", errors);
+        value.into_owned()
+    };
+    assert_eq!(format(&bundle, "bye"), "Hello from Fluent, Anna! Bye!");
+
+    // Patterns the bundle does not own reference identifiers by name.
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_raw_pattern("{ $other }, { $user }: { bye }", Some(&args), &mut errors),
+        "Other, Anna: Hello from Fluent, Anna! Bye!"
+    );
+    assert!(errors.is_empty());
+
+    // The references of the parent resolve to the entries of the overlay.
+    let bundle = Arc::new(bundle);
+    let mut overlay = bundle.overlay();
+    let res =
+        FluentResource::try_new("-brand = Tenant\nhello-tenant = { hello }".to_string()).unwrap();
+    overlay.add_resource(res).unwrap();
+    assert_eq!(format(&overlay, "bye"), "Hello from Tenant, Anna! Bye!");
+    assert_eq!(format(&overlay, "hello-tenant"), "Hello from Tenant, Anna!");
+    assert_eq!(format(&bundle, "bye"), "Hello from Fluent, Anna! Bye!");
+}
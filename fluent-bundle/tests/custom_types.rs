@@ -5,6 +5,7 @@ use fluent_bundle::FluentArgs;
 use fluent_bundle::FluentBundle;
 use fluent_bundle::FluentResource;
 use fluent_bundle::FluentValue;
+use std::str::FromStr;
 use unic_langid::langid;
 
 #[test]
@@ -176,6 +177,69 @@ key-ref = Hello { DATETIME($date, dateStyle: "full") } World
     assert_eq!(val, "Hello 2020-01-20 10:00 World");
 }
 
+#[test]
+fn fluent_date_time_options_from_typed_args() {
+    #[derive(Clone, Debug, Default, PartialEq)]
+    enum DateTimeStyleValue {
+        Full,
+        Long,
+        Medium,
+        Short,
+        #[default]
+        None,
+    }
+
+    impl FromStr for DateTimeStyleValue {
+        type Err = ();
+
+        fn from_str(input: &str) -> Result<Self, Self::Err> {
+            match input {
+                "full" => Ok(Self::Full),
+                "long" => Ok(Self::Long),
+                "medium" => Ok(Self::Medium),
+                "short" => Ok(Self::Short),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct DateTimeOptions {
+        pub date_style: DateTimeStyleValue,
+        pub time_style: DateTimeStyleValue,
+    }
+
+    impl DateTimeOptions {
+        pub fn merge(&mut self, input: &FluentArgs) {
+            if let Some(style) = input.get_enum("dateStyle") {
+                self.date_style = style.unwrap_or_default();
+            }
+            if let Some(style) = input.get_enum("timeStyle") {
+                self.time_style = style.unwrap_or_default();
+            }
+        }
+    }
+
+    let mut options = DateTimeOptions {
+        date_style: DateTimeStyleValue::Long,
+        time_style: DateTimeStyleValue::Short,
+    };
+    let mut args = FluentArgs::new();
+    args.set("dateStyle", "full");
+    args.set("timeStyle", "tiny");
+    options.merge(&args);
+    assert_eq!(
+        options,
+        DateTimeOptions {
+            date_style: DateTimeStyleValue::Full,
+            time_style: DateTimeStyleValue::None,
+        }
+    );
+
+    options.merge(&FluentArgs::new());
+    assert_eq!(options.date_style, DateTimeStyleValue::Full);
+}
+
 #[test]
 fn fluent_custom_number_format() {
     fn custom_formatter<M: MemoizerKind>(
@@ -228,3 +292,72 @@ key-num-explicit = Hello { NUMBER(5, minimumFractionDigits: 2) } World
     let val = bundle.format_pattern(msg.value().unwrap(), None, &mut errors);
     assert_eq!(val, "Hello CUSTOM World");
 }
+
+#[test]
+fn selector_matcher_matches_custom_types() {
+    #[derive(Debug, PartialEq)]
+    struct Weekday(u8);
+
+    impl FluentType for Weekday {
+        fn duplicate(&self) -> Box<dyn FluentType + Send> {
+            Box::new(Weekday(self.0))
+        }
+        fn as_string(&self, _: &intl_memoizer::IntlLangMemoizer) -> std::borrow::Cow<'static, str> {
+            format!("day {}", self.0).into()
+        }
+        fn as_string_threadsafe(
+            &self,
+            _: &intl_memoizer::concurrent::IntlLangMemoizer,
+        ) -> std::borrow::Cow<'static, str> {
+            format!("day {}", self.0).into()
+        }
+    }
+
+    let res = FluentResource::try_new(
+        r#"
+opening = { $day ->
+    [weekend] Closed
+   *[other] Open
+}
+tone = { $tone ->
+    [formal] Good evening
+   *[casual] Hi
+}
+files = { $count ->
+    [one] One file
+   *[other] Many files
+}
+"#
+        .into(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.add_resource(res).unwrap();
+    bundle.set_selector_matcher(|selector, key| match (selector, key) {
+        (FluentValue::Custom(day), FluentValue::String(key)) if key == "weekend" => {
+            day.as_any().downcast_ref::<Weekday>().map(|day| day.0 >= 6)
+        }
+        (FluentValue::String(selector), FluentValue::String(key)) => {
+            Some(selector.eq_ignore_ascii_case(key))
+        }
+        _ => None,
+    });
+
+    let format = |id: &str, name: &str, value: FluentValue<'static>| {
+        let mut args = FluentArgs::new();
+        args.set(name.to_string(), value);
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let result = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        assert!(errors.is_empty());
+        result.to_string()
+    };
+
+    let day = |day| FluentValue::Custom(Box::new(Weekday(day)));
+    assert_eq!(format("opening", "day", day(7)), "Closed");
+    assert_eq!(format("opening", "day", day(2)), "Open");
+    assert_eq!(format("tone", "tone", "Formal".into()), "Good evening");
+    // Numbers are left to the default matching with plural rules.
+    assert_eq!(format("files", "count", 1.into()), "One file");
+    assert_eq!(format("files", "count", 3.into()), "Many files");
+}
@@ -1,5 +1,15 @@
+use fluent_bundle::concurrent::FluentBundle as ConcurrentBundle;
+use fluent_bundle::resolver::ResolverError;
 use fluent_bundle::types::FluentNumber;
-use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use fluent_bundle::{
+    FluentArgs, FluentBundle, FluentError, FluentFunctionError, FluentResource, FluentValue,
+    FunctionContext,
+};
+use intl_memoizer::concurrent::IntlLangMemoizer;
+use intl_memoizer::Memoizable;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use unic_langid::{langid, LanguageIdentifier};
 
 #[test]
 fn test_function_resolve() {
@@ -37,10 +47,7 @@ liked-count2 = { NUMBER($num) ->
                 } else {
                     return FluentValue::Error;
                 };
-                let mut num = FluentNumber {
-                    value: num,
-                    options: Default::default(),
-                };
+                let mut num = FluentNumber::new(num, Default::default());
                 num.options.merge(named);
 
                 FluentValue::Number(num)
@@ -102,3 +109,254 @@ liked-count2 = { NUMBER($num) ->
     let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
     assert_eq!("One person liked your message", &value);
 }
+
+#[test]
+fn test_namespaced_function() {
+    let ftl_string = String::from(
+        r#"
+builtin = { NUMBER(1) }
+plugin = { PLUGIN.NUMBER(1) }
+separator = { ICU_NUMBER(1) }
+attribute = { msg.attr }
+    "#,
+    );
+
+    let res = FluentResource::try_new(ftl_string).expect("Could not parse an FTL string.");
+    let mut bundle = FluentBundle::default();
+    bundle.set_use_isolating(false);
+
+    bundle
+        .add_function("NUMBER", |_, _| "builtin".into())
+        .expect("Failed to add a function.");
+    bundle
+        .add_function_ns("PLUGIN", "NUMBER", |_, _| "plugin".into())
+        .expect("Failed to add a function.");
+    assert!(bundle
+        .add_function_ns("PLUGIN", "NUMBER", |_, _| "again".into())
+        .is_err());
+    bundle.set_namespace_separator("_");
+    bundle
+        .add_function_ns("ICU", "NUMBER", |_, _| "separator".into())
+        .expect("Failed to add a function.");
+
+    bundle
+        .add_resource(res)
+        .expect("Failed to add FTL resources to the bundle.");
+
+    for id in ["builtin", "plugin", "separator"] {
+        let msg = bundle.get_message(id).expect("Message doesn't exist.");
+        let mut errors = vec![];
+        let pattern = msg.value().expect("Message has no value.");
+        let value = bundle.format_pattern(pattern, None, &mut errors);
+        assert_eq!(value, id);
+        assert!(errors.is_empty());
+    }
+    assert!(bundle.has_message("attribute"));
+}
+
+#[test]
+fn test_function_with_context() {
+    struct Greeting(&'static str);
+
+    impl Memoizable for Greeting {
+        type Args = ();
+        type Error = ();
+        fn construct(lang: LanguageIdentifier, _args: Self::Args) -> Result<Self, Self::Error> {
+            match lang.language.as_str() {
+                "fr" => Ok(Self("Bonjour")),
+                "en" => Ok(Self("Hello")),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn greet<'a>(
+        _positional: &[FluentValue<'a>],
+        _named: &FluentArgs,
+        ctx: &FunctionContext,
+    ) -> FluentValue<'a> {
+        match ctx.with_try_get::<Greeting, _, _>((), |greeting| greeting.0) {
+            Some(Ok(greeting)) => format!("{} ({})", greeting, ctx.locales().len()).into(),
+            _ => FluentValue::Error,
+        }
+    }
+
+    let res = FluentResource::try_new("greeting = { GREET() }".to_string())
+        .expect("Could not parse an FTL string.");
+    let mut bundle = FluentBundle::new(vec![langid!("fr"), langid!("en")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_function_with_ctx("GREET", greet)
+        .expect("Failed to add a function.");
+    bundle
+        .add_resource(&res)
+        .expect("Failed to add FTL resources to the bundle.");
+
+    let pattern = bundle.get_message("greeting").unwrap().value().unwrap();
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(pattern, None, &mut errors),
+        "Bonjour (2)"
+    );
+    assert_eq!(
+        bundle
+            .format_pattern_in_locale(pattern, None, Some(&langid!("en")), &mut errors)
+            .to_string(),
+        "Hello (2)"
+    );
+    assert!(errors.is_empty());
+
+    let mut bundle = ConcurrentBundle::new_concurrent(vec![langid!("en")]);
+    bundle
+        .add_function_with_ctx("GREET", |positional, named, ctx| {
+            assert!(ctx.intls::<IntlLangMemoizer>().is_some());
+            greet(positional, named, ctx)
+        })
+        .expect("Failed to add a function.");
+    bundle
+        .add_resource(&res)
+        .expect("Failed to add FTL resources to the bundle.");
+    let pattern = bundle.get_message("greeting").unwrap().value().unwrap();
+    assert_eq!(
+        bundle.format_pattern(pattern, None, &mut errors),
+        "Hello (1)"
+    );
+}
+
+#[test]
+fn test_fallible_function() {
+    let res = FluentResource::try_new(
+        r#"
+parsed = { PARSE($input) }
+selected = { PARSE($input) ->
+    [1] One
+   *[other] Other
+}
+"#
+        .to_string(),
+    )
+    .expect("Could not parse an FTL string.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_fallible_function("PARSE", |positional, _named, _ctx| match positional {
+            [FluentValue::String(s)] => s
+                .parse::<f64>()
+                .map(FluentValue::from)
+                .map_err(|err| FluentFunctionError::argument("0", err.to_string())),
+            _ => Err(FluentFunctionError::new("expected a string")),
+        })
+        .expect("Failed to add a function.");
+    bundle
+        .add_resource(&res)
+        .expect("Failed to add FTL resources to the bundle.");
+
+    let format = |id: &str, input: &str| {
+        let mut args = FluentArgs::new();
+        args.set("input", input.to_string());
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
+        (value.to_string(), errors)
+    };
+
+    assert_eq!(format("parsed", "1.5"), ("1.5".to_string(), vec![]));
+    assert_eq!(format("selected", "1"), ("One".to_string(), vec![]));
+
+    let error = FluentError::ResolverError(ResolverError::Function {
+        id: "PARSE".to_string(),
+        error: FluentFunctionError::argument("0", "invalid float literal"),
+    });
+    assert_eq!(
+        format("parsed", "one"),
+        ("{PARSE()}".to_string(), vec![error.clone()])
+    );
+    assert_eq!(
+        format("selected", "one"),
+        ("Other".to_string(), vec![error])
+    );
+}
+
+#[test]
+fn test_async_function() {
+    let res = FluentResource::try_new(
+        r#"
+welcome = Hi { USERNAME($id) }, { USERNAME($id) }!
+initial = { UPPER(USERNAME($id)) }
+nested = { USERNAME(USERID($email)) }
+unread = { COUNT($id) ->
+    [0] No messages
+   *[other] Messages
+}
+"#
+        .to_string(),
+    )
+    .expect("Could not parse an FTL string.");
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut bundle = ConcurrentBundle::new_concurrent(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_function("UPPER", |positional, _named| match positional {
+            [FluentValue::String(s)] => s.to_uppercase().into(),
+            _ => FluentValue::Error,
+        })
+        .expect("Failed to add a function.");
+    let counter = calls.clone();
+    bundle
+        .add_async_function("USERNAME", move |positional, _named, _ctx| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            async move {
+                match positional.as_slice() {
+                    [FluentValue::Number(n)] if n.value == 7.0 => Ok("Anna".into()),
+                    _ => Err(FluentFunctionError::argument("0", "unknown user")),
+                }
+            }
+        })
+        .expect("Failed to add a function.");
+    bundle
+        .add_async_function("USERID", |positional, _named, _ctx| async move {
+            match positional.as_slice() {
+                [FluentValue::String(s)] if s == "anna@example.com" => Ok(7.into()),
+                _ => Ok(FluentValue::Error),
+            }
+        })
+        .expect("Failed to add a function.");
+    bundle
+        .add_async_function("COUNT", |_positional, _named, _ctx| async { Ok(0.into()) })
+        .expect("Failed to add a function.");
+    bundle
+        .add_resource(&res)
+        .expect("Failed to add FTL resources to the bundle.");
+
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+    let format = |id: &str| {
+        let mut args = FluentArgs::new();
+        args.set("id", 7);
+        args.set("email", "anna@example.com");
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        let future = assert_send(bundle.format_pattern_async(pattern, Some(&args), &mut errors));
+        let value = futures::executor::block_on(future).to_string();
+        (value, errors)
+    };
+
+    assert_eq!(format("welcome"), ("Hi Anna, Anna!".to_string(), vec![]));
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    assert_eq!(format("initial"), ("ANNA".to_string(), vec![]));
+    assert_eq!(format("nested"), ("Anna".to_string(), vec![]));
+    assert_eq!(format("unread"), ("No messages".to_string(), vec![]));
+
+    // The synchronous resolver cannot await the calls.
+    let pattern = bundle.get_message("welcome").unwrap().value().unwrap();
+    let mut args = FluentArgs::new();
+    args.set("id", 7);
+    let mut errors = vec![];
+    let value = bundle.format_pattern(pattern, Some(&args), &mut errors);
+    assert_eq!(value, "Hi {USERNAME()}, {USERNAME()}!");
+    assert!(matches!(
+        &errors[0],
+        FluentError::ResolverError(ResolverError::Function { id, .. }) if id == "USERNAME"
+    ));
+}
@@ -0,0 +1,119 @@
+use fluent_bundle::overlay::{Element, Node, Overlay};
+use fluent_bundle::{FluentBundle, FluentResource};
+use unic_langid::langid;
+
+fn link() -> Element {
+    Element::new("a")
+        .with_attribute("data-l10n-name", "link")
+        .with_attribute("href", "https://example.com")
+        .with_attribute("class", "external")
+}
+
+#[test]
+fn overlay_text_level_elements() {
+    let overlay = Overlay::parse(r#"A <em title="Note" onclick="steal()">very</em> nice<br/> day"#);
+    assert!(overlay.has_markup());
+    assert_eq!(overlay.text_content(), "A very nice day");
+    assert_eq!(
+        overlay.nodes()[1],
+        Node::Element(Element {
+            name: "em".to_string(),
+            attributes: vec![("title".to_string(), "Note".to_string())],
+            children: vec![Node::Text("very".to_string())],
+        })
+    );
+    assert_eq!(
+        overlay.to_html(&[]),
+        r#"A <em title="Note">very</em> nice<br> day"#
+    );
+}
+
+#[test]
+fn overlay_forbidden_elements() {
+    let overlay = Overlay::parse(
+        r#"<script>alert("hi")</script> <img src="x" onerror="steal()"><div>Text</div><!-- note -->"#,
+    );
+    assert!(!overlay.has_markup());
+    assert_eq!(overlay.to_html(&[]), r#"alert("hi") Text"#);
+}
+
+#[test]
+fn overlay_named_elements() {
+    let overlay = Overlay::parse(
+        r#"Click <a data-l10n-name="link" href="javascript:steal()" title="Go" class="x">here</a>."#,
+    );
+    assert_eq!(
+        overlay.to_html(&[link()]),
+        r#"Click <a data-l10n-name="link" href="https://example.com" class="external" title="Go">here</a>."#
+    );
+
+    // Without a matching source, the element is reduced to its text.
+    assert_eq!(overlay.to_html(&[]), "Click here.");
+    let button = Element::new("button").with_attribute("data-l10n-name", "link");
+    assert_eq!(overlay.to_html(&[button]), "Click here.");
+
+    // Each source is used once.
+    let overlay =
+        Overlay::parse(r#"<a data-l10n-name="link">One</a> <a data-l10n-name="link">Two</a>"#);
+    assert_eq!(
+        overlay.to_html(&[link()]),
+        r#"<a data-l10n-name="link" href="https://example.com" class="external">One</a> Two"#
+    );
+}
+
+#[test]
+fn overlay_lenient_parsing() {
+    let overlay = Overlay::parse("1 < 2 &amp; 3 &gt; 2 &#x41;&#66; &unknown; </b><strong>open");
+    assert_eq!(
+        overlay.nodes(),
+        &[
+            Node::Text("1 < 2 & 3 > 2 AB &unknown; ".to_string()),
+            Node::Element(Element {
+                name: "strong".to_string(),
+                attributes: vec![],
+                children: vec![Node::Text("open".to_string())],
+            }),
+        ]
+    );
+    assert_eq!(
+        overlay.to_html(&[]),
+        "1 &lt; 2 &amp; 3 &gt; 2 AB &amp;unknown; <strong>open</strong>"
+    );
+
+    let overlay = Overlay::parse(r#"<EM Title='a "quote"'>x</em>"#);
+    assert_eq!(
+        overlay.to_html(&[]),
+        r#"<em title="a &quot;quote&quot;">x</em>"#
+    );
+}
+
+#[test]
+fn overlay_formatted_message() {
+    let resource = FluentResource::try_new(
+        r#"terms = Read the <a data-l10n-name="terms">{ $name }</a>."#.to_string(),
+    )
+    .expect("Failed to parse an FTL string.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("Failed to add FTL resources to the bundle.");
+
+    let mut args = fluent_bundle::FluentArgs::new();
+    args.set("name", "terms of use");
+    let pattern = bundle
+        .get_message("terms")
+        .and_then(|message| message.value())
+        .expect("Message has a value.");
+    let mut errors = vec![];
+    let formatted = bundle.format_pattern(pattern, Some(&args), &mut errors);
+    assert!(errors.is_empty());
+
+    let source = Element::new("a")
+        .with_attribute("data-l10n-name", "terms")
+        .with_attribute("href", "/terms");
+    assert_eq!(
+        Overlay::parse(&formatted).to_html(&[source]),
+        r#"Read the <a data-l10n-name="terms" href="/terms">terms of use</a>."#
+    );
+}
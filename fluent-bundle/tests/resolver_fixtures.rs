@@ -342,6 +342,9 @@ fn test_errors(errors: &[FluentError], reference: Option<&[TestError]>) {
             FluentError::Overriding { .. } => {
                 assert_eq!(reference.error_type, "Overriding");
             }
+            FluentError::TermSignature(_) => {
+                assert_eq!(reference.error_type, "TermSignature");
+            }
         }
     }
 }
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue, SerializeError};
+use serde::Serialize;
+use unic_langid::langid;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Invitation {
+    user_name: String,
+    guest_count: u32,
+    ratio: f64,
+    admin: bool,
+    gender: Gender,
+    folder: Option<String>,
+    guests: Vec<&'static str>,
+    #[serde(skip)]
+    _secret: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Gender {
+    Feminine,
+}
+
+#[test]
+fn args_from_struct() {
+    let invitation = Invitation {
+        user_name: "Anna".to_string(),
+        guest_count: 2,
+        ratio: 0.5,
+        admin: true,
+        gender: Gender::Feminine,
+        folder: None,
+        guests: vec!["Bob", "Carol"],
+        _secret: "hunter2".to_string(),
+    };
+    let args = FluentArgs::from_serialize(&invitation).expect("Failed to serialize.");
+
+    assert_eq!(args.get_str("userName"), Some("Anna"));
+    assert_eq!(args.get_number("guestCount"), Some(2.0));
+    assert_eq!(args.get_number("ratio"), Some(0.5));
+    assert_eq!(args.get_str("admin"), Some("true"));
+    assert_eq!(args.get_str("gender"), Some("feminine"));
+    assert!(matches!(args.get("folder"), Some(FluentValue::None)));
+    assert_eq!(
+        args.get("guests"),
+        Some(&FluentValue::List(vec!["Bob".into(), "Carol".into()]))
+    );
+    assert_eq!(args.get("_secret"), None);
+
+    let resource = FluentResource::try_new(
+        r#"invited = { $userName } invited { $guests } ({ $guestCount })"#.to_string(),
+    )
+    .expect("Failed to parse an FTL string.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("Failed to add FTL resources to the bundle.");
+    let pattern = bundle
+        .get_message("invited")
+        .and_then(|message| message.value())
+        .expect("Message has a value.");
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(pattern, Some(&args), &mut errors),
+        "Anna invited Bob and Carol (2)"
+    );
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn args_from_map() {
+    let mut map = BTreeMap::new();
+    map.insert("name", "Anna");
+    map.insert("title", "Dr.");
+    let args = FluentArgs::from_serialize(&map).expect("Failed to serialize.");
+    assert_eq!(args.get_str("name"), Some("Anna"));
+    assert_eq!(args.get_str("title"), Some("Dr."));
+
+    let mut map = BTreeMap::new();
+    map.insert(1, "one");
+    assert_eq!(
+        FluentArgs::from_serialize(&map),
+        Err(SerializeError::KeyNotString)
+    );
+}
+
+#[test]
+fn args_from_unsupported_shapes() {
+    #[derive(Serialize)]
+    struct Address {
+        city: &'static str,
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        name: &'static str,
+        address: Address,
+    }
+
+    let user = User {
+        name: "Anna",
+        address: Address { city: "Berlin" },
+    };
+    let err = FluentArgs::from_serialize(&user).unwrap_err();
+    assert_eq!(
+        err,
+        SerializeError::Unsupported {
+            key: "address".to_string(),
+            kind: "struct",
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Argument `address` is an unsupported struct"
+    );
+
+    assert_eq!(
+        FluentArgs::from_serialize(&5),
+        Err(SerializeError::NotAStruct)
+    );
+    assert_eq!(
+        FluentArgs::from_serialize(&vec!["Anna"]),
+        Err(SerializeError::NotAStruct)
+    );
+}
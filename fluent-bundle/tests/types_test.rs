@@ -1,6 +1,7 @@
 use fluent_bundle::resolver::Scope;
 use fluent_bundle::types::{
-    FluentNumber, FluentNumberCurrencyDisplayStyle, FluentNumberOptions, FluentNumberStyle,
+    FluentDateTime, FluentDateTimeOptions, FluentNumber, FluentNumberCurrencyDisplayStyle,
+    FluentNumberOptions, FluentNumberStyle, NumberSymbols,
 };
 use fluent_bundle::FluentArgs;
 use fluent_bundle::FluentBundle;
@@ -154,3 +155,167 @@ fn fluent_number_to_operands() {
         }
     );
 }
+
+#[test]
+fn fluent_number_exact_digits() {
+    let id = FluentNumber::from(9_007_199_254_740_993_u64);
+    assert_eq!(id.exact(), Some("9007199254740993"));
+    assert_eq!(id.as_string(), "9007199254740993");
+    assert_eq!(u64::from(&id), 9_007_199_254_740_993);
+    assert_ne!(id, FluentNumber::from(9_007_199_254_740_992_u64));
+    assert_eq!(FluentNumber::from(9_007_199_254_740_992_u64).exact(), None);
+
+    let amount: FluentNumber = "-1234567890123456.70".parse().unwrap();
+    assert_eq!(amount.exact(), Some("-1234567890123456.7"));
+    assert_eq!(amount.as_string(), "-1234567890123456.70");
+    assert_eq!(
+        FluentValue::try_number("-1234567890123456.70"),
+        FluentValue::Number(amount)
+    );
+    assert_eq!("0.10".parse::<FluentNumber>().unwrap().exact(), None);
+
+    let operands: PluralOperands =
+        (&"12345678901234567891".parse::<FluentNumber>().unwrap()).into();
+    assert_eq!(operands.i, 12_345_678_901_234_567_891);
+
+    let res = FluentResource::try_new(
+        r#"
+id = { $id }
+match = { $id ->
+    [12345678901234567891] exact
+   *[other] other
+}
+percent = { NUMBER($ratio, style: "percent") }
+"#
+        .to_string(),
+    )
+    .unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(res).unwrap();
+    bundle.add_builtins().unwrap();
+    let mut args = FluentArgs::new();
+    args.set("id", 12_345_678_901_234_567_891_u64);
+    args.set("ratio", FluentValue::try_number("0.1234567890123456789"));
+    let format = |bundle: &FluentBundle<FluentResource>, id: &str| {
+        let pattern = bundle.get_message(id).unwrap().value().unwrap();
+        let mut errors = vec![];
+        bundle
+            .format_pattern(pattern, Some(&args), &mut errors)
+            .into_owned()
+    };
+    assert_eq!(format(&bundle, "match"), "exact");
+    #[cfg(not(feature = "builtins"))]
+    assert_eq!(format(&bundle, "id"), "12345678901234567891");
+    #[cfg(feature = "builtins")]
+    assert_eq!(format(&bundle, "id"), "12,345,678,901,234,567,891");
+
+    bundle.set_number_symbols(Some(NumberSymbols::default()));
+    assert_eq!(format(&bundle, "id"), "12,345,678,901,234,567,891");
+    assert_eq!(format(&bundle, "percent"), "12.3456789012345678900%");
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn fluent_number_from_decimal() {
+    use rust_decimal::Decimal;
+
+    let price: Decimal = "12345678901234567.90".parse().unwrap();
+    let number = FluentNumber::from(price);
+    assert_eq!(number.as_string(), "12345678901234567.90");
+    assert_eq!(Decimal::try_from(&number), Ok(price));
+    assert_eq!(
+        FluentValue::from(Decimal::new(1990, 2)),
+        FluentValue::try_number("19.90")
+    );
+}
+
+#[test]
+fn fluent_date_time() {
+    let date = |epoch, date_style: Option<&str>, time_style: Option<&str>, offset| {
+        let options = FluentDateTimeOptions {
+            date_style: date_style.map(Into::into),
+            time_style: time_style.map(Into::into),
+            time_zone_offset: offset,
+        };
+        FluentDateTime::new(epoch, options).as_string()
+    };
+
+    assert_eq!(date(0, None, None, 0), "1970-01-01T00:00:00Z");
+    assert_eq!(date(-1, None, None, 0), "1969-12-31T23:59:59.999Z");
+    assert_eq!(
+        date(951_782_400_000, Some("full"), None, 0),
+        "Tuesday, February 29, 2000"
+    );
+    assert_eq!(
+        date(1_709_647_629_000, Some("medium"), Some("long"), 3600),
+        "Mar 5, 2024, 15:07:09 +01:00"
+    );
+    assert_eq!(
+        date(
+            1_709_647_629_000,
+            Some("short"),
+            Some("short"),
+            -5 * 3600 - 1800
+        ),
+        "2024-03-05, 08:37"
+    );
+    assert_eq!(
+        date(1_709_647_629_000, None, Some("full"), 0),
+        "14:07:09 UTC"
+    );
+
+    assert_eq!(
+        FluentValue::from(FluentDateTime::from_epoch(0)),
+        FluentValue::DateTime(FluentDateTime::from_epoch(0))
+    );
+    assert_ne!(
+        FluentValue::from(FluentDateTime::from_epoch(0)),
+        FluentValue::from(0)
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn fluent_date_time_from_chrono() {
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    let utc = Utc.timestamp_millis_opt(1_709_647_629_000).unwrap();
+    assert_eq!(
+        FluentValue::from(utc),
+        FluentValue::DateTime(FluentDateTime::from_epoch(1_709_647_629_000))
+    );
+    let paris = utc.with_timezone(&FixedOffset::east_opt(3600).unwrap());
+    assert_eq!(FluentDateTime::from(paris).options.time_zone_offset, 3600);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn fluent_date_time_from_time() {
+    use time::{OffsetDateTime, UtcOffset};
+
+    let utc = OffsetDateTime::from_unix_timestamp(1_709_647_629).unwrap();
+    assert_eq!(
+        FluentValue::from(utc),
+        FluentValue::DateTime(FluentDateTime::from_epoch(1_709_647_629_000))
+    );
+    let paris = utc.to_offset(UtcOffset::from_hms(1, 0, 0).unwrap());
+    assert_eq!(FluentDateTime::from(paris).options.time_zone_offset, 3600);
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn fluent_date_time_from_jiff() {
+    use jiff::tz::{Offset, TimeZone};
+    use jiff::Timestamp;
+
+    let timestamp = Timestamp::from_millisecond(1_709_647_629_000).unwrap();
+    assert_eq!(
+        FluentValue::from(timestamp),
+        FluentValue::DateTime(FluentDateTime::from_epoch(1_709_647_629_000))
+    );
+    let zoned = timestamp.to_zoned(TimeZone::fixed(Offset::constant(-5)));
+    let date = FluentDateTime::from(&zoned);
+    assert_eq!(date.epoch, 1_709_647_629_000);
+    assert_eq!(date.options.time_zone_offset, -5 * 3600);
+}
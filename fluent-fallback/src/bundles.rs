@@ -107,6 +107,31 @@ where
         }
     }
 
+    /// Formats the attribute `attribute` of the message `id`, from the first
+    /// locale which has it.
+    ///
+    /// If no locale has the attribute, the value of the message is formatted
+    /// instead, from the first locale which has the message. Each locale
+    /// missing the attribute is reported as a
+    /// [`LocalizationError::MissingMessage`] of the `id.attribute` reference,
+    /// and the fallback to the value as one without a locale.
+    pub async fn format_attribute<'l>(
+        &'l self,
+        id: &'l str,
+        attribute: &'l str,
+        args: Option<&'l FluentArgs<'_>>,
+        errors: &mut Vec<LocalizationError>,
+    ) -> Option<Cow<'l, str>> {
+        match &self.0 {
+            BundlesInner::Iter(cache) => {
+                Self::format_attribute_from_iter(cache, id, attribute, args, errors)
+            }
+            BundlesInner::Stream(stream) => {
+                Self::format_attribute_from_stream(stream, id, attribute, args, errors).await
+            }
+        }
+    }
+
     pub fn format_value_sync<'l>(
         &'l self,
         id: &'l str,
@@ -119,6 +144,22 @@ where
         }
     }
 
+    /// See [`Bundles::format_attribute`].
+    pub fn format_attribute_sync<'l>(
+        &'l self,
+        id: &'l str,
+        attribute: &'l str,
+        args: Option<&'l FluentArgs>,
+        errors: &mut Vec<LocalizationError>,
+    ) -> Result<Option<Cow<'l, str>>, LocalizationError> {
+        match &self.0 {
+            BundlesInner::Iter(cache) => Ok(Self::format_attribute_from_iter(
+                cache, id, attribute, args, errors,
+            )),
+            BundlesInner::Stream(_) => Err(LocalizationError::SyncRequestInAsyncMode),
+        }
+    }
+
     pub fn format_values_sync<'l>(
         &'l self,
         keys: &'l [L10nKey<'l>],
@@ -193,6 +234,80 @@ macro_rules! format_value_from_inner {
     };
 }
 
+macro_rules! format_attribute_from_inner {
+    ($step:expr, $id:expr, $attribute:expr, $args:expr, $errors:expr) => {
+        // Missing attributes are reported as the message of their reference.
+        let reference = format!("{}.{}", $id, $attribute);
+        // The first value of the message, used if no locale has the attribute.
+        let mut fallback = None;
+        let mut found_message = false;
+
+        while let Some(bundle) = $step {
+            let bundle = bundle.as_ref().unwrap_or_else(|(bundle, err)| {
+                $errors.extend(err.iter().cloned().map(Into::into));
+                bundle
+            });
+
+            if let Some(msg) = bundle.get_message($id) {
+                found_message = true;
+                if let Some(attr) = msg.get_attribute($attribute) {
+                    let mut format_errors = vec![];
+                    let result = bundle.format_pattern(attr.value(), $args, &mut format_errors);
+                    if !format_errors.is_empty() {
+                        $errors.push(LocalizationError::Resolver {
+                            id: $id.to_string(),
+                            locale: bundle.locales[0].clone(),
+                            errors: format_errors,
+                        });
+                    }
+                    return Some(result);
+                }
+                $errors.push(LocalizationError::MissingMessage {
+                    id: reference.clone(),
+                    locale: Some(bundle.locales[0].clone()),
+                });
+                if fallback.is_none() {
+                    fallback = msg.value().map(|value| (bundle, value));
+                }
+            } else {
+                $errors.push(LocalizationError::MissingMessage {
+                    id: $id.to_string(),
+                    locale: Some(bundle.locales[0].clone()),
+                });
+            }
+        }
+
+        if !found_message {
+            $errors.push(LocalizationError::MissingMessage {
+                id: $id.to_string(),
+                locale: None,
+            });
+            return None;
+        }
+        $errors.push(LocalizationError::MissingMessage {
+            id: reference,
+            locale: None,
+        });
+        let Some((bundle, value)) = fallback else {
+            $errors.push(LocalizationError::MissingValue {
+                id: $id.to_string(),
+                locale: None,
+            });
+            return None;
+        };
+        let mut format_errors = vec![];
+        let result = bundle.format_pattern(value, $args, &mut format_errors);
+        if !format_errors.is_empty() {
+            $errors.push(LocalizationError::Resolver {
+                id: $id.to_string(),
+                locale: bundle.locales[0].clone(),
+                errors: format_errors,
+            });
+        }
+        return Some(result);
+    };
+}
+
 #[derive(Clone)]
 enum Value<'l> {
     Present(Cow<'l, str>),
@@ -363,6 +478,30 @@ where
         format_value_from_inner!(bundle_stream.next().await, id, args, errors);
     }
 
+    fn format_attribute_from_iter<'l>(
+        cache: &'l Cache<G::Iter, G::Resource>,
+        id: &'l str,
+        attribute: &'l str,
+        args: Option<&'l FluentArgs>,
+        errors: &mut Vec<LocalizationError>,
+    ) -> Option<Cow<'l, str>> {
+        let mut bundle_iter = cache.into_iter();
+        format_attribute_from_inner!(bundle_iter.next(), id, attribute, args, errors);
+    }
+
+    async fn format_attribute_from_stream<'l>(
+        stream: &'l AsyncCache<G::Stream, G::Resource>,
+        id: &'l str,
+        attribute: &'l str,
+        args: Option<&'l FluentArgs<'_>>,
+        errors: &mut Vec<LocalizationError>,
+    ) -> Option<Cow<'l, str>> {
+        use futures::StreamExt;
+
+        let mut bundle_stream = stream.stream();
+        format_attribute_from_inner!(bundle_stream.next().await, id, attribute, args, errors);
+    }
+
     async fn format_messages_from_stream<'l>(
         stream: &'l AsyncCache<G::Stream, G::Resource>,
         keys: &'l [L10nKey<'l>],
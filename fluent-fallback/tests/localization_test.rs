@@ -316,6 +316,64 @@ fn localization_format_value_sync_missing_errors() {
     );
 }
 
+#[test]
+fn localization_format_attribute_sync_fallback() {
+    let resource_ids: Vec<ResourceId> = vec![
+        "test.ftl".into(),
+        "test2.ftl".into(),
+        "attributes.ftl".into(),
+    ];
+
+    let locales = Locales::new(vec![langid!("pl"), langid!("en-US")]);
+    let res_mgr = ResourceManager;
+    let mut errors = vec![];
+
+    let loc = Localization::with_env(resource_ids, true, locales, res_mgr);
+    let bundles = loc.bundles();
+
+    let value = bundles
+        .format_attribute_sync("message-2", "attr1", None, &mut errors)
+        .unwrap();
+    assert_eq!(value, Some(Cow::Borrowed("Message 2 Attribute [pl]")));
+    assert!(errors.is_empty());
+
+    let value = bundles
+        .format_attribute_sync("message-5", "title", None, &mut errors)
+        .unwrap();
+    assert_eq!(value, Some(Cow::Borrowed("Message 5 Title [en]")));
+    assert_eq!(
+        errors,
+        vec![LocalizationError::MissingMessage {
+            id: "message-5.title".to_string(),
+            locale: Some(langid!("pl"))
+        }]
+    );
+
+    errors.clear();
+
+    let value = bundles
+        .format_attribute_sync("message-5", "missing", None, &mut errors)
+        .unwrap();
+    assert_eq!(value, Some(Cow::Borrowed("Message 5 Value [pl]")));
+    assert_eq!(
+        errors,
+        vec![
+            LocalizationError::MissingMessage {
+                id: "message-5.missing".to_string(),
+                locale: Some(langid!("pl"))
+            },
+            LocalizationError::MissingMessage {
+                id: "message-5.missing".to_string(),
+                locale: Some(langid!("en-US"))
+            },
+            LocalizationError::MissingMessage {
+                id: "message-5.missing".to_string(),
+                locale: None
+            },
+        ]
+    );
+}
+
 #[test]
 fn localization_format_values_sync_missing_errors() {
     let resource_ids: Vec<ResourceId> = vec!["test.ftl".into(), "test2.ftl".into()];
@@ -0,0 +1,2 @@
+message-5 = Message 5 Value [en]
+    .title = Message 5 Title [en]
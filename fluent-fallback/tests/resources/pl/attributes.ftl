@@ -0,0 +1 @@
+message-5 = Message 5 Value [pl]
@@ -0,0 +1,122 @@
+#ifndef FLUENT_H
+#define FLUENT_H
+
+/* Generated with cbindgen, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A map of variables passed to [`fluent_bundle_format`].
+ */
+typedef struct FluentArgs FluentArgs;
+
+/**
+ * A collection of localization messages for a single locale, along with the
+ * errors produced by the most recent calls.
+ */
+typedef struct FluentBundle FluentBundle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a new bundle for `locales_len` locales, given as BCP 47 language tags.
+ *
+ * The builtin functions, such as `NUMBER`, are registered. Returns `NULL` if any
+ * of the locales is invalid.
+ */
+FluentBundle *fluent_bundle_new(const char *const *locales, size_t locales_len);
+
+/**
+ * Release a bundle created with [`fluent_bundle_new`].
+ */
+void fluent_bundle_free(FluentBundle *bundle);
+
+/**
+ * Enable or disable Unicode bidi isolation of placeables.
+ */
+void fluent_bundle_set_use_isolating(FluentBundle *bundle, bool value);
+
+/**
+ * Parse `source` and add its messages and terms to the bundle.
+ *
+ * Existing entries are kept, unless `allow_overrides` is `true`. Returns `true`
+ * if no errors occurred; otherwise the parser and overriding errors are
+ * appended to the bundle errors and `false` is returned. Entries which parsed
+ * successfully are added in either case.
+ */
+bool fluent_bundle_add_resource(FluentBundle *bundle, const char *source, bool allow_overrides);
+
+/**
+ * Returns `true` if the bundle contains the message `id`.
+ */
+bool fluent_bundle_has_message(const FluentBundle *bundle, const char *id);
+
+/**
+ * Format the message `id`, or its attribute if `id` has the form
+ * `message.attribute`, with the optional `args`.
+ *
+ * Returns a newly allocated string, to be released with [`fluent_string_free`],
+ * or `NULL` if the message, attribute or value does not exist. Resolution
+ * errors are appended to the bundle errors; the returned string then contains
+ * the best-effort fallback, as in [`fluent_bundle::FluentBundle::format_pattern`].
+ */
+char *fluent_bundle_format(FluentBundle *bundle, const char *id, const FluentArgs *args);
+
+/**
+ * Returns the number of errors collected by the bundle.
+ */
+size_t fluent_bundle_error_count(const FluentBundle *bundle);
+
+/**
+ * Returns the error at `index`, or `NULL` if out of bounds.
+ *
+ * The string is owned by the bundle and remains valid until the next call to
+ * [`fluent_bundle_clear_errors`] or [`fluent_bundle_free`].
+ */
+const char *fluent_bundle_error(const FluentBundle *bundle, size_t index);
+
+/**
+ * Remove all errors collected by the bundle.
+ */
+void fluent_bundle_clear_errors(FluentBundle *bundle);
+
+/**
+ * Create a new, empty argument map.
+ */
+FluentArgs *fluent_args_new(void);
+
+/**
+ * Release an argument map created with [`fluent_args_new`].
+ */
+void fluent_args_free(FluentArgs *args);
+
+/**
+ * Set the variable `key` to the string `value`.
+ *
+ * Returns `false` if `key` or `value` is not valid UTF-8.
+ */
+bool fluent_args_set_string(FluentArgs *args, const char *key, const char *value);
+
+/**
+ * Set the variable `key` to the number `value`.
+ *
+ * Returns `false` if `key` is not valid UTF-8.
+ */
+bool fluent_args_set_number(FluentArgs *args, const char *key, double value);
+
+/**
+ * Release a string returned by [`fluent_bundle_format`].
+ */
+void fluent_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FLUENT_H */
@@ -0,0 +1,362 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! `fluent-ffi` exposes [`fluent-bundle`](fluent_bundle) through a C API, so that
+//! applications written in C, C++, Swift, Python and other languages which can call
+//! C functions share the exact formatting behavior of the Rust implementation.
+//!
+//! The header, `include/fluent.h`, is generated with
+//! [cbindgen](https://github.com/mozilla/cbindgen) from this crate.
+//!
+//! # Example
+//!
+//! ```c
+//! #include "fluent.h"
+//!
+//! const char *locales[] = { "en-US" };
+//! FluentBundle *bundle = fluent_bundle_new(locales, 1);
+//! fluent_bundle_set_use_isolating(bundle, false);
+//! fluent_bundle_add_resource(bundle, "hello = Hello, { $name }!", false);
+//!
+//! FluentArgs *args = fluent_args_new();
+//! fluent_args_set_string(args, "name", "World");
+//!
+//! char *value = fluent_bundle_format(bundle, "hello", args);
+//! // value == "Hello, World!"
+//!
+//! fluent_string_free(value);
+//! fluent_args_free(args);
+//! fluent_bundle_free(bundle);
+//! ```
+//!
+//! Kotlin and Swift bindings are available through UniFFI with the `uniffi`
+//! feature, see the `mobile` module.
+//!
+//! # Ownership
+//!
+//! Every pointer returned by a `*_new` function must be released with the matching
+//! `*_free` function, and every string returned by [`fluent_bundle_format`] with
+//! [`fluent_string_free`]. Strings passed in are borrowed for the duration of the
+//! call and must be valid, nul-terminated UTF-8.
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use fluent_bundle::{FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+/// A collection of localization messages for a single locale, along with the
+/// errors produced by the most recent calls.
+pub struct FluentBundle {
+    inner: fluent_bundle::FluentBundle<FluentResource>,
+    errors: Vec<CString>,
+}
+
+impl FluentBundle {
+    fn push_errors<E: ToString>(&mut self, errors: impl IntoIterator<Item = E>) {
+        self.errors.extend(
+            errors
+                .into_iter()
+                .filter_map(|err| CString::new(err.to_string()).ok()),
+        );
+    }
+}
+
+/// A map of variables passed to [`fluent_bundle_format`].
+pub struct FluentArgs(fluent_bundle::FluentArgs<'static>);
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Create a new bundle for `locales_len` locales, given as BCP 47 language tags.
+///
+/// The builtin functions, such as `NUMBER`, are registered. Returns `NULL` if any
+/// of the locales is invalid.
+///
+/// # Safety
+///
+/// `locales` must point to `locales_len` valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_new(
+    locales: *const *const c_char,
+    locales_len: usize,
+) -> *mut FluentBundle {
+    let mut langids = Vec::with_capacity(locales_len);
+    for i in 0..locales_len {
+        match to_str(*locales.add(i)).and_then(|s| s.parse::<LanguageIdentifier>().ok()) {
+            Some(langid) => langids.push(langid),
+            None => return ptr::null_mut(),
+        }
+    }
+
+    let mut inner = fluent_bundle::FluentBundle::new(langids);
+    inner
+        .add_builtins()
+        .expect("Builtins are added to an empty bundle");
+
+    Box::into_raw(Box::new(FluentBundle {
+        inner,
+        errors: vec![],
+    }))
+}
+
+/// Release a bundle created with [`fluent_bundle_new`].
+///
+/// # Safety
+///
+/// `bundle` must be `NULL` or a pointer returned by [`fluent_bundle_new`] which
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_free(bundle: *mut FluentBundle) {
+    if !bundle.is_null() {
+        drop(Box::from_raw(bundle));
+    }
+}
+
+/// Enable or disable Unicode bidi isolation of placeables.
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_set_use_isolating(bundle: *mut FluentBundle, value: bool) {
+    let bundle = &mut *bundle;
+    bundle.inner.set_use_isolating(value);
+}
+
+/// Parse `source` and add its messages and terms to the bundle.
+///
+/// Existing entries are kept, unless `allow_overrides` is `true`. Returns `true`
+/// if no errors occurred; otherwise the parser and overriding errors are
+/// appended to the bundle errors and `false` is returned. Entries which parsed
+/// successfully are added in either case.
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`] and
+/// `source` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_add_resource(
+    bundle: *mut FluentBundle,
+    source: *const c_char,
+    allow_overrides: bool,
+) -> bool {
+    let bundle = &mut *bundle;
+    let error_count = bundle.errors.len();
+    let Some(source) = to_str(source) else {
+        bundle.push_errors(["Resource source is not valid UTF-8"]);
+        return false;
+    };
+
+    let res = match FluentResource::try_new(source.to_string()) {
+        Ok(res) => res,
+        Err((res, errors)) => {
+            bundle.push_errors(errors);
+            res
+        }
+    };
+
+    if allow_overrides {
+        bundle.inner.add_resource_overriding(res);
+    } else if let Err(errors) = bundle.inner.add_resource(res) {
+        bundle.push_errors(errors);
+    }
+    bundle.errors.len() == error_count
+}
+
+/// Returns `true` if the bundle contains the message `id`.
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`] and `id`
+/// a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_has_message(
+    bundle: *const FluentBundle,
+    id: *const c_char,
+) -> bool {
+    let bundle = &*bundle;
+    to_str(id).map_or(false, |id| bundle.inner.has_message(id))
+}
+
+/// Format the message `id`, or its attribute if `id` has the form
+/// `message.attribute`, with the optional `args`.
+///
+/// Returns a newly allocated string, to be released with [`fluent_string_free`],
+/// or `NULL` if the message, attribute or value does not exist. Resolution
+/// errors are appended to the bundle errors; the returned string then contains
+/// the best-effort fallback, as in [`fluent_bundle::FluentBundle::format_pattern`].
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`], `id` a
+/// valid C string and `args` `NULL` or a valid pointer returned by
+/// [`fluent_args_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_format(
+    bundle: *mut FluentBundle,
+    id: *const c_char,
+    args: *const FluentArgs,
+) -> *mut c_char {
+    let bundle = &mut *bundle;
+    let Some(id) = to_str(id) else {
+        return ptr::null_mut();
+    };
+    let (id, attribute) = match id.split_once('.') {
+        Some((id, attribute)) => (id, Some(attribute)),
+        None => (id, None),
+    };
+    let args = args.as_ref().map(|args| &args.0);
+
+    let mut errors = vec![];
+    let value = {
+        let Some(msg) = bundle.inner.get_message(id) else {
+            return ptr::null_mut();
+        };
+        let pattern = match attribute {
+            Some(attribute) => msg.get_attribute(attribute).map(|attr| attr.value()),
+            None => msg.value(),
+        };
+        let Some(pattern) = pattern else {
+            return ptr::null_mut();
+        };
+        bundle
+            .inner
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned()
+    };
+    bundle.push_errors(errors);
+
+    // Interior nul bytes cannot be represented in a C string.
+    CString::new(value.replace('\0', "")).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Returns the number of errors collected by the bundle.
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_error_count(bundle: *const FluentBundle) -> usize {
+    let bundle = &*bundle;
+    bundle.errors.len()
+}
+
+/// Returns the error at `index`, or `NULL` if out of bounds.
+///
+/// The string is owned by the bundle and remains valid until the next call to
+/// [`fluent_bundle_clear_errors`] or [`fluent_bundle_free`].
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_error(
+    bundle: *const FluentBundle,
+    index: usize,
+) -> *const c_char {
+    let bundle = &*bundle;
+    bundle
+        .errors
+        .get(index)
+        .map_or(ptr::null(), |err| err.as_ptr())
+}
+
+/// Remove all errors collected by the bundle.
+///
+/// # Safety
+///
+/// `bundle` must be a valid pointer returned by [`fluent_bundle_new`].
+#[no_mangle]
+pub unsafe extern "C" fn fluent_bundle_clear_errors(bundle: *mut FluentBundle) {
+    let bundle = &mut *bundle;
+    bundle.errors.clear();
+}
+
+/// Create a new, empty argument map.
+#[no_mangle]
+pub extern "C" fn fluent_args_new() -> *mut FluentArgs {
+    Box::into_raw(Box::new(FluentArgs(fluent_bundle::FluentArgs::new())))
+}
+
+/// Release an argument map created with [`fluent_args_new`].
+///
+/// # Safety
+///
+/// `args` must be `NULL` or a pointer returned by [`fluent_args_new`] which has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_args_free(args: *mut FluentArgs) {
+    if !args.is_null() {
+        drop(Box::from_raw(args));
+    }
+}
+
+unsafe fn set_arg(args: *mut FluentArgs, key: *const c_char, value: FluentValue<'static>) -> bool {
+    match to_str(key) {
+        Some(key) => {
+            let args = &mut *args;
+            args.0.set(key.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Set the variable `key` to the string `value`.
+///
+/// Returns `false` if `key` or `value` is not valid UTF-8.
+///
+/// # Safety
+///
+/// `args` must be a valid pointer returned by [`fluent_args_new`], and `key`
+/// and `value` valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_args_set_string(
+    args: *mut FluentArgs,
+    key: *const c_char,
+    value: *const c_char,
+) -> bool {
+    match to_str(value) {
+        Some(value) => set_arg(args, key, value.to_string().into()),
+        None => false,
+    }
+}
+
+/// Set the variable `key` to the number `value`.
+///
+/// Returns `false` if `key` is not valid UTF-8.
+///
+/// # Safety
+///
+/// `args` must be a valid pointer returned by [`fluent_args_new`] and `key` a
+/// valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_args_set_number(
+    args: *mut FluentArgs,
+    key: *const c_char,
+    value: f64,
+) -> bool {
+    set_arg(args, key, value.into())
+}
+
+/// Release a string returned by [`fluent_bundle_format`].
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a pointer returned by [`fluent_bundle_format`] which has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fluent_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,149 @@
+//! [UniFFI](https://mozilla.github.io/uniffi-rs/) bindings, for Kotlin and Swift.
+//!
+//! Enabled with the `uniffi` feature. The foreign bindings are generated from the
+//! compiled library with the bundled `uniffi-bindgen` binary:
+//!
+//! ```sh
+//! cargo build --release --features uniffi
+//! cargo run --features uniffi-cli --bin uniffi-bindgen -- \
+//!     generate --library target/release/libfluent_ffi.so --language kotlin --out-dir out
+//! ```
+//!
+//! In Kotlin, the resulting API reads:
+//!
+//! ```kotlin
+//! val bundle = FluentBundle(listOf("en-US"))
+//! bundle.addResource("hello = Hello, { \$name }!", false)
+//! val message = bundle.format("hello", mapOf("name" to FluentArg.String("World")))
+//! ```
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use fluent_bundle::concurrent;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+/// Errors raised across the foreign language boundary.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum FluentBundleError {
+    #[error("Invalid locale: {locale}")]
+    InvalidLocale { locale: String },
+}
+
+/// A variable passed to [`FluentBundle::format`].
+#[derive(uniffi::Enum)]
+pub enum FluentArg {
+    String { value: String },
+    Number { value: f64 },
+}
+
+/// The outcome of [`FluentBundle::format`].
+#[derive(uniffi::Record)]
+pub struct FormattedMessage {
+    /// The formatted value, including fallbacks for parts which failed to resolve.
+    pub value: String,
+    /// The resolution errors, if any.
+    pub errors: Vec<String>,
+}
+
+/// A collection of localization messages for a single locale.
+#[derive(uniffi::Object)]
+pub struct FluentBundle {
+    inner: Mutex<concurrent::FluentBundle<FluentResource>>,
+}
+
+#[uniffi::export]
+impl FluentBundle {
+    /// Create a new bundle for `locales`, given as BCP 47 language tags, with
+    /// the builtin functions registered.
+    #[uniffi::constructor]
+    pub fn new(locales: Vec<String>) -> Result<Arc<Self>, FluentBundleError> {
+        let locales = locales
+            .into_iter()
+            .map(|locale| {
+                locale
+                    .parse::<LanguageIdentifier>()
+                    .map_err(|_| FluentBundleError::InvalidLocale { locale })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut inner = concurrent::FluentBundle::new_concurrent(locales);
+        inner
+            .add_builtins()
+            .expect("Builtins are added to an empty bundle");
+        Ok(Arc::new(Self {
+            inner: Mutex::new(inner),
+        }))
+    }
+
+    /// Parse `source` and add its messages and terms to the bundle.
+    ///
+    /// Existing entries are kept, unless `allow_overrides` is `true`. Returns the
+    /// parser and overriding errors; entries which parsed successfully are added
+    /// in either case.
+    pub fn add_resource(&self, source: String, allow_overrides: bool) -> Vec<String> {
+        let mut errors = vec![];
+        let res = match FluentResource::try_new(source) {
+            Ok(res) => res,
+            Err((res, errs)) => {
+                errors.extend(errs.iter().map(ToString::to_string));
+                res
+            }
+        };
+
+        let mut bundle = self.inner.lock().unwrap();
+        if allow_overrides {
+            bundle.add_resource_overriding(res);
+        } else if let Err(errs) = bundle.add_resource(res) {
+            errors.extend(errs.iter().map(ToString::to_string));
+        }
+        errors
+    }
+
+    /// Returns `true` if the bundle contains the message `id`.
+    pub fn has_message(&self, id: String) -> bool {
+        self.inner.lock().unwrap().has_message(&id)
+    }
+
+    /// Enable or disable Unicode bidi isolation of placeables.
+    pub fn set_use_isolating(&self, value: bool) {
+        self.inner.lock().unwrap().set_use_isolating(value);
+    }
+
+    /// Format the message `id`, or its attribute if `id` has the form
+    /// `message.attribute`.
+    ///
+    /// Returns `None` if the message, attribute or value does not exist.
+    pub fn format(&self, id: String, args: HashMap<String, FluentArg>) -> Option<FormattedMessage> {
+        let (id, attribute) = match id.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (id.as_str(), None),
+        };
+        let args = args
+            .into_iter()
+            .map(|(key, arg)| {
+                let value: FluentValue = match arg {
+                    FluentArg::String { value } => value.into(),
+                    FluentArg::Number { value } => value.into(),
+                };
+                (key, value)
+            })
+            .collect::<FluentArgs>();
+
+        let bundle = self.inner.lock().unwrap();
+        let msg = bundle.get_message(id)?;
+        let pattern = match attribute {
+            Some(attribute) => msg.get_attribute(attribute)?.value(),
+            None => msg.value()?,
+        };
+
+        let mut errors = vec![];
+        let value = bundle
+            .format_pattern(pattern, Some(&args), &mut errors)
+            .into_owned();
+        Some(FormattedMessage {
+            value,
+            errors: errors.iter().map(ToString::to_string).collect(),
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use fluent_ffi::*;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+unsafe fn take_string(s: *mut c_char) -> String {
+    assert!(!s.is_null());
+    let value = CStr::from_ptr(s).to_str().unwrap().to_string();
+    fluent_string_free(s);
+    value
+}
+
+unsafe fn new_bundle() -> *mut FluentBundle {
+    let en_us = c("en-US");
+    let locales = [en_us.as_ptr()];
+    let bundle = fluent_bundle_new(locales.as_ptr(), locales.len());
+    assert!(!bundle.is_null());
+    fluent_bundle_set_use_isolating(bundle, false);
+    bundle
+}
+
+#[test]
+fn format_with_args() {
+    unsafe {
+        let bundle = new_bundle();
+        assert!(fluent_bundle_add_resource(
+            bundle,
+            c("hello = Hello, { $name }! You have { $count } messages.\n    .title = Greeting")
+                .as_ptr(),
+            false,
+        ));
+        assert!(fluent_bundle_has_message(bundle, c("hello").as_ptr()));
+
+        let args = fluent_args_new();
+        assert!(fluent_args_set_string(
+            args,
+            c("name").as_ptr(),
+            c("John").as_ptr()
+        ));
+        assert!(fluent_args_set_number(args, c("count").as_ptr(), 5.0));
+
+        let value = fluent_bundle_format(bundle, c("hello").as_ptr(), args);
+        assert_eq!(take_string(value), "Hello, John! You have 5 messages.");
+
+        let value = fluent_bundle_format(bundle, c("hello.title").as_ptr(), ptr::null());
+        assert_eq!(take_string(value), "Greeting");
+
+        assert!(fluent_bundle_format(bundle, c("missing").as_ptr(), ptr::null()).is_null());
+        assert!(fluent_bundle_format(bundle, c("hello.missing").as_ptr(), ptr::null()).is_null());
+        assert_eq!(fluent_bundle_error_count(bundle), 0);
+
+        fluent_args_free(args);
+        fluent_bundle_free(bundle);
+    }
+}
+
+#[test]
+fn collect_errors() {
+    unsafe {
+        let bundle = new_bundle();
+        assert!(fluent_bundle_add_resource(
+            bundle,
+            c("key = Value").as_ptr(),
+            false
+        ));
+        assert!(!fluent_bundle_add_resource(
+            bundle,
+            c("key = Value 2").as_ptr(),
+            false
+        ));
+        assert_eq!(fluent_bundle_error_count(bundle), 1);
+
+        let value = fluent_bundle_format(bundle, c("key").as_ptr(), ptr::null());
+        assert_eq!(take_string(value), "Value");
+
+        assert!(fluent_bundle_add_resource(
+            bundle,
+            c("key = Value 3").as_ptr(),
+            true
+        ));
+        let value = fluent_bundle_format(bundle, c("key").as_ptr(), ptr::null());
+        assert_eq!(take_string(value), "Value 3");
+
+        assert!(fluent_bundle_add_resource(
+            bundle,
+            c("vars = { $missing }").as_ptr(),
+            false
+        ));
+        let value = fluent_bundle_format(bundle, c("vars").as_ptr(), ptr::null());
+        assert_eq!(take_string(value), "{$missing}");
+        assert_eq!(fluent_bundle_error_count(bundle), 2);
+        assert!(!fluent_bundle_error(bundle, 1).is_null());
+        assert!(fluent_bundle_error(bundle, 2).is_null());
+
+        fluent_bundle_clear_errors(bundle);
+        assert_eq!(fluent_bundle_error_count(bundle), 0);
+
+        fluent_bundle_free(bundle);
+    }
+}
+
+#[test]
+fn invalid_locale() {
+    unsafe {
+        let invalid = c("not a locale!");
+        let locales = [invalid.as_ptr()];
+        assert!(fluent_bundle_new(locales.as_ptr(), locales.len()).is_null());
+    }
+}
@@ -0,0 +1,156 @@
+//! The `FromFluentArgs` and `FromFluentValue` derives.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, LitStr};
+
+/// The options of the `#[fluent(...)]` attribute of a field or a variant.
+#[derive(Default)]
+struct FluentAttr {
+    rename: Option<LitStr>,
+    skip: bool,
+}
+
+impl FluentAttr {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("fluent")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    result.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename` or `skip`"))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the name set by `rename`, or else `ident` in camelCase.
+    fn name(&self, ident: &Ident) -> String {
+        match &self.rename {
+            Some(rename) => rename.value(),
+            None => camel_case(&ident.unraw().to_string()),
+        }
+    }
+}
+
+/// Converts a field name such as `minimum_fraction_digits`, or a variant name
+/// such as `TwoDigit`, to camelCase, as in `minimumFractionDigits`.
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = !result.is_empty();
+        } else if result.is_empty() {
+            result.extend(c.to_lowercase());
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+pub(crate) fn derive_from_fluent_args(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`FromFluentArgs` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "`FromFluentArgs` can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut merges = vec![];
+    for field in &fields.named {
+        let attr = FluentAttr::parse(&field.attrs)?;
+        if attr.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("Named fields have identifiers");
+        let name = attr.name(ident);
+        merges.push(quote! {
+            if let ::std::option::Option::Some(value) = args
+                .get(#name)
+                .and_then(::fluent_bundle::FromFluentValue::from_fluent_value)
+            {
+                self.#ident = value;
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::fluent_bundle::FromFluentArgs for #ident #ty_generics #where_clause {
+            fn merge(&mut self, args: &::fluent_bundle::FluentArgs) {
+                #(#merges)*
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<&::fluent_bundle::FluentArgs<'_>>
+            for #ident #ty_generics #where_clause
+        {
+            fn from(args: &::fluent_bundle::FluentArgs<'_>) -> Self {
+                <Self as ::fluent_bundle::FromFluentArgs>::from_args(args)
+            }
+        }
+    })
+}
+
+pub(crate) fn derive_from_fluent_value(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`FromFluentValue` can only be derived for enums",
+        ));
+    };
+
+    let mut arms = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`FromFluentValue` can only be derived for enums of unit variants",
+            ));
+        }
+        let attr = FluentAttr::parse(&variant.attrs)?;
+        if attr.skip {
+            continue;
+        }
+        let ident = &variant.ident;
+        let name = attr.name(ident);
+        arms.push(quote!(#name => ::std::option::Option::Some(Self::#ident),));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::fluent_bundle::FromFluentValue for #ident #ty_generics #where_clause {
+            fn from_fluent_value(
+                value: &::fluent_bundle::FluentValue,
+            ) -> ::std::option::Option<Self> {
+                let ::fluent_bundle::FluentValue::String(value) = value else {
+                    return ::std::option::Option::None;
+                };
+                match ::std::convert::AsRef::<str>::as_ref(value) {
+                    #(#arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    })
+}
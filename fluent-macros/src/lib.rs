@@ -0,0 +1,373 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! `fluent-macros` provides procedural macros for [`fluent-bundle`](https://docs.rs/fluent-bundle).
+//! They are re-exported by `fluent-bundle` with the `macros` feature, which is
+//! the intended way of using them.
+//!
+//! [`include_ftl!`] parses an FTL file at compile time and expands to a
+//! [`FluentResource`](https://docs.rs/fluent-bundle/latest/fluent_bundle/struct.FluentResource.html)
+//! built directly from its AST. The resource does not need to be parsed at
+//! runtime, and the binary contains the FTL source once, since the slices of
+//! the AST borrow from it.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_bundle::{include_ftl, FluentBundle, FluentResource};
+//! use unic_langid::langid;
+//!
+//! let res: FluentResource = include_ftl!("tests/fixtures/en-US/main.ftl");
+//!
+//! let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+//! bundle.set_use_isolating(false);
+//! bundle
+//!     .add_resource(res)
+//!     .expect("Failed to add FTL resources to the bundle.");
+//!
+//! let msg = bundle.get_message("hello-world")
+//!     .expect("Message doesn't exist.");
+//! let mut errors = vec![];
+//! let pattern = msg.value().expect("Message has no value.");
+//! assert_eq!(
+//!     bundle.format_pattern(pattern, None, &mut errors),
+//!     "Hello, world!"
+//! );
+//! ```
+//!
+//! [`FromFluentArgs`](macro@FromFluentArgs) and [`FromFluentValue`](macro@FromFluentValue)
+//! derive the conversions of the named arguments of custom functions into
+//! option structs, see
+//! [`FromFluentArgs`](https://docs.rs/fluent-bundle/latest/fluent_bundle/trait.FromFluentArgs.html).
+use std::ops::Range;
+use std::path::PathBuf;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{self, ParserError};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+mod args;
+
+/// Parse an FTL file at compile time into a `FluentResource`.
+///
+/// The path is resolved relative to the `CARGO_MANIFEST_DIR` of the crate
+/// invoking the macro. Any syntax error is reported as a compile error, and
+/// the crate is rebuilt whenever the file changes.
+#[proc_macro]
+pub fn include_ftl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match expand(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive `FromFluentArgs` and `From<&FluentArgs>` for a struct of options,
+/// each read from the named argument called after the field in camelCase.
+///
+/// The `#[fluent(rename = "...")]` attribute of a field sets the name of its
+/// argument, and `#[fluent(skip)]` leaves it unchanged.
+#[proc_macro_derive(FromFluentArgs, attributes(fluent))]
+pub fn derive_from_fluent_args(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match args::derive_from_fluent_args(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive `FromFluentValue` for an enum of unit variants, read from strings
+/// which are the names of the variants in camelCase.
+///
+/// The `#[fluent(rename = "...")]` attribute of a variant sets the string it
+/// is read from, and `#[fluent(skip)]` excludes it.
+#[proc_macro_derive(FromFluentValue, attributes(fluent))]
+pub fn derive_from_fluent_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match args::derive_from_fluent_value(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(path: &LitStr) -> syn::Result<TokenStream> {
+    let mut full_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    full_path.push(path.value());
+
+    let source = std::fs::read_to_string(&full_path).map_err(|err| {
+        syn::Error::new(
+            path.span(),
+            format!("Failed to read {}: {}", full_path.display(), err),
+        )
+    })?;
+    let res = parser::parse_runtime(source.as_str()).map_err(|(_, errors)| {
+        let mut errors = errors.iter().map(|err| parser_error(path, &source, err));
+        let mut first = errors.next().expect("Parser reported an empty error list");
+        first.extend(errors);
+        first
+    })?;
+
+    // The parser only returns slices of the source.
+    let res = res.map_source(|slice| {
+        let start = slice.as_ptr() as usize - source.as_ptr() as usize;
+        SourceSlice(start..start + slice.len())
+    });
+
+    let full_path = full_path.to_string_lossy();
+    let body = res.body.iter().map(entry);
+    Ok(quote! {
+        {
+            // Track the file, so that the crate is rebuilt when it changes.
+            const _: &[u8] = include_bytes!(#full_path);
+            let source: &'static str = #source;
+            ::fluent_bundle::FluentResource::from_static(
+                source,
+                ::fluent_bundle::__private::ast::Resource {
+                    body: ::std::vec![#(#body),*],
+                },
+            )
+        }
+    })
+}
+
+/// A slice of the source, which is emitted as a slice of the embedded source
+/// rather than as a copy, so that the AST borrows from the source like the one
+/// of a parsed resource does.
+struct SourceSlice(Range<usize>);
+
+impl ToTokens for SourceSlice {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (start, end) = (self.0.start, self.0.end);
+        tokens.extend(quote!(&source[#start..#end]));
+    }
+}
+
+fn parser_error(path: &LitStr, source: &str, err: &ParserError) -> syn::Error {
+    let line = source[..err.pos.start].matches('\n').count() + 1;
+    syn::Error::new(
+        path.span(),
+        format!("{}:{}: {}", path.value(), line, err.kind),
+    )
+}
+
+fn entry<S: ToTokens>(entry: &ast::Entry<S>) -> TokenStream {
+    match entry {
+        ast::Entry::Message(msg) => {
+            let msg = message(msg);
+            quote!(::fluent_bundle::__private::ast::Entry::Message(#msg))
+        }
+        ast::Entry::Term(term) => {
+            let term = self::term(term);
+            quote!(::fluent_bundle::__private::ast::Entry::Term(#term))
+        }
+        ast::Entry::Comment(c) => {
+            let c = comment(c);
+            quote!(::fluent_bundle::__private::ast::Entry::Comment(#c))
+        }
+        ast::Entry::GroupComment(c) => {
+            let c = comment(c);
+            quote!(::fluent_bundle::__private::ast::Entry::GroupComment(#c))
+        }
+        ast::Entry::ResourceComment(c) => {
+            let c = comment(c);
+            quote!(::fluent_bundle::__private::ast::Entry::ResourceComment(#c))
+        }
+        ast::Entry::Junk { content } => {
+            quote!(::fluent_bundle::__private::ast::Entry::Junk { content: #content })
+        }
+    }
+}
+
+fn message<S: ToTokens>(msg: &ast::Message<S>) -> TokenStream {
+    let id = identifier(&msg.id);
+    let value = option(msg.value.as_ref().map(pattern));
+    let attributes = msg.attributes.iter().map(attribute);
+    let comment = option(msg.comment.as_ref().map(comment));
+    quote! {
+        ::fluent_bundle::__private::ast::Message {
+            id: #id,
+            value: #value,
+            attributes: ::std::vec![#(#attributes),*],
+            comment: #comment,
+        }
+    }
+}
+
+fn term<S: ToTokens>(term: &ast::Term<S>) -> TokenStream {
+    let id = identifier(&term.id);
+    let value = pattern(&term.value);
+    let attributes = term.attributes.iter().map(attribute);
+    let comment = option(term.comment.as_ref().map(comment));
+    quote! {
+        ::fluent_bundle::__private::ast::Term {
+            id: #id,
+            value: #value,
+            attributes: ::std::vec![#(#attributes),*],
+            comment: #comment,
+        }
+    }
+}
+
+fn pattern<S: ToTokens>(pattern: &ast::Pattern<S>) -> TokenStream {
+    let elements = pattern.elements.iter().map(|element| match element {
+        ast::PatternElement::TextElement { value } => {
+            quote!(::fluent_bundle::__private::ast::PatternElement::TextElement { value: #value })
+        }
+        ast::PatternElement::Placeable { expression } => {
+            let expression = self::expression(expression);
+            quote!(::fluent_bundle::__private::ast::PatternElement::Placeable {
+                expression: #expression,
+            })
+        }
+        // Invalid placeables are only kept with `recover_placeables`.
+        _ => unreachable!("include_ftl! parses without recovering placeables"),
+    });
+    quote! {
+        ::fluent_bundle::__private::ast::Pattern {
+            elements: ::std::vec![#(#elements),*],
+        }
+    }
+}
+
+fn attribute<S: ToTokens>(attribute: &ast::Attribute<S>) -> TokenStream {
+    let id = identifier(&attribute.id);
+    let value = pattern(&attribute.value);
+    quote!(::fluent_bundle::__private::ast::Attribute { id: #id, value: #value })
+}
+
+fn identifier<S: ToTokens>(id: &ast::Identifier<S>) -> TokenStream {
+    let name = &id.name;
+    quote!(::fluent_bundle::__private::ast::Identifier { name: #name })
+}
+
+fn variant<S: ToTokens>(variant: &ast::Variant<S>) -> TokenStream {
+    let key = match &variant.key {
+        ast::VariantKey::Identifier { name } => {
+            quote!(::fluent_bundle::__private::ast::VariantKey::Identifier { name: #name })
+        }
+        ast::VariantKey::NumberLiteral { value } => {
+            quote!(::fluent_bundle::__private::ast::VariantKey::NumberLiteral { value: #value })
+        }
+    };
+    let value = pattern(&variant.value);
+    let default = variant.default;
+    quote! {
+        ::fluent_bundle::__private::ast::Variant {
+            key: #key,
+            value: #value,
+            default: #default,
+        }
+    }
+}
+
+fn comment<S: ToTokens>(comment: &ast::Comment<S>) -> TokenStream {
+    let content = &comment.content;
+    quote! {
+        ::fluent_bundle::__private::ast::Comment {
+            content: ::std::vec![#(#content),*],
+        }
+    }
+}
+
+fn call_arguments<S: ToTokens>(arguments: &ast::CallArguments<S>) -> TokenStream {
+    let positional = arguments.positional.iter().map(inline_expression);
+    let named = arguments.named.iter().map(|arg| {
+        let name = identifier(&arg.name);
+        let value = inline_expression(&arg.value);
+        quote!(::fluent_bundle::__private::ast::NamedArgument { name: #name, value: #value })
+    });
+    quote! {
+        ::fluent_bundle::__private::ast::CallArguments {
+            positional: ::std::vec![#(#positional),*],
+            named: ::std::vec![#(#named),*],
+        }
+    }
+}
+
+fn inline_expression<S: ToTokens>(expression: &ast::InlineExpression<S>) -> TokenStream {
+    match expression {
+        ast::InlineExpression::StringLiteral { value } => {
+            quote!(::fluent_bundle::__private::ast::InlineExpression::StringLiteral { value: #value })
+        }
+        ast::InlineExpression::NumberLiteral { value } => {
+            quote!(::fluent_bundle::__private::ast::InlineExpression::NumberLiteral { value: #value })
+        }
+        ast::InlineExpression::FunctionReference { id, arguments } => {
+            let id = identifier(id);
+            let arguments = call_arguments(arguments);
+            quote! {
+                ::fluent_bundle::__private::ast::InlineExpression::FunctionReference {
+                    id: #id,
+                    arguments: #arguments,
+                }
+            }
+        }
+        ast::InlineExpression::MessageReference { id, attribute } => {
+            let id = identifier(id);
+            let attribute = option(attribute.as_ref().map(identifier));
+            quote! {
+                ::fluent_bundle::__private::ast::InlineExpression::MessageReference {
+                    id: #id,
+                    attribute: #attribute,
+                }
+            }
+        }
+        ast::InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            let id = identifier(id);
+            let attribute = option(attribute.as_ref().map(identifier));
+            let arguments = option(arguments.as_ref().map(call_arguments));
+            quote! {
+                ::fluent_bundle::__private::ast::InlineExpression::TermReference {
+                    id: #id,
+                    attribute: #attribute,
+                    arguments: #arguments,
+                }
+            }
+        }
+        ast::InlineExpression::VariableReference { id } => {
+            let id = identifier(id);
+            quote!(::fluent_bundle::__private::ast::InlineExpression::VariableReference { id: #id })
+        }
+        ast::InlineExpression::Placeable { expression } => {
+            let expression = self::expression(expression);
+            quote! {
+                ::fluent_bundle::__private::ast::InlineExpression::Placeable {
+                    expression: ::std::boxed::Box::new(#expression),
+                }
+            }
+        }
+        // Unknown expressions are only kept with `unknown_expressions`.
+        _ => unreachable!("include_ftl! parses without keeping unknown expressions"),
+    }
+}
+
+fn expression<S: ToTokens>(expression: &ast::Expression<S>) -> TokenStream {
+    match expression {
+        ast::Expression::Select { selector, variants } => {
+            let selector = inline_expression(selector);
+            let variants = variants.iter().map(variant);
+            quote! {
+                ::fluent_bundle::__private::ast::Expression::Select {
+                    selector: #selector,
+                    variants: ::std::vec![#(#variants),*],
+                }
+            }
+        }
+        ast::Expression::Inline(expression) => {
+            let expression = inline_expression(expression);
+            quote!(::fluent_bundle::__private::ast::Expression::Inline(#expression))
+        }
+    }
+}
+
+fn option(value: Option<TokenStream>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
@@ -0,0 +1,2 @@
+# fluent: requires-flag=beta-ui
+sidebar = Sidebar
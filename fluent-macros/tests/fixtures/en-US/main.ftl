@@ -0,0 +1,18 @@
+### Resource comment
+
+hello-world = Hello, world!
+
+-brand-name = { $case ->
+   *[nominative] Firefox
+    [genitive] Firefox's
+  }
+    .gender = masculine
+
+# A message with attributes and references.
+about = About { -brand-name }
+    .title = { about } ({ -brand-name(case: "genitive") } page)
+
+emails = { $count ->
+    [one] You have one new email.
+   *[other] You have { NUMBER($count) } new emails.
+  }
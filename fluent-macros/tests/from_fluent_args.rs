@@ -0,0 +1,124 @@
+use fluent_bundle::{
+    FluentArgs, FluentBundle, FluentResource, FluentValue, FromFluentArgs, FromFluentValue,
+};
+use unic_langid::langid;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, FromFluentValue)]
+enum Style {
+    Full,
+    Long,
+    Medium,
+    #[default]
+    Short,
+    #[fluent(rename = "2-digit")]
+    TwoDigit,
+    #[fluent(skip)]
+    Hidden,
+}
+
+#[derive(Clone, Debug, PartialEq, FromFluentArgs)]
+struct DateTimeOptions {
+    date_style: Style,
+    time_style: Option<Style>,
+    hour_cycle: Option<String>,
+    fractional_second_digits: usize,
+    hour12: bool,
+    #[fluent(rename = "timeZone")]
+    zone: String,
+    #[fluent(skip)]
+    epoch_offset: i64,
+    r#type: Option<String>,
+}
+
+impl Default for DateTimeOptions {
+    fn default() -> Self {
+        Self {
+            date_style: Style::Medium,
+            time_style: None,
+            hour_cycle: None,
+            fractional_second_digits: 0,
+            hour12: false,
+            zone: "UTC".to_string(),
+            epoch_offset: 7,
+            r#type: None,
+        }
+    }
+}
+
+#[test]
+fn from_fluent_args_reads_options() {
+    let mut args = FluentArgs::new();
+    args.set("dateStyle", "full");
+    args.set("timeStyle", "2-digit");
+    args.set("hourCycle", "h23");
+    args.set("fractionalSecondDigits", 3);
+    args.set("hour12", "true");
+    args.set("timeZone", "Europe/Warsaw");
+    args.set("epochOffset", 1);
+    args.set("type", "time");
+
+    assert_eq!(
+        DateTimeOptions::from(&args),
+        DateTimeOptions {
+            date_style: Style::Full,
+            time_style: Some(Style::TwoDigit),
+            hour_cycle: Some("h23".to_string()),
+            fractional_second_digits: 3,
+            hour12: true,
+            zone: "Europe/Warsaw".to_string(),
+            epoch_offset: 7,
+            r#type: Some("time".to_string()),
+        }
+    );
+}
+
+#[test]
+fn from_fluent_args_keeps_invalid_options() {
+    let mut args = FluentArgs::new();
+    args.set("dateStyle", "hidden");
+    args.set("timeStyle", 5);
+    args.set("fractionalSecondDigits", "three");
+    args.set("hour12", "yes");
+    args.set("zone", "Europe/Warsaw");
+
+    assert_eq!(DateTimeOptions::from(&args), DateTimeOptions::default());
+    assert_eq!(Style::from_fluent_value(&"hidden".into()), None);
+    assert_ne!(Style::Hidden, Style::default());
+
+    let mut options = DateTimeOptions::default();
+    options.merge(&FluentArgs::from_iter([("dateStyle", "long")]));
+    options.merge(&FluentArgs::from_iter([("timeStyle", "short")]));
+    assert_eq!(options.date_style, Style::Long);
+    assert_eq!(options.time_style, Some(Style::Short));
+}
+
+#[test]
+fn from_fluent_args_in_function() {
+    let res =
+        FluentResource::try_new("key = { DATETIME($epoch, dateStyle: \"long\") }\n".to_string())
+            .expect("Failed to parse FTL.");
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(res)
+        .expect("Failed to add FTL resources to the bundle.");
+    bundle
+        .add_function("DATETIME", |positional, named| match positional.first() {
+            Some(FluentValue::Number(n)) => {
+                let options = DateTimeOptions::from(named);
+                format!("{} {:?}", n.value, options.date_style).into()
+            }
+            _ => FluentValue::Error,
+        })
+        .expect("Failed to add a function.");
+
+    let mut args = FluentArgs::new();
+    args.set("epoch", 10);
+    let pattern = bundle.get_message("key").unwrap().value().unwrap();
+    let mut errors = vec![];
+    assert_eq!(
+        bundle.format_pattern(pattern, Some(&args), &mut errors),
+        "10 Long"
+    );
+    assert_eq!(errors, vec![]);
+}
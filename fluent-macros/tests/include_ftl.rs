@@ -0,0 +1,72 @@
+use fluent_bundle::{include_ftl, FluentArgs, FluentBundle, FluentResource};
+use fluent_syntax::parser::parse_runtime;
+use unic_langid::langid;
+
+#[test]
+fn include_ftl_matches_parsed() {
+    let source = include_str!("fixtures/en-US/main.ftl");
+    let res: FluentResource = include_ftl!("tests/fixtures/en-US/main.ftl");
+
+    assert_eq!(res.source(), source);
+    assert_eq!(
+        res.entries().collect::<Vec<_>>(),
+        parse_runtime(source)
+            .expect("Failed to parse FTL.")
+            .body
+            .iter()
+            .collect::<Vec<_>>()
+    );
+
+    let entry = res.get_entry(1).expect("Entry doesn't exist.");
+    assert_eq!(
+        res.entry_source(entry),
+        Some("-brand-name = { $case ->\n   *[nominative] Firefox\n    [genitive] Firefox's\n  }\n    .gender = masculine")
+    );
+}
+
+#[test]
+fn include_ftl_keeps_annotations() {
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle
+        .add_resource(include_ftl!("tests/fixtures/en-US/flags.ftl"))
+        .expect("Failed to add FTL resources to the bundle.");
+
+    assert_eq!(bundle.required_flag("sidebar"), Some("beta-ui"));
+    assert!(!bundle.has_message("sidebar"));
+    bundle.set_enabled_flags(["beta-ui"]);
+    assert!(bundle.has_message("sidebar"));
+}
+
+#[test]
+fn include_ftl_formats() {
+    let mut bundle = FluentBundle::new(vec![langid!("en-US")]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_builtins()
+        .expect("Failed to add builtins to the bundle.");
+    bundle
+        .add_resource(include_ftl!("tests/fixtures/en-US/main.ftl"))
+        .expect("Failed to add FTL resources to the bundle.");
+
+    let mut errors = vec![];
+    let msg = bundle.get_message("about").expect("Message doesn't exist.");
+    let title = msg
+        .get_attribute("title")
+        .expect("Attribute doesn't exist.");
+    assert_eq!(
+        bundle.format_pattern(title.value(), None, &mut errors),
+        "About Firefox (Firefox's page)"
+    );
+
+    let mut args = FluentArgs::new();
+    args.set("count", 5);
+    let msg = bundle
+        .get_message("emails")
+        .expect("Message doesn't exist.");
+    let value = msg.value().expect("Message has no value.");
+    assert_eq!(
+        bundle.format_pattern(value, Some(&args), &mut errors),
+        "You have 5 new emails."
+    );
+    assert!(errors.is_empty());
+}
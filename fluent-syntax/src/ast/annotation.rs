@@ -0,0 +1,104 @@
+//! Key/value annotations in comments.
+//!
+//! Tools often annotate messages with metadata in their comments, one
+//! annotation per line, as in `# .maxLength: 40`. An annotation line starts
+//! with `.` and a key, followed by `:` and the value. Other lines are the free
+//! text of the comment.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast;
+//! use fluent_syntax::ast::annotation::Annotation;
+//! use fluent_syntax::parser;
+//!
+//! let ftl = concat!(
+//!     "# Greets the user in the toolbar.\n",
+//!     "# .placeholders: user\n",
+//!     "# .maxLength: 40\n",
+//!     "hello = Hello, { $user }!\n",
+//! );
+//!
+//! let resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+//! let comment = match &resource.body[0] {
+//!     ast::Entry::Message(ast::Message {
+//!         comment: Some(comment),
+//!         ..
+//!     }) => comment,
+//!     _ => panic!("Expected a message with a comment."),
+//! };
+//!
+//! assert_eq!(comment.annotation("maxLength"), Some("40"));
+//! assert_eq!(
+//!     comment.annotations().collect::<Vec<_>>(),
+//!     vec![
+//!         Annotation {
+//!             key: "placeholders",
+//!             value: "user",
+//!         },
+//!         Annotation {
+//!             key: "maxLength",
+//!             value: "40",
+//!         },
+//!     ]
+//! );
+//! ```
+use super::Comment;
+use core::slice;
+
+/// An annotation of a comment, see the [module documentation](self).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Annotation<'a> {
+    pub key: &'a str,
+    /// The value, without the whitespace around it.
+    pub value: &'a str,
+}
+
+impl<'a> Annotation<'a> {
+    /// Parses a line of a comment as an annotation.
+    ///
+    /// The key can't be empty nor contain whitespace or `:`.
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let (key, value) = line.trim_start().strip_prefix('.')?.split_once(':')?;
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+        Some(Self {
+            key,
+            value: value.trim(),
+        })
+    }
+}
+
+/// An iterator over the annotations of a comment, returned by
+/// [`Comment::annotations`].
+#[derive(Clone, Debug)]
+pub struct Annotations<'a, S> {
+    lines: slice::Iter<'a, S>,
+}
+
+impl<'a, S: AsRef<str>> Iterator for Annotations<'a, S> {
+    type Item = Annotation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines
+            .by_ref()
+            .find_map(|line| Annotation::parse(line.as_ref()))
+    }
+}
+
+impl<S: AsRef<str>> Comment<S> {
+    /// Returns the annotations of the comment, in the order of its lines.
+    pub fn annotations(&self) -> Annotations<'_, S> {
+        Annotations {
+            lines: self.content.iter(),
+        }
+    }
+
+    /// Returns the value of the first annotation with `key`.
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        self.annotations()
+            .find(|annotation| annotation.key == key)
+            .map(|annotation| annotation.value)
+    }
+}
@@ -0,0 +1,595 @@
+//! Builders for messages and terms.
+//!
+//! The builders construct well-formed entries for the [`serializer`](crate::serializer)
+//! without spelling out the nested AST nodes. They are generic over the same
+//! `S` as the AST, and check the identifiers, literals and text of the entry
+//! when it is built.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast;
+//! use fluent_syntax::ast::builder::{self, MessageBuilder, PatternBuilder, SelectBuilder};
+//! use fluent_syntax::serializer;
+//!
+//! let message = MessageBuilder::new("emails")
+//!     .comment("The number of unread emails.")
+//!     .value(
+//!         PatternBuilder::new().text("You have ").select(
+//!             SelectBuilder::new(builder::variable("count"))
+//!                 .variant("one", PatternBuilder::new().text("one new email"))
+//!                 .default_variant(
+//!                     "other",
+//!                     PatternBuilder::new()
+//!                         .placeable(builder::variable("count"))
+//!                         .text(" new emails"),
+//!                 ),
+//!         ),
+//!     )
+//!     .attribute("title", PatternBuilder::new().text("Inbox"))
+//!     .build()
+//!     .expect("Failed to build a message.");
+//!
+//! let resource = ast::Resource {
+//!     body: vec![ast::Entry::Message(message)],
+//! };
+//! assert_eq!(
+//!     serializer::serialize(&resource),
+//!     "# The number of unread emails.
+//! emails =
+//!     You have { $count ->
+//!         [one] one new email
+//!        *[other] { $count } new emails
+//!     }
+//!     .title = Inbox
+//! "
+//! );
+//!
+//! assert_eq!(
+//!     MessageBuilder::new("1st").value(PatternBuilder::new().text("First")).build(),
+//!     Err(builder::BuilderError::InvalidIdentifier("1st".to_string()))
+//! );
+//! ```
+use super::visit::{self, Visit};
+use super::*;
+use crate::literal::is_number_literal;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use thiserror::Error;
+
+/// An error found when building an entry.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum BuilderError {
+    #[error("\"{0}\" is not a valid identifier")]
+    InvalidIdentifier(String),
+    #[error("\"{0}\" is not a valid function name")]
+    InvalidFunctionName(String),
+    #[error("\"{0}\" is not a valid number literal")]
+    InvalidNumberLiteral(String),
+    #[error("\"{0}\" is not a valid string literal")]
+    InvalidStringLiteral(String),
+    /// Text can't contain braces, nor start a line with `[`, `*` or `.`.
+    #[error("\"{0}\" is not valid text")]
+    InvalidText(String),
+    /// Comment lines can't contain line ends.
+    #[error("\"{0}\" is not a valid comment line")]
+    InvalidComment(String),
+    #[error("Expected a value")]
+    MissingValue,
+    #[error("Expected a message field for \"{entry_id}\"")]
+    ExpectedMessageField { entry_id: String },
+    #[error("The select expression must have a default variant")]
+    MissingDefaultVariant,
+    #[error("A select expression can only have one default variant")]
+    MultipleDefaultVariants,
+    #[error("Message references can't be used as a selector")]
+    MessageReferenceAsSelector,
+    #[error("Term references can't be used as a selector")]
+    TermReferenceAsSelector,
+    #[error("Expected a simple expression as selector")]
+    ExpectedSimpleExpressionAsSelector,
+    #[error("Term attributes can't be used as a placeable")]
+    TermAttributeAsPlaceable,
+    #[error("The \"{0}\" argument appears twice")]
+    DuplicatedNamedArgument(String),
+    /// The values of named arguments must be literals.
+    #[error("Expected a string or number literal")]
+    ExpectedLiteral,
+}
+
+/// Builds a [`Message`].
+#[derive(Debug)]
+pub struct MessageBuilder<S> {
+    id: S,
+    value: Option<Pattern<S>>,
+    attributes: Vec<Attribute<S>>,
+    comment: Vec<S>,
+}
+
+impl<S: AsRef<str>> MessageBuilder<S> {
+    pub fn new(id: S) -> Self {
+        Self {
+            id,
+            value: None,
+            attributes: vec![],
+            comment: vec![],
+        }
+    }
+
+    pub fn value(mut self, value: PatternBuilder<S>) -> Self {
+        self.value = Some(value.into_pattern());
+        self
+    }
+
+    pub fn attribute(mut self, id: S, value: PatternBuilder<S>) -> Self {
+        self.attributes.push(Attribute {
+            id: Identifier { name: id },
+            value: value.into_pattern(),
+        });
+        self
+    }
+
+    /// Appends a line to the comment attached to the message.
+    pub fn comment(mut self, line: S) -> Self {
+        self.comment.push(line);
+        self
+    }
+
+    /// Returns the message, or the first error found in it.
+    ///
+    /// A message must have a value, attributes, or both.
+    pub fn build(self) -> Result<Message<S>, BuilderError> {
+        let message = Message {
+            id: Identifier { name: self.id },
+            value: self.value,
+            attributes: self.attributes,
+            comment: comment(self.comment),
+        };
+        let mut validator = Validator::default();
+        validator.visit_message(&message);
+        if message.value.is_none() && message.attributes.is_empty() {
+            validator.fail(BuilderError::ExpectedMessageField {
+                entry_id: message.id.name.as_ref().to_string(),
+            });
+        }
+        validator.finish(message)
+    }
+}
+
+/// Builds a [`Term`].
+#[derive(Debug)]
+pub struct TermBuilder<S> {
+    id: S,
+    value: Pattern<S>,
+    attributes: Vec<Attribute<S>>,
+    comment: Vec<S>,
+}
+
+impl<S: AsRef<str>> TermBuilder<S> {
+    /// Creates a builder for the term `-id`. The `id` doesn't include the
+    /// leading `-`.
+    pub fn new(id: S, value: PatternBuilder<S>) -> Self {
+        Self {
+            id,
+            value: value.into_pattern(),
+            attributes: vec![],
+            comment: vec![],
+        }
+    }
+
+    pub fn attribute(mut self, id: S, value: PatternBuilder<S>) -> Self {
+        self.attributes.push(Attribute {
+            id: Identifier { name: id },
+            value: value.into_pattern(),
+        });
+        self
+    }
+
+    /// Appends a line to the comment attached to the term.
+    pub fn comment(mut self, line: S) -> Self {
+        self.comment.push(line);
+        self
+    }
+
+    /// Returns the term, or the first error found in it.
+    pub fn build(self) -> Result<Term<S>, BuilderError> {
+        let term = Term {
+            id: Identifier { name: self.id },
+            value: self.value,
+            attributes: self.attributes,
+            comment: comment(self.comment),
+        };
+        let mut validator = Validator::default();
+        validator.visit_term(&term);
+        validator.finish(term)
+    }
+}
+
+/// Builds a [`Pattern`] from text and placeables.
+#[derive(Debug)]
+pub struct PatternBuilder<S> {
+    elements: Vec<PatternElement<S>>,
+}
+
+impl<S> Default for PatternBuilder<S> {
+    fn default() -> Self {
+        Self { elements: vec![] }
+    }
+}
+
+impl<S: AsRef<str>> PatternBuilder<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends text. Text can span several lines, but can't contain braces,
+    /// which must be written as string literals, nor start a line after the
+    /// first with `[`, `*` or `.`.
+    pub fn text(mut self, value: S) -> Self {
+        self.elements.push(PatternElement::TextElement { value });
+        self
+    }
+
+    /// Appends a placeable of `expression`, such as a [`variable`].
+    pub fn placeable(mut self, expression: InlineExpression<S>) -> Self {
+        self.elements.push(PatternElement::Placeable {
+            expression: Expression::Inline(expression),
+        });
+        self
+    }
+
+    /// Appends a placeable of a select expression.
+    pub fn select(mut self, select: SelectBuilder<S>) -> Self {
+        self.elements.push(PatternElement::Placeable {
+            expression: Expression::Select {
+                selector: select.selector,
+                variants: select.variants,
+            },
+        });
+        self
+    }
+
+    /// Returns the pattern, or the first error found in it.
+    pub fn build(self) -> Result<Pattern<S>, BuilderError> {
+        let pattern = self.into_pattern();
+        let mut validator = Validator::default();
+        validator.visit_pattern(&pattern);
+        validator.finish(pattern)
+    }
+
+    fn into_pattern(self) -> Pattern<S> {
+        Pattern {
+            elements: self.elements,
+        }
+    }
+}
+
+/// Builds a select expression, for [`PatternBuilder::select`].
+#[derive(Debug)]
+pub struct SelectBuilder<S> {
+    selector: InlineExpression<S>,
+    variants: Vec<Variant<S>>,
+}
+
+impl<S: AsRef<str>> SelectBuilder<S> {
+    pub fn new(selector: InlineExpression<S>) -> Self {
+        Self {
+            selector,
+            variants: vec![],
+        }
+    }
+
+    /// Appends a variant. A `key` which starts with a digit or `-` is a
+    /// number literal, and any other is an identifier.
+    pub fn variant(self, key: S, value: PatternBuilder<S>) -> Self {
+        self.push_variant(key, value, false)
+    }
+
+    /// Appends the default variant. A select expression must have exactly
+    /// one.
+    pub fn default_variant(self, key: S, value: PatternBuilder<S>) -> Self {
+        self.push_variant(key, value, true)
+    }
+
+    fn push_variant(mut self, key: S, value: PatternBuilder<S>, default: bool) -> Self {
+        let key = if key
+            .as_ref()
+            .starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        {
+            VariantKey::NumberLiteral { value: key }
+        } else {
+            VariantKey::Identifier { name: key }
+        };
+        self.variants.push(Variant {
+            key,
+            value: value.into_pattern(),
+            default,
+        });
+        self
+    }
+}
+
+/// Returns a reference to the variable `$name`.
+pub fn variable<S>(name: S) -> InlineExpression<S> {
+    InlineExpression::VariableReference {
+        id: Identifier { name },
+    }
+}
+
+/// Returns a reference to the message `id`, or to its `attribute`.
+pub fn message_reference<S>(id: S, attribute: Option<S>) -> InlineExpression<S> {
+    InlineExpression::MessageReference {
+        id: Identifier { name: id },
+        attribute: attribute.map(|name| Identifier { name }),
+    }
+}
+
+/// Returns a reference to the term `-id`, or to its `attribute`.
+pub fn term_reference<S>(id: S, attribute: Option<S>) -> InlineExpression<S> {
+    InlineExpression::TermReference {
+        id: Identifier { name: id },
+        attribute: attribute.map(|name| Identifier { name }),
+        arguments: None,
+    }
+}
+
+/// Returns a call to the function `id`.
+pub fn function_reference<S>(
+    id: S,
+    positional: Vec<InlineExpression<S>>,
+    named: Vec<(S, InlineExpression<S>)>,
+) -> InlineExpression<S> {
+    InlineExpression::FunctionReference {
+        id: Identifier { name: id },
+        arguments: CallArguments {
+            positional,
+            named: named
+                .into_iter()
+                .map(|(name, value)| NamedArgument {
+                    name: Identifier { name },
+                    value,
+                })
+                .collect(),
+        },
+    }
+}
+
+/// Returns a string literal. The `value` is written as is between the quotes,
+/// so `"` and `\` must be escaped.
+pub fn string_literal<S>(value: S) -> InlineExpression<S> {
+    InlineExpression::StringLiteral { value }
+}
+
+pub fn number_literal<S>(value: S) -> InlineExpression<S> {
+    InlineExpression::NumberLiteral { value }
+}
+
+fn comment<S>(lines: Vec<S>) -> Option<Comment<S>> {
+    if lines.is_empty() {
+        None
+    } else {
+        Some(Comment { content: lines })
+    }
+}
+
+/// Checks the nodes of an entry, keeping the first error.
+#[derive(Default)]
+struct Validator {
+    error: Option<BuilderError>,
+}
+
+impl Validator {
+    fn fail(&mut self, error: BuilderError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    fn check(&mut self, valid: bool, error: impl FnOnce() -> BuilderError) {
+        if !valid {
+            self.fail(error());
+        }
+    }
+
+    fn finish<T>(self, node: T) -> Result<T, BuilderError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(node),
+        }
+    }
+}
+
+impl<'ast, S: AsRef<str> + 'ast> Visit<'ast, S> for Validator {
+    fn visit_comment(&mut self, node: &'ast Comment<S>) {
+        for line in &node.content {
+            let line = line.as_ref();
+            self.check(!line.contains(['\n', '\r']), || {
+                BuilderError::InvalidComment(line.to_string())
+            });
+        }
+    }
+
+    fn visit_identifier(&mut self, node: &'ast Identifier<S>) {
+        let name = node.name.as_ref();
+        self.check(is_identifier(name), || {
+            BuilderError::InvalidIdentifier(name.to_string())
+        });
+    }
+
+    fn visit_pattern(&mut self, node: &'ast Pattern<S>) {
+        let is_blank = node.elements.iter().all(|element| match element {
+            PatternElement::TextElement { value } => value.as_ref().trim().is_empty(),
+            PatternElement::Placeable { .. } => false,
+            #[cfg(feature = "recover-placeables")]
+            PatternElement::Junk { .. } => false,
+        });
+        self.check(!is_blank, || BuilderError::MissingValue);
+        visit::walk_pattern(self, node);
+    }
+
+    fn visit_pattern_element(&mut self, node: &'ast PatternElement<S>) {
+        if let PatternElement::Placeable {
+            expression:
+                Expression::Inline(InlineExpression::TermReference {
+                    attribute: Some(_), ..
+                }),
+        } = node
+        {
+            self.fail(BuilderError::TermAttributeAsPlaceable);
+        }
+        visit::walk_pattern_element(self, node);
+    }
+
+    fn visit_text_element(&mut self, value: &'ast S) {
+        let value = value.as_ref();
+        self.check(is_text(value), || {
+            BuilderError::InvalidText(value.to_string())
+        });
+    }
+
+    fn visit_expression(&mut self, node: &'ast Expression<S>) {
+        if let Expression::Select { selector, variants } = node {
+            match selector {
+                InlineExpression::MessageReference { .. } => {
+                    self.fail(BuilderError::MessageReferenceAsSelector);
+                }
+                InlineExpression::TermReference {
+                    attribute: None, ..
+                } => {
+                    self.fail(BuilderError::TermReferenceAsSelector);
+                }
+                InlineExpression::Placeable { .. } => {
+                    self.fail(BuilderError::ExpectedSimpleExpressionAsSelector);
+                }
+                _ => {}
+            }
+            match variants.iter().filter(|variant| variant.default).count() {
+                0 => self.fail(BuilderError::MissingDefaultVariant),
+                1 => {}
+                _ => self.fail(BuilderError::MultipleDefaultVariants),
+            }
+        }
+        visit::walk_expression(self, node);
+    }
+
+    fn visit_variant_key(&mut self, node: &'ast VariantKey<S>) {
+        match node {
+            VariantKey::Identifier { name } => {
+                let name = name.as_ref();
+                self.check(is_identifier(name), || {
+                    BuilderError::InvalidIdentifier(name.to_string())
+                });
+            }
+            VariantKey::NumberLiteral { value } => {
+                let value = value.as_ref();
+                self.check(is_number_literal(value), || {
+                    BuilderError::InvalidNumberLiteral(value.to_string())
+                });
+            }
+        }
+    }
+
+    fn visit_inline_expression(&mut self, node: &'ast InlineExpression<S>) {
+        match node {
+            InlineExpression::StringLiteral { value } => {
+                let value = value.as_ref();
+                self.check(is_string_literal(value), || {
+                    BuilderError::InvalidStringLiteral(value.to_string())
+                });
+            }
+            InlineExpression::NumberLiteral { value } => {
+                let value = value.as_ref();
+                self.check(is_number_literal(value), || {
+                    BuilderError::InvalidNumberLiteral(value.to_string())
+                });
+            }
+            InlineExpression::FunctionReference { id, arguments } => {
+                let name = id.name.as_ref();
+                self.check(is_function_name(name), || {
+                    BuilderError::InvalidFunctionName(name.to_string())
+                });
+                self.visit_call_arguments(arguments);
+            }
+            _ => visit::walk_inline_expression(self, node),
+        }
+    }
+
+    fn visit_call_arguments(&mut self, node: &'ast CallArguments<S>) {
+        for (i, argument) in node.named.iter().enumerate() {
+            let name = argument.name.name.as_ref();
+            if node.named[..i]
+                .iter()
+                .any(|previous| previous.name.name.as_ref() == name)
+            {
+                self.fail(BuilderError::DuplicatedNamedArgument(name.to_string()));
+            }
+        }
+        visit::walk_call_arguments(self, node);
+    }
+
+    fn visit_named_argument(&mut self, node: &'ast NamedArgument<S>) {
+        self.visit_identifier(&node.name);
+        match &node.value {
+            InlineExpression::StringLiteral { .. } | InlineExpression::NumberLiteral { .. } => {
+                self.visit_inline_expression(&node.value);
+            }
+            _ => self.fail(BuilderError::ExpectedLiteral),
+        }
+    }
+}
+
+/// `[a-zA-Z][a-zA-Z0-9_-]*`
+fn is_identifier(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    bytes.next().map_or(false, |b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// An identifier of uppercase letters, digits, `_` and `-`, optionally
+/// preceded by a namespace, such as `ICU.DATETIME`.
+fn is_function_name(name: &str) -> bool {
+    let is_callee = |part: &str| {
+        is_identifier(part)
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_' || b == b'-')
+    };
+    match name.split_once('.') {
+        Some((namespace, name)) => is_callee(namespace) && is_callee(name),
+        None => is_callee(name),
+    }
+}
+
+/// The content of a string literal: no line ends, and `"` and `\` only in
+/// escape sequences.
+fn is_string_literal(value: &str) -> bool {
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\n' | '\r' => return false,
+            '\\' => {
+                let digits = match chars.next() {
+                    Some('\\') | Some('"') => 0,
+                    Some('u') => 4,
+                    Some('U') => 6,
+                    _ => return false,
+                };
+                if !(0..digits).all(|_| chars.next().map_or(false, |c| c.is_ascii_hexdigit())) {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Text without braces, whose lines after the first don't start with a
+/// character which would end the pattern.
+fn is_text(value: &str) -> bool {
+    !value.contains(['{', '}'])
+        && value
+            .split('\n')
+            .skip(1)
+            .all(|line| !line.trim_start_matches(' ').starts_with(['[', '*', '.']))
+}
@@ -0,0 +1,350 @@
+//! Fuzzing and property testing with arbitrary resources.
+//!
+//! With the `arbitrary` feature, the AST types implement
+//! [`arbitrary::Arbitrary`], so fuzzers and property tests can generate
+//! resources from raw bytes. Most generated resources are not valid: their
+//! identifiers contain spaces, their text contains braces, their select
+//! expressions lack a default variant, and so on. [`Resource::make_valid`]
+//! fixes them, so that the serialized resource parses back to the same AST,
+//! and round-trips and the resolver can be tested with it.
+//!
+//! # Example
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use fluent_syntax::{ast, parser, serializer};
+//!
+//! let mut u = Unstructured::new(b"key = {{ $selector -> }}");
+//! let mut resource =
+//!     ast::Resource::<String>::arbitrary(&mut u).expect("Failed to generate a resource.");
+//! resource.make_valid();
+//!
+//! let source = serializer::serialize(&resource);
+//! assert_eq!(parser::parse(source), Ok(resource));
+//! ```
+use super::visit::{self, VisitMut};
+use super::*;
+use crate::literal::is_number_literal;
+use alloc::string::String;
+use alloc::vec;
+
+impl<S: AsRef<str> + From<String>> Resource<S> {
+    /// Changes the resource into one which the parser returns unchanged from
+    /// its serialization.
+    ///
+    /// Junk and unknown expressions are removed, since the parser can't
+    /// produce them from a serialized resource. Otherwise, the resource is
+    /// changed as little as possible:
+    ///
+    /// - Invalid characters are removed from identifiers, number literals,
+    ///   text and comments, and escaped in string literals. Text is kept on
+    ///   one line, and the blanks the parser trims from patterns are removed.
+    /// - Empty patterns of terms, attributes and variants, and of messages
+    ///   without attributes, are replaced with an empty string literal.
+    /// - Select expressions get a valid selector and exactly one default
+    ///   variant, and the values of named arguments become literals.
+    /// - A term attribute used as a placeable loses its attribute.
+    pub fn make_valid(&mut self) {
+        self.body
+            .retain(|entry| !matches!(entry, Entry::Junk { .. }));
+        MakeValid.visit_resource_mut(self);
+    }
+}
+
+struct MakeValid;
+
+impl<S: AsRef<str> + From<String>> VisitMut<S> for MakeValid {
+    fn visit_message_mut(&mut self, node: &mut Message<S>) {
+        visit::walk_message_mut(self, node);
+        if node
+            .value
+            .as_ref()
+            .map_or(false, |value| value.elements.is_empty())
+        {
+            node.value = None;
+        }
+        if node.value.is_none() && node.attributes.is_empty() {
+            node.value = Some(placeholder());
+        }
+    }
+
+    fn visit_term_mut(&mut self, node: &mut Term<S>) {
+        visit::walk_term_mut(self, node);
+        fill(&mut node.value);
+    }
+
+    fn visit_comment_mut(&mut self, node: &mut Comment<S>) {
+        for line in &mut node.content {
+            replace(line, |line| {
+                let line = line.replace(['\n', '\r'], "");
+                // The serializer writes a line of spaces as an empty line.
+                if line.trim_start_matches(' ').is_empty() {
+                    String::new()
+                } else {
+                    line
+                }
+            });
+        }
+        if node.content.is_empty() {
+            node.content.push(S::from(String::new()));
+        }
+    }
+
+    fn visit_attribute_mut(&mut self, node: &mut Attribute<S>) {
+        visit::walk_attribute_mut(self, node);
+        fill(&mut node.value);
+    }
+
+    fn visit_identifier_mut(&mut self, node: &mut Identifier<S>) {
+        replace(&mut node.name, identifier);
+    }
+
+    fn visit_pattern_mut(&mut self, node: &mut Pattern<S>) {
+        visit::walk_pattern_mut(self, node);
+
+        let mut elements: Vec<PatternElement<S>> = Vec::with_capacity(node.elements.len());
+        for element in node.elements.drain(..) {
+            match element {
+                PatternElement::TextElement { value } => {
+                    let value = value.as_ref().replace(['{', '}', '\n', '\r'], "");
+                    match elements.last_mut() {
+                        Some(PatternElement::TextElement { value: last }) => {
+                            let merged = String::from(last.as_ref()) + &value;
+                            *last = S::from(merged);
+                        }
+                        _ => elements.push(PatternElement::TextElement {
+                            value: S::from(value),
+                        }),
+                    }
+                }
+                PatternElement::Placeable { .. } => elements.push(element),
+                #[cfg(feature = "recover-placeables")]
+                PatternElement::Junk { .. } => {}
+            }
+        }
+
+        // A pattern with a select expression starts on a new line, where text
+        // starting with `[` or `*` would be a variant.
+        let multiline = elements.iter().any(|element| match element {
+            PatternElement::Placeable { expression } => is_select(expression),
+            _ => false,
+        });
+        if let Some(PatternElement::TextElement { value }) = elements.first_mut() {
+            let mut text = value.as_ref().trim_start_matches(' ');
+            if multiline && !text.starts_with('.') {
+                text = text.trim_start_matches(['[', '*', ' ']);
+            }
+            if text.len() != value.as_ref().len() {
+                *value = S::from(String::from(text));
+            }
+        }
+        if let Some(PatternElement::TextElement { value }) = elements.last_mut() {
+            let text = value.as_ref().trim_end_matches(' ');
+            if text.len() != value.as_ref().len() {
+                *value = S::from(String::from(text));
+            }
+        }
+        elements.retain(|element| match element {
+            PatternElement::TextElement { value } => !value.as_ref().is_empty(),
+            _ => true,
+        });
+        node.elements = elements;
+    }
+
+    fn visit_expression_mut(&mut self, node: &mut Expression<S>) {
+        match node {
+            Expression::Select { selector, variants } => {
+                if !is_selector(selector) {
+                    *selector = InlineExpression::VariableReference {
+                        id: Identifier {
+                            name: S::from(String::from("selector")),
+                        },
+                    };
+                }
+                if variants.is_empty() {
+                    variants.push(Variant {
+                        key: VariantKey::Identifier {
+                            name: S::from(String::from("other")),
+                        },
+                        value: placeholder(),
+                        default: true,
+                    });
+                }
+                let default = variants
+                    .iter()
+                    .position(|variant| variant.default)
+                    .unwrap_or(variants.len() - 1);
+                for (i, variant) in variants.iter_mut().enumerate() {
+                    variant.default = i == default;
+                }
+            }
+            Expression::Inline(InlineExpression::TermReference { attribute, .. }) => {
+                *attribute = None;
+            }
+            Expression::Inline(_) => {}
+        }
+        visit::walk_expression_mut(self, node);
+    }
+
+    fn visit_variant_mut(&mut self, node: &mut Variant<S>) {
+        visit::walk_variant_mut(self, node);
+        fill(&mut node.value);
+    }
+
+    fn visit_variant_key_mut(&mut self, node: &mut VariantKey<S>) {
+        match node {
+            VariantKey::Identifier { name } => replace(name, identifier),
+            VariantKey::NumberLiteral { value } => replace(value, number),
+        }
+    }
+
+    fn visit_inline_expression_mut(&mut self, node: &mut InlineExpression<S>) {
+        match node {
+            InlineExpression::StringLiteral { value } => {
+                replace(value, string);
+            }
+            InlineExpression::NumberLiteral { value } => {
+                replace(value, number);
+            }
+            #[cfg(feature = "unknown-expressions")]
+            InlineExpression::Unknown { .. } => {
+                *node = InlineExpression::StringLiteral {
+                    value: S::from(String::new()),
+                };
+            }
+            _ => {}
+        }
+        visit::walk_inline_expression_mut(self, node);
+        if let InlineExpression::FunctionReference { id, .. } = node {
+            replace(&mut id.name, str::to_ascii_uppercase);
+        }
+    }
+
+    fn visit_call_arguments_mut(&mut self, node: &mut CallArguments<S>) {
+        visit::walk_call_arguments_mut(self, node);
+        let mut names: Vec<String> = Vec::new();
+        node.named.retain(|argument| {
+            let name = argument.name.name.as_ref();
+            if names.iter().any(|other| other == name) {
+                return false;
+            }
+            names.push(String::from(name));
+            true
+        });
+        for argument in &mut node.named {
+            if !matches!(
+                argument.value,
+                InlineExpression::StringLiteral { .. } | InlineExpression::NumberLiteral { .. }
+            ) {
+                argument.value = InlineExpression::StringLiteral {
+                    value: S::from(String::new()),
+                };
+            }
+        }
+    }
+}
+
+/// Replaces `value` with the result of `f` if it differs.
+fn replace<S: AsRef<str> + From<String>>(value: &mut S, f: impl FnOnce(&str) -> String) {
+    let new = f(value.as_ref());
+    if value.as_ref() != new {
+        *value = S::from(new);
+    }
+}
+
+fn placeholder<S: From<String>>() -> Pattern<S> {
+    Pattern {
+        elements: vec![PatternElement::Placeable {
+            expression: Expression::Inline(InlineExpression::StringLiteral {
+                value: S::from(String::new()),
+            }),
+        }],
+    }
+}
+
+/// Gives an empty pattern a value, for the nodes which require one.
+fn fill<S: From<String>>(pattern: &mut Pattern<S>) {
+    if pattern.elements.is_empty() {
+        *pattern = placeholder();
+    }
+}
+
+fn is_select<S>(expression: &Expression<S>) -> bool {
+    match expression {
+        Expression::Select { .. } => true,
+        Expression::Inline(InlineExpression::Placeable { expression }) => is_select(expression),
+        Expression::Inline(_) => false,
+    }
+}
+
+fn is_selector<S>(expression: &InlineExpression<S>) -> bool {
+    match expression {
+        InlineExpression::StringLiteral { .. }
+        | InlineExpression::NumberLiteral { .. }
+        | InlineExpression::FunctionReference { .. }
+        | InlineExpression::VariableReference { .. } => true,
+        InlineExpression::TermReference { attribute, .. } => attribute.is_some(),
+        _ => false,
+    }
+}
+
+/// Keeps the characters allowed in identifiers, and makes sure the first one
+/// is a letter.
+fn identifier(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert(0, 'x');
+    }
+    result
+}
+
+/// Keeps the sign and the digits of an invalid number literal.
+fn number(value: &str) -> String {
+    if is_number_literal(value) {
+        return String::from(value);
+    }
+    let mut result = String::from(if value.starts_with('-') { "-" } else { "" });
+    result.extend(value.chars().filter(char::is_ascii_digit));
+    if result.len() == usize::from(result.starts_with('-')) {
+        result.push('0');
+    }
+    result
+}
+
+/// Escapes the quotes and the backslashes of a string literal which don't
+/// start a valid escape sequence, and removes its line breaks.
+fn string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '\n' | '\r' => {}
+            '"' => result.push_str("\\\""),
+            '\\' => {
+                let digits = match rest.chars().next() {
+                    Some('\\') | Some('"') => Some(1),
+                    Some('u') => Some(5),
+                    Some('U') => Some(7),
+                    _ => None,
+                };
+                let escape = digits
+                    .and_then(|len| rest.get(..len))
+                    .filter(|escape| escape[1..].chars().all(|c| c.is_ascii_hexdigit()));
+                match escape {
+                    Some(escape) => {
+                        result.push('\\');
+                        result.push_str(escape);
+                        rest = &rest[escape.len()..];
+                    }
+                    None => result.push_str("\\\\"),
+                }
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
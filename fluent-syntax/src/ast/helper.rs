@@ -1,3 +1,4 @@
+use alloc::{vec, vec::Vec};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -0,0 +1,250 @@
+//! Conversion of the AST into other storage of its slices.
+use super::*;
+use alloc::string::{String, ToString};
+
+macro_rules! map_source_methods {
+    ($node:ident) => {
+        impl<S> $node<S> {
+            /// Converts every slice of the node with `f`, in the order of the
+            /// source.
+            pub fn map_source<T, F: FnMut(S) -> T>(self, mut f: F) -> $node<T> {
+                self.map_with(&mut f)
+            }
+
+            /// Copies every slice of the node into a `String`.
+            pub fn into_owned(self) -> $node<String>
+            where
+                S: AsRef<str>,
+            {
+                self.map_source(|slice| slice.as_ref().to_string())
+            }
+        }
+    };
+}
+
+map_source_methods!(Resource);
+map_source_methods!(Entry);
+map_source_methods!(Message);
+map_source_methods!(Term);
+map_source_methods!(Pattern);
+map_source_methods!(PatternElement);
+map_source_methods!(Attribute);
+map_source_methods!(Identifier);
+map_source_methods!(Variant);
+map_source_methods!(VariantKey);
+map_source_methods!(Comment);
+map_source_methods!(CallArguments);
+map_source_methods!(NamedArgument);
+map_source_methods!(InlineExpression);
+map_source_methods!(Expression);
+
+// The `map_with` methods take the function by reference, so that the
+// recursion between expressions and patterns uses a single instance of them.
+
+impl<S> Resource<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Resource<T> {
+        Resource {
+            body: self
+                .body
+                .into_iter()
+                .map(|entry| entry.map_with(f))
+                .collect(),
+        }
+    }
+}
+
+impl<S> Entry<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Entry<T> {
+        match self {
+            Self::Message(message) => Entry::Message(message.map_with(f)),
+            Self::Term(term) => Entry::Term(term.map_with(f)),
+            Self::Comment(comment) => Entry::Comment(comment.map_with(f)),
+            Self::GroupComment(comment) => Entry::GroupComment(comment.map_with(f)),
+            Self::ResourceComment(comment) => Entry::ResourceComment(comment.map_with(f)),
+            Self::Junk { content } => Entry::Junk {
+                content: f(content),
+            },
+        }
+    }
+}
+
+impl<S> Message<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Message<T> {
+        // The comment comes first in the source.
+        let comment = self.comment.map(|comment| comment.map_with(f));
+        Message {
+            id: self.id.map_with(f),
+            value: self.value.map(|value| value.map_with(f)),
+            attributes: map_attributes(self.attributes, f),
+            comment,
+        }
+    }
+}
+
+impl<S> Term<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Term<T> {
+        let comment = self.comment.map(|comment| comment.map_with(f));
+        Term {
+            id: self.id.map_with(f),
+            value: self.value.map_with(f),
+            attributes: map_attributes(self.attributes, f),
+            comment,
+        }
+    }
+}
+
+fn map_attributes<S, T, F: FnMut(S) -> T>(
+    attributes: Vec<Attribute<S>>,
+    f: &mut F,
+) -> Vec<Attribute<T>> {
+    attributes
+        .into_iter()
+        .map(|attribute| attribute.map_with(f))
+        .collect()
+}
+
+impl<S> Pattern<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Pattern<T> {
+        Pattern {
+            elements: self
+                .elements
+                .into_iter()
+                .map(|element| element.map_with(f))
+                .collect(),
+        }
+    }
+}
+
+impl<S> PatternElement<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> PatternElement<T> {
+        match self {
+            Self::TextElement { value } => PatternElement::TextElement { value: f(value) },
+            Self::Placeable { expression } => PatternElement::Placeable {
+                expression: expression.map_with(f),
+            },
+            #[cfg(feature = "recover-placeables")]
+            Self::Junk { content } => PatternElement::Junk {
+                content: f(content),
+            },
+        }
+    }
+}
+
+impl<S> Attribute<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Attribute<T> {
+        Attribute {
+            id: self.id.map_with(f),
+            value: self.value.map_with(f),
+        }
+    }
+}
+
+impl<S> Identifier<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Identifier<T> {
+        Identifier { name: f(self.name) }
+    }
+}
+
+impl<S> Variant<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Variant<T> {
+        Variant {
+            key: self.key.map_with(f),
+            value: self.value.map_with(f),
+            default: self.default,
+        }
+    }
+}
+
+impl<S> VariantKey<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> VariantKey<T> {
+        match self {
+            Self::Identifier { name } => VariantKey::Identifier { name: f(name) },
+            Self::NumberLiteral { value } => VariantKey::NumberLiteral { value: f(value) },
+        }
+    }
+}
+
+impl<S> Comment<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Comment<T> {
+        Comment {
+            content: self.content.into_iter().map(f).collect(),
+        }
+    }
+}
+
+impl<S> CallArguments<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> CallArguments<T> {
+        CallArguments {
+            positional: self
+                .positional
+                .into_iter()
+                .map(|expression| expression.map_with(f))
+                .collect(),
+            named: self
+                .named
+                .into_iter()
+                .map(|argument| argument.map_with(f))
+                .collect(),
+        }
+    }
+}
+
+impl<S> NamedArgument<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> NamedArgument<T> {
+        NamedArgument {
+            name: self.name.map_with(f),
+            value: self.value.map_with(f),
+        }
+    }
+}
+
+impl<S> InlineExpression<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> InlineExpression<T> {
+        match self {
+            Self::StringLiteral { value } => InlineExpression::StringLiteral { value: f(value) },
+            Self::NumberLiteral { value } => InlineExpression::NumberLiteral { value: f(value) },
+            Self::FunctionReference { id, arguments } => InlineExpression::FunctionReference {
+                id: id.map_with(f),
+                arguments: arguments.map_with(f),
+            },
+            Self::MessageReference { id, attribute } => InlineExpression::MessageReference {
+                id: id.map_with(f),
+                attribute: attribute.map(|attribute| attribute.map_with(f)),
+            },
+            Self::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => InlineExpression::TermReference {
+                id: id.map_with(f),
+                attribute: attribute.map(|attribute| attribute.map_with(f)),
+                arguments: arguments.map(|arguments| arguments.map_with(f)),
+            },
+            Self::VariableReference { id } => {
+                InlineExpression::VariableReference { id: id.map_with(f) }
+            }
+            Self::Placeable { expression } => InlineExpression::Placeable {
+                expression: Box::new(expression.map_with(f)),
+            },
+            #[cfg(feature = "unknown-expressions")]
+            Self::Unknown { content } => InlineExpression::Unknown {
+                content: f(content),
+            },
+        }
+    }
+}
+
+impl<S> Expression<S> {
+    fn map_with<T, F: FnMut(S) -> T>(self, f: &mut F) -> Expression<T> {
+        match self {
+            Self::Select { selector, variants } => Expression::Select {
+                selector: selector.map_with(f),
+                variants: variants
+                    .into_iter()
+                    .map(|variant| variant.map_with(f))
+                    .collect(),
+            },
+            Self::Inline(expression) => Expression::Inline(expression.map_with(f)),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Merging of resources, such as a base resource with per-brand overrides.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast::merge::MergePolicy;
+//! use fluent_syntax::{parser, serializer};
+//!
+//! let base = parser::parse("-brand = Firefox\nhello = Hello from { -brand }!\n")
+//!     .expect("Failed to parse an FTL resource.");
+//! let overlay = parser::parse("-brand = Nightly\nbye = Bye!\n")
+//!     .expect("Failed to parse an FTL resource.");
+//!
+//! let merged = base
+//!     .merge(overlay, MergePolicy::OverlayWins)
+//!     .expect("Overlay wins every conflict.");
+//! assert_eq!(
+//!     serializer::serialize(&merged),
+//!     "-brand = Nightly\nhello = Hello from { -brand }!\nbye = Bye!\n"
+//! );
+//! ```
+use super::*;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use thiserror::Error;
+
+/// How [`Resource::merge`] resolves a message or a term defined in both
+/// resources.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergePolicy {
+    /// The entry of the overlay replaces the entry of the base.
+    OverlayWins,
+    /// The entry of the base is kept.
+    BaseWins,
+    /// The merge fails with a [`MergeError`].
+    Error,
+}
+
+/// A conflict found by [`Resource::merge`] with [`MergePolicy::Error`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum MergeError {
+    #[error("The message \"{0}\" is defined in both resources")]
+    MessageConflict(String),
+    #[error("The term \"-{0}\" is defined in both resources")]
+    TermConflict(String),
+}
+
+impl<S: AsRef<str>> Resource<S> {
+    /// Merges the entries of `overlay` into this resource.
+    ///
+    /// A message or a term defined in both resources keeps the position of
+    /// the base, and its content is chosen by `policy`. The winning entry
+    /// keeps its comment, or takes the comment of the other entry if it has
+    /// none. Other messages and terms of the overlay are appended in order,
+    /// along with the group comments and standalone comments before them;
+    /// standalone comments before a conflicting entry are dropped, since they
+    /// likely describe it. Resource comments of the overlay which the base
+    /// lacks are added after the last resource comment of the base. Junk is
+    /// appended like a new entry.
+    pub fn merge(self, overlay: Self, policy: MergePolicy) -> Result<Self, MergeError> {
+        let mut body = self.body;
+        let mut index: BTreeMap<Key, usize> = BTreeMap::new();
+        for (i, entry) in body.iter().enumerate() {
+            if let Some(key) = key(entry) {
+                index.insert(key, i);
+            }
+        }
+
+        let mut resource_comments = Vec::new();
+        let mut pending = Vec::new();
+        for entry in overlay.body {
+            let key = match &entry {
+                Entry::Message(_) | Entry::Term(_) => key(&entry),
+                Entry::ResourceComment(comment) => {
+                    let known = body
+                        .iter()
+                        .chain(&resource_comments)
+                        .any(|other| match other {
+                            Entry::ResourceComment(other) => same_comment(other, comment),
+                            _ => false,
+                        });
+                    if !known {
+                        resource_comments.push(entry);
+                    }
+                    continue;
+                }
+                Entry::Comment(_) | Entry::GroupComment(_) => {
+                    pending.push(entry);
+                    continue;
+                }
+                Entry::Junk { .. } => None,
+            };
+
+            match key {
+                Some(key) if index.contains_key(&key) => {
+                    let i = index[&key];
+                    match policy {
+                        MergePolicy::OverlayWins => {
+                            let base = core::mem::replace(&mut body[i], entry);
+                            inherit_comment(&mut body[i], base);
+                        }
+                        MergePolicy::BaseWins => inherit_comment(&mut body[i], entry),
+                        MergePolicy::Error => {
+                            return Err(match key {
+                                Key::Message(id) => MergeError::MessageConflict(id),
+                                Key::Term(id) => MergeError::TermConflict(id),
+                            });
+                        }
+                    }
+                    pending.retain(|entry| matches!(entry, Entry::GroupComment(_)));
+                }
+                key => {
+                    body.append(&mut pending);
+                    if let Some(key) = key {
+                        index.insert(key, body.len());
+                    }
+                    body.push(entry);
+                }
+            }
+        }
+        body.append(&mut pending);
+
+        let position = body
+            .iter()
+            .rposition(|entry| matches!(entry, Entry::ResourceComment(_)))
+            .map_or(0, |i| i + 1);
+        body.splice(position..position, resource_comments);
+        Ok(Self { body })
+    }
+}
+
+/// Messages and terms have separate namespaces.
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Key {
+    Message(String),
+    Term(String),
+}
+
+fn key<S: AsRef<str>>(entry: &Entry<S>) -> Option<Key> {
+    match entry {
+        Entry::Message(message) => Some(Key::Message(message.id.name.as_ref().to_string())),
+        Entry::Term(term) => Some(Key::Term(term.id.name.as_ref().to_string())),
+        _ => None,
+    }
+}
+
+fn same_comment<S: AsRef<str>>(a: &Comment<S>, b: &Comment<S>) -> bool {
+    a.content.len() == b.content.len()
+        && a.content
+            .iter()
+            .zip(&b.content)
+            .all(|(a, b)| a.as_ref() == b.as_ref())
+}
+
+/// Gives `winner` the comment of `loser` if it has none.
+fn inherit_comment<S>(winner: &mut Entry<S>, loser: Entry<S>) {
+    match (winner, loser) {
+        (Entry::Message(winner), Entry::Message(loser)) if winner.comment.is_none() => {
+            winner.comment = loser.comment;
+        }
+        (Entry::Term(winner), Entry::Term(loser)) if winner.comment.is_none() => {
+            winner.comment = loser.comment;
+        }
+        _ => {}
+    }
+}
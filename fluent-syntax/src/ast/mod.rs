@@ -79,13 +79,46 @@
 //! ## Errors
 //!
 //! Fluent AST preserves blocks containing invalid syntax as [`Entry::Junk`].
+//! When the parser recovers from an invalid placeable, with the
+//! `recover-placeables` feature, it is preserved as `PatternElement::Junk`
+//! instead.
 //!
 //! ## White space
 //!
 //! At the moment, AST does not preserve white space. In result only a
 //! canonical form of the AST is suitable for a round-trip.
+//!
+//! ## Storage
+//!
+//! The nodes are generic over the storage of the slices of the source, `&str`
+//! when parsing a borrowed input. Every node has `map_source`, which converts
+//! each of its slices with a function, and `into_owned`, which copies them into
+//! `String`s, for example to keep a resource after its source is dropped.
+//!
+//! ```
+//! use fluent_syntax::{ast, parser};
+//! use std::sync::Arc;
+//!
+//! let resource: ast::Resource<String> = {
+//!     let source = String::from("hello = Hello, { $user }!");
+//!     parser::parse(source.as_str())
+//!         .expect("Failed to parse an FTL resource.")
+//!         .into_owned()
+//! };
+//!
+//! let shared: ast::Resource<Arc<str>> = resource.map_source(Arc::from);
+//! ```
+pub mod annotation;
+pub mod builder;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
 mod helper;
+mod map;
+pub mod merge;
+pub mod visit;
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -113,6 +146,7 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Resource<S> {
     pub body: Vec<Entry<S>>,
 }
@@ -196,6 +230,7 @@ pub struct Resource<S> {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Entry<S> {
     Message(Message<S>),
     Term(Term<S>),
@@ -255,6 +290,7 @@ pub enum Entry<S> {
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Message<S> {
     pub id: Identifier<S>,
     pub value: Option<Pattern<S>>,
@@ -309,6 +345,7 @@ pub struct Message<S> {
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Term<S> {
     pub id: Identifier<S>,
     pub value: Pattern<S>,
@@ -389,6 +426,7 @@ pub struct Term<S> {
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Pattern<S> {
     pub elements: Vec<PatternElement<S>>,
 }
@@ -467,9 +505,22 @@ pub struct Pattern<S> {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum PatternElement<S> {
-    TextElement { value: S },
-    Placeable { expression: Expression<S> },
+    TextElement {
+        value: S,
+    },
+    Placeable {
+        expression: Expression<S>,
+    },
+    /// An invalid placeable, kept as it is in the source by
+    /// [`parse_with_options`](crate::parser::parse_with_options) with
+    /// [`recover_placeables`](crate::parser::ParserOptions::recover_placeables).
+    #[cfg(feature = "recover-placeables")]
+    Junk {
+        content: S,
+    },
 }
 
 /// Attribute represents a part of a [`Message`] or [`Term`].
@@ -537,6 +588,7 @@ pub enum PatternElement<S> {
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Attribute<S> {
     pub id: Identifier<S>,
     pub value: Pattern<S>,
@@ -586,6 +638,7 @@ pub struct Attribute<S> {
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Identifier<S> {
     pub name: S,
 }
@@ -670,6 +723,7 @@ pub struct Identifier<S> {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Variant<S> {
     pub key: VariantKey<S>,
     pub value: Pattern<S>,
@@ -755,6 +809,7 @@ pub struct Variant<S> {
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum VariantKey<S> {
     Identifier { name: S },
     NumberLiteral { value: S },
@@ -801,6 +856,7 @@ pub enum VariantKey<S> {
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(from = "helper::CommentDef<S>"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Comment<S> {
     pub content: Vec<S>,
 }
@@ -882,6 +938,7 @@ pub struct Comment<S> {
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CallArguments<S> {
     pub positional: Vec<InlineExpression<S>>,
     pub named: Vec<NamedArgument<S>>,
@@ -951,6 +1008,7 @@ pub struct CallArguments<S> {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct NamedArgument<S> {
     pub name: Identifier<S>,
     pub value: InlineExpression<S>,
@@ -1007,6 +1065,8 @@ pub struct NamedArgument<S> {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum InlineExpression<S> {
     /// Single line string literal enclosed in `"`.
     ///
@@ -1358,6 +1418,16 @@ pub enum InlineExpression<S> {
     /// );
     /// ```
     Placeable { expression: Box<Expression<S>> },
+    /// The content of a placeable which could not be parsed, without the
+    /// braces and the blanks around it, kept by
+    /// [`parse_with_options`](crate::parser::parse_with_options) with
+    /// [`unknown_expressions`](crate::parser::ParserOptions::unknown_expressions).
+    ///
+    /// This is syntax of a later version of Fluent as far as this parser can
+    /// tell, so the rest of the message, including the select expression
+    /// around the placeable, stays usable.
+    #[cfg(feature = "unknown-expressions")]
+    Unknown { content: S },
 }
 
 /// An expression that is either a select expression or an inline expression.
@@ -1437,6 +1507,7 @@ pub enum InlineExpression<S> {
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Expression<S> {
     /// A select expression such as:
     /// ```ftl
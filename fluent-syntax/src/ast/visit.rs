@@ -0,0 +1,514 @@
+//! Traversal of the AST.
+//!
+//! [`Visit`] walks a tree by reference, and [`VisitMut`] by mutable reference.
+//! Each has a method per node type, whose default implementation calls the
+//! matching `walk_*` function to visit the children of the node. An
+//! implementation overrides the methods of the nodes it cares about, and calls
+//! the `walk_*` function from them to keep visiting the children.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast;
+//! use fluent_syntax::ast::visit::{self, Visit, VisitMut};
+//! use fluent_syntax::parser;
+//!
+//! /// Collects the names of the variables.
+//! struct Variables<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visit<'ast, &'ast str> for Variables<'ast> {
+//!     fn visit_inline_expression(&mut self, node: &'ast ast::InlineExpression<&'ast str>) {
+//!         if let ast::InlineExpression::VariableReference { id } = node {
+//!             self.0.push(id.name);
+//!         }
+//!         visit::walk_inline_expression(self, node);
+//!     }
+//! }
+//!
+//! /// Renames a message, and the references to it.
+//! struct Rename;
+//!
+//! impl<'s> VisitMut<&'s str> for Rename {
+//!     fn visit_identifier_mut(&mut self, node: &mut ast::Identifier<&'s str>) {
+//!         if node.name == "brand" {
+//!             node.name = "product";
+//!         }
+//!     }
+//! }
+//!
+//! let ftl = "brand = Firefox\nwelcome = Welcome to { brand }, { $user } ({ $email })!\n";
+//! let mut resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+//!
+//! let mut variables = Variables(vec![]);
+//! variables.visit_resource(&resource);
+//! assert_eq!(variables.0, ["user", "email"]);
+//!
+//! Rename.visit_resource_mut(&mut resource);
+//! let serialized = fluent_syntax::serializer::serialize(&resource);
+//! assert_eq!(
+//!     serialized,
+//!     "product = Firefox\nwelcome = Welcome to { product }, { $user } ({ $email })!\n"
+//! );
+//! ```
+//!
+//! Identifiers are visited wherever they appear: as the names of messages,
+//! terms and attributes, in references, and as the names of named arguments.
+use super::*;
+
+/// Visits the nodes of an AST by reference.
+///
+/// See the [module documentation](self) for an example.
+pub trait Visit<'ast, S: 'ast> {
+    fn visit_resource(&mut self, node: &'ast Resource<S>) {
+        walk_resource(self, node);
+    }
+
+    fn visit_entry(&mut self, node: &'ast Entry<S>) {
+        walk_entry(self, node);
+    }
+
+    fn visit_message(&mut self, node: &'ast Message<S>) {
+        walk_message(self, node);
+    }
+
+    fn visit_term(&mut self, node: &'ast Term<S>) {
+        walk_term(self, node);
+    }
+
+    /// Visits a comment, either free or attached to a message or a term.
+    fn visit_comment(&mut self, _node: &'ast Comment<S>) {}
+
+    /// Visits the content of [`Entry::Junk`].
+    fn visit_junk(&mut self, _content: &'ast S) {}
+
+    fn visit_attribute(&mut self, node: &'ast Attribute<S>) {
+        walk_attribute(self, node);
+    }
+
+    fn visit_identifier(&mut self, _node: &'ast Identifier<S>) {}
+
+    fn visit_pattern(&mut self, node: &'ast Pattern<S>) {
+        walk_pattern(self, node);
+    }
+
+    fn visit_pattern_element(&mut self, node: &'ast PatternElement<S>) {
+        walk_pattern_element(self, node);
+    }
+
+    /// Visits the value of [`PatternElement::TextElement`].
+    fn visit_text_element(&mut self, _value: &'ast S) {}
+
+    fn visit_expression(&mut self, node: &'ast Expression<S>) {
+        walk_expression(self, node);
+    }
+
+    fn visit_variant(&mut self, node: &'ast Variant<S>) {
+        walk_variant(self, node);
+    }
+
+    fn visit_variant_key(&mut self, _node: &'ast VariantKey<S>) {}
+
+    fn visit_inline_expression(&mut self, node: &'ast InlineExpression<S>) {
+        walk_inline_expression(self, node);
+    }
+
+    fn visit_call_arguments(&mut self, node: &'ast CallArguments<S>) {
+        walk_call_arguments(self, node);
+    }
+
+    fn visit_named_argument(&mut self, node: &'ast NamedArgument<S>) {
+        walk_named_argument(self, node);
+    }
+}
+
+pub fn walk_resource<'ast, S, V>(visitor: &mut V, node: &'ast Resource<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    for entry in &node.body {
+        visitor.visit_entry(entry);
+    }
+}
+
+pub fn walk_entry<'ast, S, V>(visitor: &mut V, node: &'ast Entry<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    match node {
+        Entry::Message(message) => visitor.visit_message(message),
+        Entry::Term(term) => visitor.visit_term(term),
+        Entry::Comment(comment)
+        | Entry::GroupComment(comment)
+        | Entry::ResourceComment(comment) => {
+            visitor.visit_comment(comment);
+        }
+        Entry::Junk { content } => visitor.visit_junk(content),
+    }
+}
+
+pub fn walk_message<'ast, S, V>(visitor: &mut V, node: &'ast Message<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    if let Some(comment) = &node.comment {
+        visitor.visit_comment(comment);
+    }
+    visitor.visit_identifier(&node.id);
+    if let Some(value) = &node.value {
+        visitor.visit_pattern(value);
+    }
+    for attribute in &node.attributes {
+        visitor.visit_attribute(attribute);
+    }
+}
+
+pub fn walk_term<'ast, S, V>(visitor: &mut V, node: &'ast Term<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    if let Some(comment) = &node.comment {
+        visitor.visit_comment(comment);
+    }
+    visitor.visit_identifier(&node.id);
+    visitor.visit_pattern(&node.value);
+    for attribute in &node.attributes {
+        visitor.visit_attribute(attribute);
+    }
+}
+
+pub fn walk_attribute<'ast, S, V>(visitor: &mut V, node: &'ast Attribute<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    visitor.visit_identifier(&node.id);
+    visitor.visit_pattern(&node.value);
+}
+
+pub fn walk_pattern<'ast, S, V>(visitor: &mut V, node: &'ast Pattern<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    for element in &node.elements {
+        visitor.visit_pattern_element(element);
+    }
+}
+
+pub fn walk_pattern_element<'ast, S, V>(visitor: &mut V, node: &'ast PatternElement<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    match node {
+        PatternElement::TextElement { value } => visitor.visit_text_element(value),
+        PatternElement::Placeable { expression } => visitor.visit_expression(expression),
+        #[cfg(feature = "recover-placeables")]
+        PatternElement::Junk { content } => visitor.visit_junk(content),
+    }
+}
+
+pub fn walk_expression<'ast, S, V>(visitor: &mut V, node: &'ast Expression<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    match node {
+        Expression::Select { selector, variants } => {
+            visitor.visit_inline_expression(selector);
+            for variant in variants {
+                visitor.visit_variant(variant);
+            }
+        }
+        Expression::Inline(expression) => visitor.visit_inline_expression(expression),
+    }
+}
+
+pub fn walk_variant<'ast, S, V>(visitor: &mut V, node: &'ast Variant<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    visitor.visit_variant_key(&node.key);
+    visitor.visit_pattern(&node.value);
+}
+
+pub fn walk_inline_expression<'ast, S, V>(visitor: &mut V, node: &'ast InlineExpression<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    match node {
+        InlineExpression::StringLiteral { .. } | InlineExpression::NumberLiteral { .. } => {}
+        #[cfg(feature = "unknown-expressions")]
+        InlineExpression::Unknown { .. } => {}
+        InlineExpression::FunctionReference { id, arguments } => {
+            visitor.visit_identifier(id);
+            visitor.visit_call_arguments(arguments);
+        }
+        InlineExpression::MessageReference { id, attribute } => {
+            visitor.visit_identifier(id);
+            if let Some(attribute) = attribute {
+                visitor.visit_identifier(attribute);
+            }
+        }
+        InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            visitor.visit_identifier(id);
+            if let Some(attribute) = attribute {
+                visitor.visit_identifier(attribute);
+            }
+            if let Some(arguments) = arguments {
+                visitor.visit_call_arguments(arguments);
+            }
+        }
+        InlineExpression::VariableReference { id } => visitor.visit_identifier(id),
+        InlineExpression::Placeable { expression } => visitor.visit_expression(expression),
+    }
+}
+
+pub fn walk_call_arguments<'ast, S, V>(visitor: &mut V, node: &'ast CallArguments<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    for argument in &node.positional {
+        visitor.visit_inline_expression(argument);
+    }
+    for argument in &node.named {
+        visitor.visit_named_argument(argument);
+    }
+}
+
+pub fn walk_named_argument<'ast, S, V>(visitor: &mut V, node: &'ast NamedArgument<S>)
+where
+    V: Visit<'ast, S> + ?Sized,
+{
+    visitor.visit_identifier(&node.name);
+    visitor.visit_inline_expression(&node.value);
+}
+
+/// Visits the nodes of an AST by mutable reference.
+///
+/// See the [module documentation](self) for an example.
+pub trait VisitMut<S> {
+    fn visit_resource_mut(&mut self, node: &mut Resource<S>) {
+        walk_resource_mut(self, node);
+    }
+
+    fn visit_entry_mut(&mut self, node: &mut Entry<S>) {
+        walk_entry_mut(self, node);
+    }
+
+    fn visit_message_mut(&mut self, node: &mut Message<S>) {
+        walk_message_mut(self, node);
+    }
+
+    fn visit_term_mut(&mut self, node: &mut Term<S>) {
+        walk_term_mut(self, node);
+    }
+
+    /// Visits a comment, either free or attached to a message or a term.
+    fn visit_comment_mut(&mut self, _node: &mut Comment<S>) {}
+
+    /// Visits the content of [`Entry::Junk`].
+    fn visit_junk_mut(&mut self, _content: &mut S) {}
+
+    fn visit_attribute_mut(&mut self, node: &mut Attribute<S>) {
+        walk_attribute_mut(self, node);
+    }
+
+    fn visit_identifier_mut(&mut self, _node: &mut Identifier<S>) {}
+
+    fn visit_pattern_mut(&mut self, node: &mut Pattern<S>) {
+        walk_pattern_mut(self, node);
+    }
+
+    fn visit_pattern_element_mut(&mut self, node: &mut PatternElement<S>) {
+        walk_pattern_element_mut(self, node);
+    }
+
+    /// Visits the value of [`PatternElement::TextElement`].
+    fn visit_text_element_mut(&mut self, _value: &mut S) {}
+
+    fn visit_expression_mut(&mut self, node: &mut Expression<S>) {
+        walk_expression_mut(self, node);
+    }
+
+    fn visit_variant_mut(&mut self, node: &mut Variant<S>) {
+        walk_variant_mut(self, node);
+    }
+
+    fn visit_variant_key_mut(&mut self, _node: &mut VariantKey<S>) {}
+
+    fn visit_inline_expression_mut(&mut self, node: &mut InlineExpression<S>) {
+        walk_inline_expression_mut(self, node);
+    }
+
+    fn visit_call_arguments_mut(&mut self, node: &mut CallArguments<S>) {
+        walk_call_arguments_mut(self, node);
+    }
+
+    fn visit_named_argument_mut(&mut self, node: &mut NamedArgument<S>) {
+        walk_named_argument_mut(self, node);
+    }
+}
+
+pub fn walk_resource_mut<S, V>(visitor: &mut V, node: &mut Resource<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    for entry in &mut node.body {
+        visitor.visit_entry_mut(entry);
+    }
+}
+
+pub fn walk_entry_mut<S, V>(visitor: &mut V, node: &mut Entry<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    match node {
+        Entry::Message(message) => visitor.visit_message_mut(message),
+        Entry::Term(term) => visitor.visit_term_mut(term),
+        Entry::Comment(comment)
+        | Entry::GroupComment(comment)
+        | Entry::ResourceComment(comment) => {
+            visitor.visit_comment_mut(comment);
+        }
+        Entry::Junk { content } => visitor.visit_junk_mut(content),
+    }
+}
+
+pub fn walk_message_mut<S, V>(visitor: &mut V, node: &mut Message<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    if let Some(comment) = &mut node.comment {
+        visitor.visit_comment_mut(comment);
+    }
+    visitor.visit_identifier_mut(&mut node.id);
+    if let Some(value) = &mut node.value {
+        visitor.visit_pattern_mut(value);
+    }
+    for attribute in &mut node.attributes {
+        visitor.visit_attribute_mut(attribute);
+    }
+}
+
+pub fn walk_term_mut<S, V>(visitor: &mut V, node: &mut Term<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    if let Some(comment) = &mut node.comment {
+        visitor.visit_comment_mut(comment);
+    }
+    visitor.visit_identifier_mut(&mut node.id);
+    visitor.visit_pattern_mut(&mut node.value);
+    for attribute in &mut node.attributes {
+        visitor.visit_attribute_mut(attribute);
+    }
+}
+
+pub fn walk_attribute_mut<S, V>(visitor: &mut V, node: &mut Attribute<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    visitor.visit_identifier_mut(&mut node.id);
+    visitor.visit_pattern_mut(&mut node.value);
+}
+
+pub fn walk_pattern_mut<S, V>(visitor: &mut V, node: &mut Pattern<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    for element in &mut node.elements {
+        visitor.visit_pattern_element_mut(element);
+    }
+}
+
+pub fn walk_pattern_element_mut<S, V>(visitor: &mut V, node: &mut PatternElement<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    match node {
+        PatternElement::TextElement { value } => visitor.visit_text_element_mut(value),
+        PatternElement::Placeable { expression } => visitor.visit_expression_mut(expression),
+        #[cfg(feature = "recover-placeables")]
+        PatternElement::Junk { content } => visitor.visit_junk_mut(content),
+    }
+}
+
+pub fn walk_expression_mut<S, V>(visitor: &mut V, node: &mut Expression<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    match node {
+        Expression::Select { selector, variants } => {
+            visitor.visit_inline_expression_mut(selector);
+            for variant in variants {
+                visitor.visit_variant_mut(variant);
+            }
+        }
+        Expression::Inline(expression) => visitor.visit_inline_expression_mut(expression),
+    }
+}
+
+pub fn walk_variant_mut<S, V>(visitor: &mut V, node: &mut Variant<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    visitor.visit_variant_key_mut(&mut node.key);
+    visitor.visit_pattern_mut(&mut node.value);
+}
+
+pub fn walk_inline_expression_mut<S, V>(visitor: &mut V, node: &mut InlineExpression<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    match node {
+        InlineExpression::StringLiteral { .. } | InlineExpression::NumberLiteral { .. } => {}
+        #[cfg(feature = "unknown-expressions")]
+        InlineExpression::Unknown { .. } => {}
+        InlineExpression::FunctionReference { id, arguments } => {
+            visitor.visit_identifier_mut(id);
+            visitor.visit_call_arguments_mut(arguments);
+        }
+        InlineExpression::MessageReference { id, attribute } => {
+            visitor.visit_identifier_mut(id);
+            if let Some(attribute) = attribute {
+                visitor.visit_identifier_mut(attribute);
+            }
+        }
+        InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            visitor.visit_identifier_mut(id);
+            if let Some(attribute) = attribute {
+                visitor.visit_identifier_mut(attribute);
+            }
+            if let Some(arguments) = arguments {
+                visitor.visit_call_arguments_mut(arguments);
+            }
+        }
+        InlineExpression::VariableReference { id } => visitor.visit_identifier_mut(id),
+        InlineExpression::Placeable { expression } => visitor.visit_expression_mut(expression),
+    }
+}
+
+pub fn walk_call_arguments_mut<S, V>(visitor: &mut V, node: &mut CallArguments<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    for argument in &mut node.positional {
+        visitor.visit_inline_expression_mut(argument);
+    }
+    for argument in &mut node.named {
+        visitor.visit_named_argument_mut(argument);
+    }
+}
+
+pub fn walk_named_argument_mut<S, V>(visitor: &mut V, node: &mut NamedArgument<S>)
+where
+    V: VisitMut<S> + ?Sized,
+{
+    visitor.visit_identifier_mut(&mut node.name);
+    visitor.visit_inline_expression_mut(&mut node.value);
+}
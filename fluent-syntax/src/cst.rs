@@ -0,0 +1,499 @@
+//! A lossless concrete syntax tree of Fluent Translation Lists.
+//!
+//! The [`ast`] drops everything which does not carry meaning: blank lines,
+//! indentation, the spacing around `=` and within placeables, and the exact
+//! form of line endings. Formatters and refactoring tools need all of it, so
+//! [`parse`] returns a tree which covers every byte of the source, and whose
+//! [`Display`](core::fmt::Display) implementation prints the source back
+//! unchanged.
+//!
+//! The leaves of the tree are [`SyntaxToken`]s: the text of identifiers,
+//! literals, text elements, comments and junk, the syntax characters such as
+//! `=` or `->`, and trivia, which is whitespace and line endings. Trivia is
+//! attached to the closest node: the trivia which follows a node up to the end
+//! of its line is its trailing trivia, and the trivia which precedes a node is
+//! its leading trivia. Blank lines between entries are thus the leading trivia
+//! of the entry which follows them.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::cst::{self, SyntaxKind};
+//!
+//! let ftl = "hello   =  Hello, { $user }!\n\n\nbye = Bye\n";
+//!
+//! let tree = cst::parse(ftl).expect("Failed to parse an FTL resource.");
+//! assert_eq!(tree.to_string(), ftl);
+//!
+//! let bye = tree.nodes().nth(1).expect("Resource has two entries.");
+//! assert_eq!(bye.kind(), SyntaxKind::Message);
+//! let blank_lines: String = bye.leading_trivia().iter().map(|t| t.text()).collect();
+//! assert_eq!(blank_lines, "\n\n");
+//! ```
+use crate::ast;
+use crate::parser::{self, ParserError, SpannedNode, SpannedResource};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// Parses `source` into a lossless syntax tree.
+///
+/// Like [`parser::parse`], this returns the tree along with the errors if the
+/// source is invalid. Invalid entries are [`SyntaxKind::Junk`], so the tree
+/// covers the whole source in both cases.
+pub fn parse(source: &str) -> Result<SyntaxNode<'_>, (SyntaxNode<'_>, Vec<ParserError>)> {
+    match parser::parse_with_spans(source) {
+        Ok(spanned) => Ok(Builder::build(source, &spanned)),
+        Err((spanned, errors)) => Err((Builder::build(source, &spanned), errors)),
+    }
+}
+
+/// The kind of a [`SyntaxNode`], which mirrors the node of the [`ast`] it
+/// represents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SyntaxKind {
+    Resource,
+    Message,
+    Term,
+    /// A comment starting with `#`, either standalone or attached to a
+    /// message or a term.
+    Comment,
+    GroupComment,
+    ResourceComment,
+    Junk,
+    Attribute,
+    Identifier,
+    Pattern,
+    TextElement,
+    /// A placeable in a pattern, or nested in another placeable, including
+    /// its braces.
+    Placeable,
+    SelectExpression,
+    Variant,
+    VariantKey,
+    StringLiteral,
+    NumberLiteral,
+    FunctionReference,
+    MessageReference,
+    TermReference,
+    VariableReference,
+    CallArguments,
+    NamedArgument,
+}
+
+/// The kind of a [`SyntaxToken`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A run of spaces or tabs.
+    Whitespace,
+    /// A line ending, either `\n` or `\r\n`.
+    LineEnd,
+    /// A run of syntax characters, such as `=`, `->` or `*[`.
+    Punctuation,
+    /// The text of a leaf node, such as an identifier or a text element.
+    Text,
+}
+
+impl TokenKind {
+    /// Returns `true` for whitespace and line endings.
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Self::Whitespace | Self::LineEnd)
+    }
+}
+
+/// A slice of the source, which is a leaf of the tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxToken<'s> {
+    kind: TokenKind,
+    text: &'s str,
+    offset: usize,
+}
+
+impl<'s> SyntaxToken<'s> {
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    pub fn text(&self) -> &'s str {
+        self.text
+    }
+
+    /// Returns the byte range of the token in the source.
+    pub fn span(&self) -> Range<usize> {
+        self.offset..self.offset + self.text.len()
+    }
+}
+
+/// A child of a [`SyntaxNode`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyntaxElement<'s> {
+    Node(SyntaxNode<'s>),
+    Token(SyntaxToken<'s>),
+}
+
+/// A node of the tree, along with the trivia attached to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxNode<'s> {
+    kind: SyntaxKind,
+    span: Range<usize>,
+    leading_trivia: Vec<SyntaxToken<'s>>,
+    children: Vec<SyntaxElement<'s>>,
+    trailing_trivia: Vec<SyntaxToken<'s>>,
+}
+
+impl<'s> SyntaxNode<'s> {
+    pub fn kind(&self) -> SyntaxKind {
+        self.kind
+    }
+
+    /// Returns the byte range of the node in the source, which excludes its
+    /// leading and trailing trivia.
+    ///
+    /// Spans follow the conventions of
+    /// [`SpannedResource::span`](parser::SpannedResource::span).
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the trivia between the node and the previous token.
+    pub fn leading_trivia(&self) -> &[SyntaxToken<'s>] {
+        &self.leading_trivia
+    }
+
+    /// Returns the trivia between the node and the end of its line, including
+    /// the line ending.
+    pub fn trailing_trivia(&self) -> &[SyntaxToken<'s>] {
+        &self.trailing_trivia
+    }
+
+    /// Returns the children of the node: its child nodes, and the tokens
+    /// between them which are not attached to them.
+    pub fn children(&self) -> &[SyntaxElement<'s>] {
+        &self.children
+    }
+
+    /// Returns the child nodes of the node.
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode<'s>> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Returns the tokens of the node in source order, including its trivia.
+    pub fn tokens(&self) -> vec::IntoIter<&SyntaxToken<'s>> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        tokens.into_iter()
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken<'s>>) {
+        tokens.extend(&self.leading_trivia);
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+        tokens.extend(&self.trailing_trivia);
+    }
+}
+
+/// Prints the source of the node, including its trivia.
+impl fmt::Display for SyntaxNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for token in self.tokens() {
+            f.write_str(token.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds the tree from the spans of the nodes of the AST, filling the gaps
+/// between the children of each node with tokens.
+struct Builder<'a, 's> {
+    source: &'s str,
+    spanned: &'a SpannedResource<&'s str>,
+}
+
+impl<'a, 's> Builder<'a, 's> {
+    fn build(source: &'s str, spanned: &'a SpannedResource<&'s str>) -> SyntaxNode<'s> {
+        let builder = Builder { source, spanned };
+        let entries = spanned
+            .resource()
+            .body
+            .iter()
+            .map(|entry| builder.entry(entry))
+            .collect();
+        builder.node(SyntaxKind::Resource, 0..source.len(), entries)
+    }
+
+    fn span<N: SpannedNode>(&self, node: &N) -> Range<usize> {
+        let span = self.spanned.span(node);
+        debug_assert!(span.is_some(), "Missing span");
+        span.unwrap_or_default()
+    }
+
+    /// Returns a node whose children are `nodes` and the tokens around them.
+    fn node(
+        &self,
+        kind: SyntaxKind,
+        span: Range<usize>,
+        nodes: Vec<SyntaxNode<'s>>,
+    ) -> SyntaxNode<'s> {
+        let mut children = Vec::with_capacity(nodes.len() * 2 + 1);
+        let mut pos = span.start;
+        for mut node in nodes {
+            let start = node.span.start;
+            self.fill(&mut children, pos..start, Some(&mut node));
+            pos = node.span.end;
+            children.push(SyntaxElement::Node(node));
+        }
+        self.fill(&mut children, pos..span.end, None);
+        SyntaxNode {
+            kind,
+            span,
+            leading_trivia: Vec::new(),
+            children,
+            trailing_trivia: Vec::new(),
+        }
+    }
+
+    /// Returns a node whose only child is its text.
+    fn leaf(&self, kind: SyntaxKind, span: Range<usize>) -> SyntaxNode<'s> {
+        let mut children = Vec::new();
+        if !span.is_empty() {
+            children.push(SyntaxElement::Token(SyntaxToken {
+                kind: TokenKind::Text,
+                text: &self.source[span.clone()],
+                offset: span.start,
+            }));
+        }
+        SyntaxNode {
+            kind,
+            span,
+            leading_trivia: Vec::new(),
+            children,
+            trailing_trivia: Vec::new(),
+        }
+    }
+
+    /// Adds the tokens of `range`, which lies between the last child and
+    /// `next`, attaching the trivia to them.
+    fn fill(
+        &self,
+        children: &mut Vec<SyntaxElement<'s>>,
+        range: Range<usize>,
+        next: Option<&mut SyntaxNode<'s>>,
+    ) {
+        let mut tokens = self.tokenize(range.clone());
+        // A node which ends a line, such as the `\n` of a multiline pattern,
+        // has no trailing trivia.
+        if !self.source[..range.start].ends_with('\n') {
+            if let Some(SyntaxElement::Node(previous)) = children.last_mut() {
+                let mut end = 0;
+                for token in &tokens {
+                    if !token.kind.is_trivia() {
+                        break;
+                    }
+                    end += 1;
+                    if token.kind == TokenKind::LineEnd {
+                        break;
+                    }
+                }
+                previous.trailing_trivia.extend(tokens.drain(..end));
+            }
+        }
+        if let Some(next) = next {
+            let start = tokens
+                .iter()
+                .rposition(|token| !token.kind.is_trivia())
+                .map_or(0, |pos| pos + 1);
+            next.leading_trivia = tokens.split_off(start);
+        }
+        children.extend(tokens.into_iter().map(SyntaxElement::Token));
+    }
+
+    fn tokenize(&self, range: Range<usize>) -> Vec<SyntaxToken<'s>> {
+        let bytes = self.source.as_bytes();
+        let mut tokens = Vec::new();
+        let mut pos = range.start;
+        while pos < range.end {
+            let start = pos;
+            let kind = match bytes[pos] {
+                b' ' | b'\t' => {
+                    while pos < range.end && matches!(bytes[pos], b' ' | b'\t') {
+                        pos += 1;
+                    }
+                    TokenKind::Whitespace
+                }
+                b'\n' => {
+                    pos += 1;
+                    TokenKind::LineEnd
+                }
+                b'\r' if bytes.get(pos + 1) == Some(&b'\n') && pos + 1 < range.end => {
+                    pos += 2;
+                    TokenKind::LineEnd
+                }
+                _ => {
+                    pos += 1;
+                    while pos < range.end
+                        && !matches!(bytes[pos], b' ' | b'\t' | b'\n')
+                        && !bytes[pos..].starts_with(b"\r\n")
+                    {
+                        pos += 1;
+                    }
+                    TokenKind::Punctuation
+                }
+            };
+            tokens.push(SyntaxToken {
+                kind,
+                text: &self.source[start..pos],
+                offset: start,
+            });
+        }
+        tokens
+    }
+
+    fn entry(&self, entry: &ast::Entry<&'s str>) -> SyntaxNode<'s> {
+        match entry {
+            ast::Entry::Message(message) => {
+                let mut nodes = Vec::new();
+                if let Some(comment) = &message.comment {
+                    nodes.push(self.leaf(SyntaxKind::Comment, self.span(comment)));
+                }
+                nodes.push(self.identifier(&message.id));
+                if let Some(value) = &message.value {
+                    nodes.push(self.pattern(value));
+                }
+                nodes.extend(message.attributes.iter().map(|a| self.attribute(a)));
+                self.node(SyntaxKind::Message, self.span(message), nodes)
+            }
+            ast::Entry::Term(term) => {
+                let mut nodes = Vec::new();
+                if let Some(comment) = &term.comment {
+                    nodes.push(self.leaf(SyntaxKind::Comment, self.span(comment)));
+                }
+                nodes.push(self.identifier(&term.id));
+                nodes.push(self.pattern(&term.value));
+                nodes.extend(term.attributes.iter().map(|a| self.attribute(a)));
+                self.node(SyntaxKind::Term, self.span(term), nodes)
+            }
+            ast::Entry::Comment(comment) => self.leaf(SyntaxKind::Comment, self.span(comment)),
+            ast::Entry::GroupComment(comment) => {
+                self.leaf(SyntaxKind::GroupComment, self.span(comment))
+            }
+            ast::Entry::ResourceComment(comment) => {
+                self.leaf(SyntaxKind::ResourceComment, self.span(comment))
+            }
+            ast::Entry::Junk { .. } => self.leaf(SyntaxKind::Junk, self.span(entry)),
+        }
+    }
+
+    fn identifier(&self, id: &ast::Identifier<&'s str>) -> SyntaxNode<'s> {
+        self.leaf(SyntaxKind::Identifier, self.span(id))
+    }
+
+    fn attribute(&self, attribute: &ast::Attribute<&'s str>) -> SyntaxNode<'s> {
+        let nodes = vec![
+            self.identifier(&attribute.id),
+            self.pattern(&attribute.value),
+        ];
+        self.node(SyntaxKind::Attribute, self.span(attribute), nodes)
+    }
+
+    fn pattern(&self, pattern: &ast::Pattern<&'s str>) -> SyntaxNode<'s> {
+        let nodes = pattern
+            .elements
+            .iter()
+            .map(|element| match element {
+                ast::PatternElement::TextElement { .. } => {
+                    self.leaf(SyntaxKind::TextElement, self.span(element))
+                }
+                ast::PatternElement::Placeable { expression } => self.node(
+                    SyntaxKind::Placeable,
+                    self.span(element),
+                    vec![self.expression(expression)],
+                ),
+                #[cfg(feature = "recover-placeables")]
+                ast::PatternElement::Junk { .. } => self.leaf(SyntaxKind::Junk, self.span(element)),
+            })
+            .collect();
+        self.node(SyntaxKind::Pattern, self.span(pattern), nodes)
+    }
+
+    fn expression(&self, expression: &ast::Expression<&'s str>) -> SyntaxNode<'s> {
+        match expression {
+            ast::Expression::Select { selector, variants } => {
+                let mut nodes = vec![self.inline_expression(selector)];
+                nodes.extend(variants.iter().map(|variant| {
+                    let key = self.leaf(SyntaxKind::VariantKey, self.span(&variant.key));
+                    let nodes = vec![key, self.pattern(&variant.value)];
+                    self.node(SyntaxKind::Variant, self.span(variant), nodes)
+                }));
+                self.node(SyntaxKind::SelectExpression, self.span(expression), nodes)
+            }
+            ast::Expression::Inline(expression) => self.inline_expression(expression),
+        }
+    }
+
+    fn inline_expression(&self, expression: &ast::InlineExpression<&'s str>) -> SyntaxNode<'s> {
+        let span = self.span(expression);
+        match expression {
+            ast::InlineExpression::StringLiteral { .. } => {
+                self.leaf(SyntaxKind::StringLiteral, span)
+            }
+            ast::InlineExpression::NumberLiteral { .. } => {
+                self.leaf(SyntaxKind::NumberLiteral, span)
+            }
+            ast::InlineExpression::FunctionReference { id, arguments } => {
+                let nodes = vec![self.identifier(id), self.call_arguments(arguments)];
+                self.node(SyntaxKind::FunctionReference, span, nodes)
+            }
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                let mut nodes = vec![self.identifier(id)];
+                nodes.extend(attribute.as_ref().map(|a| self.identifier(a)));
+                self.node(SyntaxKind::MessageReference, span, nodes)
+            }
+            ast::InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                let mut nodes = vec![self.identifier(id)];
+                nodes.extend(attribute.as_ref().map(|a| self.identifier(a)));
+                nodes.extend(arguments.as_ref().map(|a| self.call_arguments(a)));
+                self.node(SyntaxKind::TermReference, span, nodes)
+            }
+            ast::InlineExpression::VariableReference { id } => self.node(
+                SyntaxKind::VariableReference,
+                span,
+                vec![self.identifier(id)],
+            ),
+            ast::InlineExpression::Placeable { expression } => self.node(
+                SyntaxKind::Placeable,
+                span,
+                vec![self.expression(expression)],
+            ),
+            #[cfg(feature = "unknown-expressions")]
+            ast::InlineExpression::Unknown { .. } => self.leaf(SyntaxKind::Junk, span),
+        }
+    }
+
+    fn call_arguments(&self, arguments: &ast::CallArguments<&'s str>) -> SyntaxNode<'s> {
+        let mut nodes: Vec<_> = arguments
+            .positional
+            .iter()
+            .map(|argument| self.inline_expression(argument))
+            .collect();
+        nodes.extend(arguments.named.iter().map(|argument| {
+            let nodes = vec![
+                self.identifier(&argument.name),
+                self.inline_expression(&argument.value),
+            ];
+            self.node(SyntaxKind::NamedArgument, self.span(argument), nodes)
+        }));
+        self.node(SyntaxKind::CallArguments, self.span(arguments), nodes)
+    }
+}
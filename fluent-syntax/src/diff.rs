@@ -0,0 +1,264 @@
+//! Differences between two versions of a resource.
+//!
+//! [`diff`] compares the messages and terms of two resources by identifier,
+//! for translation QA and CI checks which need to know what changed between
+//! two versions of a source file.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::diff::{diff, Change, EntryChange, EntryId};
+//! use fluent_syntax::parser;
+//!
+//! let old = parser::parse("hello = Hello!\n    .title = Greeting\nbye = Bye!\n")
+//!     .expect("Failed to parse an FTL resource.");
+//! let new = parser::parse("hello = Hello, { $user }!\n    .title = Greeting\n-brand = Firefox\n")
+//!     .expect("Failed to parse an FTL resource.");
+//!
+//! let diff = diff(&old, &new);
+//! assert_eq!(diff.added, vec![EntryId::Term("brand".to_string())]);
+//! assert_eq!(diff.removed, vec![EntryId::Message("bye".to_string())]);
+//! assert_eq!(
+//!     diff.changed,
+//!     vec![EntryChange {
+//!         id: EntryId::Message("hello".to_string()),
+//!         changes: vec![
+//!             Change::Value,
+//!             Change::Variables {
+//!                 added: vec!["user".to_string()],
+//!                 removed: vec![],
+//!             },
+//!         ],
+//!     }]
+//! );
+//! ```
+use crate::ast::visit::{self, Visit};
+use crate::ast::*;
+use crate::hash::hash_pattern;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The differences between two resources, returned by [`diff`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResourceDiff {
+    /// The messages and terms of the new resource only, in its order.
+    pub added: Vec<EntryId>,
+    /// The messages and terms of the old resource only, in its order.
+    pub removed: Vec<EntryId>,
+    /// The messages and terms of both resources which differ, in the order of
+    /// the new resource.
+    pub changed: Vec<EntryChange>,
+}
+
+impl ResourceDiff {
+    /// Returns `true` if the messages and terms of the resources are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The identifier of a message or a term.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum EntryId {
+    Message(String),
+    Term(String),
+}
+
+impl fmt::Display for EntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Message(id) => f.write_str(id),
+            Self::Term(id) => write!(f, "-{}", id),
+        }
+    }
+}
+
+/// A message or a term which differs between two resources.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryChange {
+    pub id: EntryId,
+    /// The changes, with [`Change::Value`] first, then the changes of
+    /// attributes in the order of the new entry followed by removed
+    /// attributes, and [`Change::Variables`] last.
+    pub changes: Vec<Change>,
+}
+
+/// A change of a message or a term, see [`EntryChange`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Change {
+    /// The value was added, removed or changed.
+    Value,
+    AttributeAdded(String),
+    AttributeRemoved(String),
+    AttributeChanged(String),
+    /// The variables referenced by the value and the attributes changed.
+    Variables {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+}
+
+/// Returns the differences between the messages and terms of `old` and `new`.
+///
+/// Patterns are compared like [`hash_entry`](crate::hash_entry) does, so
+/// changes of layout, such as a value moved to its own line, are not
+/// differences. Comments are not compared. If an identifier is defined more
+/// than once in a resource, its last definition is used.
+pub fn diff<S: AsRef<str>, T: AsRef<str>>(old: &Resource<S>, new: &Resource<T>) -> ResourceDiff {
+    let old_entries = entries(old);
+    let new_entries = entries(new);
+    let old_map: BTreeMap<&EntryId, &Fields<S>> = old_entries
+        .iter()
+        .map(|(id, fields)| (id, fields))
+        .collect();
+    let new_map: BTreeMap<&EntryId, &Fields<T>> = new_entries
+        .iter()
+        .map(|(id, fields)| (id, fields))
+        .collect();
+
+    let mut result = ResourceDiff::default();
+    for (id, new_fields) in &new_entries {
+        match old_map.get(id) {
+            None => result.added.push(id.clone()),
+            Some(old_fields) => {
+                let changes = changes(old_fields, new_fields);
+                if !changes.is_empty() {
+                    result.changed.push(EntryChange {
+                        id: id.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    for (id, _) in &old_entries {
+        if !new_map.contains_key(id) {
+            result.removed.push(id.clone());
+        }
+    }
+    result
+}
+
+/// The parts of a message or a term which are compared.
+struct Fields<'a, S> {
+    value: Option<&'a Pattern<S>>,
+    attributes: &'a [Attribute<S>],
+}
+
+/// Returns the messages and terms of a resource, in the order of their last
+/// definitions.
+fn entries<S: AsRef<str>>(resource: &Resource<S>) -> Vec<(EntryId, Fields<'_, S>)> {
+    let mut entries = Vec::new();
+    for entry in &resource.body {
+        let (id, fields) = match entry {
+            Entry::Message(message) => (
+                EntryId::Message(message.id.name.as_ref().to_string()),
+                Fields {
+                    value: message.value.as_ref(),
+                    attributes: &message.attributes,
+                },
+            ),
+            Entry::Term(term) => (
+                EntryId::Term(term.id.name.as_ref().to_string()),
+                Fields {
+                    value: Some(&term.value),
+                    attributes: &term.attributes,
+                },
+            ),
+            _ => continue,
+        };
+        entries.push((id, fields));
+    }
+    dedup_last(entries)
+}
+
+/// Removes all but the last of the items with the same key, keeping the order
+/// of the remaining items.
+fn dedup_last<K: Ord, V>(items: Vec<(K, V)>) -> Vec<(K, V)> {
+    let mut last = BTreeMap::new();
+    for (i, (key, _)) in items.iter().enumerate() {
+        last.insert(key, i);
+    }
+    let keep: BTreeSet<usize> = last.into_values().collect();
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| keep.contains(i))
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Returns the hashes of the values of attributes by name.
+fn attributes<S: AsRef<str>>(attributes: &[Attribute<S>]) -> Vec<(&str, u64)> {
+    dedup_last(
+        attributes
+            .iter()
+            .map(|attribute| (attribute.id.name.as_ref(), hash_pattern(&attribute.value)))
+            .collect(),
+    )
+}
+
+fn changes<S: AsRef<str>, T: AsRef<str>>(old: &Fields<S>, new: &Fields<T>) -> Vec<Change> {
+    let mut changes = Vec::new();
+    if old.value.map(hash_pattern) != new.value.map(hash_pattern) {
+        changes.push(Change::Value);
+    }
+
+    let old_attributes = attributes(old.attributes);
+    let new_attributes = attributes(new.attributes);
+    let find = |attributes: &[(&str, u64)], name: &str| {
+        attributes
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map(|&(_, hash)| hash)
+    };
+    for &(name, hash) in &new_attributes {
+        match find(&old_attributes, name) {
+            None => changes.push(Change::AttributeAdded(name.to_string())),
+            Some(old_hash) if old_hash != hash => {
+                changes.push(Change::AttributeChanged(name.to_string()));
+            }
+            Some(_) => {}
+        }
+    }
+    for &(name, _) in &old_attributes {
+        if find(&new_attributes, name).is_none() {
+            changes.push(Change::AttributeRemoved(name.to_string()));
+        }
+    }
+
+    let old_variables = variables(old);
+    let new_variables = variables(new);
+    if old_variables != new_variables {
+        changes.push(Change::Variables {
+            added: new_variables.difference(&old_variables).cloned().collect(),
+            removed: old_variables.difference(&new_variables).cloned().collect(),
+        });
+    }
+    changes
+}
+
+/// Returns the names of the variables referenced by an entry.
+fn variables<S: AsRef<str>>(fields: &Fields<S>) -> BTreeSet<String> {
+    struct Variables(BTreeSet<String>);
+
+    impl<'ast, S: AsRef<str> + 'ast> Visit<'ast, S> for Variables {
+        fn visit_inline_expression(&mut self, node: &'ast InlineExpression<S>) {
+            if let InlineExpression::VariableReference { id } = node {
+                self.0.insert(id.name.as_ref().to_string());
+            }
+            visit::walk_inline_expression(self, node);
+        }
+    }
+
+    let mut variables = Variables(BTreeSet::new());
+    if let Some(value) = fields.value {
+        variables.visit_pattern(value);
+    }
+    for attribute in fields.attributes {
+        variables.visit_attribute(attribute);
+    }
+    variables.0
+}
@@ -0,0 +1,78 @@
+//! Formatting of FTL sources.
+use crate::ast::{Entry, Resource};
+use crate::parser::{self, ParserError};
+use crate::serializer::{self, SerializerOptions};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Formats an FTL source: reprints it with the layout of the
+/// [`serializer`], which normalizes the indentation of patterns, continuation
+/// lines, select expressions and attributes, and the spacing within
+/// placeables.
+///
+/// Unlike [`serializer::serialize`], formatting keeps the blank lines which
+/// group entries in the source, collapsing runs of them to
+/// `options.blank_lines_between_entries`, or to one line if it is 0. Comments
+/// are kept, and line endings are `\n`. Formatting is idempotent: formatting
+/// the output again returns it unchanged.
+///
+/// Returns the errors of the source if it does not parse, rather than dropping
+/// the invalid entries.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::format;
+/// use fluent_syntax::serializer::SerializerOptions;
+///
+/// let ftl = "## Group comment\nhello=Hello,{$user}!\n  .title   = Welcome\n\n\n\nbye =   Bye\n";
+///
+/// let formatted = format(ftl, SerializerOptions::default())
+///     .expect("Failed to parse an FTL resource.");
+///
+/// assert_eq!(
+///     formatted,
+///     "## Group comment\n\nhello = Hello,{ $user }!\n    .title = Welcome\n\nbye = Bye\n"
+/// );
+/// ```
+pub fn format(source: &str, options: SerializerOptions) -> Result<String, Vec<ParserError>> {
+    let spanned = parser::parse_with_spans(source).map_err(|(_, errors)| errors)?;
+
+    let mut result = String::with_capacity(source.len());
+    let mut previous: Option<(&Entry<&str>, usize)> = None;
+    for entry in &spanned.resource().body {
+        let span = spanned.span(entry).unwrap_or_default();
+        if let Some((previous, end)) = previous {
+            let blank_lines = if is_free_comment(previous)
+                || is_free_comment(entry)
+                || source[end..span.start].matches('\n').count() > 1
+            {
+                options.blank_lines_between_entries.max(1)
+            } else {
+                options.blank_lines_between_entries
+            };
+            for _ in 0..blank_lines {
+                result.push('\n');
+            }
+        }
+
+        let resource = Resource {
+            body: vec![entry.clone()],
+        };
+        let serialized = serializer::serialize_with_options(&resource, options);
+        result.push_str(serialized.trim_end_matches('\n'));
+        result.push('\n');
+        previous = Some((entry, span.end));
+    }
+    Ok(result)
+}
+
+/// Free comments are separated from the entries around them by blank lines,
+/// so that they are not attached to the next message or term.
+fn is_free_comment(entry: &Entry<&str>) -> bool {
+    matches!(
+        entry,
+        Entry::Comment(_) | Entry::GroupComment(_) | Entry::ResourceComment(_)
+    )
+}
@@ -0,0 +1,318 @@
+//! Dependency graph of the references between messages and terms.
+//!
+//! [`ReferenceGraph`] records the messages and terms referenced by each message
+//! and term of one or more resources, for tooling which needs to know what
+//! depends on an entry before changing or removing it.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::diff::EntryId;
+//! use fluent_syntax::graph::ReferenceGraph;
+//! use fluent_syntax::parser;
+//!
+//! let ftl = r#"
+//! -brand-name = Firefox
+//! about = About { -brand-name }
+//! menu-about = { about }
+//! hello = Hello!
+//! "#;
+//! let resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+//! let graph = ReferenceGraph::from_resource(&resource);
+//!
+//! // What breaks if `-brand-name` is deleted?
+//! assert_eq!(
+//!     graph.dependents(&EntryId::Term("brand-name".to_string())),
+//!     vec![
+//!         &EntryId::Message("about".to_string()),
+//!         &EntryId::Message("menu-about".to_string()),
+//!     ]
+//! );
+//! ```
+use crate::ast::visit::{self, Visit};
+use crate::ast::*;
+use crate::diff::EntryId;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use thiserror::Error;
+
+/// The references between the messages and terms of resources.
+///
+/// An entry defined more than once, in one resource or in several, has the
+/// references of all its definitions. References to attributes are references
+/// to their message or term.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferenceGraph {
+    defined: BTreeSet<EntryId>,
+    references: BTreeMap<EntryId, BTreeSet<EntryId>>,
+    referenced_by: BTreeMap<EntryId, BTreeSet<EntryId>>,
+}
+
+/// The error of [`ReferenceGraph::topological_order`] when entries reference
+/// each other.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("The entries reference each other in a cycle: {}", Cycle(.cycle))]
+pub struct CycleError {
+    /// The entries of the cycle, sorted by identifier.
+    pub cycle: Vec<EntryId>,
+}
+
+/// Displays the identifiers of a cycle of entries.
+struct Cycle<'a>(&'a [EntryId]);
+
+impl fmt::Display for Cycle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, id) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "\"{}\"", id)?;
+        }
+        if let Some(id) = self.0.first() {
+            write!(f, " -> \"{}\"", id)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReferenceGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the graph of the references of a resource.
+    pub fn from_resource<S: AsRef<str>>(resource: &Resource<S>) -> Self {
+        let mut graph = Self::new();
+        graph.add_resource(resource);
+        graph
+    }
+
+    /// Adds the messages and terms of a resource and their references.
+    ///
+    /// Entries of different resources with the same identifier are the same
+    /// node of the graph, like in a bundle where terms are shared between
+    /// resources.
+    pub fn add_resource<S: AsRef<str>>(&mut self, resource: &Resource<S>) {
+        for entry in &resource.body {
+            let id = match entry {
+                Entry::Message(message) => EntryId::Message(message.id.name.as_ref().to_string()),
+                Entry::Term(term) => EntryId::Term(term.id.name.as_ref().to_string()),
+                _ => continue,
+            };
+            let mut collector = References(BTreeSet::new());
+            collector.visit_entry(entry);
+            for referenced in &collector.0 {
+                self.referenced_by
+                    .entry(referenced.clone())
+                    .or_default()
+                    .insert(id.clone());
+            }
+            self.references
+                .entry(id.clone())
+                .or_default()
+                .extend(collector.0);
+            self.defined.insert(id);
+        }
+    }
+
+    /// Returns `true` if a resource of the graph defines the entry.
+    pub fn is_defined(&self, id: &EntryId) -> bool {
+        self.defined.contains(id)
+    }
+
+    /// Returns the messages and terms defined by the resources, sorted by
+    /// identifier.
+    pub fn entries(&self) -> impl Iterator<Item = &EntryId> + '_ {
+        self.defined.iter()
+    }
+
+    /// Returns the entries which are referenced but not defined, sorted by
+    /// identifier.
+    pub fn missing(&self) -> impl Iterator<Item = &EntryId> + '_ {
+        self.referenced_by
+            .keys()
+            .filter(move |id| !self.defined.contains(*id))
+    }
+
+    /// Returns the entries referenced directly by the entry, sorted by
+    /// identifier.
+    pub fn references<'a>(&'a self, id: &EntryId) -> impl Iterator<Item = &'a EntryId> + 'a {
+        self.references.get(id).into_iter().flatten()
+    }
+
+    /// Returns the entries which reference the entry directly, sorted by
+    /// identifier.
+    pub fn referenced_by<'a>(&'a self, id: &EntryId) -> impl Iterator<Item = &'a EntryId> + 'a {
+        self.referenced_by.get(id).into_iter().flatten()
+    }
+
+    /// Returns the entries referenced by the entry directly or through other
+    /// entries, sorted by identifier.
+    ///
+    /// The entry is one of them only if it is part of a cycle.
+    pub fn dependencies(&self, id: &EntryId) -> Vec<&EntryId> {
+        reachable(&self.references, id)
+    }
+
+    /// Returns the entries which reference the entry directly or through other
+    /// entries, sorted by identifier. These are the entries which can no
+    /// longer be formatted completely without it.
+    ///
+    /// The entry is one of them only if it is part of a cycle.
+    pub fn dependents(&self, id: &EntryId) -> Vec<&EntryId> {
+        reachable(&self.referenced_by, id)
+    }
+
+    /// Returns the groups of entries which reference each other, including
+    /// the entries which reference themselves. The entries of each cycle are
+    /// sorted by identifier, and the cycles by their first entry.
+    pub fn cycles(&self) -> Vec<Vec<&EntryId>> {
+        let mut cycles: Vec<_> = self
+            .components()
+            .into_iter()
+            .filter(|component| self.is_cycle(component))
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Returns the defined entries in an order where every entry comes after
+    /// the entries it references.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first of the [`cycles`](Self::cycles) if there are any.
+    pub fn topological_order(&self) -> Result<Vec<&EntryId>, CycleError> {
+        let components = self.components();
+        let first_cycle = components
+            .iter()
+            .filter(|component| self.is_cycle(component))
+            .min();
+        if let Some(cycle) = first_cycle {
+            return Err(CycleError {
+                cycle: cycle.iter().map(|&id| id.clone()).collect(),
+            });
+        }
+        Ok(components
+            .into_iter()
+            .flatten()
+            .filter(|id| self.defined.contains(*id))
+            .collect())
+    }
+
+    fn is_cycle(&self, component: &[&EntryId]) -> bool {
+        match component {
+            [id] => self.references(id).any(|other| other == *id),
+            _ => true,
+        }
+    }
+
+    /// Returns the strongly connected components of the graph with Tarjan's
+    /// algorithm, each sorted by identifier. A component comes after the
+    /// components it references.
+    fn components(&self) -> Vec<Vec<&EntryId>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let nodes: Vec<&EntryId> = self
+            .defined
+            .iter()
+            .chain(self.referenced_by.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let edges: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|id| {
+                self.references(id)
+                    .filter_map(|other| nodes.binary_search(&other).ok())
+                    .collect()
+            })
+            .collect();
+
+        let mut index = vec![UNVISITED; nodes.len()];
+        let mut low = vec![0; nodes.len()];
+        let mut on_stack = vec![false; nodes.len()];
+        let mut next_index = 0;
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        for root in 0..nodes.len() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            // The nodes being visited, with the position of their next edge.
+            let mut calls = vec![(root, 0)];
+            while let Some((node, edge)) = calls.pop() {
+                if edge == 0 {
+                    index[node] = next_index;
+                    low[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+                if let Some(&target) = edges[node].get(edge) {
+                    calls.push((node, edge + 1));
+                    if index[target] == UNVISITED {
+                        calls.push((target, 0));
+                    } else if on_stack[target] {
+                        low[node] = low[node].min(index[target]);
+                    }
+                    continue;
+                }
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                if low[node] == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(nodes[member]);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+/// Returns the nodes reachable from `id` through the edges of `edges`.
+fn reachable<'a>(
+    edges: &'a BTreeMap<EntryId, BTreeSet<EntryId>>,
+    id: &EntryId,
+) -> Vec<&'a EntryId> {
+    let mut reached = BTreeSet::new();
+    let mut stack: Vec<&EntryId> = edges.get(id).into_iter().flatten().collect();
+    while let Some(id) = stack.pop() {
+        if reached.insert(id) {
+            stack.extend(edges.get(id).into_iter().flatten());
+        }
+    }
+    reached.into_iter().collect()
+}
+
+/// Collects the messages and terms referenced by an entry.
+struct References(BTreeSet<EntryId>);
+
+impl<'ast, S: AsRef<str> + 'ast> Visit<'ast, S> for References {
+    fn visit_inline_expression(&mut self, node: &'ast InlineExpression<S>) {
+        match node {
+            InlineExpression::MessageReference { id, .. } => {
+                self.0
+                    .insert(EntryId::Message(id.name.as_ref().to_string()));
+            }
+            InlineExpression::TermReference { id, .. } => {
+                self.0.insert(EntryId::Term(id.name.as_ref().to_string()));
+            }
+            _ => {}
+        }
+        visit::walk_inline_expression(self, node);
+    }
+}
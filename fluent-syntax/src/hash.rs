@@ -0,0 +1,276 @@
+//! Stable content hashes of entries, for change tracking.
+//!
+//! Sync tools can store the hash of each message of the source locale, and
+//! mark a translation as outdated when the hash of its source changes.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::{hash_entry, parser};
+//!
+//! let old = parser::parse("# Shown on the home page.\nhello = Hello,   { $user }!\n")
+//!     .expect("Failed to parse an FTL resource.");
+//! let reformatted = parser::parse("hello =\n    Hello, { $user }!\n")
+//!     .expect("Failed to parse an FTL resource.");
+//! let changed = parser::parse("hello = Hi, { $user }!\n")
+//!     .expect("Failed to parse an FTL resource.");
+//!
+//! assert_eq!(hash_entry(&old.body[0]), hash_entry(&reformatted.body[0]));
+//! assert_ne!(hash_entry(&old.body[0]), hash_entry(&changed.body[0]));
+//! ```
+
+use crate::ast::*;
+
+/// Returns a hash of the content of `entry`, which is the same across
+/// platforms and versions of this crate.
+///
+/// The hash covers the kind of the entry, its value and its attributes, but
+/// not its identifier, its comment, or the layout of its source: runs of
+/// whitespace in text count as a single space, and text split over several
+/// lines hashes like the same text on one line. Comments and junk are hashed
+/// by their content.
+pub fn hash_entry<S: AsRef<str>>(entry: &Entry<S>) -> u64 {
+    let mut hasher = Hasher::new();
+    match entry {
+        Entry::Message(msg) => {
+            hasher.tag(b'M');
+            hasher.optional_pattern(msg.value.as_ref());
+            hasher.attributes(&msg.attributes);
+        }
+        Entry::Term(term) => {
+            hasher.tag(b'T');
+            hasher.pattern(&term.value);
+            hasher.attributes(&term.attributes);
+        }
+        Entry::Comment(comment) => hasher.comment(b'C', comment),
+        Entry::GroupComment(comment) => hasher.comment(b'G', comment),
+        Entry::ResourceComment(comment) => hasher.comment(b'R', comment),
+        Entry::Junk { content } => {
+            hasher.tag(b'J');
+            hasher.str(content.as_ref());
+        }
+    }
+    hasher.0
+}
+
+/// Returns a hash of the content of `pattern`, with the same normalization as
+/// [`hash_entry`].
+pub(crate) fn hash_pattern<S: AsRef<str>>(pattern: &Pattern<S>) -> u64 {
+    let mut hasher = Hasher::new();
+    hasher.pattern(pattern);
+    hasher.0
+}
+
+/// The 64-bit FNV-1a hash, which unlike the hashers of the standard library
+/// is stable.
+struct Hasher(u64);
+
+impl Hasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.bytes(&[tag]);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.bytes(&(len as u64).to_le_bytes());
+    }
+
+    /// Hashes `s` with its length, so that consecutive strings cannot be
+    /// confused with a different split of the same bytes.
+    fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.bytes(s.as_bytes());
+    }
+
+    fn comment<S: AsRef<str>>(&mut self, tag: u8, comment: &Comment<S>) {
+        self.tag(tag);
+        self.len(comment.content.len());
+        for line in &comment.content {
+            self.str(line.as_ref());
+        }
+    }
+
+    fn attributes<S: AsRef<str>>(&mut self, attributes: &[Attribute<S>]) {
+        self.len(attributes.len());
+        for attr in attributes {
+            self.str(attr.id.name.as_ref());
+            self.pattern(&attr.value);
+        }
+    }
+
+    fn optional_pattern<S: AsRef<str>>(&mut self, pattern: Option<&Pattern<S>>) {
+        match pattern {
+            Some(pattern) => self.pattern(pattern),
+            None => self.tag(b'0'),
+        }
+    }
+
+    fn pattern<S: AsRef<str>>(&mut self, pattern: &Pattern<S>) {
+        self.tag(b'P');
+        // Adjacent text elements, such as the lines of a multiline pattern,
+        // are hashed as one text.
+        let mut text = None;
+        for element in &pattern.elements {
+            match element {
+                PatternElement::TextElement { value } => {
+                    self.text(value.as_ref(), &mut text);
+                }
+                PatternElement::Placeable { expression } => {
+                    self.end_text(&mut text);
+                    self.expression(expression);
+                }
+                #[cfg(feature = "recover-placeables")]
+                PatternElement::Junk { content } => {
+                    self.end_text(&mut text);
+                    self.tag(b'J');
+                    self.str(content.as_ref());
+                }
+            }
+        }
+        self.end_text(&mut text);
+    }
+
+    /// Hashes a text element, collapsing whitespace. `state` is `None` outside
+    /// of a text, and otherwise tells whether whitespace is pending.
+    fn text(&mut self, value: &str, state: &mut Option<bool>) {
+        if state.is_none() {
+            self.tag(b'X');
+        }
+        let mut space = state.unwrap_or(false);
+        let mut buf = [0; 4];
+        for c in value.chars() {
+            if c.is_whitespace() {
+                space = true;
+                continue;
+            }
+            if space {
+                self.tag(b' ');
+                space = false;
+            }
+            self.bytes(c.encode_utf8(&mut buf).as_bytes());
+        }
+        *state = Some(space);
+    }
+
+    fn end_text(&mut self, state: &mut Option<bool>) {
+        if let Some(space) = state.take() {
+            if space {
+                self.tag(b' ');
+            }
+            // Text is not prefixed with its length, so it ends with a byte
+            // which is never part of UTF-8.
+            self.tag(0xFF);
+        }
+    }
+
+    fn expression<S: AsRef<str>>(&mut self, expression: &Expression<S>) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                self.tag(b'S');
+                self.inline_expression(selector);
+                self.len(variants.len());
+                for variant in variants {
+                    self.tag(if variant.default { b'*' } else { b'[' });
+                    match &variant.key {
+                        VariantKey::Identifier { name } => {
+                            self.tag(b'i');
+                            self.str(name.as_ref());
+                        }
+                        VariantKey::NumberLiteral { value } => {
+                            self.tag(b'n');
+                            self.str(value.as_ref());
+                        }
+                    }
+                    self.pattern(&variant.value);
+                }
+            }
+            Expression::Inline(expression) => self.inline_expression(expression),
+        }
+    }
+
+    fn inline_expression<S: AsRef<str>>(&mut self, expression: &InlineExpression<S>) {
+        match expression {
+            InlineExpression::StringLiteral { value } => {
+                self.tag(b'"');
+                self.str(value.as_ref());
+            }
+            InlineExpression::NumberLiteral { value } => {
+                self.tag(b'#');
+                self.str(value.as_ref());
+            }
+            InlineExpression::FunctionReference { id, arguments } => {
+                self.tag(b'F');
+                self.str(id.name.as_ref());
+                self.arguments(Some(arguments));
+            }
+            InlineExpression::MessageReference { id, attribute } => {
+                self.tag(b'm');
+                self.str(id.name.as_ref());
+                self.attribute(attribute.as_ref());
+            }
+            InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                self.tag(b't');
+                self.str(id.name.as_ref());
+                self.attribute(attribute.as_ref());
+                self.arguments(arguments.as_ref());
+            }
+            InlineExpression::VariableReference { id } => {
+                self.tag(b'$');
+                self.str(id.name.as_ref());
+            }
+            InlineExpression::Placeable { expression } => {
+                self.tag(b'{');
+                self.expression(expression);
+            }
+            #[cfg(feature = "unknown-expressions")]
+            InlineExpression::Unknown { content } => {
+                self.tag(b'?');
+                self.str(content.as_ref());
+            }
+        }
+    }
+
+    fn attribute<S: AsRef<str>>(&mut self, attribute: Option<&Identifier<S>>) {
+        match attribute {
+            Some(attribute) => {
+                self.tag(b'.');
+                self.str(attribute.name.as_ref());
+            }
+            None => self.tag(b'0'),
+        }
+    }
+
+    fn arguments<S: AsRef<str>>(&mut self, arguments: Option<&CallArguments<S>>) {
+        let arguments = match arguments {
+            Some(arguments) => arguments,
+            None => return self.tag(b'0'),
+        };
+        self.tag(b'(');
+        self.len(arguments.positional.len());
+        for argument in &arguments.positional {
+            self.inline_expression(argument);
+        }
+        self.len(arguments.named.len());
+        for argument in &arguments.named {
+            self.str(argument.name.name.as_ref());
+            self.inline_expression(&argument.value);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Classification of the source of a resource for syntax highlighting.
+//!
+//! [`highlight`] walks a resource parsed by
+//! [`parse_with_spans`](crate::parser::parse_with_spans), and returns the
+//! ranges of the source to highlight, such as the identifiers of messages and
+//! the names of functions. Editors can use them as semantic tokens, which
+//! follow the grammar exactly where regular expressions only approximate it.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::highlight::{highlight, Token, TokenKind};
+//! use fluent_syntax::parser;
+//!
+//! let ftl = "hello = Hello, { $user }!\n";
+//! let spanned = parser::parse_with_spans(ftl).expect("Failed to parse an FTL resource.");
+//!
+//! assert_eq!(
+//!     highlight(&spanned, ftl),
+//!     vec![
+//!         Token {
+//!             span: 0..5,
+//!             kind: TokenKind::MessageId,
+//!         },
+//!         Token {
+//!             span: 17..22,
+//!             kind: TokenKind::Variable,
+//!         },
+//!     ]
+//! );
+//! ```
+use crate::ast::visit::{self, Visit};
+use crate::ast::*;
+use crate::parser::{SpannedNode, SpannedResource};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A classified range of the source, see [`highlight`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token {
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+}
+
+/// Kind of a [`Token`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TokenKind {
+    /// A comment, from its first `#` to the end of its last line.
+    Comment,
+    /// The identifier of a message, where it is defined or referenced.
+    MessageId,
+    /// The identifier of a term, with its `-`, where it is defined or
+    /// referenced.
+    TermId,
+    /// The name of an attribute, where it is defined or referenced.
+    Attribute,
+    /// A variable, with its `$`.
+    Variable,
+    /// The name of a function.
+    Function,
+    /// The name of a named argument.
+    NamedArgument,
+    /// A string literal, with its quotes.
+    StringLiteral,
+    /// A number literal, either in an expression or as a variant key.
+    NumberLiteral,
+    /// The identifier of a variant key.
+    VariantKey,
+    /// The `->` of a select expression.
+    SelectArrow,
+    /// The `*` which marks the default variant.
+    DefaultVariant,
+    /// Junk, either an entry, a placeable or an unknown expression skipped by
+    /// the parser.
+    Junk,
+}
+
+/// Returns the tokens of `spanned`, which was parsed from `source`, sorted by
+/// their position.
+///
+/// Tokens do not overlap, and text which is not classified, such as the text
+/// of patterns and the punctuation of the syntax, is not part of any token.
+pub fn highlight<S: AsRef<str>>(spanned: &SpannedResource<S>, source: &str) -> Vec<Token> {
+    let mut highlighter = Highlighter {
+        spanned,
+        source,
+        tokens: Vec::new(),
+    };
+    highlighter.visit_resource(spanned.resource());
+    let mut tokens = highlighter.tokens;
+    tokens.sort_by_key(|token| token.span.start);
+    tokens
+}
+
+struct Highlighter<'a, S> {
+    spanned: &'a SpannedResource<S>,
+    source: &'a str,
+    tokens: Vec<Token>,
+}
+
+impl<S> Highlighter<'_, S> {
+    fn push<N: SpannedNode>(&mut self, node: &N, kind: TokenKind) {
+        if let Some(span) = self.spanned.span(node) {
+            self.tokens.push(Token { span, kind });
+        }
+    }
+
+    /// Pushes a token for the `-` and the identifier of a term.
+    fn push_term(&mut self, id: &Identifier<S>) {
+        if let Some(span) = self.spanned.span(id) {
+            self.tokens.push(Token {
+                span: span.start.saturating_sub(1)..span.end,
+                kind: TokenKind::TermId,
+            });
+        }
+    }
+}
+
+impl<'ast, S: AsRef<str> + 'ast> Visit<'ast, S> for Highlighter<'_, S> {
+    fn visit_entry(&mut self, node: &'ast Entry<S>) {
+        if let Entry::Junk { .. } = node {
+            self.push(node, TokenKind::Junk);
+        }
+        visit::walk_entry(self, node);
+    }
+
+    fn visit_message(&mut self, node: &'ast Message<S>) {
+        if let Some(comment) = &node.comment {
+            self.visit_comment(comment);
+        }
+        self.push(&node.id, TokenKind::MessageId);
+        if let Some(value) = &node.value {
+            self.visit_pattern(value);
+        }
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+    }
+
+    fn visit_term(&mut self, node: &'ast Term<S>) {
+        if let Some(comment) = &node.comment {
+            self.visit_comment(comment);
+        }
+        self.push_term(&node.id);
+        self.visit_pattern(&node.value);
+        for attribute in &node.attributes {
+            self.visit_attribute(attribute);
+        }
+    }
+
+    fn visit_comment(&mut self, node: &'ast Comment<S>) {
+        self.push(node, TokenKind::Comment);
+    }
+
+    fn visit_attribute(&mut self, node: &'ast Attribute<S>) {
+        self.push(&node.id, TokenKind::Attribute);
+        self.visit_pattern(&node.value);
+    }
+
+    fn visit_pattern_element(&mut self, node: &'ast PatternElement<S>) {
+        #[cfg(feature = "recover-placeables")]
+        if let PatternElement::Junk { .. } = node {
+            self.push(node, TokenKind::Junk);
+        }
+        visit::walk_pattern_element(self, node);
+    }
+
+    fn visit_expression(&mut self, node: &'ast Expression<S>) {
+        if let Expression::Select { selector, .. } = node {
+            let arrow = self.spanned.span(selector).and_then(|span| {
+                let start = span.end + self.source.get(span.end..)?.find("->")?;
+                Some(start..start + 2)
+            });
+            if let Some(span) = arrow {
+                self.tokens.push(Token {
+                    span,
+                    kind: TokenKind::SelectArrow,
+                });
+            }
+        }
+        visit::walk_expression(self, node);
+    }
+
+    fn visit_variant(&mut self, node: &'ast Variant<S>) {
+        if node.default {
+            if let Some(span) = self.spanned.span(node) {
+                self.tokens.push(Token {
+                    span: span.start..span.start + 1,
+                    kind: TokenKind::DefaultVariant,
+                });
+            }
+        }
+        visit::walk_variant(self, node);
+    }
+
+    fn visit_variant_key(&mut self, node: &'ast VariantKey<S>) {
+        let kind = match node {
+            VariantKey::Identifier { .. } => TokenKind::VariantKey,
+            VariantKey::NumberLiteral { .. } => TokenKind::NumberLiteral,
+        };
+        self.push(node, kind);
+    }
+
+    fn visit_inline_expression(&mut self, node: &'ast InlineExpression<S>) {
+        match node {
+            InlineExpression::StringLiteral { .. } => self.push(node, TokenKind::StringLiteral),
+            InlineExpression::NumberLiteral { .. } => self.push(node, TokenKind::NumberLiteral),
+            InlineExpression::FunctionReference { id, arguments } => {
+                self.push(id, TokenKind::Function);
+                self.visit_call_arguments(arguments);
+            }
+            InlineExpression::MessageReference { id, attribute } => {
+                self.push(id, TokenKind::MessageId);
+                if let Some(attribute) = attribute {
+                    self.push(attribute, TokenKind::Attribute);
+                }
+            }
+            InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                self.push_term(id);
+                if let Some(attribute) = attribute {
+                    self.push(attribute, TokenKind::Attribute);
+                }
+                if let Some(arguments) = arguments {
+                    self.visit_call_arguments(arguments);
+                }
+            }
+            InlineExpression::VariableReference { .. } => self.push(node, TokenKind::Variable),
+            InlineExpression::Placeable { expression } => self.visit_expression(expression),
+            #[cfg(feature = "unknown-expressions")]
+            InlineExpression::Unknown { .. } => self.push(node, TokenKind::Junk),
+        }
+    }
+
+    fn visit_named_argument(&mut self, node: &'ast NamedArgument<S>) {
+        self.push(&node.name, TokenKind::NamedArgument);
+        self.visit_inline_expression(&node.value);
+    }
+}
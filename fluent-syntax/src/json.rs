@@ -0,0 +1,362 @@
+//! Conversion of the AST to the JSON of the JavaScript implementation.
+//!
+//! The `fluent-syntax` package of [fluent.js](https://github.com/projectfluent/fluent.js)
+//! describes every node as an object with a `type` field, and optionally a
+//! `span` field with its range in the source. [`to_json`] and [`to_json_with_spans`]
+//! produce the same shape, so that tools written in either language can share
+//! fixtures:
+//!
+//! - Consecutive text elements, which the parser splits at line ends, are
+//!   merged into one.
+//! - Select expressions are `SelectExpression` nodes, and inline expressions
+//!   appear in their parent without a wrapper.
+//! - The lines of comments are joined with `\n`.
+//! - Missing optional fields, such as the value of a message with only
+//!   attributes, are `null`.
+//! - Junk, including the `PatternElement::Junk` of recovered placeables and
+//!   `InlineExpression::Unknown`, has an empty list of `annotations`, since
+//!   the errors of the parser are not part of the AST.
+//! - Spans are byte offsets, whereas fluent.js counts UTF-16 code units, so
+//!   they differ after the first non-ASCII character.
+//!
+//! Requires the `json` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::{json, parser};
+//! use serde_json::json;
+//!
+//! let ftl = "hello = Hello, { $user }!\n";
+//! let spanned = parser::parse_with_spans(ftl).expect("Failed to parse an FTL resource.");
+//!
+//! let value = json::to_json_with_spans(ftl, &spanned);
+//! assert_eq!(
+//!     value["body"][0],
+//!     json!({
+//!         "type": "Message",
+//!         "id": {
+//!             "type": "Identifier",
+//!             "name": "hello",
+//!             "span": { "type": "Span", "start": 0, "end": 5 }
+//!         },
+//!         "value": {
+//!             "type": "Pattern",
+//!             "elements": [
+//!                 {
+//!                     "type": "TextElement",
+//!                     "value": "Hello, ",
+//!                     "span": { "type": "Span", "start": 8, "end": 15 }
+//!                 },
+//!                 {
+//!                     "type": "Placeable",
+//!                     "expression": {
+//!                         "type": "VariableReference",
+//!                         "id": {
+//!                             "type": "Identifier",
+//!                             "name": "user",
+//!                             "span": { "type": "Span", "start": 18, "end": 22 }
+//!                         },
+//!                         "span": { "type": "Span", "start": 17, "end": 22 }
+//!                     },
+//!                     "span": { "type": "Span", "start": 15, "end": 24 }
+//!                 },
+//!                 {
+//!                     "type": "TextElement",
+//!                     "value": "!",
+//!                     "span": { "type": "Span", "start": 24, "end": 25 }
+//!                 }
+//!             ],
+//!             "span": { "type": "Span", "start": 8, "end": 25 }
+//!         },
+//!         "attributes": [],
+//!         "comment": null,
+//!         "span": { "type": "Span", "start": 0, "end": 25 }
+//!     })
+//! );
+//! ```
+use crate::ast::*;
+use crate::parser::{SpannedNode, SpannedResource};
+use ::core::ops::Range;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde_json::{json, Map, Value};
+
+/// Converts `resource` to JSON, without spans.
+pub fn to_json<S: AsRef<str>>(resource: &Resource<S>) -> Value {
+    Converter { spans: None }.resource(resource, None)
+}
+
+/// Converts a resource parsed with [`parse_with_spans`](crate::parser::parse_with_spans)
+/// to JSON, with the span of every node. The span of the resource covers the
+/// whole `source`.
+pub fn to_json_with_spans<S: AsRef<str>>(source: &str, spanned: &SpannedResource<S>) -> Value {
+    Converter {
+        spans: Some(spanned),
+    }
+    .resource(spanned.resource(), Some(0..source.len()))
+}
+
+struct Converter<'a, S> {
+    spans: Option<&'a SpannedResource<S>>,
+}
+
+impl<'a, S: AsRef<str>> Converter<'a, S> {
+    fn span<N: SpannedNode>(&self, node: &N) -> Option<Range<usize>> {
+        self.spans.and_then(|spans| spans.span(node))
+    }
+
+    /// Returns a node of `kind` with `fields`, and the span of `spanned`.
+    fn node<N: SpannedNode>(&self, kind: &str, fields: Value, spanned: &N) -> Value {
+        node(kind, fields, self.span(spanned))
+    }
+
+    fn resource(&self, resource: &Resource<S>, span: Option<Range<usize>>) -> Value {
+        let body: Vec<Value> = resource
+            .body
+            .iter()
+            .map(|entry| self.entry(entry))
+            .collect();
+        node("Resource", json!({ "body": body }), span)
+    }
+
+    fn entry(&self, entry: &Entry<S>) -> Value {
+        match entry {
+            Entry::Message(message) => self.node(
+                "Message",
+                json!({
+                    "id": self.identifier(&message.id),
+                    "value": message.value.as_ref().map(|value| self.pattern(value)),
+                    "attributes": self.attributes(&message.attributes),
+                    "comment": message.comment.as_ref().map(|comment| self.comment("Comment", comment)),
+                }),
+                message,
+            ),
+            Entry::Term(term) => self.node(
+                "Term",
+                json!({
+                    "id": self.identifier(&term.id),
+                    "value": self.pattern(&term.value),
+                    "attributes": self.attributes(&term.attributes),
+                    "comment": term.comment.as_ref().map(|comment| self.comment("Comment", comment)),
+                }),
+                term,
+            ),
+            Entry::Comment(comment) => self.comment("Comment", comment),
+            Entry::GroupComment(comment) => self.comment("GroupComment", comment),
+            Entry::ResourceComment(comment) => self.comment("ResourceComment", comment),
+            Entry::Junk { content } => self.node(
+                "Junk",
+                json!({ "annotations": [], "content": content.as_ref() }),
+                entry,
+            ),
+        }
+    }
+
+    fn comment(&self, kind: &str, comment: &Comment<S>) -> Value {
+        let lines: Vec<&str> = comment.content.iter().map(AsRef::as_ref).collect();
+        self.node(kind, json!({ "content": lines.join("\n") }), comment)
+    }
+
+    fn attributes(&self, attributes: &[Attribute<S>]) -> Vec<Value> {
+        attributes
+            .iter()
+            .map(|attribute| {
+                self.node(
+                    "Attribute",
+                    json!({
+                        "id": self.identifier(&attribute.id),
+                        "value": self.pattern(&attribute.value),
+                    }),
+                    attribute,
+                )
+            })
+            .collect()
+    }
+
+    fn identifier(&self, identifier: &Identifier<S>) -> Value {
+        self.node(
+            "Identifier",
+            json!({ "name": identifier.name.as_ref() }),
+            identifier,
+        )
+    }
+
+    fn pattern(&self, pattern: &Pattern<S>) -> Value {
+        let mut elements = vec![];
+        let mut text: Option<(String, Option<Range<usize>>)> = None;
+        for element in &pattern.elements {
+            let span = self.span(element);
+            match element {
+                PatternElement::TextElement { value } => match &mut text {
+                    Some((text, text_span)) => {
+                        text.push_str(value.as_ref());
+                        if let (Some(text_span), Some(span)) = (text_span, span) {
+                            text_span.end = span.end;
+                        }
+                    }
+                    None => text = Some((String::from(value.as_ref()), span)),
+                },
+                PatternElement::Placeable { expression } => {
+                    if let Some((text, span)) = text.take() {
+                        elements.push(node("TextElement", json!({ "value": text }), span));
+                    }
+                    elements.push(node(
+                        "Placeable",
+                        json!({ "expression": self.expression(expression) }),
+                        span,
+                    ));
+                }
+                #[cfg(feature = "recover-placeables")]
+                PatternElement::Junk { content } => {
+                    if let Some((text, span)) = text.take() {
+                        elements.push(node("TextElement", json!({ "value": text }), span));
+                    }
+                    elements.push(node(
+                        "Junk",
+                        json!({ "annotations": [], "content": content.as_ref() }),
+                        span,
+                    ));
+                }
+            }
+        }
+        if let Some((text, span)) = text {
+            elements.push(node("TextElement", json!({ "value": text }), span));
+        }
+        self.node("Pattern", json!({ "elements": elements }), pattern)
+    }
+
+    fn expression(&self, expression: &Expression<S>) -> Value {
+        match expression {
+            Expression::Select { selector, variants } => {
+                let variants: Vec<Value> = variants
+                    .iter()
+                    .map(|variant| {
+                        self.node(
+                            "Variant",
+                            json!({
+                                "key": self.variant_key(&variant.key),
+                                "value": self.pattern(&variant.value),
+                                "default": variant.default,
+                            }),
+                            variant,
+                        )
+                    })
+                    .collect();
+                self.node(
+                    "SelectExpression",
+                    json!({
+                        "selector": self.inline_expression(selector),
+                        "variants": variants,
+                    }),
+                    expression,
+                )
+            }
+            Expression::Inline(expression) => self.inline_expression(expression),
+        }
+    }
+
+    fn variant_key(&self, key: &VariantKey<S>) -> Value {
+        match key {
+            VariantKey::Identifier { name } => {
+                self.node("Identifier", json!({ "name": name.as_ref() }), key)
+            }
+            VariantKey::NumberLiteral { value } => {
+                self.node("NumberLiteral", json!({ "value": value.as_ref() }), key)
+            }
+        }
+    }
+
+    fn inline_expression(&self, expression: &InlineExpression<S>) -> Value {
+        let (kind, fields) = match expression {
+            InlineExpression::StringLiteral { value } => {
+                ("StringLiteral", json!({ "value": value.as_ref() }))
+            }
+            InlineExpression::NumberLiteral { value } => {
+                ("NumberLiteral", json!({ "value": value.as_ref() }))
+            }
+            InlineExpression::FunctionReference { id, arguments } => (
+                "FunctionReference",
+                json!({
+                    "id": self.identifier(id),
+                    "arguments": self.call_arguments(arguments),
+                }),
+            ),
+            InlineExpression::MessageReference { id, attribute } => (
+                "MessageReference",
+                json!({
+                    "id": self.identifier(id),
+                    "attribute": attribute.as_ref().map(|attribute| self.identifier(attribute)),
+                }),
+            ),
+            InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => (
+                "TermReference",
+                json!({
+                    "id": self.identifier(id),
+                    "attribute": attribute.as_ref().map(|attribute| self.identifier(attribute)),
+                    "arguments": arguments.as_ref().map(|arguments| self.call_arguments(arguments)),
+                }),
+            ),
+            InlineExpression::VariableReference { id } => {
+                ("VariableReference", json!({ "id": self.identifier(id) }))
+            }
+            InlineExpression::Placeable { expression } => (
+                "Placeable",
+                json!({ "expression": self.expression(expression) }),
+            ),
+            #[cfg(feature = "unknown-expressions")]
+            InlineExpression::Unknown { content } => (
+                "Junk",
+                json!({ "annotations": [], "content": content.as_ref() }),
+            ),
+        };
+        self.node(kind, fields, expression)
+    }
+
+    fn call_arguments(&self, arguments: &CallArguments<S>) -> Value {
+        let positional: Vec<Value> = arguments
+            .positional
+            .iter()
+            .map(|argument| self.inline_expression(argument))
+            .collect();
+        let named: Vec<Value> = arguments
+            .named
+            .iter()
+            .map(|argument| {
+                self.node(
+                    "NamedArgument",
+                    json!({
+                        "name": self.identifier(&argument.name),
+                        "value": self.inline_expression(&argument.value),
+                    }),
+                    argument,
+                )
+            })
+            .collect();
+        self.node(
+            "CallArguments",
+            json!({ "positional": positional, "named": named }),
+            arguments,
+        )
+    }
+}
+
+fn node(kind: &str, fields: Value, span: Option<Range<usize>>) -> Value {
+    let mut object = Map::new();
+    object.insert(String::from("type"), Value::from(kind));
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+    if let Some(span) = span {
+        object.insert(
+            String::from("span"),
+            json!({ "type": "Span", "start": span.start, "end": span.end }),
+        );
+    }
+    Value::Object(object)
+}
@@ -8,8 +8,29 @@
 //! The crate provides a [`parser`] module which allows for parsing of an
 //! input string to an Abstract Syntax Tree defined in the [`ast`] module.
 //!
+//! The [`cst`] module parses a lossless syntax tree instead, which keeps the
+//! comments, blank lines and whitespace of the source for tooling such as
+//! formatters.
+//!
+//! The [`format()`] function reprints an FTL source with a normalized layout,
+//! for use in formatters.
+//!
 //! The [`unicode`] module exposes a set of helper functions used to decode
-//! escaped unicode literals according to Fluent specification.
+//! escaped unicode literals according to Fluent specification, and the
+//! [`literal`] module evaluates string and number literals of the AST.
+//!
+//! The [`diff()`] function compares the messages and terms of two versions of
+//! a resource.
+//!
+//! The [`graph`] module builds the graph of the references between the
+//! messages and terms of resources, with cycle detection and topological
+//! ordering.
+//!
+//! The [`highlight`] module classifies the source of a parsed resource for
+//! syntax highlighting in editors.
+//!
+//! The [`lint()`] function reports mistakes in a resource which parse
+//! correctly, such as duplicate messages and cyclic terms.
 //!
 //! # Example
 //!
@@ -46,7 +67,65 @@
 //!     ),
 //! );
 //! ```
+//!
+//! # Features
+//!
+//! The optional `serde` feature derives `Serialize` and `Deserialize` for all
+//! the [`ast`] types, for storing parsed resources. Nodes are written in the
+//! shape of the JSON AST of the reference parser, where enums are tagged with
+//! a `type` field, so they need a self-describing format such as JSON or CBOR.
+//! Resources are deserialized as `Resource<String>`.
+//!
+//! The `json` feature adds the `json` module, which converts the AST to the
+//! JSON of the JavaScript implementation, and lets the `parser` binary print
+//! the AST.
+//!
+//! The `miette` feature implements `miette::Diagnostic` for
+//! [`ParserError`](parser::ParserError), with the code, labels and help of
+//! [`ParserError::diagnostic`](parser::ParserError::diagnostic).
+//!
+//! The `arbitrary` feature derives `arbitrary::Arbitrary` for all the [`ast`]
+//! types, and adds the `ast::fuzz` module, which makes generated resources
+//! valid for fuzzing and property tests.
+//!
+//! The `rayon` feature adds `parser::parse_all`, which parses many resources
+//! in parallel.
+//!
+//! The `recover-placeables` feature adds `ast::PatternElement::Junk` and
+//! `parser::ParserOptions::recover_placeables`, which keeps an invalid
+//! placeable in its pattern rather than turning its entry into junk.
+//!
+//! The `unknown-expressions` feature adds `ast::InlineExpression::Unknown` and
+//! `parser::ParserOptions::unknown_expressions`, which keeps the content of a
+//! placeable using syntax of a later version of Fluent.
+//!
+//! # `no_std`
+//!
+//! The crate depends only on `core` and `alloc` when the default `std` feature
+//! is disabled. Building it this way requires Rust 1.81, where the `Error` trait
+//! is available in `core`. The parser, the serializer and the `serde` feature
+//! are available without `std`, while the `json`, `miette`, `arbitrary` and
+//! `rayon` features require it.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod ast;
+pub mod cst;
+pub mod diff;
+mod format;
+pub mod graph;
+pub mod hash;
+pub mod highlight;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod lint;
+pub mod literal;
 pub mod parser;
 pub mod serializer;
 pub mod unicode;
+
+pub use diff::diff;
+pub use format::format;
+pub use hash::hash_entry;
+pub use lint::lint;
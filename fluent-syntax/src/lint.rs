@@ -0,0 +1,345 @@
+//! Checks of a resource which go beyond its syntax.
+//!
+//! [`lint`] reports the mistakes which parse correctly but which are most
+//! likely unintended, such as a message defined twice or a term which
+//! references itself.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::lint::{lint, LintDiagnostic, LintKind};
+//! use fluent_syntax::parser;
+//!
+//! let ftl = r#"
+//! -brand = Firefox
+//! -unused = Unused
+//!
+//! hello = Hello from { -brand }!
+//! hello = Hi!
+//! "#;
+//! let resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+//!
+//! assert_eq!(
+//!     lint(&resource),
+//!     vec![
+//!         LintDiagnostic {
+//!             entry: 3,
+//!             kind: LintKind::DuplicateMessage { id: "hello".to_string() },
+//!         },
+//!         LintDiagnostic {
+//!             entry: 1,
+//!             kind: LintKind::UnusedTerm { id: "unused".to_string() },
+//!         },
+//!     ]
+//! );
+//! ```
+use crate::ast::visit::{self, Visit};
+use crate::ast::*;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use thiserror::Error;
+
+/// A problem found by [`lint`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("{}", self.kind)]
+pub struct LintDiagnostic {
+    /// The index of the entry in the body of the resource.
+    pub entry: usize,
+    pub kind: LintKind,
+}
+
+/// Kind of a [`LintDiagnostic`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum LintKind {
+    /// The message is defined by an earlier entry. Only the last definition
+    /// is used by `fluent-bundle` when it overrides messages, and adding the
+    /// resource fails otherwise.
+    #[error("The message \"{id}\" is already defined")]
+    DuplicateMessage { id: String },
+    /// The term is defined by an earlier entry.
+    #[error("The term \"-{id}\" is already defined")]
+    DuplicateTerm { id: String },
+    /// No other message or term of the resource references the term.
+    ///
+    /// Terms are private to their resource, so such a term can't be used
+    /// anywhere.
+    #[error("The term \"-{id}\" is never referenced")]
+    UnusedTerm { id: String },
+    /// The terms reference each other, or the term references itself, so
+    /// formatting them never ends. The terms are in the order of their
+    /// entries, and the diagnostic is reported at the first of them.
+    #[error("The terms reference each other in a cycle: {}", Terms(.cycle))]
+    CyclicTerm { cycle: Vec<String> },
+    /// A select expression of the entry has no default variant.
+    ///
+    /// The parser reports this as an error, but an AST built by hand may
+    /// still lack a default variant.
+    #[error("The select expression must have a default variant")]
+    MissingDefaultVariant,
+    /// The variable is used as two different types in the same entry, for
+    /// example as the argument of `NUMBER` in one variant and of `DATETIME`
+    /// in another.
+    #[error("The variable \"${name}\" is used as {first} and as {second}")]
+    InconsistentVariableType {
+        name: String,
+        first: VariableType,
+        second: VariableType,
+    },
+}
+
+/// The type a variable is used as, see
+/// [`LintKind::InconsistentVariableType`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VariableType {
+    /// The argument of `NUMBER`, or the selector of variants with number or
+    /// plural category keys.
+    Number,
+    /// The argument of `DATETIME`.
+    DateTime,
+    /// The selector of variants with other keys, such as `[male]` and
+    /// `[female]`.
+    String,
+}
+
+impl fmt::Display for VariableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Number => "a number",
+            Self::DateTime => "a date",
+            Self::String => "a string",
+        })
+    }
+}
+
+impl LintKind {
+    /// The position of the kind in the order of the diagnostics of [`lint`],
+    /// where duplicates are reported together.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::DuplicateMessage { .. } | Self::DuplicateTerm { .. } => 0,
+            Self::UnusedTerm { .. } => 1,
+            Self::CyclicTerm { .. } => 2,
+            Self::MissingDefaultVariant => 3,
+            Self::InconsistentVariableType { .. } => 4,
+        }
+    }
+}
+
+/// Displays the names of a cycle of terms.
+struct Terms<'a>(&'a [String]);
+
+impl fmt::Display for Terms<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, id) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "\"-{}\"", id)?;
+        }
+        if let Some(id) = self.0.first() {
+            write!(f, " -> \"-{}\"", id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `resource` for mistakes which are valid syntax.
+///
+/// The diagnostics are sorted by their kind, in the order of [`LintKind`]
+/// except that duplicate messages and terms come together, and then by their
+/// entry.
+pub fn lint<S: AsRef<str>>(resource: &Resource<S>) -> Vec<LintDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut messages = BTreeSet::new();
+    // The first entry of each term, the terms it references, and the terms
+    // referenced by other entries.
+    let mut terms: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut references: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut used = BTreeSet::new();
+
+    for (index, entry) in resource.body.iter().enumerate() {
+        let id = match entry {
+            Entry::Message(message) => {
+                let id = message.id.name.as_ref();
+                if !messages.insert(id) {
+                    diagnostics.push(LintDiagnostic {
+                        entry: index,
+                        kind: LintKind::DuplicateMessage { id: id.to_string() },
+                    });
+                }
+                None
+            }
+            Entry::Term(term) => {
+                let id = term.id.name.as_ref();
+                if terms.contains_key(id) {
+                    diagnostics.push(LintDiagnostic {
+                        entry: index,
+                        kind: LintKind::DuplicateTerm { id: id.to_string() },
+                    });
+                } else {
+                    terms.insert(id, index);
+                }
+                Some(id)
+            }
+            _ => continue,
+        };
+
+        let mut linter = EntryLinter::default();
+        linter.visit_entry(entry);
+        for kind in linter.diagnostics {
+            diagnostics.push(LintDiagnostic { entry: index, kind });
+        }
+        for referenced in linter.terms {
+            if id != Some(referenced) {
+                used.insert(referenced);
+            }
+            if let Some(id) = id {
+                references.entry(id).or_default().insert(referenced);
+            }
+        }
+    }
+
+    let mut first_entries: Vec<(usize, &str)> =
+        terms.iter().map(|(&id, &index)| (index, id)).collect();
+    first_entries.sort_unstable();
+    for &(index, id) in &first_entries {
+        if !used.contains(id) {
+            diagnostics.push(LintDiagnostic {
+                entry: index,
+                kind: LintKind::UnusedTerm { id: id.to_string() },
+            });
+        }
+    }
+
+    let reachable = |id: &str| {
+        let mut reached = BTreeSet::new();
+        let mut stack = Vec::from([id]);
+        while let Some(id) = stack.pop() {
+            for &next in references.get(id).into_iter().flatten() {
+                if reached.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        reached
+    };
+    let mut in_cycle = BTreeSet::new();
+    for &(index, id) in &first_entries {
+        if in_cycle.contains(id) {
+            continue;
+        }
+        let reached = reachable(id);
+        if !reached.contains(id) {
+            continue;
+        }
+        // The terms which reach back to `id` form a cycle with it.
+        let cycle: Vec<&str> = first_entries
+            .iter()
+            .map(|&(_, other)| other)
+            .filter(|&other| reached.contains(other) && reachable(other).contains(id))
+            .collect();
+        in_cycle.extend(cycle.iter().copied());
+        diagnostics.push(LintDiagnostic {
+            entry: index,
+            kind: LintKind::CyclicTerm {
+                cycle: cycle.into_iter().map(ToString::to_string).collect(),
+            },
+        });
+    }
+
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.kind.rank(), diagnostic.entry));
+    diagnostics
+}
+
+/// Collects the term references of an entry, and checks its select
+/// expressions and variables.
+#[derive(Default)]
+struct EntryLinter<'ast> {
+    terms: BTreeSet<&'ast str>,
+    variables: BTreeMap<&'ast str, VariableType>,
+    inconsistent: BTreeSet<&'ast str>,
+    diagnostics: Vec<LintKind>,
+}
+
+impl<'ast> EntryLinter<'ast> {
+    fn use_variable(&mut self, name: &'ast str, ty: VariableType) {
+        match self.variables.get(name) {
+            None => {
+                self.variables.insert(name, ty);
+            }
+            Some(&first) if first != ty => {
+                // Only the first inconsistent use of each variable is reported.
+                if self.inconsistent.insert(name) {
+                    self.diagnostics.push(LintKind::InconsistentVariableType {
+                        name: name.to_string(),
+                        first,
+                        second: ty,
+                    });
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// The type of a selector, from the keys of its variants, or `None` if they
+/// only contain `other` or mix both kinds of keys.
+fn selector_type<S: AsRef<str>>(variants: &[Variant<S>]) -> Option<VariableType> {
+    let mut number = false;
+    let mut string = false;
+    for variant in variants {
+        match &variant.key {
+            VariantKey::NumberLiteral { .. } => number = true,
+            VariantKey::Identifier { name } => match name.as_ref() {
+                "other" => {}
+                "zero" | "one" | "two" | "few" | "many" => number = true,
+                _ => string = true,
+            },
+        }
+    }
+    match (number, string) {
+        (true, false) => Some(VariableType::Number),
+        (false, true) => Some(VariableType::String),
+        _ => None,
+    }
+}
+
+impl<'ast, S: AsRef<str> + 'ast> Visit<'ast, S> for EntryLinter<'ast> {
+    fn visit_expression(&mut self, node: &'ast Expression<S>) {
+        if let Expression::Select { selector, variants } = node {
+            if !variants.iter().any(|variant| variant.default) {
+                self.diagnostics.push(LintKind::MissingDefaultVariant);
+            }
+            if let InlineExpression::VariableReference { id } = selector {
+                if let Some(ty) = selector_type(variants) {
+                    self.use_variable(id.name.as_ref(), ty);
+                }
+            }
+        }
+        visit::walk_expression(self, node);
+    }
+
+    fn visit_inline_expression(&mut self, node: &'ast InlineExpression<S>) {
+        match node {
+            InlineExpression::TermReference { id, .. } => {
+                self.terms.insert(id.name.as_ref());
+            }
+            InlineExpression::FunctionReference { id, arguments } => {
+                let ty = match id.name.as_ref() {
+                    "NUMBER" => Some(VariableType::Number),
+                    "DATETIME" => Some(VariableType::DateTime),
+                    _ => None,
+                };
+                if let (Some(ty), Some(InlineExpression::VariableReference { id })) =
+                    (ty, arguments.positional.first())
+                {
+                    self.use_variable(id.name.as_ref(), ty);
+                }
+            }
+            _ => {}
+        }
+        visit::walk_inline_expression(self, node);
+    }
+}
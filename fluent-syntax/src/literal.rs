@@ -0,0 +1,131 @@
+//! Evaluation of the literals of the AST.
+//!
+//! The AST keeps string and number literals as they are written in the
+//! source. The functions of this module compute their values the same way
+//! `fluent-bundle` does when it formats a message, for tools which need them
+//! without resolving messages.
+//!
+//! # Example
+//!
+//! ```
+//! use fluent_syntax::ast;
+//! use fluent_syntax::literal::{LiteralValue, NumberValue};
+//!
+//! let string = ast::InlineExpression::StringLiteral {
+//!     value: "Caf\\u00E9 \\\"Fluent\\\"",
+//! };
+//! assert_eq!(
+//!     string.literal_value(),
+//!     Some(LiteralValue::String("Café \"Fluent\"".into()))
+//! );
+//!
+//! let number = ast::InlineExpression::NumberLiteral { value: "-1.50" };
+//! assert_eq!(
+//!     number.literal_value(),
+//!     Some(LiteralValue::Number(NumberValue {
+//!         value: -1.5,
+//!         fraction_digits: 2,
+//!     }))
+//! );
+//! ```
+use crate::ast::{InlineExpression, VariantKey};
+use crate::unicode::unescape_unicode_to_string;
+use alloc::borrow::Cow;
+
+/// The value of a string or a number literal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LiteralValue<'a> {
+    String(Cow<'a, str>),
+    Number(NumberValue),
+}
+
+/// The value of a number literal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberValue {
+    pub value: f64,
+    /// The number of digits after the decimal point, which `fluent-bundle`
+    /// keeps as the minimum number of fraction digits when it formats the
+    /// number.
+    pub fraction_digits: usize,
+}
+
+/// Returns the value of the content of a string literal, with its escape
+/// sequences replaced.
+///
+/// Unknown or invalid escape sequences are replaced with U+FFFD, the
+/// replacement character.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::literal::string_value;
+///
+/// assert_eq!(string_value("Tab\\u0009 and \\\\"), "Tab\t and \\");
+/// assert_eq!(string_value("\\q"), "\u{FFFD}");
+/// ```
+pub fn string_value(value: &str) -> Cow<'_, str> {
+    unescape_unicode_to_string(value)
+}
+
+/// Returns the value of a number literal, or `None` if `value` is not a
+/// number literal of the syntax, `-?[0-9]+(\.[0-9]+)?`.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::literal::{number_value, NumberValue};
+///
+/// assert_eq!(
+///     number_value("007"),
+///     Some(NumberValue {
+///         value: 7.0,
+///         fraction_digits: 0,
+///     })
+/// );
+/// assert_eq!(number_value("1e3"), None);
+/// ```
+pub fn number_value(value: &str) -> Option<NumberValue> {
+    if !is_number_literal(value) {
+        return None;
+    }
+    let fraction_digits = value.find('.').map_or(0, |pos| value.len() - pos - 1);
+    value.parse().ok().map(|value| NumberValue {
+        value,
+        fraction_digits,
+    })
+}
+
+/// `-?[0-9]+(\.[0-9]+)?`
+pub(crate) fn is_number_literal(value: &str) -> bool {
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let value = value.strip_prefix('-').unwrap_or(value);
+    match value.split_once('.') {
+        Some((integer, fraction)) => is_digits(integer) && is_digits(fraction),
+        None => is_digits(value),
+    }
+}
+
+impl<S: AsRef<str>> InlineExpression<S> {
+    /// Returns the value of a string or a number literal, or `None` for other
+    /// expressions and for invalid number literals.
+    pub fn literal_value(&self) -> Option<LiteralValue<'_>> {
+        match self {
+            Self::StringLiteral { value } => {
+                Some(LiteralValue::String(string_value(value.as_ref())))
+            }
+            Self::NumberLiteral { value } => number_value(value.as_ref()).map(LiteralValue::Number),
+            _ => None,
+        }
+    }
+}
+
+impl<S: AsRef<str>> VariantKey<S> {
+    /// Returns the value of a number key, or `None` for identifiers and for
+    /// invalid number literals.
+    pub fn number_value(&self) -> Option<NumberValue> {
+        match self {
+            Self::NumberLiteral { value } => number_value(value.as_ref()),
+            Self::Identifier { .. } => None,
+        }
+    }
+}
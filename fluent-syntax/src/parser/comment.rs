@@ -1,5 +1,6 @@
 use super::{core::Parser, core::Result, Slice};
 use crate::ast;
+use alloc::vec;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub(super) enum Level {
@@ -16,6 +17,8 @@ where
     pub(super) fn get_comment(&mut self) -> Result<(ast::Comment<S>, Level)> {
         let mut level = Level::None;
         let mut content = vec![];
+        let start = self.ptr;
+        let mut end = self.ptr;
 
         while self.ptr < self.length {
             let line_level = self.get_comment_level();
@@ -30,6 +33,7 @@ where
             level = line_level;
 
             if self.ptr == self.length {
+                end = self.ptr;
                 break;
             } else if self.is_eol() {
                 content.push(self.get_comment_line());
@@ -44,17 +48,17 @@ where
                 }
                 content.push(self.get_comment_line());
             }
+            end = self.ptr;
             self.skip_eol();
         }
 
+        self.record_span(start..end);
         Ok((ast::Comment { content }, level))
     }
 
     pub(super) fn skip_comment(&mut self) {
         loop {
-            while self.ptr < self.length && !self.is_eol() {
-                self.ptr += 1;
-            }
+            self.skip_to_eol();
             self.ptr += 1;
             if self.is_current_byte(b'#') {
                 self.ptr += 1;
@@ -80,9 +84,7 @@ where
     fn get_comment_line(&mut self) -> S {
         let start_pos = self.ptr;
 
-        while !self.is_eol() {
-            self.ptr += 1;
-        }
+        self.skip_to_eol();
 
         self.source.slice(start_pos..self.ptr)
     }
@@ -1,16 +1,31 @@
 use super::{
     comment,
     errors::{ErrorKind, ParserError},
+    options::ParserOptions,
     slice::Slice,
+    spans::{self, SpannedResource},
 };
 use crate::ast;
+use ::core::ops::Range;
+use alloc::borrow::ToOwned;
+use alloc::string::ToString;
+use alloc::{vec, vec::Vec};
 
-pub type Result<T> = std::result::Result<T, ParserError>;
+pub type Result<T> = ::core::result::Result<T, ParserError>;
 
 pub struct Parser<S> {
     pub(super) source: S,
     pub(super) ptr: usize,
     pub(super) length: usize,
+    pub(super) spans: Option<Vec<Range<usize>>>,
+    pub(super) options: ParserOptions,
+    /// The errors of the placeables recovered in the current entry.
+    pub(super) recovered: Vec<ParserError>,
+    /// The last comment and its start, if it may belong to the next entry.
+    pub(super) last_comment: Option<(ast::Comment<S>, usize)>,
+    last_blank_count: usize,
+    /// The number of placeables and call arguments the parser is in.
+    pub(super) depth: usize,
 }
 
 impl<'s, S> Parser<S>
@@ -18,69 +33,133 @@ where
     S: Slice<'s>,
 {
     pub fn new(source: S) -> Self {
+        Self::with_options(source, ParserOptions::default())
+    }
+
+    pub fn with_options(source: S, options: ParserOptions) -> Self {
         let length = source.as_ref().len();
         Self {
             source,
             ptr: 0,
             length,
+            spans: None,
+            options,
+            recovered: vec![],
+            last_comment: None,
+            last_blank_count: 0,
+            depth: 0,
         }
     }
 
     pub fn parse(
         mut self,
-    ) -> std::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)> {
+    ) -> ::core::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)> {
+        let (body, errors) = self.get_body();
+        if errors.is_empty() {
+            Ok(ast::Resource { body })
+        } else {
+            Err((ast::Resource { body }, errors))
+        }
+    }
+
+    pub fn parse_with_spans(
+        mut self,
+    ) -> ::core::result::Result<SpannedResource<S>, (SpannedResource<S>, Vec<ParserError>)> {
+        self.spans = Some(vec![]);
+        let (body, errors) = self.get_body();
+        let resource = spans::build(ast::Resource { body }, self.spans.unwrap_or_default());
+        if errors.is_empty() {
+            Ok(resource)
+        } else {
+            Err((resource, errors))
+        }
+    }
+
+    fn get_body(&mut self) -> (Vec<ast::Entry<S>>, Vec<ParserError>) {
+        self.get_body_until(|_| false)
+    }
+
+    /// Parses entries until the end of the input, or until `stop` returns
+    /// `true` for the start of the next entry.
+    pub(super) fn get_body_until(
+        &mut self,
+        mut stop: impl FnMut(usize) -> bool,
+    ) -> (Vec<ast::Entry<S>>, Vec<ParserError>) {
         let mut errors = vec![];
 
         let mut body = vec![];
 
         self.skip_blank_block();
-        let mut last_comment = None;
-        let mut last_blank_count = 0;
-
         while self.ptr < self.length {
-            let entry_start = self.ptr;
-            let mut entry = self.get_entry(entry_start);
-
-            if let Some(comment) = last_comment.take() {
-                match entry {
-                    Ok(ast::Entry::Message(ref mut msg)) if last_blank_count < 2 => {
-                        msg.comment = Some(comment);
-                    }
-                    Ok(ast::Entry::Term(ref mut term)) if last_blank_count < 2 => {
-                        term.comment = Some(comment);
-                    }
-                    _ => {
-                        body.push(ast::Entry::Comment(comment));
-                    }
-                }
+            // A pending comment may belong to the next entry.
+            if self.last_comment.is_none() && stop(self.ptr) {
+                break;
+            }
+            self.get_next_entries(&mut body, &mut errors);
+        }
+
+        if let Some((last_comment, _)) = self.last_comment.take() {
+            body.push(ast::Entry::Comment(last_comment));
+        }
+        (body, errors)
+    }
+
+    /// Parses the next entry, and pushes the entries it completes to `body`:
+    /// the pending comment, if it does not belong to the entry, and the entry,
+    /// unless it is a comment which may belong to the next one.
+    pub(super) fn get_next_entries(
+        &mut self,
+        body: &mut Vec<ast::Entry<S>>,
+        errors: &mut Vec<ParserError>,
+    ) {
+        let entry_start = self.ptr;
+        let spans_len = self.spans_len();
+        let mut entry = self.get_entry(entry_start);
+        if let Some(limit) = self.options.max_entry_length {
+            if entry.is_ok() && self.ptr - entry_start > limit {
+                entry = error!(ErrorKind::EntryTooLong { limit }, entry_start + limit);
             }
+        }
+        if entry.is_ok() {
+            errors.append(&mut self.recovered);
+        } else {
+            self.recovered.clear();
+        }
 
+        if let Some((comment, comment_start)) = self.last_comment.take() {
             match entry {
-                Ok(ast::Entry::Comment(comment)) => {
-                    last_comment = Some(comment);
+                Ok(ast::Entry::Message(ref mut msg)) if self.last_blank_count < 2 => {
+                    msg.comment = Some(comment);
+                    self.extend_last_span(comment_start);
                 }
-                Ok(entry) => {
-                    body.push(entry);
+                Ok(ast::Entry::Term(ref mut term)) if self.last_blank_count < 2 => {
+                    term.comment = Some(comment);
+                    self.extend_last_span(comment_start);
                 }
-                Err(mut err) => {
-                    self.skip_to_next_entry_start();
-                    err.slice = Some(entry_start..self.ptr);
-                    errors.push(err);
-                    let content = self.source.slice(entry_start..self.ptr);
-                    body.push(ast::Entry::Junk { content });
+                _ => {
+                    body.push(ast::Entry::Comment(comment));
                 }
             }
-            last_blank_count = self.skip_blank_block();
         }
 
-        if let Some(last_comment) = last_comment.take() {
-            body.push(ast::Entry::Comment(last_comment));
-        }
-        if errors.is_empty() {
-            Ok(ast::Resource { body })
-        } else {
-            Err((ast::Resource { body }, errors))
+        match entry {
+            Ok(ast::Entry::Comment(comment)) => {
+                self.last_comment = Some((comment, entry_start));
+            }
+            Ok(entry) => {
+                body.push(entry);
+            }
+            Err(mut err) => {
+                self.skip_to_next_entry_start();
+                err.slice = Some(entry_start..self.ptr);
+                errors.push(err);
+                self.truncate_spans(spans_len);
+                self.record_span(entry_start..self.ptr);
+                let content = self.source.slice(entry_start..self.ptr);
+                body.push(ast::Entry::Junk { content });
+            }
         }
+        self.last_blank_count = self.skip_blank_block();
     }
 
     fn get_entry(&mut self, entry_start: usize) -> Result<ast::Entry<S>> {
@@ -117,6 +196,7 @@ where
                 entry_start, self.ptr
             );
         }
+        self.record_span(entry_start..self.last_span_end());
 
         Ok(ast::Message {
             id,
@@ -140,6 +220,7 @@ where
         let attributes = self.get_attributes();
 
         if let Some(value) = value {
+            self.record_span(entry_start..self.last_span_end());
             Ok(ast::Term {
                 id,
                 value,
@@ -162,15 +243,19 @@ where
         loop {
             let line_start = self.ptr;
             self.skip_blank_inline();
+            let start = self.ptr;
             if !self.take_byte_if(b'.') {
                 self.ptr = line_start;
                 break;
             }
 
+            let spans_len = self.spans_len();
             if let Ok(attr) = self.get_attribute() {
+                self.record_span(start..self.last_span_end());
                 attributes.push(attr);
             } else {
                 self.ptr = line_start;
+                self.truncate_spans(spans_len);
                 break;
             }
         }
@@ -198,6 +283,7 @@ where
         }
 
         let name = self.source.slice(self.ptr - 1..ptr);
+        self.record_span(self.ptr - 1..ptr);
         self.ptr = ptr;
 
         ast::Identifier { name }
@@ -229,10 +315,12 @@ where
         self.skip_blank();
 
         let key = if self.is_number_start() {
-            ast::VariantKey::NumberLiteral {
-                value: self.get_number_literal()?,
-            }
+            let start = self.ptr;
+            let value = self.get_number_literal()?;
+            self.record_span(start..self.ptr);
+            ast::VariantKey::NumberLiteral { value }
         } else {
+            // The span recorded for the identifier is that of the key.
             ast::VariantKey::Identifier {
                 name: self.get_identifier()?.name,
             }
@@ -250,6 +338,7 @@ where
         let mut has_default = false;
 
         loop {
+            let start = self.ptr;
             let default = self.take_byte_if(b'*');
             if default {
                 if has_default {
@@ -262,12 +351,18 @@ where
             if !self.take_byte_if(b'[') {
                 break;
             }
+            if let Some(limit) = self.options.max_variants {
+                if variants.len() == limit {
+                    return error!(ErrorKind::TooManyVariants { limit }, start);
+                }
+            }
 
             let key = self.get_variant_key()?;
 
             let value = self.get_pattern()?;
 
             if let Some(value) = value {
+                self.record_span(start..self.last_span_end());
                 variants.push(ast::Variant {
                     key,
                     value,
@@ -286,7 +381,64 @@ where
         }
     }
 
+    #[cfg(feature = "unknown-expressions")]
     pub(super) fn get_placeable(&mut self) -> Result<ast::Expression<S>> {
+        let open = self.ptr - 1;
+        let spans_len = self.spans_len();
+        let recovered_len = self.recovered.len();
+        match self.nested(Self::get_placeable_unnested) {
+            Err(err) if self.options.unknown_expressions => {
+                self.get_unknown_expression(open, spans_len, recovered_len, err)
+            }
+            result => result,
+        }
+    }
+
+    #[cfg(not(feature = "unknown-expressions"))]
+    pub(super) fn get_placeable(&mut self) -> Result<ast::Expression<S>> {
+        self.nested(Self::get_placeable_unnested)
+    }
+
+    /// Skips the invalid placeable opened at `open`, and returns its content as
+    /// an unknown expression, or `err` if the placeable is empty or unclosed.
+    #[cfg(feature = "unknown-expressions")]
+    fn get_unknown_expression(
+        &mut self,
+        open: usize,
+        spans_len: usize,
+        recovered_len: usize,
+        mut err: ParserError,
+    ) -> Result<ast::Expression<S>> {
+        if !self.skip_placeable(open) {
+            return Err(err);
+        }
+        let bytes = self.source.as_ref().as_bytes();
+        let is_blank = |b: &u8| matches!(b, b' ' | b'\r' | b'\n');
+        let close = self.ptr - 1;
+        let start = bytes[open + 1..close]
+            .iter()
+            .position(|b| !is_blank(b))
+            .map_or(close, |pos| open + 1 + pos);
+        let end = bytes[start..close]
+            .iter()
+            .rposition(|b| !is_blank(b))
+            .map_or(start, |pos| start + pos + 1);
+        if start == end {
+            return Err(err);
+        }
+
+        self.truncate_spans(spans_len);
+        self.recovered.truncate(recovered_len);
+        self.record_span(start..end);
+        self.record_span(start..end);
+        err.slice = Some(open..self.ptr);
+        self.recovered.push(err);
+        Ok(ast::Expression::Inline(ast::InlineExpression::Unknown {
+            content: self.source.slice(start..end),
+        }))
+    }
+
+    fn get_placeable_unnested(&mut self) -> Result<ast::Expression<S>> {
         self.skip_blank();
         let exp = self.get_expression()?;
         self.skip_blank_inline();
@@ -0,0 +1,220 @@
+//! Structured diagnostics for parser errors.
+use super::{ErrorKind, ParserError};
+use ::core::ops::Range;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A [`ParserError`] described for display by compilers, linters and
+/// editors: a stable code, a message, labeled spans of the source and an
+/// optional hint on how to fix it.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+///
+/// let ftl = "key = { $n ->\n    [one] One\n    [other] Many\n}\n";
+/// let (_, errors) = parser::parse(ftl).expect_err("Resource should contain errors.");
+///
+/// let diagnostic = errors[0].diagnostic();
+/// assert_eq!(diagnostic.code, "E0010");
+/// assert_eq!(diagnostic.message, "The select expression must have a default variant");
+/// assert_eq!(diagnostic.primary.message, "no default variant");
+/// assert_eq!(&ftl[diagnostic.secondary[0].span.clone()], ftl);
+/// assert_eq!(
+///     diagnostic.help,
+///     Some("mark one of the variants as the default with `*`, as in `*[other]`")
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// The code of the error, see [`ErrorKind::code`].
+    pub code: &'static str,
+    /// The message of the error, which is its [`Display`](::core::fmt::Display)
+    /// output.
+    pub message: String,
+    /// The position of the error.
+    pub primary: Label,
+    /// Other spans related to the error, such as the range of the input
+    /// turned into [`Junk`](crate::ast::Entry::Junk).
+    pub secondary: Vec<Label>,
+    /// A hint on how to fix the error, see [`ErrorKind::help`].
+    pub help: Option<&'static str>,
+}
+
+/// A span of the source, labeled with a short message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Label {
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl ParserError {
+    /// Returns the error as a [`Diagnostic`].
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut secondary = Vec::new();
+        if let Some(slice) = &self.slice {
+            secondary.push(Label {
+                span: slice.clone(),
+                message: "skipped as junk".to_string(),
+            });
+        }
+        Diagnostic {
+            code: self.kind.code(),
+            message: self.kind.to_string(),
+            primary: Label {
+                span: self.pos.clone(),
+                message: self.kind.label(),
+            },
+            secondary,
+            help: self.kind.help(),
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Returns the code of the error, such as `E0003`.
+    ///
+    /// Codes are stable across versions, and the same as the codes of the
+    /// JavaScript implementation for the same errors. The errors for the
+    /// limits of [`ParserOptions`](super::ParserOptions), which it does not
+    /// have, use codes from `E0100`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ExpectedToken(_) => "E0003",
+            Self::ExpectedCharRange { .. } => "E0004",
+            Self::ExpectedMessageField { .. } => "E0005",
+            Self::ExpectedTermField { .. } => "E0006",
+            Self::ForbiddenCallee => "E0008",
+            Self::MissingDefaultVariant => "E0010",
+            Self::MissingValue => "E0012",
+            Self::ExpectedLiteral => "E0014",
+            Self::MultipleDefaultVariants => "E0015",
+            Self::MessageReferenceAsSelector => "E0016",
+            Self::TermReferenceAsSelector => "E0017",
+            Self::MessageAttributeAsSelector => "E0018",
+            Self::TermAttributeAsPlaceable => "E0019",
+            Self::UnterminatedStringLiteral => "E0020",
+            Self::PositionalArgumentFollowsNamed => "E0021",
+            Self::DuplicatedNamedArgument(_) => "E0022",
+            Self::UnknownEscapeSequence(_) => "E0025",
+            Self::InvalidUnicodeEscapeSequence(_) => "E0026",
+            Self::UnbalancedClosingBrace => "E0027",
+            Self::ExpectedInlineExpression => "E0028",
+            Self::ExpectedSimpleExpressionAsSelector => "E0029",
+            Self::TooDeeplyNested { .. } => "E0100",
+            Self::EntryTooLong { .. } => "E0101",
+            Self::TooManyVariants { .. } => "E0102",
+        }
+    }
+
+    /// Returns a hint on how to fix the error, if there is one beyond its
+    /// message.
+    pub fn help(&self) -> Option<&'static str> {
+        match self {
+            Self::ExpectedMessageField { .. } => {
+                Some("add a value after `=`, or an attribute on the next line")
+            }
+            Self::ExpectedTermField { .. } => Some("add a value after `=`"),
+            Self::ForbiddenCallee => {
+                Some("function names only contain uppercase letters, digits, `_` and `-`")
+            }
+            Self::MissingDefaultVariant => {
+                Some("mark one of the variants as the default with `*`, as in `*[other]`")
+            }
+            Self::MultipleDefaultVariants => Some("remove the `*` from all but one variant"),
+            Self::MessageReferenceAsSelector | Self::MessageAttributeAsSelector => {
+                Some("select on a variable, or on an attribute of a term")
+            }
+            Self::TermReferenceAsSelector => {
+                Some("select on an attribute of the term, as in `-term.attr`")
+            }
+            Self::TermAttributeAsPlaceable => Some("attributes of terms are only for selectors"),
+            Self::UnterminatedStringLiteral => {
+                Some("close the string with `\"` before the end of the line")
+            }
+            Self::PositionalArgumentFollowsNamed => {
+                Some("move the positional arguments before the named ones")
+            }
+            Self::UnknownEscapeSequence(_) | Self::InvalidUnicodeEscapeSequence(_) => {
+                Some("the escape sequences are `\\\\`, `\\\"`, `\\uXXXX` and `\\UXXXXXX`")
+            }
+            Self::UnbalancedClosingBrace => Some("write a literal `}` as `{ \"}\" }`"),
+            Self::ExpectedLiteral => Some("named arguments only take string and number literals"),
+            Self::ExpectedSimpleExpressionAsSelector => {
+                Some("select on a variable, a literal, a function call or a term attribute")
+            }
+            Self::ExpectedToken(_)
+            | Self::ExpectedCharRange { .. }
+            | Self::MissingValue
+            | Self::DuplicatedNamedArgument(_)
+            | Self::ExpectedInlineExpression
+            | Self::TooDeeplyNested { .. }
+            | Self::EntryTooLong { .. }
+            | Self::TooManyVariants { .. } => None,
+        }
+    }
+
+    /// Returns the short message of the primary label.
+    fn label(&self) -> String {
+        match self {
+            Self::ExpectedToken(token) => format!("expected `{}`", token),
+            Self::ExpectedCharRange { range } => format!("expected one of `{}`", range),
+            Self::ExpectedMessageField { .. } => "expected a value or an attribute".to_string(),
+            Self::ExpectedTermField { .. } | Self::MissingValue => "expected a value".to_string(),
+            Self::ForbiddenCallee => "not a function name".to_string(),
+            Self::MissingDefaultVariant => "no default variant".to_string(),
+            Self::MultipleDefaultVariants => "second default variant".to_string(),
+            Self::MessageReferenceAsSelector => "message reference".to_string(),
+            Self::TermReferenceAsSelector => "term reference".to_string(),
+            Self::MessageAttributeAsSelector => "message attribute".to_string(),
+            Self::TermAttributeAsPlaceable => "term attribute".to_string(),
+            Self::UnterminatedStringLiteral => "unterminated string literal".to_string(),
+            Self::PositionalArgumentFollowsNamed => "positional argument".to_string(),
+            Self::DuplicatedNamedArgument(name) => format!("`{}` is already set", name),
+            Self::UnknownEscapeSequence(_) => "unknown escape sequence".to_string(),
+            Self::InvalidUnicodeEscapeSequence(_) => "invalid escape sequence".to_string(),
+            Self::UnbalancedClosingBrace => "unbalanced `}`".to_string(),
+            Self::ExpectedInlineExpression => "expected an inline expression".to_string(),
+            Self::ExpectedSimpleExpressionAsSelector => "not a simple expression".to_string(),
+            Self::ExpectedLiteral => "expected a string or number literal".to_string(),
+            Self::TooDeeplyNested { limit } => format!("nested more than {} levels deep", limit),
+            Self::EntryTooLong { limit } => format!("more than {} bytes", limit),
+            Self::TooManyVariants { limit } => format!("more than {} variants", limit),
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+mod miette_impl {
+    use super::ParserError;
+    use ::core::fmt::Display;
+    use ::core::iter;
+    use alloc::boxed::Box;
+    use miette::LabeledSpan;
+
+    impl miette::Diagnostic for ParserError {
+        fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            Some(Box::new(self.kind.code()))
+        }
+
+        fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            let help = self.kind.help()?;
+            Some(Box::new(help))
+        }
+
+        fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+            let diagnostic = self.diagnostic();
+            let primary = LabeledSpan::new_primary_with_span(
+                Some(diagnostic.primary.message),
+                diagnostic.primary.span,
+            );
+            let secondary = diagnostic
+                .secondary
+                .into_iter()
+                .map(|label| LabeledSpan::new_with_span(Some(label.message), label.span));
+            Some(Box::new(iter::once(primary).chain(secondary)))
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use std::ops::Range;
+use ::core::ops::Range;
+use alloc::string::String;
 use thiserror::Error;
 
 /// Error containing information about an error encountered by the Fluent Parser.
@@ -98,7 +99,8 @@ pub struct ParserError {
     /// Precise location of where the parser encountered the error.
     pub pos: Range<usize>,
     /// Slice of the input from the end of the last valid entry to the beginning
-    /// of the next valid entry with the invalid syntax in the middle.
+    /// of the next valid entry with the invalid syntax in the middle, or the
+    /// invalid placeable when the parser recovers from it.
     pub slice: Option<Range<usize>>,
     /// The type of the error that the parser encountered.
     pub kind: ErrorKind,
@@ -166,4 +168,10 @@ pub enum ErrorKind {
     ExpectedSimpleExpressionAsSelector,
     #[error("Expected a string or number literal")]
     ExpectedLiteral,
+    #[error("Expressions are nested more than {limit} levels deep")]
+    TooDeeplyNested { limit: usize },
+    #[error("The entry is longer than {limit} bytes")]
+    EntryTooLong { limit: usize },
+    #[error("The select expression has more than {limit} variants")]
+    TooManyVariants { limit: usize },
 }
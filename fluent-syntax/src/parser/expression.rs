@@ -1,13 +1,19 @@
 use super::errors::{ErrorKind, ParserError};
 use super::{core::Parser, core::Result, slice::Slice};
 use crate::ast;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
 
 impl<'s, S> Parser<S>
 where
     S: Slice<'s>,
 {
     pub(super) fn get_expression(&mut self) -> Result<ast::Expression<S>> {
+        let start = self.ptr;
         let exp = self.get_inline_expression(false)?;
+        let end = self.last_span_end();
 
         self.skip_blank();
 
@@ -17,6 +23,7 @@ where
                     return error!(ErrorKind::TermAttributeAsPlaceable, self.ptr);
                 }
             }
+            self.record_span(start..end);
             return Ok(ast::Expression::Inline(exp));
         }
 
@@ -56,6 +63,7 @@ where
         self.skip_blank();
 
         let variants = self.get_variants()?;
+        self.record_span(start..self.last_span_end());
 
         Ok(ast::Expression::Select {
             selector: exp,
@@ -66,13 +74,36 @@ where
     pub(super) fn get_inline_expression(
         &mut self,
         only_literal: bool,
+    ) -> Result<ast::InlineExpression<S>> {
+        let start = self.ptr;
+        let exp = self.get_inline_expression_unspanned(only_literal)?;
+        // References may be followed by blanks skipped while looking for call
+        // arguments, so they end with their last child.
+        let end = match exp {
+            ast::InlineExpression::StringLiteral { .. }
+            | ast::InlineExpression::NumberLiteral { .. }
+            | ast::InlineExpression::Placeable { .. } => self.ptr,
+            _ => self.last_span_end(),
+        };
+        self.record_span(start..end);
+        Ok(exp)
+    }
+
+    fn get_inline_expression_unspanned(
+        &mut self,
+        only_literal: bool,
     ) -> Result<ast::InlineExpression<S>> {
         match get_current_byte!(self) {
             Some(b'"') => {
                 self.ptr += 1; // "
                 let start = self.ptr;
-                while let Some(b) = get_current_byte!(self) {
-                    match b {
+                while let Some(rest) = get_remaining_bytes!(self) {
+                    let Some(pos) = memchr::memchr3(b'\\', b'"', b'\n', rest) else {
+                        self.ptr = self.length;
+                        break;
+                    };
+                    self.ptr += pos;
+                    match rest[pos] {
                         b'\\' => match get_byte!(self, self.ptr + 1) {
                             Some(b'\\') | Some(b'{') | Some(b'"') => self.ptr += 2,
                             Some(b'u') => {
@@ -91,10 +122,9 @@ where
                         b'"' => {
                             break;
                         }
-                        b'\n' => {
+                        _ => {
                             return error!(ErrorKind::UnterminatedStringLiteral, self.ptr);
                         }
-                        _ => self.ptr += 1,
                     }
                 }
 
@@ -106,7 +136,11 @@ where
                 let num = self.get_number_literal()?;
                 Ok(ast::InlineExpression::NumberLiteral { value: num })
             }
-            Some(b'-') if !only_literal => {
+            Some(b'-') if only_literal => {
+                let num = self.get_number_literal()?;
+                Ok(ast::InlineExpression::NumberLiteral { value: num })
+            }
+            Some(b'-') => {
                 self.ptr += 1; // -
                 if self.is_identifier_start() {
                     self.ptr += 1;
@@ -130,6 +164,8 @@ where
                 Ok(ast::InlineExpression::VariableReference { id })
             }
             Some(b) if b.is_ascii_alphabetic() => {
+                let start = self.ptr;
+                let spans_len = self.spans_len();
                 self.ptr += 1;
                 let id = self.get_identifier_unchecked();
                 let arguments = self.get_call_arguments()?;
@@ -141,7 +177,24 @@ where
                     Ok(ast::InlineExpression::FunctionReference { id, arguments })
                 } else {
                     let attribute = self.get_attribute_accessor()?;
-                    Ok(ast::InlineExpression::MessageReference { id, attribute })
+                    let end = self.ptr;
+                    match (attribute, self.get_call_arguments()?) {
+                        // A namespaced function, such as `ICU.DATETIME()`, whose
+                        // identifier includes the namespace.
+                        (Some(name), Some(arguments)) => {
+                            if !Self::is_callee(&id.name) || !Self::is_callee(&name.name) {
+                                return error!(ErrorKind::ForbiddenCallee, self.ptr);
+                            }
+                            let id = ast::Identifier {
+                                name: self.source.slice(start..end),
+                            };
+                            self.splice_spans(spans_len..spans_len + 2, start..end);
+                            Ok(ast::InlineExpression::FunctionReference { id, arguments })
+                        }
+                        (attribute, _) => {
+                            Ok(ast::InlineExpression::MessageReference { id, attribute })
+                        }
+                    }
                 }
             }
             Some(b'{') if !only_literal => {
@@ -158,10 +211,15 @@ where
 
     pub fn get_call_arguments(&mut self) -> Result<Option<ast::CallArguments<S>>> {
         self.skip_blank();
+        let start = self.ptr;
         if !self.take_byte_if(b'(') {
             return Ok(None);
         }
+        self.nested(|parser| parser.get_call_arguments_list(start))
+            .map(Some)
+    }
 
+    fn get_call_arguments_list(&mut self, start: usize) -> Result<ast::CallArguments<S>> {
         let mut positional = vec![];
         let mut named = vec![];
         let mut argument_names = vec![];
@@ -173,6 +231,7 @@ where
                 break;
             }
 
+            let expr_start = self.ptr;
             let expr = self.get_inline_expression(false)?;
 
             if let ast::InlineExpression::MessageReference {
@@ -190,7 +249,11 @@ where
                     }
                     self.ptr += 1;
                     self.skip_blank();
+                    // The span of the identifier is kept as that of the name,
+                    // but the reference is not a node of the AST.
+                    self.truncate_spans(self.spans_len().saturating_sub(1));
                     let val = self.get_inline_expression(true)?;
+                    self.record_span(expr_start..self.last_span_end());
 
                     argument_names.push(id.name.clone());
                     named.push(ast::NamedArgument {
@@ -218,7 +281,8 @@ where
         }
 
         self.expect_byte(b')')?;
+        self.record_span(start..self.ptr);
 
-        Ok(Some(ast::CallArguments { positional, named }))
+        Ok(ast::CallArguments { positional, named })
     }
 }
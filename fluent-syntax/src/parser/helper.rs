@@ -1,10 +1,26 @@
 use super::errors::{ErrorKind, ParserError};
 use super::{core::Parser, core::Result, slice::Slice};
+use alloc::borrow::ToOwned;
+use alloc::string::ToString;
 
 impl<'s, S> Parser<S>
 where
     S: Slice<'s>,
 {
+    /// Parses a nested expression with `f`, unless that exceeds
+    /// [`ParserOptions::max_depth`](super::ParserOptions::max_depth).
+    pub(super) fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if let Some(limit) = self.options.max_depth {
+            if self.depth >= limit {
+                return error!(ErrorKind::TooDeeplyNested { limit }, self.ptr);
+            }
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
     pub(super) fn is_current_byte(&self, b: u8) -> bool {
         get_current_byte!(self) == Some(&b)
     }
@@ -14,14 +30,43 @@ where
     }
 
     pub(super) fn skip_to_next_entry_start(&mut self) {
-        while let Some(b) = get_current_byte!(self) {
-            let new_line = self.ptr == 0 || get_byte!(self, self.ptr - 1) == Some(&b'\n');
+        let is_entry_start = |b: Option<&u8>| {
+            b.map_or(false, |b| {
+                b.is_ascii_alphabetic() || [b'-', b'#'].contains(b)
+            })
+        };
+
+        let new_line = self.ptr == 0 || get_byte!(self, self.ptr - 1) == Some(&b'\n');
+        if new_line && is_entry_start(get_current_byte!(self)) {
+            return;
+        }
 
-            if new_line && (b.is_ascii_alphabetic() || [b'-', b'#'].contains(b)) {
-                break;
+        while let Some(rest) = get_remaining_bytes!(self) {
+            match memchr::memchr(b'\n', rest) {
+                Some(pos) => {
+                    self.ptr += pos + 1;
+                    if is_entry_start(get_current_byte!(self)) {
+                        break;
+                    }
+                }
+                None => {
+                    self.ptr = self.length;
+                    break;
+                }
             }
+        }
+    }
 
-            self.ptr += 1;
+    /// Moves to the end of the current line, that is either the next line
+    /// ending or the end of the source.
+    pub(super) fn skip_to_eol(&mut self) {
+        let Some(rest) = get_remaining_bytes!(self) else {
+            return;
+        };
+        match memchr::memchr(b'\n', rest) {
+            Some(pos) if pos > 0 && rest[pos - 1] == b'\r' => self.ptr += pos - 1,
+            Some(pos) => self.ptr += pos,
+            None => self.ptr = self.length,
         }
     }
 
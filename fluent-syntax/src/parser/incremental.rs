@@ -0,0 +1,160 @@
+//! Reparsing of a resource after an edit of its source.
+//!
+//! Editors reparse the source on every change, and most changes only touch one
+//! entry. [`reparse`] takes the result of [`parse_with_spans`](super::parse_with_spans)
+//! for the source before an edit, and parses again only the entries which the
+//! edit may have changed. The entries after them are reused, with their spans
+//! moved by the change in length of the source.
+use super::{core::Parser, slice::Slice, spans, ParserError, ParserOptions, SpannedResource};
+use crate::ast;
+use ::core::ops::Range;
+use alloc::vec::Vec;
+
+/// Parses `source` like [`parse_with_spans`](super::parse_with_spans), reusing
+/// the entries of `previous` which the edit did not change.
+///
+/// `previous` and `errors` are the result of parsing `old_source`, and `edit`
+/// is the range of `old_source` which was replaced to produce `source`. The
+/// result and its errors are the same as if `source` was parsed from scratch.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+///
+/// let old = "hello = Hello\nbye = Bye\n";
+/// let spanned = parser::parse_with_spans(old).expect("Failed to parse an FTL resource.");
+///
+/// // Replace `Hello` with `Hello, { $user }`.
+/// let new = "hello = Hello, { $user }\nbye = Bye\n";
+/// let spanned = parser::reparse(spanned, vec![], old, 13..13, new)
+///     .expect("Failed to parse an FTL resource.");
+///
+/// assert_eq!(spanned.resource(), &parser::parse(new).unwrap());
+/// let bye = &spanned.resource().body[1];
+/// assert_eq!(spanned.span(bye), Some(25..34));
+/// ```
+pub fn reparse<'s, S>(
+    previous: SpannedResource<S>,
+    errors: Vec<ParserError>,
+    old_source: &str,
+    edit: Range<usize>,
+    source: S,
+) -> Result<SpannedResource<S>, (SpannedResource<S>, Vec<ParserError>)>
+where
+    S: Slice<'s>,
+{
+    reparse_with_options(
+        previous,
+        errors,
+        old_source,
+        edit,
+        source,
+        ParserOptions::default(),
+    )
+}
+
+/// Parses `source` like [`reparse`], with `options`.
+///
+/// `options` have to be the ones `previous` was parsed with.
+pub fn reparse_with_options<'s, S>(
+    previous: SpannedResource<S>,
+    errors: Vec<ParserError>,
+    old_source: &str,
+    edit: Range<usize>,
+    source: S,
+    options: ParserOptions,
+) -> Result<SpannedResource<S>, (SpannedResource<S>, Vec<ParserError>)>
+where
+    S: Slice<'s>,
+{
+    let old_len = old_source.len();
+    let new_len = source.as_ref().len();
+    debug_assert!(
+        edit.start <= edit.end && edit.end <= old_len,
+        "Invalid edit"
+    );
+    debug_assert_eq!(
+        old_source.get(..edit.start),
+        source.as_ref().get(..edit.start),
+        "Source changed before the edit"
+    );
+    debug_assert_eq!(
+        old_source.get(edit.end..),
+        source.as_ref().get(new_len + edit.end - old_len..),
+        "Source changed after the edit"
+    );
+    // Moves a position after the edit to the new source.
+    let shift = |pos: usize| pos + new_len - old_len;
+
+    let body = &previous.resource().body;
+    let starts: Vec<usize> = body
+        .iter()
+        .map(|entry| previous.span(entry).map_or(0, |span| span.start))
+        .collect();
+
+    // An entry is parsed up to the start of the next one, so it changes if the
+    // edit starts before that, and so does a standalone comment before it,
+    // which may now belong to it.
+    let mut first = starts[1.min(starts.len())..]
+        .iter()
+        .position(|&start| start >= edit.start)
+        .unwrap_or(starts.len().saturating_sub(1));
+    while first > 0 && matches!(body[first - 1], ast::Entry::Comment(_)) {
+        first -= 1;
+    }
+    let start = if first == 0 { 0 } else { starts[first] };
+
+    // Entries after the edit are reused once the parser reaches the start of
+    // one of them.
+    let reusable = starts.partition_point(|&start| start < edit.end);
+    let mut next = reusable;
+    let mut parser = Parser::with_options(source, options);
+    parser.spans = Some(Vec::new());
+    parser.ptr = start;
+    let (entries, new_errors) = parser.get_body_until(|ptr| {
+        while next < starts.len() && shift(starts[next]) < ptr {
+            next += 1;
+        }
+        next < starts.len() && shift(starts[next]) == ptr
+    });
+    let last = if parser.ptr < parser.length {
+        next
+    } else {
+        starts.len()
+    };
+    let end = starts.get(last).copied().unwrap_or(old_len);
+
+    let mut spans = previous.collect_spans(&body[..first], |pos| pos);
+    spans.extend(parser.spans.take().unwrap_or_default());
+    spans.extend(previous.collect_spans(&body[last..], shift));
+
+    let mut resource = previous.into_resource();
+    resource.body.splice(first..last, entries);
+    let resource = spans::build(resource, spans);
+
+    let position = |error: &ParserError| error.slice.as_ref().unwrap_or(&error.pos).start;
+    let mut errors: Vec<ParserError> = errors
+        .into_iter()
+        .filter(|error| !(start..end).contains(&position(error)))
+        .collect();
+    let after = errors.partition_point(|error| position(error) < start);
+    let shifted: Vec<ParserError> = errors
+        .drain(after..)
+        .map(|mut error| {
+            error.pos = shift(error.pos.start)..shift(error.pos.end);
+            error.slice = error
+                .slice
+                .map(|slice| shift(slice.start)..shift(slice.end));
+            error
+        })
+        .collect();
+    errors.extend(new_errors);
+    errors.extend(shifted);
+
+    if errors.is_empty() {
+        Ok(resource)
+    } else {
+        Err((resource, errors))
+    }
+}
@@ -0,0 +1,245 @@
+//! Lookup of the nodes at a position of the source, for language servers.
+use super::{SpannedNode, SpannedResource};
+use crate::ast;
+use alloc::vec::Vec;
+
+/// A reference to a node of the AST, as returned by
+/// [`SpannedResource::path_at`].
+#[derive(Debug, PartialEq)]
+pub enum Node<'a, S> {
+    Entry(&'a ast::Entry<S>),
+    Comment(&'a ast::Comment<S>),
+    Attribute(&'a ast::Attribute<S>),
+    Identifier(&'a ast::Identifier<S>),
+    Pattern(&'a ast::Pattern<S>),
+    PatternElement(&'a ast::PatternElement<S>),
+    Expression(&'a ast::Expression<S>),
+    InlineExpression(&'a ast::InlineExpression<S>),
+    Variant(&'a ast::Variant<S>),
+    VariantKey(&'a ast::VariantKey<S>),
+    CallArguments(&'a ast::CallArguments<S>),
+    NamedArgument(&'a ast::NamedArgument<S>),
+}
+
+impl<S> Clone for Node<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Node<'_, S> {}
+
+impl<S> SpannedResource<S> {
+    /// Returns the nodes whose span contains the byte `offset` of the source,
+    /// from the entry down to the innermost node.
+    ///
+    /// Messages and terms are returned as their [`Node::Entry`]. A node
+    /// contains the offset at its end, so that a cursor right after an
+    /// identifier still finds it; of two nodes which touch, the one starting
+    /// at the offset is returned. The path is empty if the offset is outside
+    /// of all the entries, such as on a blank line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_syntax::ast;
+    /// use fluent_syntax::parser::{self, Node};
+    ///
+    /// let ftl = "hello = Hello, { -brand }!\n";
+    /// let spanned = parser::parse_with_spans(ftl).expect("Failed to parse an FTL resource.");
+    ///
+    /// let path = spanned.path_at(20);
+    /// assert!(matches!(path[0], Node::Entry(ast::Entry::Message(_))));
+    /// assert!(matches!(path[1], Node::Pattern(_)));
+    /// assert!(matches!(path[2], Node::PatternElement(ast::PatternElement::Placeable { .. })));
+    /// assert!(matches!(path[3], Node::Expression(ast::Expression::Inline(_))));
+    /// assert!(matches!(
+    ///     path[4],
+    ///     Node::InlineExpression(ast::InlineExpression::TermReference { .. })
+    /// ));
+    /// assert_eq!(path[5], Node::Identifier(&ast::Identifier { name: "brand" }));
+    /// ```
+    pub fn path_at(&self, offset: usize) -> Vec<Node<'_, S>> {
+        let mut lookup = Lookup {
+            spanned: self,
+            offset,
+            path: Vec::new(),
+        };
+        if let Some(entry) = lookup.last(&self.resource().body) {
+            lookup.entry(entry);
+        }
+        lookup.path
+    }
+}
+
+struct Lookup<'a, S> {
+    spanned: &'a SpannedResource<S>,
+    offset: usize,
+    path: Vec<Node<'a, S>>,
+}
+
+impl<'a, S> Lookup<'a, S> {
+    fn contains<N: SpannedNode>(&self, node: &N) -> bool {
+        self.spanned.span(node).map_or(false, |span| {
+            span.start <= self.offset && self.offset <= span.end
+        })
+    }
+
+    /// Returns the last of `nodes` which contains the offset.
+    fn last<N: SpannedNode>(&self, nodes: &'a [N]) -> Option<&'a N> {
+        nodes.iter().rev().find(|node| self.contains(*node))
+    }
+
+    fn entry(&mut self, entry: &'a ast::Entry<S>) {
+        self.path.push(Node::Entry(entry));
+        match entry {
+            ast::Entry::Message(message) => {
+                if let Some(attribute) = self.last(&message.attributes) {
+                    self.attribute(attribute);
+                } else if let Some(value) = message.value.as_ref().filter(|v| self.contains(*v)) {
+                    self.pattern(value);
+                } else {
+                    self.identifier_or_comment(&message.id, message.comment.as_ref());
+                }
+            }
+            ast::Entry::Term(term) => {
+                if let Some(attribute) = self.last(&term.attributes) {
+                    self.attribute(attribute);
+                } else if self.contains(&term.value) {
+                    self.pattern(&term.value);
+                } else {
+                    self.identifier_or_comment(&term.id, term.comment.as_ref());
+                }
+            }
+            ast::Entry::Comment(_)
+            | ast::Entry::GroupComment(_)
+            | ast::Entry::ResourceComment(_)
+            | ast::Entry::Junk { .. } => {}
+        }
+    }
+
+    fn identifier_or_comment(
+        &mut self,
+        id: &'a ast::Identifier<S>,
+        comment: Option<&'a ast::Comment<S>>,
+    ) {
+        if self.contains(id) {
+            self.path.push(Node::Identifier(id));
+        } else if let Some(comment) = comment.filter(|comment| self.contains(*comment)) {
+            self.path.push(Node::Comment(comment));
+        }
+    }
+
+    fn attribute(&mut self, attribute: &'a ast::Attribute<S>) {
+        self.path.push(Node::Attribute(attribute));
+        if self.contains(&attribute.value) {
+            self.pattern(&attribute.value);
+        } else if self.contains(&attribute.id) {
+            self.path.push(Node::Identifier(&attribute.id));
+        }
+    }
+
+    fn pattern(&mut self, pattern: &'a ast::Pattern<S>) {
+        self.path.push(Node::Pattern(pattern));
+        if let Some(element) = self.last(&pattern.elements) {
+            self.path.push(Node::PatternElement(element));
+            if let ast::PatternElement::Placeable { expression } = element {
+                if self.contains(expression) {
+                    self.expression(expression);
+                }
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &'a ast::Expression<S>) {
+        self.path.push(Node::Expression(expression));
+        match expression {
+            ast::Expression::Select { selector, variants } => {
+                if let Some(variant) = self.last(variants) {
+                    self.path.push(Node::Variant(variant));
+                    if self.contains(&variant.value) {
+                        self.pattern(&variant.value);
+                    } else if self.contains(&variant.key) {
+                        self.path.push(Node::VariantKey(&variant.key));
+                    }
+                } else if self.contains(selector) {
+                    self.inline_expression(selector);
+                }
+            }
+            ast::Expression::Inline(expression) => {
+                if self.contains(expression) {
+                    self.inline_expression(expression);
+                }
+            }
+        }
+    }
+
+    fn inline_expression(&mut self, expression: &'a ast::InlineExpression<S>) {
+        self.path.push(Node::InlineExpression(expression));
+        match expression {
+            ast::InlineExpression::StringLiteral { .. }
+            | ast::InlineExpression::NumberLiteral { .. } => {}
+            #[cfg(feature = "unknown-expressions")]
+            ast::InlineExpression::Unknown { .. } => {}
+            ast::InlineExpression::FunctionReference { id, arguments } => {
+                if self.contains(arguments) {
+                    self.call_arguments(arguments);
+                } else {
+                    self.identifier(id);
+                }
+            }
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                match attribute
+                    .as_ref()
+                    .filter(|attribute| self.contains(*attribute))
+                {
+                    Some(attribute) => self.identifier(attribute),
+                    None => self.identifier(id),
+                }
+            }
+            ast::InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                if let Some(arguments) = arguments.as_ref().filter(|a| self.contains(*a)) {
+                    self.call_arguments(arguments);
+                } else {
+                    match attribute
+                        .as_ref()
+                        .filter(|attribute| self.contains(*attribute))
+                    {
+                        Some(attribute) => self.identifier(attribute),
+                        None => self.identifier(id),
+                    }
+                }
+            }
+            ast::InlineExpression::VariableReference { id } => self.identifier(id),
+            ast::InlineExpression::Placeable { expression } => {
+                if self.contains(&**expression) {
+                    self.expression(expression);
+                }
+            }
+        }
+    }
+
+    fn identifier(&mut self, id: &'a ast::Identifier<S>) {
+        if self.contains(id) {
+            self.path.push(Node::Identifier(id));
+        }
+    }
+
+    fn call_arguments(&mut self, arguments: &'a ast::CallArguments<S>) {
+        self.path.push(Node::CallArguments(arguments));
+        if let Some(argument) = self.last(&arguments.named) {
+            self.path.push(Node::NamedArgument(argument));
+            if self.contains(&argument.value) {
+                self.inline_expression(&argument.value);
+            } else {
+                self.identifier(&argument.name);
+            }
+        } else if let Some(argument) = self.last(&arguments.positional) {
+            self.inline_expression(argument);
+        }
+    }
+}
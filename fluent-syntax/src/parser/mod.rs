@@ -4,6 +4,18 @@
 //! * [`parse`] - parses an input into a complete Abstract Syntax Tree representation with all source information preserved.
 //! * [`parse_runtime`] - parses an input into a runtime optimized Abstract Syntax Tree
 //!   representation with comments stripped.
+//! * [`parse_with_spans`] - parses an input like [`parse`], and also records the
+//!   position of every node in the input.
+//! * [`parse_streaming`] - parses an input like [`parse`], one entry at a time.
+//!
+//! [`parse_with_options`] and [`parse_with_spans_and_options`] take
+//! [`ParserOptions`] in addition.
+//!
+//! With the `rayon` feature, `parse_all` parses many inputs like [`parse`]
+//! in parallel.
+//!
+//! [`reparse`] parses an input again after an edit, reusing the entries of the
+//! result of [`parse_with_spans`] which the edit did not change.
 //!
 //! # Example
 //!
@@ -65,22 +77,40 @@
 //! error scenario a vector of [`ParserError`] elements is returned as well.
 //!
 //! Any unparsed parts of the input are returned as [`ast::Entry::Junk`] elements.
+//! With the `recover-placeables` feature and `ParserOptions::recover_placeables`,
+//! an invalid placeable is returned as an `ast::PatternElement::Junk` element of
+//! its pattern instead, and the rest of the entry is kept.
 #[macro_use]
 mod errors;
 #[macro_use]
 mod macros;
 mod comment;
 mod core;
+mod diagnostic;
 mod expression;
 mod helper;
+mod incremental;
+mod lookup;
+mod options;
 mod pattern;
 mod runtime;
 mod slice;
+mod source_map;
+mod spans;
+mod streaming;
 
 use crate::ast;
+use alloc::vec::Vec;
+pub use diagnostic::{Diagnostic, Label};
 pub use errors::{ErrorKind, ParserError};
+pub use incremental::{reparse, reparse_with_options};
+pub use lookup::Node;
+pub use options::ParserOptions;
 pub(crate) use slice::matches_fluent_ws;
 pub use slice::Slice;
+pub use source_map::{DisplayParserError, SourceMap};
+pub use spans::{SpannedNode, SpannedResource};
+pub use streaming::{parse_streaming, Entries, EntryResult};
 
 /// Parser result always returns an AST representation of the input,
 /// and if parsing errors were encountered, a list of [`ParserError`] elements
@@ -162,7 +192,7 @@ pub use slice::Slice;
 ///     ),
 /// );
 /// ```
-pub type Result<S> = std::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)>;
+pub type Result<S> = ::core::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)>;
 
 /// Parses an input into a complete Abstract Syntax Tree representation with
 /// all source information preserved.
@@ -277,3 +307,156 @@ where
 {
     core::Parser::new(input).parse_runtime()
 }
+
+/// Parses an input into a complete Abstract Syntax Tree representation, and
+/// records the byte range of every node in the input.
+///
+/// Recording spans makes parsing slower, so this is intended for tools, such as
+/// linters and editors, which report positions.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+/// use fluent_syntax::ast;
+///
+/// let ftl = r#"
+/// ## This is a message comment
+/// hello-world = Hello { $user }!
+///     .title = Welcome
+/// "#;
+///
+/// let spanned = parser::parse_with_spans(ftl)
+///     .expect("Failed to parse an FTL resource.");
+///
+/// let message = match &spanned.resource().body[0] {
+///     ast::Entry::Message(message) => message,
+///     _ => panic!("Expected a message."),
+/// };
+/// let span = spanned.span(message).expect("Message has a span.");
+/// assert_eq!(&ftl[span], ftl.trim());
+///
+/// let attribute = &message.attributes[0];
+/// let span = spanned.span(attribute).expect("Attribute has a span.");
+/// assert_eq!(&ftl[span], ".title = Welcome");
+/// ```
+pub fn parse_with_spans<'s, S>(
+    input: S,
+) -> ::core::result::Result<SpannedResource<S>, (SpannedResource<S>, Vec<ParserError>)>
+where
+    S: Slice<'s>,
+{
+    core::Parser::new(input).parse_with_spans()
+}
+
+/// Parses an input like [`parse`], with `options`.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::ast;
+/// use fluent_syntax::parser::{self, ParserOptions};
+///
+/// let ftl = "hi = Hi!\nlong = A message longer than the limit\n";
+/// let options = ParserOptions {
+///     max_entry_length: Some(16),
+///     ..ParserOptions::default()
+/// };
+///
+/// let (resource, errors) = parser::parse_with_options(ftl, options)
+///     .expect_err("Resource should contain errors.");
+///
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(
+///     errors[0].kind,
+///     parser::ErrorKind::EntryTooLong { limit: 16 }
+/// );
+/// assert!(matches!(resource.body[0], ast::Entry::Message(_)));
+/// assert!(matches!(resource.body[1], ast::Entry::Junk { .. }));
+/// ```
+pub fn parse_with_options<'s, S>(input: S, options: ParserOptions) -> Result<S>
+where
+    S: Slice<'s>,
+{
+    core::Parser::with_options(input, options).parse()
+}
+
+/// Parses an input like [`parse_with_spans`], with `options`.
+pub fn parse_with_spans_and_options<'s, S>(
+    input: S,
+    options: ParserOptions,
+) -> ::core::result::Result<SpannedResource<S>, (SpannedResource<S>, Vec<ParserError>)>
+where
+    S: Slice<'s>,
+{
+    core::Parser::with_options(input, options).parse_with_spans()
+}
+
+/// Parses an input like [`parse_runtime`], with `options`.
+pub fn parse_runtime_with_options<'s, S>(input: S, options: ParserOptions) -> Result<S>
+where
+    S: Slice<'s>,
+{
+    core::Parser::with_options(input, options).parse_runtime()
+}
+
+/// Parses an input as the value of a single message, without an identifier.
+///
+/// The whole input is the pattern: its text is kept as is, including blank and
+/// indented lines, and lines starting with `.`, `[` or `*`. Placeables are
+/// parsed as in messages, and the positions of errors are offsets into `input`.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::ast;
+/// use fluent_syntax::parser::{self, ErrorKind};
+///
+/// let pattern = parser::parse_pattern(" Hello, { $user }!\n.title = Welcome ")
+///     .expect("Failed to parse a pattern.");
+///
+/// assert_eq!(pattern.elements.len(), 3);
+/// assert_eq!(
+///     pattern.elements[2],
+///     ast::PatternElement::TextElement {
+///         value: "!\n.title = Welcome "
+///     }
+/// );
+///
+/// let err = parser::parse_pattern("Hello }").expect_err("Pattern should be invalid.");
+/// assert_eq!(err.kind, ErrorKind::UnbalancedClosingBrace);
+/// assert_eq!(err.pos, 6..7);
+/// ```
+pub fn parse_pattern<'s, S>(input: S) -> ::core::result::Result<ast::Pattern<S>, ParserError>
+where
+    S: Slice<'s>,
+{
+    core::Parser::new(input).parse_pattern()
+}
+
+/// Parses many inputs at once on the rayon thread pool, returning the result
+/// of [`parse`] for each input, in order.
+///
+/// The parser keeps no state between inputs, so applications which load many
+/// resources at startup can parse them on all cores.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+///
+/// let sources = vec!["hello = Hello!", "goodbye = { $"];
+/// let resources = parser::parse_all(sources);
+///
+/// assert!(resources[0].is_ok());
+/// assert!(resources[1].is_err());
+/// ```
+#[cfg(feature = "rayon")]
+pub fn parse_all<'s, S>(inputs: Vec<S>) -> Vec<Result<S>>
+where
+    S: Slice<'s> + Send,
+{
+    use rayon::prelude::*;
+
+    inputs.into_par_iter().map(parse).collect()
+}
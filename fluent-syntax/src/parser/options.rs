@@ -0,0 +1,57 @@
+/// Options for parsing a resource, see [`parse_with_options`](super::parse_with_options).
+///
+/// The limits make the parser report an error, rather than exhaust the stack
+/// or the memory on malicious input. The entry which exceeds a limit is turned
+/// into [`Entry::Junk`](crate::ast::Entry::Junk) like any invalid entry.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser::{self, ErrorKind, ParserOptions};
+///
+/// let options = ParserOptions {
+///     max_depth: Some(2),
+///     ..ParserOptions::default()
+/// };
+///
+/// let ftl = "key = { { { $x } } }\n";
+/// let (_, errors) = parser::parse_with_options(ftl, options)
+///     .expect_err("Resource should contain errors.");
+/// assert_eq!(errors[0].kind, ErrorKind::TooDeeplyNested { limit: 2 });
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ParserOptions {
+    /// Whether an invalid placeable is kept in its pattern as
+    /// [`PatternElement::Junk`](crate::ast::PatternElement::Junk), rather than
+    /// turning the whole entry into [`Entry::Junk`](crate::ast::Entry::Junk).
+    /// `false` by default.
+    ///
+    /// The error is reported either way. This keeps the rest of the message
+    /// usable while it is being edited.
+    #[cfg(feature = "recover-placeables")]
+    pub recover_placeables: bool,
+    /// Whether the content of an invalid placeable is kept as
+    /// [`InlineExpression::Unknown`](crate::ast::InlineExpression::Unknown),
+    /// rather than failing the placeable. `false` by default.
+    ///
+    /// Unlike `recover_placeables`, which keeps the outermost placeable as
+    /// text, this applies to the innermost invalid placeable, so resources
+    /// using syntax of a later version of Fluent keep the expressions around
+    /// it. Empty and unclosed placeables are not
+    /// unknown expressions. The error is still reported.
+    #[cfg(feature = "unknown-expressions")]
+    pub unknown_expressions: bool,
+    /// The maximum number of placeables and call arguments nested in each
+    /// other, such as `{ $n -> *[other] { FUN($x) } }` which nests three.
+    /// Unlimited by default.
+    ///
+    /// The parser recurses into nested expressions, so this bounds its use of
+    /// the stack on untrusted input.
+    pub max_depth: Option<usize>,
+    /// The maximum length of a message, a term or a comment, in bytes.
+    /// Unlimited by default.
+    pub max_entry_length: Option<usize>,
+    /// The maximum number of variants of a select expression. Unlimited by
+    /// default.
+    pub max_variants: Option<usize>,
+}
@@ -1,6 +1,7 @@
 use super::errors::{ErrorKind, ParserError};
 use super::{core::Parser, core::Result, slice::Slice};
 use crate::ast;
+use alloc::vec;
 
 #[derive(Debug, PartialEq)]
 enum TextElementTermination {
@@ -24,7 +25,11 @@ enum TextElementPosition {
 // cheaper since they'll happen on the pointers, rather than extracted slices.
 #[derive(Debug)]
 enum PatternElementPlaceholders<S> {
-    Placeable(ast::Expression<S>),
+    // (expression, start, end)
+    Placeable(ast::Expression<S>, usize, usize),
+    // (start, end)
+    #[cfg(feature = "recover-placeables")]
+    Junk(usize, usize),
     // (start, end, indent, position)
     TextElement(usize, usize, usize, TextElementPosition),
 }
@@ -57,13 +62,32 @@ where
         };
 
         while self.ptr < self.length {
+            let start = self.ptr;
             if self.take_byte_if(b'{') {
                 if text_element_role == TextElementPosition::LineStart {
                     common_indent = Some(0);
                 }
-                let exp = self.get_placeable()?;
-                last_non_blank = Some(elements.len());
-                elements.push(PatternElementPlaceholders::Placeable(exp));
+                #[cfg(feature = "recover-placeables")]
+                let (spans_len, recovered_len) = (self.spans_len(), self.recovered.len());
+                match self.get_placeable() {
+                    Ok(exp) => {
+                        self.record_span(start..self.ptr);
+                        last_non_blank = Some(elements.len());
+                        elements.push(PatternElementPlaceholders::Placeable(exp, start, self.ptr));
+                    }
+                    #[cfg(feature = "recover-placeables")]
+                    Err(mut err) if self.options.recover_placeables => {
+                        self.skip_placeable(start);
+                        self.truncate_spans(spans_len);
+                        self.recovered.truncate(recovered_len);
+                        self.record_span(start..self.ptr);
+                        err.slice = Some(start..self.ptr);
+                        self.recovered.push(err);
+                        last_non_blank = Some(elements.len());
+                        elements.push(PatternElementPlaceholders::Junk(start, self.ptr));
+                    }
+                    Err(err) => return Err(err),
+                }
                 text_element_role = TextElementPosition::Continuation;
             } else {
                 let slice_start = self.ptr;
@@ -122,19 +146,34 @@ where
         }
 
         if let Some(last_non_blank) = last_non_blank {
+            // The spans of text elements, which are recorded after those of
+            // placeables, and the span of the pattern.
+            let mut text_spans = self.spans.as_ref().map(|_| vec![]);
+            let mut span_start = None;
+            let mut span_end = 0;
             let elements = elements
                 .into_iter()
                 .take(last_non_blank + 1)
                 .enumerate()
                 .map(|(i, elem)| match elem {
-                    PatternElementPlaceholders::Placeable(expression) => {
+                    PatternElementPlaceholders::Placeable(expression, start, end) => {
+                        span_start.get_or_insert(start);
+                        span_end = end;
                         ast::PatternElement::Placeable { expression }
                     }
+                    #[cfg(feature = "recover-placeables")]
+                    PatternElementPlaceholders::Junk(start, end) => {
+                        span_start.get_or_insert(start);
+                        span_end = end;
+                        ast::PatternElement::Junk {
+                            content: self.source.slice(start..end),
+                        }
+                    }
                     PatternElementPlaceholders::TextElement(start, end, indent, role) => {
                         let start = if role == TextElementPosition::LineStart {
                             common_indent.map_or_else(
                                 || start + indent,
-                                |common_indent| start + std::cmp::min(indent, common_indent),
+                                |common_indent| start + ::core::cmp::min(indent, common_indent),
                             )
                         } else {
                             start
@@ -143,16 +182,98 @@ where
                         if last_non_blank == i {
                             value.trim();
                         }
+                        let end = start + value.as_ref().len();
+                        if let Some(text_spans) = &mut text_spans {
+                            text_spans.push(start..end);
+                        }
+                        span_start.get_or_insert(start);
+                        span_end = end;
                         ast::PatternElement::TextElement { value }
                     }
                 })
                 .collect();
+            for text_span in text_spans.into_iter().flatten() {
+                self.record_span(text_span);
+            }
+            self.record_span(span_start.unwrap_or_default()..span_end);
             return Ok(Some(ast::Pattern { elements }));
         }
 
         Ok(None)
     }
 
+    /// Parses the whole input as the text and placeables of a single pattern,
+    /// with no indentation or line starts treated specially.
+    pub(super) fn parse_pattern(mut self) -> Result<ast::Pattern<S>> {
+        let length = self.length;
+        self.get_raw_pattern().map_err(|mut err| {
+            err.slice = Some(0..length);
+            err
+        })
+    }
+
+    fn get_raw_pattern(&mut self) -> Result<ast::Pattern<S>> {
+        let mut elements = vec![];
+        while self.ptr < self.length {
+            if self.take_byte_if(b'{') {
+                let expression = self.get_placeable()?;
+                elements.push(ast::PatternElement::Placeable { expression });
+                continue;
+            }
+            let start = self.ptr;
+            let rest = &self.source.as_ref().as_bytes()[start..];
+            self.ptr += memchr::memchr2(b'{', b'}', rest).unwrap_or(rest.len());
+            if self.is_current_byte(b'}') {
+                return error!(ErrorKind::UnbalancedClosingBrace, self.ptr);
+            }
+            elements.push(ast::PatternElement::TextElement {
+                value: self.source.slice(start..self.ptr),
+            });
+        }
+        Ok(ast::Pattern { elements })
+    }
+
+    /// Moves past the invalid placeable which starts at `start`: to its
+    /// closing brace, or to the end of the line before the next entry if it
+    /// is not closed. Returns whether it is closed.
+    #[cfg(any(feature = "recover-placeables", feature = "unknown-expressions"))]
+    pub(super) fn skip_placeable(&mut self, start: usize) -> bool {
+        let bytes = self.source.as_ref().as_bytes();
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut ptr = start;
+        while let Some(&b) = bytes.get(ptr) {
+            match b {
+                b'\n' => {
+                    in_string = false;
+                    if bytes
+                        .get(ptr + 1)
+                        .map_or(true, |b| !matches!(b, b' ' | b'\r' | b'\n'))
+                    {
+                        if ptr > start && bytes[ptr - 1] == b'\r' {
+                            ptr -= 1;
+                        }
+                        break;
+                    }
+                }
+                b'\\' if in_string => ptr += 1,
+                b'"' => in_string = !in_string,
+                b'{' if !in_string => depth += 1,
+                b'}' if !in_string => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.ptr = ptr + 1;
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+            ptr += 1;
+        }
+        self.ptr = ptr.min(self.length);
+        false
+    }
+
     fn get_text_slice(
         &mut self,
     ) -> Result<(usize, usize, TextElementType, TextElementTermination)> {
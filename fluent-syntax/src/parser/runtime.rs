@@ -1,9 +1,10 @@
 use super::{
     core::{Parser, Result},
-    errors::ParserError,
+    errors::{ErrorKind, ParserError},
     slice::Slice,
 };
 use crate::ast;
+use alloc::{vec, vec::Vec};
 
 impl<'s, S> Parser<S>
 where
@@ -11,7 +12,7 @@ where
 {
     pub fn parse_runtime(
         mut self,
-    ) -> std::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)> {
+    ) -> ::core::result::Result<ast::Resource<S>, (ast::Resource<S>, Vec<ParserError>)> {
         let mut errors = vec![];
 
         // That default allocation gives the lowest
@@ -22,7 +23,17 @@ where
 
         while self.ptr < self.length {
             let entry_start = self.ptr;
-            let entry = self.get_entry_runtime(entry_start);
+            let mut entry = self.get_entry_runtime(entry_start);
+            if let Some(limit) = self.options.max_entry_length {
+                if entry.is_ok() && self.ptr - entry_start > limit {
+                    entry = error!(ErrorKind::EntryTooLong { limit }, entry_start + limit);
+                }
+            }
+            if entry.is_ok() {
+                errors.append(&mut self.recovered);
+            } else {
+                self.recovered.clear();
+            }
 
             match entry {
                 Ok(Some(entry)) => {
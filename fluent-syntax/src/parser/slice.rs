@@ -1,4 +1,5 @@
-use std::ops::Range;
+use ::core::ops::Range;
+use alloc::string::{String, ToString};
 
 pub(crate) fn matches_fluent_ws(c: char) -> bool {
     c == ' ' || c == '\r' || c == '\n'
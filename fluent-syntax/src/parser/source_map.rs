@@ -0,0 +1,129 @@
+use super::errors::ParserError;
+use ::core::fmt;
+use alloc::vec::Vec;
+
+/// Converts the byte offsets reported by the parser, such as
+/// [`ParserError::pos`], to lines and columns.
+///
+/// The map is built once from the source, after which each lookup is a
+/// binary search over its lines.
+///
+/// Lines and columns start at 1, as in most error reports. Columns count
+/// characters rather than bytes, and lines end with `\n`, so the `\r` of a
+/// CRLF line ending is the last column of its line.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser::{self, SourceMap};
+///
+/// let ftl = "key1 = Value 1\ng@Rb@ge = #2y ds\n";
+///
+/// let (_, errors) = parser::parse(ftl)
+///     .expect_err("Resource should contain errors.");
+///
+/// let source_map = SourceMap::new(ftl);
+/// assert_eq!(source_map.line_col(errors[0].pos.start), (2, 2));
+/// assert_eq!(
+///     errors[0].display_in(&source_map).to_string(),
+///     "2:2: Expected a token starting with \"=\""
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct SourceMap<'s> {
+    source: &'s str,
+    /// The offset of the start of each line.
+    line_starts: Vec<usize>,
+}
+
+impl<'s> SourceMap<'s> {
+    /// Builds the map of the lines of `source`.
+    pub fn new(source: &'s str) -> Self {
+        let line_starts = ::core::iter::once(0)
+            .chain(memchr::memchr_iter(b'\n', source.as_bytes()).map(|pos| pos + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Returns the number of lines of the source, which is one more than its
+    /// number of line endings.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the line and the column of the character at `offset`.
+    ///
+    /// Offsets past the end of the source are those of its end, and offsets
+    /// within a character are those of the character.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let bytes = self.source.as_bytes();
+        let mut column = bytes[self.line_starts[line]..offset]
+            .iter()
+            .filter(|b| !is_continuation_byte(**b))
+            .count();
+        // The character containing `offset` started before it.
+        if bytes
+            .get(offset)
+            .map_or(false, |b| is_continuation_byte(*b))
+        {
+            column -= 1;
+        }
+        (line + 1, column + 1)
+    }
+
+    /// Returns the byte offset of the character at `line` and `column`, the
+    /// inverse of [`SourceMap::line_col`].
+    ///
+    /// Returns `None` if the source has no such line, or if the line has less
+    /// than `column - 1` characters. The column after the last character of a
+    /// line is its end.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let column = column.checked_sub(1)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.source.len(), |next| next - 1);
+        let line = &self.source[start..end];
+        line.char_indices()
+            .map(|(pos, _)| pos)
+            .chain(Some(line.len()))
+            .nth(column)
+            .map(|pos| start + pos)
+    }
+}
+
+fn is_continuation_byte(b: u8) -> bool {
+    b & 0b1100_0000 == 0b1000_0000
+}
+
+impl ParserError {
+    /// Returns a value displaying the error prefixed with the line and the
+    /// column at which it starts, as in `2:14: Expected a token`.
+    pub fn display_in<'a>(&'a self, source_map: &'a SourceMap<'_>) -> DisplayParserError<'a> {
+        DisplayParserError {
+            error: self,
+            line_col: source_map.line_col(self.pos.start),
+        }
+    }
+}
+
+/// Displays a [`ParserError`] along with its position, see
+/// [`ParserError::display_in`].
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayParserError<'a> {
+    error: &'a ParserError,
+    line_col: (usize, usize),
+}
+
+impl fmt::Display for DisplayParserError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (line, column) = self.line_col;
+        write!(f, "{}:{}: {}", line, column, self.error)
+    }
+}
@@ -0,0 +1,408 @@
+//! Source spans of the nodes of a parsed resource.
+//!
+//! The AST does not store positions, so that resources parsed for runtime use
+//! stay small. Instead, [`parse_with_spans`](super::parse_with_spans) records
+//! the byte range of every node while parsing, and returns it alongside the
+//! resource in a [`SpannedResource`].
+use super::{core::Parser, slice::Slice};
+use crate::ast;
+use ::core::ops::Range;
+use alloc::collections::BTreeMap;
+use alloc::vec::{self, Vec};
+
+/// A resource along with the byte ranges of its nodes in the source.
+///
+/// The spans of nodes are looked up by their address, so the resource can only
+/// be borrowed immutably. Use [`SpannedResource::into_resource`] to take
+/// ownership of it, which drops the spans.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::ast;
+/// use fluent_syntax::parser;
+///
+/// let ftl = "hello = Hello, { $user }!\n";
+///
+/// let spanned = parser::parse_with_spans(ftl)
+///     .expect("Failed to parse an FTL resource.");
+///
+/// let message = match &spanned.resource().body[0] {
+///     ast::Entry::Message(message) => message,
+///     _ => panic!("Expected a message."),
+/// };
+/// assert_eq!(spanned.span(message), Some(0..25));
+/// assert_eq!(spanned.span(&message.id), Some(0..5));
+///
+/// let pattern = message.value.as_ref().expect("Message has a value.");
+/// assert_eq!(spanned.span(&pattern.elements[1]), Some(15..24));
+/// ```
+#[derive(Debug)]
+pub struct SpannedResource<S> {
+    resource: ast::Resource<S>,
+    spans: BTreeMap<(usize, NodeKind), Range<usize>>,
+}
+
+impl<S> SpannedResource<S> {
+    /// Returns the parsed resource.
+    pub fn resource(&self) -> &ast::Resource<S> {
+        &self.resource
+    }
+
+    /// Returns the byte range of `node` in the source, or `None` if `node` is
+    /// not part of [`SpannedResource::resource`].
+    ///
+    /// The spans of messages, terms and comments exclude the line ending which
+    /// terminates them, and the span of a message or a term starts at its
+    /// comment, if it has one. The span of junk is the range of its content.
+    pub fn span<N: SpannedNode>(&self, node: &N) -> Option<Range<usize>> {
+        self.spans.get(&key(node)).cloned()
+    }
+
+    /// Returns the parsed resource, dropping the spans.
+    pub fn into_resource(self) -> ast::Resource<S> {
+        self.resource
+    }
+}
+
+/// A node of the AST whose span is recorded by
+/// [`parse_with_spans`](super::parse_with_spans).
+///
+/// This trait is sealed, and implemented by every node type but
+/// [`ast::Resource`].
+pub trait SpannedNode: private::Sealed {}
+
+mod private {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum NodeKind {
+        Entry,
+        Message,
+        Term,
+        Comment,
+        Attribute,
+        Identifier,
+        Pattern,
+        PatternElement,
+        Expression,
+        InlineExpression,
+        Variant,
+        VariantKey,
+        CallArguments,
+        NamedArgument,
+    }
+
+    pub trait Sealed {
+        const KIND: NodeKind;
+    }
+}
+
+use private::NodeKind;
+
+macro_rules! spanned_nodes {
+    ($($node:ident,)*) => {
+        $(
+            impl<S> private::Sealed for ast::$node<S> {
+                const KIND: NodeKind = NodeKind::$node;
+            }
+
+            impl<S> SpannedNode for ast::$node<S> {}
+        )*
+    };
+}
+
+spanned_nodes!(
+    Entry,
+    Message,
+    Term,
+    Comment,
+    Attribute,
+    Identifier,
+    Pattern,
+    PatternElement,
+    Expression,
+    InlineExpression,
+    Variant,
+    VariantKey,
+    CallArguments,
+    NamedArgument,
+);
+
+/// Nodes are keyed by their kind as well as their address, since a node and
+/// its first field share an address.
+fn key<N: SpannedNode>(node: &N) -> (usize, NodeKind) {
+    (node as *const N as usize, N::KIND)
+}
+
+impl<'s, S> Parser<S>
+where
+    S: Slice<'s>,
+{
+    /// Records the span of a node, if spans are enabled.
+    ///
+    /// Nodes are recorded as they are completed, so that their children come
+    /// first. Text elements are the exception: they are only created once the
+    /// whole pattern is parsed, and so are recorded after the placeables of
+    /// the pattern. [`SpanWalker`] visits the AST in the same order.
+    pub(super) fn record_span(&mut self, span: Range<usize>) {
+        if let Some(spans) = &mut self.spans {
+            spans.push(span);
+        }
+    }
+
+    /// Returns the end of the last recorded span, which is the end of the
+    /// last completed node if spans are enabled.
+    pub(super) fn last_span_end(&self) -> usize {
+        self.spans
+            .as_ref()
+            .and_then(|spans| spans.last())
+            .map_or(self.ptr, |span| span.end)
+    }
+
+    /// Returns the number of recorded spans, to be passed to
+    /// [`Parser::truncate_spans`] when the parser backtracks.
+    pub(super) fn spans_len(&self) -> usize {
+        self.spans.as_ref().map_or(0, Vec::len)
+    }
+
+    pub(super) fn truncate_spans(&mut self, len: usize) {
+        if let Some(spans) = &mut self.spans {
+            spans.truncate(len);
+        }
+    }
+
+    /// Replaces the recorded spans in `range` with `span`.
+    pub(super) fn splice_spans(&mut self, range: Range<usize>, span: Range<usize>) {
+        if let Some(spans) = &mut self.spans {
+            spans.splice(range, Some(span));
+        }
+    }
+
+    /// Moves the start of the last recorded span to `start`.
+    pub(super) fn extend_last_span(&mut self, start: usize) {
+        if let Some(span) = self.spans.as_mut().and_then(|spans| spans.last_mut()) {
+            span.start = start;
+        }
+    }
+}
+
+pub(super) fn build<S>(resource: ast::Resource<S>, spans: Vec<Range<usize>>) -> SpannedResource<S> {
+    let mut walker = SpanWalker(Assign {
+        spans: spans.into_iter(),
+        map: BTreeMap::new(),
+    });
+    for entry in &resource.body {
+        walker.entry(entry);
+    }
+    debug_assert!(walker.0.spans.next().is_none(), "Unused spans");
+    SpannedResource {
+        spans: walker.0.map,
+        resource,
+    }
+}
+
+impl<S> SpannedResource<S> {
+    /// Returns the spans of the nodes of `entries`, mapped with `f`, in the
+    /// order in which the parser records them, to be passed to [`build`].
+    pub(super) fn collect_spans(
+        &self,
+        entries: &[ast::Entry<S>],
+        f: impl Fn(usize) -> usize,
+    ) -> Vec<Range<usize>> {
+        let mut walker = SpanWalker(Collect {
+            map: &self.spans,
+            f,
+            spans: Vec::new(),
+        });
+        for entry in entries {
+            walker.entry(entry);
+        }
+        walker.0.spans
+    }
+}
+
+/// Where [`SpanWalker`] takes the spans of nodes from.
+trait Spans {
+    /// Returns the span of `node`, which is the next one in record order.
+    fn next<N: SpannedNode>(&mut self, node: &N) -> Range<usize>;
+
+    /// Sets the span of `node`, which shares the span of another node.
+    fn insert<N: SpannedNode>(&mut self, node: &N, span: Range<usize>);
+}
+
+/// Assigns the spans recorded by the parser to the nodes.
+struct Assign {
+    spans: vec::IntoIter<Range<usize>>,
+    map: BTreeMap<(usize, NodeKind), Range<usize>>,
+}
+
+impl Spans for Assign {
+    fn next<N: SpannedNode>(&mut self, node: &N) -> Range<usize> {
+        let span = self.spans.next();
+        debug_assert!(span.is_some(), "Missing spans");
+        let span = span.unwrap_or_default();
+        self.insert(node, span.clone());
+        span
+    }
+
+    fn insert<N: SpannedNode>(&mut self, node: &N, span: Range<usize>) {
+        self.map.insert(key(node), span);
+    }
+}
+
+/// Collects the spans already assigned to the nodes, in record order.
+struct Collect<'m, F> {
+    map: &'m BTreeMap<(usize, NodeKind), Range<usize>>,
+    f: F,
+    spans: Vec<Range<usize>>,
+}
+
+impl<F: Fn(usize) -> usize> Spans for Collect<'_, F> {
+    fn next<N: SpannedNode>(&mut self, node: &N) -> Range<usize> {
+        let span = self.map.get(&key(node));
+        debug_assert!(span.is_some(), "Missing spans");
+        let span = span.map_or(0..0, |span| (self.f)(span.start)..(self.f)(span.end));
+        self.spans.push(span.clone());
+        span
+    }
+
+    fn insert<N: SpannedNode>(&mut self, _node: &N, _span: Range<usize>) {}
+}
+
+/// Visits the nodes of the AST in the order in which the parser recorded
+/// their spans.
+struct SpanWalker<T>(T);
+
+impl<T: Spans> SpanWalker<T> {
+    fn next<N: SpannedNode>(&mut self, node: &N) -> Range<usize> {
+        self.0.next(node)
+    }
+
+    fn entry<S>(&mut self, entry: &ast::Entry<S>) {
+        let span = match entry {
+            ast::Entry::Message(message) => {
+                if let Some(comment) = &message.comment {
+                    self.next(comment);
+                }
+                self.next(&message.id);
+                if let Some(value) = &message.value {
+                    self.pattern(value);
+                }
+                self.attributes(&message.attributes);
+                self.next(message)
+            }
+            ast::Entry::Term(term) => {
+                if let Some(comment) = &term.comment {
+                    self.next(comment);
+                }
+                self.next(&term.id);
+                self.pattern(&term.value);
+                self.attributes(&term.attributes);
+                self.next(term)
+            }
+            ast::Entry::Comment(comment)
+            | ast::Entry::GroupComment(comment)
+            | ast::Entry::ResourceComment(comment) => self.next(comment),
+            ast::Entry::Junk { .. } => {
+                self.next(entry);
+                return;
+            }
+        };
+        self.0.insert(entry, span);
+    }
+
+    fn attributes<S>(&mut self, attributes: &[ast::Attribute<S>]) {
+        for attribute in attributes {
+            self.next(&attribute.id);
+            self.pattern(&attribute.value);
+            self.next(attribute);
+        }
+    }
+
+    fn pattern<S>(&mut self, pattern: &ast::Pattern<S>) {
+        for element in &pattern.elements {
+            match element {
+                ast::PatternElement::Placeable { expression } => {
+                    self.expression(expression);
+                    self.next(element);
+                }
+                #[cfg(feature = "recover-placeables")]
+                ast::PatternElement::Junk { .. } => {
+                    self.next(element);
+                }
+                ast::PatternElement::TextElement { .. } => {}
+            }
+        }
+        for element in &pattern.elements {
+            if let ast::PatternElement::TextElement { .. } = element {
+                self.next(element);
+            }
+        }
+        self.next(pattern);
+    }
+
+    fn expression<S>(&mut self, expression: &ast::Expression<S>) {
+        match expression {
+            ast::Expression::Select { selector, variants } => {
+                self.inline_expression(selector);
+                for variant in variants {
+                    self.next(&variant.key);
+                    self.pattern(&variant.value);
+                    self.next(variant);
+                }
+            }
+            ast::Expression::Inline(expression) => self.inline_expression(expression),
+        }
+        self.next(expression);
+    }
+
+    fn inline_expression<S>(&mut self, expression: &ast::InlineExpression<S>) {
+        match expression {
+            ast::InlineExpression::StringLiteral { .. }
+            | ast::InlineExpression::NumberLiteral { .. } => {}
+            #[cfg(feature = "unknown-expressions")]
+            ast::InlineExpression::Unknown { .. } => {}
+            ast::InlineExpression::FunctionReference { id, arguments } => {
+                self.next(id);
+                self.call_arguments(arguments);
+            }
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                self.next(id);
+                if let Some(attribute) = attribute {
+                    self.next(attribute);
+                }
+            }
+            ast::InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                self.next(id);
+                if let Some(attribute) = attribute {
+                    self.next(attribute);
+                }
+                if let Some(arguments) = arguments {
+                    self.call_arguments(arguments);
+                }
+            }
+            ast::InlineExpression::VariableReference { id } => {
+                self.next(id);
+            }
+            ast::InlineExpression::Placeable { expression } => {
+                self.expression(expression);
+            }
+        }
+        self.next(expression);
+    }
+
+    fn call_arguments<S>(&mut self, arguments: &ast::CallArguments<S>) {
+        for argument in &arguments.positional {
+            self.inline_expression(argument);
+        }
+        for argument in &arguments.named {
+            self.next(&argument.name);
+            self.inline_expression(&argument.value);
+            self.next(argument);
+        }
+        self.next(arguments);
+    }
+}
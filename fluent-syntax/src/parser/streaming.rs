@@ -0,0 +1,100 @@
+use super::{core::Parser, slice::Slice, ParserError};
+use crate::ast;
+use alloc::vec::Vec;
+
+/// An iterator over the entries of an input, returned by [`parse_streaming`].
+///
+/// Entries are parsed one at a time as the iterator advances, and are the
+/// same as the entries of [`parse`](super::parse). An entry comes with the
+/// errors encountered while parsing it, such as the error which turned it into
+/// [`ast::Entry::Junk`].
+pub struct Entries<S> {
+    parser: Parser<S>,
+    next: Option<EntryResult<S>>,
+}
+
+/// An entry, along with its errors if there are any.
+pub type EntryResult<S> = ::core::result::Result<ast::Entry<S>, (ast::Entry<S>, Vec<ParserError>)>;
+
+impl<'s, S> Iterator for Entries<S>
+where
+    S: Slice<'s>,
+{
+    type Item = EntryResult<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(next) = self.next.take() {
+            return Some(next);
+        }
+
+        let mut entries = Vec::with_capacity(2);
+        let mut errors = Vec::new();
+        while entries.is_empty() {
+            if self.parser.ptr >= self.parser.length {
+                let (comment, _) = self.parser.last_comment.take()?;
+                return Some(Ok(ast::Entry::Comment(comment)));
+            }
+            self.parser.get_next_entries(&mut entries, &mut errors);
+        }
+
+        // Errors belong to the last entry, since a pending comment is only
+        // completed by the entry after it.
+        let entry = entries.pop()?;
+        let entry = if errors.is_empty() {
+            Ok(entry)
+        } else {
+            Err((entry, errors))
+        };
+        match entries.pop() {
+            Some(comment) => {
+                self.next = Some(entry);
+                Some(Ok(comment))
+            }
+            None => Some(entry),
+        }
+    }
+}
+
+/// Parses an input into its entries one at a time, rather than into an
+/// [`ast::Resource`].
+///
+/// This is intended for large inputs which are processed entry by entry, such
+/// as when converting or indexing them, so that the whole resource is never
+/// held in memory.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::ast;
+/// use fluent_syntax::parser;
+///
+/// let ftl = r#"
+/// key1 = Value 1
+///
+/// g@Rb@ge = #2y ds
+///
+/// key2 = Value 2
+/// "#;
+///
+/// let ids: Vec<_> = parser::parse_streaming(ftl)
+///     .map(|entry| match entry {
+///         Ok(ast::Entry::Message(message)) => Ok(message.id.name),
+///         Ok(_) => unreachable!(),
+///         Err((ast::Entry::Junk { content }, errors)) => Err((content, errors.len())),
+///         Err(_) => unreachable!(),
+///     })
+///     .collect();
+///
+/// assert_eq!(
+///     ids,
+///     vec![Ok("key1"), Err(("g@Rb@ge = #2y ds\n\n", 1)), Ok("key2")]
+/// );
+/// ```
+pub fn parse_streaming<'s, S>(input: S) -> Entries<S>
+where
+    S: Slice<'s>,
+{
+    let mut parser = Parser::new(input);
+    parser.skip_blank_block();
+    Entries { parser, next: None }
+}
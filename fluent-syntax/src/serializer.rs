@@ -22,7 +22,9 @@
 //! ```
 
 use crate::{ast::*, parser::matches_fluent_ws, parser::Slice};
-use std::fmt::Write;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
 
 /// Serializes an abstract syntax tree representing a Fluent Translation List into a
 /// String.
@@ -49,14 +51,35 @@ use std::fmt::Write;
 /// assert_eq!(expected, serialized);
 /// ```
 pub fn serialize<'s, S: Slice<'s>>(resource: &Resource<S>) -> String {
-    serialize_with_options(resource, Options::default())
+    serialize_with_options(resource, SerializerOptions::default())
 }
 
 /// Serializes an abstract syntax tree representing a Fluent Translation List into a
 /// String accepting custom options.
+///
+/// # Example
+///
+/// ```
+/// use fluent_syntax::parser;
+/// use fluent_syntax::serializer::{self, MultilinePatterns, SerializerOptions};
+///
+/// let ftl = "hello = Hello\n    World\nbye = Bye\n";
+///
+/// let resource = parser::parse(ftl).expect("Failed to parse an FTL resource.");
+///
+/// let options = SerializerOptions {
+///     indent_width: 2,
+///     blank_lines_between_entries: 1,
+///     multiline_patterns: MultilinePatterns::Inline,
+///     ..SerializerOptions::default()
+/// };
+/// let serialized = serializer::serialize_with_options(&resource, options);
+///
+/// assert_eq!(serialized, "hello = Hello\n  World\n\nbye = Bye\n");
+/// ```
 pub fn serialize_with_options<'s, S: Slice<'s>>(
     resource: &Resource<S>,
-    options: Options,
+    options: SerializerOptions,
 ) -> String {
     let mut ser = Serializer::new(options);
     ser.serialize_resource(resource);
@@ -66,35 +89,68 @@ pub fn serialize_with_options<'s, S: Slice<'s>>(
 #[derive(Debug)]
 struct Serializer {
     writer: TextWriter,
-    options: Options,
+    options: SerializerOptions,
     state: State,
 }
 
 impl Serializer {
-    fn new(options: Options) -> Self {
+    fn new(options: SerializerOptions) -> Self {
         Serializer {
-            writer: TextWriter::default(),
+            // Continuation lines must be indented to be part of a pattern.
+            writer: TextWriter::new(options.indent_width.max(1)),
             options,
             state: State::default(),
         }
     }
 
     fn serialize_resource<'s, S: Slice<'s>>(&mut self, res: &Resource<S>) {
-        for entry in &res.body {
-            match entry {
-                Entry::Message(msg) => self.serialize_message(msg),
-                Entry::Term(term) => self.serialize_term(term),
-                Entry::Comment(comment) => self.serialize_free_comment(comment, "#"),
-                Entry::GroupComment(comment) => self.serialize_free_comment(comment, "##"),
-                Entry::ResourceComment(comment) => self.serialize_free_comment(comment, "###"),
-                Entry::Junk { content } => {
-                    if self.options.with_junk {
-                        self.serialize_junk(content.as_ref());
-                    }
+        if self.options.sort_entries {
+            for entry in sorted_entries(&res.body) {
+                self.serialize_entry(entry);
+            }
+        } else {
+            for entry in &res.body {
+                self.serialize_entry(entry);
+            }
+        }
+    }
+
+    fn serialize_entry<'s, S: Slice<'s>>(&mut self, entry: &Entry<S>) {
+        if matches!(entry, Entry::Message(_) | Entry::Term(_)) {
+            self.separate_entry();
+        }
+        match entry {
+            Entry::Message(msg) => self.serialize_message(msg),
+            Entry::Term(term) => self.serialize_term(term),
+            Entry::Comment(comment) => self.serialize_free_comment(comment, "#"),
+            Entry::GroupComment(comment) => self.serialize_free_comment(comment, "##"),
+            Entry::ResourceComment(comment) => self.serialize_free_comment(comment, "###"),
+            Entry::Junk { content } => {
+                if self.options.with_junk {
+                    self.serialize_junk(content.as_ref());
                 }
-            };
+            }
+        };
 
-            self.state.wrote_non_junk_entry = !matches!(entry, Entry::Junk { .. });
+        self.state.wrote_non_junk_entry = !matches!(entry, Entry::Junk { .. });
+        self.state.wrote_free_comment = matches!(
+            entry,
+            Entry::Comment(_) | Entry::GroupComment(_) | Entry::ResourceComment(_)
+        );
+    }
+
+    /// Writes the blank lines between a message or a term and the previous
+    /// entry. Free comments are already followed by a blank line.
+    fn separate_entry(&mut self) {
+        if !self.state.wrote_non_junk_entry {
+            return;
+        }
+        let mut blank_lines = self.options.blank_lines_between_entries;
+        if self.state.wrote_free_comment {
+            blank_lines = blank_lines.saturating_sub(1);
+        }
+        for _ in 0..blank_lines {
+            self.writer.newline();
         }
     }
 
@@ -108,7 +164,11 @@ impl Serializer {
 
     fn serialize_free_comment<'s, S: Slice<'s>>(&mut self, comment: &Comment<S>, prefix: &str) {
         if self.state.wrote_non_junk_entry {
-            self.writer.newline();
+            // Free comments are separated from the previous entry by at least
+            // one blank line, or they would look like its continuation.
+            for _ in 0..self.options.blank_lines_between_entries.max(1) {
+                self.writer.newline();
+            }
         }
         self.serialize_comment(comment, prefix);
         self.writer.newline();
@@ -160,11 +220,17 @@ impl Serializer {
     }
 
     fn serialize_pattern<'s, S: Slice<'s>>(&mut self, pattern: &Pattern<S>) {
-        let start_on_newline = pattern.starts_on_new_line();
+        let start_on_newline = self.options.multiline_patterns == MultilinePatterns::Block
+            && pattern.starts_on_new_line();
+        // Patterns which start on the same line still need their continuation
+        // lines indented. Select expressions indent their variants themselves.
+        let indent = start_on_newline || pattern.has_multiline_text();
 
+        if indent {
+            self.writer.indent();
+        }
         if start_on_newline {
             self.writer.newline();
-            self.writer.indent();
         } else {
             self.writer.write_literal(" ");
         }
@@ -173,7 +239,7 @@ impl Serializer {
             self.serialize_element(element);
         }
 
-        if start_on_newline {
+        if indent {
             self.writer.dedent();
         }
     }
@@ -225,6 +291,8 @@ impl Serializer {
                     self.writer.write_literal(" }");
                 }
             },
+            #[cfg(feature = "recover-placeables")]
+            PatternElement::Junk { content } => self.writer.write_literal(content.as_ref()),
         }
     }
 
@@ -284,6 +352,8 @@ impl Serializer {
                 self.serialize_expression(expression);
                 self.writer.write_literal("}");
             }
+            #[cfg(feature = "unknown-expressions")]
+            InlineExpression::Unknown { content } => self.writer.write_literal(content.as_ref()),
         }
     }
 
@@ -354,15 +424,60 @@ impl Serializer {
     }
 }
 
+/// Returns the entries in the order of [`SerializerOptions::sort_entries`].
+fn sorted_entries<S: AsRef<str>>(body: &[Entry<S>]) -> Vec<&Entry<S>> {
+    /// A message or a term, with the entries before it which move along.
+    type Block<'a, S> = (&'a str, Vec<&'a Entry<S>>);
+
+    fn flush<'a, S>(sorted: &mut Vec<&'a Entry<S>>, blocks: &mut Vec<Block<'a, S>>) {
+        blocks.sort_by_key(|(id, _)| *id);
+        for (_, entries) in blocks.drain(..) {
+            sorted.extend(entries);
+        }
+    }
+
+    let mut sorted = Vec::with_capacity(body.len());
+    let mut blocks = Vec::new();
+    let mut leading = Vec::new();
+    for entry in body {
+        match entry {
+            Entry::Message(Message { id, .. }) | Entry::Term(Term { id, .. }) => {
+                leading.push(entry);
+                blocks.push((id.name.as_ref(), core::mem::take(&mut leading)));
+            }
+            Entry::Comment(_) | Entry::Junk { .. } => leading.push(entry),
+            Entry::GroupComment(_) | Entry::ResourceComment(_) => {
+                flush(&mut sorted, &mut blocks);
+                sorted.append(&mut leading);
+                sorted.push(entry);
+            }
+        }
+    }
+    flush(&mut sorted, &mut blocks);
+    sorted.append(&mut leading);
+    sorted
+}
+
 impl<'s, S: Slice<'s>> Pattern<S> {
     fn starts_on_new_line(&self) -> bool {
         !self.has_leading_text_dot() && self.is_multiline()
     }
 
+    fn has_multiline_text(&self) -> bool {
+        self.elements.iter().any(|elem| match elem {
+            PatternElement::TextElement { value } => value.as_ref().contains('\n'),
+            PatternElement::Placeable { .. } => false,
+            #[cfg(feature = "recover-placeables")]
+            PatternElement::Junk { .. } => false,
+        })
+    }
+
     fn is_multiline(&self) -> bool {
         self.elements.iter().any(|elem| match elem {
             PatternElement::TextElement { value } => value.as_ref().contains('\n'),
             PatternElement::Placeable { expression } => is_select_expr(expression),
+            #[cfg(feature = "recover-placeables")]
+            PatternElement::Junk { content } => content.as_ref().contains('\n'),
         })
     }
 
@@ -386,24 +501,102 @@ fn is_select_expr<'s, S: Slice<'s>>(expr: &Expression<S>) -> bool {
 }
 
 /// Options for serializing an abstract syntax tree.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub struct Options {
+///
+/// Attributes always start on their own line, as the syntax requires.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SerializerOptions {
     /// Whether invalid text fragments should be serialized, too.
     pub with_junk: bool,
+    /// The number of spaces per level of indentation, 4 by default. Values
+    /// below 1 are serialized as 1, since continuation lines must be indented.
+    pub indent_width: usize,
+    /// The number of blank lines between entries, 0 by default. Free comments
+    /// are always separated from the previous entry and from the next one by
+    /// at least one blank line.
+    pub blank_lines_between_entries: usize,
+    /// Where multiline patterns start.
+    pub multiline_patterns: MultilinePatterns,
+    /// Whether messages and terms are sorted by their identifiers, `false` by
+    /// default.
+    ///
+    /// Group comments and resource comments stay in place, and the messages
+    /// and terms are sorted within each group. Standalone comments and junk
+    /// move along with the message or the term after them. This gives
+    /// machine-written files a canonical order, so that their diffs only show
+    /// actual changes.
+    pub sort_entries: bool,
+}
+
+impl Default for SerializerOptions {
+    fn default() -> Self {
+        Self {
+            with_junk: false,
+            indent_width: 4,
+            blank_lines_between_entries: 0,
+            multiline_patterns: MultilinePatterns::Block,
+            sort_entries: false,
+        }
+    }
+}
+
+/// The former name of [`SerializerOptions`].
+pub type Options = SerializerOptions;
+
+/// Where multiline patterns, which contain line breaks or select
+/// expressions, start.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MultilinePatterns {
+    /// On the line after the `=` or the variant key, indented. This is the
+    /// default.
+    ///
+    /// ```ftl
+    /// emails =
+    ///     { $count ->
+    ///         [one] One email
+    ///        *[other] { $count } emails
+    ///     }
+    /// ```
+    #[default]
+    Block,
+    /// On the same line as the `=` or the variant key.
+    ///
+    /// ```ftl
+    /// emails = { $count ->
+    ///     [one] One email
+    ///    *[other] { $count } emails
+    /// }
+    /// ```
+    Inline,
 }
 
 #[derive(Debug, Default, PartialEq)]
 struct State {
     wrote_non_junk_entry: bool,
+    wrote_free_comment: bool,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 struct TextWriter {
     buffer: String,
     indent_level: usize,
+    indent_width: usize,
+}
+
+impl Default for TextWriter {
+    fn default() -> Self {
+        Self::new(4)
+    }
 }
 
 impl TextWriter {
+    fn new(indent_width: usize) -> Self {
+        Self {
+            buffer: String::new(),
+            indent_level: 0,
+            indent_width,
+        }
+    }
+
     fn indent(&mut self) {
         self.indent_level += 1;
     }
@@ -416,8 +609,8 @@ impl TextWriter {
     }
 
     fn write_indent(&mut self) {
-        for _ in 0..self.indent_level {
-            self.buffer.push_str("    ");
+        for _ in 0..self.indent_level * self.indent_width {
+            self.buffer.push(' ');
         }
     }
 
@@ -43,9 +43,10 @@
 //!     "Foo \\ Bar"
 //! );
 //! ```
-use std::borrow::Cow;
-use std::char;
-use std::fmt;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::char;
+use core::fmt;
 
 const UNKNOWN_CHAR: char = '�';
 
@@ -75,7 +76,7 @@ where
     w.write_str(input)
 }
 
-fn unescape<W>(w: &mut W, input: &str) -> Result<bool, std::fmt::Error>
+fn unescape<W>(w: &mut W, input: &str) -> Result<bool, fmt::Error>
 where
     W: fmt::Write,
 {
@@ -0,0 +1,60 @@
+use fluent_syntax::ast;
+use fluent_syntax::ast::annotation::Annotation;
+
+#[test]
+fn parse_annotations() {
+    assert_eq!(
+        Annotation::parse(".maxLength: 40"),
+        Some(Annotation {
+            key: "maxLength",
+            value: "40",
+        })
+    );
+    assert_eq!(
+        Annotation::parse("  .note:  Shown in the toolbar: keep it short.  "),
+        Some(Annotation {
+            key: "note",
+            value: "Shown in the toolbar: keep it short.",
+        })
+    );
+    assert_eq!(
+        Annotation::parse(".empty:"),
+        Some(Annotation {
+            key: "empty",
+            value: "",
+        })
+    );
+
+    for line in [
+        "",
+        "maxLength: 40",
+        ".: 40",
+        ".max length: 40",
+        ".maxLength 40",
+        ". maxLength: 40",
+    ] {
+        assert_eq!(Annotation::parse(line), None, "{:?}", line);
+    }
+}
+
+#[test]
+fn comment_annotations() {
+    let comment = ast::Comment {
+        content: vec![
+            "Free text.",
+            ".placeholders: user, count",
+            "",
+            ".maxLength: 40",
+            "More free text: not an annotation.",
+            ".maxLength: 20",
+        ],
+    };
+    let keys: Vec<_> = comment
+        .annotations()
+        .map(|annotation| annotation.key)
+        .collect();
+    assert_eq!(keys, ["placeholders", "maxLength", "maxLength"]);
+    assert_eq!(comment.annotation("placeholders"), Some("user, count"));
+    assert_eq!(comment.annotation("maxLength"), Some("40"));
+    assert_eq!(comment.annotation("missing"), None);
+}
@@ -0,0 +1,114 @@
+use arbitrary::{Arbitrary, Unstructured};
+use fluent_syntax::ast;
+use fluent_syntax::{parser, serializer};
+
+/// Returns pseudo-random bytes, the same on every run.
+fn bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn valid_resources_round_trip() {
+    for seed in 0..2000 {
+        let data = bytes(seed, 1024);
+        let mut u = Unstructured::new(&data);
+        let mut resource = ast::Resource::<String>::arbitrary(&mut u).unwrap();
+        resource.make_valid();
+
+        let source = serializer::serialize(&resource);
+        match parser::parse(source.clone()) {
+            Ok(parsed) => assert_eq!(parsed, resource, "{}", source),
+            Err((_, errors)) => panic!("{}\n{:?}", source, errors),
+        }
+    }
+}
+
+#[test]
+fn make_valid() {
+    let text = |value: &str| ast::PatternElement::TextElement {
+        value: value.to_string(),
+    };
+    let mut resource = ast::Resource {
+        body: vec![
+            ast::Entry::Junk {
+                content: "junk".to_string(),
+            },
+            ast::Entry::Message(ast::Message {
+                id: ast::Identifier {
+                    name: "1 key".to_string(),
+                },
+                value: Some(ast::Pattern {
+                    elements: vec![
+                        text("  {Hello}"),
+                        text(",\nworld "),
+                        ast::PatternElement::Placeable {
+                            expression: ast::Expression::Select {
+                                selector: ast::InlineExpression::MessageReference {
+                                    id: ast::Identifier {
+                                        name: "msg".to_string(),
+                                    },
+                                    attribute: None,
+                                },
+                                variants: vec![],
+                            },
+                        },
+                        text(" "),
+                    ],
+                }),
+                attributes: vec![],
+                comment: Some(ast::Comment { content: vec![] }),
+            }),
+            ast::Entry::Term(ast::Term {
+                id: ast::Identifier {
+                    name: "brand".to_string(),
+                },
+                value: ast::Pattern {
+                    elements: vec![ast::PatternElement::Placeable {
+                        expression: ast::Expression::Inline(
+                            ast::InlineExpression::FunctionReference {
+                                id: ast::Identifier {
+                                    name: "number".to_string(),
+                                },
+                                arguments: ast::CallArguments {
+                                    positional: vec![ast::InlineExpression::StringLiteral {
+                                        value: "a\"b\\c\\u00e9".to_string(),
+                                    }],
+                                    named: vec![ast::NamedArgument {
+                                        name: ast::Identifier {
+                                            name: "style".to_string(),
+                                        },
+                                        value: ast::InlineExpression::NumberLiteral {
+                                            value: "1.".to_string(),
+                                        },
+                                    }],
+                                },
+                            },
+                        ),
+                    }],
+                },
+                attributes: vec![],
+                comment: None,
+            }),
+        ],
+    };
+    resource.make_valid();
+    assert_eq!(
+        serializer::serialize(&resource),
+        "\
+#
+x1key =
+    Hello,world { $selector ->
+       *[other] { \"\" }
+    }
+-brand = { NUMBER(\"a\\\"b\\\\c\\u00e9\", style: 1) }
+"
+    );
+}
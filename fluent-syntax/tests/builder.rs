@@ -0,0 +1,228 @@
+use fluent_syntax::ast;
+use fluent_syntax::ast::builder::{
+    function_reference, message_reference, number_literal, string_literal, term_reference,
+    variable, BuilderError, MessageBuilder, PatternBuilder, SelectBuilder, TermBuilder,
+};
+use fluent_syntax::parser::parse;
+use fluent_syntax::serializer::serialize;
+
+fn serialize_entries(body: Vec<ast::Entry<&str>>) -> String {
+    serialize(&ast::Resource { body })
+}
+
+#[test]
+fn built_entries_roundtrip() {
+    let term = TermBuilder::new("brand", PatternBuilder::new().text("Firefox"))
+        .attribute("gender", PatternBuilder::new().text("masculine"))
+        .build()
+        .unwrap();
+    let message = MessageBuilder::new("welcome")
+        .comment("Shown on startup.")
+        .comment("{ $user } is the user name.")
+        .value(
+            PatternBuilder::new()
+                .text("Welcome to ")
+                .placeable(term_reference("brand", None))
+                .text(", ")
+                .placeable(variable("user"))
+                .text(" ")
+                .placeable(string_literal("{"))
+                .placeable(function_reference(
+                    "NUMBER",
+                    vec![variable("count")],
+                    vec![("minimumFractionDigits", number_literal("2"))],
+                ))
+                .placeable(string_literal("}")),
+        )
+        .attribute(
+            "title",
+            PatternBuilder::new().select(
+                SelectBuilder::new(term_reference("brand", Some("gender")))
+                    .variant("masculine", PatternBuilder::new().text("His"))
+                    .variant("-1.5", PatternBuilder::new().text("Odd"))
+                    .default_variant(
+                        "other",
+                        PatternBuilder::new().placeable(message_reference("other", Some("attr"))),
+                    ),
+            ),
+        )
+        .build()
+        .unwrap();
+
+    let body = vec![ast::Entry::Term(term), ast::Entry::Message(message)];
+    let serialized = serialize_entries(body.clone());
+    assert_eq!(
+        serialized,
+        r#"-brand = Firefox
+    .gender = masculine
+# Shown on startup.
+# { $user } is the user name.
+welcome = Welcome to { -brand }, { $user } { "{" }{ NUMBER($count, minimumFractionDigits: 2) }{ "}" }
+    .title =
+        { -brand.gender ->
+            [masculine] His
+            [-1.5] Odd
+           *[other] { other.attr }
+        }
+"#
+    );
+
+    let reparsed = parse(serialized.as_str()).expect("Failed to parse an FTL resource.");
+    assert_eq!(reparsed.body, body);
+}
+
+#[test]
+fn build_pattern() {
+    let pattern = PatternBuilder::new()
+        .text("Line one\n  line two")
+        .build()
+        .unwrap();
+    assert_eq!(
+        pattern.elements,
+        [ast::PatternElement::TextElement {
+            value: "Line one\n  line two"
+        }]
+    );
+
+    assert_eq!(
+        PatternBuilder::<&str>::new().build(),
+        Err(BuilderError::MissingValue)
+    );
+}
+
+#[test]
+fn build_errors() {
+    let text = |value| MessageBuilder::new("key").value(PatternBuilder::new().text(value));
+    let placeable =
+        |expression| MessageBuilder::new("key").value(PatternBuilder::new().placeable(expression));
+    let select = |select| MessageBuilder::new("key").value(PatternBuilder::new().select(select));
+    let other = || PatternBuilder::new().text("Other");
+
+    let cases = vec![
+        (
+            MessageBuilder::new("-key").value(PatternBuilder::new().text("Value")),
+            BuilderError::InvalidIdentifier("-key".to_string()),
+        ),
+        (
+            MessageBuilder::new("key"),
+            BuilderError::ExpectedMessageField {
+                entry_id: "key".to_string(),
+            },
+        ),
+        (
+            MessageBuilder::new("key").attribute("attr", PatternBuilder::new().text("  ")),
+            BuilderError::MissingValue,
+        ),
+        (
+            MessageBuilder::new("key")
+                .comment("Two\nlines")
+                .value(PatternBuilder::new().text("Value")),
+            BuilderError::InvalidComment("Two\nlines".to_string()),
+        ),
+        (
+            text("Hello {user}"),
+            BuilderError::InvalidText("Hello {user}".to_string()),
+        ),
+        (
+            text("One\n  [two]"),
+            BuilderError::InvalidText("One\n  [two]".to_string()),
+        ),
+        (
+            text("One\n.two"),
+            BuilderError::InvalidText("One\n.two".to_string()),
+        ),
+        (
+            placeable(variable("user name")),
+            BuilderError::InvalidIdentifier("user name".to_string()),
+        ),
+        (
+            placeable(number_literal("1e3")),
+            BuilderError::InvalidNumberLiteral("1e3".to_string()),
+        ),
+        (
+            placeable(string_literal("say \"hi\"")),
+            BuilderError::InvalidStringLiteral("say \"hi\"".to_string()),
+        ),
+        (
+            placeable(string_literal("\\u00")),
+            BuilderError::InvalidStringLiteral("\\u00".to_string()),
+        ),
+        (
+            placeable(term_reference("brand", Some("gender"))),
+            BuilderError::TermAttributeAsPlaceable,
+        ),
+        (
+            placeable(function_reference("number", vec![], vec![])),
+            BuilderError::InvalidFunctionName("number".to_string()),
+        ),
+        (
+            placeable(function_reference(
+                "NUMBER",
+                vec![],
+                vec![("style", variable("style"))],
+            )),
+            BuilderError::ExpectedLiteral,
+        ),
+        (
+            placeable(function_reference(
+                "NUMBER",
+                vec![],
+                vec![
+                    ("style", string_literal("a")),
+                    ("style", string_literal("b")),
+                ],
+            )),
+            BuilderError::DuplicatedNamedArgument("style".to_string()),
+        ),
+        (
+            select(SelectBuilder::new(variable("n")).variant("one", other())),
+            BuilderError::MissingDefaultVariant,
+        ),
+        (
+            select(
+                SelectBuilder::new(variable("n"))
+                    .default_variant("one", other())
+                    .default_variant("other", other()),
+            ),
+            BuilderError::MultipleDefaultVariants,
+        ),
+        (
+            select(SelectBuilder::new(variable("n")).default_variant("1x", other())),
+            BuilderError::InvalidNumberLiteral("1x".to_string()),
+        ),
+        (
+            select(
+                SelectBuilder::new(message_reference("msg", None))
+                    .default_variant("other", other()),
+            ),
+            BuilderError::MessageReferenceAsSelector,
+        ),
+        (
+            select(
+                SelectBuilder::new(term_reference("brand", None)).default_variant("other", other()),
+            ),
+            BuilderError::TermReferenceAsSelector,
+        ),
+    ];
+
+    for (builder, error) in cases {
+        assert_eq!(builder.build(), Err(error));
+    }
+}
+
+#[test]
+fn build_owned_term() {
+    let term = TermBuilder::new(
+        String::from("brand"),
+        PatternBuilder::new().text(String::from("Firefox")),
+    )
+    .comment(String::from("The product name."))
+    .build()
+    .unwrap();
+    assert_eq!(
+        serialize(&ast::Resource {
+            body: vec![ast::Entry::Term(term)]
+        }),
+        "# The product name.\n-brand = Firefox\n"
+    );
+}
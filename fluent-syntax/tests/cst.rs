@@ -0,0 +1,131 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::cst::{self, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TokenKind};
+
+fn parse(source: &str) -> SyntaxNode<'_> {
+    match cst::parse(source) {
+        Ok(tree) => tree,
+        Err((tree, _)) => tree,
+    }
+}
+
+fn texts<'s>(tokens: &[SyntaxToken<'s>]) -> Vec<&'s str> {
+    tokens.iter().map(SyntaxToken::text).collect()
+}
+
+/// Checks that the tokens of the tree cover the source, in order.
+fn check(source: &str) {
+    let tree = parse(source);
+    assert_eq!(tree.to_string(), source);
+    let mut pos = 0;
+    for token in tree.tokens() {
+        assert_eq!(token.span().start, pos);
+        assert_eq!(&source[token.span()], token.text());
+        pos = token.span().end;
+    }
+    assert_eq!(pos, source.len());
+}
+
+#[test]
+fn cst_round_trips_fixtures() {
+    for pattern in ["./tests/fixtures/*.ftl", "./benches/*.ftl"] {
+        for entry in glob(pattern).expect("Failed to read glob pattern") {
+            let path = entry.expect("Error while getting an entry");
+            println!("Checking: {:?}", path);
+            let source = fs::read_to_string(path).expect("Failed to read the fixture");
+            check(&source);
+        }
+    }
+}
+
+#[test]
+fn cst_round_trips_layout() {
+    check("");
+    check("\n\n");
+    check("key=Value");
+    check("key   =   Value   \n\n\n   \n");
+    check("key =\n    Multiline\n      indented\n\n    text\n");
+    check("key = { $n ->\n    [one]   One\n   *[other]{$n}\n}\n");
+    check("key = { FUNC( $x ,  opt :  \"a\" ) }\n");
+    check("key = Value\r\n    .attr = Attribute\r\n\r\n-term = Term\r\n");
+    check("# Comment\n\n## Group\n\n### Resource\nkey = { -term.attr(x: 1) } { msg.attr }\n");
+    check("key = { \"unclosed\njunk junk\n\nother = Value");
+}
+
+#[test]
+fn cst_attaches_trivia() {
+    let source = "\
+# Comment
+key =   Hello { $user }!   \n    .title = Title
+
+
+other = Other
+";
+    let tree = parse(source);
+    assert_eq!(tree.kind(), SyntaxKind::Resource);
+    let entries: Vec<_> = tree.nodes().collect();
+    assert_eq!(entries.len(), 2);
+
+    let message = entries[0];
+    assert_eq!(message.kind(), SyntaxKind::Message);
+    assert_eq!(
+        &source[message.span()],
+        source[..source.find("\n\n").unwrap()].trim_end()
+    );
+    assert_eq!(texts(message.leading_trivia()), Vec::<&str>::new());
+    assert_eq!(texts(message.trailing_trivia()), vec!["\n"]);
+
+    let kinds: Vec<_> = message.nodes().map(SyntaxNode::kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            SyntaxKind::Comment,
+            SyntaxKind::Identifier,
+            SyntaxKind::Pattern,
+            SyntaxKind::Attribute
+        ]
+    );
+    let nodes: Vec<_> = message.nodes().collect();
+    assert_eq!(texts(nodes[0].trailing_trivia()), vec!["\n"]);
+    assert_eq!(texts(nodes[1].trailing_trivia()), vec![" "]);
+    assert_eq!(texts(nodes[2].leading_trivia()), vec!["   "]);
+    assert_eq!(texts(nodes[2].trailing_trivia()), vec!["   ", "\n"]);
+    assert_eq!(texts(nodes[3].leading_trivia()), vec!["    "]);
+    let punctuation: Vec<_> = message
+        .children()
+        .iter()
+        .filter_map(|child| match child {
+            SyntaxElement::Token(token) => Some((token.kind(), token.text())),
+            SyntaxElement::Node(_) => None,
+        })
+        .collect();
+    assert_eq!(punctuation, vec![(TokenKind::Punctuation, "=")]);
+
+    let placeable = nodes[2].nodes().nth(1).expect("Pattern has a placeable");
+    assert_eq!(placeable.kind(), SyntaxKind::Placeable);
+    assert_eq!(placeable.to_string(), "{ $user }");
+    let variable = placeable
+        .nodes()
+        .next()
+        .expect("Placeable has an expression");
+    assert_eq!(variable.kind(), SyntaxKind::VariableReference);
+    assert_eq!(texts(variable.leading_trivia()), vec![" "]);
+    assert_eq!(texts(variable.trailing_trivia()), vec![" "]);
+
+    let other = entries[1];
+    assert_eq!(texts(other.leading_trivia()), vec!["\n", "\n"]);
+    assert_eq!(texts(other.trailing_trivia()), vec!["\n"]);
+    assert_eq!(other.to_string(), "\n\nother = Other\n");
+}
+
+#[test]
+fn cst_keeps_junk() {
+    let source = "key = { $x\nother = Other\n";
+    let (tree, errors) = cst::parse(source).expect_err("Resource contains junk");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(tree.to_string(), source);
+    let kinds: Vec<_> = tree.nodes().map(SyntaxNode::kind).collect();
+    assert_eq!(kinds, vec![SyntaxKind::Junk, SyntaxKind::Message]);
+}
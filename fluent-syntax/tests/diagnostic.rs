@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use fluent_syntax::parser::{parse, Diagnostic, ErrorKind, Label};
+
+fn diagnostics(ftl: &str) -> Vec<Diagnostic> {
+    let (_, errors) = parse(ftl).expect_err("Resource should contain errors.");
+    errors.iter().map(|error| error.diagnostic()).collect()
+}
+
+#[test]
+fn diagnostic_labels() {
+    let ftl = "key = { FOO(a: 1, a: 2) }\nother = Value\n";
+    assert_eq!(
+        diagnostics(ftl),
+        [Diagnostic {
+            code: "E0022",
+            message: "The \"a\" argument appears twice".to_string(),
+            primary: Label {
+                span: 19..20,
+                message: "`a` is already set".to_string(),
+            },
+            secondary: vec![Label {
+                span: 0..26,
+                message: "skipped as junk".to_string(),
+            }],
+            help: None,
+        }]
+    );
+
+    let diagnostic = &diagnostics("key = { -term.attr }\n")[0];
+    assert_eq!(diagnostic.code, "E0019");
+    assert_eq!(diagnostic.primary.message, "term attribute");
+    assert_eq!(
+        diagnostic.help,
+        Some("attributes of terms are only for selectors")
+    );
+
+    let diagnostic = &diagnostics("key = {\"\\q\"}\n")[0];
+    assert_eq!(diagnostic.code, "E0025");
+    assert_eq!(
+        diagnostic.help,
+        Some(r#"the escape sequences are `\\`, `\"`, `\uXXXX` and `\UXXXXXX`"#)
+    );
+}
+
+#[test]
+fn diagnostic_codes_are_unique() {
+    let kinds = [
+        ErrorKind::ExpectedToken('='),
+        ErrorKind::ExpectedCharRange {
+            range: "a-z".to_string(),
+        },
+        ErrorKind::ExpectedMessageField {
+            entry_id: "key".to_string(),
+        },
+        ErrorKind::ExpectedTermField {
+            entry_id: "key".to_string(),
+        },
+        ErrorKind::ForbiddenCallee,
+        ErrorKind::MissingDefaultVariant,
+        ErrorKind::MissingValue,
+        ErrorKind::MultipleDefaultVariants,
+        ErrorKind::MessageReferenceAsSelector,
+        ErrorKind::TermReferenceAsSelector,
+        ErrorKind::MessageAttributeAsSelector,
+        ErrorKind::TermAttributeAsPlaceable,
+        ErrorKind::UnterminatedStringLiteral,
+        ErrorKind::PositionalArgumentFollowsNamed,
+        ErrorKind::DuplicatedNamedArgument("a".to_string()),
+        ErrorKind::UnknownEscapeSequence("q".to_string()),
+        ErrorKind::InvalidUnicodeEscapeSequence("u00".to_string()),
+        ErrorKind::UnbalancedClosingBrace,
+        ErrorKind::ExpectedInlineExpression,
+        ErrorKind::ExpectedSimpleExpressionAsSelector,
+        ErrorKind::ExpectedLiteral,
+        ErrorKind::TooDeeplyNested { limit: 1 },
+        ErrorKind::EntryTooLong { limit: 1 },
+        ErrorKind::TooManyVariants { limit: 1 },
+    ];
+    let codes: HashSet<_> = kinds.iter().map(ErrorKind::code).collect();
+    assert_eq!(codes.len(), kinds.len());
+}
@@ -0,0 +1,123 @@
+use fluent_syntax::ast;
+use fluent_syntax::diff::{diff, Change, EntryChange, EntryId, ResourceDiff};
+use fluent_syntax::parser;
+
+fn diff_ftl(old: &str, new: &str) -> ResourceDiff {
+    let old = parser::parse(old).expect("Failed to parse the old resource.");
+    let new = parser::parse(new).expect("Failed to parse the new resource.");
+    diff(&old, &new)
+}
+
+fn message(id: &str) -> EntryId {
+    EntryId::Message(id.to_string())
+}
+
+#[test]
+fn identical_resources() {
+    let ftl = "# Comment\nhello = Hello, { $user }!\n    .title = Title\n-brand = Firefox\n";
+    assert!(diff_ftl(ftl, ftl).is_empty());
+    // Comments and layout are not compared.
+    let reformatted =
+        "hello =\n    Hello,   { $user }!\n    .title =\n        Title\n-brand = Firefox\n";
+    assert!(diff_ftl(ftl, reformatted).is_empty());
+}
+
+#[test]
+fn added_and_removed() {
+    let diff = diff_ftl("a = A\n-b = B\nc = C\n", "d = D\n-c = C\na = A\nb = B\n");
+    assert_eq!(
+        diff.added,
+        [message("d"), EntryId::Term("c".to_string()), message("b")]
+    );
+    assert_eq!(diff.removed, [EntryId::Term("b".to_string()), message("c")]);
+    assert_eq!(diff.changed, []);
+    assert_eq!(diff.added[1].to_string(), "-c");
+}
+
+#[test]
+fn changed_values_and_attributes() {
+    let old = "\
+a = A
+    .kept = Kept
+    .changed = Old
+    .removed = Removed
+b = B
+c =
+    .title = Title
+";
+    let new = "\
+a = A
+    .added = Added
+    .kept = Kept
+    .changed = New
+b = B changed
+c = Value
+    .title = Title
+";
+    assert_eq!(
+        diff_ftl(old, new).changed,
+        [
+            EntryChange {
+                id: message("a"),
+                changes: vec![
+                    Change::AttributeAdded("added".to_string()),
+                    Change::AttributeChanged("changed".to_string()),
+                    Change::AttributeRemoved("removed".to_string()),
+                ],
+            },
+            EntryChange {
+                id: message("b"),
+                changes: vec![Change::Value],
+            },
+            EntryChange {
+                id: message("c"),
+                changes: vec![Change::Value],
+            },
+        ]
+    );
+}
+
+#[test]
+fn changed_variables() {
+    let old = "key = { $a } { NUMBER($b) }\n    .title = { $c }\n";
+    let new = "key = { $a } { $d ->\n       *[other] { $e }\n    }\n    .title = { $c }\n";
+    assert_eq!(
+        diff_ftl(old, new).changed,
+        [EntryChange {
+            id: message("key"),
+            changes: vec![
+                Change::Value,
+                Change::Variables {
+                    added: vec!["d".to_string(), "e".to_string()],
+                    removed: vec!["b".to_string()],
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn last_definition_is_compared() {
+    let diff = diff_ftl("key = One\nkey = Two\n", "key = Two\n");
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn resources_of_different_types() {
+    let old = parser::parse("key = Value\n").expect("Failed to parse.");
+    let new: ast::Resource<String> = ast::Resource {
+        body: vec![ast::Entry::Message(ast::Message {
+            id: ast::Identifier {
+                name: "key".to_string(),
+            },
+            value: Some(ast::Pattern {
+                elements: vec![ast::PatternElement::TextElement {
+                    value: "Value".to_string(),
+                }],
+            }),
+            attributes: vec![],
+            comment: None,
+        })],
+    };
+    assert!(diff(&old, &new).is_empty());
+}
@@ -0,0 +1,14 @@
+## Number literals as values of named arguments
+
+positive-named-arg = {FUN(x: 1)}
+negative-named-arg = {FUN(x: -1)}
+negative-float-named-arg = {FUN(x: -0.5)}
+mixed-args = {FUN(-1, x: -1, y: 2, z: "-3")}
+sparse-named-arg = {FUN(  x  :  -1  )}
+
+# JUNK Term references are not literals
+invalid-named-arg-01 = {FUN(x: -term)}
+# JUNK A minus sign must be followed by digits
+invalid-named-arg-02 = {FUN(x: -)}
+# JUNK No whitespace is allowed after the minus sign
+invalid-named-arg-03 = {FUN(x: - 1)}
@@ -0,0 +1,110 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::format;
+use fluent_syntax::parser::parse;
+use fluent_syntax::serializer::{MultilinePatterns, SerializerOptions};
+
+/// `multiline_values.ftl` does not roundtrip through the serializer:
+/// <https://github.com/projectfluent/fluent-rs/issues/286>
+const IGNORE_LIST: [&str; 1] = ["multiline_values.ftl"];
+
+#[test]
+fn format_fixtures() {
+    let options = [
+        SerializerOptions::default(),
+        SerializerOptions {
+            indent_width: 2,
+            blank_lines_between_entries: 1,
+            multiline_patterns: MultilinePatterns::Inline,
+            ..SerializerOptions::default()
+        },
+    ];
+    for pattern in ["./tests/fixtures/*.ftl", "./benches/*.ftl"] {
+        for entry in glob(pattern).expect("Failed to read glob pattern") {
+            let path = entry.expect("Error while getting an entry");
+            if path.file_name().map_or(false, |name| {
+                IGNORE_LIST.iter().any(|ignored| name == *ignored)
+            }) {
+                continue;
+            }
+            let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+            for options in options {
+                let formatted = match format(&source, options) {
+                    Ok(formatted) => formatted,
+                    Err(errors) => {
+                        assert!(parse(source.as_str()).is_err());
+                        assert!(!errors.is_empty());
+                        continue;
+                    }
+                };
+                println!("Checking: {:?}", path);
+                assert_eq!(format(&formatted, options), Ok(formatted.clone()));
+                let formatted_ast = parse(formatted.as_str()).expect("Failed to parse");
+                let formatted_normalized =
+                    format(&source.replace("\r\n", "\n"), options).expect("Failed to parse");
+                assert_eq!(formatted_normalized, formatted);
+                assert_eq!(
+                    formatted_ast.body.len(),
+                    parse(source.as_str()).unwrap().body.len()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn format_keeps_groups_of_entries() {
+    let ftl = "\
+### Resource comment
+## Group comment
+# Comment
+hello =Hello
+    { $user}
+welcome =   Welcome
+-brand  =  Firefox
+
+
+
+# Standalone comment
+
+bye = { $n ->
+[one]  Bye
+*[other] Byes
+}
+";
+    let expected = "\
+### Resource comment
+
+## Group comment
+
+# Comment
+hello =
+    Hello
+    { $user }
+welcome = Welcome
+-brand = Firefox
+
+# Standalone comment
+
+bye =
+    { $n ->
+        [one] Bye
+       *[other] Byes
+    }
+";
+    let formatted = format(ftl, SerializerOptions::default()).expect("Failed to parse");
+    assert_eq!(formatted, expected);
+    assert_eq!(
+        format(expected, SerializerOptions::default()),
+        Ok(formatted)
+    );
+}
+
+#[test]
+fn format_rejects_junk() {
+    let errors =
+        format("key = { $x\n", SerializerOptions::default()).expect_err("Resource contains junk");
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,143 @@
+use fluent_syntax::diff::EntryId;
+use fluent_syntax::graph::{CycleError, ReferenceGraph};
+use fluent_syntax::parser;
+
+fn graph(ftl: &str) -> ReferenceGraph {
+    let resource = parser::parse(ftl).expect("Failed to parse.");
+    ReferenceGraph::from_resource(&resource)
+}
+
+fn message(id: &str) -> EntryId {
+    EntryId::Message(id.to_string())
+}
+
+fn term(id: &str) -> EntryId {
+    EntryId::Term(id.to_string())
+}
+
+#[test]
+fn references() {
+    let graph = graph(
+        r#"
+-brand = Firefox
+    .gender = masculine
+about = About { -brand }
+    .title = { -brand.gender ->
+        [masculine] { menu.label }
+       *[other] { missing }
+    }
+menu = Menu
+    .label = { about }
+"#,
+    );
+
+    assert_eq!(
+        graph.entries().collect::<Vec<_>>(),
+        vec![&message("about"), &message("menu"), &term("brand")]
+    );
+    assert_eq!(
+        graph.references(&message("about")).collect::<Vec<_>>(),
+        vec![&message("menu"), &message("missing"), &term("brand")]
+    );
+    assert_eq!(
+        graph.referenced_by(&term("brand")).collect::<Vec<_>>(),
+        vec![&message("about")]
+    );
+    assert_eq!(
+        graph.missing().collect::<Vec<_>>(),
+        vec![&message("missing")]
+    );
+    assert!(graph.is_defined(&term("brand")));
+    assert!(!graph.is_defined(&message("missing")));
+}
+
+#[test]
+fn dependents_and_dependencies() {
+    let graph = graph(
+        r#"
+-brand-name = Firefox
+-brand-short = { -brand-name }
+about = About { -brand-short }
+menu-about = { about }
+hello = Hello!
+"#,
+    );
+
+    assert_eq!(
+        graph.dependents(&term("brand-name")),
+        vec![
+            &message("about"),
+            &message("menu-about"),
+            &term("brand-short")
+        ]
+    );
+    assert_eq!(
+        graph.dependencies(&message("menu-about")),
+        vec![&message("about"), &term("brand-name"), &term("brand-short")]
+    );
+    assert!(graph.dependents(&message("hello")).is_empty());
+}
+
+#[test]
+fn topological_order() {
+    let graph = graph(
+        r#"
+menu-about = { about }
+about = About { -brand }
+-brand = Firefox
+hello = Hello { missing }!
+"#,
+    );
+
+    let order = graph.topological_order().expect("No cycles.");
+    assert_eq!(order.len(), 4);
+    let position = |id: &EntryId| order.iter().position(|other| *other == id).unwrap();
+    assert!(position(&term("brand")) < position(&message("about")));
+    assert!(position(&message("about")) < position(&message("menu-about")));
+}
+
+#[test]
+fn cycles() {
+    let graph = graph(
+        r#"
+a = { b }
+b = { -c }
+-c = { a }
+self = { self }
+ok = { a }
+"#,
+    );
+
+    assert_eq!(
+        graph.cycles(),
+        vec![
+            vec![&message("a"), &message("b"), &term("c")],
+            vec![&message("self")],
+        ]
+    );
+    let error = graph.topological_order().unwrap_err();
+    assert_eq!(
+        error,
+        CycleError {
+            cycle: vec![message("a"), message("b"), term("c")],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        r#"The entries reference each other in a cycle: "a" -> "b" -> "-c" -> "a""#
+    );
+    assert!(graph.dependents(&message("a")).contains(&&message("a")));
+}
+
+#[test]
+fn multiple_resources() {
+    let first = parser::parse("-brand = Firefox\n").expect("Failed to parse.");
+    let second = parser::parse("hello = Hello from { -brand }\n").expect("Failed to parse.");
+
+    let mut graph = ReferenceGraph::new();
+    graph.add_resource(&first);
+    graph.add_resource(&second);
+
+    assert_eq!(graph.dependents(&term("brand")), vec![&message("hello")]);
+    assert_eq!(graph.missing().count(), 0);
+}
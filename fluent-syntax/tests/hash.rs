@@ -0,0 +1,57 @@
+use fluent_syntax::hash_entry;
+use fluent_syntax::parser::parse;
+
+/// Returns the hash of the first entry of `source`.
+fn hash(source: &str) -> u64 {
+    let resource = parse(source).expect("Failed to parse an FTL resource.");
+    hash_entry(&resource.body[0])
+}
+
+#[test]
+fn hash_is_stable() {
+    // The hash must not change across versions, since it is stored by sync
+    // tools.
+    assert_eq!(hash("hello = Hello, { $user }!\n"), 12730816670961156148);
+}
+
+#[test]
+fn hash_ignores_layout_and_comments() {
+    let base = hash("key = One two { $three }\n    .attr = Four\n");
+    assert_eq!(
+        base,
+        hash("# A comment.\nkey =\n    One   two\n    { $three }\n    .attr = Four\n")
+    );
+    assert_eq!(base, hash("other-id = One two {$three}\n  .attr =  Four\n"));
+
+    let select = hash("key = { $n ->\n    [one] One\n   *[other] Many\n}\n");
+    assert_eq!(
+        select,
+        hash("key =\n    {   $n   ->\n        [one]   One\n       *[other]   Many\n    }\n")
+    );
+}
+
+#[test]
+fn hash_detects_changes() {
+    let base = hash("key = One two { $three }\n    .attr = Four\n");
+    for changed in [
+        "key = One too { $three }\n    .attr = Four\n",
+        "key = One two { $four }\n    .attr = Four\n",
+        "key = One two{ $three }\n    .attr = Four\n",
+        "key = One two { three }\n    .attr = Four\n",
+        "key = One two { -three }\n    .attr = Four\n",
+        "key = One two { $three }\n    .title = Four\n",
+        "key = One two { $three }\n",
+        "-key = One two { $three }\n    .attr = Four\n",
+    ] {
+        assert_ne!(base, hash(changed), "{}", changed);
+    }
+
+    assert_ne!(
+        hash("key = { $n ->\n    [one] One\n   *[other] Many\n}\n"),
+        hash("key = { $n ->\n   *[one] One\n    [other] Many\n}\n")
+    );
+    assert_ne!(
+        hash("key = { NUMBER($n, style: \"percent\") }\n"),
+        hash("key = { NUMBER($n, style: \"decimal\") }\n")
+    );
+}
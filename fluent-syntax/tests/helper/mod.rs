@@ -46,6 +46,7 @@ fn adapt_pattern(pattern: &mut ast::Pattern<String>, crlf: bool) {
                 adapt_expression(&mut expression, crlf);
                 elements.push(ast::PatternElement::Placeable { expression });
             }
+            _ => elements.push(element.clone()),
         }
     }
     pattern.elements = elements;
@@ -0,0 +1,126 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::highlight::{highlight, TokenKind};
+use fluent_syntax::parser::parse_with_spans;
+
+/// Returns the kinds and the source text of the tokens of `source`.
+fn tokens(source: &str) -> Vec<(TokenKind, &str)> {
+    let spanned = match parse_with_spans(source) {
+        Ok(spanned) => spanned,
+        Err((spanned, _)) => spanned,
+    };
+    highlight(&spanned, source)
+        .into_iter()
+        .map(|token| (token.kind, &source[token.span]))
+        .collect()
+}
+
+#[test]
+fn highlight_entries() {
+    let source = "\
+### Resource
+
+# Comment
+-brand = Firefox
+    .gender = masculine
+key = { -brand.gender ->
+        [masculine] { NUMBER($n, style: \"percent\") }
+       *[other] { msg.title } { 5 }
+    }
+    .attr = { -brand(case: 1) }
+
+junk junk
+";
+    assert_eq!(
+        tokens(source),
+        [
+            (TokenKind::Comment, "### Resource"),
+            (TokenKind::Comment, "# Comment"),
+            (TokenKind::TermId, "-brand"),
+            (TokenKind::Attribute, "gender"),
+            (TokenKind::MessageId, "key"),
+            (TokenKind::TermId, "-brand"),
+            (TokenKind::Attribute, "gender"),
+            (TokenKind::SelectArrow, "->"),
+            (TokenKind::VariantKey, "masculine"),
+            (TokenKind::Function, "NUMBER"),
+            (TokenKind::Variable, "$n"),
+            (TokenKind::NamedArgument, "style"),
+            (TokenKind::StringLiteral, "\"percent\""),
+            (TokenKind::DefaultVariant, "*"),
+            (TokenKind::VariantKey, "other"),
+            (TokenKind::MessageId, "msg"),
+            (TokenKind::Attribute, "title"),
+            (TokenKind::NumberLiteral, "5"),
+            (TokenKind::Attribute, "attr"),
+            (TokenKind::TermId, "-brand"),
+            (TokenKind::NamedArgument, "case"),
+            (TokenKind::NumberLiteral, "1"),
+            (TokenKind::Junk, "junk junk\n"),
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "recover-placeables")]
+fn highlight_number_keys_and_junk_placeables() {
+    use fluent_syntax::parser::{parse_with_spans_and_options, ParserOptions};
+
+    let source = "key = { $n ->\n    [0] None\n   *[1.5] Some { $n }\n}\nother = A { $x ~ } B\n";
+    let spanned = match parse_with_spans_and_options(
+        source,
+        ParserOptions {
+            recover_placeables: true,
+            ..ParserOptions::default()
+        },
+    ) {
+        Ok(spanned) => spanned,
+        Err((spanned, _)) => spanned,
+    };
+    let tokens: Vec<_> = highlight(&spanned, source)
+        .into_iter()
+        .map(|token| (token.kind, &source[token.span]))
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            (TokenKind::MessageId, "key"),
+            (TokenKind::Variable, "$n"),
+            (TokenKind::SelectArrow, "->"),
+            (TokenKind::NumberLiteral, "0"),
+            (TokenKind::DefaultVariant, "*"),
+            (TokenKind::NumberLiteral, "1.5"),
+            (TokenKind::Variable, "$n"),
+            (TokenKind::MessageId, "other"),
+            (TokenKind::Junk, "{ $x ~ }"),
+        ]
+    );
+}
+
+#[test]
+fn highlight_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+        let mut end = 0;
+        for (kind, text) in tokens(&source) {
+            let start = text.as_ptr() as usize - source.as_ptr() as usize;
+            assert!(end <= start, "Tokens overlap in {:?}", path);
+            end = start + text.len();
+
+            let valid = match kind {
+                TokenKind::Comment => text.starts_with('#'),
+                TokenKind::TermId => text.starts_with('-'),
+                TokenKind::Variable => text.starts_with('$'),
+                TokenKind::StringLiteral => text.starts_with('"') && text.ends_with('"'),
+                TokenKind::SelectArrow => text == "->",
+                TokenKind::DefaultVariant => text == "*",
+                TokenKind::Junk => !text.is_empty(),
+                _ => !text.is_empty() && !text.contains(char::is_whitespace),
+            };
+            assert!(valid, "{:?} is not a valid {:?} in {:?}", text, kind, path);
+        }
+    }
+}
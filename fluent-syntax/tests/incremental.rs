@@ -0,0 +1,180 @@
+use std::fs;
+use std::ops::Range;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::ast::visit::{self, Visit};
+use fluent_syntax::parser::{
+    parse_with_spans, parse_with_spans_and_options, reparse, reparse_with_options, ParserError,
+    ParserOptions, SpannedNode, SpannedResource,
+};
+
+/// Collects the spans of all the nodes of a resource, in visiting order.
+struct Spans<'a, 's> {
+    spanned: &'a SpannedResource<&'s str>,
+    spans: Vec<Option<Range<usize>>>,
+}
+
+impl<'a, 's> Spans<'a, 's> {
+    fn collect(spanned: &'a SpannedResource<&'s str>) -> Vec<Option<Range<usize>>> {
+        let mut spans = Self {
+            spanned,
+            spans: vec![],
+        };
+        spans.visit_resource(spanned.resource());
+        spans.spans
+    }
+
+    fn push<N: SpannedNode>(&mut self, node: &N) {
+        self.spans.push(self.spanned.span(node));
+    }
+}
+
+impl<'a, 's: 'a> Visit<'a, &'s str> for Spans<'_, 's> {
+    fn visit_entry(&mut self, node: &'a ast::Entry<&'s str>) {
+        self.push(node);
+        visit::walk_entry(self, node);
+    }
+
+    fn visit_identifier(&mut self, node: &'a ast::Identifier<&'s str>) {
+        self.push(node);
+    }
+
+    fn visit_pattern(&mut self, node: &'a ast::Pattern<&'s str>) {
+        self.push(node);
+        for element in &node.elements {
+            self.push(element);
+        }
+        visit::walk_pattern(self, node);
+    }
+
+    fn visit_inline_expression(&mut self, node: &'a ast::InlineExpression<&'s str>) {
+        self.push(node);
+        visit::walk_inline_expression(self, node);
+    }
+
+    fn visit_variant(&mut self, node: &'a ast::Variant<&'s str>) {
+        self.push(node);
+        self.push(&node.key);
+        visit::walk_variant(self, node);
+    }
+
+    fn visit_named_argument(&mut self, node: &'a ast::NamedArgument<&'s str>) {
+        self.push(node);
+        visit::walk_named_argument(self, node);
+    }
+}
+
+type Parsed<'s> = (SpannedResource<&'s str>, Vec<ParserError>);
+
+fn unwrap<'s>(
+    result: Result<SpannedResource<&'s str>, (SpannedResource<&'s str>, Vec<ParserError>)>,
+) -> Parsed<'s> {
+    match result {
+        Ok(resource) => (resource, vec![]),
+        Err((resource, errors)) => (resource, errors),
+    }
+}
+
+/// Reparses `old` after replacing `edit` with `text`, and checks the result
+/// against parsing the new source from scratch.
+fn check_edit(old: &str, edit: Range<usize>, text: &str, options: ParserOptions) {
+    let new = [&old[..edit.start], text, &old[edit.end..]].concat();
+    let (previous, errors) = unwrap(parse_with_spans_and_options(old, options));
+    let (reparsed, reparsed_errors) = unwrap(reparse_with_options(
+        previous,
+        errors,
+        old,
+        edit.clone(),
+        new.as_str(),
+        options,
+    ));
+    let (expected, expected_errors) = unwrap(parse_with_spans_and_options(new.as_str(), options));
+
+    let context = format!("{:?} replaced with {:?} in {:?}", edit, text, old);
+    assert_eq!(reparsed.resource(), expected.resource(), "{}", context);
+    assert_eq!(
+        Spans::collect(&reparsed),
+        Spans::collect(&expected),
+        "{}",
+        context
+    );
+    assert_eq!(reparsed_errors, expected_errors, "{}", context);
+}
+
+fn char_boundaries(source: &str) -> impl Iterator<Item = usize> + '_ {
+    (0..=source.len()).filter(move |&i| source.is_char_boundary(i))
+}
+
+#[test]
+fn reparse_edits() {
+    let old = "# Comment\n\nhello = Hello, { $user }!\n    .title = Title\n\n-term = { $n ->\n    [one] One\n   *[other] Other\n}\n\nbye = Bye\n";
+    let texts = [
+        "",
+        "x",
+        " ",
+        "\n",
+        "\n\n",
+        "{",
+        "}",
+        "#",
+        "# ",
+        "-",
+        ".",
+        "*",
+        "[",
+        "=",
+        "key = Key\n",
+        "\n    ",
+        "\nkey",
+    ];
+    #[allow(unused_mut)]
+    let mut options = vec![ParserOptions::default()];
+    #[cfg(feature = "recover-placeables")]
+    options.push(ParserOptions {
+        recover_placeables: true,
+        ..ParserOptions::default()
+    });
+    for options in options {
+        for start in char_boundaries(old) {
+            for text in texts {
+                check_edit(old, start..start, text, options);
+            }
+            for end in char_boundaries(old).filter(|&end| end > start).take(12) {
+                check_edit(old, start..end, "", options);
+                check_edit(old, start..end, "x", options);
+            }
+        }
+    }
+}
+
+#[test]
+fn reparse_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+        let boundaries: Vec<usize> = char_boundaries(&source).collect();
+        for &start in boundaries.iter().step_by(11) {
+            for text in ["", "x", "\n", "{", "#"] {
+                check_edit(&source, start..start, text, ParserOptions::default());
+            }
+            let end = (start + 5).min(source.len());
+            if source.is_char_boundary(end) {
+                check_edit(&source, start..end, "", ParserOptions::default());
+            }
+        }
+    }
+}
+
+#[test]
+fn reparse_reuses_entries() {
+    let old = "one = One\ntwo = Two\nthree = Three\n";
+    let new = "one = One\ntwo = Two, 2\nthree = Three\n";
+    let (previous, errors) = unwrap(parse_with_spans(old));
+    let spanned = reparse(previous, errors, old, 19..19, new).expect("Failed to parse.");
+    let body = &spanned.resource().body;
+    assert_eq!(spanned.span(&body[0]), Some(0..9));
+    assert_eq!(spanned.span(&body[1]), Some(10..22));
+    assert_eq!(spanned.span(&body[2]), Some(23..36));
+}
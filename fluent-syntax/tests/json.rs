@@ -0,0 +1,89 @@
+use std::fs;
+
+use glob::glob;
+use serde_json::Value;
+
+use fluent_syntax::json::{to_json, to_json_with_spans};
+use fluent_syntax::parser::{parse, parse_with_spans};
+
+#[test]
+fn json_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+        let reference =
+            fs::read_to_string(path.with_extension("json")).expect("Failed to read the reference");
+        let reference: Value = serde_json::from_str(&reference).unwrap();
+
+        let resource = parse(source.as_str()).unwrap_or_else(|(resource, _)| resource);
+        assert_eq!(to_json(&resource), reference, "{}", path.display());
+    }
+}
+
+/// Checks that the spans of the leaves of `node` cover their text in `source`.
+fn check_spans(source: &str, node: &Value) {
+    match node {
+        Value::Object(object) => {
+            if let Some(span) = object.get("span") {
+                let start = span["start"].as_u64().unwrap() as usize;
+                let end = span["end"].as_u64().unwrap() as usize;
+                let text = &source[start..end];
+                match object["type"].as_str().unwrap() {
+                    "Identifier" => assert_eq!(Some(text), object["name"].as_str()),
+                    "NumberLiteral" => assert_eq!(Some(text), object["value"].as_str()),
+                    "StringLiteral" => {
+                        assert_eq!(text, format!("\"{}\"", object["value"].as_str().unwrap()))
+                    }
+                    "Junk" => assert_eq!(Some(text), object["content"].as_str()),
+                    "Placeable" => assert!(text.starts_with('{') && text.ends_with('}')),
+                    _ => {}
+                }
+            }
+            object.values().for_each(|value| check_spans(source, value));
+        }
+        Value::Array(values) => values.iter().for_each(|value| check_spans(source, value)),
+        _ => {}
+    }
+}
+
+#[test]
+fn json_fixtures_with_spans() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+
+        let spanned = parse_with_spans(source.as_str()).unwrap_or_else(|(spanned, _)| spanned);
+        let value = to_json_with_spans(&source, &spanned);
+        assert_eq!(value["span"]["start"].as_u64(), Some(0));
+        assert_eq!(value["span"]["end"].as_u64(), Some(source.len() as u64));
+        check_spans(&source, &value);
+
+        // Apart from the spans, the JSON is the same as without them.
+        let mut value = value;
+        strip_spans(&mut value);
+        assert_eq!(value, to_json(spanned.resource()), "{}", path.display());
+    }
+}
+
+fn strip_spans(node: &mut Value) {
+    match node {
+        Value::Object(object) => {
+            object.remove("span");
+            object.values_mut().for_each(strip_spans);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_spans),
+        _ => {}
+    }
+}
+
+#[test]
+fn json_merges_text_spans() {
+    let ftl = "key =\n    Line one\n    line two { $var }\n";
+    let spanned = parse_with_spans(ftl).expect("Failed to parse an FTL resource.");
+    let value = to_json_with_spans(ftl, &spanned);
+
+    let text = &value["body"][0]["value"]["elements"][0];
+    assert_eq!(text["value"].as_str(), Some("Line one\nline two "));
+    assert_eq!(text["span"]["start"].as_u64(), Some(10));
+    assert_eq!(text["span"]["end"].as_u64(), Some(32));
+}
@@ -0,0 +1,105 @@
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse, parse_with_options, ErrorKind, ParserOptions};
+
+fn errors(ftl: &str, options: ParserOptions) -> Vec<ErrorKind> {
+    match parse_with_options(ftl, options) {
+        Ok(_) => vec![],
+        Err((_, errors)) => errors.into_iter().map(|error| error.kind).collect(),
+    }
+}
+
+#[test]
+fn max_depth() {
+    let options = ParserOptions {
+        max_depth: Some(3),
+        ..ParserOptions::default()
+    };
+    assert_eq!(errors("key = { { { $x } } }\n", options), []);
+    assert_eq!(
+        errors("key = { { { { $x } } } }\n", options),
+        [ErrorKind::TooDeeplyNested { limit: 3 }]
+    );
+    assert_eq!(
+        errors("key = { $n ->\n   *[other] { FUN($x) }\n  }\n", options),
+        []
+    );
+    assert_eq!(
+        errors(
+            "key = { $n ->\n   *[other] { FUN(OTHER($x)) }\n  }\n",
+            options
+        ),
+        [ErrorKind::TooDeeplyNested { limit: 3 }]
+    );
+
+    // The next entries are parsed as usual.
+    let ftl = "key = { { { { $x } } } }\nnext = { { $y } }\n";
+    let (resource, _) = parse_with_options(ftl, options).unwrap_err();
+    assert!(matches!(resource.body[0], ast::Entry::Junk { .. }));
+    assert!(matches!(resource.body[1], ast::Entry::Message(_)));
+}
+
+#[test]
+fn max_depth_does_not_overflow_the_stack() {
+    let depth = 100_000;
+    let ftl = format!("key = {}$x{}\n", "{".repeat(depth), "}".repeat(depth));
+    let options = ParserOptions {
+        max_depth: Some(64),
+        ..ParserOptions::default()
+    };
+    assert_eq!(
+        errors(&ftl, options),
+        [ErrorKind::TooDeeplyNested { limit: 64 }]
+    );
+
+    let ftl = format!(
+        "key = {{ {}$x{} }}\n",
+        "FUN(".repeat(depth),
+        ")".repeat(depth)
+    );
+    assert_eq!(
+        errors(&ftl, options),
+        [ErrorKind::TooDeeplyNested { limit: 64 }]
+    );
+}
+
+#[test]
+fn max_entry_length() {
+    let options = ParserOptions {
+        max_entry_length: Some(20),
+        ..ParserOptions::default()
+    };
+    let ftl = "short = Short\nlong = A longer value\nnext = Next\n";
+    let (resource, errors) = parse_with_options(ftl, options).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::EntryTooLong { limit: 20 });
+    assert_eq!(errors[0].pos, 34..35);
+    assert_eq!(errors[0].slice, Some(14..36));
+    assert!(matches!(resource.body[0], ast::Entry::Message(_)));
+    assert_eq!(
+        resource.body[1],
+        ast::Entry::Junk {
+            content: "long = A longer value\n"
+        }
+    );
+    assert!(matches!(resource.body[2], ast::Entry::Message(_)));
+}
+
+#[test]
+fn max_variants() {
+    let options = ParserOptions {
+        max_variants: Some(2),
+        ..ParserOptions::default()
+    };
+    let two = "key = { $n ->\n    [one] One\n   *[other] Other\n}\n";
+    assert_eq!(errors(two, options), []);
+    let three = "key = { $n ->\n    [one] One\n    [two] Two\n   *[other] Other\n}\n";
+    assert_eq!(
+        errors(three, options),
+        [ErrorKind::TooManyVariants { limit: 2 }]
+    );
+    assert_eq!(
+        parse_with_options(three, options).unwrap_err().1[0].pos,
+        45..46
+    );
+    assert!(parse(three).is_ok());
+}
@@ -0,0 +1,231 @@
+use fluent_syntax::ast;
+use fluent_syntax::lint::{lint, LintDiagnostic, LintKind, VariableType};
+use fluent_syntax::parser;
+
+fn lint_ftl(ftl: &str) -> Vec<LintDiagnostic> {
+    let resource = parser::parse(ftl).expect("Failed to parse.");
+    lint(&resource)
+}
+
+#[test]
+fn clean_resource() {
+    let ftl = r#"
+-brand = Firefox
+    .gender = masculine
+
+hello = Hello from { -brand }
+emails = { $count ->
+        [one] One email
+       *[other] { NUMBER($count) } emails
+    }
+"#;
+    assert_eq!(lint_ftl(ftl), vec![]);
+}
+
+#[test]
+fn duplicates() {
+    let ftl = "key = One\n-term = Term\nkey = Two\n-term = Other\nother = { -term }\nkey = Three\n";
+    assert_eq!(
+        lint_ftl(ftl),
+        vec![
+            LintDiagnostic {
+                entry: 2,
+                kind: LintKind::DuplicateMessage {
+                    id: "key".to_string()
+                },
+            },
+            LintDiagnostic {
+                entry: 3,
+                kind: LintKind::DuplicateTerm {
+                    id: "term".to_string()
+                },
+            },
+            LintDiagnostic {
+                entry: 5,
+                kind: LintKind::DuplicateMessage {
+                    id: "key".to_string()
+                },
+            },
+        ]
+    );
+    // A message and a term may share a name.
+    assert_eq!(lint_ftl("key = { -key }\n-key = Key\n"), vec![]);
+}
+
+#[test]
+fn unused_terms() {
+    let ftl = r#"
+-used = Used
+-unused = { -used }
+-self = { -self }
+    .attr = Attribute
+key = { -used }
+"#;
+    let diagnostics = lint_ftl(ftl);
+    assert_eq!(
+        diagnostics[..2],
+        [
+            LintDiagnostic {
+                entry: 1,
+                kind: LintKind::UnusedTerm {
+                    id: "unused".to_string()
+                },
+            },
+            LintDiagnostic {
+                entry: 2,
+                kind: LintKind::UnusedTerm {
+                    id: "self".to_string()
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn cyclic_terms() {
+    let ftl = r#"
+-a = { -b }
+-b = { -c } { -d }
+-c = { -a }
+-d = { -d }
+-e = { -a }
+key = { -a } { -e }
+"#;
+    assert_eq!(
+        lint_ftl(ftl),
+        vec![
+            LintDiagnostic {
+                entry: 0,
+                kind: LintKind::CyclicTerm {
+                    cycle: vec!["a".to_string(), "b".to_string(), "c".to_string()]
+                },
+            },
+            LintDiagnostic {
+                entry: 3,
+                kind: LintKind::CyclicTerm {
+                    cycle: vec!["d".to_string()]
+                },
+            },
+        ]
+    );
+    assert_eq!(
+        lint_ftl(ftl)[0].to_string(),
+        "The terms reference each other in a cycle: \"-a\" -> \"-b\" -> \"-c\" -> \"-a\""
+    );
+}
+
+#[test]
+fn missing_default_variant() {
+    let variant = |name, default| ast::Variant {
+        key: ast::VariantKey::Identifier { name },
+        value: ast::Pattern {
+            elements: vec![ast::PatternElement::TextElement { value: name }],
+        },
+        default,
+    };
+    let select = |default| ast::Pattern {
+        elements: vec![ast::PatternElement::Placeable {
+            expression: ast::Expression::Select {
+                selector: ast::InlineExpression::VariableReference {
+                    id: ast::Identifier { name: "n" },
+                },
+                variants: vec![variant("one", false), variant("other", default)],
+            },
+        }],
+    };
+    let resource = ast::Resource {
+        body: vec![
+            ast::Entry::Message(ast::Message {
+                id: ast::Identifier { name: "valid" },
+                value: Some(select(true)),
+                attributes: vec![],
+                comment: None,
+            }),
+            ast::Entry::Message(ast::Message {
+                id: ast::Identifier { name: "invalid" },
+                value: None,
+                attributes: vec![ast::Attribute {
+                    id: ast::Identifier { name: "title" },
+                    value: select(false),
+                }],
+                comment: None,
+            }),
+        ],
+    };
+    assert_eq!(
+        lint(&resource),
+        vec![LintDiagnostic {
+            entry: 1,
+            kind: LintKind::MissingDefaultVariant,
+        }]
+    );
+}
+
+#[test]
+fn inconsistent_variable_types() {
+    let ftl = r#"
+key = { $gender ->
+        [male] { NUMBER($n) } { DATETIME($date) }
+       *[other] { DATETIME($n) } { DATETIME($gender) }
+    }
+    .title = { $date ->
+        [1] { NUMBER($n) }
+       *[other] Other
+    }
+other = { DATETIME($n) } { $n ->
+        [few] Few
+       *[other] Other
+    }
+"#;
+    assert_eq!(
+        lint_ftl(ftl),
+        vec![
+            LintDiagnostic {
+                entry: 0,
+                kind: LintKind::InconsistentVariableType {
+                    name: "n".to_string(),
+                    first: VariableType::Number,
+                    second: VariableType::DateTime,
+                },
+            },
+            LintDiagnostic {
+                entry: 0,
+                kind: LintKind::InconsistentVariableType {
+                    name: "gender".to_string(),
+                    first: VariableType::String,
+                    second: VariableType::DateTime,
+                },
+            },
+            LintDiagnostic {
+                entry: 0,
+                kind: LintKind::InconsistentVariableType {
+                    name: "date".to_string(),
+                    first: VariableType::DateTime,
+                    second: VariableType::Number,
+                },
+            },
+            LintDiagnostic {
+                entry: 1,
+                kind: LintKind::InconsistentVariableType {
+                    name: "n".to_string(),
+                    first: VariableType::DateTime,
+                    second: VariableType::Number,
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn ambiguous_selectors_have_no_type() {
+    let ftl = r#"
+key = { DATETIME($a) } { $a ->
+       *[other] Other
+    } { DATETIME($b) } { $b ->
+        [one] One
+        [male] Male
+       *[other] Other
+    }
+"#;
+    assert_eq!(lint_ftl(ftl), vec![]);
+}
@@ -0,0 +1,99 @@
+use fluent_syntax::ast;
+use fluent_syntax::literal::{number_value, string_value, LiteralValue, NumberValue};
+use fluent_syntax::parser;
+
+fn number(value: f64, fraction_digits: usize) -> Option<NumberValue> {
+    Some(NumberValue {
+        value,
+        fraction_digits,
+    })
+}
+
+#[test]
+fn number_values() {
+    assert_eq!(number_value("0"), number(0.0, 0));
+    assert_eq!(number_value("-0"), number(-0.0, 0));
+    assert_eq!(number_value("42"), number(42.0, 0));
+    assert_eq!(number_value("-2.75"), number(-2.75, 2));
+    assert_eq!(number_value("1.000"), number(1.0, 3));
+    assert_eq!(number_value("0012.50"), number(12.5, 2));
+
+    for invalid in [
+        "", "-", "1.", ".5", "+1", "1e3", "1_000", "0x10", " 1", "NaN",
+    ] {
+        assert_eq!(number_value(invalid), None, "{:?}", invalid);
+    }
+}
+
+#[test]
+fn string_values() {
+    assert_eq!(string_value("plain"), "plain");
+    assert_eq!(string_value("\\\"quoted\\\""), "\"quoted\"");
+    assert_eq!(string_value("back\\\\slash"), "back\\slash");
+    assert_eq!(string_value("\\u00A0\\U01F602"), "\u{A0}\u{1F602}");
+    assert_eq!(string_value("\\UFFFFFF"), "\u{FFFD}");
+}
+
+fn inline<'a>(expression: &'a ast::Expression<&str>) -> Option<LiteralValue<'a>> {
+    match expression {
+        ast::Expression::Inline(inline) => inline.literal_value(),
+        ast::Expression::Select { .. } => None,
+    }
+}
+
+#[test]
+fn literal_values_of_parsed_resource() {
+    let ftl = r#"
+key = { "Café" } { -5.0 } { $n ->
+        [0.5] Half
+       *[other] Other
+    } { NUMBER(1) }
+"#;
+    let resource = parser::parse(ftl).expect("Failed to parse.");
+    let pattern = match &resource.body[0] {
+        ast::Entry::Message(ast::Message {
+            value: Some(pattern),
+            ..
+        }) => pattern,
+        entry => panic!("Expected a message, got {:?}", entry),
+    };
+    let expressions: Vec<_> = pattern
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            ast::PatternElement::Placeable { expression } => Some(expression),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        inline(expressions[0]),
+        Some(LiteralValue::String("Café".into()))
+    );
+    assert_eq!(
+        inline(expressions[1]),
+        Some(LiteralValue::Number(NumberValue {
+            value: -5.0,
+            fraction_digits: 1,
+        }))
+    );
+    assert_eq!(inline(expressions[3]), None);
+
+    match expressions[2] {
+        ast::Expression::Select { variants, .. } => {
+            assert_eq!(variants[0].key.number_value(), number(0.5, 1));
+            assert_eq!(variants[1].key.number_value(), None);
+        }
+        expression => panic!("Expected a select expression, got {:?}", expression),
+    }
+}
+
+#[test]
+fn invalid_number_literal_has_no_value() {
+    let expression = ast::InlineExpression::NumberLiteral { value: "1e3" };
+    assert_eq!(expression.literal_value(), None);
+    let key = ast::VariantKey::NumberLiteral {
+        value: String::from("1."),
+    };
+    assert_eq!(key.number_value(), None);
+}
@@ -0,0 +1,173 @@
+use std::fs;
+use std::ops::Range;
+
+use glob::glob;
+
+use fluent_syntax::parser::{parse_with_spans, Node, SpannedResource};
+
+fn span<'s>(spanned: &SpannedResource<&'s str>, node: Node<&'s str>) -> Option<Range<usize>> {
+    match node {
+        Node::Entry(node) => spanned.span(node),
+        Node::Comment(node) => spanned.span(node),
+        Node::Attribute(node) => spanned.span(node),
+        Node::Identifier(node) => spanned.span(node),
+        Node::Pattern(node) => spanned.span(node),
+        Node::PatternElement(node) => spanned.span(node),
+        Node::Expression(node) => spanned.span(node),
+        Node::InlineExpression(node) => spanned.span(node),
+        Node::Variant(node) => spanned.span(node),
+        Node::VariantKey(node) => spanned.span(node),
+        Node::CallArguments(node) => spanned.span(node),
+        Node::NamedArgument(node) => spanned.span(node),
+    }
+}
+
+fn kind<S>(node: Node<S>) -> &'static str {
+    match node {
+        Node::Entry(_) => "Entry",
+        Node::Comment(_) => "Comment",
+        Node::Attribute(_) => "Attribute",
+        Node::Identifier(_) => "Identifier",
+        Node::Pattern(_) => "Pattern",
+        Node::PatternElement(_) => "PatternElement",
+        Node::Expression(_) => "Expression",
+        Node::InlineExpression(_) => "InlineExpression",
+        Node::Variant(_) => "Variant",
+        Node::VariantKey(_) => "VariantKey",
+        Node::CallArguments(_) => "CallArguments",
+        Node::NamedArgument(_) => "NamedArgument",
+    }
+}
+
+/// Returns the kinds and the source text of the nodes at `offset`.
+fn path_at<'s>(
+    source: &'s str,
+    spanned: &SpannedResource<&'s str>,
+    offset: usize,
+) -> Vec<(&'static str, &'s str)> {
+    spanned
+        .path_at(offset)
+        .into_iter()
+        .map(|node| {
+            let span = span(spanned, node).expect("Node has a span");
+            (kind(node), &source[span])
+        })
+        .collect()
+}
+
+const SOURCE: &str = "\
+## Group
+
+# Comment
+key = Hello { $n ->
+    [one] { -brand(case: \"short\") }
+   *[other] { NUMBER($n) } items
+}
+    .attr = { msg.title }
+";
+
+#[test]
+fn path_in_variant() {
+    let spanned = parse_with_spans(SOURCE).expect("Failed to parse.");
+    let offset = SOURCE.find("short").unwrap();
+    assert_eq!(
+        path_at(SOURCE, &spanned, offset),
+        [
+            ("Entry", &SOURCE[10..SOURCE.len() - 1]),
+            ("Pattern", "Hello { $n ->\n    [one] { -brand(case: \"short\") }\n   *[other] { NUMBER($n) } items\n}"),
+            ("PatternElement", "{ $n ->\n    [one] { -brand(case: \"short\") }\n   *[other] { NUMBER($n) } items\n}"),
+            ("Expression", "$n ->\n    [one] { -brand(case: \"short\") }\n   *[other] { NUMBER($n) } items"),
+            ("Variant", "[one] { -brand(case: \"short\") }"),
+            ("Pattern", "{ -brand(case: \"short\") }"),
+            ("PatternElement", "{ -brand(case: \"short\") }"),
+            ("Expression", "-brand(case: \"short\")"),
+            ("InlineExpression", "-brand(case: \"short\")"),
+            ("CallArguments", "(case: \"short\")"),
+            ("NamedArgument", "case: \"short\""),
+            ("InlineExpression", "\"short\""),
+        ]
+    );
+}
+
+#[test]
+fn path_at_identifiers() {
+    let spanned = parse_with_spans(SOURCE).expect("Failed to parse.");
+    let last = |path: Vec<(&'static str, &'static str)>| path.last().copied();
+
+    // Both at the start and at the end of an identifier.
+    let start = SOURCE.find("NUMBER").unwrap();
+    assert_eq!(
+        last(path_at(SOURCE, &spanned, start)),
+        Some(("Identifier", "NUMBER"))
+    );
+    let end = start + "NUMBER".len();
+    assert_eq!(
+        last(path_at(SOURCE, &spanned, end)),
+        Some(("CallArguments", "($n)"))
+    );
+    assert_eq!(
+        last(path_at(SOURCE, &spanned, end + 2)),
+        Some(("Identifier", "n"))
+    );
+
+    let title = SOURCE.find("title").unwrap();
+    let path = path_at(SOURCE, &spanned, title + 2);
+    assert_eq!(path[1], ("Attribute", ".attr = { msg.title }"));
+    assert_eq!(path.last().copied(), Some(("Identifier", "title")));
+
+    let key = SOURCE.find("key").unwrap();
+    assert_eq!(
+        path_at(SOURCE, &spanned, key + 3)[1..],
+        [("Identifier", "key")]
+    );
+    assert_eq!(
+        path_at(SOURCE, &spanned, SOURCE.find("[other]").unwrap() + 3)[4..],
+        [
+            ("Variant", "*[other] { NUMBER($n) } items"),
+            ("VariantKey", "other")
+        ]
+    );
+}
+
+#[test]
+fn path_in_comments_and_blank_lines() {
+    let spanned = parse_with_spans(SOURCE).expect("Failed to parse.");
+    assert_eq!(path_at(SOURCE, &spanned, 3), [("Entry", "## Group")]);
+    assert_eq!(path_at(SOURCE, &spanned, 8), [("Entry", "## Group")]);
+    assert_eq!(path_at(SOURCE, &spanned, 9), []);
+    assert_eq!(
+        path_at(SOURCE, &spanned, 12),
+        [
+            ("Entry", &SOURCE[10..SOURCE.len() - 1]),
+            ("Comment", "# Comment")
+        ]
+    );
+    assert_eq!(path_at(SOURCE, &spanned, SOURCE.len()), []);
+}
+
+#[test]
+fn paths_of_fixtures_nest() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+        let spanned = match parse_with_spans(source.as_str()) {
+            Ok(spanned) => spanned,
+            Err((spanned, _)) => spanned,
+        };
+        for offset in 0..=source.len() {
+            let mut outer = 0..source.len();
+            for node in spanned.path_at(offset) {
+                let span = span(&spanned, node).expect("Node has a span");
+                assert!(
+                    span.start <= offset && offset <= span.end,
+                    "{:?} does not contain {} in {:?}",
+                    span,
+                    offset,
+                    path
+                );
+                assert!(outer.start <= span.start && span.end <= outer.end);
+                outer = span;
+            }
+        }
+    }
+}
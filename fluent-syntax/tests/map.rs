@@ -0,0 +1,98 @@
+use std::fs;
+use std::sync::Arc;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser;
+
+#[test]
+fn into_owned_matches_owned_parse() {
+    for path in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = path.expect("Failed to read path");
+        let source = fs::read_to_string(&path).unwrap();
+        let borrowed = match parser::parse(source.as_str()) {
+            Ok(resource) | Err((resource, _)) => resource,
+        };
+        let owned = match parser::parse(source.clone()) {
+            Ok(resource) | Err((resource, _)) => resource,
+        };
+        assert_eq!(borrowed.into_owned(), owned, "{}", path.display());
+    }
+}
+
+#[test]
+fn map_source_order() {
+    let ftl = r#"
+# Comment
+-term = Term { $var }
+    .attr = { "literal" }
+key = { -term.attr(case: 1) ->
+        [one] One
+       *[other] { FUNC(key2) }
+    }
+"#;
+    let resource = parser::parse(ftl).expect("Failed to parse.");
+    let mut slices = vec![];
+    let mapped = resource.map_source(|slice| {
+        slices.push(slice);
+        slice.len()
+    });
+
+    assert_eq!(
+        slices,
+        vec![
+            "Comment", "term", "Term ", "var", "attr", "literal", "key", "term", "attr", "case",
+            "1", "one", "One", "other", "FUNC", "key2"
+        ]
+    );
+    assert_eq!(
+        mapped.body[0],
+        ast::Entry::Term(ast::Term {
+            id: ast::Identifier { name: 4 },
+            value: ast::Pattern {
+                elements: vec![
+                    ast::PatternElement::TextElement { value: 5 },
+                    ast::PatternElement::Placeable {
+                        expression: ast::Expression::Inline(
+                            ast::InlineExpression::VariableReference {
+                                id: ast::Identifier { name: 3 }
+                            }
+                        )
+                    },
+                ]
+            },
+            attributes: vec![ast::Attribute {
+                id: ast::Identifier { name: 4 },
+                value: ast::Pattern {
+                    elements: vec![ast::PatternElement::Placeable {
+                        expression: ast::Expression::Inline(ast::InlineExpression::StringLiteral {
+                            value: 7
+                        })
+                    }]
+                }
+            }],
+            comment: Some(ast::Comment { content: vec![7] }),
+        })
+    );
+}
+
+#[test]
+fn map_source_nodes() {
+    let resource = parser::parse("hello = Hello, { $user }!\n").expect("Failed to parse.");
+    let shared: ast::Resource<Arc<str>> = resource.clone().map_source(Arc::from);
+    assert_eq!(shared.into_owned(), resource.into_owned());
+
+    let id = ast::Identifier { name: "hello" };
+    assert_eq!(
+        id.into_owned(),
+        ast::Identifier {
+            name: "hello".to_string()
+        }
+    );
+    let key: ast::VariantKey<&str> = ast::VariantKey::NumberLiteral { value: "1" };
+    assert_eq!(
+        key.map_source(str::len),
+        ast::VariantKey::NumberLiteral { value: 1 }
+    );
+}
@@ -0,0 +1,108 @@
+use fluent_syntax::ast::merge::{MergeError, MergePolicy};
+use fluent_syntax::{parser, serializer};
+
+fn merge(base: &str, overlay: &str, policy: MergePolicy) -> Result<String, MergeError> {
+    let base = parser::parse(base).expect("Failed to parse the base.");
+    let overlay = parser::parse(overlay).expect("Failed to parse the overlay.");
+    base.merge(overlay, policy)
+        .map(|merged| serializer::serialize(&merged))
+}
+
+const BASE: &str = "\
+### Base resource.
+
+# The name of the product.
+-brand = Firefox
+hello = Hello from { -brand }!
+# Shown on exit.
+bye = Bye!
+";
+
+const OVERLAY: &str = "\
+### Base resource.
+
+### Brand overrides.
+
+-brand = Nightly
+    .gender = feminine
+
+# Describes bye.
+
+# A new comment.
+bye = See you!
+
+## New messages
+
+welcome = Welcome!
+-brand = Aurora
+";
+
+#[test]
+fn overlay_wins() {
+    assert_eq!(
+        merge(BASE, OVERLAY, MergePolicy::OverlayWins).unwrap(),
+        "\
+### Base resource.
+
+
+### Brand overrides.
+
+# The name of the product.
+-brand = Aurora
+hello = Hello from { -brand }!
+# A new comment.
+bye = See you!
+
+## New messages
+
+welcome = Welcome!
+"
+    );
+}
+
+#[test]
+fn base_wins() {
+    assert_eq!(
+        merge(BASE, OVERLAY, MergePolicy::BaseWins).unwrap(),
+        "\
+### Base resource.
+
+
+### Brand overrides.
+
+# The name of the product.
+-brand = Firefox
+hello = Hello from { -brand }!
+# Shown on exit.
+bye = Bye!
+
+## New messages
+
+welcome = Welcome!
+"
+    );
+}
+
+#[test]
+fn error_on_conflict() {
+    assert_eq!(
+        merge(BASE, OVERLAY, MergePolicy::Error),
+        Err(MergeError::TermConflict("brand".to_string()))
+    );
+    assert_eq!(
+        merge(BASE, "bye = Later\n", MergePolicy::Error),
+        Err(MergeError::MessageConflict("bye".to_string()))
+    );
+    assert_eq!(
+        merge(BASE, "\n# New.\n\nnew = New\n", MergePolicy::Error).unwrap(),
+        format!("{}\n# New.\n\nnew = New\n", BASE)
+    );
+}
+
+#[test]
+fn messages_and_terms_do_not_conflict() {
+    assert_eq!(
+        merge("brand = Brand\n", "-brand = Firefox\n", MergePolicy::Error).unwrap(),
+        "brand = Brand\n-brand = Firefox\n"
+    );
+}
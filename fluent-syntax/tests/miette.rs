@@ -0,0 +1,42 @@
+use miette::Diagnostic;
+
+use fluent_syntax::parser::parse;
+
+#[test]
+fn miette_diagnostic() {
+    let ftl = "key = { -term.attr }\n";
+    let (_, errors) = parse(ftl).expect_err("Resource should contain errors.");
+    let error = &errors[0];
+
+    assert_eq!(
+        error.code().map(|code| code.to_string()).as_deref(),
+        Some("E0019")
+    );
+    assert_eq!(
+        error.help().map(|help| help.to_string()).as_deref(),
+        Some("attributes of terms are only for selectors")
+    );
+
+    let diagnostic = error.diagnostic();
+    let labels: Vec<_> = error
+        .labels()
+        .expect("Error should have labels.")
+        .map(|label| {
+            let span = label.offset()..label.offset() + label.len();
+            (label.primary(), span, label.label().map(str::to_string))
+        })
+        .collect();
+    let mut expected = vec![(
+        true,
+        diagnostic.primary.span,
+        Some(diagnostic.primary.message),
+    )];
+    expected.extend(
+        diagnostic
+            .secondary
+            .into_iter()
+            .map(|label| (false, label.span, Some(label.message))),
+    );
+    assert_eq!(labels, expected);
+    assert_eq!(labels[0].2.as_deref(), Some("term attribute"));
+}
@@ -0,0 +1,30 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::parser;
+
+#[test]
+fn parse_all_matches_parse() {
+    let sources: Vec<String> = glob("./tests/fixtures/*.ftl")
+        .expect("Failed to read glob pattern")
+        .map(|path| fs::read_to_string(path.expect("Failed to read path")).unwrap())
+        .collect();
+    assert!(!sources.is_empty());
+
+    let inputs: Vec<&str> = sources.iter().map(String::as_str).collect();
+    let expected: Vec<_> = inputs.iter().map(|&input| parser::parse(input)).collect();
+    assert_eq!(parser::parse_all(inputs), expected);
+}
+
+#[test]
+fn parse_all_owned() {
+    let sources = vec!["hello = Hello!".to_string(), "goodbye = { $".to_string()];
+    let resources = parser::parse_all(sources);
+
+    assert_eq!(resources.len(), 2);
+    assert!(resources[0].is_ok());
+    let (resource, errors) = resources[1].as_ref().unwrap_err();
+    assert_eq!(resource.body.len(), 1);
+    assert_eq!(errors.len(), 1);
+}
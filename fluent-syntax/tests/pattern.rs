@@ -0,0 +1,53 @@
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse_pattern, ErrorKind};
+
+fn text(value: &str) -> ast::PatternElement<&str> {
+    ast::PatternElement::TextElement { value }
+}
+
+#[test]
+fn pattern_text_is_kept_as_is() {
+    let source = "\n  Hello,\n    .title = world\n[one] and\n*[other]\n  ";
+    let pattern = parse_pattern(source).expect("Failed to parse a pattern.");
+    assert_eq!(pattern.elements, [text(source)]);
+
+    let pattern = parse_pattern("").expect("Failed to parse a pattern.");
+    assert_eq!(pattern.elements, []);
+}
+
+#[test]
+fn pattern_placeables() {
+    let source = "{ hello } from { -brand }\n{ $count ->\n    [one] one report\n   *[other] { $count } reports\n}\n.";
+    let pattern = parse_pattern(source).expect("Failed to parse a pattern.");
+    assert_eq!(pattern.elements.len(), 6);
+    assert!(matches!(
+        pattern.elements[0],
+        ast::PatternElement::Placeable {
+            expression: ast::Expression::Inline(ast::InlineExpression::MessageReference { .. })
+        }
+    ));
+    assert_eq!(pattern.elements[1], text(" from "));
+    assert_eq!(pattern.elements[3], text("\n"));
+    assert!(matches!(
+        pattern.elements[4],
+        ast::PatternElement::Placeable {
+            expression: ast::Expression::Select { .. }
+        }
+    ));
+    assert_eq!(pattern.elements[5], text("\n."));
+}
+
+#[test]
+fn pattern_errors_point_into_the_source() {
+    let err = parse_pattern("Hello\n}").expect_err("Pattern should be invalid.");
+    assert_eq!(err.kind, ErrorKind::UnbalancedClosingBrace);
+    assert_eq!(err.pos, 6..7);
+    assert_eq!(err.slice, Some(0..7));
+
+    let err = parse_pattern("Hi { $user! }").expect_err("Pattern should be invalid.");
+    assert_eq!(err.kind, ErrorKind::ExpectedToken('}'));
+    assert_eq!(err.pos, 10..11);
+
+    let err = parse_pattern("Hi { $user").expect_err("Pattern should be invalid.");
+    assert_eq!(err.pos.start, 10);
+}
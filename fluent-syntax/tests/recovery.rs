@@ -0,0 +1,136 @@
+use fluent_syntax::ast;
+use fluent_syntax::parser::{
+    parse, parse_with_options, parse_with_spans_and_options, ErrorKind, ParserOptions,
+};
+use fluent_syntax::serializer::serialize;
+
+const OPTIONS: ParserOptions = ParserOptions {
+    recover_placeables: true,
+    #[cfg(feature = "unknown-expressions")]
+    unknown_expressions: false,
+    max_depth: None,
+    max_entry_length: None,
+    max_variants: None,
+};
+
+fn message_value<'a, 's>(entry: &'a ast::Entry<&'s str>) -> &'a [ast::PatternElement<&'s str>] {
+    match entry {
+        ast::Entry::Message(ast::Message {
+            value: Some(value), ..
+        }) => &value.elements,
+        _ => panic!("Expected a message with a value, got {:?}", entry),
+    }
+}
+
+#[test]
+fn recover_invalid_placeable() {
+    let ftl = "key1 = Hello { $user! } and { $other }.\nkey2 = Value 2\n";
+
+    let (resource, errors) = parse(ftl).expect_err("Resource should contain errors.");
+    assert!(matches!(resource.body[0], ast::Entry::Junk { .. }));
+
+    let (resource, errors_recovered) =
+        parse_with_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    assert_eq!(resource.body.len(), 2);
+    assert_eq!(
+        message_value(&resource.body[0]),
+        [
+            ast::PatternElement::TextElement { value: "Hello " },
+            ast::PatternElement::Junk {
+                content: "{ $user! }"
+            },
+            ast::PatternElement::TextElement { value: " and " },
+            ast::PatternElement::Placeable {
+                expression: ast::Expression::Inline(ast::InlineExpression::VariableReference {
+                    id: ast::Identifier { name: "other" }
+                })
+            },
+            ast::PatternElement::TextElement { value: "." },
+        ]
+    );
+
+    assert_eq!(errors_recovered.len(), 1);
+    assert_eq!(errors_recovered[0].kind, errors[0].kind);
+    assert_eq!(errors_recovered[0].pos, errors[0].pos);
+    assert_eq!(errors_recovered[0].slice, Some(13..23));
+}
+
+#[test]
+fn recover_nested_and_quoted_braces() {
+    let ftl = "key = A { FUN({ \"}\" }, x:) } B\n";
+    let (resource, errors) =
+        parse_with_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        message_value(&resource.body[0])[1],
+        ast::PatternElement::Junk {
+            content: "{ FUN({ \"}\" }, x:) }"
+        }
+    );
+    assert_eq!(
+        message_value(&resource.body[0])[2],
+        ast::PatternElement::TextElement { value: " B" }
+    );
+}
+
+#[test]
+fn recover_unclosed_placeable() {
+    let ftl = "key1 = Hello { $user\n    continued\nkey2 = Value 2\n";
+    let (resource, errors) =
+        parse_with_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::ExpectedToken('}'));
+    assert_eq!(errors[0].slice, Some(13..34));
+    assert_eq!(
+        message_value(&resource.body[0]),
+        [
+            ast::PatternElement::TextElement { value: "Hello " },
+            ast::PatternElement::Junk {
+                content: "{ $user\n    continued"
+            },
+        ]
+    );
+    assert_eq!(message_value(&resource.body[1]).len(), 1);
+}
+
+#[test]
+fn recover_inside_select() {
+    let ftl = "key = { $n ->\n    [one] One { -term.attr }\n   *[other] Many\n}\n";
+    let (resource, errors) =
+        parse_with_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::TermAttributeAsPlaceable);
+    assert!(matches!(resource.body[0], ast::Entry::Message(_)));
+    assert_eq!(
+        serialize(&resource),
+        "key =\n    { $n ->\n        [one] One { -term.attr }\n       *[other] Many\n    }\n"
+    );
+}
+
+#[test]
+fn errors_of_junk_entries_are_not_duplicated() {
+    let ftl = "key = { $user! } {\n";
+    let (resource, errors) =
+        parse_with_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    assert!(matches!(resource.body[0], ast::Entry::Message(_)));
+    assert_eq!(errors.len(), 2);
+
+    // The entry is junk anyway, so only its own error is reported.
+    let ftl = "key = { $user! } }\nnext = Next\n";
+    let (resource, errors) =
+        parse_with_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    assert!(matches!(resource.body[0], ast::Entry::Junk { .. }));
+    assert!(matches!(resource.body[1], ast::Entry::Message(_)));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::UnbalancedClosingBrace);
+}
+
+#[test]
+fn recovered_spans() {
+    let ftl = "key = A { $x! } B\n";
+    let (spanned, _) =
+        parse_with_spans_and_options(ftl, OPTIONS).expect_err("Resource should contain errors.");
+    let elements = message_value(&spanned.resource().body[0]);
+    assert_eq!(spanned.span(&elements[1]), Some(8..15));
+    assert_eq!(spanned.span(&elements[2]), Some(15..17));
+}
@@ -0,0 +1,34 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::parse;
+
+#[test]
+fn serde_roundtrip_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+
+        let resource = parse(source).unwrap_or_else(|(resource, _)| resource);
+        let json = serde_json::to_string(&resource).unwrap();
+        let deserialized: ast::Resource<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, resource, "{}", path.display());
+    }
+}
+
+#[test]
+fn serde_json_shape() {
+    let ftl = "# Comment\nkey = Value { $var ->\n   *[other] { -term }\n    }\n";
+    let resource = parse(ftl).expect("Failed to parse an FTL resource.");
+
+    let json = serde_json::to_string(&resource).unwrap();
+    let deserialized: ast::Resource<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, parse(ftl.to_string()).unwrap());
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["body"][0]["type"], "Message");
+    assert_eq!(value["body"][0]["id"]["name"], "key");
+    assert_eq!(value["body"][0]["comment"]["content"][0], "Comment");
+}
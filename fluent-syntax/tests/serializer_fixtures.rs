@@ -5,7 +5,9 @@ use std::fs;
 use std::path::Path;
 
 use fluent_syntax::parser::parse;
-use fluent_syntax::serializer::{serialize, serialize_with_options, Options};
+use fluent_syntax::serializer::{
+    serialize, serialize_with_options, MultilinePatterns, SerializerOptions,
+};
 
 /// List of files that currently do not roundtrip correctly.
 ///
@@ -58,9 +60,13 @@ fn roundtrip_unnormalized_fixtures() {
         let content = fs::read_to_string(&path).expect("Failed to read file");
         let parsed = parse(content.as_str()).unwrap_or_else(|(res, _)| res);
         let parsed_without_junk = clone_without_junk(&parsed);
-        let reserialized = serialize_with_options(&parsed, Options { with_junk: true });
+        let with_junk = SerializerOptions {
+            with_junk: true,
+            ..SerializerOptions::default()
+        };
+        let reserialized = serialize_with_options(&parsed, with_junk);
         let reserialized_without_junk =
-            serialize_with_options(&parsed, Options { with_junk: false });
+            serialize_with_options(&parsed, SerializerOptions::default());
         let reparsed = parse(reserialized.as_str()).unwrap_or_else(|(res, _)| res);
         let reparsed_without_junk =
             parse(reserialized_without_junk.as_str()).unwrap_or_else(|(res, _)| res);
@@ -69,3 +75,143 @@ fn roundtrip_unnormalized_fixtures() {
         assert_eq!(reparsed, parsed);
     }
 }
+
+/// Serializing with any options preserves the meaning of the resource.
+#[test]
+fn roundtrip_fixtures_with_options() {
+    let options = [
+        SerializerOptions {
+            indent_width: 2,
+            ..SerializerOptions::default()
+        },
+        SerializerOptions {
+            indent_width: 0,
+            blank_lines_between_entries: 2,
+            ..SerializerOptions::default()
+        },
+        SerializerOptions {
+            blank_lines_between_entries: 1,
+            multiline_patterns: MultilinePatterns::Inline,
+            ..SerializerOptions::default()
+        },
+    ];
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        if is_ignored(&path) {
+            continue;
+        }
+
+        let content = fs::read_to_string(&path).expect("Failed to read file");
+        let parsed = parse(content.as_str()).unwrap_or_else(|(res, _)| res);
+        let parsed_without_junk = clone_without_junk(&parsed);
+        for options in options {
+            let reserialized = serialize_with_options(&parsed, options);
+            let reparsed = parse(reserialized.as_str()).unwrap_or_else(|(res, _)| res);
+            assert_eq!(reparsed, parsed_without_junk, "{:?}", options);
+        }
+    }
+}
+
+#[test]
+fn serialize_with_layout_options() {
+    let ftl = "\
+# Free comment
+
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+    .title = Inbox
+-brand = Firefox
+";
+    let parsed = parse(ftl).expect("Failed to parse");
+
+    assert_eq!(
+        serialize(&parsed),
+        "\
+# Free comment
+
+emails =
+    { $count ->
+        [one] One email
+       *[other] { $count } emails
+    }
+    .title = Inbox
+-brand = Firefox
+"
+    );
+
+    let options = SerializerOptions {
+        indent_width: 2,
+        blank_lines_between_entries: 2,
+        multiline_patterns: MultilinePatterns::Inline,
+        ..SerializerOptions::default()
+    };
+    assert_eq!(
+        serialize_with_options(&parsed, options),
+        "\
+# Free comment
+
+
+emails = { $count ->
+  [one] One email
+ *[other] { $count } emails
+}
+  .title = Inbox
+
+
+-brand = Firefox
+"
+    );
+}
+
+#[test]
+fn serialize_sorted_entries() {
+    let ftl = "\
+### Resource comment.
+
+zebra = Zebra
+# Describes apple.
+apple = Apple
+-brand = Firefox
+
+# About mango.
+
+mango = Mango
+
+## Second group
+
+beta = Beta
+alpha = Alpha
+
+# Trailing comment.
+";
+    let parsed = parse(ftl).expect("Failed to parse");
+    let options = SerializerOptions {
+        sort_entries: true,
+        ..SerializerOptions::default()
+    };
+    assert_eq!(
+        serialize_with_options(&parsed, options),
+        "\
+### Resource comment.
+
+# Describes apple.
+apple = Apple
+-brand = Firefox
+
+# About mango.
+
+mango = Mango
+zebra = Zebra
+
+## Second group
+
+alpha = Alpha
+beta = Beta
+
+# Trailing comment.
+
+"
+    );
+}
@@ -0,0 +1,52 @@
+use fluent_syntax::parser::{self, SourceMap};
+
+#[test]
+fn line_col_of_offsets() {
+    let source = "one\r\ntwo ünï\n\nthree";
+    let source_map = SourceMap::new(source);
+    assert_eq!(source_map.line_count(), 4);
+
+    assert_eq!(source_map.line_col(0), (1, 1));
+    assert_eq!(source_map.line_col(3), (1, 4));
+    assert_eq!(source_map.line_col(4), (1, 5));
+    assert_eq!(source_map.line_col(5), (2, 1));
+    // Columns count characters, and offsets within one are those of it.
+    let n = source.find("nï").unwrap();
+    assert_eq!(source_map.line_col(n), (2, 6));
+    assert_eq!(source_map.line_col(n - 1), (2, 5));
+    assert_eq!(
+        source_map.line_col(source.find("\n\n").unwrap() + 1),
+        (3, 1)
+    );
+    assert_eq!(source_map.line_col(source.len()), (4, 6));
+    assert_eq!(source_map.line_col(source.len() + 10), (4, 6));
+}
+
+#[test]
+fn offset_of_line_col() {
+    let source = "one\r\ntwo ünï\n\nthree";
+    let source_map = SourceMap::new(source);
+    for offset in source.char_indices().map(|(pos, _)| pos) {
+        let (line, column) = source_map.line_col(offset);
+        assert_eq!(source_map.offset(line, column), Some(offset));
+    }
+    assert_eq!(
+        source_map.offset(3, 1),
+        Some(source.find("\n\n").unwrap() + 1)
+    );
+    assert_eq!(source_map.offset(3, 2), None);
+    assert_eq!(source_map.offset(4, 6), Some(source.len()));
+    assert_eq!(source_map.offset(5, 1), None);
+    assert_eq!(source_map.offset(0, 1), None);
+}
+
+#[test]
+fn display_errors_with_positions() {
+    let source = "key1 = Value 1\n\ng@Rb@ge = #2y ds\n";
+    let (_, errors) = parser::parse(source).expect_err("Resource should contain errors.");
+    let source_map = SourceMap::new(source);
+    assert_eq!(
+        errors[0].display_in(&source_map).to_string(),
+        "3:2: Expected a token starting with \"=\""
+    );
+}
@@ -0,0 +1,296 @@
+use std::fs;
+use std::ops::Range;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse, parse_with_spans, SpannedNode, SpannedResource};
+
+/// Checks that the spans of the nodes of a resource are consistent with the
+/// source: nodes lie within their parent, and leaves span their content.
+struct Checker<'a, 's> {
+    source: &'s str,
+    spanned: &'a SpannedResource<&'s str>,
+}
+
+impl<'a, 's> Checker<'a, 's> {
+    fn span<N: SpannedNode>(&self, node: &N, parent: &Range<usize>) -> Range<usize> {
+        let span = self.spanned.span(node).expect("Every node has a span");
+        assert!(
+            parent.start <= span.start && span.end <= parent.end,
+            "{:?} is not within {:?}",
+            span,
+            parent
+        );
+        span
+    }
+
+    fn text<N: SpannedNode>(&self, node: &N, parent: &Range<usize>) -> &'s str {
+        &self.source[self.span(node, parent)]
+    }
+
+    fn entry(&self, entry: &ast::Entry<&str>) {
+        let span = self.span(entry, &(0..self.source.len()));
+        match entry {
+            ast::Entry::Message(message) => {
+                assert_eq!(self.spanned.span(message), Some(span.clone()));
+                if let Some(comment) = &message.comment {
+                    self.comment(comment, &span);
+                }
+                assert_eq!(self.text(&message.id, &span), message.id.name);
+                if let Some(value) = &message.value {
+                    self.pattern(value, &span);
+                }
+                self.attributes(&message.attributes, &span);
+            }
+            ast::Entry::Term(term) => {
+                assert_eq!(self.spanned.span(term), Some(span.clone()));
+                if let Some(comment) = &term.comment {
+                    self.comment(comment, &span);
+                }
+                assert_eq!(self.text(&term.id, &span), term.id.name);
+                self.pattern(&term.value, &span);
+                self.attributes(&term.attributes, &span);
+            }
+            ast::Entry::Comment(comment)
+            | ast::Entry::GroupComment(comment)
+            | ast::Entry::ResourceComment(comment) => {
+                assert_eq!(self.spanned.span(comment), Some(span.clone()));
+                self.comment(comment, &span);
+            }
+            ast::Entry::Junk { content } => assert_eq!(&self.source[span], *content),
+        }
+    }
+
+    fn comment(&self, comment: &ast::Comment<&str>, parent: &Range<usize>) {
+        let text = self.text(comment, parent);
+        assert!(text.starts_with('#'));
+        assert!(text.ends_with(comment.content.last().copied().unwrap_or("#")));
+    }
+
+    fn attributes(&self, attributes: &[ast::Attribute<&str>], parent: &Range<usize>) {
+        for attribute in attributes {
+            let span = self.span(attribute, parent);
+            assert!(self.source[span.clone()].starts_with('.'));
+            assert_eq!(self.text(&attribute.id, &span), attribute.id.name);
+            self.pattern(&attribute.value, &span);
+        }
+    }
+
+    fn pattern(&self, pattern: &ast::Pattern<&str>, parent: &Range<usize>) {
+        let span = self.span(pattern, parent);
+        for element in &pattern.elements {
+            match element {
+                ast::PatternElement::TextElement { value } => {
+                    assert_eq!(self.text(element, &span), *value);
+                }
+                ast::PatternElement::Placeable { expression } => {
+                    let element_span = self.span(element, &span);
+                    let text = &self.source[element_span.clone()];
+                    assert!(text.starts_with('{') && text.ends_with('}'));
+                    self.expression(expression, &element_span);
+                }
+                #[cfg(feature = "recover-placeables")]
+                ast::PatternElement::Junk { content } => {
+                    assert_eq!(self.text(element, &span), *content);
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    fn expression(&self, expression: &ast::Expression<&str>, parent: &Range<usize>) {
+        let span = self.span(expression, parent);
+        match expression {
+            ast::Expression::Select { selector, variants } => {
+                self.inline_expression(selector, &span);
+                for variant in variants {
+                    let variant_span = self.span(variant, &span);
+                    let key = match variant.key {
+                        ast::VariantKey::Identifier { name } => name,
+                        ast::VariantKey::NumberLiteral { value } => value,
+                    };
+                    assert_eq!(self.text(&variant.key, &variant_span), key);
+                    self.pattern(&variant.value, &variant_span);
+                }
+            }
+            ast::Expression::Inline(expression) => {
+                assert_eq!(self.spanned.span(expression), Some(span.clone()));
+                self.inline_expression(expression, &span);
+            }
+        }
+    }
+
+    fn inline_expression(&self, expression: &ast::InlineExpression<&str>, parent: &Range<usize>) {
+        let span = self.span(expression, parent);
+        let text = &self.source[span.clone()];
+        match expression {
+            ast::InlineExpression::StringLiteral { value } => {
+                assert_eq!(text, format!("\"{}\"", value));
+            }
+            ast::InlineExpression::NumberLiteral { value } => assert_eq!(text, *value),
+            ast::InlineExpression::FunctionReference { id, arguments } => {
+                assert_eq!(self.text(id, &span), id.name);
+                self.call_arguments(arguments, &span);
+            }
+            ast::InlineExpression::MessageReference { id, attribute } => {
+                assert_eq!(self.text(id, &span), id.name);
+                if let Some(attribute) = attribute {
+                    assert_eq!(self.text(attribute, &span), attribute.name);
+                }
+            }
+            ast::InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                assert!(text.starts_with('-'));
+                assert_eq!(self.text(id, &span), id.name);
+                if let Some(attribute) = attribute {
+                    assert_eq!(self.text(attribute, &span), attribute.name);
+                }
+                if let Some(arguments) = arguments {
+                    self.call_arguments(arguments, &span);
+                }
+            }
+            ast::InlineExpression::VariableReference { id } => {
+                assert_eq!(text, format!("${}", id.name));
+            }
+            ast::InlineExpression::Placeable { expression } => {
+                assert!(text.starts_with('{') && text.ends_with('}'));
+                self.expression(expression, &span);
+            }
+            #[cfg(feature = "unknown-expressions")]
+            ast::InlineExpression::Unknown { content } => assert_eq!(text, *content),
+            _ => unreachable!(),
+        }
+    }
+
+    fn call_arguments(&self, arguments: &ast::CallArguments<&str>, parent: &Range<usize>) {
+        let span = self.span(arguments, parent);
+        let text = &self.source[span.clone()];
+        assert!(text.starts_with('(') && text.ends_with(')'));
+        for argument in &arguments.positional {
+            self.inline_expression(argument, &span);
+        }
+        for argument in &arguments.named {
+            let argument_span = self.span(argument, &span);
+            assert_eq!(
+                self.text(&argument.name, &argument_span),
+                argument.name.name
+            );
+            self.inline_expression(&argument.value, &argument_span);
+        }
+    }
+}
+
+fn check(source: &str) {
+    let spanned = match parse_with_spans(source) {
+        Ok(spanned) => spanned,
+        Err((spanned, _)) => spanned,
+    };
+    let resource = match parse(source) {
+        Ok(resource) => resource,
+        Err((resource, _)) => resource,
+    };
+    assert_eq!(spanned.resource(), &resource);
+
+    let checker = Checker {
+        source,
+        spanned: &spanned,
+    };
+    for entry in &spanned.resource().body {
+        checker.entry(entry);
+    }
+}
+
+#[test]
+fn spans_of_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        println!("Checking: {:?}", path);
+        let source = fs::read_to_string(path).expect("Failed to read the fixture");
+        check(&source);
+    }
+}
+
+#[test]
+fn spans_of_nodes() {
+    let source = "\
+# Comment
+key = Hello { $n ->
+    [one] { -brand(case: \"short\") }
+   *[other] { NUMBER($n, minimumFractionDigits: 2) } items
+}
+    .attr = { ICU.DATETIME($date) }
+
+junk junk
+";
+    let spanned = parse_with_spans(source)
+        .expect_err("Resource contains junk")
+        .0;
+    let text = |span: Option<Range<usize>>| &source[span.expect("Node has a span")];
+
+    let message = match &spanned.resource().body[0] {
+        ast::Entry::Message(message) => message,
+        _ => panic!("Expected a message"),
+    };
+    assert!(text(spanned.span(message)).starts_with("# Comment\nkey = "));
+    assert!(text(spanned.span(message)).ends_with("{ ICU.DATETIME($date) }"));
+    assert_eq!(
+        text(spanned.span(message.comment.as_ref().unwrap())),
+        "# Comment"
+    );
+
+    let elements = &message.value.as_ref().unwrap().elements;
+    assert_eq!(text(spanned.span(&elements[0])), "Hello ");
+    let expression = match &elements[1] {
+        ast::PatternElement::Placeable { expression } => expression,
+        _ => panic!("Expected a placeable"),
+    };
+    let variants = match expression {
+        ast::Expression::Select { variants, .. } => variants,
+        _ => panic!("Expected a select expression"),
+    };
+    assert!(text(spanned.span(expression)).starts_with("$n ->"));
+    assert_eq!(
+        text(spanned.span(&variants[0])),
+        "[one] { -brand(case: \"short\") }"
+    );
+    assert_eq!(
+        text(spanned.span(&variants[1])),
+        "*[other] { NUMBER($n, minimumFractionDigits: 2) } items"
+    );
+
+    let attribute = &message.attributes[0];
+    assert_eq!(
+        text(spanned.span(attribute)),
+        ".attr = { ICU.DATETIME($date) }"
+    );
+    match &attribute.value.elements[0] {
+        ast::PatternElement::Placeable {
+            expression:
+                ast::Expression::Inline(ast::InlineExpression::FunctionReference { id, arguments }),
+        } => {
+            assert_eq!(text(spanned.span(id)), "ICU.DATETIME");
+            assert_eq!(text(spanned.span(arguments)), "($date)");
+        }
+        _ => panic!("Expected a function reference"),
+    }
+
+    let junk = &spanned.resource().body[1];
+    assert_eq!(text(spanned.span(junk)), "junk junk\n");
+
+    // Nodes of another resource have no span.
+    let other = parse(source).expect_err("Resource contains junk").0;
+    assert_eq!(spanned.span(&other.body[0]), None);
+}
+
+#[test]
+fn spans_after_backtracking() {
+    check("key = Value\n    .attr = { $x\nother = Other\n");
+    check("key = Value\n    .valid = Valid\n    .invalid =\n");
+    check("# Comment\n\nkey = { $x ->\n  [one] One\n}\n");
+    check("# Comment\nkey = { -term.attr }\n-term = Term\n");
+    check("key = Value\r\n    .attr = Attribute\r\n");
+}
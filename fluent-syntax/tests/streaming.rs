@@ -0,0 +1,76 @@
+use std::fs;
+
+use glob::glob;
+
+use fluent_syntax::ast;
+use fluent_syntax::parser::{parse, parse_streaming};
+
+fn parse_streamed(
+    source: &str,
+) -> (
+    Vec<ast::Entry<&str>>,
+    Vec<fluent_syntax::parser::ParserError>,
+) {
+    let mut body = vec![];
+    let mut errors = vec![];
+    for entry in parse_streaming(source) {
+        match entry {
+            Ok(entry) => body.push(entry),
+            Err((entry, entry_errors)) => {
+                assert!(!entry_errors.is_empty());
+                body.push(entry);
+                errors.extend(entry_errors);
+            }
+        }
+    }
+    (body, errors)
+}
+
+#[test]
+fn streaming_fixtures() {
+    for entry in glob("./tests/fixtures/*.ftl").expect("Failed to read glob pattern") {
+        let path = entry.expect("Error while getting an entry");
+        let source = fs::read_to_string(&path).expect("Failed to read the fixture");
+
+        let (resource, errors) = match parse(source.as_str()) {
+            Ok(resource) => (resource, vec![]),
+            Err((resource, errors)) => (resource, errors),
+        };
+        let (body, streamed_errors) = parse_streamed(&source);
+        assert_eq!(body, resource.body, "{}", path.display());
+        assert_eq!(streamed_errors, errors, "{}", path.display());
+    }
+}
+
+#[test]
+fn streaming_comments() {
+    let ftl = "# Standalone\n\n\n# Attached\nkey = Value\n# Last\n";
+    let entries: Vec<_> = parse_streaming(ftl).map(Result::unwrap).collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(
+        entries[0],
+        ast::Entry::Comment(ast::Comment {
+            content: vec!["Standalone"]
+        })
+    );
+    match &entries[1] {
+        ast::Entry::Message(message) => {
+            assert_eq!(message.comment.as_ref().unwrap().content, ["Attached"]);
+        }
+        entry => panic!("Expected a message, got {:?}", entry),
+    }
+    assert_eq!(
+        entries[2],
+        ast::Entry::Comment(ast::Comment {
+            content: vec!["Last"]
+        })
+    );
+
+    let mut entries = parse_streaming("# Comment\n\n\n§ junk\n");
+    assert!(matches!(entries.next(), Some(Ok(ast::Entry::Comment(_)))));
+    assert!(matches!(
+        entries.next(),
+        Some(Err((ast::Entry::Junk { .. }, _)))
+    ));
+    assert!(entries.next().is_none());
+}
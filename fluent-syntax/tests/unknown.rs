@@ -0,0 +1,96 @@
+use fluent_syntax::ast;
+use fluent_syntax::parser::{
+    parse_with_options, parse_with_spans_and_options, ErrorKind, ParserOptions,
+};
+use fluent_syntax::serializer::serialize;
+
+const UNKNOWN: ParserOptions = ParserOptions {
+    #[cfg(feature = "recover-placeables")]
+    recover_placeables: false,
+    unknown_expressions: true,
+    max_depth: None,
+    max_entry_length: None,
+    max_variants: None,
+};
+
+fn message_value<'a, 's>(entry: &'a ast::Entry<&'s str>) -> &'a [ast::PatternElement<&'s str>] {
+    match entry {
+        ast::Entry::Message(ast::Message {
+            value: Some(value), ..
+        }) => &value.elements,
+        _ => panic!("Expected a message with a value, got {:?}", entry),
+    }
+}
+
+fn unknown(content: &str) -> ast::Expression<&str> {
+    ast::Expression::Inline(ast::InlineExpression::Unknown { content })
+}
+
+#[test]
+fn keep_unknown_expression() {
+    let ftl = "key = A { @future(x) } B
+";
+    let (resource, errors) =
+        parse_with_options(ftl, UNKNOWN).expect_err("Resource should contain errors.");
+    assert_eq!(
+        message_value(&resource.body[0]),
+        [
+            ast::PatternElement::TextElement { value: "A " },
+            ast::PatternElement::Placeable {
+                expression: unknown("@future(x)")
+            },
+            ast::PatternElement::TextElement { value: " B" },
+        ]
+    );
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ErrorKind::ExpectedInlineExpression);
+    assert_eq!(errors[0].slice, Some(8..22));
+    assert_eq!(serialize(&resource), ftl);
+}
+
+#[test]
+fn keep_unknown_expression_inside_select() {
+    let ftl = "key =\n    { $n ->\n        [one] One {{ \"}\" @x }}\n       *[other] Many\n    }\n";
+    let (resource, errors) =
+        parse_with_options(ftl, UNKNOWN).expect_err("Resource should contain errors.");
+    assert_eq!(errors.len(), 1);
+    let variants = match message_value(&resource.body[0]) {
+        [ast::PatternElement::Placeable {
+            expression: ast::Expression::Select { variants, .. },
+        }] => variants,
+        elements => panic!("Expected a select expression, got {:?}", elements),
+    };
+    assert_eq!(
+        variants[0].value.elements[1],
+        ast::PatternElement::Placeable {
+            expression: ast::Expression::Inline(ast::InlineExpression::Placeable {
+                expression: Box::new(unknown("\"}\" @x"))
+            })
+        }
+    );
+    assert_eq!(serialize(&resource), ftl);
+}
+
+#[test]
+fn empty_and_unclosed_placeables_are_not_unknown() {
+    for ftl in ["key = { }\n", "key = { @x\nnext = Next\n"] {
+        let (resource, _) =
+            parse_with_options(ftl, UNKNOWN).expect_err("Resource should contain errors.");
+        assert!(matches!(resource.body[0], ast::Entry::Junk { .. }));
+    }
+}
+
+#[test]
+fn unknown_expression_spans() {
+    let ftl = "key = A { @x } B\n";
+    let (spanned, _) =
+        parse_with_spans_and_options(ftl, UNKNOWN).expect_err("Resource should contain errors.");
+    let elements = message_value(&spanned.resource().body[0]);
+    let expression = match &elements[1] {
+        ast::PatternElement::Placeable { expression } => expression,
+        element => panic!("Expected a placeable, got {:?}", element),
+    };
+    assert_eq!(spanned.span(&elements[1]), Some(8..14));
+    assert_eq!(spanned.span(expression), Some(10..12));
+    assert_eq!(spanned.span(&elements[2]), Some(14..16));
+}
@@ -0,0 +1,162 @@
+use fluent_syntax::ast;
+use fluent_syntax::ast::visit::{self, Visit, VisitMut};
+use fluent_syntax::parser::parse;
+use fluent_syntax::serializer::serialize;
+
+/// Records the kind of every node it visits.
+#[derive(Default)]
+struct Recorder<'ast> {
+    nodes: Vec<String>,
+    identifiers: Vec<&'ast str>,
+}
+
+impl<'ast> Visit<'ast, &'ast str> for Recorder<'ast> {
+    fn visit_entry(&mut self, node: &'ast ast::Entry<&'ast str>) {
+        self.nodes.push("entry".to_string());
+        visit::walk_entry(self, node);
+    }
+
+    fn visit_comment(&mut self, node: &'ast ast::Comment<&'ast str>) {
+        self.nodes.push(format!("comment {:?}", node.content));
+    }
+
+    fn visit_junk(&mut self, content: &'ast &'ast str) {
+        self.nodes.push(format!("junk {:?}", content));
+    }
+
+    fn visit_identifier(&mut self, node: &'ast ast::Identifier<&'ast str>) {
+        self.identifiers.push(node.name);
+    }
+
+    fn visit_text_element(&mut self, value: &'ast &'ast str) {
+        self.nodes.push(format!("text {:?}", value));
+    }
+
+    fn visit_variant_key(&mut self, node: &'ast ast::VariantKey<&'ast str>) {
+        self.nodes.push(format!("key {:?}", node));
+    }
+
+    fn visit_inline_expression(&mut self, node: &'ast ast::InlineExpression<&'ast str>) {
+        match node {
+            ast::InlineExpression::StringLiteral { value } => {
+                self.nodes.push(format!("string {:?}", value));
+            }
+            ast::InlineExpression::NumberLiteral { value } => {
+                self.nodes.push(format!("number {:?}", value));
+            }
+            _ => {}
+        }
+        visit::walk_inline_expression(self, node);
+    }
+}
+
+#[test]
+fn visit_covers_all_nodes() {
+    let ftl = r#"# Attached comment
+key = Hello { -brand(case: "nominative") }, { NUMBER($count, minimumFractionDigits: 2) }!
+    .title = { other.attr }
+-brand = { $case ->
+    [genitive] Firefoxu
+   *[other] { "Firefox" }
+}
+
+## Group
+
+selector = { 1 ->
+    [1] One
+   *[other] { { $nested } }
+}
+err = {
+"#;
+    let resource = parse(ftl).unwrap_or_else(|(resource, _)| resource);
+
+    let mut recorder = Recorder::default();
+    recorder.visit_resource(&resource);
+
+    assert_eq!(
+        recorder.identifiers,
+        [
+            "key",
+            "brand",
+            "case",
+            "NUMBER",
+            "count",
+            "minimumFractionDigits",
+            "title",
+            "other",
+            "attr",
+            "brand",
+            "case",
+            "selector",
+            "nested",
+        ]
+    );
+    assert_eq!(
+        recorder.nodes,
+        [
+            "entry",
+            "comment [\"Attached comment\"]",
+            "text \"Hello \"",
+            "string \"nominative\"",
+            "text \", \"",
+            "number \"2\"",
+            "text \"!\"",
+            "entry",
+            "key Identifier { name: \"genitive\" }",
+            "text \"Firefoxu\"",
+            "key Identifier { name: \"other\" }",
+            "string \"Firefox\"",
+            "entry",
+            "comment [\"Group\"]",
+            "entry",
+            "number \"1\"",
+            "key NumberLiteral { value: \"1\" }",
+            "text \"One\"",
+            "key Identifier { name: \"other\" }",
+            "entry",
+            "junk \"err = {\\n\"",
+        ]
+    );
+}
+
+/// Prefixes the names of variables.
+struct PrefixVariables;
+
+impl VisitMut<String> for PrefixVariables {
+    fn visit_inline_expression_mut(&mut self, node: &mut ast::InlineExpression<String>) {
+        if let ast::InlineExpression::VariableReference { id } = node {
+            id.name.insert_str(0, "ctx-");
+        }
+        visit::walk_inline_expression_mut(self, node);
+    }
+}
+
+/// Uppercases text and string literals, but leaves comments alone.
+struct Uppercase;
+
+impl VisitMut<String> for Uppercase {
+    fn visit_text_element_mut(&mut self, value: &mut String) {
+        *value = value.to_uppercase();
+    }
+
+    fn visit_inline_expression_mut(&mut self, node: &mut ast::InlineExpression<String>) {
+        if let ast::InlineExpression::StringLiteral { value } = node {
+            *value = value.to_uppercase();
+        }
+        visit::walk_inline_expression_mut(self, node);
+    }
+}
+
+#[test]
+fn visit_mut_transforms_nodes() {
+    let ftl = "# comment\nkey = Hi { $user }, { $n ->\n    [one] { \"one\" } { $item }\n   *[other] { $n } items\n    }\n    .title = { FUN($arg, x: \"y\") }\n";
+    let mut resource = parse(ftl.to_string()).expect("Failed to parse an FTL resource.");
+
+    PrefixVariables.visit_resource_mut(&mut resource);
+    Uppercase.visit_resource_mut(&mut resource);
+
+    assert_eq!(
+        serialize(&resource),
+        "# comment\nkey =\n    HI { $ctx-user }, { $ctx-n ->\n        [one] { \"ONE\" } { $ctx-item }\n       *[other] { $ctx-n } ITEMS\n    }\n    .title = { FUN($ctx-arg, x: \"Y\") }\n"
+    );
+}
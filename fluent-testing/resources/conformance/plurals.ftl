@@ -0,0 +1,14 @@
+emails =
+    { $count ->
+        [0] No emails
+        [one] One email
+        [few] { $count } emails (few)
+       *[other] { $count } emails
+    }
+ranking =
+    { NUMBER($position, type: "ordinal") ->
+        [one] { $position }st
+        [two] { $position }nd
+        [few] { $position }rd
+       *[other] { $position }th
+    }
@@ -0,0 +1,14 @@
+-brand-name =
+    { $case ->
+       *[nominative] Firefox
+        [genitive] Firefoksa
+    }
+    .gender = masculine
+
+about = About { -brand-name }
+update = Update of { -brand-name(case: "genitive") }
+welcome = Welcome, { $user }!
+    .title = { about }
+login =
+    .placeholder = Email
+missing = { -missing-term } and { missing-message }
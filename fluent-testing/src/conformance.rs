@@ -0,0 +1,509 @@
+//! A golden-file harness which checks the parser and the resolver against the
+//! fixtures of the reference implementations of Fluent.
+//!
+//! A fixture is an FTL file, `name.ftl`, along with optional expectations in
+//! files next to it:
+//!
+//!  - `name.json` is the expected AST, in the JSON format of the fixtures of
+//!    [fluent.js](https://github.com/projectfluent/fluent.js) and
+//!    [python-fluent](https://github.com/projectfluent/python-fluent). Spans
+//!    and annotations are ignored.
+//!  - `name.output.json` lists messages to format and their expected values:
+//!
+//! ```json
+//! {
+//!     "locales": ["en-US"],
+//!     "useIsolating": false,
+//!     "assertions": [
+//!         { "id": "emails", "args": { "count": 1 }, "value": "One email" },
+//!         { "id": "emails", "attribute": "title", "value": "Inbox" },
+//!         { "id": "broken", "value": "{$missing}", "errors": 1 }
+//!     ]
+//! }
+//! ```
+//!
+//! `locales` defaults to `["en-US"]` and `useIsolating` to `false`. The
+//! `value` of a message without a value is `null`, and `errors` is only
+//! checked when given.
+//!
+//! Instead of failing on the first difference, the harness collects every
+//! [`Divergence`] from the expectations, so that a run over a whole directory
+//! reports how far an implementation is from the reference.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use fluent_testing::conformance;
+//!
+//! let divergences = conformance::check_dir("tests/fixtures")
+//!     .expect("Failed to read the fixtures.");
+//! for divergence in &divergences {
+//!     eprintln!("{}", divergence);
+//! }
+//! assert!(divergences.is_empty());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use fluent_syntax::ast;
+use fluent_syntax::parser;
+use serde::Deserialize;
+use unic_langid::LanguageIdentifier;
+
+/// An FTL source and the expectations it is checked against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fixture {
+    /// The name of the fixture, which is the file name of its source without
+    /// the extension.
+    pub name: String,
+    /// The FTL source.
+    pub source: String,
+    /// The expected AST, as JSON.
+    pub ast: Option<String>,
+    /// The expected formatted output, as JSON.
+    pub output: Option<String>,
+}
+
+impl Fixture {
+    /// Reads the fixture whose source is at `path`, along with the `.json`
+    /// and `.output.json` files next to it, if they exist.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self {
+            source: fs::read_to_string(path)?,
+            ast: read_optional(&path.with_extension("json"))?,
+            output: read_optional(&path.with_extension("output.json"))?,
+            name,
+        })
+    }
+
+    /// Checks the fixture against the parser and the resolver, and returns
+    /// the differences with its expectations.
+    pub fn check(&self) -> Vec<Divergence> {
+        let mut divergences = vec![];
+        if let Some(ast) = &self.ast {
+            self.check_ast(ast, &mut divergences);
+        }
+        if let Some(output) = &self.output {
+            self.check_output(output, &mut divergences);
+        }
+        divergences
+    }
+
+    fn divergence(&self, kind: DivergenceKind) -> Divergence {
+        Divergence {
+            fixture: self.name.clone(),
+            kind,
+        }
+    }
+
+    fn check_ast(&self, json: &str, divergences: &mut Vec<Divergence>) {
+        let mut expected: ast::Resource<String> = match serde_json::from_str(json) {
+            Ok(expected) => expected,
+            Err(err) => {
+                divergences.push(self.divergence(DivergenceKind::InvalidExpectation(format!(
+                    "Invalid AST: {}",
+                    err
+                ))));
+                return;
+            }
+        };
+        adapt_resource(&mut expected, self.source.contains("\r\n"));
+
+        let actual = match parser::parse(self.source.as_str()) {
+            Ok(resource) => resource,
+            Err((resource, _)) => resource,
+        };
+
+        if actual.body.len() != expected.body.len() {
+            divergences.push(self.divergence(DivergenceKind::EntryCount {
+                expected: expected.body.len(),
+                actual: actual.body.len(),
+            }));
+        }
+        // The entries hold different string types, whose `Debug`
+        // representations are the same.
+        for (index, (actual, expected)) in actual.body.iter().zip(&expected.body).enumerate() {
+            let actual = format!("{:?}", actual);
+            let expected = format!("{:?}", expected);
+            if actual != expected {
+                divergences.push(self.divergence(DivergenceKind::Entry {
+                    index,
+                    expected,
+                    actual,
+                }));
+            }
+        }
+    }
+
+    fn check_output(&self, json: &str, divergences: &mut Vec<Divergence>) {
+        let expected: ExpectedOutput = match serde_json::from_str(json) {
+            Ok(expected) => expected,
+            Err(err) => {
+                divergences.push(self.divergence(DivergenceKind::InvalidExpectation(format!(
+                    "Invalid output: {}",
+                    err
+                ))));
+                return;
+            }
+        };
+        let locales = match expected
+            .locales
+            .iter()
+            .map(|locale| locale.parse())
+            .collect::<Result<Vec<LanguageIdentifier>, _>>()
+        {
+            Ok(locales) => locales,
+            Err(err) => {
+                divergences.push(self.divergence(DivergenceKind::InvalidExpectation(format!(
+                    "Invalid locale: {}",
+                    err
+                ))));
+                return;
+            }
+        };
+
+        let resource = match FluentResource::try_new(self.source.clone()) {
+            Ok(resource) => resource,
+            Err((resource, _)) => resource,
+        };
+        // The other implementations provide the builtin functions by default.
+        let mut bundle = FluentBundle::new(locales);
+        bundle
+            .add_builtins()
+            .expect("The bundle has no functions yet");
+        bundle.set_use_isolating(expected.use_isolating);
+        // Overriding entries is not an error of the resolver, and the first
+        // definition is kept as in the other implementations.
+        let _ = bundle.add_resource(&resource);
+
+        for assertion in &expected.assertions {
+            let Some(message) = bundle.get_message(&assertion.id) else {
+                divergences.push(self.divergence(DivergenceKind::MissingMessage {
+                    id: assertion.id.clone(),
+                }));
+                continue;
+            };
+            let pattern = match &assertion.attribute {
+                Some(name) => match message.get_attribute(name) {
+                    Some(attribute) => Some(attribute.value()),
+                    None => {
+                        divergences.push(self.divergence(DivergenceKind::MissingAttribute {
+                            id: assertion.id.clone(),
+                            attribute: name.clone(),
+                        }));
+                        continue;
+                    }
+                },
+                None => message.value(),
+            };
+
+            let args = assertion.args.as_ref().map(|args| {
+                let mut result = FluentArgs::with_capacity(args.len());
+                for (name, value) in args {
+                    result.set(name.as_str(), value.to_fluent_value());
+                }
+                result
+            });
+            let mut errors = vec![];
+            let actual = pattern.map(|pattern| {
+                bundle
+                    .format_pattern(pattern, args.as_ref(), &mut errors)
+                    .into_owned()
+            });
+
+            let path = match &assertion.attribute {
+                Some(attribute) => format!("{}.{}", assertion.id, attribute),
+                None => assertion.id.clone(),
+            };
+            if actual != assertion.value {
+                divergences.push(self.divergence(DivergenceKind::Value {
+                    path: path.clone(),
+                    expected: assertion.value.clone(),
+                    actual,
+                }));
+            }
+            if let Some(expected) = assertion.errors {
+                if errors.len() != expected {
+                    divergences.push(self.divergence(DivergenceKind::Errors {
+                        path,
+                        expected,
+                        actual: errors.len(),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+fn read_optional(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(source) => Ok(Some(source)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Reads the fixtures of the `.ftl` files in `dir`, sorted by name.
+pub fn load_dir(dir: impl AsRef<Path>) -> io::Result<Vec<Fixture>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(false, |ext| ext == "ftl") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    paths.iter().map(Fixture::load).collect()
+}
+
+/// Checks the fixtures of the `.ftl` files in `dir`, and returns the
+/// differences with their expectations.
+pub fn check_dir(dir: impl AsRef<Path>) -> io::Result<Vec<Divergence>> {
+    Ok(load_dir(dir)?.iter().flat_map(Fixture::check).collect())
+}
+
+/// A difference between the behavior of this implementation and the
+/// expectations of a fixture.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The name of the fixture.
+    pub fixture: String,
+    pub kind: DivergenceKind,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.fixture, self.kind)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// An expectation file could not be read.
+    InvalidExpectation(String),
+    /// The resource has a different number of entries than expected.
+    EntryCount { expected: usize, actual: usize },
+    /// The entry at `index` differs from the expected one. Both entries are
+    /// in their `Debug` representation.
+    Entry {
+        index: usize,
+        expected: String,
+        actual: String,
+    },
+    /// The message to format is not in the bundle.
+    MissingMessage { id: String },
+    /// The attribute to format is not in the message.
+    MissingAttribute { id: String, attribute: String },
+    /// The message or the attribute at `path`, as in `id.attribute`, was
+    /// formatted to a different value.
+    Value {
+        path: String,
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+    /// Formatting the message or the attribute at `path` reported a different
+    /// number of errors.
+    Errors {
+        path: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for DivergenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidExpectation(err) => f.write_str(err),
+            Self::EntryCount { expected, actual } => {
+                write!(f, "expected {} entries, found {}", expected, actual)
+            }
+            Self::Entry {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "entry {} differs\n  expected: {}\n     found: {}",
+                index, expected, actual
+            ),
+            Self::MissingMessage { id } => write!(f, "message {} is missing", id),
+            Self::MissingAttribute { id, attribute } => {
+                write!(f, "attribute {}.{} is missing", id, attribute)
+            }
+            Self::Value {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} formatted to {}, expected {}",
+                path,
+                describe_value(actual),
+                describe_value(expected)
+            ),
+            Self::Errors {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} reported {} errors, expected {}",
+                path, actual, expected
+            ),
+        }
+    }
+}
+
+fn describe_value(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{:?}", value),
+        None => "no value".to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExpectedOutput {
+    #[serde(default = "default_locales")]
+    locales: Vec<String>,
+    #[serde(default)]
+    use_isolating: bool,
+    #[serde(default)]
+    assertions: Vec<Assertion>,
+}
+
+fn default_locales() -> Vec<String> {
+    vec!["en-US".to_string()]
+}
+
+#[derive(Deserialize)]
+struct Assertion {
+    id: String,
+    #[serde(default)]
+    attribute: Option<String>,
+    #[serde(default)]
+    args: Option<HashMap<String, ArgValue>>,
+    value: Option<String>,
+    #[serde(default)]
+    errors: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArgValue {
+    Number(f64),
+    String(String),
+}
+
+impl ArgValue {
+    fn to_fluent_value(&self) -> FluentValue<'_> {
+        match self {
+            Self::Number(number) => FluentValue::from(*number),
+            Self::String(string) => FluentValue::from(string.as_str()),
+        }
+    }
+}
+
+/// The reference AST keeps multiline text in a single element, while the
+/// parser of this crate returns one element per line, and splits CRLF line
+/// endings into a line and its `\n`. The reference AST also joins the lines
+/// of comments.
+fn adapt_resource(resource: &mut ast::Resource<String>, crlf: bool) {
+    for entry in &mut resource.body {
+        match entry {
+            ast::Entry::Comment(comment)
+            | ast::Entry::GroupComment(comment)
+            | ast::Entry::ResourceComment(comment) => adapt_comment(comment),
+            ast::Entry::Message(message) => {
+                if let Some(comment) = &mut message.comment {
+                    adapt_comment(comment);
+                }
+                if let Some(value) = &mut message.value {
+                    adapt_pattern(value, crlf);
+                }
+                for attribute in &mut message.attributes {
+                    adapt_pattern(&mut attribute.value, crlf);
+                }
+            }
+            ast::Entry::Term(term) => {
+                if let Some(comment) = &mut term.comment {
+                    adapt_comment(comment);
+                }
+                adapt_pattern(&mut term.value, crlf);
+                for attribute in &mut term.attributes {
+                    adapt_pattern(&mut attribute.value, crlf);
+                }
+            }
+            ast::Entry::Junk { .. } => {}
+        }
+    }
+}
+
+fn adapt_comment(comment: &mut ast::Comment<String>) {
+    comment.content = comment
+        .content
+        .iter()
+        .flat_map(|line| line.split('\n').map(str::to_string))
+        .collect();
+}
+
+fn adapt_pattern(pattern: &mut ast::Pattern<String>, crlf: bool) {
+    let mut elements = vec![];
+    for element in pattern.elements.drain(..) {
+        match element {
+            ast::PatternElement::TextElement { value } => {
+                for line in value.split_inclusive('\n') {
+                    match line.strip_suffix('\n') {
+                        Some(line) if crlf => {
+                            if !line.is_empty() {
+                                elements.push(text_element(line));
+                            }
+                            elements.push(text_element("\n"));
+                        }
+                        _ => elements.push(text_element(line)),
+                    }
+                }
+            }
+            ast::PatternElement::Placeable { mut expression } => {
+                adapt_expression(&mut expression, crlf);
+                elements.push(ast::PatternElement::Placeable { expression });
+            }
+            element => elements.push(element),
+        }
+    }
+    pattern.elements = elements;
+}
+
+fn text_element(value: &str) -> ast::PatternElement<String> {
+    ast::PatternElement::TextElement {
+        value: value.to_string(),
+    }
+}
+
+fn adapt_expression(expression: &mut ast::Expression<String>, crlf: bool) {
+    match expression {
+        ast::Expression::Select { selector, variants } => {
+            adapt_inline_expression(selector, crlf);
+            for variant in variants {
+                adapt_pattern(&mut variant.value, crlf);
+            }
+        }
+        ast::Expression::Inline(expression) => adapt_inline_expression(expression, crlf),
+    }
+}
+
+fn adapt_inline_expression(expression: &mut ast::InlineExpression<String>, crlf: bool) {
+    if let ast::InlineExpression::Placeable { expression } = expression {
+        adapt_expression(expression, crlf);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "conformance")]
+pub mod conformance;
 mod fs;
 pub mod scenarios;
 
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use fluent_testing::conformance::{self, Divergence, DivergenceKind, Fixture};
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+}
+
+fn assert_conformance(dir: PathBuf) {
+    let divergences = conformance::check_dir(dir).expect("Failed to read the fixtures");
+    for divergence in &divergences {
+        println!("{}", divergence);
+    }
+    assert!(divergences.is_empty());
+}
+
+#[test]
+fn conformance_of_the_parser() {
+    assert_conformance(manifest_dir().join("../fluent-syntax/tests/fixtures"));
+}
+
+#[test]
+fn conformance_of_the_resolver() {
+    assert_conformance(manifest_dir().join("resources/conformance"));
+}
+
+#[test]
+fn conformance_reports_divergences() {
+    let fixture = Fixture {
+        name: "example".to_string(),
+        source: "hello = Hello, { $user }!\n".to_string(),
+        ast: Some(r#"{ "type": "Resource", "body": [] }"#.to_string()),
+        output: Some(
+            r#"{ "assertions": [
+                { "id": "hello", "args": { "user": "Anna" }, "value": "Hi, Anna!", "errors": 1 },
+                { "id": "hello", "attribute": "title", "value": "Hello" },
+                { "id": "missing", "value": null }
+            ] }"#
+                .to_string(),
+        ),
+    };
+
+    let divergence = |kind| Divergence {
+        fixture: "example".to_string(),
+        kind,
+    };
+    assert_eq!(
+        fixture.check(),
+        vec![
+            divergence(DivergenceKind::EntryCount {
+                expected: 0,
+                actual: 1
+            }),
+            divergence(DivergenceKind::Value {
+                path: "hello".to_string(),
+                expected: Some("Hi, Anna!".to_string()),
+                actual: Some("Hello, Anna!".to_string()),
+            }),
+            divergence(DivergenceKind::Errors {
+                path: "hello".to_string(),
+                expected: 1,
+                actual: 0,
+            }),
+            divergence(DivergenceKind::MissingAttribute {
+                id: "hello".to_string(),
+                attribute: "title".to_string(),
+            }),
+            divergence(DivergenceKind::MissingMessage {
+                id: "missing".to_string(),
+            }),
+        ]
+    );
+    assert_eq!(
+        fixture.check()[1].to_string(),
+        "example: hello formatted to \"Hello, Anna!\", expected \"Hi, Anna!\""
+    );
+}
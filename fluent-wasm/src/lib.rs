@@ -0,0 +1,179 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! `fluent-wasm` exposes [`fluent-bundle`](fluent_bundle) to JavaScript through
+//! [`wasm-bindgen`](wasm_bindgen), so that a web frontend can parse and resolve
+//! messages with exactly the same code as a Rust backend.
+//!
+//! The API follows the shape of `@fluent/bundle`:
+//!
+//! ```js
+//! import { FluentBundle, FluentResource } from "fluent-wasm";
+//!
+//! const resource = new FluentResource("hello = Hello, { $name }!");
+//! const bundle = new FluentBundle(["en-US"]);
+//! bundle.setUseIsolating(false);
+//!
+//! const errors = bundle.addResource(resource);
+//! bundle.formatPattern("hello", { name: "World" }); // "Hello, World!"
+//! ```
+//!
+//! Unlike `@fluent/bundle`, `formatPattern` takes the message identifier rather than a
+//! pattern object. Attributes are addressed as `message.attribute`.
+use std::rc::Rc;
+
+use fluent_bundle::{FluentArgs, FluentValue};
+use js_sys::{Array, Object};
+use unic_langid::LanguageIdentifier;
+use wasm_bindgen::prelude::*;
+
+/// A parsed Fluent resource.
+///
+/// Syntax errors are recovered from in the same way as in
+/// [`fluent_bundle::FluentResource`]; the entries which failed to parse are
+/// skipped, and the errors are available through [`FluentResource::errors`].
+#[wasm_bindgen]
+pub struct FluentResource {
+    inner: Rc<fluent_bundle::FluentResource>,
+    errors: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl FluentResource {
+    /// Parse `source` into a new resource.
+    #[wasm_bindgen(constructor)]
+    pub fn new(source: String) -> Self {
+        let (inner, errors) = match fluent_bundle::FluentResource::try_new(source) {
+            Ok(res) => (res, vec![]),
+            Err((res, errors)) => (res, errors.iter().map(ToString::to_string).collect()),
+        };
+        Self {
+            inner: Rc::new(inner),
+            errors,
+        }
+    }
+
+    /// The syntax errors encountered while parsing.
+    #[wasm_bindgen(getter)]
+    pub fn errors(&self) -> Vec<String> {
+        self.errors.clone()
+    }
+}
+
+/// A collection of messages for a given locale, see [`fluent_bundle::FluentBundle`].
+#[wasm_bindgen]
+pub struct FluentBundle {
+    inner: fluent_bundle::FluentBundle<Rc<fluent_bundle::FluentResource>>,
+}
+
+#[wasm_bindgen]
+impl FluentBundle {
+    /// Create a new bundle for `locales`, given as BCP 47 language tags, with
+    /// the builtin functions registered.
+    #[wasm_bindgen(constructor)]
+    pub fn new(locales: Vec<String>) -> Result<FluentBundle, JsError> {
+        let locales = locales
+            .iter()
+            .map(|locale| locale.parse::<LanguageIdentifier>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut inner = fluent_bundle::FluentBundle::new(locales);
+        inner
+            .add_builtins()
+            .expect("Builtins are added to an empty bundle");
+        Ok(Self { inner })
+    }
+
+    /// The locales of the bundle.
+    #[wasm_bindgen(getter)]
+    pub fn locales(&self) -> Vec<String> {
+        self.inner.locales.iter().map(ToString::to_string).collect()
+    }
+
+    /// Add the messages and terms of `resource` to the bundle.
+    ///
+    /// Entries which already exist are kept, unless `allowOverrides` is
+    /// `true`. Returns the list of errors as strings.
+    #[wasm_bindgen(js_name = addResource)]
+    pub fn add_resource(
+        &mut self,
+        resource: &FluentResource,
+        allow_overrides: Option<bool>,
+    ) -> Vec<String> {
+        let res = resource.inner.clone();
+        if allow_overrides.unwrap_or(false) {
+            self.inner.add_resource_overriding(res);
+            return vec![];
+        }
+        match self.inner.add_resource(res) {
+            Ok(()) => vec![],
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Returns `true` if the bundle contains the message `id`.
+    #[wasm_bindgen(js_name = hasMessage)]
+    pub fn has_message(&self, id: &str) -> bool {
+        self.inner.has_message(id)
+    }
+
+    /// Format the message `id`, or its attribute if `id` has the form
+    /// `message.attribute`.
+    ///
+    /// `args` is a plain object whose numeric and string properties are passed
+    /// as variables. Resolution errors are appended, as strings, to `errors`
+    /// when it is given. Returns `undefined` if the message, attribute or value
+    /// does not exist.
+    #[wasm_bindgen(js_name = formatPattern)]
+    pub fn format_pattern(
+        &self,
+        id: &str,
+        args: Option<Object>,
+        errors: Option<Array>,
+    ) -> Option<String> {
+        let (id, attribute) = match id.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (id, None),
+        };
+        let msg = self.inner.get_message(id)?;
+        let pattern = match attribute {
+            Some(attribute) => msg.get_attribute(attribute)?.value(),
+            None => msg.value()?,
+        };
+
+        let args = args.as_ref().map(to_fluent_args);
+        let mut errs = vec![];
+        let value = self.inner.format_pattern(pattern, args.as_ref(), &mut errs);
+
+        if let Some(errors) = errors {
+            for err in errs {
+                errors.push(&JsValue::from(err.to_string()));
+            }
+        }
+        Some(value.into_owned())
+    }
+
+    /// Enable or disable Unicode bidi isolation of placeables, see
+    /// [`fluent_bundle::FluentBundle::set_use_isolating`].
+    #[wasm_bindgen(js_name = setUseIsolating)]
+    pub fn set_use_isolating(&mut self, value: bool) {
+        self.inner.set_use_isolating(value);
+    }
+}
+
+fn to_fluent_args(object: &Object) -> FluentArgs<'static> {
+    let mut args = FluentArgs::new();
+    for entry in Object::entries(object).iter() {
+        let entry = Array::from(&entry);
+        let Some(key) = entry.get(0).as_string() else {
+            continue;
+        };
+        let value = entry.get(1);
+        if let Some(number) = value.as_f64() {
+            args.set(key, number);
+        } else if let Some(string) = value.as_string() {
+            args.set(key, string);
+        } else if value.is_null() || value.is_undefined() {
+            args.set(key, FluentValue::None);
+        }
+    }
+    args
+}
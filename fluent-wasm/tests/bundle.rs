@@ -0,0 +1,139 @@
+#![cfg(target_arch = "wasm32")]
+
+use fluent_wasm::{FluentBundle, FluentResource};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+fn get_bundle(source: &str) -> FluentBundle {
+    let mut bundle = FluentBundle::new(vec!["en-US".to_string()]).unwrap();
+    bundle.set_use_isolating(false);
+    let errors = bundle.add_resource(&FluentResource::new(source.to_string()), None);
+    assert_eq!(errors, Vec::<String>::new());
+    bundle
+}
+
+fn get_args(args: &[(&str, JsValue)]) -> Object {
+    let object = Object::new();
+    for (key, value) in args {
+        Reflect::set(&object, &JsValue::from_str(key), value).unwrap();
+    }
+    object
+}
+
+#[wasm_bindgen_test]
+fn invalid_locales_are_rejected() {
+    assert!(FluentBundle::new(vec!["en-US".to_string(), "x".to_string()]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn syntax_errors_are_reported() {
+    let resource = FluentResource::new("hello = Hello\n= broken\nbye = Bye".to_string());
+    assert_eq!(resource.errors().len(), 1);
+
+    let mut bundle = FluentBundle::new(vec!["en-US".to_string()]).unwrap();
+    assert_eq!(bundle.add_resource(&resource, None), Vec::<String>::new());
+    assert!(bundle.has_message("hello"));
+    assert!(bundle.has_message("bye"));
+}
+
+#[wasm_bindgen_test]
+fn overriding_entries_is_reported() {
+    let mut bundle = get_bundle("hello = Hello\n-brand = Fluent");
+    let resource = FluentResource::new("hello = Hi\n-brand = Other\nbye = Bye".to_string());
+
+    let errors = bundle.add_resource(&resource, None);
+    assert_eq!(
+        errors,
+        vec![
+            "Attempt to override an existing message: \"hello\".".to_string(),
+            "Attempt to override an existing term: \"brand\".".to_string(),
+        ]
+    );
+    assert_eq!(
+        bundle.format_pattern("hello", None, None),
+        Some("Hello".to_string())
+    );
+    assert_eq!(
+        bundle.format_pattern("bye", None, None),
+        Some("Bye".to_string())
+    );
+
+    assert_eq!(
+        bundle.add_resource(&resource, Some(true)),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        bundle.format_pattern("hello", None, None),
+        Some("Hi".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn formats_with_args() {
+    let bundle = get_bundle(
+        "
+hello = Hello, { $name }!
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+    .title = Emails of { $name }
+",
+    );
+
+    let args = get_args(&[
+        ("name", JsValue::from_str("World")),
+        ("count", JsValue::from_f64(3.0)),
+    ]);
+    let errors = Array::new();
+    assert_eq!(
+        bundle.format_pattern("hello", Some(args.clone()), Some(errors.clone())),
+        Some("Hello, World!".to_string())
+    );
+    assert_eq!(
+        bundle.format_pattern("emails", Some(args.clone()), Some(errors.clone())),
+        Some("3 emails".to_string())
+    );
+    assert_eq!(
+        bundle.format_pattern("emails.title", Some(args), Some(errors.clone())),
+        Some("Emails of World".to_string())
+    );
+    assert_eq!(errors.length(), 0);
+
+    let args = get_args(&[("count", JsValue::from_f64(1.0))]);
+    assert_eq!(
+        bundle.format_pattern("emails", Some(args), None),
+        Some("One email".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn missing_args_are_reported() {
+    let bundle = get_bundle("hello = Hello, { $name }!");
+
+    let errors = Array::new();
+    assert_eq!(
+        bundle.format_pattern("hello", None, Some(errors.clone())),
+        Some("Hello, {$name}!".to_string())
+    );
+    assert_eq!(errors.length(), 1);
+    assert_eq!(
+        errors.get(0).as_string(),
+        Some("Resolver error: Unknown variable: $name".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn missing_messages_are_undefined() {
+    let bundle = get_bundle("hello = Hello\n    .title = Title\nempty =\n    .title = Title");
+
+    assert!(!bundle.has_message("missing"));
+    assert_eq!(bundle.format_pattern("missing", None, None), None);
+    assert_eq!(bundle.format_pattern("hello.missing", None, None), None);
+    assert_eq!(bundle.format_pattern("empty", None, None), None);
+    assert_eq!(
+        bundle.format_pattern("empty.title", None, None),
+        Some("Title".to_string())
+    );
+}
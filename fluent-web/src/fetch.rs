@@ -0,0 +1,144 @@
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use fluent_bundle::{FluentBundle, FluentResource};
+use fluent_fallback::generator::{BundleGenerator, BundleStream, FluentBundleResult};
+use fluent_fallback::types::ResourceId;
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use futures::stream::{self, LocalBoxStream, Stream, StreamExt};
+use rustc_hash::{FxHashMap, FxHashSet};
+use unic_langid::LanguageIdentifier;
+
+type PendingResource = Shared<LocalBoxFuture<'static, Option<Rc<FluentResource>>>>;
+
+/// A [`BundleGenerator`] which retrieves resources with the browser `fetch` API.
+///
+/// Resource URLs are built from a path scheme in which `{locale}` and `{res_id}`
+/// are replaced by the locale and resource identifier, in the same manner as
+/// `fluent-resmgr`. Every resource is requested at most once per locale; the
+/// result is shared between all clones of the generator.
+///
+/// Only asynchronous iteration is supported, since the browser does not allow
+/// blocking on network requests. The synchronous iterator is always empty.
+#[derive(Clone)]
+pub struct FetchGenerator {
+    path_scheme: Rc<str>,
+    resources: Rc<RefCell<FxHashMap<String, PendingResource>>>,
+}
+
+impl FetchGenerator {
+    /// Create a new generator which resolves resource URLs using `path_scheme`.
+    pub fn new(path_scheme: String) -> Self {
+        Self {
+            path_scheme: path_scheme.into(),
+            resources: Rc::default(),
+        }
+    }
+
+    fn get_resource(&self, res_id: &str, locale: &str) -> PendingResource {
+        let path = self
+            .path_scheme
+            .replace("{locale}", locale)
+            .replace("{res_id}", res_id);
+
+        match self.resources.borrow_mut().entry(path) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => {
+                let url = entry.key().clone();
+                entry
+                    .insert(fetch_resource(url).boxed_local().shared())
+                    .clone()
+            }
+        }
+    }
+
+    async fn get_bundle(
+        self,
+        locale: LanguageIdentifier,
+        res_ids: Rc<[ResourceId]>,
+    ) -> Option<FluentBundleResult<Rc<FluentResource>>> {
+        let mut bundle = FluentBundle::new(vec![locale.clone()]);
+        let mut errors = vec![];
+        let locale = locale.to_string();
+
+        for res_id in res_ids.iter() {
+            match self.get_resource(&res_id.value, &locale).await {
+                Some(resource) => {
+                    if let Err(errs) = bundle.add_resource(resource) {
+                        errors.extend(errs);
+                    }
+                }
+                // A missing required resource disqualifies the whole locale.
+                None if res_id.is_required() => return None,
+                None => {}
+            }
+        }
+
+        Some(if errors.is_empty() {
+            Ok(bundle)
+        } else {
+            Err((bundle, errors))
+        })
+    }
+}
+
+async fn fetch_resource(url: String) -> Option<Rc<FluentResource>> {
+    let response = gloo_net::http::Request::get(&url).send().await.ok()?;
+    if !response.ok() {
+        return None;
+    }
+    let source = response.text().await.ok()?;
+    let resource = match FluentResource::try_new(source) {
+        Ok(resource) => resource,
+        // Resources with syntax errors are still usable; the parser recovers
+        // every entry it can.
+        Err((resource, _errors)) => resource,
+    };
+    Some(Rc::new(resource))
+}
+
+/// The stream of bundles produced by a [`FetchGenerator`].
+pub struct FetchStream(LocalBoxStream<'static, FluentBundleResult<Rc<FluentResource>>>);
+
+impl Stream for FetchStream {
+    type Item = FluentBundleResult<Rc<FluentResource>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next_unpin(cx)
+    }
+}
+
+impl BundleStream for FetchStream {}
+
+impl BundleGenerator for FetchGenerator {
+    type Resource = Rc<FluentResource>;
+    type LocalesIter = std::vec::IntoIter<LanguageIdentifier>;
+    type Iter = std::iter::Empty<FluentBundleResult<Rc<FluentResource>>>;
+    type Stream = FetchStream;
+
+    fn bundles_iter(
+        &self,
+        _locales: Self::LocalesIter,
+        _res_ids: FxHashSet<ResourceId>,
+    ) -> Self::Iter {
+        std::iter::empty()
+    }
+
+    fn bundles_stream(
+        &self,
+        locales: Self::LocalesIter,
+        res_ids: FxHashSet<ResourceId>,
+    ) -> Self::Stream {
+        let generator = self.clone();
+        let res_ids: Rc<[ResourceId]> = res_ids.into_iter().collect();
+
+        FetchStream(
+            stream::iter(locales)
+                .filter_map(move |locale| generator.clone().get_bundle(locale, res_ids.clone()))
+                .boxed_local(),
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fluent_bundle::FluentArgs;
+use fluent_fallback::{types::ResourceId, Localization};
+use unic_langid::LanguageIdentifier;
+
+use crate::{FetchGenerator, Locales};
+
+/// An asynchronous [`Localization`] backed by a [`FetchGenerator`].
+///
+/// This is a cheap, reference counted handle; clones share the same bundles,
+/// locales and resource cache.
+#[derive(Clone)]
+pub struct WebLocalization {
+    inner: Rc<RefCell<Localization<FetchGenerator, Locales>>>,
+    locales: Locales,
+}
+
+impl WebLocalization {
+    /// Create a new localization for `res_ids`, initially using `locales`.
+    ///
+    /// The locales should already be negotiated against the available ones
+    /// and sorted by user preference.
+    pub fn new(
+        res_ids: Vec<ResourceId>,
+        locales: Vec<LanguageIdentifier>,
+        generator: FetchGenerator,
+    ) -> Self {
+        let locales = Locales::new(locales);
+        let inner = Localization::with_env(res_ids, false, locales.clone(), generator);
+        Self {
+            inner: Rc::new(RefCell::new(inner)),
+            locales,
+        }
+    }
+
+    /// Returns `true` if both handles refer to the same localization.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns the current locales.
+    pub fn locales(&self) -> Vec<LanguageIdentifier> {
+        self.locales.get()
+    }
+
+    /// Switch to a new set of locales. Bundles are re-generated lazily, on the
+    /// next format call.
+    pub fn set_locales(&self, locales: Vec<LanguageIdentifier>) {
+        self.locales.set(locales);
+        self.inner.borrow_mut().on_change();
+    }
+
+    /// Format the value of the message `id`, falling back on `id` itself if
+    /// the message cannot be found in any locale.
+    pub async fn format_value(&self, id: &str, args: Option<&FluentArgs<'_>>) -> String {
+        // Hold on to the bundles, rather than the localization, across the
+        // await point, so that `set_locales` may be called in the meantime.
+        let bundles = self.inner.borrow().bundles().clone();
+        let mut errors = vec![];
+        bundles
+            .format_value(id, args, &mut errors)
+            .await
+            .map_or_else(|| id.to_string(), |value| value.into_owned())
+    }
+}
@@ -0,0 +1,128 @@
+//! Hooks for [leptos](https://leptos.dev).
+//!
+//! Call [`provide_localization`] once near the root of the application, then
+//! use [`use_translation`] anywhere below it:
+//!
+//! ```no_run
+//! use fluent_bundle::FluentArgs;
+//! use fluent_web::leptos::{provide_localization, use_locales, use_translation};
+//! use fluent_web::{FetchGenerator, WebLocalization};
+//! use leptos::*;
+//! use unic_langid::langid;
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     provide_localization(WebLocalization::new(
+//!         vec!["main.ftl".into()],
+//!         vec![langid!("en-US")],
+//!         FetchGenerator::new("/locales/{locale}/{res_id}".to_string()),
+//!     ));
+//!
+//!     let (count, set_count) = create_signal(1);
+//!     let emails = use_translation("emails", move || {
+//!         let mut args = FluentArgs::new();
+//!         args.set("count", count.get());
+//!         Some(args)
+//!     });
+//!     let locales = use_locales();
+//!
+//!     view! {
+//!         <p>{emails}</p>
+//!         <button on:click=move |_| set_count.update(|c| *c += 1)>"+"</button>
+//!         <button on:click=move |_| locales.set(vec![langid!("pl")])>"PL"</button>
+//!     }
+//! }
+//! ```
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fluent_bundle::FluentArgs;
+use leptos::*;
+use unic_langid::LanguageIdentifier;
+
+use crate::WebLocalization;
+
+#[derive(Clone)]
+struct LocalizationContext {
+    l10n: WebLocalization,
+    locales: RwSignal<Vec<LanguageIdentifier>>,
+}
+
+fn use_context_or_panic() -> LocalizationContext {
+    use_context::<LocalizationContext>()
+        .expect("`provide_localization` must be called by an ancestor component")
+}
+
+/// Make `l10n` available to the current component and its descendants.
+pub fn provide_localization(l10n: WebLocalization) {
+    let locales = create_rw_signal(l10n.locales());
+    provide_context(LocalizationContext { l10n, locales });
+}
+
+/// The reactive locale selection of the current [`WebLocalization`].
+#[derive(Clone, Copy)]
+pub struct LocalesSignal {
+    locales: RwSignal<Vec<LanguageIdentifier>>,
+    context: StoredValue<WebLocalization>,
+}
+
+impl LocalesSignal {
+    /// Returns the current locales, subscribing to changes.
+    pub fn get(&self) -> Vec<LanguageIdentifier> {
+        self.locales.get()
+    }
+
+    /// Switch to a new set of locales, re-rendering every translation.
+    pub fn set(&self, locales: Vec<LanguageIdentifier>) {
+        // Update the localization before notifying subscribers, so that they
+        // format against the new bundles.
+        self.context
+            .with_value(|l10n| l10n.set_locales(locales.clone()));
+        self.locales.set(locales);
+    }
+}
+
+/// Returns the locale signal of the nearest [`provide_localization`].
+pub fn use_locales() -> LocalesSignal {
+    let LocalizationContext { l10n, locales } = use_context_or_panic();
+    LocalesSignal {
+        locales,
+        context: store_value(l10n),
+    }
+}
+
+/// Returns a signal holding the translation of the message `id`.
+///
+/// The translation is updated whenever the locales change or any signal read
+/// by `args` changes. Until the first translation resolves, the signal holds
+/// an empty string.
+pub fn use_translation<A>(id: impl Into<String>, args: A) -> Signal<String>
+where
+    A: Fn() -> Option<FluentArgs<'static>> + 'static,
+{
+    let LocalizationContext { l10n, locales } = use_context_or_panic();
+    let id: Rc<str> = id.into().into();
+    let (value, set_value) = create_signal(String::new());
+    let generation = Rc::new(Cell::new(0_u64));
+
+    create_effect(move |_| {
+        locales.track();
+        let args = args();
+
+        let current = generation.get() + 1;
+        generation.set(current);
+
+        let l10n = l10n.clone();
+        let id = id.clone();
+        let generation = generation.clone();
+        spawn_local(async move {
+            let formatted = l10n.format_value(&id, args.as_ref()).await;
+            // Drop the result if a newer request was issued in the meantime.
+            if generation.get() == current {
+                set_value.set(formatted);
+            }
+        });
+    });
+
+    value.into()
+}
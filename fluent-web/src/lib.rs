@@ -0,0 +1,47 @@
+//! Fluent is a modern localization system designed to improve how software is translated.
+//!
+//! `fluent-web` connects [`fluent-fallback`](fluent_fallback) to Rust web frontends
+//! compiled to WebAssembly.
+//!
+//! The crate provides three building blocks:
+//!
+//! * [`FetchGenerator`], a [`BundleGenerator`](fluent_fallback::generator::BundleGenerator)
+//!   which downloads resources over HTTP using the browser `fetch` API and caches them
+//!   for the lifetime of the page.
+//! * [`Locales`], a shared, mutable [`LocalesProvider`](fluent_fallback::env::LocalesProvider).
+//! * [`WebLocalization`], an asynchronous [`Localization`](fluent_fallback::Localization)
+//!   wired to the two above, which re-generates its bundles whenever the locales change.
+//!
+//! On top of those, the `leptos` and `yew` features expose a `use_translation` hook and
+//! a locale signal, so that switching languages re-renders only the translated nodes.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use fluent_web::{FetchGenerator, WebLocalization};
+//! use unic_langid::langid;
+//!
+//! let generator = FetchGenerator::new("/locales/{locale}/{res_id}".to_string());
+//! let l10n = WebLocalization::new(
+//!     vec!["main.ftl".into()],
+//!     vec![langid!("en-US")],
+//!     generator,
+//! );
+//!
+//! wasm_bindgen_futures::spawn_local(async move {
+//!     let value = l10n.format_value("hello-world", None).await;
+//!     l10n.set_locales(vec![langid!("pl"), langid!("en-US")]);
+//! });
+//! ```
+mod fetch;
+mod l10n;
+mod locales;
+
+#[cfg(feature = "leptos")]
+pub mod leptos;
+#[cfg(feature = "yew")]
+pub mod yew;
+
+pub use fetch::{FetchGenerator, FetchStream};
+pub use l10n::WebLocalization;
+pub use locales::Locales;
@@ -0,0 +1,40 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fluent_fallback::env::LocalesProvider;
+use unic_langid::LanguageIdentifier;
+
+/// A shared list of locales, in order of preference.
+///
+/// Clones refer to the same list, so the instance handed to a
+/// [`Localization`](fluent_fallback::Localization) can be updated from the outside.
+/// After updating, [`Localization::on_change`](fluent_fallback::Localization::on_change)
+/// must be called for the new locales to take effect; [`WebLocalization`](crate::WebLocalization)
+/// does this for you.
+#[derive(Clone, Debug, Default)]
+pub struct Locales(Rc<RefCell<Vec<LanguageIdentifier>>>);
+
+impl Locales {
+    /// Create a new provider with the given locales.
+    pub fn new(locales: Vec<LanguageIdentifier>) -> Self {
+        Self(Rc::new(RefCell::new(locales)))
+    }
+
+    /// Returns a copy of the current locales.
+    pub fn get(&self) -> Vec<LanguageIdentifier> {
+        self.0.borrow().clone()
+    }
+
+    /// Replace the current locales.
+    pub fn set(&self, locales: Vec<LanguageIdentifier>) {
+        *self.0.borrow_mut() = locales;
+    }
+}
+
+impl LocalesProvider for Locales {
+    type Iter = <Vec<LanguageIdentifier> as IntoIterator>::IntoIter;
+
+    fn locales(&self) -> Self::Iter {
+        self.get().into_iter()
+    }
+}
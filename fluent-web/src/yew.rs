@@ -0,0 +1,160 @@
+//! Hooks for [yew](https://yew.rs).
+//!
+//! Wrap the application in a [`LocalizationProvider`], then use
+//! [`use_translation`] in any function component below it:
+//!
+//! ```no_run
+//! use fluent_bundle::FluentArgs;
+//! use fluent_web::yew::{use_locales, use_translation, LocalizationProvider};
+//! use fluent_web::{FetchGenerator, WebLocalization};
+//! use unic_langid::langid;
+//! use yew::prelude::*;
+//!
+//! #[function_component]
+//! fn Inbox() -> Html {
+//!     let mut args = FluentArgs::new();
+//!     args.set("count", 5);
+//!     let emails = use_translation("emails", Some(args));
+//!     let locales = use_locales();
+//!     let onclick = Callback::from(move |_| locales.set(vec![langid!("pl")]));
+//!
+//!     html! {
+//!         <>
+//!             <p>{ emails }</p>
+//!             <button {onclick}>{ "PL" }</button>
+//!         </>
+//!     }
+//! }
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     let l10n = WebLocalization::new(
+//!         vec!["main.ftl".into()],
+//!         vec![langid!("en-US")],
+//!         FetchGenerator::new("/locales/{locale}/{res_id}".to_string()),
+//!     );
+//!
+//!     html! {
+//!         <LocalizationProvider {l10n}>
+//!             <Inbox />
+//!         </LocalizationProvider>
+//!     }
+//! }
+//! ```
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fluent_bundle::FluentArgs;
+use unic_langid::LanguageIdentifier;
+use yew::prelude::*;
+
+use crate::WebLocalization;
+
+#[derive(Clone)]
+struct LocalizationContext {
+    l10n: WebLocalization,
+    locales: UseStateHandle<Vec<LanguageIdentifier>>,
+}
+
+impl PartialEq for LocalizationContext {
+    fn eq(&self, other: &Self) -> bool {
+        self.l10n.ptr_eq(&other.l10n) && self.locales == other.locales
+    }
+}
+
+/// Properties of a [`LocalizationProvider`].
+#[derive(Properties)]
+pub struct LocalizationProviderProps {
+    /// The localization made available to descendants.
+    pub l10n: WebLocalization,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+impl PartialEq for LocalizationProviderProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.l10n.ptr_eq(&other.l10n) && self.children == other.children
+    }
+}
+
+/// Makes a [`WebLocalization`] available to its descendants.
+#[function_component]
+pub fn LocalizationProvider(props: &LocalizationProviderProps) -> Html {
+    let locales = use_state(|| props.l10n.locales());
+    let context = LocalizationContext {
+        l10n: props.l10n.clone(),
+        locales,
+    };
+
+    html! {
+        <ContextProvider<LocalizationContext> {context}>
+            { props.children.clone() }
+        </ContextProvider<LocalizationContext>>
+    }
+}
+
+/// A handle to the locale selection of the nearest [`LocalizationProvider`].
+#[derive(Clone)]
+pub struct UseLocalesHandle {
+    context: LocalizationContext,
+}
+
+impl UseLocalesHandle {
+    /// Returns the current locales.
+    pub fn get(&self) -> Vec<LanguageIdentifier> {
+        (*self.context.locales).clone()
+    }
+
+    /// Switch to a new set of locales, re-rendering every translation.
+    pub fn set(&self, locales: Vec<LanguageIdentifier>) {
+        // Update the localization before scheduling a re-render, so that
+        // translations format against the new bundles.
+        self.context.l10n.set_locales(locales.clone());
+        self.context.locales.set(locales);
+    }
+}
+
+/// Returns a handle to the locale selection of the nearest [`LocalizationProvider`].
+#[hook]
+pub fn use_locales() -> UseLocalesHandle {
+    let context = use_context::<LocalizationContext>()
+        .expect("`use_locales` must be used below a `LocalizationProvider`");
+    UseLocalesHandle { context }
+}
+
+/// Returns the translation of the message `id`.
+///
+/// The translation is refreshed whenever the locales, `id` or `args` change.
+/// Until the first translation resolves, an empty string is returned.
+#[hook]
+pub fn use_translation(id: &str, args: Option<FluentArgs<'static>>) -> String {
+    let context = use_context::<LocalizationContext>()
+        .expect("`use_translation` must be used below a `LocalizationProvider`");
+    let value = use_state(String::new);
+
+    {
+        let value = value.clone();
+        let l10n = context.l10n.clone();
+        use_effect_with(
+            (Rc::<str>::from(id), context.locales.clone(), args),
+            move |(id, _, args)| {
+                let id = id.clone();
+                let args = args.clone();
+                let stale = Rc::new(Cell::new(false));
+                {
+                    let stale = stale.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let formatted = l10n.format_value(&id, args.as_ref()).await;
+                        // Drop the result if the dependencies changed in the meantime.
+                        if !stale.get() {
+                            value.set(formatted);
+                        }
+                    });
+                }
+                move || stale.set(true)
+            },
+        );
+    }
+
+    (*value).clone()
+}
@@ -0,0 +1,72 @@
+#![cfg(all(target_arch = "wasm32", feature = "leptos"))]
+
+use fluent_bundle::FluentArgs;
+use fluent_web::leptos::{provide_localization, use_locales, use_translation};
+use fluent_web::{FetchGenerator, WebLocalization};
+use gloo_timers::future::TimeoutFuture;
+use leptos::*;
+use unic_langid::LanguageIdentifier;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Resources are fetched from `data:` URLs defining `hello = Hello { $name }`
+/// and `locale = {locale}`.
+const PATH_SCHEME: &str =
+    "data:text/plain,hello%20%3D%20Hello%20%7B%20%24name%20%7D%0Alocale%20%3D%20{locale}";
+
+fn langids(locales: &[&str]) -> Vec<LanguageIdentifier> {
+    locales
+        .iter()
+        .map(|locale| locale.parse().unwrap())
+        .collect()
+}
+
+/// Waits for `signal` to hold `expected`, as translations resolve
+/// asynchronously.
+async fn wait_for(signal: Signal<String>, expected: &str) {
+    for _ in 0..100 {
+        if signal.get_untracked() == expected {
+            return;
+        }
+        TimeoutFuture::new(10).await;
+    }
+    assert_eq!(signal.get_untracked(), expected);
+}
+
+#[wasm_bindgen_test]
+async fn translations_follow_locales_and_args() {
+    let runtime = create_runtime();
+    provide_localization(WebLocalization::new(
+        vec!["main.ftl".into()],
+        langids(&["en-US"]),
+        FetchGenerator::new(PATH_SCHEME.to_string()),
+    ));
+
+    let locale = use_translation("locale", || None);
+    let (name, set_name) = create_signal("Anna");
+    let hello = use_translation("hello", move || {
+        let mut args = FluentArgs::new();
+        args.set("name", name.get());
+        Some(args)
+    });
+    let missing = use_translation("missing", || None);
+
+    // Translations are empty until they resolve.
+    assert_eq!(locale.get_untracked(), "");
+    wait_for(locale, "en-US").await;
+    wait_for(hello, "Hello \u{2068}Anna\u{2069}").await;
+    wait_for(missing, "missing").await;
+
+    set_name.set("John");
+    wait_for(hello, "Hello \u{2068}John\u{2069}").await;
+
+    let locales = use_locales();
+    assert_eq!(locales.get(), langids(&["en-US"]));
+    locales.set(langids(&["pl"]));
+    assert_eq!(locales.get(), langids(&["pl"]));
+    wait_for(locale, "pl").await;
+    wait_for(hello, "Hello \u{2068}John\u{2069}").await;
+
+    runtime.dispose();
+}
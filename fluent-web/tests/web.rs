@@ -0,0 +1,107 @@
+#![cfg(target_arch = "wasm32")]
+
+use fluent_bundle::FluentArgs;
+use fluent_fallback::env::LocalesProvider;
+use fluent_fallback::types::{ResourceId, ResourceType};
+use fluent_web::{FetchGenerator, Locales, WebLocalization};
+use unic_langid::LanguageIdentifier;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Resources are fetched from `data:` URLs, whose content is the resource
+/// identifier followed by messages naming the locale, such as
+/// `only-en-US = Only en-US`.
+const PATH_SCHEME: &str =
+    "data:text/plain,{res_id}%0Alocale%20%3D%20{locale}%0Aonly-{locale}%20%3D%20Only%20{locale}";
+
+/// `hello = Hello { $name }`
+const HELLO: &str = "hello%20%3D%20Hello%20%7B%20%24name%20%7D";
+
+fn langids(locales: &[&str]) -> Vec<LanguageIdentifier> {
+    locales
+        .iter()
+        .map(|locale| locale.parse().unwrap())
+        .collect()
+}
+
+fn get_localization(res_ids: Vec<ResourceId>, locales: &[&str]) -> WebLocalization {
+    WebLocalization::new(
+        res_ids,
+        langids(locales),
+        FetchGenerator::new(PATH_SCHEME.to_string()),
+    )
+}
+
+#[wasm_bindgen_test]
+fn locales_are_shared_between_clones() {
+    let locales = Locales::new(langids(&["en-US"]));
+    let clone = locales.clone();
+    clone.set(langids(&["pl", "en-US"]));
+    assert_eq!(locales.get(), langids(&["pl", "en-US"]));
+    assert_eq!(
+        LocalesProvider::locales(&locales).collect::<Vec<_>>(),
+        langids(&["pl", "en-US"])
+    );
+}
+
+#[wasm_bindgen_test]
+async fn formats_fetched_resources() {
+    let l10n = get_localization(vec![HELLO.into()], &["en-US"]);
+
+    let mut args = FluentArgs::new();
+    args.set("name", "Anna");
+    assert_eq!(
+        l10n.format_value("hello", Some(&args)).await,
+        "Hello \u{2068}Anna\u{2069}"
+    );
+    assert_eq!(l10n.format_value("locale", None).await, "en-US");
+    // Missing messages are formatted as their identifier.
+    assert_eq!(l10n.format_value("missing", None).await, "missing");
+}
+
+#[wasm_bindgen_test]
+async fn switches_locales() {
+    let l10n = get_localization(vec![HELLO.into()], &["en-US"]);
+    let clone = l10n.clone();
+    assert!(l10n.ptr_eq(&clone));
+    assert_eq!(l10n.format_value("locale", None).await, "en-US");
+
+    clone.set_locales(langids(&["pl", "en-US"]));
+    assert_eq!(l10n.locales(), langids(&["pl", "en-US"]));
+    assert_eq!(l10n.format_value("locale", None).await, "pl");
+    // Messages missing in the first locale fall back to the next one.
+    assert_eq!(l10n.format_value("only-en-US", None).await, "Only en-US");
+    assert_eq!(l10n.format_value("only-pl", None).await, "Only pl");
+
+    l10n.set_locales(langids(&["en-US"]));
+    assert_eq!(l10n.format_value("locale", None).await, "en-US");
+    assert_eq!(l10n.format_value("only-pl", None).await, "only-pl");
+}
+
+#[wasm_bindgen_test]
+async fn skips_locales_missing_required_resources() {
+    // The identifiers of the resources are URLs to which the locale is
+    // appended, and the `invalid:` ones fail to load.
+    let generator = FetchGenerator::new("{res_id}{locale}".to_string());
+    let locale = || ResourceId::from("data:text/plain,locale%20%3D%20");
+    let l10n = WebLocalization::new(
+        vec![
+            locale(),
+            ResourceId::new("invalid:", ResourceType::Required),
+        ],
+        langids(&["en-US"]),
+        generator.clone(),
+    );
+    assert_eq!(l10n.format_value("locale", None).await, "locale");
+
+    let l10n = WebLocalization::new(
+        vec![
+            locale(),
+            ResourceId::new("invalid:", ResourceType::Optional),
+        ],
+        langids(&["en-US"]),
+        generator,
+    );
+    assert_eq!(l10n.format_value("locale", None).await, "en-US");
+}
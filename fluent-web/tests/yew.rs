@@ -0,0 +1,102 @@
+#![cfg(all(target_arch = "wasm32", feature = "yew"))]
+
+use fluent_bundle::FluentArgs;
+use fluent_web::yew::{use_locales, use_translation, LocalizationProvider};
+use fluent_web::{FetchGenerator, WebLocalization};
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::{Element, HtmlElement};
+use yew::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Resources are fetched from `data:` URLs defining `hello = Hello { $name }`
+/// and `locale = {locale}`.
+const PATH_SCHEME: &str =
+    "data:text/plain,hello%20%3D%20Hello%20%7B%20%24name%20%7D%0Alocale%20%3D%20{locale}";
+
+#[function_component]
+fn Translations() -> Html {
+    let locale = use_translation("locale", None);
+    let mut args = FluentArgs::new();
+    args.set("name", "Anna");
+    let hello = use_translation("hello", Some(args));
+    let missing = use_translation("missing", None);
+    let locales = use_locales();
+    let onclick = Callback::from(move |_| locales.set(vec!["pl".parse().unwrap()]));
+
+    html! {
+        <>
+            <p class="locale">{ locale }</p>
+            <p class="hello">{ hello }</p>
+            <p class="missing">{ missing }</p>
+            <button {onclick}>{ "PL" }</button>
+        </>
+    }
+}
+
+#[derive(Properties)]
+struct AppProps {
+    l10n: WebLocalization,
+}
+
+impl PartialEq for AppProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.l10n.ptr_eq(&other.l10n)
+    }
+}
+
+#[function_component]
+fn App(props: &AppProps) -> Html {
+    html! {
+        <LocalizationProvider l10n={props.l10n.clone()}>
+            <Translations />
+        </LocalizationProvider>
+    }
+}
+
+fn query(root: &Element, selector: &str) -> Element {
+    root.query_selector(selector).unwrap().unwrap()
+}
+
+/// Waits for the element matching `selector` to contain `expected`, as
+/// translations resolve asynchronously.
+async fn wait_for(root: &Element, selector: &str, expected: &str) {
+    for _ in 0..100 {
+        if query(root, selector).text_content().as_deref() == Some(expected) {
+            return;
+        }
+        TimeoutFuture::new(10).await;
+    }
+    assert_eq!(
+        query(root, selector).text_content().as_deref(),
+        Some(expected)
+    );
+}
+
+#[wasm_bindgen_test]
+async fn translations_follow_locales() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+
+    let l10n = WebLocalization::new(
+        vec!["main.ftl".into()],
+        vec!["en-US".parse().unwrap()],
+        FetchGenerator::new(PATH_SCHEME.to_string()),
+    );
+    yew::Renderer::<App>::with_root_and_props(root.clone(), AppProps { l10n: l10n.clone() })
+        .render();
+
+    wait_for(&root, ".locale", "en-US").await;
+    wait_for(&root, ".hello", "Hello \u{2068}Anna\u{2069}").await;
+    wait_for(&root, ".missing", "missing").await;
+
+    query(&root, "button")
+        .unchecked_into::<HtmlElement>()
+        .click();
+    wait_for(&root, ".locale", "pl").await;
+    wait_for(&root, ".hello", "Hello \u{2068}Anna\u{2069}").await;
+    assert_eq!(l10n.locales(), vec!["pl"]);
+}
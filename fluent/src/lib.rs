@@ -77,18 +77,28 @@
 
 pub use fluent_bundle::*;
 
+#[cfg(any(feature = "askama", feature = "tera"))]
+pub mod templates;
+
 /// A helper macro to simplify creation of `FluentArgs`.
 ///
+/// Values which convert to [`FluentValue::None`], such as `Option<T>` values
+/// which are `None`, are skipped, so that the argument is left unset.
+///
 /// # Example
 ///
 /// ```
 /// use fluent::fluent_args;
 ///
+/// let title: Option<&str> = None;
 /// let mut args = fluent_args![
 ///     "name" => "John",
 ///     "emailCount" => 5,
+///     "title" => title,
 /// ];
 ///
+/// assert_eq!(args.get_str("name"), Some("John"));
+/// assert_eq!(args.get("title"), None);
 /// ```
 #[macro_export]
 macro_rules! fluent_args {
@@ -96,7 +106,10 @@ macro_rules! fluent_args {
         {
             let mut args: $crate::FluentArgs = $crate::FluentArgs::new();
             $(
-                args.set($key, $value);
+                let value: $crate::FluentValue = $value.into();
+                if !matches!(value, $crate::FluentValue::None) {
+                    args.set($key, value);
+                }
             )*
             args
         }
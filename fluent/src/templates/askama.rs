@@ -0,0 +1,113 @@
+//! Filters for [`askama`](https://crates.io/crates/askama) templates.
+//!
+//! Askama looks up custom filters in a `filters` module in scope of the template
+//! struct, so the filters have to be re-exported there:
+//!
+//! ```text
+//! mod filters {
+//!     pub use fluent::templates::askama::{fluent, fluent_args};
+//! }
+//! ```
+//!
+//! [`fluent_strict`] and [`fluent_args_strict`] also fail the render when
+//! resolving the message reports an error, such as a missing argument, which
+//! [`fluent`] and [`fluent_args`] write as its fallback.
+//!
+//! The bundle is passed to the filter as an argument, which allows each request
+//! to render with the bundle negotiated for its locale:
+//!
+//! ```text
+//! <h1>{{ "cart-title"|fluent(bundle) }}</h1>
+//! <p>{{ "cart-summary"|fluent_args(bundle, args) }}</p>
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use fluent::{FluentArgs, FluentBundle, FluentResource};
+//! use fluent::templates::askama::{fluent, fluent_args, fluent_args_strict};
+//!
+//! let res = FluentResource::try_new(String::from("
+//! cart-title = Your cart
+//! cart-summary = { $count } items
+//!     .title = Summary
+//! ")).expect("Failed to parse an FTL string.");
+//!
+//! let mut bundle = FluentBundle::default();
+//! bundle.set_use_isolating(false);
+//! bundle.add_resource(res).expect("Failed to add a resource.");
+//!
+//! let mut args = FluentArgs::new();
+//! args.set("count", 3);
+//!
+//! assert_eq!(fluent("cart-title", &bundle).unwrap(), "Your cart");
+//! assert_eq!(fluent("cart-summary.title", &bundle).unwrap(), "Summary");
+//! assert_eq!(fluent_args("cart-summary", &bundle, &args).unwrap(), "3 items");
+//! assert!(fluent("missing", &bundle).is_err());
+//!
+//! let args = FluentArgs::new();
+//! assert_eq!(fluent_args("cart-summary", &bundle, &args).unwrap(), "{$count} items");
+//! assert!(fluent_args_strict("cart-summary", &bundle, &args).is_err());
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt;
+
+use fluent_bundle::memoizer::MemoizerKind;
+use fluent_bundle::{bundle::FluentBundle, FluentArgs, FluentResource};
+
+/// Formats the message, or the `message.attribute`, named by `id` using `bundle`.
+pub fn fluent<T, R, M>(id: T, bundle: &FluentBundle<R, M>) -> ::askama::Result<String>
+where
+    T: fmt::Display,
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    super::format_message(bundle, &id.to_string(), None, false)
+        .map_err(|err| ::askama::Error::Custom(Box::new(err)))
+}
+
+/// Formats the message, or the `message.attribute`, named by `id` using `bundle`
+/// and the provided arguments.
+pub fn fluent_args<T, R, M>(
+    id: T,
+    bundle: &FluentBundle<R, M>,
+    args: &FluentArgs,
+) -> ::askama::Result<String>
+where
+    T: fmt::Display,
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    super::format_message(bundle, &id.to_string(), Some(args), false)
+        .map_err(|err| ::askama::Error::Custom(Box::new(err)))
+}
+
+/// Formats the message, or the `message.attribute`, named by `id` using `bundle`,
+/// failing on the first error reported while resolving it.
+pub fn fluent_strict<T, R, M>(id: T, bundle: &FluentBundle<R, M>) -> ::askama::Result<String>
+where
+    T: fmt::Display,
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    super::format_message(bundle, &id.to_string(), None, true)
+        .map_err(|err| ::askama::Error::Custom(Box::new(err)))
+}
+
+/// Formats the message, or the `message.attribute`, named by `id` using `bundle`
+/// and the provided arguments, failing on the first error reported while
+/// resolving it.
+pub fn fluent_args_strict<T, R, M>(
+    id: T,
+    bundle: &FluentBundle<R, M>,
+    args: &FluentArgs,
+) -> ::askama::Result<String>
+where
+    T: fmt::Display,
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    super::format_message(bundle, &id.to_string(), Some(args), true)
+        .map_err(|err| ::askama::Error::Custom(Box::new(err)))
+}
@@ -0,0 +1,74 @@
+//! Integrations with template engines.
+//!
+//! Server-rendered templates can resolve Fluent messages directly, instead of
+//! having every handler pre-format the strings a template needs.
+//!
+//! Each engine lives behind its own feature:
+//!
+//! * `askama` - filter functions for [`askama`](https://crates.io/crates/askama) templates.
+//! * `tera` - a filter that can be registered on a [`tera`](https://crates.io/crates/tera) instance.
+//!
+//! Messages are referenced by their id, and attributes with the `id.attribute` notation.
+//! A missing message or attribute fails the render. Errors resolving the pattern, such as
+//! a missing argument, are written as their fallback, like `{ $count }`, unless the strict
+//! variant of the filter is used, which fails the render with them instead.
+
+#[cfg(feature = "askama")]
+pub mod askama;
+#[cfg(feature = "tera")]
+pub mod tera;
+
+use std::borrow::Borrow;
+
+use fluent_bundle::memoizer::MemoizerKind;
+use fluent_bundle::resolver::errors::{ReferenceKind, ResolverError};
+use fluent_bundle::{bundle, FluentArgs, FluentError, FluentResource};
+
+/// Formats the message or attribute identified by `path` in `bundle`.
+///
+/// A missing message, attribute or value is always reported as an error.
+/// Other errors encountered while resolving the pattern, such as a missing
+/// argument, don't prevent the output from being produced, which matches the
+/// behavior of
+/// [`FluentBundle::format_pattern`](fluent_bundle::bundle::FluentBundle::format_pattern),
+/// unless `strict` is set, in which case the first of them is returned
+/// instead, like
+/// [`FluentBundle::format_pattern_strict`](fluent_bundle::bundle::FluentBundle::format_pattern_strict)
+/// does.
+pub(crate) fn format_message<R, M>(
+    bundle: &bundle::FluentBundle<R, M>,
+    path: &str,
+    args: Option<&FluentArgs>,
+    strict: bool,
+) -> Result<String, FluentError>
+where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    let (id, attribute) = match path.split_once('.') {
+        Some((id, attribute)) => (id, Some(attribute)),
+        None => (path, None),
+    };
+
+    let missing = || {
+        FluentError::from(ResolverError::Reference(ReferenceKind::Message {
+            id: id.to_string(),
+            attribute: attribute.map(ToString::to_string),
+        }))
+    };
+
+    let msg = bundle.get_message(id).ok_or_else(missing)?;
+    let pattern = match attribute {
+        Some(attribute) => msg.get_attribute(attribute).ok_or_else(missing)?.value(),
+        None => msg
+            .value()
+            .ok_or_else(|| FluentError::from(ResolverError::NoValue(id.to_string())))?,
+    };
+
+    let mut errors = vec![];
+    let value = bundle.format_pattern(pattern, args, &mut errors);
+    match errors.into_iter().next() {
+        Some(error) if strict => Err(error),
+        _ => Ok(value.into_owned()),
+    }
+}
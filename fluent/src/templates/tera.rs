@@ -0,0 +1,151 @@
+//! A filter for [`tera`](https://crates.io/crates/tera) templates.
+//!
+//! [`FluentFilter`] holds the bundles of all supported locales, and each render
+//! selects one with the `lang` argument, so a single [`Tera`](::tera::Tera) instance
+//! can serve requests in different locales. All other named arguments are passed
+//! to the message as Fluent arguments.
+//!
+//! ```text
+//! {{ "cart-summary" | fluent(lang=lang, count=items) }}
+//! ```
+//!
+//! Errors resolving a message, such as a missing argument, are written as their
+//! fallback unless the filter is made strict with [`FluentFilter::set_strict`].
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use fluent::concurrent::FluentBundle;
+//! use fluent::templates::tera::FluentFilter;
+//! use fluent::FluentResource;
+//!
+//! let mut bundles = vec![];
+//! for (lang, source) in [
+//!     ("en-US", "cart-summary = { $count } items in your cart"),
+//!     ("pl", "cart-summary = Przedmioty w koszyku: { $count }"),
+//! ] {
+//!     let res = FluentResource::try_new(source.to_string())
+//!         .expect("Failed to parse an FTL string.");
+//!     let mut bundle = FluentBundle::new_concurrent(vec![lang.parse().unwrap()]);
+//!     bundle.set_use_isolating(false);
+//!     bundle.add_resource(res).expect("Failed to add a resource.");
+//!     bundles.push(Arc::new(bundle));
+//! }
+//!
+//! let mut tera = tera::Tera::default();
+//! tera.register_filter("fluent", FluentFilter::new(bundles));
+//! tera.add_raw_template("cart", r#"{{ "cart-summary" | fluent(lang=lang, count=items) }}"#)
+//!     .unwrap();
+//!
+//! let mut context = tera::Context::new();
+//! context.insert("items", &3);
+//!
+//! context.insert("lang", "en-US");
+//! assert_eq!(tera.render("cart", &context).unwrap(), "3 items in your cart");
+//!
+//! context.insert("lang", "pl");
+//! assert_eq!(tera.render("cart", &context).unwrap(), "Przedmioty w koszyku: 3");
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use tera::{Error, Filter, Result, Value};
+use unic_langid::LanguageIdentifier;
+
+/// A [`Filter`] resolving Fluent messages from a set of concurrent bundles.
+pub struct FluentFilter<R> {
+    bundles: Vec<Arc<FluentBundle<R>>>,
+    strict: bool,
+}
+
+impl<R> FluentFilter<R> {
+    /// Creates a filter over `bundles`.
+    ///
+    /// The first bundle is used when the template doesn't pass a `lang`
+    /// argument, or when no bundle matches it.
+    pub fn new(bundles: Vec<Arc<FluentBundle<R>>>) -> Self {
+        Self {
+            bundles,
+            strict: false,
+        }
+    }
+
+    /// Sets whether an error reported while resolving a message, such as a
+    /// missing argument, fails the render. By default, the message is written
+    /// with the fallback of the failed placeable, like `{ $count }`.
+    ///
+    /// A missing message or attribute fails the render either way.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    fn get_bundle(&self, lang: Option<&str>) -> Option<&FluentBundle<R>> {
+        let langid = lang.and_then(|lang| lang.parse::<LanguageIdentifier>().ok());
+        let position = langid.and_then(|langid| {
+            let primary = |bundle: &Arc<FluentBundle<R>>| bundle.locales.first().cloned();
+            self.bundles
+                .iter()
+                .position(|bundle| primary(bundle).as_ref() == Some(&langid))
+                .or_else(|| {
+                    self.bundles.iter().position(|bundle| {
+                        primary(bundle).map_or(false, |primary| primary.language == langid.language)
+                    })
+                })
+        });
+        self.bundles.get(position.unwrap_or(0)).map(AsRef::as_ref)
+    }
+}
+
+fn to_fluent_value<'v>(key: &str, value: &'v Value) -> Result<FluentValue<'v>> {
+    match value {
+        Value::Null => Ok(FluentValue::None),
+        Value::Bool(b) => Ok(b.to_string().into()),
+        Value::Number(n) => n
+            .as_i64()
+            .map(FluentValue::from)
+            .or_else(|| n.as_f64().map(FluentValue::from))
+            .ok_or_else(|| Error::msg(format!("Unsupported number in argument `{}`", key))),
+        Value::String(s) => Ok(s.as_str().into()),
+        Value::Array(_) | Value::Object(_) => Err(Error::msg(format!(
+            "Argument `{}` must be a string, a number, a boolean or null",
+            key
+        ))),
+    }
+}
+
+impl<R> Filter for FluentFilter<R>
+where
+    R: Borrow<FluentResource> + Send + Sync,
+{
+    fn filter(&self, value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+        let id = value
+            .as_str()
+            .ok_or_else(|| Error::msg("Filter `fluent` expects a message id string"))?;
+
+        let lang = match args.get("lang") {
+            Some(Value::String(lang)) => Some(lang.as_str()),
+            Some(_) => return Err(Error::msg("Argument `lang` must be a string")),
+            None => None,
+        };
+        let bundle = self
+            .get_bundle(lang)
+            .ok_or_else(|| Error::msg("Filter `fluent` has no bundles"))?;
+
+        let mut fluent_args = FluentArgs::with_capacity(args.len());
+        for (key, value) in args {
+            if key != "lang" {
+                fluent_args.set(key.as_str(), to_fluent_value(key, value)?);
+            }
+        }
+
+        super::format_message(bundle, id, Some(&fluent_args), self.strict)
+            .map(Value::String)
+            .map_err(|err| Error::msg(err.to_string()))
+    }
+}
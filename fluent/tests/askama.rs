@@ -0,0 +1,150 @@
+#![cfg(feature = "askama")]
+
+use askama::Template;
+use fluent::{FluentArgs, FluentBundle, FluentResource};
+
+mod filters {
+    pub use fluent::templates::askama::{fluent, fluent_args, fluent_args_strict, fluent_strict};
+}
+
+fn get_bundle() -> FluentBundle<FluentResource> {
+    let res = FluentResource::try_new(String::from(
+        "
+cart-title = Your cart
+cart-summary = { $count ->
+    [one] One item for { $user }
+   *[other] { $count } items for { $user }
+}
+    .title = Summary of { $user }
+",
+    ))
+    .expect("Failed to parse an FTL string.");
+
+    let mut bundle = FluentBundle::new(vec!["en-US".parse().unwrap()]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(res).expect("Failed to add a resource.");
+    bundle
+}
+
+#[derive(Template)]
+#[template(
+    source = r#"<h1>{{ "cart-title"|fluent(bundle) }}</h1><p>{{ "cart-summary"|fluent_args(bundle, args) }}</p>"#,
+    ext = "html"
+)]
+struct Cart<'a> {
+    bundle: &'a FluentBundle<FluentResource>,
+    args: &'a FluentArgs<'a>,
+}
+
+#[derive(Template)]
+#[template(source = r#"{{ id|fluent_args(bundle, args) }}"#, ext = "txt")]
+struct Message<'a> {
+    id: &'a str,
+    bundle: &'a FluentBundle<FluentResource>,
+    args: &'a FluentArgs<'a>,
+}
+
+#[derive(Template)]
+#[template(source = r#"{{ id|fluent_args_strict(bundle, args) }}"#, ext = "txt")]
+struct StrictMessage<'a> {
+    id: &'a str,
+    bundle: &'a FluentBundle<FluentResource>,
+    args: &'a FluentArgs<'a>,
+}
+
+#[test]
+fn renders_messages_with_args() {
+    let bundle = get_bundle();
+    let mut args = FluentArgs::new();
+    args.set("count", 3);
+    args.set("user", "Anna");
+
+    let cart = Cart {
+        bundle: &bundle,
+        args: &args,
+    };
+    assert_eq!(
+        cart.render().unwrap(),
+        "<h1>Your cart</h1><p>3 items for Anna</p>"
+    );
+
+    args.set("count", 1);
+    let cart = Cart {
+        bundle: &bundle,
+        args: &args,
+    };
+    assert_eq!(
+        cart.render().unwrap(),
+        "<h1>Your cart</h1><p>One item for Anna</p>"
+    );
+}
+
+#[test]
+fn renders_attributes() {
+    let bundle = get_bundle();
+    let mut args = FluentArgs::new();
+    args.set("user", "Anna");
+
+    let message = Message {
+        id: "cart-summary.title",
+        bundle: &bundle,
+        args: &args,
+    };
+    assert_eq!(message.render().unwrap(), "Summary of Anna");
+    assert_eq!(filters::fluent("cart-title", &bundle).unwrap(), "Your cart");
+}
+
+#[test]
+fn missing_messages_fail_the_render() {
+    let bundle = get_bundle();
+    let args = FluentArgs::new();
+
+    for id in [
+        "missing",
+        "cart-title.missing",
+        "cart-summary.title.missing",
+    ] {
+        let message = Message {
+            id,
+            bundle: &bundle,
+            args: &args,
+        };
+        assert!(message.render().is_err(), "{} rendered", id);
+    }
+    assert!(filters::fluent("missing", &bundle).is_err());
+    assert!(filters::fluent_strict("missing", &bundle).is_err());
+}
+
+#[test]
+fn strict_filters_fail_on_resolver_errors() {
+    let bundle = get_bundle();
+    let mut args = FluentArgs::new();
+    args.set("user", "Anna");
+
+    let message = Message {
+        id: "cart-summary",
+        bundle: &bundle,
+        args: &args,
+    };
+    assert_eq!(message.render().unwrap(), "{$count} items for Anna");
+
+    let message = StrictMessage {
+        id: "cart-summary",
+        bundle: &bundle,
+        args: &args,
+    };
+    let error = message.render().unwrap_err();
+    assert!(error.to_string().contains("count"), "{}", error);
+
+    args.set("count", 2);
+    let message = StrictMessage {
+        id: "cart-summary",
+        bundle: &bundle,
+        args: &args,
+    };
+    assert_eq!(message.render().unwrap(), "2 items for Anna");
+    assert_eq!(
+        filters::fluent_strict("cart-title", &bundle).unwrap(),
+        "Your cart"
+    );
+}
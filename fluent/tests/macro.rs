@@ -19,3 +19,17 @@ fn test_fluent_args() {
         Some(&FluentValue::String(Cow::Borrowed("My Value")))
     );
 }
+
+#[test]
+fn test_fluent_args_optional_values() {
+    let title: Option<&str> = None;
+    let count = Some(3);
+    let args = fluent_args![
+        "name" => "John",
+        "title" => title,
+        "count" => count,
+    ];
+    assert_eq!(args.get("title"), None);
+    assert_eq!(args.get("count"), Some(&FluentValue::try_number("3")));
+    assert_eq!(args.iter().count(), 2);
+}
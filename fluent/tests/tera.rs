@@ -0,0 +1,149 @@
+#![cfg(feature = "tera")]
+
+use std::sync::Arc;
+
+use fluent::concurrent::FluentBundle;
+use fluent::templates::tera::FluentFilter;
+use fluent::FluentResource;
+use tera::{Context, Tera};
+
+fn get_bundles() -> Vec<Arc<FluentBundle<FluentResource>>> {
+    let mut bundles = vec![];
+    for (lang, source) in [
+        (
+            "en-US",
+            "
+cart-summary = { $count ->
+    [one] One item for { $user }
+   *[other] { $count } items for { $user }
+}
+    .title = Summary of { $user }
+",
+        ),
+        (
+            "pl",
+            "
+cart-summary = Przedmioty w koszyku: { $count }
+    .title = Podsumowanie
+",
+        ),
+    ] {
+        let res =
+            FluentResource::try_new(source.to_string()).expect("Failed to parse an FTL string.");
+        let mut bundle = FluentBundle::new_concurrent(vec![lang.parse().unwrap()]);
+        bundle.set_use_isolating(false);
+        bundle.add_resource(res).expect("Failed to add a resource.");
+        bundles.push(Arc::new(bundle));
+    }
+    bundles
+}
+
+fn get_tera(filter: FluentFilter<FluentResource>) -> Tera {
+    let mut tera = Tera::default();
+    tera.register_filter("fluent", filter);
+    tera.add_raw_templates([
+        (
+            "summary",
+            r#"{{ "cart-summary" | fluent(lang=lang, count=count, user=user) }}"#,
+        ),
+        (
+            "title",
+            r#"{{ "cart-summary.title" | fluent(lang=lang, user=user) }}"#,
+        ),
+        ("missing", r#"{{ id | fluent(lang=lang) }}"#),
+        ("no-count", r#"{{ "cart-summary" | fluent(user=user) }}"#),
+    ])
+    .unwrap();
+    tera
+}
+
+#[test]
+fn renders_messages_with_args() {
+    let tera = get_tera(FluentFilter::new(get_bundles()));
+    let mut context = Context::new();
+    context.insert("lang", "en-US");
+    context.insert("user", "Anna");
+
+    context.insert("count", &1);
+    assert_eq!(
+        tera.render("summary", &context).unwrap(),
+        "One item for Anna"
+    );
+    context.insert("count", &3);
+    assert_eq!(
+        tera.render("summary", &context).unwrap(),
+        "3 items for Anna"
+    );
+    context.insert("count", &2.5);
+    assert_eq!(
+        tera.render("summary", &context).unwrap(),
+        "2.5 items for Anna"
+    );
+
+    // The bundle of the language is used for other regions, and the first
+    // bundle for unknown languages.
+    context.insert("lang", "pl-PL");
+    assert_eq!(
+        tera.render("summary", &context).unwrap(),
+        "Przedmioty w koszyku: 2.5"
+    );
+    context.insert("lang", "de");
+    assert_eq!(
+        tera.render("summary", &context).unwrap(),
+        "2.5 items for Anna"
+    );
+
+    context.insert("count", &vec![1, 2]);
+    assert!(tera.render("summary", &context).is_err());
+}
+
+#[test]
+fn renders_attributes() {
+    let tera = get_tera(FluentFilter::new(get_bundles()));
+    let mut context = Context::new();
+    context.insert("user", "Anna");
+
+    context.insert("lang", "en-US");
+    assert_eq!(tera.render("title", &context).unwrap(), "Summary of Anna");
+    context.insert("lang", "pl");
+    assert_eq!(tera.render("title", &context).unwrap(), "Podsumowanie");
+}
+
+#[test]
+fn missing_messages_fail_the_render() {
+    let tera = get_tera(FluentFilter::new(get_bundles()));
+    let mut context = Context::new();
+    context.insert("lang", "en-US");
+
+    for id in ["missing", "cart-summary.missing"] {
+        context.insert("id", id);
+        assert!(tera.render("missing", &context).is_err(), "{} rendered", id);
+    }
+
+    let tera = get_tera(FluentFilter::new(vec![]));
+    context.insert("id", "cart-summary");
+    assert!(tera.render("missing", &context).is_err());
+}
+
+#[test]
+fn strict_filters_fail_on_resolver_errors() {
+    let mut context = Context::new();
+    context.insert("user", "Anna");
+
+    let tera = get_tera(FluentFilter::new(get_bundles()));
+    assert_eq!(
+        tera.render("no-count", &context).unwrap(),
+        "{$count} items for Anna"
+    );
+
+    let mut filter = FluentFilter::new(get_bundles());
+    filter.set_strict(true);
+    let tera = get_tera(filter);
+    assert!(tera.render("no-count", &context).is_err());
+    context.insert("lang", "en-US");
+    context.insert("count", &3);
+    assert_eq!(
+        tera.render("summary", &context).unwrap(),
+        "3 items for Anna"
+    );
+}
@@ -8,6 +8,7 @@ use std::sync::Mutex;
 pub struct IntlLangMemoizer {
     lang: LanguageIdentifier,
     map: Mutex<type_map::concurrent::TypeMap>,
+    stats: Mutex<MemoryStats>,
 }
 
 impl IntlLangMemoizer {
@@ -16,6 +17,7 @@ impl IntlLangMemoizer {
         Self {
             lang,
             map: Mutex::new(type_map::concurrent::TypeMap::new()),
+            stats: Mutex::default(),
         }
     }
 
@@ -37,10 +39,24 @@ impl IntlLangMemoizer {
         let e = match cache.entry(args.clone()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!(
+                    "intl_memoizer::construct",
+                    formatter = std::any::type_name::<I>(),
+                    lang = %self.lang,
+                )
+                .entered();
                 let val = I::construct(self.lang.clone(), args)?;
+                self.stats.lock().unwrap().record::<I>();
                 entry.insert(val)
             }
         };
         Ok(cb(e))
     }
+
+    /// Returns the number of formatters memoized so far, and an estimate of
+    /// the memory they use.
+    pub fn memory_stats(&self) -> MemoryStats {
+        *self.stats.lock().unwrap()
+    }
 }
@@ -4,7 +4,7 @@
 //!
 //! The [`IntlMemoizer`] is the main struct that creates a per-locale [`IntlLangMemoizer`].
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::hash::Hash;
@@ -29,6 +29,38 @@ pub trait Memoizable {
         Self: std::marker::Sized;
 }
 
+/// An estimate of the memory held by a memoizer, see
+/// [`IntlLangMemoizer::memory_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The number of memoized formatters.
+    pub formatters: usize,
+    /// The size in bytes of the memoized formatters and their construction
+    /// arguments.
+    ///
+    /// Only the inline size of each value is counted; memory which the
+    /// formatters allocate themselves is not visible to the memoizer.
+    pub heap_size: usize,
+}
+
+impl MemoryStats {
+    fn record<I: Memoizable>(&mut self) {
+        self.formatters += 1;
+        self.heap_size += std::mem::size_of::<I>() + std::mem::size_of::<I::Args>();
+    }
+}
+
+impl std::ops::Add for MemoryStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            formatters: self.formatters + other.formatters,
+            heap_size: self.heap_size + other.heap_size,
+        }
+    }
+}
+
 /// The [`IntlLangMemoizer`] can memoize multiple constructed internationalization
 /// formatters, and their configuration for a single locale. For instance, given "en-US",
 /// a memorizer could retain 3 `DateTimeFormat` instances, and a `PluralRules`.
@@ -177,6 +209,7 @@ pub trait Memoizable {
 pub struct IntlLangMemoizer {
     lang: LanguageIdentifier,
     map: RefCell<type_map::TypeMap>,
+    stats: Cell<MemoryStats>,
 }
 
 impl IntlLangMemoizer {
@@ -186,6 +219,7 @@ impl IntlLangMemoizer {
         Self {
             lang,
             map: RefCell::new(type_map::TypeMap::new()),
+            stats: Cell::default(),
         }
     }
 
@@ -222,12 +256,28 @@ impl IntlLangMemoizer {
         let e = match cache.entry(construct_args.clone()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!(
+                    "intl_memoizer::construct",
+                    formatter = std::any::type_name::<I>(),
+                    lang = %self.lang,
+                )
+                .entered();
                 let val = I::construct(self.lang.clone(), construct_args)?;
+                let mut stats = self.stats.get();
+                stats.record::<I>();
+                self.stats.set(stats);
                 entry.insert(val)
             }
         };
         Ok(callback(e))
     }
+
+    /// Returns the number of formatters memoized so far, and an estimate of
+    /// the memory they use.
+    pub fn memory_stats(&self) -> MemoryStats {
+        self.stats.get()
+    }
 }
 
 /// [`IntlMemoizer`] is designed to handle lazily-initialized references to
@@ -348,6 +398,17 @@ impl IntlMemoizer {
             }
         }
     }
+
+    /// Returns the sum of the [`MemoryStats`] of the [`IntlLangMemoizer`]s
+    /// which are still alive.
+    pub fn memory_stats(&self) -> MemoryStats {
+        self.map
+            .values()
+            .filter_map(Weak::upgrade)
+            .fold(MemoryStats::default(), |stats, memoizer| {
+                stats + memoizer.memory_stats()
+            })
+    }
 }
 
 #[cfg(test)]
@@ -409,6 +470,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_memory_stats() {
+        let mut memoizer = IntlMemoizer::default();
+        let en_memoizer = memoizer.get_for_lang("en".parse().unwrap());
+        let pl_memoizer = memoizer.get_for_lang("pl".parse().unwrap());
+        assert_eq!(memoizer.memory_stats(), MemoryStats::default());
+
+        for pr_type in [PluralRuleType::CARDINAL, PluralRuleType::ORDINAL] {
+            en_memoizer
+                .with_try_get::<PluralRules, _, _>((pr_type,), |_| ())
+                .unwrap();
+        }
+        pl_memoizer
+            .with_try_get::<PluralRules, _, _>((PluralRuleType::CARDINAL,), |_| ())
+            .unwrap();
+        // Memoized formatters are not counted again.
+        pl_memoizer
+            .with_try_get::<PluralRules, _, _>((PluralRuleType::CARDINAL,), |_| ())
+            .unwrap();
+
+        let size = std::mem::size_of::<PluralRules>() + std::mem::size_of::<(PluralRuleType,)>();
+        assert_eq!(en_memoizer.memory_stats().formatters, 2);
+        assert_eq!(
+            memoizer.memory_stats(),
+            MemoryStats {
+                formatters: 3,
+                heap_size: 3 * size,
+            }
+        );
+
+        drop(pl_memoizer);
+        assert_eq!(memoizer.memory_stats().formatters, 2);
+    }
+
     #[test]
     fn test_concurrent() {
         let lang: LanguageIdentifier = "en".parse().unwrap();